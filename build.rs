@@ -18,6 +18,7 @@ fn main() {
         // Native displays.
         x11_platform: { all(feature = "x11", free_unix, not(redox)) },
         wayland_platform: { all(feature = "wayland", free_unix, not(redox)) },
+        headless_platform: { all(feature = "headless", free_unix, not(redox)) },
         orbital_platform: { redox },
     }
 
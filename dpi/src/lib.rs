@@ -759,6 +759,100 @@ impl<P: Pixel> From<LogicalPosition<P>> for Position {
     }
 }
 
+/// The distances from the edges of a rectangle represented in logical pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LogicalInsets<P> {
+    pub top: P,
+    pub left: P,
+    pub bottom: P,
+    pub right: P,
+}
+
+impl<P> LogicalInsets<P> {
+    #[inline]
+    pub const fn new(top: P, left: P, bottom: P, right: P) -> Self {
+        LogicalInsets { top, left, bottom, right }
+    }
+}
+
+impl<P: Pixel> LogicalInsets<P> {
+    #[inline]
+    pub fn from_physical<T: Into<PhysicalInsets<X>>, X: Pixel>(
+        physical: T,
+        scale_factor: f64,
+    ) -> Self {
+        physical.into().to_logical(scale_factor)
+    }
+
+    #[inline]
+    pub fn to_physical<X: Pixel>(&self, scale_factor: f64) -> PhysicalInsets<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let top = self.top.into() * scale_factor;
+        let left = self.left.into() * scale_factor;
+        let bottom = self.bottom.into() * scale_factor;
+        let right = self.right.into() * scale_factor;
+        PhysicalInsets::new(top, left, bottom, right).cast()
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> LogicalInsets<X> {
+        LogicalInsets {
+            top: self.top.cast(),
+            left: self.left.cast(),
+            bottom: self.bottom.cast(),
+            right: self.right.cast(),
+        }
+    }
+}
+
+/// The distances from the edges of a rectangle represented in physical pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalInsets<P> {
+    pub top: P,
+    pub left: P,
+    pub bottom: P,
+    pub right: P,
+}
+
+impl<P> PhysicalInsets<P> {
+    #[inline]
+    pub const fn new(top: P, left: P, bottom: P, right: P) -> Self {
+        PhysicalInsets { top, left, bottom, right }
+    }
+}
+
+impl<P: Pixel> PhysicalInsets<P> {
+    #[inline]
+    pub fn from_logical<T: Into<LogicalInsets<X>>, X: Pixel>(
+        logical: T,
+        scale_factor: f64,
+    ) -> Self {
+        logical.into().to_physical(scale_factor)
+    }
+
+    #[inline]
+    pub fn to_logical<X: Pixel>(&self, scale_factor: f64) -> LogicalInsets<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let top = self.top.into() / scale_factor;
+        let left = self.left.into() / scale_factor;
+        let bottom = self.bottom.into() / scale_factor;
+        let right = self.right.into() / scale_factor;
+        LogicalInsets::new(top, left, bottom, right).cast()
+    }
+
+    #[inline]
+    pub fn cast<X: Pixel>(&self) -> PhysicalInsets<X> {
+        PhysicalInsets {
+            top: self.top.cast(),
+            left: self.left.cast(),
+            bottom: self.bottom.cast(),
+            right: self.right.cast(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1033,6 +1127,30 @@ mod tests {
         assert_eq!(x, [1.0, 2.0]);
     }
 
+    #[test]
+    fn test_logical_insets() {
+        let log_insets = LogicalInsets::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(log_insets.to_physical::<u32>(1.0), PhysicalInsets::new(1, 2, 3, 4));
+        assert_eq!(log_insets.to_physical::<u32>(2.0), PhysicalInsets::new(2, 4, 6, 8));
+        assert_eq!(log_insets.cast::<u32>(), LogicalInsets::new(1, 2, 3, 4));
+        assert_eq!(
+            log_insets,
+            LogicalInsets::from_physical(PhysicalInsets::new(2.0, 4.0, 6.0, 8.0), 2.0)
+        );
+    }
+
+    #[test]
+    fn test_physical_insets() {
+        assert_eq!(
+            PhysicalInsets::from_logical(LogicalInsets::new(1.0, 2.0, 3.0, 4.0), 1.0),
+            PhysicalInsets::new(1, 2, 3, 4)
+        );
+        assert_eq!(
+            PhysicalInsets::from_logical(LogicalInsets::new(2.0, 4.0, 6.0, 8.0), 0.5),
+            PhysicalInsets::new(1, 2, 3, 4)
+        );
+    }
+
     #[test]
     fn test_size() {
         assert_eq!(Size::new(PhysicalSize::new(1, 2)), Size::Physical(PhysicalSize::new(1, 2)));
@@ -1137,6 +1255,12 @@ mod tests {
         let _ = format!("{:?}", PhysicalSize::<u32>::default().clone());
         HashSet::new().insert(PhysicalSize::<u32>::default());
 
+        let _ = format!("{:?}", LogicalInsets::<u32>::default().clone());
+        HashSet::new().insert(LogicalInsets::<u32>::default());
+
+        let _ = format!("{:?}", PhysicalInsets::<u32>::default().clone());
+        HashSet::new().insert(PhysicalInsets::<u32>::default());
+
         let _ = format!("{:?}", Size::Physical((1, 2).into()).clone());
         let _ = format!("{:?}", Position::Physical((1, 2).into()).clone());
     }
@@ -1156,6 +1280,9 @@ mod tests {
         is_copy::<LogicalPosition<i32>>();
         is_copy::<PhysicalPosition<f64>>();
         is_copy::<Position>();
+
+        is_copy::<LogicalInsets<i32>>();
+        is_copy::<PhysicalInsets<f64>>();
     }
 
     #[test]
@@ -1173,5 +1300,8 @@ mod tests {
         is_partial_eq::<LogicalPosition<i32>>();
         is_partial_eq::<PhysicalPosition<f64>>();
         is_partial_eq::<Position>();
+
+        is_partial_eq::<LogicalInsets<i32>>();
+        is_partial_eq::<PhysicalInsets<f64>>();
     }
 }
@@ -49,7 +49,7 @@ fn main() -> Result<(), impl std::error::Error> {
 }
 
 #[derive(Default)]
-struct ControlFlowDemo {
+pub(crate) struct ControlFlowDemo {
     mode: Mode,
     request_redraw: bool,
     wait_cancelled: bool,
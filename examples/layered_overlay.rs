@@ -0,0 +1,126 @@
+//! A click-through crosshair overlay, drawn on a color-keyed layered window.
+use std::error::Error;
+
+#[cfg(windows_platform)]
+fn main() -> Result<(), Box<dyn Error>> {
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+
+    use softbuffer::{Context, Surface};
+    use winit::application::ApplicationHandler;
+    use winit::dpi::LogicalSize;
+    use winit::event::{ElementState, KeyEvent, WindowEvent};
+    use winit::event_loop::{ActiveEventLoop, EventLoop};
+    use winit::keyboard::{Key, NamedKey};
+    use winit::platform::windows::{Color, LayeredMode, WindowExtWindows};
+    use winit::window::{Window, WindowId, WindowLevel};
+
+    /// The color key, which is fully transparent and lets the clicks through.
+    const KEY: Color = Color::from_rgb(0xff, 0x00, 0xff);
+    /// The key and the crosshair in the `0RGB` format of softbuffer.
+    const KEY_PIXEL: u32 = 0xff00ff;
+    const CROSSHAIR_PIXEL: u32 = 0x00ff00;
+    /// The length of the arms of the crosshair and their thickness, in physical pixels.
+    const ARM: usize = 24;
+    const THICKNESS: usize = 2;
+
+    #[derive(Default)]
+    struct Overlay {
+        window: Option<Arc<Window>>,
+        context: Option<Context<Arc<Window>>>,
+        surface: Option<Surface<Arc<Window>, Arc<Window>>>,
+    }
+
+    impl Overlay {
+        fn draw(&mut self) {
+            let (Some(window), Some(surface)) = (self.window.as_ref(), self.surface.as_mut())
+            else {
+                return;
+            };
+
+            let size = window.inner_size();
+            let (Some(width), Some(height)) =
+                (NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+            else {
+                return;
+            };
+            surface.resize(width, height).expect("Failed to resize the softbuffer surface");
+
+            let mut buffer = surface.buffer_mut().expect("Failed to get the softbuffer buffer");
+            let width = size.width as usize;
+            let (center_x, center_y) = (width / 2, size.height as usize / 2);
+            for (index, pixel) in buffer.iter_mut().enumerate() {
+                let (x, y) = (index % width, index / width);
+                let (dx, dy) = (x.abs_diff(center_x), y.abs_diff(center_y));
+                let crosshair = (dx <= ARM && dy < THICKNESS) || (dy <= ARM && dx < THICKNESS);
+                *pixel = if crosshair { CROSSHAIR_PIXEL } else { KEY_PIXEL };
+            }
+
+            window.pre_present_notify();
+            buffer.present().expect("Failed to present the softbuffer buffer");
+        }
+    }
+
+    impl ApplicationHandler for Overlay {
+        fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+            let window_attributes = Window::default_attributes()
+                .with_title("Crosshair overlay")
+                .with_decorations(false)
+                .with_window_level(WindowLevel::AlwaysOnTop)
+                .with_inner_size(LogicalSize::new(200.0, 200.0));
+            let window = Arc::new(event_loop.create_window(window_attributes).unwrap());
+
+            // The layered mode takes effect right away, without recreating the window.
+            window.set_layered_mode(LayeredMode::ColorKey(KEY));
+            println!("Layered mode: {:?}", window.layered_mode());
+            println!("Click the crosshair, then press Space to toggle the mode or Escape to exit.");
+
+            let context = Context::new(Arc::clone(&window)).unwrap();
+            self.surface = Some(Surface::new(&context, Arc::clone(&window)).unwrap());
+            self.context = Some(context);
+            self.window = Some(window);
+        }
+
+        fn window_event(
+            &mut self,
+            event_loop: &ActiveEventLoop,
+            _window_id: WindowId,
+            event: WindowEvent,
+        ) {
+            match event {
+                WindowEvent::CloseRequested => event_loop.exit(),
+                WindowEvent::KeyboardInput {
+                    event: KeyEvent { logical_key, state: ElementState::Pressed, .. },
+                    ..
+                } => match logical_key {
+                    Key::Named(NamedKey::Escape) => event_loop.exit(),
+                    // Compare the color key with a constant alpha applied to the whole window.
+                    Key::Named(NamedKey::Space) => {
+                        let window = self.window.as_ref().unwrap();
+                        let mode = match window.layered_mode() {
+                            LayeredMode::ColorKey(_) => LayeredMode::Alpha(128),
+                            _ => LayeredMode::ColorKey(KEY),
+                        };
+                        window.set_layered_mode(mode);
+                        println!("Layered mode: {:?}", window.layered_mode());
+                    },
+                    _ => (),
+                },
+                WindowEvent::RedrawRequested => self.draw(),
+                _ => (),
+            }
+        }
+    }
+
+    tracing_subscriber::fmt::init();
+    let event_loop = EventLoop::new()?;
+
+    let mut app = Overlay::default();
+    event_loop.run_app(&mut app).map_err(Into::into)
+}
+
+#[cfg(not(windows_platform))]
+fn main() -> Result<(), Box<dyn Error>> {
+    println!("This example is only supported on Windows.");
+    Ok(())
+}
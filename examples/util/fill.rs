@@ -20,6 +20,7 @@ mod platform {
     use std::num::NonZeroU32;
 
     use softbuffer::{Context, Surface};
+    use winit::raw_window_handle::HasWindowHandle;
     use winit::window::{Window, WindowId};
 
     thread_local! {
@@ -77,6 +78,11 @@ mod platform {
                 return;
             };
 
+            // Windows without a handle, like the headless ones, have nothing to draw to.
+            if window.window_handle().is_err() {
+                return;
+            }
+
             // Either get the last context used or create a new one.
             let mut gc = gc.borrow_mut();
             let surface =
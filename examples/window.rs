@@ -1,5 +1,6 @@
 //! Simple winit application.
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Debug;
@@ -21,8 +22,8 @@ use winit::event::{DeviceEvent, DeviceId, Ime, MouseButton, MouseScrollDelta, Wi
 use winit::event_loop::{ActiveEventLoop, EventLoop};
 use winit::keyboard::{Key, ModifiersState};
 use winit::window::{
-    Cursor, CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen, Icon, ResizeDirection,
-    Theme, Window, WindowId,
+    Cursor, CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen, Icon, ProgressState,
+    ResizeDirection, Theme, Window, WindowId,
 };
 
 #[cfg(macos_platform)]
@@ -50,11 +51,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Wire the user event from another thread.
     #[cfg(not(web_platform))]
     std::thread::spawn(move || {
-        // Wake up the `event_loop` once every second and dispatch a custom event
-        // from a different thread.
+        // Send a custom event to the `event_loop` once every second from a different thread.
         info!("Starting to send user event every second");
-        loop {
-            _event_loop_proxy.wake_up();
+        for tick in 0u64.. {
+            if _event_loop_proxy.send_event(tick).is_err() {
+                break;
+            }
             std::thread::sleep(std::time::Duration::from_secs(1));
         }
     });
@@ -186,6 +188,8 @@ impl Application {
             Action::ToggleResizeIncrements => window.toggle_resize_increments(),
             Action::ToggleCursorVisibility => window.toggle_cursor_visibility(),
             Action::ToggleResizable => window.toggle_resizable(),
+            Action::CycleOpacity => window.cycle_opacity(),
+            Action::CycleProgress => window.cycle_progress(),
             Action::ToggleDecorations => window.toggle_decorations(),
             Action::ToggleFullscreen => window.toggle_fullscreen(),
             Action::ToggleMaximize => window.toggle_maximize(),
@@ -307,6 +311,12 @@ impl ApplicationHandler for Application {
         info!("User wake up");
     }
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        if let Some(tick) = event.downcast_ref::<u64>() {
+            info!("User event: tick {tick}");
+        }
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -329,6 +339,9 @@ impl ApplicationHandler for Application {
                     info!("Window={window_id:?} unfocused");
                 }
             },
+            WindowEvent::MonitorChanged { new, .. } => {
+                info!("Window={window_id:?} moved to monitor {:?}", new.name());
+            },
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 info!("Window={window_id:?} changed scale to {scale_factor}");
             },
@@ -409,6 +422,9 @@ impl ApplicationHandler for Application {
                 Ime::Commit(text) => {
                     info!("Committed: {}", text);
                 },
+                Ime::DeleteSurrounding { before_bytes, after_bytes } => {
+                    info!("Delete surrounding: {before_bytes} bytes before, {after_bytes} after");
+                },
                 Ime::Disabled => info!("IME disabled for Window={window_id:?}"),
             },
             WindowEvent::PinchGesture { delta, .. } => {
@@ -437,6 +453,17 @@ impl ApplicationHandler for Application {
             WindowEvent::DoubleTapGesture { .. } => {
                 info!("Smart zoom");
             },
+            WindowEvent::DragDrop(event) => {
+                info!("Drag and drop: {event:?}");
+            },
+            WindowEvent::DragSourceFinished { action } => {
+                info!("Drag from the window finished with {action:?}");
+            },
+            WindowEvent::SafeAreaChanged(safe_area) => {
+                info!("Safe area changed to {safe_area:?}");
+                window.window.request_redraw();
+            },
+            #[allow(deprecated)]
             WindowEvent::TouchpadPressure { .. }
             | WindowEvent::HoveredFileCancelled
             | WindowEvent::KeyboardInput { .. }
@@ -446,6 +473,18 @@ impl ApplicationHandler for Application {
             | WindowEvent::HoveredFile(_)
             | WindowEvent::Destroyed
             | WindowEvent::Touch(_)
+            | WindowEvent::EdrHeadroomChanged(_)
+            | WindowEvent::ColorPropertiesChanged(_)
+            | WindowEvent::DisplayCutoutChanged
+            | WindowEvent::SoftKeyboardFrameChanged { .. }
+            | WindowEvent::InterfaceOrientationChanged(_)
+            | WindowEvent::StylusHover { .. }
+            | WindowEvent::TextScaleFactorChanged(_)
+            | WindowEvent::StatusBarTapped
+            | WindowEvent::BackInvoked
+            | WindowEvent::PictureInPictureChanged(_)
+            | WindowEvent::MultiWindowChanged(_)
+            | WindowEvent::FramePresented(_)
             | WindowEvent::Moved(_) => (),
         }
     }
@@ -518,6 +557,9 @@ struct WindowState {
     named_idx: usize,
     custom_idx: usize,
     cursor_hidden: bool,
+
+    /// The progress shown on the taskbar or dock.
+    progress: ProgressState,
 }
 
 impl WindowState {
@@ -552,6 +594,7 @@ impl WindowState {
             ime,
             cursor_position: Default::default(),
             cursor_hidden: Default::default(),
+            progress: Default::default(),
             modifiers: Default::default(),
             occluded: Default::default(),
             rotated: Default::default(),
@@ -604,6 +647,33 @@ impl WindowState {
         self.window.set_resizable(!resizable);
     }
 
+    /// Cycle through fully opaque, 75% and 50% opacity.
+    fn cycle_opacity(&self) {
+        let opacity = match self.window.opacity() {
+            opacity if opacity > 0.75 => 0.75,
+            opacity if opacity > 0.5 => 0.5,
+            _ => 1.0,
+        };
+        info!("Setting opacity to {opacity}");
+        self.window.set_opacity(opacity);
+    }
+
+    /// Cycle through the taskbar / dock progress states.
+    fn cycle_progress(&mut self) {
+        let progress = match self.progress {
+            ProgressState::None => ProgressState::Indeterminate,
+            ProgressState::Indeterminate => ProgressState::Value(0.3),
+            ProgressState::Value(_) => ProgressState::Paused(0.6),
+            ProgressState::Paused(_) => ProgressState::Error(0.9),
+            ProgressState::Error(_) => ProgressState::None,
+        };
+        info!("Setting progress to {progress:?}");
+        match self.window.set_progress(progress) {
+            Ok(()) => self.progress = progress,
+            Err(err) => error!("Error setting progress: {err}"),
+        }
+    }
+
     /// Toggle cursor visibility
     fn toggle_cursor_visibility(&mut self) {
         self.cursor_hidden = !self.cursor_hidden;
@@ -823,14 +893,32 @@ impl WindowState {
 
         const WHITE: u32 = 0xffffffff;
         const DARK_GRAY: u32 = 0xff181818;
+        const LIGHT_RED: u32 = 0xffffc0c0;
+        const DARK_RED: u32 = 0xff401818;
 
-        let color = match self.theme {
-            Theme::Light => WHITE,
-            Theme::Dark => DARK_GRAY,
+        let (color, unsafe_color) = match self.theme {
+            Theme::Light => (WHITE, LIGHT_RED),
+            Theme::Dark => (DARK_GRAY, DARK_RED),
         };
 
         let mut buffer = self.surface.buffer_mut()?;
         buffer.fill(color);
+
+        // Shade the area outside of the safe area.
+        let size = self.window.inner_size();
+        let safe_area = self.window.safe_area();
+        let (width, height) = (size.width as usize, size.height as usize);
+        if buffer.len() == width * height {
+            let safe_x = safe_area.left as usize..width.saturating_sub(safe_area.right as usize);
+            let safe_y = safe_area.top as usize..height.saturating_sub(safe_area.bottom as usize);
+            for (y, row) in buffer.chunks_exact_mut(width.max(1)).enumerate() {
+                for (x, pixel) in row.iter_mut().enumerate() {
+                    if !safe_x.contains(&x) || !safe_y.contains(&y) {
+                        *pixel = unsafe_color;
+                    }
+                }
+            }
+        }
         self.window.pre_present_notify();
         buffer.present()?;
         Ok(())
@@ -868,6 +956,8 @@ enum Action {
     ToggleImeInput,
     ToggleDecorations,
     ToggleResizable,
+    CycleOpacity,
+    CycleProgress,
     ToggleFullscreen,
     ToggleMaximize,
     Minimize,
@@ -899,6 +989,8 @@ impl Action {
             Action::ToggleImeInput => "Toggle IME input",
             Action::ToggleDecorations => "Toggle decorations",
             Action::ToggleResizable => "Toggle window resizable state",
+            Action::CycleOpacity => "Cycle through window opacities",
+            Action::CycleProgress => "Cycle through taskbar / dock progress states",
             Action::ToggleFullscreen => "Toggle fullscreen",
             Action::ToggleMaximize => "Maximize",
             Action::Minimize => "Minimize",
@@ -1063,6 +1155,8 @@ const KEY_BINDINGS: &[Binding<&'static str>] = &[
         Action::AnimationCustomCursor,
     ),
     Binding::new("Z", ModifiersState::CONTROL, Action::ToggleCursorVisibility),
+    Binding::new("O", ModifiersState::ALT, Action::CycleOpacity),
+    Binding::new("P", ModifiersState::ALT, Action::CycleProgress),
     // K.
     Binding::new("K", ModifiersState::empty(), Action::SetTheme(None)),
     Binding::new("K", ModifiersState::SUPER, Action::SetTheme(Some(Theme::Light))),
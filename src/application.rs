@@ -1,7 +1,10 @@
 //! End user application handling.
 
-use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
-use crate::event_loop::ActiveEventLoop;
+use std::any::Any;
+
+use crate::event::{DeviceEvent, DeviceId, SessionEvent, ShortcutItem, StartCause, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, UserEvents};
+use crate::monitor::MonitorHandle;
 use crate::window::WindowId;
 
 /// The handler of the application events.
@@ -89,6 +92,19 @@ pub trait ApplicationHandler {
     /// [`VkSurfaceKHR`]: https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkSurfaceKHR.html
     /// [`wgpu::Surface`]: https://docs.rs/wgpu/latest/wgpu/struct.Surface.html
     ///
+    /// ### iOS
+    ///
+    /// In scene-based applications, this is additionally called when the system connects a new
+    /// scene without a window having been created for it, e.g. when the user opens a new window
+    /// from the app switcher. The next window that is created is shown in that scene.
+    ///
+    /// After the application has returned from the background, this is called again in
+    /// [`applicationWillEnterForeground`] (or [`sceneWillEnterForeground`] for the first scene to
+    /// do so), before any [`WindowEvent::RedrawRequested`] is delivered.
+    ///
+    /// [`applicationWillEnterForeground`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1623076-applicationwillenterforeground
+    /// [`sceneWillEnterForeground`]: https://developer.apple.com/documentation/uikit/uiscenedelegate/3197918-scenewillenterforeground
+    ///
     /// [`can_create_surfaces()`]: Self::can_create_surfaces
     /// [`destroy_surfaces()`]: Self::destroy_surfaces
     fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop);
@@ -184,6 +200,54 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted for each event sent with [`EventLoopProxy::send_event()`].
+    ///
+    /// Events are delivered in the order they were sent, before the [`proxy_wake_up()`] of the
+    /// same loop iteration, and are never merged. Use [`Box::downcast`] to recover the type of
+    /// the event.
+    ///
+    /// [`EventLoopProxy::send_event()`]: crate::event_loop::EventLoopProxy::send_event
+    /// [`proxy_wake_up()`]: Self::proxy_wake_up
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::any::Any;
+    ///
+    /// use winit::application::ApplicationHandler;
+    /// use winit::event_loop::ActiveEventLoop;
+    ///
+    /// enum Message {
+    ///     Progress(f64),
+    ///     Done,
+    /// }
+    ///
+    /// struct MyApp;
+    ///
+    /// impl ApplicationHandler for MyApp {
+    ///     # fn window_event(
+    ///     #     &mut self,
+    ///     #     _event_loop: &ActiveEventLoop,
+    ///     #     _window_id: winit::window::WindowId,
+    ///     #     _event: winit::event::WindowEvent,
+    ///     # ) {
+    ///     # }
+    ///     #
+    ///     # fn can_create_surfaces(&mut self, _event_loop: &ActiveEventLoop) {}
+    ///     #
+    ///     fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+    ///         match event.downcast::<Message>().map(|message| *message) {
+    ///             Ok(Message::Progress(progress)) => println!("{:.0}%", progress * 100.0),
+    ///             Ok(Message::Done) => event_loop.exit(),
+    ///             Err(_) => {},
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        let _ = (event_loop, event);
+    }
+
     /// Emitted when the OS sends an event to a winit window.
     fn window_event(
         &mut self,
@@ -280,9 +344,23 @@ pub trait ApplicationHandler {
     /// [`VkSurfaceKHR`]: https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkSurfaceKHR.html
     /// [`wgpu::Surface`]: https://docs.rs/wgpu/latest/wgpu/struct.Surface.html
     ///
+    /// ### iOS
+    ///
+    /// On iOS, the [`destroy_surfaces()`] method is called in response to an
+    /// [`applicationDidEnterBackground`] callback (or [`sceneDidEnterBackground`] for the last
+    /// scene to do so), before control is returned to UIKit. Applications must stop submitting
+    /// GPU work before the event callback completes, as the system terminates applications that
+    /// render in the background.
+    ///
+    /// Redraws that are requested while in the background are delivered after
+    /// [`can_create_surfaces()`] has been called again.
+    ///
+    /// [`applicationDidEnterBackground`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1622997-applicationdidenterbackground
+    /// [`sceneDidEnterBackground`]: https://developer.apple.com/documentation/uikit/uiscenedelegate/3197917-scenedidenterbackground
+    ///
     /// ### Others
     ///
-    /// - **iOS / macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
     ///
     /// [`can_create_surfaces()`]: Self::can_create_surfaces
     /// [`destroy_surfaces()`]: Self::destroy_surfaces
@@ -304,12 +382,26 @@ pub trait ApplicationHandler {
     ///
     /// ### Android
     ///
-    /// On Android, the `MemoryWarning` event is sent when [`onLowMemory`] was called. The
-    /// application must [release memory] or risk being killed.
+    /// On Android, the `MemoryWarning` event is sent when [`onLowMemory`] was called, or with the
+    /// `android-game-activity` feature also when [`onTrimMemory`] was called. The application
+    /// must [release memory] or risk being killed. The level of the warning is available with
+    /// [`ActiveEventLoopExtAndroid::memory_trim_level()`].
+    ///
+    /// Warnings that arrive while the application is suspended are delivered after it was
+    /// resumed.
     ///
     /// [`onLowMemory`]: https://developer.android.com/reference/android/app/Application.html#onLowMemory()
+    /// [`onTrimMemory`]: https://developer.android.com/reference/android/content/ComponentCallbacks2#onTrimMemory(int)
     /// [release memory]: https://developer.android.com/topic/performance/memory#release
-    ///
+    #[cfg_attr(
+        android_platform,
+        doc = "[`ActiveEventLoopExtAndroid::memory_trim_level()`]: \
+               crate::platform::android::ActiveEventLoopExtAndroid::memory_trim_level"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "[`ActiveEventLoopExtAndroid::memory_trim_level()`]: #only-available-on-android"
+    )]
     /// ### iOS
     ///
     /// On iOS, the `MemoryWarning` event is emitted in response to an
@@ -325,6 +417,296 @@ pub trait ApplicationHandler {
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         let _ = event_loop;
     }
+
+    /// Emitted when the user changed the system's accessibility display settings, such as
+    /// "Reduce motion" or "Increase contrast".
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### macOS
+    ///
+    /// On macOS, this is emitted in response to
+    /// [`NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification`]. The new values can be
+    /// queried with [`ActiveEventLoopExtMacOS`].
+    ///
+    /// [`NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification`]: https://developer.apple.com/documentation/appkit/nsworkspace/1526090-accessibilitydisplayoptionsdidch
+    #[cfg_attr(
+        macos_platform,
+        doc = "[`ActiveEventLoopExtMacOS`]: crate::platform::macos::ActiveEventLoopExtMacOS"
+    )]
+    #[cfg_attr(not(macos_platform), doc = "[`ActiveEventLoopExtMacOS`]: #only-available-on-macos")]
+    /// ### Web
+    ///
+    /// On Web, this is emitted when the [`prefers-reduced-motion`] or [`prefers-contrast`] media
+    /// queries change. The new values can be queried with [`ActiveEventLoopExtWebSys`].
+    ///
+    /// [`prefers-reduced-motion`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-reduced-motion
+    /// [`prefers-contrast`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-contrast
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "[`ActiveEventLoopExtWebSys`]: crate::platform::web::ActiveEventLoopExtWebSys"
+    )]
+    #[cfg_attr(
+        not(any(web_platform, docsrs)),
+        doc = "[`ActiveEventLoopExtWebSys`]: #only-available-on-wasm"
+    )]
+    /// ### Others
+    ///
+    /// - **Android / iOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the system's low power mode was toggled or its thermal state changed.
+    ///
+    /// Applications might wish to reduce their frame rate or the quality of their effects while
+    /// low power mode is enabled or the system is under thermal pressure.
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS and macOS
+    ///
+    /// This is emitted in response to [`NSProcessInfoPowerStateDidChangeNotification`] and
+    /// [`NSProcessInfoThermalStateDidChangeNotification`]. The new state can be queried with
+    /// [`ActiveEventLoopExtIOS`] and [`ActiveEventLoopExtMacOS`] respectively.
+    ///
+    /// [`NSProcessInfoPowerStateDidChangeNotification`]: https://developer.apple.com/documentation/foundation/nsprocessinfopowerstatedidchangenotification
+    /// [`NSProcessInfoThermalStateDidChangeNotification`]: https://developer.apple.com/documentation/foundation/nsprocessinfothermalstatedidchangenotification
+    #[cfg_attr(
+        ios_platform,
+        doc = "[`ActiveEventLoopExtIOS`]: crate::platform::ios::ActiveEventLoopExtIOS"
+    )]
+    #[cfg_attr(not(ios_platform), doc = "[`ActiveEventLoopExtIOS`]: #only-available-on-ios")]
+    #[cfg_attr(
+        macos_platform,
+        doc = "[`ActiveEventLoopExtMacOS`]: crate::platform::macos::ActiveEventLoopExtMacOS"
+    )]
+    #[cfg_attr(not(macos_platform), doc = "[`ActiveEventLoopExtMacOS`]: #only-available-on-macos")]
+    /// ### Android
+    ///
+    /// On Android, this is emitted when the user changed the game mode of the application, e.g.
+    /// in the Game Dashboard, which is checked when the activity resumes or gains focus. The new
+    /// mode can be queried with [`ActiveEventLoopExtAndroid::game_mode()`].
+    #[cfg_attr(
+        android_platform,
+        doc = "[`ActiveEventLoopExtAndroid::game_mode()`]: \
+               crate::platform::android::ActiveEventLoopExtAndroid::game_mode"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "[`ActiveEventLoopExtAndroid::game_mode()`]: #only-available-on-android"
+    )]
+    /// ### Others
+    ///
+    /// - **Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the system is about to sleep or woke up, when the session was locked or
+    /// unlocked, or when the battery saver was toggled.
+    ///
+    /// Applications might wish to pause their simulations while the system sleeps or the session
+    /// is locked, and to reduce their work while the battery saver is enabled. This isn't emitted
+    /// when the windows merely lose the focus, see [`WindowEvent::Focused`] for that.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The sleep is reported in response to `WM_POWERBROADCAST`, the lock in
+    ///   response to `WM_WTSSESSION_CHANGE`, and the battery saver with the
+    ///   `GUID_POWER_SAVING_STATUS` power setting.
+    /// - **macOS:** The sleep is reported in response to `NSWorkspaceWillSleepNotification` and
+    ///   `NSWorkspaceDidWakeNotification`, the lock in response to the `com.apple.screenIsLocked`
+    ///   and `com.apple.screenIsUnlocked` distributed notifications, and the battery saver is the
+    ///   low power mode.
+    /// - **X11 / Wayland:** Requires the `dbus` feature. The sleep is reported in response to the
+    ///   `PrepareForSleep` signal of logind, and the lock in response to the `Lock` and `Unlock`
+    ///   signals of the session of the process. The battery saver is the `power-saver` profile of
+    ///   `power-profiles-daemon`, when it's running.
+    /// - **iOS:** The lock is reported in response to
+    ///   `UIApplicationProtectedDataWillBecomeUnavailable` and
+    ///   `UIApplicationProtectedDataDidBecomeAvailable`, which requires a passcode, and the battery
+    ///   saver is the low power mode. The sleep is unsupported.
+    /// - **Android:** Only the battery saver is reported, which is checked when the activity
+    ///   resumes or gains focus.
+    /// - **Web:** Only the sleep is reported, in response to the `freeze` and `resume` events of
+    ///   the page lifecycle, which are also emitted when the page is frozen in the background. Only
+    ///   supported by Chromium.
+    /// - **Orbital:** Unsupported.
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        let _ = (event_loop, event);
+    }
+
+    /// Emitted when the user switched to another keyboard layout, or the layouts were changed.
+    ///
+    /// The keys returned by [`ActiveEventLoop::key_from_key_code`] and
+    /// [`ActiveEventLoop::key_code_from_key`] follow the active layout, so the results cached by
+    /// the application, like the labels of its keybindings, should be computed again.
+    ///
+    /// This can be emitted even though no key maps differently, e.g. when the same layout is
+    /// selected again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when the selected keyboard input source changed.
+    /// - **Windows:** Emitted in response to `WM_INPUTLANGCHANGE`, which is only received while one
+    ///   of the windows is focused.
+    /// - **Wayland:** Emitted when the compositor sent a new keymap or switched the layout group.
+    /// - **X11:** Emitted when the keymap was reloaded or the layout group changed.
+    /// - **Web / iOS / Android / Orbital:** Unsupported.
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when a monitor was connected to the system.
+    ///
+    /// The monitor is already part of [`ActiveEventLoop::available_monitors`] when this is
+    /// emitted.
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS
+    ///
+    /// On iOS, this is emitted in response to [`UIScreenDidConnectNotification`], e.g. when an
+    /// external display or AirPlay target becomes available.
+    ///
+    /// [`UIScreenDidConnectNotification`]: https://developer.apple.com/documentation/uikit/uiscreen/1617812-didconnectnotification
+    ///
+    /// ### Web
+    ///
+    /// On Web, this is only emitted once the detailed monitor permission was granted.
+    ///
+    /// ### Others
+    ///
+    /// - **Windows:** Emitted in response to `WM_DISPLAYCHANGE`.
+    /// - **macOS:** Emitted in response to `applicationDidChangeScreenParameters:`.
+    /// - **X11:** Emitted when RandR enables a CRTC for an output.
+    /// - **Wayland:** Emitted when the compositor announces a new `wl_output`.
+    /// - **Android / Orbital:** Unsupported.
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when a monitor was disconnected from the system.
+    ///
+    /// The given handle compares equal to handles previously returned for the same monitor, and
+    /// the monitor is no longer part of [`ActiveEventLoop::available_monitors`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS
+    ///
+    /// On iOS, this is emitted in response to [`UIScreenDidDisconnectNotification`]. Windows
+    /// that were hosted on the disconnected screen receive [`WindowEvent::CloseRequested`]
+    /// before this is emitted.
+    ///
+    /// [`UIScreenDidDisconnectNotification`]: https://developer.apple.com/documentation/uikit/uiscreen/1617836-diddisconnectnotification
+    ///
+    /// ### Web
+    ///
+    /// On Web, this is only emitted once the detailed monitor permission was granted.
+    ///
+    /// ### Others
+    ///
+    /// - **Windows:** Emitted in response to `WM_DISPLAYCHANGE`.
+    /// - **macOS:** Emitted in response to `applicationDidChangeScreenParameters:`.
+    /// - **X11:** Emitted when RandR disables the CRTC of an output.
+    /// - **Wayland:** Emitted when the compositor removes a `wl_output`.
+    /// - **Android / Orbital:** Unsupported.
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when the current video mode of a monitor changed.
+    ///
+    /// This is emitted both for changes made through winit and for changes made by the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS
+    ///
+    /// On iOS, this is emitted in response to [`UIScreenModeDidChangeNotification`], e.g. when
+    /// the mode of an external display was changed with [`MonitorHandleExtIOS::set_video_mode`].
+    ///
+    /// [`UIScreenModeDidChangeNotification`]: https://developer.apple.com/documentation/uikit/uiscreen/1617815-modedidchangenotification
+    #[cfg_attr(
+        ios_platform,
+        doc = "[`MonitorHandleExtIOS::set_video_mode`]: \
+               crate::platform::ios::MonitorHandleExtIOS::set_video_mode"
+    )]
+    #[cfg_attr(
+        not(ios_platform),
+        doc = "[`MonitorHandleExtIOS::set_video_mode`]: #only-available-on-ios"
+    )]
+    /// ### Android
+    ///
+    /// On Android, this is emitted when the refresh rate of the display changed, which is only
+    /// reported since API 30, e.g. after [`WindowExtAndroid::set_frame_rate`], and when a change
+    /// of the resolution is noticed after the configuration changed.
+    #[cfg_attr(
+        android_platform,
+        doc = "",
+        doc = "[`WindowExtAndroid::set_frame_rate`]: \
+               crate::platform::android::WindowExtAndroid::set_frame_rate"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "",
+        doc = "[`WindowExtAndroid::set_frame_rate`]: #only-available-on-android"
+    )]
+    /// ### Others
+    ///
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when the application was asked to open URLs.
+    ///
+    /// The URLs are passed as strings, e.g. `myapp://settings` for a custom URL scheme or
+    /// `https://example.com/item/1` for a universal link.
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS
+    ///
+    /// On iOS, this is emitted for URLs passed to [`application:openURL:options:`] and for
+    /// universal links passed to [`application:continueUserActivity:restorationHandler:`], or
+    /// their scene-based counterparts. This includes the URL the application was launched with,
+    /// which is delivered after the initial [`can_create_surfaces`].
+    ///
+    /// [`application:openURL:options:`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1623112-application
+    /// [`application:continueUserActivity:restorationHandler:`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1623072-application
+    /// [`can_create_surfaces`]: Self::can_create_surfaces
+    ///
+    /// ### Others
+    ///
+    /// - **Android / macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        let _ = (event_loop, urls);
+    }
+
+    /// Emitted when the user chose a quick action from the application's icon on the home
+    /// screen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// ### iOS
+    ///
+    /// On iOS, this is emitted in response to
+    /// [`application:performActionForShortcutItem:completionHandler:`] or its scene-based
+    /// counterpart. This includes the quick action the application was launched with, which is
+    /// delivered after the initial [`can_create_surfaces`].
+    ///
+    /// [`application:performActionForShortcutItem:completionHandler:`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1622935-application
+    /// [`can_create_surfaces`]: Self::can_create_surfaces
+    ///
+    /// ### Others
+    ///
+    /// - **Android / macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        let _ = (event_loop, item);
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -349,6 +731,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        (**self).user_event(event_loop, event);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -393,6 +780,51 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         (**self).memory_warning(event_loop);
     }
+
+    #[inline]
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).accessibility_settings_changed(event_loop);
+    }
+
+    #[inline]
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).power_state_changed(event_loop);
+    }
+
+    #[inline]
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        (**self).session_event(event_loop, event);
+    }
+
+    #[inline]
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).keyboard_layout_changed(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        (**self).urls_opened(event_loop, urls);
+    }
+
+    #[inline]
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        (**self).shortcut_item_activated(event_loop, item);
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -417,6 +849,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        (**self).user_event(event_loop, event);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -461,4 +898,189 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         (**self).memory_warning(event_loop);
     }
+
+    #[inline]
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).accessibility_settings_changed(event_loop);
+    }
+
+    #[inline]
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).power_state_changed(event_loop);
+    }
+
+    #[inline]
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        (**self).session_event(event_loop, event);
+    }
+
+    #[inline]
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).keyboard_layout_changed(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        (**self).urls_opened(event_loop, urls);
+    }
+
+    #[inline]
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        (**self).shortcut_item_activated(event_loop, item);
+    }
+}
+
+/// Delivers the events sent with [`EventLoopProxy::send_event()`] to the application, wrapping
+/// it while the event loop runs.
+///
+/// [`EventLoopProxy::send_event()`]: crate::event_loop::EventLoopProxy::send_event
+pub(crate) struct UserEventHandler<A> {
+    app: A,
+    user_events: UserEvents,
+}
+
+impl<A> UserEventHandler<A> {
+    pub(crate) fn new(app: A, user_events: UserEvents) -> Self {
+        Self { app, user_events }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for UserEventHandler<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop) {
+        // Deliver all the events sent so far even if their wake-ups were merged. Events sent from
+        // the handlers come with their own wake-up, so they're delivered on the next one.
+        for event in self.user_events.take() {
+            self.app.user_event(event_loop, event);
+        }
+
+        if self.user_events.take_wake_up() {
+            self.app.proxy_wake_up(event_loop);
+        }
+    }
+
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        self.app.user_event(event_loop, event);
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[inline]
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.accessibility_settings_changed(event_loop);
+    }
+
+    #[inline]
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.power_state_changed(event_loop);
+    }
+
+    #[inline]
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        self.app.session_event(event_loop, event);
+    }
+
+    #[inline]
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.keyboard_layout_changed(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        self.app.urls_opened(event_loop, urls);
+    }
+
+    #[inline]
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        self.app.shortcut_item_activated(event_loop, item);
+    }
 }
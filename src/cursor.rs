@@ -2,6 +2,7 @@ use core::fmt;
 use std::error::Error;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 
 use cursor_icon::CursorIcon;
 
@@ -102,6 +103,60 @@ impl CustomCursor {
             )?,
         })
     }
+
+    /// Creates a new animated cursor from rgba frames, which are shown one after another for their
+    /// [`duration`](CursorFrame::duration) and then repeated.
+    ///
+    /// The alpha channel is assumed to be **not** premultiplied. A single frame creates the same
+    /// cursor as [`CustomCursor::from_rgba`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// The animation only runs while the cursor is over a window that uses it. The durations are
+    /// rounded to the resolution of the platform's timers, which is around 10ms.
+    ///
+    /// - **X11:** Animated by the X server with `XcursorImagesLoadCursor`.
+    /// - **Web:** Animated by the browser with CSS animations.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    pub fn from_frames(frames: Vec<CursorFrame>) -> Result<CustomCursorSource, BadImage> {
+        let _span =
+            tracing::debug_span!("winit::Cursor::from_frames", frames = frames.len()).entered();
+
+        let mut frames = frames.into_iter();
+        match (frames.next(), frames.len()) {
+            (None, _) => Err(BadImage::NoFrames),
+            (Some(frame), 0) => Self::from_rgba(
+                frame.rgba,
+                frame.width,
+                frame.height,
+                frame.hotspot_x,
+                frame.hotspot_y,
+            ),
+            (Some(first), _) => Ok(CustomCursorSource {
+                inner: PlatformCustomCursorSource::from_animation(
+                    std::iter::once(first)
+                        .chain(frames)
+                        .map(CursorAnimationFrame::new)
+                        .collect::<Result<_, _>>()?,
+                )?,
+            }),
+        }
+    }
+}
+
+/// A frame of an animated cursor.
+///
+/// See [`CustomCursor::from_frames`] for more details.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CursorFrame {
+    /// The pixels of the frame, see [`CustomCursor::from_rgba`].
+    pub rgba: Vec<u8>,
+    pub width: u16,
+    pub height: u16,
+    pub hotspot_x: u16,
+    pub hotspot_y: u16,
+    /// How long the frame is shown before the next one.
+    pub duration: Duration,
 }
 
 /// Source for [`CustomCursor`].
@@ -127,6 +182,8 @@ pub enum BadImage {
     DimensionsVsPixelCount { width: u16, height: u16, width_x_height: u64, pixel_count: u64 },
     /// Produced when the hotspot is outside the image bounds
     HotspotOutOfBounds { width: u16, height: u16, hotspot_x: u16, hotspot_y: u16 },
+    /// Produced when no frames were passed to [`CustomCursor::from_frames`].
+    NoFrames,
 }
 
 impl fmt::Display for BadImage {
@@ -155,6 +212,7 @@ impl fmt::Display for BadImage {
                 "The specified hotspot ({hotspot_x:?}, {hotspot_y:?}) is outside the image bounds \
                  ({width:?}x{height:?}).",
             ),
+            BadImage::NoFrames => write!(f, "No frames were supplied for the animated cursor."),
         }
     }
 }
@@ -165,7 +223,11 @@ impl Error for BadImage {}
 /// images.
 #[allow(dead_code)]
 #[derive(Debug)]
-pub(crate) struct OnlyCursorImageSource(pub(crate) CursorImage);
+pub(crate) enum OnlyCursorImageSource {
+    Image(CursorImage),
+    /// Contains at least two frames.
+    Animation(Vec<CursorAnimationFrame>),
+}
 
 #[allow(dead_code)]
 impl OnlyCursorImageSource {
@@ -176,14 +238,18 @@ impl OnlyCursorImageSource {
         hotspot_x: u16,
         hotspot_y: u16,
     ) -> Result<Self, BadImage> {
-        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map(Self)
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map(Self::Image)
+    }
+
+    pub(crate) fn from_animation(frames: Vec<CursorAnimationFrame>) -> Result<Self, BadImage> {
+        Ok(Self::Animation(frames))
     }
 }
 
 /// Platforms export this directly as `PlatformCustomCursor` if they don't implement caching.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
-pub(crate) struct OnlyCursorImage(pub(crate) Arc<CursorImage>);
+pub(crate) struct OnlyCursorImage(pub(crate) Arc<OnlyCursorImageSource>);
 
 impl Hash for OnlyCursorImage {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -209,6 +275,27 @@ pub(crate) struct CursorImage {
     pub(crate) hotspot_y: u16,
 }
 
+/// A validated [`CursorFrame`].
+#[derive(Debug)]
+#[allow(dead_code)]
+pub(crate) struct CursorAnimationFrame {
+    pub(crate) image: CursorImage,
+    pub(crate) duration: Duration,
+}
+
+impl CursorAnimationFrame {
+    fn new(frame: CursorFrame) -> Result<Self, BadImage> {
+        let image = CursorImage::from_rgba(
+            frame.rgba,
+            frame.width,
+            frame.height,
+            frame.hotspot_x,
+            frame.hotspot_y,
+        )?;
+        Ok(Self { image, duration: frame.duration })
+    }
+}
+
 impl CursorImage {
     pub(crate) fn from_rgba(
         rgba: Vec<u8>,
@@ -260,4 +347,8 @@ impl NoCustomCursor {
         CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?;
         Ok(Self)
     }
+
+    pub(crate) fn from_animation(_frames: Vec<CursorAnimationFrame>) -> Result<Self, BadImage> {
+        Ok(Self)
+    }
 }
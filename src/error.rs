@@ -1,6 +1,8 @@
+use std::borrow::Cow;
 use std::{error, fmt};
 
 use crate::platform_impl;
+use crate::window::ImeCapabilities;
 
 // TODO: Rename
 /// An error that may be generated when requesting Winit state
@@ -17,7 +19,7 @@ pub enum ExternalError {
 /// The error type for when the requested operation is not supported by the backend.
 #[derive(Clone)]
 pub struct NotSupportedError {
-    _marker: (),
+    platform: Option<PlatformError>,
 }
 
 /// The error type for when the OS cannot perform the requested operation.
@@ -26,6 +28,31 @@ pub struct OsError {
     line: u32,
     file: &'static str,
     error: platform_impl::OsError,
+    platform: Option<PlatformError>,
+}
+
+/// The code and message of an error as reported by the platform.
+///
+/// This is attached to [`OsError`] and [`NotSupportedError`] when the platform reported why the
+/// operation failed, see [`ExternalError::os_error()`].
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The domain is `Win32` for the codes of `GetLastError()` and `HRESULT` for COM
+///   errors.
+/// - **X11:** The domain is `X11` for protocol errors, with the error code of the X server, and
+///   `X11 GrabStatus` for a denied grab.
+/// - **Wayland:** The domain is the interface of the object a protocol error was raised on, e.g.
+///   `xdg_wm_base`, with the error code of the protocol, or `wayland` when a protocol isn't
+///   available.
+/// - **macOS:** The domain is `CGError` for errors of Core Graphics.
+/// - **Orbital:** The domain is `errno`.
+/// - **iOS / Android / Web:** Never attached.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlatformError {
+    domain: Cow<'static, str>,
+    code: Option<i64>,
+    message: String,
 }
 
 /// A general error that may occur while running the Winit event loop
@@ -41,24 +68,117 @@ pub enum EventLoopError {
     ExitFailure(i32),
 }
 
+/// The error returned by [`Window::request_ime_update`] when the backend can't fulfill the
+/// request.
+///
+/// [`Window::request_ime_update`]: crate::window::Window::request_ime_update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImeRequestError {
+    /// IME is not supported by the backend.
+    NotSupported,
+    /// The backend lacks the capabilities that the request needs.
+    MissingCapabilities(ImeCapabilities),
+}
+
+/// The error returned by [`EventLoopProxy::send_event`] when the [`EventLoop`] no longer exists,
+/// containing the event that couldn't be sent.
+///
+/// [`EventLoopProxy::send_event`]: crate::event_loop::EventLoopProxy::send_event
+/// [`EventLoop`]: crate::event_loop::EventLoop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventLoopClosed<T>(pub T);
+
 impl From<OsError> for EventLoopError {
     fn from(value: OsError) -> Self {
         Self::Os(value)
     }
 }
 
+impl ExternalError {
+    /// The error reported by the platform, if any.
+    pub fn os_error(&self) -> Option<&PlatformError> {
+        match self {
+            ExternalError::NotSupported(e) => e.os_error(),
+            ExternalError::Ignored => None,
+            ExternalError::Os(e) => e.os_error(),
+        }
+    }
+}
+
+impl EventLoopError {
+    /// The error reported by the platform, if any.
+    pub fn os_error(&self) -> Option<&PlatformError> {
+        match self {
+            EventLoopError::NotSupported(e) => e.os_error(),
+            EventLoopError::Os(e) => e.os_error(),
+            EventLoopError::RecreationAttempt | EventLoopError::ExitFailure(_) => None,
+        }
+    }
+}
+
 impl NotSupportedError {
     #[inline]
     #[allow(dead_code)]
     pub(crate) fn new() -> NotSupportedError {
-        NotSupportedError { _marker: () }
+        NotSupportedError { platform: None }
+    }
+
+    /// Attach the reason the platform gave for not supporting the operation.
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn with_platform_error(platform: PlatformError) -> NotSupportedError {
+        NotSupportedError { platform: Some(platform) }
+    }
+
+    /// The reason the platform gave for not supporting the operation, if any.
+    pub fn os_error(&self) -> Option<&PlatformError> {
+        self.platform.as_ref()
     }
 }
 
 impl OsError {
     #[allow(dead_code)]
     pub(crate) fn new(line: u32, file: &'static str, error: platform_impl::OsError) -> OsError {
-        OsError { line, file, error }
+        let platform = platform_impl::platform_error(&error);
+        OsError { line, file, error, platform }
+    }
+
+    /// Attach the error reported by the platform, when the backend error doesn't carry it.
+    #[allow(dead_code)]
+    pub(crate) fn with_platform_error(mut self, platform: PlatformError) -> OsError {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// The error reported by the platform, if any.
+    pub fn os_error(&self) -> Option<&PlatformError> {
+        self.platform.as_ref()
+    }
+}
+
+impl PlatformError {
+    #[allow(dead_code)]
+    pub(crate) fn new(
+        domain: impl Into<Cow<'static, str>>,
+        code: Option<i64>,
+        message: impl Into<String>,
+    ) -> PlatformError {
+        PlatformError { domain: domain.into(), code, message: message.into() }
+    }
+
+    /// The domain of the [code][Self::code()], e.g. `Win32` or `X11`.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The code of the error in its [domain][Self::domain()], if the platform reported one.
+    pub fn code(&self) -> Option<i64> {
+        self.code
+    }
+
+    /// The description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
     }
 }
 
@@ -71,7 +191,22 @@ macro_rules! os_error {
 
 impl fmt::Display for OsError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.pad(&format!("os error at {}:{}: {}", self.file, self.line, self.error))
+        match &self.platform {
+            Some(platform) => f.pad(&format!(
+                "os error at {}:{}: {} ({platform})",
+                self.file, self.line, self.error
+            )),
+            None => f.pad(&format!("os error at {}:{}: {}", self.file, self.line, self.error)),
+        }
+    }
+}
+
+impl fmt::Display for PlatformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self.code {
+            Some(code) => f.pad(&format!("{} error {code}: {}", self.domain, self.message)),
+            None => f.pad(&format!("{} error: {}", self.domain, self.message)),
+        }
     }
 }
 
@@ -87,13 +222,22 @@ impl fmt::Display for ExternalError {
 
 impl fmt::Debug for NotSupportedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.debug_struct("NotSupportedError").finish()
+        let mut debug = f.debug_struct("NotSupportedError");
+        if let Some(platform) = &self.platform {
+            debug.field("platform", platform);
+        }
+        debug.finish()
     }
 }
 
 impl fmt::Display for NotSupportedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.pad("the requested operation is not supported by Winit")
+        match &self.platform {
+            Some(platform) => {
+                f.pad(&format!("the requested operation is not supported by Winit: {platform}"))
+            },
+            None => f.pad("the requested operation is not supported by Winit"),
+        }
     }
 }
 
@@ -108,10 +252,38 @@ impl fmt::Display for EventLoopError {
     }
 }
 
-impl error::Error for OsError {}
+impl fmt::Display for ImeRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            ImeRequestError::NotSupported => f.pad("IME is not supported by the backend"),
+            ImeRequestError::MissingCapabilities(capabilities) => {
+                write!(f, "the backend lacks the IME capabilities {capabilities:?}")
+            },
+        }
+    }
+}
+
+impl<T> fmt::Display for EventLoopClosed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.pad("tried to send an event to an event loop that no longer exists")
+    }
+}
+
+impl error::Error for OsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.platform.as_ref().map(|platform| platform as _)
+    }
+}
 impl error::Error for ExternalError {}
-impl error::Error for NotSupportedError {}
+impl error::Error for NotSupportedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.platform.as_ref().map(|platform| platform as _)
+    }
+}
+impl error::Error for PlatformError {}
 impl error::Error for EventLoopError {}
+impl error::Error for ImeRequestError {}
+impl<T: fmt::Debug> error::Error for EventLoopClosed<T> {}
 
 #[cfg(test)]
 #[allow(clippy::redundant_clone)]
@@ -128,4 +300,54 @@ mod tests {
             ExternalError::NotSupported(NotSupportedError::new())
         );
     }
+
+    #[test]
+    fn platform_error_is_displayed() {
+        let error = NotSupportedError::new();
+        assert_eq!(error.os_error(), None);
+        assert_eq!(error.to_string(), "the requested operation is not supported by Winit");
+
+        let platform = PlatformError::new("wayland", None, "zwp_pointer_constraints_v1 is missing");
+        let error = ExternalError::NotSupported(NotSupportedError::with_platform_error(platform));
+        assert_eq!(error.os_error().map(PlatformError::domain), Some("wayland"));
+        assert_eq!(
+            error.to_string(),
+            "the requested operation is not supported by Winit: wayland error: \
+             zwp_pointer_constraints_v1 is missing"
+        );
+
+        let platform = PlatformError::new("X11", Some(3), "BadWindow");
+        assert_eq!(platform.code(), Some(3));
+        assert_eq!(platform.message(), "BadWindow");
+        assert_eq!(platform.to_string(), "X11 error 3: BadWindow");
+        assert_eq!(ExternalError::Ignored.os_error(), None);
+    }
+
+    #[test]
+    fn platform_error_is_the_source() {
+        use std::error::Error;
+
+        let platform = PlatformError::new("wayland", None, "zwp_pointer_constraints_v1 is missing");
+        let error = NotSupportedError::with_platform_error(platform.clone());
+        assert_eq!(error.source().unwrap().to_string(), platform.to_string());
+        assert!(NotSupportedError::new().source().is_none());
+    }
+
+    #[test]
+    #[cfg(any(x11_platform, wayland_platform, headless_platform))]
+    fn os_error_keeps_the_backend_error() {
+        use std::error::Error;
+
+        let error = OsError::new(1, "grab.rs", platform_impl::OsError::Misc("the grab failed"));
+        assert!(error.source().is_none());
+        assert_eq!(error.to_string(), "os error at grab.rs:1: the grab failed");
+
+        let platform = PlatformError::new("X11 GrabStatus", Some(3), "GrabFrozen");
+        let error = error.with_platform_error(platform.clone());
+        assert_eq!(error.source().unwrap().to_string(), platform.to_string());
+        assert_eq!(
+            error.to_string(),
+            "os error at grab.rs:1: the grab failed (X11 GrabStatus error 3: GrabFrozen)"
+        );
+    }
 }
@@ -34,8 +34,10 @@
 //!
 //! [`EventLoop::run_app(...)`]: crate::event_loop::EventLoop::run_app
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Mutex, Weak};
+use std::time::Duration;
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
@@ -45,14 +47,15 @@ use smol_str::SmolStr;
 #[cfg(web_platform)]
 use web_time::Instant;
 
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize};
 use crate::error::ExternalError;
 use crate::event_loop::AsyncRequestSerial;
-use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::keyboard::{self, LockKeys, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::monitor::{ColorProperties, MonitorHandle};
 use crate::platform_impl;
 #[cfg(doc)]
 use crate::window::Window;
-use crate::window::{ActivationToken, Theme, WindowId};
+use crate::window::{ActivationToken, InterfaceOrientation, Theme, WindowId};
 
 // TODO: Remove once the backends can call `ApplicationHandler` methods directly. For now backends
 // like Windows and Web require `Event` to wire user events, otherwise each backend will have to
@@ -90,6 +93,11 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::can_create_surfaces`]: crate::application::ApplicationHandler::can_create_surfaces
     CreateSurfaces,
 
+    /// See [`ApplicationHandler::destroy_surfaces`] for details.
+    ///
+    /// [`ApplicationHandler::destroy_surfaces`]: crate::application::ApplicationHandler::destroy_surfaces
+    DestroySurfaces,
+
     /// See [`ApplicationHandler::resumed`] for details.
     ///
     /// [`ApplicationHandler::resumed`]: crate::application::ApplicationHandler::resumed
@@ -110,23 +118,83 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::memory_warning`]: crate::application::ApplicationHandler::memory_warning
     MemoryWarning,
 
+    /// See [`ApplicationHandler::power_state_changed`] for details.
+    ///
+    /// [`ApplicationHandler::power_state_changed`]: crate::application::ApplicationHandler::power_state_changed
+    PowerStateChanged,
+
+    /// See [`ApplicationHandler::accessibility_settings_changed`] for details.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    AccessibilitySettingsChanged,
+
+    /// See [`ApplicationHandler::keyboard_layout_changed`] for details.
+    ///
+    /// [`ApplicationHandler::keyboard_layout_changed`]: crate::application::ApplicationHandler::keyboard_layout_changed
+    KeyboardLayoutChanged,
+
+    /// See [`ApplicationHandler::session_event`] for details.
+    ///
+    /// [`ApplicationHandler::session_event`]: crate::application::ApplicationHandler::session_event
+    SessionEvent(SessionEvent),
+
+    /// See [`ApplicationHandler::monitor_connected`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_connected`]: crate::application::ApplicationHandler::monitor_connected
+    MonitorConnected(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_disconnected`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_disconnected`]: crate::application::ApplicationHandler::monitor_disconnected
+    MonitorDisconnected(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_video_mode_changed`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_video_mode_changed`]: crate::application::ApplicationHandler::monitor_video_mode_changed
+    MonitorVideoModeChanged(MonitorHandle),
+
+    /// See [`ApplicationHandler::urls_opened`] for details.
+    ///
+    /// [`ApplicationHandler::urls_opened`]: crate::application::ApplicationHandler::urls_opened
+    UrlsOpened(Vec<String>),
+
+    /// See [`ApplicationHandler::shortcut_item_activated`] for details.
+    ///
+    /// [`ApplicationHandler::shortcut_item_activated`]: crate::application::ApplicationHandler::shortcut_item_activated
+    ShortcutItemActivated(ShortcutItem),
+
     /// User requested a wake up.
     UserWakeUp,
 }
 
 /// Describes the reason the event loop is resuming.
+///
+/// With the `serde` feature, instants are serialized as the number of seconds from the moment of
+/// serialization, and deserialized relative to the moment of deserialization. Only the time
+/// between the instants of an event is preserved.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StartCause {
     /// Sent if the time specified by [`ControlFlow::WaitUntil`] has been reached. Contains the
     /// moment the timeout was requested and the requested resume time. The actual resume time is
     /// guaranteed to be equal to or after the requested resume time.
     ///
     /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
-    ResumeTimeReached { start: Instant, requested_resume: Instant },
+    ResumeTimeReached {
+        #[cfg_attr(feature = "serde", serde(with = "instant_serde"))]
+        start: Instant,
+        #[cfg_attr(feature = "serde", serde(with = "instant_serde"))]
+        requested_resume: Instant,
+    },
 
     /// Sent if the OS has new events to send to the window, after a wait was requested. Contains
     /// the moment the wait was requested and the resume time, if requested.
-    WaitCancelled { start: Instant, requested_resume: Option<Instant> },
+    WaitCancelled {
+        #[cfg_attr(feature = "serde", serde(with = "instant_serde"))]
+        start: Instant,
+        #[cfg_attr(feature = "serde", serde(with = "instant_serde::option"))]
+        requested_resume: Option<Instant>,
+    },
 
     /// Sent if the event loop is being resumed after the loop's control flow was set to
     /// [`ControlFlow::Poll`].
@@ -139,7 +207,18 @@ pub enum StartCause {
 }
 
 /// Describes an event from a [`Window`].
+///
+/// With the `serde` feature, the events can be serialized, for example to record them, but the
+/// handles they hold can't be:
+///
+/// - [`DeviceId`]s are deserialized as [`DeviceId::dummy`].
+/// - [`WindowEvent::ScaleFactorChanged`] is deserialized with an [`InnerSizeWriter`] ignoring the
+///   requests.
+/// - [`WindowEvent::MonitorChanged`] fails to serialize and is never deserialized.
+/// - The platform-specific data of [`KeyEvent`]s is only kept when available on both platforms.
+/// - Instants are serialized relative to the moment of serialization, like for [`StartCause`].
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowEvent {
     /// The activation token was delivered back and now could be used.
     #[cfg_attr(not(any(x11_platform, wayland_platform)), allow(rustdoc::broken_intra_doc_links))]
@@ -168,20 +247,63 @@ pub enum WindowEvent {
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// Deprecated in favor of [`DragDrop`][Self::DragDrop], which also reports the position and
+    /// dropped text.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The path only contains the file name, the content is available through
+    ///   [`WindowExtWebSys::dropped_files()`]. The drop position is reported with a
+    ///   [`CursorMoved`][Self::CursorMoved] event right before.
+    ///
+    /// [`WindowExtWebSys::dropped_files()`]: crate::platform::web::WindowExtWebSys::dropped_files
+    #[cfg_attr(not(any(web_platform, docsrs)), allow(rustdoc::broken_intra_doc_links))]
+    #[deprecated = "use `WindowEvent::DragDrop` instead"]
     DroppedFile(PathBuf),
 
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// Deprecated in favor of [`DragDrop`][Self::DragDrop].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The path is empty, browsers only expose file names once they are dropped. While
+    ///   hovering, the position is reported with [`CursorMoved`][Self::CursorMoved] events.
+    #[deprecated = "use `WindowEvent::DragDrop` instead"]
     HoveredFile(PathBuf),
 
     /// A file was hovered, but has exited the window.
     ///
     /// There will be a single `HoveredFileCancelled` event triggered even if multiple files were
     /// hovered.
+    ///
+    /// Deprecated in favor of [`DragDrop`][Self::DragDrop].
+    #[deprecated = "use `WindowEvent::DragDrop` instead"]
     HoveredFileCancelled,
 
+    /// Something is being dragged over the window, or was dropped onto it.
+    ///
+    /// A drag starts with [`DragDropEvent::Entered`], followed by [`DragDropEvent::Moved`]
+    /// events, and ends with either [`DragDropEvent::Dropped`] or [`DragDropEvent::Left`]. Drags
+    /// that don't offer any of the [`DragDropKinds`] are ignored and don't emit events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Unsupported.
+    DragDrop(DragDropEvent),
+
+    /// The drag started with [`Window::start_drag_and_drop()`] ended.
+    ///
+    /// [`Window::start_drag_and_drop()`]: crate::window::Window::start_drag_and_drop
+    DragSourceFinished {
+        /// The action the drop was accepted with, `None` if the drag was cancelled or rejected.
+        action: Option<DragAction>,
+    },
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
@@ -193,6 +315,12 @@ pub enum WindowEvent {
     /// - **Windows:** The shift key overrides NumLock. In other words, while shift is held down,
     ///   numpad keys act as if NumLock wasn't active. When this is used, the OS sends fake key
     ///   events which are not marked as `is_synthetic`.
+    /// - **iOS:** Only hardware keyboards are supported, on iOS 13.4+. The globe key is reported
+    ///   as [`NamedKey::Fn`], and the presses of dead keys as [`Key::Dead`]. While IME is allowed,
+    ///   the return and backspace keys of the on-screen keyboard are reported as well.
+    ///
+    /// [`NamedKey::Fn`]: crate::keyboard::NamedKey::Fn
+    /// [`Key::Dead`]: crate::keyboard::Key::Dead
     KeyboardInput {
         device_id: DeviceId,
         event: KeyEvent,
@@ -263,17 +391,68 @@ pub enum WindowEvent {
     CursorLeft { device_id: DeviceId },
 
     /// A mouse wheel movement or touchpad scroll occurred.
-    MouseWheel { device_id: DeviceId, delta: MouseScrollDelta, phase: TouchPhase },
+    MouseWheel {
+        device_id: DeviceId,
+        delta: MouseScrollDelta,
+        /// The phase of the scroll gesture, or of the momentum scroll following it.
+        phase: TouchPhase,
+        /// The phase of the momentum ("inertial") scroll that follows a touchpad scroll gesture
+        /// once the fingers are lifted, or `None` if the event was caused by the gesture itself.
+        ///
+        /// This allows distinguishing scrolling driven by the user's fingers from the system's
+        /// coasting, e.g. to implement rubber-band overscroll. A momentum scroll is
+        /// [`TouchPhase::Cancelled`] when the window loses focus.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Android / iOS / Orbital / Wayland / Web / Windows / X11:** Always `None`.
+        momentum_phase: Option<TouchPhase>,
+    },
 
     /// An mouse button press has been received.
-    MouseInput { device_id: DeviceId, state: ElementState, button: MouseButton },
+    MouseInput {
+        device_id: DeviceId,
+        state: ElementState,
+        button: MouseButton,
+        /// The number of consecutive clicks of the button, e.g. `2` for a double click.
+        ///
+        /// A press continues the clicks of the previous one when it's the same button, pressed
+        /// within the double-click time of the system and without moving the cursor farther than
+        /// the double-click distance, otherwise the count starts over at `1`. A release has the
+        /// click count of the press it ends.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS:** The click count of the system.
+        /// - **iOS:** The tap count of the touch.
+        /// - **Windows:** Counted with the double-click time and rectangle of the system.
+        /// - **X11:** Counted with the `Net/DoubleClickTime` and `Net/DoubleClickDistance`
+        ///   XSETTINGS, or the defaults below without them.
+        /// - **Wayland / Web / Orbital:** Counted with a double-click time of 500 ms and a
+        ///   distance of 2 pixels on each axis.
+        #[cfg_attr(feature = "serde", serde(default = "default_click_count"))]
+        click_count: u32,
+    },
 
     /// Two-finger pinch gesture, often used for magnification.
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
+    /// - Only available on **macOS**, **iOS** and **Web**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - On Web, reported from wheel events once enabled with
+    ///   [`WindowExtWebSys::set_wheel_pinch_gesture()`].
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "",
+        doc = "[`WindowExtWebSys::set_wheel_pinch_gesture()`]: \
+               crate::platform::web::WindowExtWebSys::set_wheel_pinch_gesture"
+    )]
+    #[cfg_attr(
+        not(any(web_platform, docsrs)),
+        doc = "",
+        doc = "[`WindowExtWebSys::set_wheel_pinch_gesture()`]: #only-available-on-wasm"
+    )]
     PinchGesture {
         device_id: DeviceId,
         /// Positive values indicate magnification (zooming in) and  negative
@@ -355,6 +534,63 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Touch(Touch),
 
+    /// A stylus is hovering above the window without touching it.
+    ///
+    /// A [`HoverPhase::Entered`] event is emitted when the stylus comes into range, followed by
+    /// zero or more [`HoverPhase::Moved`] events, and finally a [`HoverPhase::Exited`] event
+    /// when the stylus leaves the hover range or touches the screen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Only emitted on devices supporting Apple Pencil hover, and only after enabling
+    ///   it with [`WindowExtIOS::recognize_stylus_hover`].
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    #[cfg_attr(
+        ios_platform,
+        doc = "",
+        doc = "[`WindowExtIOS::recognize_stylus_hover`]: \
+               crate::platform::ios::WindowExtIOS::recognize_stylus_hover"
+    )]
+    #[cfg_attr(
+        not(ios_platform),
+        doc = "",
+        doc = "[`WindowExtIOS::recognize_stylus_hover`]: #only-available-on-ios"
+    )]
+    StylusHover {
+        device_id: DeviceId,
+        phase: HoverPhase,
+        location: PhysicalPosition<f64>,
+        /// The altitude (in radians) of the stylus, see [`Force::Calibrated`].
+        ///
+        /// - **iOS:** Only available on iOS 16.4+.
+        altitude_angle: Option<f64>,
+        /// The azimuth (in radians) of the stylus, see [`Touch::azimuth_angle`].
+        ///
+        /// - **iOS:** Only available on iOS 16.4+.
+        azimuth_angle: Option<f64>,
+    },
+
+    /// The window moved to a different monitor.
+    ///
+    /// The monitor of the window is the one containing the largest part of it, so dragging a
+    /// window across two monitors emits this once, when most of it crossed over. This is
+    /// emitted before the [`WindowEvent::ScaleFactorChanged`] caused by the move, if any.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The compositor doesn't expose the position of windows, the monitor is the
+    ///   first one the window entered among those it's still on, so this is emitted once the
+    ///   window completely left the previous monitor. This is also emitted with `old` set to
+    ///   [`None`] when the window is first shown.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    MonitorChanged {
+        /// The monitor the window was on, if it was known.
+        old: Option<MonitorHandle>,
+        /// The monitor the window is now on.
+        new: MonitorHandle,
+    },
+
     /// The window's scale factor has changed.
     ///
     /// The following user actions can cause DPI changes:
@@ -384,9 +620,106 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
+    /// - **iOS:** Also reported when the system switches the appearance automatically, e.g. at
+    ///   sunset.
+    /// - **Android / X11 / Wayland / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
+    /// The user's preferred text size has changed.
+    ///
+    /// The value is the factor by which applications that honor the preference should scale their
+    /// text, where `1.0` is the system's default text size.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Derived from the [`preferredContentSizeCategory`] of the window, using the sizes
+    ///   of the body text style.
+    /// - **Android / macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    ///
+    /// [`preferredContentSizeCategory`]: https://developer.apple.com/documentation/uikit/uitraitcollection/1623523-preferredcontentsizecategory
+    TextScaleFactorChanged(f64),
+
+    /// The orientation of the window's user interface has changed.
+    ///
+    /// This is emitted together with the [`WindowEvent::Resized`] caused by the rotation.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted when the orientation of the screen changes, before the resize of the
+    ///   canvas is observed.
+    /// - **Android / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    InterfaceOrientationChanged(InterfaceOrientation),
+
+    /// The status bar was tapped.
+    ///
+    /// Applications usually scroll their main content to the top in response, like scroll views
+    /// do on iOS.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **iOS**, where it is only emitted for the key window on the main
+    ///   screen.
+    StatusBarTapped,
+
+    /// The user asked to go back, with the back button or the back gesture.
+    ///
+    /// This is only emitted when the application consumes back navigation, otherwise the system
+    /// handles it, e.g. by closing the application.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, where it is emitted once
+    ///   [`WindowExtAndroid::set_consumes_back()`] was enabled. Back navigation is received as
+    ///   `KEYCODE_BACK`, which Android only delivers if the application didn't opt into
+    ///   `android:enableOnBackInvokedCallback` in its manifest. The progress of the predictive
+    ///   back gesture of Android 14 needs an `OnBackAnimationCallback` implemented in Java, and
+    ///   isn't reported.
+    #[cfg_attr(
+        android_platform,
+        doc = "",
+        doc = "[`WindowExtAndroid::set_consumes_back()`]: \
+               crate::platform::android::WindowExtAndroid::set_consumes_back"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "",
+        doc = "[`WindowExtAndroid::set_consumes_back()`]: #only-available-on-android"
+    )]
+    BackInvoked,
+
+    /// The window entered or left picture-in-picture, where it is shown in a small window on top
+    /// of other applications.
+    ///
+    /// The contained value is `true` while the window is in picture-in-picture. Applications
+    /// should hide controls and only show the main content, e.g. the video. The safe area is
+    /// empty while the window is in picture-in-picture.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, see [`WindowExtAndroid::enter_picture_in_picture()`].
+    #[cfg_attr(
+        android_platform,
+        doc = "",
+        doc = "[`WindowExtAndroid::enter_picture_in_picture()`]: \
+               crate::platform::android::WindowExtAndroid::enter_picture_in_picture"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "",
+        doc = "[`WindowExtAndroid::enter_picture_in_picture()`]: #only-available-on-android"
+    )]
+    PictureInPictureChanged(bool),
+
+    /// The window entered or left a multi-window mode, e.g. split-screen.
+    ///
+    /// The contained value is `true` while the window shares the screen with other windows. It
+    /// is also `true` in picture-in-picture.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, since API 24.
+    MultiWindowChanged(bool),
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
@@ -416,6 +749,107 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// The extended dynamic range (EDR) headroom available to the window has changed.
+    ///
+    /// Contains the new headroom, that is the maximum color component value that can currently
+    /// be displayed. This changes when the window moves to a different monitor, or when the
+    /// headroom of the current monitor changes, for example when its brightness is adjusted.
+    ///
+    /// A value of `1.0` means that the monitor doesn't currently support values above the
+    /// standard dynamic range.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Only emitted since iOS 16. Changes of the headroom are detected when the
+    ///   brightness of the screen changes.
+    /// - **Android / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    EdrHeadroomChanged(f32),
+
+    /// The color properties of the monitor the window is on have changed, for example because
+    /// HDR was toggled in the system settings.
+    ///
+    /// Contains the new [`MonitorHandle::color_properties`]. This isn't emitted when the window
+    /// moves to another monitor, see [`WindowEvent::MonitorChanged`] for that.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital / Wayland / Web / X11:** Unsupported.
+    ColorPropertiesChanged(Option<ColorProperties>),
+
+    /// The safe area of the window has changed.
+    ///
+    /// The insets are the distances from the edges of the window to its safe area, the part of
+    /// the window that isn't obscured by system UI such as the status bar, the camera housing or
+    /// the home indicator. They can change without the window being resized, e.g. when the status
+    /// bar is hidden. When both change, this is emitted after [`WindowEvent::Resized`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Reported at most once per frame, also while the interface is animating. Not
+    ///   emitted before iOS 11.
+    /// - **Android:** Emitted when the window insets change, e.g. when the device is rotated or
+    ///   the system bars are hidden.
+    /// - **macOS:** Emitted when the titlebar starts or stops covering the content view, or when
+    ///   entering or exiting fullscreen on a screen with a camera housing. Not emitted before
+    ///   macOS 11.
+    /// - **Web:** Emitted when the `env(safe-area-inset-*)` CSS variables change or the canvas
+    ///   moves in or out of them, which requires `viewport-fit=cover` in the viewport `<meta>`
+    ///   tag.
+    /// - **Orbital / Wayland / Windows / X11:** Unsupported.
+    SafeAreaChanged(PhysicalInsets<u32>),
+
+    /// The shape of the display cutout has changed, e.g. when the device was rotated.
+    ///
+    /// This is emitted together with [`WindowEvent::SafeAreaChanged`], which contains the
+    /// conservative insets around the cutout.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, see [`WindowExtAndroid::display_cutout()`].
+    #[cfg_attr(
+        android_platform,
+        doc = "",
+        doc = "[`WindowExtAndroid::display_cutout()`]: \
+               crate::platform::android::WindowExtAndroid::display_cutout"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "",
+        doc = "[`WindowExtAndroid::display_cutout()`]: #only-available-on-android"
+    )]
+    DisplayCutoutChanged,
+
+    /// The area of the window covered by the on-screen keyboard is about to change.
+    ///
+    /// This is emitted when the on-screen keyboard is shown, hidden or changes its size, and
+    /// allows moving content, like the focused text field, out from under the keyboard. The
+    /// change is animated by the system, the content should be animated with the given duration
+    /// and curve to stay in sync.
+    ///
+    /// This is separate from the window's safe area, which doesn't change when the keyboard is
+    /// shown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Emitted in response to `UIKeyboardWillChangeFrameNotification` and
+    ///   `UIKeyboardWillHideNotification`. Keyboards that are undocked or floating, such as the
+    ///   split keyboard on iPad, don't cover the window and are reported with zero insets.
+    /// - **Android:** Emitted when the window insets of the keyboard change, once the animation
+    ///   started. Since API 30 the system animates the keyboard for a fixed duration with an
+    ///   [`AnimationCurve::Other`] that decelerates strongly, the progress of the animation isn't
+    ///   reported. Before API 30 the keyboard appears without animation and only the bottom inset
+    ///   is reported.
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    SoftKeyboardFrameChanged {
+        /// The area of the window that will be covered by the keyboard, as distances from the
+        /// edges of the window.
+        insets: PhysicalInsets<u32>,
+        /// The duration of the keyboard animation.
+        animation_duration: Duration,
+        /// The timing curve of the keyboard animation.
+        animation_curve: AnimationCurve,
+    },
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in two scenarios:
@@ -426,6 +860,24 @@ pub enum WindowEvent {
     /// Winit will aggregate duplicate redraw requests into a single event, to
     /// help avoid duplicating rendering work.
     RedrawRequested,
+
+    /// A frame was presented, or dropped, after [`Window::pre_present_notify()`].
+    ///
+    /// Only emitted while enabled with [`Window::set_presentation_feedback()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the `wp_presentation` protocol.
+    /// - **X11:** Requires the Present extension.
+    /// - **Windows:** The presentation time is estimated from the timing of the DWM composition.
+    /// - **iOS:** The presentation time is the target timestamp of the next display refresh.
+    /// - **Web:** The presentation time is the timestamp of the next animation frame, the refresh
+    ///   duration isn't known.
+    /// - **Android / macOS / Orbital:** Unsupported.
+    ///
+    /// [`Window::pre_present_notify()`]: crate::window::Window::pre_present_notify
+    /// [`Window::set_presentation_feedback()`]: crate::window::Window::set_presentation_feedback
+    FramePresented(PresentationFeedback),
 }
 
 /// Identifier of an input device.
@@ -462,6 +914,7 @@ impl DeviceId {
 ///
 /// Note that these events are delivered regardless of input focus.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeviceEvent {
     Added,
     Removed,
@@ -659,6 +1112,8 @@ pub struct Modifiers {
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
     pub(crate) pressed_mods: ModifiersKeys,
+
+    pub(crate) lock_keys: LockKeys,
 }
 
 impl Modifiers {
@@ -667,6 +1122,21 @@ impl Modifiers {
         self.state
     }
 
+    /// The lock keys that are on.
+    ///
+    /// They're reported with the modifiers when a window gains focus, and whenever they're
+    /// toggled afterwards.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / iOS:** Num lock is never reported.
+    /// - **Android:** Only updated with the next key event.
+    /// - **Web:** Only updated with the next keyboard or pointer event.
+    /// - **Orbital:** Unsupported.
+    pub fn lock_keys(&self) -> LockKeys {
+        self.lock_keys
+    }
+
     /// The state of the left shift key.
     pub fn lshift_state(&self) -> ModifiersKeyState {
         self.mod_state(ModifiersKeys::LSHIFT)
@@ -718,7 +1188,7 @@ impl Modifiers {
 
 impl From<ModifiersState> for Modifiers {
     fn from(value: ModifiersState) -> Self {
-        Self { state: value, pressed_mods: Default::default() }
+        Self { state: value, pressed_mods: Default::default(), lock_keys: Default::default() }
     }
 }
 
@@ -788,6 +1258,23 @@ pub enum Ime {
     /// Right before this event winit will send empty [`Self::Preedit`] event.
     Commit(String),
 
+    /// Notifies when text around the cursor should be deleted, e.g. when the user pressed
+    /// backspace on the on-screen keyboard.
+    ///
+    /// `before_bytes` and `after_bytes` are the lengths in bytes of the text to delete before and
+    /// after the cursor, in the text that was committed until now. Any preedit is cleared with
+    /// an empty [`Self::Preedit`] right before this event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on **Android**.
+    DeleteSurrounding {
+        /// The number of bytes to delete before the cursor.
+        before_bytes: usize,
+        /// The number of bytes to delete after the cursor.
+        after_bytes: usize,
+    },
+
     /// Notifies when the IME was disabled.
     ///
     /// After receiving this event you won't get any more [`Preedit`][Self::Preedit] or
@@ -797,6 +1284,117 @@ pub enum Ime {
     Disabled,
 }
 
+/// A step of a drag and drop operation, see [`WindowEvent::DragDrop`].
+///
+/// Positions are in physical pixels, relative to the top-left corner of the window's inner area.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DragDropEvent {
+    /// A drag entered the window.
+    Entered {
+        /// The kinds of data offered by the drag.
+        kinds: DragDropKinds,
+        /// The position of the pointer.
+        position: PhysicalPosition<f64>,
+    },
+
+    /// The pointer moved while dragging over the window.
+    Moved {
+        /// The position of the pointer.
+        position: PhysicalPosition<f64>,
+    },
+
+    /// The drag was dropped onto the window, at the position of the last
+    /// [`Entered`][Self::Entered] or [`Moved`][Self::Moved] event.
+    ///
+    /// When files are dropped, the text some platforms offer alongside them isn't reported.
+    Dropped {
+        /// The dropped items, which may be empty if the data couldn't be read.
+        items: Vec<DragDropItem>,
+    },
+
+    /// The drag left the window, or was cancelled, without dropping.
+    Left,
+}
+
+/// The action a drop was accepted with, see [`WindowEvent::DragSourceFinished`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DragAction {
+    /// The target copied the data.
+    Copy,
+    /// The target moved the data, the source should delete it.
+    Move,
+}
+
+/// The timing of a presented frame, see [`WindowEvent::FramePresented`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PresentationFeedback {
+    /// The [`Window::pre_present_notify()`] call that preceded the frame, counting from `1` for
+    /// the first call on the window.
+    ///
+    /// [`Window::pre_present_notify()`]: crate::window::Window::pre_present_notify
+    pub generation: u64,
+    /// When the frame was, or is estimated to be, shown on the display, `None` if it was dropped.
+    #[cfg_attr(feature = "serde", serde(with = "instant_serde::option"))]
+    pub presentation_time: Option<Instant>,
+    /// The refresh duration of the display, if known.
+    pub refresh_interval: Option<Duration>,
+    /// Whether the frame was dropped, or shown later than the first refresh it could be shown at.
+    pub missed: bool,
+}
+
+bitflags::bitflags! {
+    /// The kinds of data a drag offers, see [`DragDropEvent::Entered`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DragDropKinds: u32 {
+        /// [`DragDropItem::Path`].
+        const PATHS = 1 << 0;
+        /// [`DragDropItem::Text`].
+        const TEXT = 1 << 1;
+    }
+}
+
+/// An item dropped onto a window, see [`DragDropEvent::Dropped`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DragDropItem {
+    /// A file or directory.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Only contains the file name, the content is available through
+    ///   [`WindowExtWebSys::dropped_files()`].
+    ///
+    /// [`WindowExtWebSys::dropped_files()`]: crate::platform::web::WindowExtWebSys::dropped_files
+    #[cfg_attr(not(any(web_platform, docsrs)), allow(rustdoc::broken_intra_doc_links))]
+    Path(PathBuf),
+
+    /// Plain text.
+    Text(String),
+}
+
+/// The timing curve of a system animation.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnimationCurve {
+    /// The animation progresses at a constant speed.
+    Linear,
+    /// The animation starts slowly and speeds up.
+    EaseIn,
+    /// The animation starts quickly and slows down.
+    EaseOut,
+    /// The animation starts slowly, speeds up and slows down again.
+    EaseInOut,
+    /// An unspecified system curve.
+    ///
+    /// The on-screen keyboard on iOS uses a curve that closely follows a critically damped
+    /// spring, it can be approximated with [`AnimationCurve::EaseInOut`].
+    Other,
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -807,6 +1405,30 @@ pub enum TouchPhase {
     Cancelled,
 }
 
+/// Describes the state of a hovering stylus.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HoverPhase {
+    Entered,
+    Moved,
+    Exited,
+}
+
+/// Describes the tool that generated a touch.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TouchTool {
+    /// A finger, or a tool the platform can't distinguish from one.
+    #[default]
+    Finger,
+    /// A stylus, such as Apple Pencil or a Windows pen.
+    Stylus,
+    /// The eraser end of a stylus, or a stylus that is held upside down.
+    Eraser,
+    /// A mouse, on platforms that report mouse buttons as touches.
+    Mouse,
+}
+
 /// Represents a touch event
 ///
 /// Every time the user touches the screen, a new [`TouchPhase::Started`] event with an unique
@@ -833,6 +1455,7 @@ pub enum TouchPhase {
 /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
 /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Touch {
     pub device_id: DeviceId,
     pub phase: TouchPhase,
@@ -847,12 +1470,75 @@ pub struct Touch {
     /// sensitivity, force will either be 0.0 or 1.0. Also see the
     /// [android documentation](https://developer.android.com/reference/android/view/MotionEvent#AXIS_PRESSURE).
     pub force: Option<Force>,
+    /// The tool that generated this touch.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Reports all tools. Touches made by a palm are rejected with
+    ///   [`TouchPhase::Cancelled`].
+    /// - **iOS / Web / Windows:** Reports [`TouchTool::Stylus`] for pen input.
+    /// - **Others:** Always [`TouchTool::Finger`].
+    pub tool: TouchTool,
+    /// The azimuth (in radians) of the stylus. `None` if the touch wasn't made by a stylus.
+    ///
+    /// The azimuth is the angle between the x-axis of the window and the projection of the
+    /// stylus onto the surface, increasing clockwise.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, **iOS** and **Web**.
+    pub azimuth_angle: Option<f64>,
+    /// The altitude (in radians) of the stylus. `None` if the touch wasn't made by a stylus.
+    ///
+    /// The altitude is the angle between the stylus and the surface, `0` if the stylus lies flat
+    /// on it and `π/2` if it is perpendicular to it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Android**, **iOS** and **Web**.
+    pub altitude_angle: Option<f64>,
+    /// The rotation (in radians) of the stylus around its own axis, increasing clockwise. `None`
+    /// if the touch wasn't made by a stylus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** `0` if the stylus doesn't report its rotation.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    pub twist_angle: Option<f64>,
+    /// Whether some of the values of this touch, such as the force or the angles of the stylus,
+    /// are estimates that the platform expects to refine later.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only reported on **iOS**, always `false` on other platforms.
+    pub is_estimated: bool,
+    /// Whether this touch is a prediction of where the touch will be next, rather than a
+    /// measured one. Predicted touches are never [`TouchPhase::Started`] or
+    /// [`TouchPhase::Ended`], and should be discarded once the next measured touch arrives.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Only emitted after opting in with [`WindowExtIOS::set_coalesced_touches`].
+    /// - **Others:** Always `false`.
+    #[cfg_attr(
+        ios_platform,
+        doc = "",
+        doc = "[`WindowExtIOS::set_coalesced_touches`]: \
+               crate::platform::ios::WindowExtIOS::set_coalesced_touches"
+    )]
+    #[cfg_attr(
+        not(ios_platform),
+        doc = "",
+        doc = "[`WindowExtIOS::set_coalesced_touches`]: #only-available-on-ios"
+    )]
+    pub is_predicted: bool,
     /// Unique identifier of a finger.
     pub id: u64,
 }
 
 /// Describes the force of a touch event
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Force {
     /// On iOS, the force is calibrated so that the same number corresponds to
     /// roughly the same amount of pressure on the screen regardless of the
@@ -969,6 +1655,43 @@ pub enum MouseScrollDelta {
     PixelDelta(PhysicalPosition<f64>),
 }
 
+/// A change of the power or session state of the system.
+///
+/// See [`ApplicationHandler::session_event`].
+///
+/// [`ApplicationHandler::session_event`]: crate::application::ApplicationHandler::session_event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum SessionEvent {
+    /// The system is about to sleep.
+    Suspending,
+    /// The system woke up from sleep.
+    Resumed,
+    /// The session was locked, e.g. the lock screen is shown.
+    SessionLocked,
+    /// The session was unlocked.
+    SessionUnlocked,
+    /// The battery saver was enabled (`true`) or disabled (`false`).
+    PowerSaverChanged(bool),
+}
+
+/// A quick action that the user chose from the application's icon on the home screen.
+///
+/// See [`ApplicationHandler::shortcut_item_activated`].
+///
+/// [`ApplicationHandler::shortcut_item_activated`]: crate::application::ApplicationHandler::shortcut_item_activated
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShortcutItem {
+    /// The application-defined type of the item, which identifies the action to perform.
+    pub item_type: String,
+    /// The string values of the application-defined user info of the item.
+    ///
+    /// Values of other types are skipped.
+    pub user_info: HashMap<String, String>,
+}
+
 /// Handle to synchronously change the size of the window from the
 /// [`WindowEvent`].
 #[derive(Debug, Clone)]
@@ -1002,11 +1725,350 @@ impl PartialEq for InnerSizeWriter {
     }
 }
 
+#[cfg(feature = "serde")]
+mod device_id_serde {
+    use super::DeviceId;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for DeviceId {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            // Device IDs are only meaningful to the event loop that created them.
+            serializer.serialize_unit_struct("DeviceId")
+        }
+    }
+
+    impl<'de> Deserialize<'de> for DeviceId {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            #[serde(rename = "DeviceId")]
+            struct DeviceIdSerialize;
+
+            DeviceIdSerialize::deserialize(deserializer)?;
+            Ok(unsafe { DeviceId::dummy() })
+        }
+    }
+}
+
+/// The click count of the recordings made before it was reported.
+#[cfg(feature = "serde")]
+fn default_click_count() -> u32 {
+    1
+}
+
+#[cfg(feature = "serde")]
+mod key_event_serde {
+    use super::{ElementState, KeyEvent};
+    use crate::keyboard::{Key, KeyLocation, PhysicalKey};
+    use crate::platform_impl;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use smol_str::SmolStr;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "KeyEvent")]
+    pub struct KeyEventSerialize {
+        pub physical_key: PhysicalKey,
+        pub logical_key: Key,
+        pub text: Option<SmolStr>,
+        pub location: KeyLocation,
+        pub state: ElementState,
+        pub repeat: bool,
+        #[serde(default)]
+        pub text_with_all_modifiers: Option<SmolStr>,
+        #[serde(default)]
+        pub key_without_modifiers: Option<Key>,
+    }
+
+    impl Serialize for KeyEvent {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            #[cfg(any(
+                windows_platform,
+                macos_platform,
+                x11_platform,
+                wayland_platform,
+                headless_platform,
+                orbital_platform,
+            ))]
+            let (text_with_all_modifiers, key_without_modifiers) = (
+                self.platform_specific.text_with_all_modifiers.clone(),
+                Some(self.platform_specific.key_without_modifiers.clone()),
+            );
+            #[cfg(not(any(
+                windows_platform,
+                macos_platform,
+                x11_platform,
+                wayland_platform,
+                headless_platform,
+                orbital_platform,
+            )))]
+            let (text_with_all_modifiers, key_without_modifiers) = (None, None);
+
+            let s = KeyEventSerialize {
+                physical_key: self.physical_key,
+                logical_key: self.logical_key.clone(),
+                text: self.text.clone(),
+                location: self.location,
+                state: self.state,
+                repeat: self.repeat,
+                text_with_all_modifiers,
+                key_without_modifiers,
+            };
+            s.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for KeyEvent {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = KeyEventSerialize::deserialize(deserializer)?;
+
+            // Without the platform-specific data, fall back to the text and key with the
+            // modifiers.
+            #[cfg(any(
+                windows_platform,
+                macos_platform,
+                x11_platform,
+                wayland_platform,
+                headless_platform,
+                orbital_platform,
+            ))]
+            let platform_specific = platform_impl::KeyEventExtra {
+                text_with_all_modifiers: s.text_with_all_modifiers.or_else(|| s.text.clone()),
+                key_without_modifiers: s
+                    .key_without_modifiers
+                    .unwrap_or_else(|| s.logical_key.clone()),
+            };
+            #[cfg(not(any(
+                windows_platform,
+                macos_platform,
+                x11_platform,
+                wayland_platform,
+                headless_platform,
+                orbital_platform,
+            )))]
+            let platform_specific = platform_impl::KeyEventExtra {};
+
+            Ok(KeyEvent {
+                physical_key: s.physical_key,
+                logical_key: s.logical_key,
+                text: s.text,
+                location: s.location,
+                state: s.state,
+                repeat: s.repeat,
+                platform_specific,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod modifiers_serde {
+    use super::Modifiers;
+    use crate::keyboard::{LockKeys, ModifiersKeys, ModifiersState};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default)]
+    #[serde(rename = "Modifiers")]
+    pub struct ModifiersSerialize {
+        pub state: ModifiersState,
+        pub lshift: bool,
+        pub rshift: bool,
+        pub lcontrol: bool,
+        pub rcontrol: bool,
+        pub lalt: bool,
+        pub ralt: bool,
+        pub lsuper: bool,
+        pub rsuper: bool,
+        pub lock_keys: LockKeys,
+    }
+
+    impl Serialize for Modifiers {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let pressed = |key| self.pressed_mods.contains(key);
+            let s = ModifiersSerialize {
+                state: self.state,
+                lshift: pressed(ModifiersKeys::LSHIFT),
+                rshift: pressed(ModifiersKeys::RSHIFT),
+                lcontrol: pressed(ModifiersKeys::LCONTROL),
+                rcontrol: pressed(ModifiersKeys::RCONTROL),
+                lalt: pressed(ModifiersKeys::LALT),
+                ralt: pressed(ModifiersKeys::RALT),
+                lsuper: pressed(ModifiersKeys::LSUPER),
+                rsuper: pressed(ModifiersKeys::RSUPER),
+                lock_keys: self.lock_keys,
+            };
+            s.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Modifiers {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = ModifiersSerialize::deserialize(deserializer)?;
+            let mut pressed_mods = ModifiersKeys::empty();
+            pressed_mods.set(ModifiersKeys::LSHIFT, s.lshift);
+            pressed_mods.set(ModifiersKeys::RSHIFT, s.rshift);
+            pressed_mods.set(ModifiersKeys::LCONTROL, s.lcontrol);
+            pressed_mods.set(ModifiersKeys::RCONTROL, s.rcontrol);
+            pressed_mods.set(ModifiersKeys::LALT, s.lalt);
+            pressed_mods.set(ModifiersKeys::RALT, s.ralt);
+            pressed_mods.set(ModifiersKeys::LSUPER, s.lsuper);
+            pressed_mods.set(ModifiersKeys::RSUPER, s.rsuper);
+            Ok(Modifiers { state: s.state, pressed_mods, lock_keys: s.lock_keys })
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod drag_drop_kinds_serde {
+    use super::DragDropKinds;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default)]
+    #[serde(rename = "DragDropKinds")]
+    pub struct DragDropKindsSerialize {
+        pub paths: bool,
+        pub text: bool,
+    }
+
+    impl Serialize for DragDropKinds {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let s = DragDropKindsSerialize {
+                paths: self.contains(DragDropKinds::PATHS),
+                text: self.contains(DragDropKinds::TEXT),
+            };
+            s.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for DragDropKinds {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let DragDropKindsSerialize { paths, text } =
+                DragDropKindsSerialize::deserialize(deserializer)?;
+            let mut kinds = DragDropKinds::empty();
+            kinds.set(DragDropKinds::PATHS, paths);
+            kinds.set(DragDropKinds::TEXT, text);
+            Ok(kinds)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod inner_size_writer_serde {
+    use super::InnerSizeWriter;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Weak;
+
+    impl Serialize for InnerSizeWriter {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_unit_struct("InnerSizeWriter")
+        }
+    }
+
+    impl<'de> Deserialize<'de> for InnerSizeWriter {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            #[serde(rename = "InnerSizeWriter")]
+            struct InnerSizeWriterSerialize;
+
+            // The window the writer was for is gone, so the requests are ignored.
+            InnerSizeWriterSerialize::deserialize(deserializer)?;
+            Ok(InnerSizeWriter { new_inner_size: Weak::new() })
+        }
+    }
+}
+
+/// Serializes instants as the signed number of seconds from now.
+#[cfg(feature = "serde")]
+mod instant_serde {
+    use super::Instant;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let now = Instant::now();
+        let secs = if *instant >= now {
+            instant.duration_since(now).as_secs_f64()
+        } else {
+            -now.duration_since(*instant).as_secs_f64()
+        };
+        serializer.serialize_f64(secs)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Instant, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let secs = f64::deserialize(deserializer)?;
+        let offset = Duration::try_from_secs_f64(secs.abs()).map_err(D::Error::custom)?;
+        let now = Instant::now();
+        let instant = if secs >= 0.0 { now.checked_add(offset) } else { now.checked_sub(offset) };
+        instant.ok_or_else(|| D::Error::custom("instant out of range"))
+    }
+
+    pub mod option {
+        use super::Instant;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Serialize, Deserialize)]
+        #[serde(transparent)]
+        struct InstantSerialize(#[serde(with = "super")] Instant);
+
+        pub fn serialize<S>(instant: &Option<Instant>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            instant.map(InstantSerialize).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Instant>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Option::<InstantSerialize>::deserialize(deserializer)?.map(|instant| instant.0))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::dpi::PhysicalPosition;
+    use crate::dpi::{PhysicalInsets, PhysicalPosition};
     use crate::event;
     use std::collections::{BTreeSet, HashSet};
+    use std::time::Duration;
 
     macro_rules! foreach_event {
         ($closure:expr) => {{
@@ -1040,6 +2102,13 @@ mod tests {
                 with_window_event(DroppedFile("x.txt".into()));
                 with_window_event(HoveredFile("x.txt".into()));
                 with_window_event(HoveredFileCancelled);
+                with_window_event(DragDrop(event::DragDropEvent::Entered {
+                    kinds: event::DragDropKinds::PATHS,
+                    position: (0, 0).into(),
+                }));
+                with_window_event(DragDrop(event::DragDropEvent::Dropped {
+                    items: vec![event::DragDropItem::Path("x.txt".into())],
+                }));
                 with_window_event(Ime(Enabled));
                 with_window_event(CursorMoved { device_id: did, position: (0, 0).into() });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
@@ -1049,11 +2118,13 @@ mod tests {
                     device_id: did,
                     delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                     phase: event::TouchPhase::Started,
+                    momentum_phase: None,
                 });
                 with_window_event(MouseInput {
                     device_id: did,
                     state: event::ElementState::Pressed,
                     button: event::MouseButton::Other(0),
+                    click_count: 1,
                 });
                 with_window_event(PinchGesture {
                     device_id: did,
@@ -1079,9 +2150,46 @@ mod tests {
                     location: (0.0, 0.0).into(),
                     id: 0,
                     force: Some(event::Force::Normalized(0.0)),
+                    tool: event::TouchTool::Finger,
+                    azimuth_angle: None,
+                    altitude_angle: None,
+                    twist_angle: None,
+                    is_estimated: false,
+                    is_predicted: false,
                 }));
+                with_window_event(StylusHover {
+                    device_id: did,
+                    phase: event::HoverPhase::Entered,
+                    location: (0.0, 0.0).into(),
+                    altitude_angle: None,
+                    azimuth_angle: None,
+                });
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
+                with_window_event(TextScaleFactorChanged(1.0));
+                with_window_event(InterfaceOrientationChanged(
+                    crate::window::InterfaceOrientation::Portrait,
+                ));
+                with_window_event(StatusBarTapped);
+                with_window_event(BackInvoked);
+                with_window_event(PictureInPictureChanged(true));
+                with_window_event(MultiWindowChanged(true));
+                with_window_event(DragSourceFinished { action: Some(event::DragAction::Copy) });
+                with_window_event(FramePresented(event::PresentationFeedback {
+                    generation: 1,
+                    presentation_time: None,
+                    refresh_interval: None,
+                    missed: false,
+                }));
                 with_window_event(Occluded(true));
+                with_window_event(EdrHeadroomChanged(1.0));
+                with_window_event(ColorPropertiesChanged(None));
+                with_window_event(SafeAreaChanged(PhysicalInsets::new(0, 0, 0, 0)));
+                with_window_event(DisplayCutoutChanged);
+                with_window_event(SoftKeyboardFrameChanged {
+                    insets: PhysicalInsets::new(0, 0, 0, 0),
+                    animation_duration: Duration::ZERO,
+                    animation_curve: event::AnimationCurve::Linear,
+                });
             }
 
             #[allow(deprecated)]
@@ -1112,6 +2220,25 @@ mod tests {
         })
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_event_serde() {
+        fn round_trip<T>(value: T)
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+        {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+        }
+
+        foreach_event!(|event: event::Event| match event {
+            event::Event::NewEvents(cause) => round_trip(cause),
+            event::Event::WindowEvent { event, .. } => round_trip(event),
+            event::Event::DeviceEvent { event, .. } => round_trip(event),
+            _ => {},
+        });
+    }
+
     #[test]
     fn test_force_normalize() {
         let force = event::Force::Normalized(0.0);
@@ -1155,8 +2282,16 @@ mod tests {
             location: (0.0, 0.0).into(),
             id: 0,
             force: Some(event::Force::Normalized(0.0)),
+            tool: event::TouchTool::Stylus,
+            azimuth_angle: Some(0.0),
+            altitude_angle: None,
+            twist_angle: None,
+            is_estimated: true,
+            is_predicted: false,
         }
         .clone();
+        HashSet::new().insert(event::TouchTool::Finger);
+        HashSet::new().insert(event::HoverPhase::Entered);
         let _ =
             event::Force::Calibrated { force: 0.0, max_possible_force: 0.0, altitude_angle: None }
                 .clone();
@@ -3,27 +3,39 @@
 //!
 //! If you want to send custom events to the event loop, use
 //! [`EventLoop::create_proxy`] to acquire an [`EventLoopProxy`] and call its
-//! [`wake_up`][EventLoopProxy::wake_up] method. Then during handling the wake up
-//! you can poll your event sources.
+//! [`send_event`][EventLoopProxy::send_event] method, or its
+//! [`wake_up`][EventLoopProxy::wake_up] method to poll your own event sources
+//! during handling the wake up.
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
-use std::fmt;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::future::Future;
 use std::marker::PhantomData;
-#[cfg(any(x11_platform, wayland_platform))]
+#[cfg(any(x11_platform, wayland_platform, headless_platform))]
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::{fmt, mem};
 
 #[cfg(not(web_platform))]
 use std::time::{Duration, Instant};
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
-use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, OsError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::application::{ApplicationHandler, UserEventHandler};
+use crate::error::{EventLoopClosed, EventLoopError, OsError};
+use crate::event::DeviceId;
+use crate::keyboard::{Key, KeyCode};
 use crate::monitor::MonitorHandle;
 use crate::platform_impl;
-use crate::window::{CustomCursor, CustomCursorSource, Window, WindowAttributes};
+use crate::window::{
+    CustomCursor, CustomCursorSource, Fullscreen, FullscreenDescriptor, Window, WindowAttributes,
+};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -42,6 +54,7 @@ use crate::window::{CustomCursor, CustomCursorSource, Window, WindowAttributes};
 /// [`Window`]: crate::window::Window
 pub struct EventLoop {
     pub(crate) event_loop: platform_impl::EventLoop,
+    pub(crate) user_events: UserEvents,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -51,6 +64,8 @@ pub struct EventLoop {
 /// your callback.
 pub struct ActiveEventLoop {
     pub(crate) p: platform_impl::ActiveEventLoop,
+    /// The queue of the user events, to create proxies.
+    pub(crate) user_event_queue: Arc<UserEventQueue>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -120,10 +135,11 @@ impl EventLoopBuilder {
 
         // Certain platforms accept a mutable reference in their API.
         #[allow(clippy::unnecessary_mut_passed)]
-        Ok(EventLoop {
-            event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
-            _marker: PhantomData,
-        })
+        let event_loop = platform_impl::EventLoop::new(&mut self.platform_specific)?;
+
+        let user_events = UserEvents::new(Arc::clone(&event_loop.window_target().user_event_queue));
+
+        Ok(EventLoop { event_loop, user_events, _marker: PhantomData })
     }
 
     #[cfg(web_platform)]
@@ -169,6 +185,19 @@ pub enum ControlFlow {
     /// display's native refresh rate should instead use [`Poll`] and the VSync functionality
     /// of a graphics API to reduce odds of missed frames.
     ///
+    /// Deadlines less than 100 ms away are waited for precisely, further ones may be woken up for
+    /// later to save power.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Precise waits need Windows 10 1803, earlier versions raise the resolution of
+    ///   the system timer to 1 ms while waiting.
+    /// - **Wayland / X11:** The kernel delays wake ups by the timer slack of the thread, 50 µs by
+    ///   default.
+    /// - **macOS / iOS:** The system may still delay wake ups when the application is in the
+    ///   background, e.g. because of App Nap.
+    /// - **Web:** Browsers clamp timeouts to a few milliseconds, and more for background tabs.
+    ///
     /// [`Poll`]: Self::Poll
     WaitUntil(Instant),
 }
@@ -241,13 +270,17 @@ impl EventLoop {
     #[inline]
     #[cfg(not(all(web_platform, target_feature = "exception-handling")))]
     pub fn run_app<A: ApplicationHandler>(self, app: &mut A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app(app)
+        let user_events = self.user_events.clone();
+        self.event_loop.run_app(&mut UserEventHandler::new(app, user_events))
     }
 
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events
     /// to the main event loop, possibly from another thread.
     pub fn create_proxy(&self) -> EventLoopProxy {
-        EventLoopProxy { event_loop_proxy: self.event_loop.window_target().p.create_proxy() }
+        EventLoopProxy {
+            event_loop_proxy: self.event_loop.window_target().p.create_proxy(),
+            user_event_queue: Arc::clone(&self.user_events.0),
+        }
     }
 
     /// Gets a persistent reference to the underlying platform display.
@@ -257,19 +290,33 @@ impl EventLoop {
         OwnedDisplayHandle { platform: self.event_loop.window_target().p.owned_display_handle() }
     }
 
-    /// Change if or when [`DeviceEvent`]s are captured.
+    /// Change which [`DeviceEvent`]s are captured, and when.
     ///
     /// See [`ActiveEventLoop::listen_device_events`] for details.
     ///
     /// [`DeviceEvent`]: crate::event::DeviceEvent
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+    pub fn listen_device_events(&self, subscription: impl Into<DeviceEventSubscription>) {
+        let subscription = subscription.into();
         let _span = tracing::debug_span!(
             "winit::EventLoop::listen_device_events",
-            allowed = ?allowed
+            subscription = ?subscription
         )
         .entered();
 
-        self.event_loop.window_target().p.listen_device_events(allowed);
+        self.event_loop.window_target().p.listen_device_events(subscription);
+    }
+
+    /// Sets how the motion of the cursor is reported.
+    ///
+    /// See [`ActiveEventLoop::set_motion_event_policy`] for details.
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        let _span = tracing::debug_span!(
+            "winit::EventLoop::set_motion_event_policy",
+            policy = ?policy
+        )
+        .entered();
+
+        self.event_loop.window_target().p.set_motion_event_policy(policy);
     }
 
     /// Sets the [`ControlFlow`].
@@ -316,7 +363,7 @@ unsafe impl rwh_05::HasRawDisplayHandle for EventLoop {
     }
 }
 
-#[cfg(any(x11_platform, wayland_platform))]
+#[cfg(any(x11_platform, wayland_platform, headless_platform))]
 impl AsFd for EventLoop {
     /// Get the underlying [EventLoop]'s `fd` which you can register
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
@@ -330,7 +377,7 @@ impl AsFd for EventLoop {
     }
 }
 
-#[cfg(any(x11_platform, wayland_platform))]
+#[cfg(any(x11_platform, wayland_platform, headless_platform))]
 impl AsRawFd for EventLoop {
     /// Get the underlying [EventLoop]'s raw `fd` which you can register
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
@@ -348,7 +395,10 @@ impl ActiveEventLoop {
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events
     /// to the main event loop, possibly from another thread.
     pub fn create_proxy(&self) -> EventLoopProxy {
-        EventLoopProxy { event_loop_proxy: self.p.create_proxy() }
+        EventLoopProxy {
+            event_loop_proxy: self.p.create_proxy(),
+            user_event_queue: Arc::clone(&self.user_event_queue),
+        }
     }
 
     /// Create the window.
@@ -371,6 +421,45 @@ impl ActiveEventLoop {
         Ok(Window { window })
     }
 
+    /// Async version of [`ActiveEventLoop::create_window()`], for platforms where creating a
+    /// window involves asynchronous steps.
+    ///
+    /// The returned future doesn't need to be polled for the window to be created, but dropping
+    /// it before it completes cancels the creation.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The window is created by the event loop right before
+    ///   [`ApplicationHandler::about_to_wait()`] is called, or in the next iteration if this is
+    ///   called from it, and the future completes once it was. If the future was dropped by then,
+    ///   no canvas is created or registered.
+    /// - **Others:** The window is created immediately, and the future is ready right away.
+    #[inline]
+    pub fn create_window_async(
+        &self,
+        window_attributes: WindowAttributes,
+    ) -> impl Future<Output = Result<Window, OsError>> {
+        let _span = tracing::debug_span!(
+            "winit::ActiveEventLoop::create_window_async",
+            window_attributes = ?window_attributes
+        )
+        .entered();
+
+        #[cfg(web_platform)]
+        {
+            let future = self.p.create_window_async(window_attributes);
+            async move { future.await.map(|window| Window { window }) }
+        }
+
+        #[cfg(not(web_platform))]
+        {
+            std::future::ready(
+                platform_impl::Window::new(&self.p, window_attributes)
+                    .map(|window| Window { window }),
+            )
+        }
+    }
+
     /// Create custom cursor.
     pub fn create_custom_cursor(&self, custom_cursor: CustomCursorSource) -> CustomCursor {
         let _span = tracing::debug_span!("winit::ActiveEventLoop::create_custom_cursor",).entered();
@@ -393,7 +482,10 @@ impl ActiveEventLoop {
     ///
     /// ## Platform-specific
     ///
-    /// **Wayland / Web:** Always returns `None`.
+    /// - **Wayland:** Always returns `None`.
+    /// - **Web:** Returns the screen the browser window is on, unless permission to use the Window
+    ///   Management API was granted with
+    ///   `EventLoopExtWebSys::request_detailed_monitor_permission()`.
     #[inline]
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
         let _span = tracing::debug_span!("winit::ActiveEventLoop::primary_monitor",).entered();
@@ -401,25 +493,156 @@ impl ActiveEventLoop {
         self.p.primary_monitor().map(|inner| MonitorHandle { inner })
     }
 
-    /// Change if or when [`DeviceEvent`]s are captured.
+    /// Turns a [`FullscreenDescriptor`] back into a [`Fullscreen`] mode.
+    ///
+    /// The monitor is looked up by name in [`available_monitors`], falling back to the
+    /// [`primary_monitor`] for an exclusive descriptor without a monitor name, and the video mode
+    /// by its size, bit depth and refresh rate. Returns `None` if either of them can't be found.
+    ///
+    /// [`available_monitors`]: Self::available_monitors
+    /// [`primary_monitor`]: Self::primary_monitor
+    pub fn resolve_fullscreen(&self, descriptor: &FullscreenDescriptor) -> Option<Fullscreen> {
+        let _span = tracing::debug_span!(
+            "winit::ActiveEventLoop::resolve_fullscreen",
+            descriptor = ?descriptor
+        )
+        .entered();
+
+        let find_monitor = |name: &str| {
+            self.available_monitors().find(|monitor| monitor.name().as_deref() == Some(name))
+        };
+
+        match descriptor {
+            FullscreenDescriptor::Borderless { monitor: None } => {
+                Some(Fullscreen::Borderless(None))
+            },
+            FullscreenDescriptor::Borderless { monitor: Some(name) } => {
+                find_monitor(name).map(|monitor| Fullscreen::Borderless(Some(monitor)))
+            },
+            FullscreenDescriptor::Exclusive {
+                monitor,
+                size,
+                bit_depth,
+                refresh_rate_millihertz,
+            } => {
+                let monitor = match monitor {
+                    Some(name) => find_monitor(name)?,
+                    None => self.primary_monitor().or_else(|| self.available_monitors().next())?,
+                };
+                monitor
+                    .video_modes()
+                    .find(|video_mode| {
+                        video_mode.size() == *size
+                            && video_mode.bit_depth() == *bit_depth
+                            && video_mode.refresh_rate_millihertz() == *refresh_rate_millihertz
+                    })
+                    .map(Fullscreen::Exclusive)
+            },
+        }
+    }
+
+    /// Change which [`DeviceEvent`]s are captured, and when.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
     /// will ignore them by default for unfocused windows on Linux/BSD. This method allows changing
     /// this at runtime to explicitly capture them again.
     ///
+    /// A [`DeviceEventSubscription`] can also restrict the capture to some [`DeviceEventKinds`]
+    /// or to a single device, e.g. to only process the raw mouse motion without capturing the raw
+    /// keyboard input. Only the requested events are delivered, but when the platform can't
+    /// capture them at this granularity, it captures the closest superset instead.
+    ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / macOS / iOS / Android / Orbital:** Unsupported.
+    /// - **Windows:** Keyboards and mice are registered separately, the kinds of mouse events and
+    ///   the device are filtered after capture.
+    /// - **X11:** [`DeviceEventKinds::MOTION`] and [`DeviceEventKinds::WHEEL`] are captured
+    ///   together.
+    /// - **Wayland:** Only [`DeviceEventKinds::MOTION`] is supported, and only captured while the
+    ///   pointer is over a window.
+    /// - **macOS / iOS / Android / Orbital:** Unsupported.
     ///
     /// [`DeviceEvent`]: crate::event::DeviceEvent
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
+    pub fn listen_device_events(&self, subscription: impl Into<DeviceEventSubscription>) {
+        let subscription = subscription.into();
         let _span = tracing::debug_span!(
             "winit::ActiveEventLoop::listen_device_events",
-            allowed = ?allowed
+            subscription = ?subscription
         )
         .entered();
 
-        self.p.listen_device_events(allowed);
+        self.p.listen_device_events(subscription);
+    }
+
+    /// Sets how the motion of the cursor is reported, see [`MotionEventPolicy`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / iOS / Android:** The motion is always coalesced by the system.
+    /// - **Orbital:** Unsupported, every motion is reported.
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        let _span = tracing::debug_span!(
+            "winit::ActiveEventLoop::set_motion_event_policy",
+            policy = ?policy
+        )
+        .entered();
+
+        self.p.set_motion_event_policy(policy);
+    }
+
+    /// Gets how the motion of the cursor is reported.
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.p.motion_event_policy()
+    }
+
+    /// Returns the key that the physical key produces without modifiers on the active keyboard
+    /// layout.
+    ///
+    /// This is the key to show to the user for a keybinding stored as a [`KeyCode`], e.g. `"z"`
+    /// for [`KeyCode::KeyY`] on a German layout. Dead keys are returned as the character they
+    /// produce on their own, e.g. `"´"` rather than [`Key::Dead`]. Looking up a key doesn't
+    /// change the state of the keyboard, so a dead key the user has pressed still applies to the
+    /// next key they type.
+    ///
+    /// Returns [`Key::Unidentified`] when the physical key doesn't exist on the layout.
+    ///
+    /// The layout can change at any time, which is reported with
+    /// [`ApplicationHandler::keyboard_layout_changed`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / iOS / Android / Orbital:** The layout is unknown, only the keys that are the same
+    ///   on every layout, like [`KeyCode::Enter`] or [`KeyCode::F1`], are mapped.
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::key_from_key_code", code = ?code)
+            .entered();
+
+        self.p.key_from_key_code(code)
+    }
+
+    /// Returns the physical key that produces the key without modifiers on the active keyboard
+    /// layout.
+    ///
+    /// This is the reverse of [`key_from_key_code()`][Self::key_from_key_code], to store a
+    /// keybinding given as a character as a [`KeyCode`] that follows the layout. Characters are
+    /// matched regardless of their case when no key produces them exactly, so `"A"` returns
+    /// [`KeyCode::KeyA`] on a QWERTY layout. Dead keys are matched with the character they produce
+    /// on their own.
+    ///
+    /// Returns `None` when no physical key produces the key without modifiers.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / iOS / Android / Orbital:** The layout is unknown, only the keys that are the same
+    ///   on every layout, like [`NamedKey::Enter`] or [`NamedKey::F1`], are mapped.
+    ///
+    /// [`NamedKey::Enter`]: crate::keyboard::NamedKey::Enter
+    /// [`NamedKey::F1`]: crate::keyboard::NamedKey::F1
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        let _span =
+            tracing::debug_span!("winit::ActiveEventLoop::key_code_from_key", key = ?key).entered();
+
+        self.p.key_code_from_key(key)
     }
 
     /// Sets the [`ControlFlow`].
@@ -523,6 +746,7 @@ unsafe impl rwh_05::HasRawDisplayHandle for OwnedDisplayHandle {
 #[derive(Clone)]
 pub struct EventLoopProxy {
     event_loop_proxy: platform_impl::EventLoopProxy,
+    user_event_queue: Arc<UserEventQueue>,
 }
 
 impl EventLoopProxy {
@@ -542,7 +766,30 @@ impl EventLoopProxy {
     ///
     /// [#3687]: https://github.com/rust-windowing/winit/pull/3687
     pub fn wake_up(&self) {
+        self.user_event_queue.woken.store(true, Ordering::Release);
+        self.event_loop_proxy.wake_up();
+    }
+
+    /// Send an event to the [`EventLoop`], which is delivered to
+    /// [`ApplicationHandler::user_event()`].
+    ///
+    /// Unlike wake-ups, events are never coalesced: each of them is delivered exactly once, in the
+    /// order they were sent, even when sent from several threads. Events sent before the event
+    /// loop starts running are delivered once it does.
+    ///
+    /// Returns the event back in an [`EventLoopClosed`] error if the [`EventLoop`] no longer
+    /// exists.
+    ///
+    /// # Platform-specific
+    ///
+    /// - **Windows**: Under high contention the delivery may be delayed until the next wake-up, see
+    ///   [`wake_up()`].
+    ///
+    /// [`wake_up()`]: Self::wake_up
+    pub fn send_event<T: Any + Send>(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        self.user_event_queue.push(event)?;
         self.event_loop_proxy.wake_up();
+        Ok(())
     }
 }
 
@@ -552,6 +799,73 @@ impl fmt::Debug for EventLoopProxy {
     }
 }
 
+/// The events sent with [`EventLoopProxy::send_event`] and not yet delivered.
+#[derive(Debug, Default)]
+pub(crate) struct UserEventQueue {
+    state: Mutex<UserEventQueueState>,
+    /// Whether [`EventLoopProxy::wake_up`] was called since the last delivery.
+    woken: AtomicBool,
+}
+
+#[derive(Debug, Default)]
+struct UserEventQueueState {
+    events: VecDeque<Box<dyn Any + Send>>,
+    /// The number of [`UserEvents`] keeping the queue open.
+    owners: usize,
+}
+
+impl UserEventQueue {
+    fn push<T: Any + Send>(&self, event: T) -> Result<(), EventLoopClosed<T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.owners == 0 {
+            return Err(EventLoopClosed(event));
+        }
+        state.events.push_back(Box::new(event));
+        Ok(())
+    }
+}
+
+/// Keeps the [`UserEventQueue`] open, it's held by the [`EventLoop`] and by the application
+/// handler while the event loop is running.
+pub(crate) struct UserEvents(Arc<UserEventQueue>);
+
+impl UserEvents {
+    fn new(queue: Arc<UserEventQueue>) -> Self {
+        queue.state.lock().unwrap().owners += 1;
+        Self(queue)
+    }
+
+    /// Take the events sent so far.
+    pub(crate) fn take(&self) -> VecDeque<Box<dyn Any + Send>> {
+        mem::take(&mut self.0.state.lock().unwrap().events)
+    }
+
+    /// Whether [`EventLoopProxy::wake_up`] was called since the last call to this.
+    pub(crate) fn take_wake_up(&self) -> bool {
+        self.0.woken.swap(false, Ordering::Acquire)
+    }
+}
+
+impl Clone for UserEvents {
+    fn clone(&self) -> Self {
+        self.0.state.lock().unwrap().owners += 1;
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl Drop for UserEvents {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap();
+        state.owners -= 1;
+        if state.owners == 0 {
+            // Drop the undelivered events outside of the lock, in case they send events themselves.
+            let events = mem::take(&mut state.events);
+            drop(state);
+            drop(events);
+        }
+    }
+}
+
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub enum DeviceEvents {
@@ -564,6 +878,169 @@ pub enum DeviceEvents {
     Never,
 }
 
+bitflags::bitflags! {
+    /// The kinds of [`DeviceEvent`]s to capture.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DeviceEventKinds: u32 {
+        /// [`DeviceEvent::MouseMotion`] and [`DeviceEvent::Motion`].
+        ///
+        /// [`DeviceEvent::MouseMotion`]: crate::event::DeviceEvent::MouseMotion
+        /// [`DeviceEvent::Motion`]: crate::event::DeviceEvent::Motion
+        const MOTION = 1 << 0;
+        /// [`DeviceEvent::Button`].
+        ///
+        /// [`DeviceEvent::Button`]: crate::event::DeviceEvent::Button
+        const BUTTON = 1 << 1;
+        /// [`DeviceEvent::MouseWheel`].
+        ///
+        /// [`DeviceEvent::MouseWheel`]: crate::event::DeviceEvent::MouseWheel
+        const WHEEL = 1 << 2;
+        /// [`DeviceEvent::Key`].
+        ///
+        /// [`DeviceEvent::Key`]: crate::event::DeviceEvent::Key
+        const KEY = 1 << 3;
+    }
+}
+
+impl Default for DeviceEventKinds {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Control which [`DeviceEvent`]s are captured, and when.
+///
+/// A subscription is built from [`DeviceEvents`], and can be narrowed down to some
+/// [`DeviceEventKinds`] and to a single device. [`DeviceEvents`] converts into a subscription
+/// capturing every kind of event from every device.
+///
+/// ```no_run
+/// # use winit::event_loop::{ActiveEventLoop, DeviceEventKinds, DeviceEventSubscription, DeviceEvents};
+/// # fn scope(event_loop: &ActiveEventLoop) {
+/// // Only capture the raw mouse motion, even when the window isn't focused.
+/// event_loop.listen_device_events(
+///     DeviceEventSubscription::new(DeviceEvents::Always).with_kinds(DeviceEventKinds::MOTION),
+/// );
+/// # }
+/// ```
+///
+/// [`DeviceEvent`]: crate::event::DeviceEvent
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct DeviceEventSubscription {
+    when: DeviceEvents,
+    kinds: DeviceEventKinds,
+    device: Option<DeviceId>,
+}
+
+impl DeviceEventSubscription {
+    /// Capture every kind of event from every device, according to `when`.
+    pub fn new(when: DeviceEvents) -> Self {
+        Self { when, kinds: DeviceEventKinds::all(), device: None }
+    }
+
+    /// Only capture the given kinds of events.
+    ///
+    /// [`DeviceEvent::Added`] and [`DeviceEvent::Removed`] are always captured, unless
+    /// [`DeviceEvents::Never`] is used.
+    ///
+    /// [`DeviceEvent::Added`]: crate::event::DeviceEvent::Added
+    /// [`DeviceEvent::Removed`]: crate::event::DeviceEvent::Removed
+    pub fn with_kinds(mut self, kinds: DeviceEventKinds) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Only capture the events from the given device.
+    pub fn with_device(mut self, device: Option<DeviceId>) -> Self {
+        self.device = device;
+        self
+    }
+
+    /// When the events are captured.
+    pub fn when(&self) -> DeviceEvents {
+        self.when
+    }
+
+    /// The kinds of events that are captured.
+    pub fn kinds(&self) -> DeviceEventKinds {
+        self.kinds
+    }
+
+    /// The device the events are captured from, or [`None`] for every device.
+    pub fn device(&self) -> Option<DeviceId> {
+        self.device
+    }
+
+    /// Whether this captures events of the given kind coming from the given device.
+    #[cfg(any(windows_platform, web_platform))]
+    pub(crate) fn captures(&self, kind: DeviceEventKinds, device: DeviceId) -> bool {
+        self.when != DeviceEvents::Never
+            && self.kinds.contains(kind)
+            && self.device.map_or(true, |id| id == device)
+    }
+}
+
+impl From<DeviceEvents> for DeviceEventSubscription {
+    fn from(when: DeviceEvents) -> Self {
+        Self::new(when)
+    }
+}
+
+/// How the motion of the cursor is reported with [`WindowEvent::CursorMoved`].
+///
+/// High polling rate mice can report thousands of positions per second, more than most
+/// applications need to process. By default, the positions received between two dispatches of
+/// the events are merged into a single event with the last one. Applications that need every
+/// sample, e.g. drawing applications or games, can opt out of it.
+///
+/// The [`DeviceEvent`]s aren't affected, they're always reported as they're received.
+///
+/// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+/// [`DeviceEvent`]: crate::event::DeviceEvent
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum MotionEventPolicy {
+    /// Merge consecutive cursor motions of the same window and device into one event.
+    #[default]
+    Coalesced,
+    /// Report every position received from the system.
+    Unfiltered,
+}
+
+/// The thermal state of the system.
+///
+/// Changes are reported with [`ApplicationHandler::power_state_changed`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum ThermalState {
+    /// The thermal state is within normal limits.
+    #[default]
+    Nominal,
+    /// The thermal state is slightly elevated.
+    Fair,
+    /// The thermal state is high, the system reduces performance to cool down.
+    Serious,
+    /// The thermal state is significantly impacting the performance of the system and it needs to
+    /// cool down.
+    Critical,
+}
+
+/// The contrast the user prefers.
+///
+/// Changes are reported with [`ApplicationHandler::accessibility_settings_changed`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum ContrastPreference {
+    /// The user didn't express a preference, or it isn't known.
+    #[default]
+    NoPreference,
+    /// The user prefers more contrast.
+    More,
+    /// The user prefers less contrast.
+    Less,
+    /// The user chose a specific set of colors, e.g. with Windows' contrast themes.
+    Custom,
+}
+
 /// A unique identifier of the winit's async request.
 ///
 /// This could be used to identify the async request once it's done
@@ -574,6 +1051,7 @@ pub enum DeviceEvents {
 /// Then once event is arriving the working list is being traversed and a job
 /// executed and removed from the list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
 pub struct AsyncRequestSerial {
     serial: usize,
 }
@@ -589,3 +1067,54 @@ impl AsyncRequestSerial {
         Self { serial }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn user_events_are_queued_in_order() {
+        let user_events = UserEvents::new(Arc::default());
+        let queue = Arc::clone(&user_events.0);
+
+        let handles: Vec<_> = (0..4usize)
+            .map(|thread| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..100i32 {
+                        queue.push((thread, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut last = [None; 4];
+        let events = user_events.take();
+        assert_eq!(events.len(), 400);
+        for event in events {
+            let (thread, i) = *event.downcast::<(usize, i32)>().unwrap();
+            assert!(last[thread] < Some(i));
+            last[thread] = Some(i);
+        }
+        assert!(user_events.take().is_empty());
+    }
+
+    #[test]
+    fn user_events_closed() {
+        let user_events = UserEvents::new(Arc::default());
+        let running = user_events.clone();
+        let queue = Arc::clone(&user_events.0);
+
+        drop(user_events);
+        queue.push(1u8).unwrap();
+        assert_eq!(running.take().len(), 1);
+
+        drop(running);
+        assert_eq!(queue.push(String::from("late")), Err(EventLoopClosed(String::from("late"))));
+    }
+}
@@ -91,6 +91,8 @@ pub enum NativeKeyCode {
     Unidentified,
     /// An Android "scancode".
     Android(u32),
+    /// An iOS "HID usage", the USB HID usage ID of the key on the keyboard usage page.
+    Ios(u16),
     /// A macOS "scancode".
     MacOS(u16),
     /// A Windows "scancode".
@@ -101,7 +103,7 @@ pub enum NativeKeyCode {
 
 impl std::fmt::Debug for NativeKeyCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use NativeKeyCode::{Android, MacOS, Unidentified, Windows, Xkb};
+        use NativeKeyCode::{Android, Ios, MacOS, Unidentified, Windows, Xkb};
         let mut debug_tuple;
         match self {
             Unidentified => {
@@ -111,6 +113,10 @@ impl std::fmt::Debug for NativeKeyCode {
                 debug_tuple = f.debug_tuple("Android");
                 debug_tuple.field(&format_args!("0x{code:04X}"));
             },
+            Ios(code) => {
+                debug_tuple = f.debug_tuple("Ios");
+                debug_tuple.field(&format_args!("0x{code:04X}"));
+            },
             MacOS(code) => {
                 debug_tuple = f.debug_tuple("MacOS");
                 debug_tuple.field(&format_args!("0x{code:04X}"));
@@ -144,6 +150,9 @@ pub enum NativeKey {
     Unidentified,
     /// An Android "keycode", which is similar to a "virtual-key code" on Windows.
     Android(u32),
+    /// An iOS "HID usage". UIKit doesn't expose anything resembling keysyms, so we report the
+    /// USB HID usage ID of the key instead.
+    Ios(u16),
     /// A macOS "scancode". There does not appear to be any direct analogue to either keysyms or
     /// "virtual-key" codes in macOS, so we report the scancode instead.
     MacOS(u16),
@@ -157,7 +166,7 @@ pub enum NativeKey {
 
 impl std::fmt::Debug for NativeKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use NativeKey::{Android, MacOS, Unidentified, Web, Windows, Xkb};
+        use NativeKey::{Android, Ios, MacOS, Unidentified, Web, Windows, Xkb};
         let mut debug_tuple;
         match self {
             Unidentified => {
@@ -167,6 +176,10 @@ impl std::fmt::Debug for NativeKey {
                 debug_tuple = f.debug_tuple("Android");
                 debug_tuple.field(&format_args!("0x{code:04X}"));
             },
+            Ios(code) => {
+                debug_tuple = f.debug_tuple("Ios");
+                debug_tuple.field(&format_args!("0x{code:04X}"));
+            },
             MacOS(code) => {
                 debug_tuple = f.debug_tuple("MacOS");
                 debug_tuple.field(&format_args!("0x{code:04X}"));
@@ -194,6 +207,7 @@ impl From<NativeKeyCode> for NativeKey {
         match code {
             NativeKeyCode::Unidentified => NativeKey::Unidentified,
             NativeKeyCode::Android(x) => NativeKey::Android(x),
+            NativeKeyCode::Ios(x) => NativeKey::Ios(x),
             NativeKeyCode::MacOS(x) => NativeKey::MacOS(x),
             NativeKeyCode::Windows(x) => NativeKey::Windows(x),
             NativeKeyCode::Xkb(x) => NativeKey::Xkb(x),
@@ -1724,6 +1738,32 @@ impl ModifiersState {
     }
 }
 
+bitflags! {
+    /// Represents the current state of the lock keys.
+    ///
+    /// Each flag represents a lock key and is set if it's toggled on. They're kept apart from
+    /// [`ModifiersState`] so that comparing the modifiers of a shortcut isn't affected by them.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct LockKeys: u8 {
+        /// The "caps lock" key.
+        const CAPS_LOCK = 0b01;
+        /// The "num lock" key.
+        const NUM_LOCK = 0b10;
+    }
+}
+
+impl LockKeys {
+    /// Returns `true` if caps lock is on.
+    pub fn caps_lock(&self) -> bool {
+        self.intersects(Self::CAPS_LOCK)
+    }
+
+    /// Returns `true` if num lock is on.
+    pub fn num_lock(&self) -> bool {
+        self.intersects(Self::NUM_LOCK)
+    }
+}
+
 /// The state of the particular modifiers key.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ModifiersKeyState {
@@ -1756,6 +1796,135 @@ bitflags! {
     }
 }
 
+/// The physical keys that produce the same named key on every keyboard layout.
+///
+/// Used to map keys when the platform can't tell which layout is active.
+const LAYOUT_INDEPENDENT_KEYS: &[(KeyCode, NamedKey)] = &[
+    (KeyCode::AltLeft, NamedKey::Alt),
+    (KeyCode::AltRight, NamedKey::Alt),
+    (KeyCode::Backspace, NamedKey::Backspace),
+    (KeyCode::CapsLock, NamedKey::CapsLock),
+    (KeyCode::ContextMenu, NamedKey::ContextMenu),
+    (KeyCode::ControlLeft, NamedKey::Control),
+    (KeyCode::ControlRight, NamedKey::Control),
+    (KeyCode::Enter, NamedKey::Enter),
+    (KeyCode::SuperLeft, NamedKey::Super),
+    (KeyCode::SuperRight, NamedKey::Super),
+    (KeyCode::ShiftLeft, NamedKey::Shift),
+    (KeyCode::ShiftRight, NamedKey::Shift),
+    (KeyCode::Space, NamedKey::Space),
+    (KeyCode::Tab, NamedKey::Tab),
+    (KeyCode::Delete, NamedKey::Delete),
+    (KeyCode::End, NamedKey::End),
+    (KeyCode::Help, NamedKey::Help),
+    (KeyCode::Home, NamedKey::Home),
+    (KeyCode::Insert, NamedKey::Insert),
+    (KeyCode::PageDown, NamedKey::PageDown),
+    (KeyCode::PageUp, NamedKey::PageUp),
+    (KeyCode::ArrowDown, NamedKey::ArrowDown),
+    (KeyCode::ArrowLeft, NamedKey::ArrowLeft),
+    (KeyCode::ArrowRight, NamedKey::ArrowRight),
+    (KeyCode::ArrowUp, NamedKey::ArrowUp),
+    (KeyCode::NumLock, NamedKey::NumLock),
+    (KeyCode::NumpadEnter, NamedKey::Enter),
+    (KeyCode::Escape, NamedKey::Escape),
+    (KeyCode::PrintScreen, NamedKey::PrintScreen),
+    (KeyCode::ScrollLock, NamedKey::ScrollLock),
+    (KeyCode::Pause, NamedKey::Pause),
+    (KeyCode::BrowserBack, NamedKey::BrowserBack),
+    (KeyCode::BrowserFavorites, NamedKey::BrowserFavorites),
+    (KeyCode::BrowserForward, NamedKey::BrowserForward),
+    (KeyCode::BrowserHome, NamedKey::BrowserHome),
+    (KeyCode::BrowserRefresh, NamedKey::BrowserRefresh),
+    (KeyCode::BrowserSearch, NamedKey::BrowserSearch),
+    (KeyCode::BrowserStop, NamedKey::BrowserStop),
+    (KeyCode::LaunchMail, NamedKey::LaunchMail),
+    (KeyCode::MediaPlayPause, NamedKey::MediaPlayPause),
+    (KeyCode::MediaStop, NamedKey::MediaStop),
+    (KeyCode::MediaTrackNext, NamedKey::MediaTrackNext),
+    (KeyCode::MediaTrackPrevious, NamedKey::MediaTrackPrevious),
+    (KeyCode::AudioVolumeDown, NamedKey::AudioVolumeDown),
+    (KeyCode::AudioVolumeMute, NamedKey::AudioVolumeMute),
+    (KeyCode::AudioVolumeUp, NamedKey::AudioVolumeUp),
+    (KeyCode::F1, NamedKey::F1),
+    (KeyCode::F2, NamedKey::F2),
+    (KeyCode::F3, NamedKey::F3),
+    (KeyCode::F4, NamedKey::F4),
+    (KeyCode::F5, NamedKey::F5),
+    (KeyCode::F6, NamedKey::F6),
+    (KeyCode::F7, NamedKey::F7),
+    (KeyCode::F8, NamedKey::F8),
+    (KeyCode::F9, NamedKey::F9),
+    (KeyCode::F10, NamedKey::F10),
+    (KeyCode::F11, NamedKey::F11),
+    (KeyCode::F12, NamedKey::F12),
+    (KeyCode::F13, NamedKey::F13),
+    (KeyCode::F14, NamedKey::F14),
+    (KeyCode::F15, NamedKey::F15),
+    (KeyCode::F16, NamedKey::F16),
+    (KeyCode::F17, NamedKey::F17),
+    (KeyCode::F18, NamedKey::F18),
+    (KeyCode::F19, NamedKey::F19),
+    (KeyCode::F20, NamedKey::F20),
+    (KeyCode::F21, NamedKey::F21),
+    (KeyCode::F22, NamedKey::F22),
+    (KeyCode::F23, NamedKey::F23),
+    (KeyCode::F24, NamedKey::F24),
+];
+
+/// The keys of the physical keys that produce the same named key on every keyboard layout.
+pub(crate) fn layout_independent_keys() -> impl Iterator<Item = (KeyCode, Key)> {
+    LAYOUT_INDEPENDENT_KEYS.iter().map(|&(code, key)| (code, Key::Named(key)))
+}
+
+/// The key produced by the physical key when it doesn't depend on the keyboard layout, or
+/// [`Key::Unidentified`].
+pub(crate) fn layout_independent_key(code: KeyCode) -> Key {
+    layout_independent_keys()
+        .find_map(|(candidate, key)| (candidate == code).then_some(key))
+        .unwrap_or(Key::Unidentified(NativeKey::Unidentified))
+}
+
+/// Replace a dead key by the character it produces on its own, e.g. when pressed twice.
+pub(crate) fn standalone_key(key: Key) -> Key {
+    match key {
+        Key::Dead(Some(ch)) => Key::Character(SmolStr::new(ch.encode_utf8(&mut [0; 4]))),
+        key => key,
+    }
+}
+
+/// Find the physical key producing `key` among the `keys` of a layout.
+///
+/// Characters are matched exactly first, then regardless of their case, so that `"A"` finds the
+/// key producing `"a"`. Dead keys are matched with their standalone character. When several
+/// physical keys produce the key, the first one in the order of [`KeyCode`] is returned, e.g.
+/// [`KeyCode::Enter`] rather than [`KeyCode::NumpadEnter`].
+pub(crate) fn find_key_code(
+    key: Key,
+    keys: impl IntoIterator<Item = (KeyCode, Key)>,
+) -> Option<KeyCode> {
+    let key = standalone_key(key);
+    let lowercase = match &key {
+        Key::Character(ch) => Some(ch.to_lowercase()),
+        _ => None,
+    };
+
+    let mut exact: Option<KeyCode> = None;
+    let mut caseless: Option<KeyCode> = None;
+    for (code, candidate) in keys {
+        let candidate = standalone_key(candidate);
+        if candidate == key {
+            exact = Some(exact.map_or(code, |exact| exact.min(code)));
+        } else if let (Some(lowercase), Key::Character(ch)) = (&lowercase, &candidate) {
+            if ch.to_lowercase() == *lowercase {
+                caseless = Some(caseless.map_or(code, |caseless| caseless.min(code)));
+            }
+        }
+    }
+
+    exact.or(caseless)
+}
+
 #[cfg(feature = "serde")]
 mod modifiers_serde {
     use super::ModifiersState;
@@ -1802,3 +1971,41 @@ mod modifiers_serde {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+mod lock_keys_serde {
+    use super::LockKeys;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default)]
+    #[serde(rename = "LockKeys")]
+    pub struct LockKeysSerialize {
+        pub caps_lock: bool,
+        pub num_lock: bool,
+    }
+
+    impl Serialize for LockKeys {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let s = LockKeysSerialize { caps_lock: self.caps_lock(), num_lock: self.num_lock() };
+            s.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for LockKeys {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let LockKeysSerialize { caps_lock, num_lock } =
+                LockKeysSerialize::deserialize(deserializer)?;
+            let mut m = LockKeys::empty();
+            m.set(LockKeys::CAPS_LOCK, caps_lock);
+            m.set(LockKeys::NUM_LOCK, num_lock);
+            Ok(m)
+        }
+    }
+}
@@ -29,11 +29,25 @@
 //! model, since that can't be implemented properly on some platforms (e.g web, iOS) and works
 //! poorly on most other platforms. However, this model can be re-implemented to an extent with
 #![cfg_attr(
-    any(windows_platform, macos_platform, android_platform, x11_platform, wayland_platform),
+    any(
+        windows_platform,
+        macos_platform,
+        ios_platform,
+        android_platform,
+        x11_platform,
+        wayland_platform
+    ),
     doc = "[`EventLoopExtPumpEvents::pump_app_events()`][platform::pump_events::EventLoopExtPumpEvents::pump_app_events()]"
 )]
 #![cfg_attr(
-    not(any(windows_platform, macos_platform, android_platform, x11_platform, wayland_platform)),
+    not(any(
+        windows_platform,
+        macos_platform,
+        ios_platform,
+        android_platform,
+        x11_platform,
+        wayland_platform
+    )),
     doc = "`EventLoopExtPumpEvents::pump_app_events()`"
 )]
 //! [^1]. See that method's documentation for more reasons about why
@@ -140,10 +154,15 @@
 //!
 //! * `x11` (enabled by default): On Unix platforms, enables the X11 backend.
 //! * `wayland` (enabled by default): On Unix platforms, enables the Wayland backend.
+//! * `headless`: On Unix platforms, enables the headless backend, for tests and machines without a
+//!   display.
+//! * `dbus`: On X11 and Wayland, reports session events from the system bus, which requires
+//!   `libdbus`.
 //! * `rwh_04`: Implement `raw-window-handle v0.4` traits.
 //! * `rwh_05`: Implement `raw-window-handle v0.5` traits.
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
-//! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
+//! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde),
+//!   and the recording and replaying of events in the `record` module.
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
 //!
 //! See the [`platform`] module for documentation on platform-specific cargo
@@ -165,7 +184,7 @@
 //! [`exiting()`]: crate::application::ApplicationHandler::exiting
 //! [`raw_window_handle`]: ./window/struct.Window.html#method.raw_window_handle
 //! [`raw_display_handle`]: ./window/struct.Window.html#method.raw_display_handle
-//! [^1]: `EventLoopExtPumpEvents::pump_app_events()` is only available on Windows, macOS, Android, X11 and Wayland.
+//! [^1]: `EventLoopExtPumpEvents::pump_app_events()` is only available on Windows, macOS, iOS, Android, X11 and Wayland.
 
 #![deny(rust_2018_idioms)]
 #![deny(rustdoc::broken_intra_doc_links)]
@@ -200,6 +219,8 @@ mod icon;
 pub mod keyboard;
 pub mod monitor;
 mod platform_impl;
+#[cfg(feature = "serde")]
+pub mod record;
 mod utils;
 pub mod window;
 
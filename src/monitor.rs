@@ -5,6 +5,9 @@
 //! methods, which return an iterator of [`MonitorHandle`]:
 //! - [`ActiveEventLoop::available_monitors`][crate::event_loop::ActiveEventLoop::available_monitors].
 //! - [`Window::available_monitors`][crate::window::Window::available_monitors].
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::platform_impl;
 
@@ -94,6 +97,64 @@ impl std::fmt::Display for VideoModeHandle {
     }
 }
 
+/// The color space a monitor outputs in, see [`ColorProperties::color_space`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColorSpace {
+    /// The standard dynamic range sRGB color space.
+    Srgb,
+    /// The Display P3 wide color gamut.
+    DisplayP3,
+    /// The ITU-R BT.2020 color space, used when outputting HDR10.
+    Bt2020,
+}
+
+/// The color capabilities of a monitor, see [`MonitorHandle::color_properties`].
+///
+/// Properties that couldn't be queried are [`None`], while a monitor known to not output HDR has
+/// [`hdr_enabled`] set to `false`.
+///
+/// [`hdr_enabled`]: Self::hdr_enabled
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColorProperties {
+    /// Whether the monitor currently outputs high dynamic range content.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / macOS:** Whether the screen supports extended dynamic range, that is whether its
+    ///   potential EDR headroom is above `1.0`.
+    pub hdr_enabled: bool,
+    /// The peak luminance of the monitor, in nits.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Windows / macOS:** Always [`None`].
+    pub max_luminance_nits: Option<f32>,
+    /// The luminance standard dynamic range white is displayed at, in nits.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / macOS:** Always [`None`].
+    pub sdr_white_level_nits: Option<f32>,
+    /// The color space the monitor currently outputs in.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** [`None`] when advanced color is forced on a standard dynamic range monitor.
+    /// - **iOS / macOS:** Only distinguishes between [`ColorSpace::Srgb`] and
+    ///   [`ColorSpace::DisplayP3`].
+    pub color_space: Option<ColorSpace>,
+    /// The number of bits per color channel the monitor is driven with.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Always [`None`].
+    pub bits_per_channel: Option<u8>,
+}
+
 /// Handle to a monitor.
 ///
 /// Allows you to retrieve information about a given monitor and can be used in [`Window`] creation.
@@ -155,6 +216,22 @@ impl MonitorHandle {
         self.inner.scale_factor()
     }
 
+    /// Returns the color capabilities of the monitor, like whether it currently outputs HDR.
+    ///
+    /// Returns [`None`] if they're unknown, which is distinct from a monitor known to only
+    /// support the standard dynamic range. See also [`WindowEvent::ColorPropertiesChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Only available since iOS 16.
+    /// - **Android / Web / Wayland / X11 / Orbital:** Always returns [`None`].
+    ///
+    /// [`WindowEvent::ColorPropertiesChanged`]: crate::event::WindowEvent::ColorPropertiesChanged
+    #[inline]
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        self.inner.color_properties()
+    }
+
     /// Returns all fullscreen video modes supported by this monitor.
     ///
     /// ## Platform-specific
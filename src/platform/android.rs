@@ -70,6 +70,11 @@
 //! 4. Pass a clone of the `AndroidApp` that your application receives to Winit when building your
 //!    event loop (as shown above).
 
+use std::fmt;
+use std::time::Duration;
+
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize};
+use crate::error::NotSupportedError;
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::window::{Window, WindowAttributes};
 
@@ -81,13 +86,229 @@ pub trait EventLoopExtAndroid {}
 impl EventLoopExtAndroid for EventLoop {}
 
 /// Additional methods on [`ActiveEventLoop`] that are specific to Android.
-pub trait ActiveEventLoopExtAndroid {}
+pub trait ActiveEventLoopExtAndroid {
+    /// Returns how urgently memory should be released, while
+    /// [`ApplicationHandler::memory_warning()`] is called.
+    ///
+    /// Returns [`None`] outside of [`ApplicationHandler::memory_warning()`], or if the system
+    /// didn't report a level, e.g. when only `onLowMemory()` was called.
+    ///
+    /// [`ApplicationHandler::memory_warning()`]: crate::application::ApplicationHandler::memory_warning
+    fn memory_trim_level(&self) -> Option<TrimLevel>;
+
+    /// Sets the callback that returns the state to save when the activity may be destroyed,
+    /// e.g. before it is recreated because the device was rotated.
+    ///
+    /// The state is available with [`restored_state()`](Self::restored_state) after the
+    /// activity was recreated, even if the process was killed in the meantime. States that are
+    /// longer than [`MAX_SAVED_STATE_LEN`] are dropped with an error.
+    fn set_save_state_callback(&self, callback: Option<Box<dyn FnMut() -> Vec<u8>>>);
+
+    /// Returns the state that was saved by the callback of
+    /// [`set_save_state_callback()`](Self::set_save_state_callback) before the activity was
+    /// recreated.
+    ///
+    /// The state is available before [`ApplicationHandler::can_create_surfaces()`] is called
+    /// for the recreated activity.
+    ///
+    /// [`ApplicationHandler::can_create_surfaces()`]: crate::application::ApplicationHandler::can_create_surfaces
+    fn restored_state(&self) -> Option<Vec<u8>>;
+
+    /// Returns whether the activity was recreated, e.g. after a configuration change or after
+    /// the process was killed in the background, instead of being launched fresh.
+    fn is_recreated(&self) -> bool;
+
+    /// Returns the game mode that the user selected for the application, e.g. in the Game
+    /// Dashboard.
+    ///
+    /// [`ApplicationHandler::power_state_changed()`] is called when this changes. Returns
+    /// [`GameMode::Unsupported`] before API 31.
+    ///
+    /// [`ApplicationHandler::power_state_changed()`]: crate::application::ApplicationHandler::power_state_changed
+    fn game_mode(&self) -> GameMode;
+
+    /// Creates a session of the [performance hint API] for the given threads, so that the
+    /// system adjusts the clocks of the CPU to the work that they report.
+    ///
+    /// `thread_ids` are the Linux thread IDs, e.g. from `gettid()`, of threads of the process.
+    /// `target_work_duration` is the time in which the threads should finish their work, e.g.
+    /// the duration of a frame.
+    ///
+    /// The session is closed while the activity is paused and created again when it resumes,
+    /// durations that are reported in the meantime are ignored.
+    ///
+    /// Returns [`NotSupportedError`] before API 33, or if the device doesn't support
+    /// performance hints.
+    ///
+    /// [performance hint API]: https://developer.android.com/games/optimize/adpf/performance-hint-api
+    fn create_performance_hint_session(
+        &self,
+        thread_ids: &[i32],
+        target_work_duration: Duration,
+    ) -> Result<PerformanceHintSession, NotSupportedError>;
+}
+
+/// The maximum length of the state saved by the callback of
+/// [`ActiveEventLoopExtAndroid::set_save_state_callback()`].
+///
+/// The state is sent through a Binder transaction, whose buffer is limited to 1 MiB and shared
+/// with the other transactions of the process.
+pub const MAX_SAVED_STATE_LEN: usize = 512 * 1024;
 
 /// Additional methods on [`Window`] that are specific to Android.
 pub trait WindowExtAndroid {
     fn content_rect(&self) -> Rect;
 
     fn config(&self) -> ConfigurationRef;
+
+    /// Returns whether the application handles back navigation itself.
+    ///
+    /// See [`set_consumes_back()`](Self::set_consumes_back).
+    fn consumes_back(&self) -> bool;
+
+    /// Sets whether the application handles back navigation itself.
+    ///
+    /// When enabled, [`WindowEvent::BackInvoked`] is emitted when the user presses the back
+    /// button or performs the back gesture, and the activity is no longer finished. This can be
+    /// used to close an in-app menu instead of closing the application. It should be disabled
+    /// again when there is nothing to go back to.
+    ///
+    /// The default is `false`.
+    ///
+    /// [`WindowEvent::BackInvoked`]: crate::event::WindowEvent::BackInvoked
+    fn set_consumes_back(&self, consumes: bool);
+
+    /// Returns how the window is laid out around the display cutout.
+    ///
+    /// See [`set_display_cutout_mode()`](Self::set_display_cutout_mode).
+    fn display_cutout_mode(&self) -> DisplayCutoutMode;
+
+    /// Sets how the window is laid out around the display cutout, i.e. the
+    /// `layoutInDisplayCutoutMode` of the window.
+    ///
+    /// This is kept when the activity is recreated. It has no effect before API 28.
+    fn set_display_cutout_mode(&self, mode: DisplayCutoutMode);
+
+    /// Returns the bounding rectangles of the display cutout and the insets of the curved edges,
+    /// e.g. to draw decorative content around the camera housing.
+    ///
+    /// This is the detail behind [`Window::safe_area()`], which is the conservative inset to keep
+    /// content out of the cutout. [`WindowEvent::DisplayCutoutChanged`] is emitted when this
+    /// changes, e.g. when the device is rotated.
+    ///
+    /// Returns [`None`] if the display has no cutout, the window doesn't extend into it, see
+    /// [`set_display_cutout_mode()`](Self::set_display_cutout_mode), or before API 29.
+    ///
+    /// [`WindowEvent::DisplayCutoutChanged`]: crate::event::WindowEvent::DisplayCutoutChanged
+    fn display_cutout(&self) -> Option<DisplayCutout>;
+
+    /// Returns whether the window is laid out behind the system bars.
+    ///
+    /// See [`set_edge_to_edge()`](Self::set_edge_to_edge).
+    fn is_edge_to_edge(&self) -> bool;
+
+    /// Sets whether the window is laid out behind the system bars, which is the default since
+    /// API 35.
+    ///
+    /// Use [`Window::safe_area()`] to keep content out from under the system bars. The colors of
+    /// the system bars are taken from the theme of the activity.
+    ///
+    /// This is kept when the activity is recreated.
+    fn set_edge_to_edge(&self, edge_to_edge: bool);
+
+    /// Returns how the system bars behave while the window is fullscreen.
+    ///
+    /// See [`set_system_bars_behavior()`](Self::set_system_bars_behavior).
+    fn system_bars_behavior(&self) -> SystemBarsBehavior;
+
+    /// Sets how the system bars behave while the window is fullscreen, i.e. while they are hidden
+    /// with [`Window::set_fullscreen()`].
+    ///
+    /// This is kept when the activity is recreated.
+    fn set_system_bars_behavior(&self, behavior: SystemBarsBehavior);
+
+    /// Returns the timing of the frame that is being drawn.
+    ///
+    /// This is only available during [`WindowEvent::RedrawRequested`] and when redraws are driven
+    /// by `AChoreographer`, see [`EventLoopBuilderExtAndroid::with_choreographer_redraws()`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn frame_timing(&self) -> Option<FrameTiming>;
+
+    /// Sets whether the historical samples of touches are reported.
+    ///
+    /// Android batches the movements of touches into one `MotionEvent` per frame. When enabled,
+    /// every [`WindowEvent::Touch`] with [`TouchPhase::Moved`] is preceded by the samples that
+    /// were batched into it, in the order in which they were recorded, which a stylus can
+    /// report at several hundred Hz.
+    ///
+    /// The default is to only report the most recent sample.
+    ///
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    /// [`TouchPhase::Moved`]: crate::event::TouchPhase::Moved
+    fn set_coalesced_touches(&self, enabled: bool);
+
+    /// Sets the frame rate that the application intends to draw at, so that the system can pick
+    /// a matching refresh rate for the display, e.g. 120 Hz for a game or 60 Hz to save power.
+    ///
+    /// This calls `ANativeWindow_setFrameRateWithChangeStrategy()`, switching the refresh rate
+    /// only if it is seamless, and is kept when the surface is recreated. Pass `0.0` to let the
+    /// system choose again. [`ApplicationHandler::monitor_video_mode_changed`] is emitted when
+    /// the refresh rate changed.
+    ///
+    /// Returns [`NotSupportedError`] before API 30.
+    ///
+    /// [`ApplicationHandler::monitor_video_mode_changed`]: crate::application::ApplicationHandler::monitor_video_mode_changed
+    fn set_frame_rate(
+        &self,
+        frame_rate: f32,
+        compatibility: FrameRateCompatibility,
+    ) -> Result<(), NotSupportedError>;
+
+    /// Returns whether the screen is kept on while the window is visible.
+    ///
+    /// See [`set_keep_screen_on()`](Self::set_keep_screen_on).
+    fn is_keep_screen_on(&self) -> bool;
+
+    /// Sets whether the screen is kept on while the window is visible, i.e. the
+    /// `FLAG_KEEP_SCREEN_ON` flag of the window, e.g. while a video is playing.
+    ///
+    /// This is kept when the activity is recreated. The default is `false`.
+    fn set_keep_screen_on(&self, keep_screen_on: bool);
+
+    /// Returns whether the sustained performance mode is enabled.
+    ///
+    /// See [`set_sustained_performance_mode()`](Self::set_sustained_performance_mode).
+    fn is_sustained_performance_mode(&self) -> bool;
+
+    /// Sets whether the window uses the sustained performance mode, in which the device keeps
+    /// a consistent level of performance over long periods instead of throttling when it gets
+    /// hot.
+    ///
+    /// This is kept when the activity is recreated. The default is `false`.
+    ///
+    /// Returns [`NotSupportedError`] when enabling it on a device that doesn't support it, see
+    /// [`PowerManager.isSustainedPerformanceModeSupported()`], which is always the case before
+    /// API 24.
+    ///
+    /// [`PowerManager.isSustainedPerformanceModeSupported()`]: https://developer.android.com/reference/android/os/PowerManager#isSustainedPerformanceModeSupported()
+    fn set_sustained_performance_mode(&self, enabled: bool) -> Result<(), NotSupportedError>;
+
+    /// Asks the system to show the activity in a small picture-in-picture window, e.g. to keep
+    /// playing a video while the user does something else.
+    ///
+    /// The activity needs `android:supportsPictureInPicture="true"` in the manifest, and should
+    /// handle the `screenSize|smallestScreenSize|screenLayout|orientation` configuration changes
+    /// so that it isn't recreated. [`WindowEvent::PictureInPictureChanged`] is emitted once the
+    /// window entered picture-in-picture. Redraws keep being emitted while the activity is paused
+    /// in picture-in-picture, and the fullscreen state is applied again when the window returns
+    /// from it.
+    ///
+    /// Returns [`NotSupportedError`] before API 26, or if the device doesn't support
+    /// picture-in-picture.
+    ///
+    /// [`WindowEvent::PictureInPictureChanged`]: crate::event::WindowEvent::PictureInPictureChanged
+    fn enter_picture_in_picture(&self, params: PipParams) -> Result<(), NotSupportedError>;
 }
 
 impl WindowExtAndroid for Window {
@@ -98,9 +319,111 @@ impl WindowExtAndroid for Window {
     fn config(&self) -> ConfigurationRef {
         self.window.config()
     }
+
+    fn consumes_back(&self) -> bool {
+        self.window.consumes_back()
+    }
+
+    fn set_consumes_back(&self, consumes: bool) {
+        self.window.set_consumes_back(consumes)
+    }
+
+    fn display_cutout_mode(&self) -> DisplayCutoutMode {
+        self.window.display_cutout_mode()
+    }
+
+    fn set_display_cutout_mode(&self, mode: DisplayCutoutMode) {
+        self.window.set_display_cutout_mode(mode)
+    }
+
+    fn display_cutout(&self) -> Option<DisplayCutout> {
+        self.window.display_cutout()
+    }
+
+    fn is_edge_to_edge(&self) -> bool {
+        self.window.is_edge_to_edge()
+    }
+
+    fn set_edge_to_edge(&self, edge_to_edge: bool) {
+        self.window.set_edge_to_edge(edge_to_edge)
+    }
+
+    fn system_bars_behavior(&self) -> SystemBarsBehavior {
+        self.window.system_bars_behavior()
+    }
+
+    fn set_system_bars_behavior(&self, behavior: SystemBarsBehavior) {
+        self.window.set_system_bars_behavior(behavior)
+    }
+
+    fn frame_timing(&self) -> Option<FrameTiming> {
+        self.window.frame_timing()
+    }
+
+    fn set_coalesced_touches(&self, enabled: bool) {
+        self.window.set_coalesced_touches(enabled)
+    }
+
+    fn set_frame_rate(
+        &self,
+        frame_rate: f32,
+        compatibility: FrameRateCompatibility,
+    ) -> Result<(), NotSupportedError> {
+        self.window.set_frame_rate(frame_rate, compatibility)
+    }
+
+    fn is_keep_screen_on(&self) -> bool {
+        self.window.is_keep_screen_on()
+    }
+
+    fn set_keep_screen_on(&self, keep_screen_on: bool) {
+        self.window.set_keep_screen_on(keep_screen_on)
+    }
+
+    fn is_sustained_performance_mode(&self) -> bool {
+        self.window.is_sustained_performance_mode()
+    }
+
+    fn set_sustained_performance_mode(&self, enabled: bool) -> Result<(), NotSupportedError> {
+        self.window.set_sustained_performance_mode(enabled)
+    }
+
+    fn enter_picture_in_picture(&self, params: PipParams) -> Result<(), NotSupportedError> {
+        self.window.enter_picture_in_picture(params)
+    }
 }
 
-impl ActiveEventLoopExtAndroid for ActiveEventLoop {}
+impl ActiveEventLoopExtAndroid for ActiveEventLoop {
+    fn memory_trim_level(&self) -> Option<TrimLevel> {
+        self.p.memory_trim_level()
+    }
+
+    fn set_save_state_callback(&self, callback: Option<Box<dyn FnMut() -> Vec<u8>>>) {
+        self.p.set_save_state_callback(callback)
+    }
+
+    fn restored_state(&self) -> Option<Vec<u8>> {
+        self.p.restored_state()
+    }
+
+    fn is_recreated(&self) -> bool {
+        self.p.is_recreated()
+    }
+
+    fn game_mode(&self) -> GameMode {
+        self.p.game_mode()
+    }
+
+    fn create_performance_hint_session(
+        &self,
+        thread_ids: &[i32],
+        target_work_duration: Duration,
+    ) -> Result<PerformanceHintSession, NotSupportedError> {
+        self.p
+            .create_performance_hint_session(thread_ids, target_work_duration)
+            .map(|inner| PerformanceHintSession { inner })
+    }
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to Android.
 pub trait WindowAttributesExtAndroid {}
@@ -116,7 +439,31 @@ pub trait EventLoopBuilderExtAndroid {
     /// Calling this will mark the volume keys to be manually handled by the application
     ///
     /// Default is to let the operating system handle the volume keys
+    #[deprecated = "use `handle_system_keys` instead"]
     fn handle_volume_keys(&mut self) -> &mut Self;
+
+    /// Calling this will deliver system keys as [`WindowEvent::KeyboardInput`] and mark them to
+    /// be manually handled by the application.
+    ///
+    /// System keys are those that `KeyEvent.isSystem()` returns `true` for, e.g. the volume and
+    /// media keys. `Home` and `Power` are never delivered, and `Back` follows
+    /// [`WindowExtAndroid::set_consumes_back()`] instead.
+    ///
+    /// Default is to let the operating system handle the system keys.
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn handle_system_keys(&mut self) -> &mut Self;
+
+    /// Whether [`WindowEvent::RedrawRequested`] is driven by `AChoreographer`, so that it is
+    /// emitted once per vsync after [`Window::request_redraw()`], together with the
+    /// [`FrameTiming`] of the frame.
+    ///
+    /// Requires API 29, redraws are emitted as soon as possible before that.
+    ///
+    /// The default is `false`.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn with_choreographer_redraws(&mut self, enabled: bool) -> &mut Self;
 }
 
 impl EventLoopBuilderExtAndroid for EventLoopBuilder {
@@ -126,11 +473,232 @@ impl EventLoopBuilderExtAndroid for EventLoopBuilder {
     }
 
     fn handle_volume_keys(&mut self) -> &mut Self {
-        self.platform_specific.ignore_volume_keys = false;
+        self.handle_system_keys()
+    }
+
+    fn handle_system_keys(&mut self) -> &mut Self {
+        self.platform_specific.ignore_system_keys = false;
+        self
+    }
+
+    fn with_choreographer_redraws(&mut self, enabled: bool) -> &mut Self {
+        self.platform_specific.choreographer_redraws = enabled;
         self
     }
 }
 
+/// The timing of a frame reported by `AChoreographer`.
+///
+/// All times are in nanoseconds of `CLOCK_MONOTONIC`, i.e. `System.nanoTime()`.
+///
+/// See [`WindowExtAndroid::frame_timing()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameTiming {
+    /// The time at which the frame started, i.e. the time of the vsync.
+    pub frame_time_nanos: i64,
+    /// The time at which the frame is expected to be presented on the display.
+    ///
+    /// Only available since API 33.
+    pub expected_presentation_time_nanos: Option<i64>,
+    /// The time by which the frame needs to be submitted to be presented at
+    /// [`expected_presentation_time_nanos`](Self::expected_presentation_time_nanos).
+    ///
+    /// Only available since API 33.
+    pub deadline_nanos: Option<i64>,
+}
+
+/// How the window is laid out around the display cutout.
+///
+/// See [`WindowExtAndroid::set_display_cutout_mode()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DisplayCutoutMode {
+    /// The window extends into the cutout area in portrait orientation, but not in landscape,
+    /// unless the window is laid out behind the system bars.
+    ///
+    /// `LAYOUT_IN_DISPLAY_CUTOUT_MODE_DEFAULT`.
+    #[default]
+    Default,
+    /// The window always extends into the cutout area on the short edges of the screen.
+    ///
+    /// `LAYOUT_IN_DISPLAY_CUTOUT_MODE_SHORT_EDGES`.
+    ShortEdges,
+    /// The window never extends into the cutout area.
+    ///
+    /// `LAYOUT_IN_DISPLAY_CUTOUT_MODE_NEVER`.
+    Never,
+    /// The window always extends into the cutout area on all edges of the screen.
+    ///
+    /// `LAYOUT_IN_DISPLAY_CUTOUT_MODE_ALWAYS`, which is only available since API 30. Before that
+    /// this behaves like [`DisplayCutoutMode::ShortEdges`].
+    Always,
+}
+
+/// The shape of the display cutout, i.e. a `DisplayCutout`, in physical pixels relative to the
+/// window.
+///
+/// See [`WindowExtAndroid::display_cutout()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DisplayCutout {
+    /// The bounding rectangle of the cutout on the top edge of the display, as the position and
+    /// size.
+    pub top: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// The bounding rectangle of the cutout on the left edge of the display.
+    pub left: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// The bounding rectangle of the cutout on the bottom edge of the display.
+    pub bottom: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// The bounding rectangle of the cutout on the right edge of the display.
+    pub right: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// The insets of the curved edges of a waterfall display, which are zero before API 30.
+    pub waterfall_insets: PhysicalInsets<u32>,
+}
+
+/// How the system bars behave while the window is fullscreen.
+///
+/// See [`WindowExtAndroid::set_system_bars_behavior()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SystemBarsBehavior {
+    /// The system bars are shown when the user touches the screen, and stay visible, leaving
+    /// fullscreen.
+    ///
+    /// Since API 31 this behaves like [`SystemBarsBehavior::Immersive`].
+    LeanBack,
+    /// The system bars are shown when the user swipes from an edge of the screen, and stay
+    /// visible, leaving fullscreen.
+    Immersive,
+    /// The system bars are shown semi-transparently over the window when the user swipes from an
+    /// edge of the screen, and are hidden again after a short time. The swipe isn't delivered to
+    /// the window.
+    #[default]
+    ImmersiveSticky,
+}
+
+/// What the frame rate passed to [`WindowExtAndroid::set_frame_rate()`] is used for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FrameRateCompatibility {
+    /// The application draws at a variable rate, e.g. a game, and the display should run at the
+    /// frame rate or a multiple of it.
+    ///
+    /// `ANATIVEWINDOW_FRAME_RATE_COMPATIBILITY_DEFAULT`.
+    #[default]
+    Default,
+    /// The application plays content with a fixed rate, e.g. a video, and the system may use
+    /// pull-down to present it at a different refresh rate.
+    ///
+    /// `ANATIVEWINDOW_FRAME_RATE_COMPATIBILITY_FIXED_SOURCE`.
+    FixedSource,
+}
+
+/// The parameters of the picture-in-picture window.
+///
+/// See [`WindowExtAndroid::enter_picture_in_picture()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PipParams {
+    /// The aspect ratio of the picture-in-picture window, as width and height, e.g. `(16, 9)`.
+    ///
+    /// Android only accepts ratios between 1:2.39 and 2.39:1, other ratios are clamped. The
+    /// system picks a ratio if this is [`None`].
+    pub aspect_ratio: Option<(u32, u32)>,
+    /// The area of the window that shows the content, e.g. the video, as the position and size
+    /// in physical pixels.
+    ///
+    /// The system animates this area into the picture-in-picture window.
+    pub source_rect_hint: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+}
+
+/// The game mode that the user selected for the application.
+///
+/// See [`ActiveEventLoopExtAndroid::game_mode()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameMode {
+    /// The application doesn't support game modes, or the device doesn't support them.
+    ///
+    /// `GameManager.GAME_MODE_UNSUPPORTED`.
+    Unsupported,
+    /// The default behavior of the application.
+    ///
+    /// `GameManager.GAME_MODE_STANDARD`.
+    Standard,
+    /// The user prefers the highest performance, e.g. a higher frame rate.
+    ///
+    /// `GameManager.GAME_MODE_PERFORMANCE`.
+    Performance,
+    /// The user prefers a longer battery life, e.g. a lower frame rate or resolution.
+    ///
+    /// `GameManager.GAME_MODE_BATTERY`.
+    Battery,
+    /// The user configured the settings of the application, API 34 and later.
+    ///
+    /// `GameManager.GAME_MODE_CUSTOM`.
+    Custom,
+}
+
+/// A session of the performance hint API, see
+/// [`ActiveEventLoopExtAndroid::create_performance_hint_session()`].
+///
+/// The session is closed when this is dropped.
+pub struct PerformanceHintSession {
+    inner: crate::platform_impl::PerformanceHintSession,
+}
+
+impl PerformanceHintSession {
+    /// Updates the time in which the threads of the session should finish their work.
+    pub fn update_target_work_duration(&self, duration: Duration) {
+        self.inner.update_target_work_duration(duration)
+    }
+
+    /// Reports the time that the threads of the session actually took for their work, e.g. the
+    /// CPU time of the last frame.
+    pub fn report_actual_work_duration(&self, duration: Duration) {
+        self.inner.report_actual_work_duration(duration)
+    }
+}
+
+impl fmt::Debug for PerformanceHintSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerformanceHintSession").finish_non_exhaustive()
+    }
+}
+
+/// The level of a memory warning, as passed to `onTrimMemory()`, from the least to the most
+/// severe.
+///
+/// See [`ActiveEventLoopExtAndroid::memory_trim_level()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrimLevel {
+    /// The application is running, and the device is beginning to run low on memory.
+    ///
+    /// `TRIM_MEMORY_RUNNING_MODERATE`.
+    RunningModerate,
+    /// The application is running, and the device is running much lower on memory.
+    ///
+    /// `TRIM_MEMORY_RUNNING_LOW`.
+    RunningLow,
+    /// The application is running, and the device is running extremely low on memory, background
+    /// processes are being killed.
+    ///
+    /// `TRIM_MEMORY_RUNNING_CRITICAL`.
+    RunningCritical,
+    /// The user interface of the application is no longer visible, and large allocations that
+    /// are only used by it can be released.
+    ///
+    /// `TRIM_MEMORY_UI_HIDDEN`.
+    UiHidden,
+    /// The application is in the background, and is among the processes that are killed when
+    /// the device runs low on memory.
+    ///
+    /// `TRIM_MEMORY_BACKGROUND`.
+    Background,
+    /// The application is in the background, and is in the middle of the processes that are
+    /// killed.
+    ///
+    /// `TRIM_MEMORY_MODERATE`.
+    Moderate,
+    /// The application is in the background, and is among the first processes that are killed.
+    ///
+    /// `TRIM_MEMORY_COMPLETE`.
+    Complete,
+}
+
 /// Re-export of the `android_activity` API
 ///
 /// Winit re-exports the `android_activity` API for convenience so that most
@@ -0,0 +1,221 @@
+//! # Headless
+//!
+//! The headless backend doesn't connect to any display server: its windows only exist in memory
+//! and the input comes from a [`HeadlessDriver`]. This makes it possible to run an application in
+//! tests or on machines without a display, and to drive it deterministically.
+//!
+//! It's enabled with the `"headless"` cargo feature, and is used when forced with
+//! [`EventLoopBuilderExtHeadless::with_headless`] or when it's the only backend compiled in.
+//!
+//! The backend has a single monitor and its own clock, which only follows the real one until
+//! [`HeadlessDriver::advance_time`] moves it forward. [`ControlFlow::WaitUntil`] deadlines are
+//! measured with that clock, so timers can fire without waiting for them.
+//!
+//! Since there is no surface to render to, the windows have no raw window handle.
+//!
+//! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use smol_str::SmolStr;
+
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event::{DeviceEvent, ElementState, MouseButton, SessionEvent, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
+use crate::keyboard::{Key, KeyCode};
+use crate::platform_impl::headless::Shared;
+use crate::window::{ImePurpose, WindowId};
+
+/// Additional methods on [`ActiveEventLoop`] that are specific to the headless backend.
+pub trait ActiveEventLoopExtHeadless {
+    /// Returns a driver for the event loop, or `None` if it doesn't use the headless backend.
+    fn headless_driver(&self) -> Option<HeadlessDriver>;
+}
+
+impl ActiveEventLoopExtHeadless for ActiveEventLoop {
+    #[inline]
+    fn headless_driver(&self) -> Option<HeadlessDriver> {
+        self.p.headless_shared().map(|shared| HeadlessDriver { shared: shared.clone() })
+    }
+}
+
+/// Additional methods on [`EventLoop`] that are specific to the headless backend.
+pub trait EventLoopExtHeadless {
+    /// Returns a driver for the event loop, or `None` if it doesn't use the headless backend.
+    fn headless_driver(&self) -> Option<HeadlessDriver>;
+}
+
+impl EventLoopExtHeadless for EventLoop {
+    #[inline]
+    fn headless_driver(&self) -> Option<HeadlessDriver> {
+        self.event_loop.window_target().headless_driver()
+    }
+}
+
+/// Additional methods on [`EventLoopBuilder`] that are specific to the headless backend.
+pub trait EventLoopBuilderExtHeadless {
+    /// Force using the headless backend.
+    fn with_headless(&mut self) -> &mut Self;
+}
+
+impl EventLoopBuilderExtHeadless for EventLoopBuilder {
+    #[inline]
+    fn with_headless(&mut self) -> &mut Self {
+        self.platform_specific.forced_backend = Some(crate::platform_impl::Backend::Headless);
+        self
+    }
+}
+
+/// Feeds input to the windows of a headless event loop.
+///
+/// The events are queued in order and dispatched by the next iteration of the event loop, which
+/// is woken up if it's waiting. The driver can be cloned and sent to other threads.
+///
+/// Like a display server would, the driver keeps track of the focus, of the pressed keys and of
+/// the pointer, and derives the events that follow from them: [`WindowEvent::ModifiersChanged`]
+/// when pressing a modifier key or toggling a lock key, [`WindowEvent::CursorEntered`] when the
+/// pointer moves into a window, and so on. The input is ignored when it targets a window that
+/// doesn't exist.
+#[derive(Clone)]
+pub struct HeadlessDriver {
+    shared: Arc<Shared>,
+}
+
+impl HeadlessDriver {
+    /// Returns the identifiers of the windows, in the order they were created.
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.shared.lock().window_ids().map(WindowId).collect()
+    }
+
+    /// Queues an arbitrary event for the window.
+    ///
+    /// The event is dispatched as is, without changing the state of the window or of the input.
+    pub fn window_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.shared.update(|state| state.window_event(window_id.0, event));
+    }
+
+    /// Queues an arbitrary device event.
+    pub fn device_event(&self, event: DeviceEvent) {
+        self.shared.update(|state| state.device_event(event));
+    }
+
+    /// Presses or releases a key on the focused window.
+    ///
+    /// The `text` is only reported when pressing the key. Pressing a key that is already pressed
+    /// is reported as a repeat. Pressing [`KeyCode::CapsLock`] or [`KeyCode::NumLock`] toggles
+    /// the lock key, which stays on when the focus changes.
+    pub fn key(&self, code: KeyCode, logical_key: Key, text: Option<SmolStr>, state: ElementState) {
+        self.shared.update(|inner| inner.key(code, logical_key, text, state));
+    }
+
+    /// Switches to a keyboard layout, with [`ApplicationHandler::keyboard_layout_changed`].
+    ///
+    /// The layout gives the logical key of each physical key without modifiers, and is used by
+    /// [`ActiveEventLoop::key_from_key_code`] and [`ActiveEventLoop::key_code_from_key`]. The keys
+    /// it doesn't list keep their layout-independent meaning, like [`NamedKey::Enter`] for
+    /// [`KeyCode::Enter`].
+    ///
+    /// [`ApplicationHandler::keyboard_layout_changed`]: crate::application::ApplicationHandler::keyboard_layout_changed
+    /// [`NamedKey::Enter`]: crate::keyboard::NamedKey::Enter
+    pub fn set_keyboard_layout(&self, keys: impl IntoIterator<Item = (KeyCode, Key)>) {
+        let keys = keys.into_iter().collect();
+        self.shared.update(|state| state.set_keyboard_layout(keys));
+    }
+
+    /// Reports a change of the power or session state of the system, with
+    /// [`ApplicationHandler::session_event`].
+    ///
+    /// The state of the windows, like their focus, is left as is.
+    ///
+    /// [`ApplicationHandler::session_event`]: crate::application::ApplicationHandler::session_event
+    pub fn session_event(&self, event: SessionEvent) {
+        self.shared.update(|state| state.session_event(event));
+    }
+
+    /// Moves the pointer to a position in a window, or out of the windows with `None`.
+    pub fn move_pointer(&self, target: Option<(WindowId, PhysicalPosition<f64>)>) {
+        self.shared.update(|state| {
+            state.move_pointer(target.map(|(window_id, position)| (window_id.0, position)))
+        });
+    }
+
+    /// Presses or releases a mouse button on the window under the pointer.
+    ///
+    /// The presses are counted as consecutive clicks when they're less than 500 ms apart on the
+    /// clock of the event loop, and the pointer didn't move more than 2 pixels on each axis.
+    pub fn mouse_input(&self, state: ElementState, button: MouseButton) {
+        self.shared.update(|inner| inner.mouse_input(state, button));
+    }
+
+    /// Resizes the window, as the user would.
+    ///
+    /// The size is clamped to the size constraints of the window.
+    pub fn resize(&self, window_id: WindowId, size: PhysicalSize<u32>) {
+        self.shared.update(|state| {
+            state.resize(window_id.0, size);
+        });
+    }
+
+    /// Changes the scale factor of the window.
+    ///
+    /// The window keeps its logical size unless the application asks for another one in
+    /// [`WindowEvent::ScaleFactorChanged`].
+    pub fn set_scale_factor(&self, window_id: WindowId, scale_factor: f64) {
+        self.shared.update(|state| state.set_scale_factor(window_id.0, scale_factor));
+    }
+
+    /// Asks the application to close the window, with [`WindowEvent::CloseRequested`].
+    pub fn close(&self, window_id: WindowId) {
+        self.shared.update(|state| state.window_event(window_id.0, WindowEvent::CloseRequested));
+    }
+
+    /// Focuses the window, or no window with `None`.
+    pub fn focus(&self, window_id: Option<WindowId>) {
+        self.shared.update(|state| state.focus(window_id.map(|window_id| window_id.0)));
+    }
+
+    /// Returns the IME state of the window, as an input method would see it, or `None` if the
+    /// window doesn't exist.
+    pub fn ime_state(&self, window_id: WindowId) -> Option<HeadlessImeState> {
+        self.shared.lock().windows.get(&window_id.0).map(|window| HeadlessImeState {
+            allowed: window.ime_allowed,
+            cursor_area: window.ime_cursor_area,
+            purpose: window.ime_purpose,
+        })
+    }
+
+    /// Moves the clock of the event loop forward.
+    pub fn advance_time(&self, duration: Duration) {
+        self.shared.update(|state| state.advance_time(duration));
+    }
+
+    /// Returns the current time of the clock of the event loop.
+    pub fn now(&self) -> Instant {
+        self.shared.lock().now()
+    }
+}
+
+/// The IME state of a window, returned by [`HeadlessDriver::ime_state`].
+///
+/// The cursor area and purpose are kept while IME is disallowed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadlessImeState {
+    /// Whether IME is allowed, see [`Window::set_ime_allowed`].
+    ///
+    /// [`Window::set_ime_allowed`]: crate::window::Window::set_ime_allowed
+    pub allowed: bool,
+    /// The last cursor area, in physical pixels, see [`Window::set_ime_cursor_area`].
+    ///
+    /// [`Window::set_ime_cursor_area`]: crate::window::Window::set_ime_cursor_area
+    pub cursor_area: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// The last purpose, see [`Window::set_ime_purpose`].
+    ///
+    /// [`Window::set_ime_purpose`]: crate::window::Window::set_ime_purpose
+    pub purpose: ImePurpose,
+}
+
+impl std::fmt::Debug for HeadlessDriver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HeadlessDriver").finish_non_exhaustive()
+    }
+}
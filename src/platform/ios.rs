@@ -57,17 +57,70 @@
 //!
 //!  - applicationDidBecomeActive is Resumed
 //!  - applicationWillResignActive is Suspended
+//!  - applicationDidEnterBackground is DestroySurfaces
+//!  - applicationWillEnterForeground is CreateSurfaces
 //!  - applicationWillTerminate is LoopExiting
 //!
 //! Keep in mind that after LoopExiting event is received every attempt to draw with
 //! opengl will result in segfault.
 //!
 //! Also note that app may not receive the LoopExiting event if suspended; it might be SIGKILL'ed.
+//!
+//! ## Scenes
+//!
+//! If the app's `Info.plist` contains a [`UIApplicationSceneManifest`], winit runs the app with
+//! the scene-based lifecycle (iOS 13 and later), and each [`Window`] is shown in its own
+//! [`UIWindowScene`]. The first window is attached to the scene the system connects at launch;
+//! creating further windows requests a new scene session, which requires
+//! `UIApplicationSupportsMultipleScenes` to be enabled in the manifest.
+//!
+//! With scenes, lifecycle events are derived per scene:
+//!
+//!  - sceneWillEnterForeground and sceneDidEnterBackground are `WindowEvent::Occluded` for the
+//!    scene's windows
+//!  - the first scene becoming active is Resumed, and the last one resigning active is Suspended
+//!  - the first scene entering the foreground is CreateSurfaces, and the last one entering the
+//!    background is DestroySurfaces
+//!  - sceneDidDisconnect is `WindowEvent::Destroyed` for the scene's windows
+//!
+//! When the system connects a scene on its own, for example when the user opens a new window from
+//! the app switcher, `ApplicationHandler::can_create_surfaces` is called so that the app can create
+//! a window for it.
+//!
+//! Apps without a scene manifest, and tvOS, keep using the application lifecycle described above.
+//!
+//! [`UIApplicationSceneManifest`]: https://developer.apple.com/documentation/bundleresources/information_property_list/uiapplicationscenemanifest
+//! [`UIWindowScene`]: https://developer.apple.com/documentation/uikit/uiwindowscene?language=objc
+//!
+//! ## Trackpads and mice
+//!
+//! Since iOS 13.4, the pointer of trackpads and mice is reported with `WindowEvent::CursorEntered`,
+//! `WindowEvent::CursorMoved` and `WindowEvent::CursorLeft` while it hovers over a window, and
+//! scrolling is reported as `WindowEvent::MouseWheel` with pixel deltas.
+//!
+//! Clicks are only reported as `WindowEvent::MouseInput`, with the button that was pressed, if
+//! the app's `Info.plist` enables [`UIApplicationSupportsIndirectInputEvents`]. Otherwise, UIKit
+//! delivers them as touches.
+//!
+//! [`UIApplicationSupportsIndirectInputEvents`]: https://developer.apple.com/documentation/bundleresources/information_property_list/uiapplicationsupportsindirectinputevents
+//!
+//! ## Embedding
+//!
+//! Applications built with UIKit or SwiftUI can show winit's view as part of their own interface
+//! with [`WindowAttributesExtIOS::with_parent_view`]. They launch themselves and own the
+//! application lifecycle, so instead of calling `EventLoop::run_app`, they create the event loop
+//! once the application has launched and call `EventLoopExtPumpEvents::pump_app_events` every
+//! frame to receive the events of the embedded views. The lifecycle events described above
+//! are not delivered, except for the initial `CreateSurfaces`.
 
 use std::os::raw::c_void;
+use std::ptr::NonNull;
 
+use crate::error::NotSupportedError;
+use crate::event_loop::{ActiveEventLoop, ThermalState};
 use crate::monitor::{MonitorHandle, VideoModeHandle};
-use crate::window::{Window, WindowAttributes};
+use crate::platform_impl::ParentView;
+use crate::window::{InterfaceOrientation, Window, WindowAttributes};
 
 /// Additional methods on [`Window`] that are specific to iOS.
 pub trait WindowExtIOS {
@@ -90,6 +143,37 @@ pub trait WindowExtIOS {
     /// [`-[UIViewController attemptRotationToDeviceOrientation]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621400-attemptrotationtodeviceorientati?language=objc).
     fn set_valid_orientations(&self, valid_orientations: ValidOrientations);
 
+    /// Sets the interface orientations supported by the [`Window`].
+    ///
+    /// This is a more fine-grained version of
+    /// [`set_valid_orientations`](Self::set_valid_orientations). An empty mask is ignored.
+    ///
+    /// This changes the value returned by
+    /// [`-[UIViewController supportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621435-supportedinterfaceorientations?language=objc),
+    /// and then calls
+    /// [`-[UIViewController setNeedsUpdateOfSupportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/4047535-setneedsupdateofsupportedinterfa?language=objc)
+    /// on iOS 16.0+, or
+    /// [`-[UIViewController attemptRotationToDeviceOrientation]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621400-attemptrotationtodeviceorientati?language=objc)
+    /// on earlier versions.
+    ///
+    /// If the interface is currently rotating, the new orientations are applied once the
+    /// rotation has finished.
+    fn set_supported_interface_orientations(&self, orientations: OrientationMask);
+
+    /// Returns the current orientation of the [`Window`]'s user interface.
+    ///
+    /// Changes are reported with [`WindowEvent::InterfaceOrientationChanged`].
+    ///
+    /// [`WindowEvent::InterfaceOrientationChanged`]: crate::event::WindowEvent::InterfaceOrientationChanged
+    fn interface_orientation(&self) -> Option<InterfaceOrientation>;
+
+    /// Returns the factor by which text should be scaled to honor the user's preferred text size.
+    ///
+    /// Changes are reported with [`WindowEvent::TextScaleFactorChanged`].
+    ///
+    /// [`WindowEvent::TextScaleFactorChanged`]: crate::event::WindowEvent::TextScaleFactorChanged
+    fn text_scale_factor(&self) -> f64;
+
     /// Sets whether the [`Window`] prefers the home indicator hidden.
     ///
     /// The default is to prefer showing the home indicator.
@@ -102,6 +186,11 @@ pub trait WindowExtIOS {
     /// This only has an effect on iOS 11.0+.
     fn set_prefers_home_indicator_hidden(&self, hidden: bool);
 
+    /// Returns whether the [`Window`] prefers the home indicator hidden.
+    ///
+    /// See [`set_prefers_home_indicator_hidden`](Self::set_prefers_home_indicator_hidden).
+    fn prefers_home_indicator_hidden(&self) -> bool;
+
     /// Sets the screen edges for which the system gestures will take a lower priority than the
     /// application's touch handling.
     ///
@@ -113,6 +202,12 @@ pub trait WindowExtIOS {
     /// This only has an effect on iOS 11.0+.
     fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge);
 
+    /// Returns the screen edges for which the system gestures will take a lower priority than the
+    /// application's touch handling.
+    ///
+    /// See [`set_preferred_screen_edges_deferring_system_gestures`](Self::set_preferred_screen_edges_deferring_system_gestures).
+    fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge;
+
     /// Sets whether the [`Window`] prefers the status bar hidden.
     ///
     /// The default is to prefer showing the status bar.
@@ -123,6 +218,11 @@ pub trait WindowExtIOS {
     ///
     /// [`setNeedsStatusBarAppearanceUpdate()`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621354-setneedsstatusbarappearanceupdat?language=objc)
     /// is also called for you.
+    ///
+    /// Once the safe area has been updated, [`WindowEvent::Resized`] is emitted so that the new
+    /// safe area can be queried with [`Window::inner_position`] and [`Window::inner_size`].
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     fn set_prefers_status_bar_hidden(&self, hidden: bool);
 
     /// Sets the preferred status bar style for the [`Window`].
@@ -137,6 +237,47 @@ pub trait WindowExtIOS {
     /// is also called for you.
     fn set_preferred_status_bar_style(&self, status_bar_style: StatusBarStyle);
 
+    /// Sets the animation used when the status bar is shown or hidden.
+    ///
+    /// The default is [`StatusBarAnimation::None`].
+    ///
+    /// This sets the value of the
+    /// [`preferredStatusBarUpdateAnimation`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621434-preferredstatusbarupdateanimatio?language=objc)
+    /// property, which is used by subsequent calls to
+    /// [`set_prefers_status_bar_hidden`](Self::set_prefers_status_bar_hidden).
+    ///
+    /// Status bar changes made while the interface is rotating are animated alongside the
+    /// rotation instead.
+    fn set_status_bar_update_animation(&self, animation: StatusBarAnimation);
+
+    /// Sets the range of frame rates at which [`WindowEvent::RedrawRequested`] should be
+    /// emitted, in frames per second.
+    ///
+    /// While a range is set, a display link emits [`WindowEvent::RedrawRequested`] continuously
+    /// at a rate chosen by the system within the range, e.g. to use 120 Hz on ProMotion
+    /// displays, or to cap the frame rate at 30 Hz to save battery. Passing `0.0` for all values
+    /// removes the display link again.
+    ///
+    /// Values outside of what the screen supports are clamped to
+    /// [`maximum_frame_rate`](Self::maximum_frame_rate). The range is kept when the window moves
+    /// to another screen.
+    ///
+    /// This sets the
+    /// [`preferredFrameRateRange`](https://developer.apple.com/documentation/quartzcore/cadisplaylink/3875343-preferredframeraterange?language=objc)
+    /// of the display link on iOS 15.0+, and
+    /// [`preferredFramesPerSecond`](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1648421-preferredframespersecond?language=objc)
+    /// to the preferred value on earlier versions.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn set_preferred_frame_rate_range(&self, min: f32, preferred: f32, max: f32);
+
+    /// Returns the maximum frame rate of the screen hosting the [`Window`], in frames per second.
+    ///
+    /// This is the value of
+    /// [`-[UIScreen maximumFramesPerSecond]`](https://developer.apple.com/documentation/uikit/uiscreen/2806814-maximumframespersecond?language=objc),
+    /// or 60 before iOS 10.3.
+    fn maximum_frame_rate(&self) -> f32;
+
     /// Sets whether the [`Window`] should recognize pinch gestures.
     ///
     /// The default is to not recognize gestures.
@@ -166,6 +307,34 @@ pub trait WindowExtIOS {
     ///
     /// The default is to not recognize gestures.
     fn recognize_rotation_gesture(&self, should_recognize: bool);
+
+    /// Sets whether the [`Window`] should report [`WindowEvent::StylusHover`] events when an
+    /// Apple Pencil hovers above the screen.
+    ///
+    /// The default is to not recognize hovering. Installs
+    /// [`UIHoverGestureRecognizer`](https://developer.apple.com/documentation/uikit/uihovergesturerecognizer)
+    /// onto the view.
+    ///
+    /// This is only supported on iOS 16.1+, and the altitude and azimuth of the pencil are only
+    /// available on iOS 16.4+.
+    ///
+    /// [`WindowEvent::StylusHover`]: crate::event::WindowEvent::StylusHover
+    fn recognize_stylus_hover(&self, should_recognize: bool);
+
+    /// Sets whether the [`Window`] should report coalesced and predicted touches.
+    ///
+    /// When enabled, every [`WindowEvent::Touch`] is preceded by the intermediate touches that
+    /// UIKit coalesced into it, which Apple Pencil samples at up to 240 Hz, and followed by
+    /// the touches UIKit predicts, with [`Touch::is_predicted`] set.
+    ///
+    /// The default is to only report the most recent touch, once per frame.
+    ///
+    /// See [`-[UIEvent coalescedTouchesForTouch:]`](https://developer.apple.com/documentation/uikit/uievent/1613808-coalescedtouchesfortouch?language=objc)
+    /// and [`-[UIEvent predictedTouchesForTouch:]`](https://developer.apple.com/documentation/uikit/uievent/1613814-predictedtouchesfortouch?language=objc).
+    ///
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    /// [`Touch::is_predicted`]: crate::event::Touch::is_predicted
+    fn set_coalesced_touches(&self, enabled: bool);
 }
 
 impl WindowExtIOS for Window {
@@ -179,11 +348,32 @@ impl WindowExtIOS for Window {
         self.window.maybe_queue_on_main(move |w| w.set_valid_orientations(valid_orientations))
     }
 
+    #[inline]
+    fn set_supported_interface_orientations(&self, orientations: OrientationMask) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_supported_interface_orientations(orientations))
+    }
+
+    #[inline]
+    fn interface_orientation(&self) -> Option<InterfaceOrientation> {
+        self.window.maybe_wait_on_main(|w| w.interface_orientation())
+    }
+
+    #[inline]
+    fn text_scale_factor(&self) -> f64 {
+        self.window.maybe_wait_on_main(|w| w.text_scale_factor())
+    }
+
     #[inline]
     fn set_prefers_home_indicator_hidden(&self, hidden: bool) {
         self.window.maybe_queue_on_main(move |w| w.set_prefers_home_indicator_hidden(hidden))
     }
 
+    #[inline]
+    fn prefers_home_indicator_hidden(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| w.prefers_home_indicator_hidden())
+    }
+
     #[inline]
     fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge) {
         self.window.maybe_queue_on_main(move |w| {
@@ -191,6 +381,11 @@ impl WindowExtIOS for Window {
         })
     }
 
+    #[inline]
+    fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge {
+        self.window.maybe_wait_on_main(|w| w.preferred_screen_edges_deferring_system_gestures())
+    }
+
     #[inline]
     fn set_prefers_status_bar_hidden(&self, hidden: bool) {
         self.window.maybe_queue_on_main(move |w| w.set_prefers_status_bar_hidden(hidden))
@@ -201,6 +396,22 @@ impl WindowExtIOS for Window {
         self.window.maybe_queue_on_main(move |w| w.set_preferred_status_bar_style(status_bar_style))
     }
 
+    #[inline]
+    fn set_status_bar_update_animation(&self, animation: StatusBarAnimation) {
+        self.window.maybe_queue_on_main(move |w| w.set_status_bar_update_animation(animation))
+    }
+
+    #[inline]
+    fn set_preferred_frame_rate_range(&self, min: f32, preferred: f32, max: f32) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_preferred_frame_rate_range(min, preferred, max))
+    }
+
+    #[inline]
+    fn maximum_frame_rate(&self) -> f32 {
+        self.window.maybe_wait_on_main(|w| w.maximum_frame_rate())
+    }
+
     #[inline]
     fn recognize_pinch_gesture(&self, should_recognize: bool) {
         self.window.maybe_queue_on_main(move |w| w.recognize_pinch_gesture(should_recognize));
@@ -231,6 +442,16 @@ impl WindowExtIOS for Window {
     fn recognize_rotation_gesture(&self, should_recognize: bool) {
         self.window.maybe_queue_on_main(move |w| w.recognize_rotation_gesture(should_recognize));
     }
+
+    #[inline]
+    fn recognize_stylus_hover(&self, should_recognize: bool) {
+        self.window.maybe_queue_on_main(move |w| w.recognize_stylus_hover(should_recognize));
+    }
+
+    #[inline]
+    fn set_coalesced_touches(&self, enabled: bool) {
+        self.window.maybe_queue_on_main(move |w| w.set_coalesced_touches(enabled));
+    }
 }
 
 /// Additional methods on [`WindowAttributes`] that are specific to iOS.
@@ -252,6 +473,15 @@ pub trait WindowAttributesExtIOS {
     /// [`-[UIViewController supportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621435-supportedinterfaceorientations?language=objc).
     fn with_valid_orientations(self, valid_orientations: ValidOrientations) -> Self;
 
+    /// Sets the interface orientations supported by the [`Window`].
+    ///
+    /// This takes precedence over
+    /// [`with_valid_orientations`](Self::with_valid_orientations).
+    ///
+    /// This sets the initial value returned by
+    /// [`-[UIViewController supportedInterfaceOrientations]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621435-supportedinterfaceorientations?language=objc).
+    fn with_supported_interface_orientations(self, orientations: OrientationMask) -> Self;
+
     /// Sets whether the [`Window`] prefers the home indicator hidden.
     ///
     /// The default is to prefer showing the home indicator.
@@ -286,6 +516,38 @@ pub trait WindowAttributesExtIOS {
     /// This sets the initial value returned by
     /// [`-[UIViewController preferredStatusBarStyle]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621416-preferredstatusbarstyle?language=objc),
     fn with_preferred_status_bar_style(self, status_bar_style: StatusBarStyle) -> Self;
+
+    /// Sets the animation used when the [`Window`]'s status bar is shown or hidden.
+    ///
+    /// The default is [`StatusBarAnimation::None`].
+    ///
+    /// This sets the initial value returned by
+    /// [`-[UIViewController preferredStatusBarUpdateAnimation]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621434-preferredstatusbarupdateanimatio?language=objc).
+    fn with_status_bar_update_animation(self, animation: StatusBarAnimation) -> Self;
+
+    /// Embeds the [`Window`] into `parent_view`, a [`UIView`] of the application, instead of
+    /// creating a [`UIWindow`] for it.
+    ///
+    /// This allows showing winit's view as part of a larger UIKit or SwiftUI interface, whose
+    /// application lifecycle is managed by the host application. Such applications drive winit
+    /// with [`EventLoopExtPumpEvents::pump_app_events`] instead of [`EventLoop::run_app`].
+    ///
+    /// The view covers the bounds of `parent_view` and is resized with it, and it is removed from
+    /// `parent_view` when the [`Window`] is dropped. Only events of the view itself are delivered,
+    /// such as touches, `RedrawRequested` and `Resized`; there are no `Focused` or `Occluded`
+    /// events. Methods that need a window of their own, such as [`Window::set_fullscreen`],
+    /// [`Window::set_outer_position`] and the orientation and status bar methods of
+    /// [`WindowExtIOS`], are ignored.
+    ///
+    /// # Safety
+    ///
+    /// `parent_view` must be a valid pointer to a `UIView` when the [`Window`] is created.
+    ///
+    /// [`UIView`]: https://developer.apple.com/documentation/uikit/uiview?language=objc
+    /// [`UIWindow`]: https://developer.apple.com/documentation/uikit/uiwindow?language=objc
+    /// [`EventLoopExtPumpEvents::pump_app_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_app_events
+    /// [`EventLoop::run_app`]: crate::event_loop::EventLoop::run_app
+    unsafe fn with_parent_view(self, parent_view: *mut c_void) -> Self;
 }
 
 impl WindowAttributesExtIOS for WindowAttributes {
@@ -301,6 +563,12 @@ impl WindowAttributesExtIOS for WindowAttributes {
         self
     }
 
+    #[inline]
+    fn with_supported_interface_orientations(mut self, orientations: OrientationMask) -> Self {
+        self.platform_specific.supported_interface_orientations = Some(orientations);
+        self
+    }
+
     #[inline]
     fn with_prefers_home_indicator_hidden(mut self, hidden: bool) -> Self {
         self.platform_specific.prefers_home_indicator_hidden = hidden;
@@ -324,6 +592,18 @@ impl WindowAttributesExtIOS for WindowAttributes {
         self.platform_specific.preferred_status_bar_style = status_bar_style;
         self
     }
+
+    #[inline]
+    fn with_status_bar_update_animation(mut self, animation: StatusBarAnimation) -> Self {
+        self.platform_specific.preferred_status_bar_update_animation = animation;
+        self
+    }
+
+    #[inline]
+    unsafe fn with_parent_view(mut self, parent_view: *mut c_void) -> Self {
+        self.platform_specific.parent_view = NonNull::new(parent_view).map(ParentView);
+        self
+    }
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to iOS.
@@ -337,6 +617,51 @@ pub trait MonitorHandleExtIOS {
     ///
     /// This translates to a call to [`-[UIScreen preferredMode]`](https://developer.apple.com/documentation/uikit/uiscreen/1617823-preferredmode?language=objc).
     fn preferred_video_mode(&self) -> VideoModeHandle;
+
+    /// Returns the [`VideoModeHandle`] this monitor currently uses.
+    ///
+    /// This translates to a call to [`-[UIScreen currentMode]`](https://developer.apple.com/documentation/uikit/uiscreen/1617829-currentmode?language=objc).
+    fn current_video_mode(&self) -> Option<VideoModeHandle>;
+
+    /// Sets the video mode of this monitor to one of its [`video_modes`].
+    ///
+    /// This sets [`-[UIScreen currentMode]`](https://developer.apple.com/documentation/uikit/uiscreen/1617829-currentmode?language=objc)
+    /// and disables overscan compensation, so that the mode fills the whole display.
+    /// [`ApplicationHandler::monitor_video_mode_changed`] is emitted once the mode was applied.
+    ///
+    /// Returns [`NotSupportedError`] for the built-in display, whose mode can't be changed, and
+    /// for video modes of other monitors.
+    ///
+    /// [`video_modes`]: MonitorHandle::video_modes
+    /// [`ApplicationHandler::monitor_video_mode_changed`]: crate::application::ApplicationHandler::monitor_video_mode_changed
+    fn set_video_mode(&self, video_mode: &VideoModeHandle) -> Result<(), NotSupportedError>;
+
+    /// Returns the color gamut of this monitor.
+    ///
+    /// This is the [`displayGamut`] of the screen's trait collection, and is always
+    /// [`DisplayGamut::Srgb`] when the gamut is unknown, e.g. before iOS 10.
+    ///
+    /// [`displayGamut`]: https://developer.apple.com/documentation/uikit/uitraitcollection/1648371-displaygamut?language=objc
+    fn display_gamut(&self) -> DisplayGamut;
+
+    /// Returns the headroom above SDR white this monitor can currently display extended dynamic
+    /// range content with, as a multiple of SDR white.
+    ///
+    /// [`WindowEvent::EdrHeadroomChanged`] is emitted to the windows on this monitor when this
+    /// changes. Returns `None` before iOS 16.
+    ///
+    /// This translates to a call to [`-[UIScreen currentEDRHeadroom]`](https://developer.apple.com/documentation/uikit/uiscreen/3974283-currentedrheadroom?language=objc).
+    ///
+    /// [`WindowEvent::EdrHeadroomChanged`]: crate::event::WindowEvent::EdrHeadroomChanged
+    fn edr_headroom(&self) -> Option<f32>;
+
+    /// Returns the maximum headroom above SDR white this monitor can display extended dynamic
+    /// range content with, as a multiple of SDR white.
+    ///
+    /// Returns `None` before iOS 16.
+    ///
+    /// This translates to a call to [`-[UIScreen potentialEDRHeadroom]`](https://developer.apple.com/documentation/uikit/uiscreen/3974284-potentialedrheadroom?language=objc).
+    fn max_potential_edr_headroom(&self) -> Option<f32>;
 }
 
 impl MonitorHandleExtIOS for MonitorHandle {
@@ -351,6 +676,81 @@ impl MonitorHandleExtIOS for MonitorHandle {
     fn preferred_video_mode(&self) -> VideoModeHandle {
         VideoModeHandle { video_mode: self.inner.preferred_video_mode() }
     }
+
+    #[inline]
+    fn current_video_mode(&self) -> Option<VideoModeHandle> {
+        self.inner.current_video_mode().map(|video_mode| VideoModeHandle { video_mode })
+    }
+
+    #[inline]
+    fn set_video_mode(&self, video_mode: &VideoModeHandle) -> Result<(), NotSupportedError> {
+        self.inner.set_video_mode(&video_mode.video_mode)
+    }
+
+    #[inline]
+    fn display_gamut(&self) -> DisplayGamut {
+        self.inner.display_gamut()
+    }
+
+    #[inline]
+    fn edr_headroom(&self) -> Option<f32> {
+        self.inner.edr_headroom()
+    }
+
+    #[inline]
+    fn max_potential_edr_headroom(&self) -> Option<f32> {
+        self.inner.max_potential_edr_headroom()
+    }
+}
+
+/// Additional methods on [`ActiveEventLoop`] that are specific to iOS.
+pub trait ActiveEventLoopExtIOS {
+    /// Returns whether the user has enabled Low Power Mode.
+    ///
+    /// [`ApplicationHandler::power_state_changed`] is called when this changes.
+    ///
+    /// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1617047-lowpowermodeenabled>
+    ///
+    /// [`ApplicationHandler::power_state_changed`]: crate::application::ApplicationHandler::power_state_changed
+    fn is_low_power_mode_enabled(&self) -> bool;
+
+    /// Returns the thermal state of the device.
+    ///
+    /// [`ApplicationHandler::power_state_changed`] is called when this changes. This is always
+    /// [`ThermalState::Nominal`] before iOS 11.
+    ///
+    /// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1417480-thermalstate>
+    ///
+    /// [`ApplicationHandler::power_state_changed`]: crate::application::ApplicationHandler::power_state_changed
+    fn thermal_state(&self) -> ThermalState;
+
+    /// Returns why the application was launched, according to the options passed to
+    /// [`application:didFinishLaunchingWithOptions:`].
+    ///
+    /// URLs and quick actions the application was launched with are delivered to
+    /// [`ApplicationHandler::urls_opened`] and [`ApplicationHandler::shortcut_item_activated`].
+    ///
+    /// [`application:didFinishLaunchingWithOptions:`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1622921-application
+    /// [`ApplicationHandler::urls_opened`]: crate::application::ApplicationHandler::urls_opened
+    /// [`ApplicationHandler::shortcut_item_activated`]: crate::application::ApplicationHandler::shortcut_item_activated
+    fn launch_options(&self) -> LaunchOptions;
+}
+
+impl ActiveEventLoopExtIOS for ActiveEventLoop {
+    #[inline]
+    fn is_low_power_mode_enabled(&self) -> bool {
+        self.p.is_low_power_mode_enabled()
+    }
+
+    #[inline]
+    fn thermal_state(&self) -> ThermalState {
+        self.p.thermal_state()
+    }
+
+    #[inline]
+    fn launch_options(&self) -> LaunchOptions {
+        self.p.launch_options()
+    }
 }
 
 /// Valid orientations for a particular [`Window`].
@@ -366,6 +766,25 @@ pub enum ValidOrientations {
     Portrait,
 }
 
+bitflags::bitflags! {
+    /// A set of [interface orientations].
+    ///
+    /// [interface orientations]: https://developer.apple.com/documentation/uikit/uiinterfaceorientationmask?language=objc
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct OrientationMask: u8 {
+        const PORTRAIT             = 1 << 0;
+        const PORTRAIT_UPSIDE_DOWN = 1 << 1;
+        const LANDSCAPE_LEFT       = 1 << 2;
+        const LANDSCAPE_RIGHT      = 1 << 3;
+        const LANDSCAPE = OrientationMask::LANDSCAPE_LEFT.bits()
+            | OrientationMask::LANDSCAPE_RIGHT.bits();
+        const ALL_BUT_UPSIDE_DOWN = OrientationMask::PORTRAIT.bits()
+            | OrientationMask::LANDSCAPE.bits();
+        const ALL = OrientationMask::ALL_BUT_UPSIDE_DOWN.bits()
+            | OrientationMask::PORTRAIT_UPSIDE_DOWN.bits();
+    }
+}
+
 bitflags::bitflags! {
     /// The [edges] of a screen.
     ///
@@ -382,6 +801,45 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// The reasons the application was launched for.
+    ///
+    /// An application that was launched in the background, e.g. for a location or Bluetooth
+    /// event, is not visible to the user and should avoid doing unnecessary work.
+    ///
+    /// See [`ActiveEventLoopExtIOS::launch_options`].
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct LaunchOptions: u8 {
+        /// The application was launched to open a URL.
+        const URL                   = 1 << 0;
+        /// The application was launched with a quick action from its home screen icon.
+        const SHORTCUT_ITEM         = 1 << 1;
+        /// The application was launched to continue a user activity, e.g. a universal link.
+        const USER_ACTIVITY         = 1 << 2;
+        /// The application was launched for a remote notification.
+        const REMOTE_NOTIFICATION   = 1 << 3;
+        /// The application was launched in the background for a location event.
+        const LOCATION              = 1 << 4;
+        /// The application was launched in the background to restore Bluetooth central managers.
+        const BLUETOOTH_CENTRALS    = 1 << 5;
+        /// The application was launched in the background to restore Bluetooth peripheral
+        /// managers.
+        const BLUETOOTH_PERIPHERALS = 1 << 6;
+    }
+}
+
+/// The color gamut of a monitor.
+///
+/// See [`MonitorHandleExtIOS::display_gamut`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DisplayGamut {
+    /// The sRGB color space.
+    #[default]
+    Srgb,
+    /// The Display P3 color space.
+    P3,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum StatusBarStyle {
     #[default]
@@ -389,3 +847,14 @@ pub enum StatusBarStyle {
     LightContent,
     DarkContent,
 }
+
+/// The animation used when showing or hiding the status bar.
+///
+/// See [`WindowExtIOS::set_status_bar_update_animation`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StatusBarAnimation {
+    #[default]
+    None,
+    Fade,
+    Slide,
+}
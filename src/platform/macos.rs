@@ -19,7 +19,7 @@ use std::os::raw::c_void;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::event_loop::{ActiveEventLoop, EventLoopBuilder};
+use crate::event_loop::{ActiveEventLoop, ContrastPreference, EventLoopBuilder, ThermalState};
 use crate::monitor::MonitorHandle;
 use crate::window::{Window, WindowAttributes};
 
@@ -94,6 +94,97 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_option_as_alt`].
     fn option_as_alt(&self) -> OptionAsAlt;
+
+    /// Sets whether key equivalents (key presses with <kbd>Cmd</kbd> held) are offered to the
+    /// menu bar before being delivered to the window.
+    ///
+    /// When enabled (the default), a key press consumed by a menu item (e.g. <kbd>Cmd</kbd> +
+    /// <kbd>Q</kbd> with the default menu) is not reported as [`WindowEvent::KeyboardInput`].
+    /// Key presses that no menu item handles are reported as usual.
+    ///
+    /// When disabled, the menu bar never sees key equivalents sent to this window. Every such
+    /// key press is reported exactly once as [`WindowEvent::KeyboardInput`], and AppKit does not
+    /// play the "unhandled key" sound. This also applies to the shortcuts installed by the
+    /// default menu, such as <kbd>Cmd</kbd> + <kbd>H</kbd> and <kbd>Cmd</kbd> + <kbd>Q</kbd>.
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn set_key_equivalents_handled_by_menu(&self, handled_by_menu: bool);
+
+    /// Getter for the [`WindowExtMacOS::set_key_equivalents_handled_by_menu`].
+    fn key_equivalents_handled_by_menu(&self) -> bool;
+
+    /// Sets how the window behaves with regards to Spaces, Exposé and fullscreen.
+    ///
+    /// The behavior is applied immediately, and is re-applied after the window enters or exits
+    /// fullscreen, since AppKit may modify it during the transition.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/1419471-collectionbehavior>
+    fn set_collection_behavior(&self, behavior: CollectionBehavior);
+
+    /// Returns the window's current [`CollectionBehavior`].
+    fn collection_behavior(&self) -> CollectionBehavior;
+
+    /// Sets whether the window moves to the active Space when it is made key, instead of the
+    /// user being switched to the Space the window is on.
+    ///
+    /// This is a shorthand for toggling [`CollectionBehavior::MOVE_TO_ACTIVE_SPACE`] with
+    /// [`WindowExtMacOS::set_collection_behavior`]. Since the two are mutually exclusive, enabling
+    /// it also removes [`CollectionBehavior::CAN_JOIN_ALL_SPACES`].
+    fn set_moves_to_active_space(&self, moves_to_active_space: bool);
+
+    /// Sets the name used to automatically save the window's frame in the user defaults.
+    ///
+    /// AppKit saves the frame whenever it changes, see
+    /// [`WindowAttributesExtMacOS::with_frame_autosave_name`] for restoring it on creation.
+    /// Setting an empty name stops saving the frame.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/1419509-setframeautosavename>
+    fn set_frame_autosave_name(&self, name: &str);
+
+    /// Sets whether mouse events over the titlebar are delivered to the window.
+    ///
+    /// This is useful together with [`WindowAttributesExtMacOS::with_fullsize_content_view`] and
+    /// a transparent titlebar, to draw custom controls (e.g. tabs) in the titlebar area. Clicks on
+    /// the window buttons are still handled by AppKit.
+    ///
+    /// While enabled, clicking the titlebar doesn't move the window anymore. Use
+    /// [`Window::drag_window`] to start moving the window, and
+    /// [`WindowExtMacOS::perform_titlebar_double_click_action`] to handle double clicks on empty
+    /// parts of the titlebar.
+    fn set_titlebar_pointer_events(&self, titlebar_pointer_events: bool);
+
+    /// Getter for the [`WindowExtMacOS::set_titlebar_pointer_events`].
+    fn titlebar_pointer_events(&self) -> bool;
+
+    /// Performs the action the user configured for double clicks on a window's titlebar, which is
+    /// zooming the window by default.
+    fn perform_titlebar_double_click_action(&self);
+
+    /// Sets how [`Fullscreen::Borderless`] is implemented, see [`FullscreenStyle`].
+    ///
+    /// If the window is currently in borderless fullscreen, it transitions to the new style.
+    ///
+    /// [`Fullscreen::Borderless`]: crate::window::Fullscreen::Borderless
+    fn set_fullscreen_style(&self, style: FullscreenStyle);
+
+    /// Getter for the [`WindowExtMacOS::set_fullscreen_style`].
+    fn fullscreen_style(&self) -> FullscreenStyle;
+
+    /// Sets the presentation options used while the window is in borderless fullscreen.
+    ///
+    /// The options are applied while the window is both fullscreen and the key window, and
+    /// reverted when it resigns key or leaves fullscreen. When several windows are fullscreen,
+    /// the options of the key window win.
+    ///
+    /// By default, Winit uses the options chosen by AppKit for native fullscreen, and hides the
+    /// dock and menu bar for [`FullscreenStyle::Fast`]. The options don't apply in exclusive
+    /// fullscreen, where the dock and menu bar are always hidden.
+    fn set_fullscreen_presentation_options(&self, options: PresentationOptions);
+
+    /// Getter for the [`WindowExtMacOS::set_fullscreen_presentation_options`].
+    ///
+    /// Returns `None` if the options were never set.
+    fn fullscreen_presentation_options(&self) -> Option<PresentationOptions>;
 }
 
 impl WindowExtMacOS for Window {
@@ -166,6 +257,73 @@ impl WindowExtMacOS for Window {
     fn option_as_alt(&self) -> OptionAsAlt {
         self.window.maybe_wait_on_main(|w| w.option_as_alt())
     }
+
+    #[inline]
+    fn set_key_equivalents_handled_by_menu(&self, handled_by_menu: bool) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_key_equivalents_handled_by_menu(handled_by_menu))
+    }
+
+    #[inline]
+    fn key_equivalents_handled_by_menu(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| w.key_equivalents_handled_by_menu())
+    }
+
+    #[inline]
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        self.window.maybe_queue_on_main(move |w| w.set_collection_behavior(behavior))
+    }
+
+    #[inline]
+    fn collection_behavior(&self) -> CollectionBehavior {
+        self.window.maybe_wait_on_main(|w| w.collection_behavior())
+    }
+
+    #[inline]
+    fn set_moves_to_active_space(&self, moves_to_active_space: bool) {
+        self.window.maybe_queue_on_main(move |w| w.set_moves_to_active_space(moves_to_active_space))
+    }
+
+    #[inline]
+    fn set_frame_autosave_name(&self, name: &str) {
+        self.window.maybe_wait_on_main(|w| w.set_frame_autosave_name(name))
+    }
+
+    #[inline]
+    fn set_titlebar_pointer_events(&self, titlebar_pointer_events: bool) {
+        self.window
+            .maybe_queue_on_main(move |w| w.set_titlebar_pointer_events(titlebar_pointer_events))
+    }
+
+    #[inline]
+    fn titlebar_pointer_events(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| w.titlebar_pointer_events())
+    }
+
+    #[inline]
+    fn perform_titlebar_double_click_action(&self) {
+        self.window.maybe_queue_on_main(|w| w.perform_titlebar_double_click_action())
+    }
+
+    #[inline]
+    fn set_fullscreen_style(&self, style: FullscreenStyle) {
+        self.window.maybe_queue_on_main(move |w| w.set_fullscreen_style(style))
+    }
+
+    #[inline]
+    fn fullscreen_style(&self) -> FullscreenStyle {
+        self.window.maybe_wait_on_main(|w| w.fullscreen_style())
+    }
+
+    #[inline]
+    fn set_fullscreen_presentation_options(&self, options: PresentationOptions) {
+        self.window.maybe_queue_on_main(move |w| w.set_fullscreen_presentation_options(options))
+    }
+
+    #[inline]
+    fn fullscreen_presentation_options(&self) -> Option<PresentationOptions> {
+        self.window.maybe_wait_on_main(|w| w.fullscreen_presentation_options())
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -208,6 +366,10 @@ pub trait WindowAttributesExtMacOS {
     fn with_has_shadow(self, has_shadow: bool) -> Self;
     /// Window accepts click-through mouse events.
     fn with_accepts_first_mouse(self, accepts_first_mouse: bool) -> Self;
+    /// Window receives mouse events over the titlebar.
+    ///
+    /// See [`WindowExtMacOS::set_titlebar_pointer_events`] for details.
+    fn with_titlebar_pointer_events(self, titlebar_pointer_events: bool) -> Self;
     /// Defines the window tabbing identifier.
     ///
     /// <https://developer.apple.com/documentation/appkit/nswindow/1644704-tabbingidentifier>
@@ -216,6 +378,25 @@ pub trait WindowAttributesExtMacOS {
     ///
     /// See [`WindowExtMacOS::set_option_as_alt`] for details on what this means if set.
     fn with_option_as_alt(self, option_as_alt: OptionAsAlt) -> Self;
+    /// Set whether key equivalents are offered to the menu bar.
+    ///
+    /// See [`WindowExtMacOS::set_key_equivalents_handled_by_menu`] for details.
+    fn with_key_equivalents_handled_by_menu(self, handled_by_menu: bool) -> Self;
+    /// Restore the window's frame from, and automatically save it to, the user defaults under
+    /// the given name.
+    ///
+    /// The saved frame is only restored when neither [`WindowAttributes::with_position`] nor
+    /// [`WindowAttributes::with_inner_size`] were given, since explicit attributes take
+    /// precedence. It is also not restored when the window is created in fullscreen.
+    ///
+    /// See [`WindowExtMacOS::set_frame_autosave_name`] for changing the name afterwards.
+    fn with_frame_autosave_name(self, name: &str) -> Self;
+    /// Set how [`Fullscreen::Borderless`] is implemented.
+    ///
+    /// See [`WindowExtMacOS::set_fullscreen_style`] for details.
+    ///
+    /// [`Fullscreen::Borderless`]: crate::window::Fullscreen::Borderless
+    fn with_fullscreen_style(self, style: FullscreenStyle) -> Self;
 }
 
 impl WindowAttributesExtMacOS for WindowAttributes {
@@ -273,6 +454,12 @@ impl WindowAttributesExtMacOS for WindowAttributes {
         self
     }
 
+    #[inline]
+    fn with_titlebar_pointer_events(mut self, titlebar_pointer_events: bool) -> Self {
+        self.platform_specific.titlebar_pointer_events = titlebar_pointer_events;
+        self
+    }
+
     #[inline]
     fn with_tabbing_identifier(mut self, tabbing_identifier: &str) -> Self {
         self.platform_specific.tabbing_identifier.replace(tabbing_identifier.to_string());
@@ -284,6 +471,24 @@ impl WindowAttributesExtMacOS for WindowAttributes {
         self.platform_specific.option_as_alt = option_as_alt;
         self
     }
+
+    #[inline]
+    fn with_key_equivalents_handled_by_menu(mut self, handled_by_menu: bool) -> Self {
+        self.platform_specific.key_equivalents_handled_by_menu = handled_by_menu;
+        self
+    }
+
+    #[inline]
+    fn with_frame_autosave_name(mut self, name: &str) -> Self {
+        self.platform_specific.frame_autosave_name = Some(name.to_owned());
+        self
+    }
+
+    #[inline]
+    fn with_fullscreen_style(mut self, style: FullscreenStyle) -> Self {
+        self.platform_specific.fullscreen_style = style;
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -364,6 +569,26 @@ pub trait MonitorHandleExtMacOS {
     fn native_id(&self) -> u32;
     /// Returns a pointer to the NSScreen representing this monitor.
     fn ns_screen(&self) -> Option<*mut c_void>;
+
+    /// Returns the current extended dynamic range (EDR) headroom of the monitor.
+    ///
+    /// This is the maximum color component value that can currently be displayed, and may change
+    /// over time, for example when the display's brightness is adjusted. See
+    /// [`WindowEvent::EdrHeadroomChanged`] for how to get notified of changes.
+    ///
+    /// Returns `1.0` on displays that don't support EDR.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsscreen/3180381-maximumextendeddynamicrangecolor>
+    ///
+    /// [`WindowEvent::EdrHeadroomChanged`]: crate::event::WindowEvent::EdrHeadroomChanged
+    fn edr_headroom(&self) -> f32;
+
+    /// Returns the maximum extended dynamic range (EDR) headroom the monitor could ever provide.
+    ///
+    /// Returns `1.0` on displays that don't support EDR.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsscreen/3180382-maximumpotentialextendeddynamic>
+    fn max_potential_edr_headroom(&self) -> f32;
 }
 
 impl MonitorHandleExtMacOS for MonitorHandle {
@@ -377,6 +602,16 @@ impl MonitorHandleExtMacOS for MonitorHandle {
         let mtm = unsafe { objc2_foundation::MainThreadMarker::new_unchecked() };
         self.inner.ns_screen(mtm).map(|s| objc2::rc::Retained::as_ptr(&s) as _)
     }
+
+    #[inline]
+    fn edr_headroom(&self) -> f32 {
+        self.inner.edr_headroom()
+    }
+
+    #[inline]
+    fn max_potential_edr_headroom(&self) -> f32 {
+        self.inner.max_potential_edr_headroom()
+    }
 }
 
 /// Additional methods on [`ActiveEventLoop`] that are specific to macOS.
@@ -393,6 +628,45 @@ pub trait ActiveEventLoopExtMacOS {
     fn set_allows_automatic_window_tabbing(&self, enabled: bool);
     /// Returns whether the system can automatically organize windows into tabs.
     fn allows_automatic_window_tabbing(&self) -> bool;
+    /// Returns whether the user has enabled "Reduce motion" in the accessibility settings.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed`] is called when this changes.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsworkspace/1644069-accessibilitydisplayshouldreduce>
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    fn should_reduce_motion(&self) -> bool;
+    /// Returns whether the user has enabled "Increase contrast" in the accessibility settings.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsworkspace/1526290-accessibilitydisplayshouldincrea>
+    fn should_increase_contrast(&self) -> bool;
+    /// Returns the contrast the user prefers, derived from
+    /// [`should_increase_contrast()`](Self::should_increase_contrast).
+    ///
+    /// This is either [`ContrastPreference::More`] or [`ContrastPreference::NoPreference`], and
+    /// allows sharing code with the web backend.
+    fn contrast_preference(&self) -> ContrastPreference;
+    /// Returns whether the user has enabled "Reduce transparency" in the accessibility settings.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nsworkspace/1533006-accessibilitydisplayshouldreduce>
+    fn should_reduce_transparency(&self) -> bool;
+    /// Returns whether the user has enabled Low Power Mode. This is always `false` before macOS
+    /// 12.
+    ///
+    /// [`ApplicationHandler::power_state_changed`] is called when this changes.
+    ///
+    /// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1617047-lowpowermodeenabled>
+    ///
+    /// [`ApplicationHandler::power_state_changed`]: crate::application::ApplicationHandler::power_state_changed
+    fn is_low_power_mode_enabled(&self) -> bool;
+    /// Returns the thermal state of the system.
+    ///
+    /// [`ApplicationHandler::power_state_changed`] is called when this changes.
+    ///
+    /// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1417480-thermalstate>
+    ///
+    /// [`ApplicationHandler::power_state_changed`]: crate::application::ApplicationHandler::power_state_changed
+    fn thermal_state(&self) -> ThermalState;
 }
 
 impl ActiveEventLoopExtMacOS for ActiveEventLoop {
@@ -411,6 +685,108 @@ impl ActiveEventLoopExtMacOS for ActiveEventLoop {
     fn allows_automatic_window_tabbing(&self) -> bool {
         self.p.allows_automatic_window_tabbing()
     }
+
+    fn should_reduce_motion(&self) -> bool {
+        self.p.should_reduce_motion()
+    }
+
+    fn should_increase_contrast(&self) -> bool {
+        self.p.should_increase_contrast()
+    }
+
+    fn contrast_preference(&self) -> ContrastPreference {
+        if self.p.should_increase_contrast() {
+            ContrastPreference::More
+        } else {
+            ContrastPreference::NoPreference
+        }
+    }
+
+    fn should_reduce_transparency(&self) -> bool {
+        self.p.should_reduce_transparency()
+    }
+
+    fn is_low_power_mode_enabled(&self) -> bool {
+        self.p.is_low_power_mode_enabled()
+    }
+
+    fn thermal_state(&self) -> ThermalState {
+        self.p.thermal_state()
+    }
+}
+
+bitflags::bitflags! {
+    /// How a window behaves with regards to Spaces, Exposé and fullscreen.
+    ///
+    /// Corresponds to [`NSWindowCollectionBehavior`].
+    ///
+    /// [`NSWindowCollectionBehavior`]: https://developer.apple.com/documentation/appkit/nswindowcollectionbehavior?language=objc
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct CollectionBehavior: u32 {
+        /// The window appears in all Spaces.
+        const CAN_JOIN_ALL_SPACES = 1 << 0;
+        /// Making the window active moves it to the active Space.
+        const MOVE_TO_ACTIVE_SPACE = 1 << 1;
+        /// The window participates in Spaces and Exposé.
+        const MANAGED = 1 << 2;
+        /// The window floats in Spaces and is hidden by Exposé.
+        const TRANSIENT = 1 << 3;
+        /// The window is unaffected by Exposé.
+        const STATIONARY = 1 << 4;
+        /// The window participates in the window cycle for use with the Cycle Through Windows
+        /// menu item.
+        const PARTICIPATES_IN_CYCLE = 1 << 5;
+        /// The window isn't part of the window cycle.
+        const IGNORES_CYCLE = 1 << 6;
+        /// The window can enter fullscreen on its own Space.
+        const FULL_SCREEN_PRIMARY = 1 << 7;
+        /// The window can be shown on the same Space as a fullscreen window.
+        const FULL_SCREEN_AUXILIARY = 1 << 8;
+        /// The window doesn't support fullscreen.
+        const FULL_SCREEN_NONE = 1 << 9;
+        /// The window can be a secondary fullscreen tile, even if it can't be a fullscreen
+        /// window itself.
+        const FULL_SCREEN_ALLOWS_TILING = 1 << 11;
+        /// The window doesn't support being a fullscreen tile window.
+        const FULL_SCREEN_DISALLOWS_TILING = 1 << 12;
+    }
+}
+
+bitflags::bitflags! {
+    /// Presentation options applied while a window is in borderless fullscreen.
+    ///
+    /// Corresponds to a subset of [`NSApplicationPresentationOptions`].
+    ///
+    /// [`NSApplicationPresentationOptions`]: https://developer.apple.com/documentation/appkit/nsapplication/presentationoptions?language=objc
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct PresentationOptions: u8 {
+        /// The dock is hidden, and only shown when the mouse moves to its edge of the screen.
+        const AUTO_HIDE_DOCK = 1 << 0;
+        /// The menu bar is hidden, and only shown when the mouse moves to the top of the screen.
+        const AUTO_HIDE_MENU_BAR = 1 << 1;
+        /// Cmd+Tab and Cmd+Shift+Tab don't switch to other applications.
+        const DISABLE_PROCESS_SWITCHING = 1 << 2;
+        /// The "Hide" menu item is disabled.
+        const DISABLE_HIDE_APPLICATION = 1 << 3;
+    }
+}
+
+/// How [`Fullscreen::Borderless`] is implemented.
+///
+/// The default is [`FullscreenStyle::Native`].
+///
+/// [`Fullscreen::Borderless`]: crate::window::Fullscreen::Borderless
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FullscreenStyle {
+    /// Use the native fullscreen, which moves the window to its own Space with an animation.
+    #[default]
+    Native,
+    /// Resize the window to cover the screen, and hide the menu bar and the dock.
+    ///
+    /// This doesn't create a new Space and takes effect immediately, which is useful for
+    /// applications that toggle fullscreen frequently, like games.
+    Fast,
 }
 
 /// Option as alt behavior.
@@ -4,6 +4,8 @@
 
 #[cfg(any(android_platform, docsrs))]
 pub mod android;
+#[cfg(any(headless_platform, docsrs))]
+pub mod headless;
 #[cfg(any(ios_platform, docsrs))]
 pub mod ios;
 #[cfg(any(macos_platform, docsrs))]
@@ -27,6 +29,7 @@ pub mod x11;
     android_platform,
     x11_platform,
     wayland_platform,
+    headless_platform,
     docsrs,
 ))]
 pub mod run_on_demand;
@@ -34,9 +37,11 @@ pub mod run_on_demand;
 #[cfg(any(
     windows_platform,
     macos_platform,
+    ios_platform,
     android_platform,
     x11_platform,
     wayland_platform,
+    headless_platform,
     docsrs,
 ))]
 pub mod pump_events;
@@ -46,10 +51,18 @@ pub mod pump_events;
     macos_platform,
     x11_platform,
     wayland_platform,
+    headless_platform,
     orbital_platform,
     docsrs
 ))]
 pub mod modifier_supplement;
 
-#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform, docsrs))]
+#[cfg(any(
+    windows_platform,
+    macos_platform,
+    x11_platform,
+    wayland_platform,
+    headless_platform,
+    docsrs
+))]
 pub mod scancode;
@@ -1,7 +1,11 @@
-use std::time::Duration;
+use std::any::Any;
+use std::time::{Duration, Instant};
 
-use crate::application::ApplicationHandler;
-use crate::event_loop::EventLoop;
+use crate::application::{ApplicationHandler, UserEventHandler};
+use crate::event::{DeviceEvent, DeviceId, SessionEvent, ShortcutItem, StartCause, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, EventLoop};
+use crate::monitor::MonitorHandle;
+use crate::window::WindowId;
 
 /// Additional methods on [`EventLoop`] for pumping events within an external event loop
 pub trait EventLoopExtPumpEvents {
@@ -14,7 +18,8 @@ pub trait EventLoopExtPumpEvents {
     ///
     /// Passing a `timeout` of `Some(Duration::ZERO)` would ensure your external
     /// event loop is never blocked but you would likely need to consider how
-    /// to throttle your own external loop.
+    /// to throttle your own external loop. Only the events that are already
+    /// pending are dispatched then, and the call returns even if there are none.
     ///
     /// Passing a `timeout` of `None` means that it may wait indefinitely for new
     /// events before returning control back to the external loop.
@@ -66,6 +71,7 @@ pub trait EventLoopExtPumpEvents {
     /// - Windows
     /// - Linux
     /// - MacOS
+    /// - iOS
     /// - Android
     ///
     /// ## Unsupported Platforms
@@ -75,8 +81,6 @@ pub trait EventLoopExtPumpEvents {
     /// loop that would block the browser and there is nothing that can be
     /// polled to ask for new new events. Events are delivered via callbacks based
     /// on an event loop that is internal to the browser itself.
-    /// - **iOS:** It's not possible to stop and start an `NSApplication` repeatedly on iOS so
-    /// there's no way to support the same approach to polling as on MacOS.
     ///
     /// ## Platform-specific
     ///
@@ -99,11 +103,38 @@ pub trait EventLoopExtPumpEvents {
     ///   If you render outside of Winit you are likely to see window resizing artifacts
     ///   since MacOS expects applications to render synchronously during any `drawRect`
     ///   callback.
+    ///
+    /// - **iOS**: This is only supported for applications that were launched by UIKit or SwiftUI
+    ///   themselves, and that embed Winit's view with
+    ///   [`WindowAttributesExtIOS::with_parent_view()`]. It's not possible to stop and start a
+    ///   `UIApplication`, so the host application always runs the main loop, and `timeout` is
+    ///   ignored.
+    ///
+    ///   Instead, the events observed while the host application runs are buffered, and
+    ///   dispatched by the next call. This includes `RedrawRequested`, so the application
+    ///   should call this once per frame, e.g. from a `CADisplayLink`.
+    ///
+    ///   [`WindowAttributesExtIOS::with_parent_view()`]:
+    /// crate::platform::ios::WindowAttributesExtIOS::with_parent_view
     fn pump_app_events<A: ApplicationHandler>(
         &mut self,
         timeout: Option<Duration>,
         app: &mut A,
     ) -> PumpStatus;
+
+    /// Pump the `EventLoop` like [`pump_app_events()`], waiting for new events until `deadline`
+    /// at most, and report what was dispatched.
+    ///
+    /// Taking a deadline instead of a timeout allows pumping several times per frame of an
+    /// external loop without accumulating the time spent between the calls. A `deadline` that
+    /// has already passed is a timeout of [`Duration::ZERO`], and `None` may wait indefinitely.
+    ///
+    /// [`pump_app_events()`]: Self::pump_app_events
+    fn pump_app_events_until<A: ApplicationHandler>(
+        &mut self,
+        deadline: Option<Instant>,
+        app: &mut A,
+    ) -> PumpReport;
 }
 
 impl EventLoopExtPumpEvents for EventLoop {
@@ -112,14 +143,175 @@ impl EventLoopExtPumpEvents for EventLoop {
         timeout: Option<Duration>,
         app: &mut A,
     ) -> PumpStatus {
-        self.event_loop.pump_app_events(timeout, app)
+        let user_events = self.user_events.clone();
+        self.event_loop.pump_app_events(timeout, &mut UserEventHandler::new(app, user_events))
+    }
+
+    fn pump_app_events_until<A: ApplicationHandler>(
+        &mut self,
+        deadline: Option<Instant>,
+        app: &mut A,
+    ) -> PumpReport {
+        let start = Instant::now();
+        let timeout = deadline.map(|deadline| deadline.saturating_duration_since(start));
+        let mut app = CountingHandler { app, window_events: 0, redraws: 0 };
+        let status = self.pump_app_events(timeout, &mut app);
+        PumpReport {
+            status,
+            window_events: app.window_events,
+            redraws: app.redraws,
+            elapsed: start.elapsed(),
+        }
     }
 }
 
 /// The return status for `pump_events`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PumpStatus {
     /// Continue running external loop.
     Continue,
     /// Exit external loop.
     Exit(i32),
 }
+
+/// What a call to [`EventLoopExtPumpEvents::pump_app_events_until()`] dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpReport {
+    /// Whether to continue running the external loop.
+    pub status: PumpStatus,
+    /// The number of [`WindowEvent`]s dispatched, including [`WindowEvent::RedrawRequested`].
+    pub window_events: usize,
+    /// The number of [`WindowEvent::RedrawRequested`] dispatched.
+    pub redraws: usize,
+    /// The time spent in the call, waiting and dispatching.
+    pub elapsed: Duration,
+}
+
+/// Counts the events dispatched to the wrapped application.
+struct CountingHandler<'a, A> {
+    app: &'a mut A,
+    window_events: usize,
+    redraws: usize,
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for CountingHandler<'_, A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        self.app.user_event(event_loop, event);
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        self.window_events += 1;
+        if matches!(event, WindowEvent::RedrawRequested) {
+            self.redraws += 1;
+        }
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[inline]
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.accessibility_settings_changed(event_loop);
+    }
+
+    #[inline]
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.power_state_changed(event_loop);
+    }
+
+    #[inline]
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        self.app.session_event(event_loop, event);
+    }
+
+    #[inline]
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.keyboard_layout_changed(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        self.app.urls_opened(event_loop, urls);
+    }
+
+    #[inline]
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        self.app.shortcut_item_activated(event_loop, item);
+    }
+}
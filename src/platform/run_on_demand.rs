@@ -1,4 +1,4 @@
-use crate::application::ApplicationHandler;
+use crate::application::{ApplicationHandler, UserEventHandler};
 use crate::error::EventLoopError;
 use crate::event_loop::{ActiveEventLoop, EventLoop};
 
@@ -50,7 +50,6 @@ pub trait EventLoopExtRunOnDemand {
     /// - **iOS:** It's not possible to stop and start an `UIApplication` repeatedly on iOS.
     #[cfg_attr(not(web_platform), doc = "[^1]: `spawn()` is only available on `wasm` platforms.")]
     #[rustfmt::skip]
-    ///
     /// [`exit()`]: ActiveEventLoop::exit()
     /// [`set_control_flow()`]: ActiveEventLoop::set_control_flow()
     fn run_app_on_demand<A: ApplicationHandler>(
@@ -65,7 +64,8 @@ impl EventLoopExtRunOnDemand for EventLoop {
         app: &mut A,
     ) -> Result<(), EventLoopError> {
         self.event_loop.window_target().clear_exit();
-        self.event_loop.run_app_on_demand(app)
+        let user_events = self.user_events.clone();
+        self.event_loop.run_app_on_demand(&mut UserEventHandler::new(app, user_events))
     }
 }
 
@@ -62,6 +62,8 @@ impl EventLoopExtStartupNotify for ActiveEventLoop {
             crate::platform_impl::ActiveEventLoop::Wayland(_) => env::var(WAYLAND_VAR),
             #[cfg(x11_platform)]
             crate::platform_impl::ActiveEventLoop::X(_) => env::var(X11_VAR),
+            #[cfg(headless_platform)]
+            crate::platform_impl::ActiveEventLoop::Headless(_) => return None,
         }
         .ok()
         .map(ActivationToken::_new)
@@ -42,6 +42,12 @@
 //! [`WindowEvent::CursorLeft`]: crate::event::WindowEvent::CursorLeft
 //! [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
 //! [`Window::set_outer_position()`]: crate::window::Window::set_outer_position
+//!
+//! ## Rendering from a worker
+//!
+//! A canvas can be rendered to from a worker by passing it to [`WorkerCanvasShim`] on the main
+//! thread, which forwards its events to a [`WorkerCanvas`] in the worker. The event loop itself
+//! still has to run on the main thread.
 
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
@@ -51,20 +57,44 @@ use std::task::{Context, Poll};
 use std::time::Duration;
 
 #[cfg(web_platform)]
-use web_sys::HtmlCanvasElement;
+use web_sys::{HtmlCanvasElement, HtmlElement};
 
-use crate::application::ApplicationHandler;
+use crate::application::{ApplicationHandler, UserEventHandler};
 use crate::cursor::CustomCursorSource;
-use crate::event_loop::{ActiveEventLoop, EventLoop};
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::ExternalError;
+#[cfg(web_platform)]
+use crate::error::OsError;
+#[cfg(web_platform)]
+use crate::event::WindowEvent;
+use crate::event_loop::{ActiveEventLoop, ContrastPreference, EventLoop};
+use crate::keyboard::KeyCode;
 #[cfg(web_platform)]
 use crate::platform_impl::CustomCursorFuture as PlatformCustomCursorFuture;
+#[cfg(web_platform)]
+use crate::platform_impl::DroppedFile as PlatformDroppedFile;
+#[cfg(web_platform)]
+use crate::platform_impl::DroppedFileReadFuture as PlatformDroppedFileReadFuture;
+#[cfg(web_platform)]
+use crate::platform_impl::KeyboardLockFuture as PlatformKeyboardLockFuture;
+#[cfg(web_platform)]
+use crate::platform_impl::MonitorPermissionFuture as PlatformMonitorPermissionFuture;
+#[cfg(web_platform)]
+use crate::platform_impl::OrientationLockFuture as PlatformOrientationLockFuture;
 use crate::platform_impl::PlatformCustomCursorSource;
-use crate::window::{CustomCursor, Window, WindowAttributes};
+use crate::window::{
+    CustomCursor, InterfaceOrientation, OrientationLock, Window, WindowAttributes,
+};
 
 #[cfg(not(web_platform))]
 #[doc(hidden)]
 pub struct HtmlCanvasElement;
 
+#[cfg(not(web_platform))]
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct HtmlElement;
+
 pub trait WindowExtWebSys {
     /// Only returns the canvas if called from inside the window context (the
     /// main thread).
@@ -84,6 +114,276 @@ pub trait WindowExtWebSys {
     /// Some events are impossible to prevent. E.g. Firefox allows to access the native browser
     /// context menu with Shift+Rightclick.
     fn set_prevent_default(&self, prevent_default: bool);
+
+    /// Returns [`true`] if [`Window::set_title()`] also sets the title of the web page.
+    ///
+    /// See [`WindowExtWebSys::set_document_title_enabled()`] for more details.
+    fn document_title_enabled(&self) -> bool;
+
+    /// Sets whether [`Window::set_title()`] should also set `document.title`, the title of the
+    /// web page.
+    ///
+    /// Only one window can set the title of the page at a time: enabling this on a window
+    /// disables it on all other windows, and immediately sets the page title to the title of
+    /// this window.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_set_document_title()`] for the default.
+    fn set_document_title_enabled(&self, enabled: bool);
+
+    /// Returns the prefix the title of the web page alternates with while
+    /// [`UserAttentionType::Critical`] is requested.
+    ///
+    /// See [`WindowExtWebSys::set_user_attention_prefix()`] for more details.
+    ///
+    /// [`UserAttentionType::Critical`]: crate::window::UserAttentionType::Critical
+    fn user_attention_prefix(&self) -> String;
+
+    /// Sets the prefix the title of the web page alternates with while
+    /// [`UserAttentionType::Critical`] is requested with [`Window::request_user_attention()`].
+    ///
+    /// The title flashes until the page becomes visible or gains focus again, or the request is
+    /// unset, after which the title the page had before is restored.
+    ///
+    /// `"(!) "` by default.
+    ///
+    /// [`UserAttentionType::Critical`]: crate::window::UserAttentionType::Critical
+    fn set_user_attention_prefix(&self, prefix: &str);
+
+    /// Returns the URL of the favicon shown while [`UserAttentionType::Critical`] is requested.
+    ///
+    /// See [`WindowExtWebSys::set_user_attention_favicon()`] for more details.
+    ///
+    /// [`UserAttentionType::Critical`]: crate::window::UserAttentionType::Critical
+    fn user_attention_favicon(&self) -> Option<String>;
+
+    /// Sets the URL of the favicon the page shows while [`UserAttentionType::Critical`] is
+    /// requested with [`Window::request_user_attention()`]. The original favicon is restored
+    /// alongside the title. Changes only apply to the next request.
+    ///
+    /// [`None`] by default, which keeps the favicon of the page.
+    ///
+    /// [`UserAttentionType::Critical`]: crate::window::UserAttentionType::Critical
+    fn set_user_attention_favicon(&self, url: Option<&str>);
+
+    /// Sets the keys to capture while the canvas is fullscreen, using the [Keyboard Lock API].
+    ///
+    /// Captured keys, like Escape, Tab or media keys, are delivered as
+    /// [`WindowEvent::KeyboardInput`] instead of triggering their browser or system action, e.g.
+    /// exiting fullscreen. An empty slice captures all keys, [`None`] releases them. Whether
+    /// their default behavior is prevented still follows
+    /// [`WindowExtWebSys::set_prevent_default()`].
+    ///
+    /// The keys are captured again whenever [`Window::set_fullscreen()`] enters fullscreen. Keys
+    /// that don't exist on the web are ignored.
+    ///
+    /// The returned future resolves once the browser locked the keys, it doesn't have to be
+    /// polled for the keys to be captured. It resolves to [`ExternalError::NotSupported`] if the
+    /// browser doesn't support the Keyboard Lock API, which is currently only available in
+    /// Chromium-based browsers, and to [`ExternalError::Os`] if the browser rejected the request.
+    ///
+    /// [Keyboard Lock API]: https://developer.mozilla.org/en-US/docs/Web/API/Keyboard_API#keyboard_locking
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn set_keyboard_lock(&self, keys: Option<&[KeyCode]>) -> KeyboardLockFuture;
+
+    /// Sets the element [`Window::set_fullscreen()`] makes fullscreen instead of the canvas, e.g.
+    /// a container of the canvas with HTML elements overlaid on top of it. [`None`] makes the
+    /// canvas itself fullscreen.
+    ///
+    /// [`Window::fullscreen()`] reports fullscreen while either this element or the canvas is
+    /// fullscreen.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_fullscreen_element()`] for the default.
+    fn set_fullscreen_element(&self, element: Option<FullscreenElement>);
+
+    /// Returns whether the browser shows its navigation UI while fullscreen.
+    ///
+    /// See [`WindowExtWebSys::set_fullscreen_navigation_ui()`] for more details.
+    fn fullscreen_navigation_ui(&self) -> FullscreenNavigationUi;
+
+    /// Sets whether the browser should show its navigation UI while fullscreen. Changes only
+    /// apply the next time [`Window::set_fullscreen()`] enters fullscreen.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_fullscreen_navigation_ui()`] for the default.
+    fn set_fullscreen_navigation_ui(&self, navigation_ui: FullscreenNavigationUi);
+
+    /// Returns the timestamp the browser passed to the last [`requestAnimationFrame()`] callback
+    /// of this window, or [`None`] if none ran yet.
+    ///
+    /// While handling a [`WindowEvent::RedrawRequested`] caused by [`Window::request_redraw()`],
+    /// this is the time of the frame being drawn, which should be used for frame pacing instead of
+    /// reading the current time again. The duration is measured from [`performance.timeOrigin`],
+    /// the same time origin as `performance.now()`. All windows redrawn in the same frame
+    /// receive the same timestamp.
+    ///
+    /// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestAnimationFrame
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`performance.timeOrigin`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin
+    fn last_frame_time(&self) -> Option<Duration>;
+
+    /// Returns [`true`] if the browser asks for confirmation before the page is closed.
+    ///
+    /// See [`WindowExtWebSys::set_confirm_close()`] for more details.
+    fn confirm_close(&self) -> bool;
+
+    /// Sets whether the browser should ask the user for confirmation before the page is closed
+    /// or navigated away from, e.g. to warn about unsaved changes.
+    ///
+    /// This can be set while handling [`WindowEvent::CloseRequested`]. It only has an effect if
+    /// the window was created with [`WindowAttributesExtWebSys::with_close_requested_on_unload()`].
+    /// Browsers show their own message and might not ask if the user never interacted with the
+    /// page.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    fn set_confirm_close(&self, confirm_close: bool);
+
+    /// Returns the files of the last drop onto the canvas.
+    ///
+    /// Browsers don't expose paths to files, the [`DragDropEvent::Dropped`] event of a drop only
+    /// contains the file names. This should be called while handling it to get access to the
+    /// content of the files. Dropped directories are skipped.
+    ///
+    /// [`DragDropEvent::Dropped`]: crate::event::DragDropEvent::Dropped
+    fn dropped_files(&self) -> Vec<DroppedFile>;
+
+    /// Returns the part of the page that is visible on screen, which differs from the browser
+    /// window while the user pinch-zooms, or [`None`] if the browser doesn't support
+    /// [`visualViewport`].
+    ///
+    /// Unlike with other platforms, the position of events isn't affected by pinch-zoom, but the
+    /// visible area can be used to e.g. keep UI elements on screen.
+    ///
+    /// [`visualViewport`]: https://developer.mozilla.org/en-US/docs/Web/API/VisualViewport
+    fn visual_viewport(&self) -> Option<VisualViewport>;
+
+    /// Locks the orientation of the screen with the [Screen Orientation API].
+    ///
+    /// Most browsers only allow this while the page is fullscreen, see
+    /// [`Window::set_fullscreen()`], and reject it with [`OrientationLockError::NotAllowed`]
+    /// otherwise. Desktop browsers usually don't support it at all. Leaving fullscreen releases
+    /// the lock.
+    ///
+    /// The returned future resolves once the screen was rotated, it doesn't have to be polled for
+    /// the orientation to be locked.
+    ///
+    /// [Screen Orientation API]: https://developer.mozilla.org/en-US/docs/Web/API/Screen_Orientation_API
+    fn lock_orientation(&self, lock: OrientationLock) -> OrientationLockFuture;
+
+    /// Releases the lock set with [`WindowExtWebSys::lock_orientation()`].
+    fn unlock_orientation(&self);
+
+    /// Returns the current orientation of the screen, or [`None`] if the browser doesn't support
+    /// the [Screen Orientation API].
+    ///
+    /// Changes are reported with [`WindowEvent::InterfaceOrientationChanged`].
+    ///
+    /// [Screen Orientation API]: https://developer.mozilla.org/en-US/docs/Web/API/Screen_Orientation_API
+    /// [`WindowEvent::InterfaceOrientationChanged`]: crate::event::WindowEvent::InterfaceOrientationChanged
+    fn interface_orientation(&self) -> Option<InterfaceOrientation>;
+
+    /// Returns the rotation of the screen from its natural orientation in degrees, or [`None`] if
+    /// the browser doesn't support the [Screen Orientation API].
+    ///
+    /// [Screen Orientation API]: https://developer.mozilla.org/en-US/docs/Web/API/Screen_Orientation_API
+    fn orientation_angle(&self) -> Option<u16>;
+
+    /// Focuses the canvas like [`Window::focus_window()`], but allows to prevent the browser from
+    /// scrolling the canvas into view, see the [`preventScroll`] option.
+    ///
+    /// [`preventScroll`]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/focus#preventscroll
+    fn focus_with_options(&self, prevent_scroll: bool);
+
+    /// Returns [`true`] if the canvas automatically focuses itself when it is clicked or touched.
+    ///
+    /// See [`WindowExtWebSys::set_focus_on_pointer_down()`] for more details.
+    fn focus_on_pointer_down(&self) -> bool;
+
+    /// Sets whether the canvas should focus itself when it is clicked or touched.
+    ///
+    /// This only has an effect while [`WindowExtWebSys::set_prevent_default()`] is enabled, as
+    /// the browser otherwise focuses the canvas itself, as long as it is focusable.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_focus_on_pointer_down()`] for the default.
+    fn set_focus_on_pointer_down(&self, focus_on_pointer_down: bool);
+
+    /// Returns [`true`] if the browser would show a focus indicator for the canvas, determined by
+    /// the [`:focus-visible`] pseudo-class when [`WindowEvent::Focused(true)`] was last emitted.
+    ///
+    /// This is usually the case if the canvas was focused with the keyboard, but not if it was
+    /// clicked. Always [`false`] in browsers that don't support `:focus-visible`.
+    ///
+    /// [`:focus-visible`]: https://developer.mozilla.org/en-US/docs/Web/CSS/:focus-visible
+    /// [`WindowEvent::Focused(true)`]: crate::event::WindowEvent::Focused
+    fn focus_visible(&self) -> bool;
+
+    /// Returns why the canvas lost focus when [`WindowEvent::Focused(false)`] was last emitted,
+    /// or [`None`] if the canvas is focused or was never focused.
+    ///
+    /// [`WindowEvent::Focused(false)`]: crate::event::WindowEvent::Focused
+    fn focus_loss(&self) -> Option<FocusLoss>;
+
+    /// Returns how [`Window::set_outer_position()`] positions the canvas.
+    ///
+    /// See [`WindowExtWebSys::set_position_mode()`] for more details.
+    fn position_mode(&self) -> PositionMode;
+
+    /// Sets how [`Window::set_outer_position()`] positions the canvas.
+    ///
+    /// A canvas positioned by winit is moved over to the new mode at its current position.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_position_mode()`] for the default.
+    fn set_position_mode(&self, mode: PositionMode);
+
+    /// Returns how the deltas of [`WindowEvent::MouseWheel`] are reported.
+    ///
+    /// See [`WindowExtWebSys::set_wheel_delta_policy()`] for more details.
+    ///
+    /// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+    fn wheel_delta_policy(&self) -> WheelDeltaPolicy;
+
+    /// Sets how the deltas of [`WindowEvent::MouseWheel`] are reported.
+    ///
+    /// Browsers report wheel deltas in pixels, lines or pages depending on the browser, device
+    /// and system settings, e.g. Firefox reports mouse wheels in lines, while Chromium reports
+    /// them in pixels.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_wheel_delta_policy()`] for the default.
+    ///
+    /// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+    fn set_wheel_delta_policy(&self, policy: WheelDeltaPolicy);
+
+    /// Returns details about the last wheel event on the canvas that weren't reported with
+    /// [`WindowEvent::MouseWheel`] or [`WindowEvent::PinchGesture`], or [`None`] if there wasn't
+    /// one yet.
+    ///
+    /// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+    /// [`WindowEvent::PinchGesture`]: crate::event::WindowEvent::PinchGesture
+    fn last_wheel_details(&self) -> Option<WheelDetails>;
+
+    /// Returns [`true`] if wheel events with `ctrlKey` set are reported as
+    /// [`WindowEvent::PinchGesture`].
+    ///
+    /// See [`WindowExtWebSys::set_wheel_pinch_gesture()`] for more details.
+    ///
+    /// [`WindowEvent::PinchGesture`]: crate::event::WindowEvent::PinchGesture
+    fn wheel_pinch_gesture(&self) -> bool;
+
+    /// Sets whether wheel events with `ctrlKey` set should be reported as
+    /// [`WindowEvent::PinchGesture`] instead of [`WindowEvent::MouseWheel`].
+    ///
+    /// Browsers report pinch gestures on trackpads as such wheel events, without the Control key
+    /// actually being pressed, see [`WheelDetails::synthesized_ctrl`]. Scrolling a mouse wheel
+    /// while holding the Control key is reported as a pinch gesture as well, similar to how
+    /// browsers zoom the page in both cases. Only [`TouchPhase::Moved`] is reported, as wheel
+    /// events don't tell when the gesture started or ended.
+    ///
+    /// See [`WindowAttributesExtWebSys::with_wheel_pinch_gesture()`] for the default.
+    ///
+    /// [`WindowEvent::PinchGesture`]: crate::event::WindowEvent::PinchGesture
+    /// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+    /// [`TouchPhase::Moved`]: crate::event::TouchPhase::Moved
+    fn set_wheel_pinch_gesture(&self, enabled: bool);
 }
 
 impl WindowExtWebSys for Window {
@@ -99,6 +399,130 @@ impl WindowExtWebSys for Window {
     fn set_prevent_default(&self, prevent_default: bool) {
         self.window.set_prevent_default(prevent_default)
     }
+
+    fn document_title_enabled(&self) -> bool {
+        self.window.document_title_enabled()
+    }
+
+    fn set_document_title_enabled(&self, enabled: bool) {
+        self.window.set_document_title_enabled(enabled)
+    }
+
+    fn user_attention_prefix(&self) -> String {
+        self.window.user_attention_prefix()
+    }
+
+    fn set_user_attention_prefix(&self, prefix: &str) {
+        self.window.set_user_attention_prefix(prefix.to_owned())
+    }
+
+    fn user_attention_favicon(&self) -> Option<String> {
+        self.window.user_attention_favicon()
+    }
+
+    fn set_user_attention_favicon(&self, url: Option<&str>) {
+        self.window.set_user_attention_favicon(url.map(str::to_owned))
+    }
+
+    fn set_keyboard_lock(&self, keys: Option<&[KeyCode]>) -> KeyboardLockFuture {
+        KeyboardLockFuture(self.window.set_keyboard_lock(keys.map(<[_]>::to_vec)))
+    }
+
+    fn set_fullscreen_element(&self, element: Option<FullscreenElement>) {
+        self.window.set_fullscreen_element(element)
+    }
+
+    fn fullscreen_navigation_ui(&self) -> FullscreenNavigationUi {
+        self.window.fullscreen_navigation_ui()
+    }
+
+    fn set_fullscreen_navigation_ui(&self, navigation_ui: FullscreenNavigationUi) {
+        self.window.set_fullscreen_navigation_ui(navigation_ui)
+    }
+
+    fn last_frame_time(&self) -> Option<Duration> {
+        self.window.last_frame_time()
+    }
+
+    fn confirm_close(&self) -> bool {
+        self.window.confirm_close()
+    }
+
+    fn set_confirm_close(&self, confirm_close: bool) {
+        self.window.set_confirm_close(confirm_close)
+    }
+
+    fn dropped_files(&self) -> Vec<DroppedFile> {
+        self.window.dropped_files().into_iter().map(DroppedFile).collect()
+    }
+
+    fn visual_viewport(&self) -> Option<VisualViewport> {
+        self.window.visual_viewport()
+    }
+
+    fn lock_orientation(&self, lock: OrientationLock) -> OrientationLockFuture {
+        OrientationLockFuture(self.window.lock_orientation(lock))
+    }
+
+    fn unlock_orientation(&self) {
+        self.window.unlock_orientation()
+    }
+
+    fn interface_orientation(&self) -> Option<InterfaceOrientation> {
+        self.window.interface_orientation()
+    }
+
+    fn orientation_angle(&self) -> Option<u16> {
+        self.window.orientation_angle()
+    }
+
+    fn focus_with_options(&self, prevent_scroll: bool) {
+        self.window.focus_with_options(prevent_scroll)
+    }
+
+    fn focus_on_pointer_down(&self) -> bool {
+        self.window.focus_on_pointer_down()
+    }
+
+    fn set_focus_on_pointer_down(&self, focus_on_pointer_down: bool) {
+        self.window.set_focus_on_pointer_down(focus_on_pointer_down)
+    }
+
+    fn focus_visible(&self) -> bool {
+        self.window.focus_visible()
+    }
+
+    fn focus_loss(&self) -> Option<FocusLoss> {
+        self.window.focus_loss()
+    }
+
+    fn position_mode(&self) -> PositionMode {
+        self.window.position_mode()
+    }
+
+    fn set_position_mode(&self, mode: PositionMode) {
+        self.window.set_position_mode(mode)
+    }
+
+    fn wheel_delta_policy(&self) -> WheelDeltaPolicy {
+        self.window.wheel_delta_policy()
+    }
+
+    fn set_wheel_delta_policy(&self, policy: WheelDeltaPolicy) {
+        self.window.set_wheel_delta_policy(policy)
+    }
+
+    fn last_wheel_details(&self) -> Option<WheelDetails> {
+        self.window.last_wheel_details()
+    }
+
+    fn wheel_pinch_gesture(&self) -> bool {
+        self.window.wheel_pinch_gesture()
+    }
+
+    fn set_wheel_pinch_gesture(&self, enabled: bool) {
+        self.window.set_wheel_pinch_gesture(enabled)
+    }
 }
 
 pub trait WindowAttributesExtWebSys {
@@ -119,16 +543,109 @@ pub trait WindowAttributesExtWebSys {
     /// Enabled by default.
     fn with_prevent_default(self, prevent_default: bool) -> Self;
 
-    /// Whether the canvas should be focusable using the tab key. This is necessary to capture
-    /// canvas keyboard events.
+    /// Whether the canvas should be focusable using the tab key, by setting its `tabindex`
+    /// attribute to `0`. This is necessary to capture canvas keyboard events.
+    ///
+    /// When disabled, the `tabindex` attribute is left untouched, so pages managing focus
+    /// themselves can set it on their own.
     ///
     /// Enabled by default.
     fn with_focusable(self, focusable: bool) -> Self;
 
+    /// Whether the canvas should focus itself when it is clicked or touched.
+    ///
+    /// See [`WindowExtWebSys::set_focus_on_pointer_down()`] for more details.
+    ///
+    /// Enabled by default.
+    fn with_focus_on_pointer_down(self, focus_on_pointer_down: bool) -> Self;
+
     /// On window creation, append the canvas element to the web page if it isn't already.
     ///
     /// Disabled by default.
     fn with_append(self, append: bool) -> Self;
+
+    /// Whether [`Window::set_title()`] should also set `document.title`, the title of the web
+    /// page. Enabling this takes it over from any other window.
+    ///
+    /// See [`WindowExtWebSys::set_document_title_enabled()`] for more details.
+    ///
+    /// Enabled by default for the first window, as long as no other window sets the title of the
+    /// page.
+    fn with_set_document_title(self, set_document_title: bool) -> Self;
+
+    /// Make the canvas fill its parent element and follow its size.
+    ///
+    /// The CSS `width` and `height` of the canvas are set to `100%`, and the size of the backing
+    /// store of the canvas, its `width` and `height` attributes, is kept equal to the size in
+    /// device pixels the canvas is displayed at. [`WindowEvent::Resized`] reports the same size,
+    /// without rounding through CSS pixels, as long as the browser supports
+    /// `devicePixelContentBoxSize`.
+    ///
+    /// [`WindowAttributes::with_inner_size()`] is ignored, and [`Window::request_inner_size()`]
+    /// returns the current size without changing it.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    fn with_auto_parent_size(self, auto_parent_size: bool) -> Self;
+
+    /// Emit [`WindowEvent::CloseRequested`] when the page is about to be closed or navigated away
+    /// from, by listening to the [`beforeunload`] event.
+    ///
+    /// See [`WindowExtWebSys::set_confirm_close()`] to ask the user for confirmation. Note that
+    /// listening to `beforeunload` can prevent browsers from putting the page into the
+    /// back/forward cache.
+    ///
+    /// Independently of this, [`WindowEvent::Destroyed`] is emitted for all windows when the page
+    /// is unloaded.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`WindowEvent::Destroyed`]: crate::event::WindowEvent::Destroyed
+    /// [`beforeunload`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/beforeunload_event
+    fn with_close_requested_on_unload(self, close_requested_on_unload: bool) -> Self;
+
+    /// Sets the element to make fullscreen instead of the canvas.
+    ///
+    /// See [`WindowExtWebSys::set_fullscreen_element()`] for more details.
+    ///
+    /// [`None`] by default.
+    fn with_fullscreen_element(self, element: Option<FullscreenElement>) -> Self;
+
+    /// Sets whether the browser should show its navigation UI while fullscreen.
+    ///
+    /// See [`WindowExtWebSys::set_fullscreen_navigation_ui()`] for more details.
+    ///
+    /// [`FullscreenNavigationUi::Auto`] by default.
+    fn with_fullscreen_navigation_ui(self, navigation_ui: FullscreenNavigationUi) -> Self;
+
+    /// Sets how [`Window::set_outer_position()`] and [`WindowAttributes::with_position()`]
+    /// position the canvas.
+    ///
+    /// See [`WindowExtWebSys::set_position_mode()`] for more details.
+    ///
+    /// [`PositionMode::Absolute`] by default.
+    fn with_position_mode(self, mode: PositionMode) -> Self;
+
+    /// Sets how the deltas of [`WindowEvent::MouseWheel`] are reported.
+    ///
+    /// See [`WindowExtWebSys::set_wheel_delta_policy()`] for more details.
+    ///
+    /// [`WheelDeltaPolicy::Raw`] by default.
+    ///
+    /// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+    fn with_wheel_delta_policy(self, policy: WheelDeltaPolicy) -> Self;
+
+    /// Sets whether wheel events with `ctrlKey` set should be reported as
+    /// [`WindowEvent::PinchGesture`].
+    ///
+    /// See [`WindowExtWebSys::set_wheel_pinch_gesture()`] for more details.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`WindowEvent::PinchGesture`]: crate::event::WindowEvent::PinchGesture
+    fn with_wheel_pinch_gesture(self, enabled: bool) -> Self;
 }
 
 impl WindowAttributesExtWebSys for WindowAttributes {
@@ -147,10 +664,55 @@ impl WindowAttributesExtWebSys for WindowAttributes {
         self
     }
 
+    fn with_focus_on_pointer_down(mut self, focus_on_pointer_down: bool) -> Self {
+        self.platform_specific.focus_on_pointer_down = focus_on_pointer_down;
+        self
+    }
+
     fn with_append(mut self, append: bool) -> Self {
         self.platform_specific.append = append;
         self
     }
+
+    fn with_set_document_title(mut self, set_document_title: bool) -> Self {
+        self.platform_specific.set_document_title = Some(set_document_title);
+        self
+    }
+
+    fn with_auto_parent_size(mut self, auto_parent_size: bool) -> Self {
+        self.platform_specific.auto_parent_size = auto_parent_size;
+        self
+    }
+
+    fn with_close_requested_on_unload(mut self, close_requested_on_unload: bool) -> Self {
+        self.platform_specific.close_requested_on_unload = close_requested_on_unload;
+        self
+    }
+
+    fn with_fullscreen_element(mut self, element: Option<FullscreenElement>) -> Self {
+        self.platform_specific.set_fullscreen_element(element);
+        self
+    }
+
+    fn with_fullscreen_navigation_ui(mut self, navigation_ui: FullscreenNavigationUi) -> Self {
+        self.platform_specific.fullscreen_navigation_ui = navigation_ui;
+        self
+    }
+
+    fn with_position_mode(mut self, mode: PositionMode) -> Self {
+        self.platform_specific.position_mode = mode;
+        self
+    }
+
+    fn with_wheel_delta_policy(mut self, policy: WheelDeltaPolicy) -> Self {
+        self.platform_specific.wheel_delta_policy = policy;
+        self
+    }
+
+    fn with_wheel_pinch_gesture(mut self, enabled: bool) -> Self {
+        self.platform_specific.wheel_pinch_gesture = enabled;
+        self
+    }
 }
 
 /// Additional methods on `EventLoop` that are specific to the web.
@@ -206,11 +768,97 @@ pub trait EventLoopExtWebSys {
     ///
     /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
     fn wait_until_strategy(&self) -> WaitUntilStrategy;
+
+    /// Sets the interval [`ControlFlow::Poll`] is slowed down to while the page is hidden, or
+    /// [`None`] to keep polling with the [`PollStrategy`]. The timer is scheduled with the
+    /// [`WaitUntilStrategy`].
+    ///
+    /// While the page is hidden, [`WindowEvent::RedrawRequested`] is held back and emitted once
+    /// per window when the page becomes visible again.
+    ///
+    /// The default is one second, which matches how much browsers throttle timers in hidden tabs.
+    ///
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn set_hidden_poll_interval(&self, interval: Option<Duration>);
+
+    /// Gets the interval [`ControlFlow::Poll`] is slowed down to while the page is hidden.
+    ///
+    /// See [`set_hidden_poll_interval()`](Self::set_hidden_poll_interval).
+    ///
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    fn hidden_poll_interval(&self) -> Option<Duration>;
+
+    /// Sets whether the scale factor includes the pinch-zoom scale of the page, reported by
+    /// [`visualViewport.scale`].
+    ///
+    /// Pinch-zoom doesn't change `devicePixelRatio`, so by default the canvas is only upscaled by
+    /// the browser and gets blurry. When enabled, [`WindowEvent::ScaleFactorChanged`] is emitted
+    /// while the user pinch-zooms and all sizes and positions in physical pixels account for the
+    /// zoom, so rendering at the reported [`Window::inner_size()`] stays sharp. With
+    /// [`WindowAttributesExtWebSys::with_auto_parent_size()`] the backing store of the canvas is
+    /// resized accordingly. Use [`WindowExtWebSys::visual_viewport()`] to find out which part of
+    /// the canvas is visible.
+    ///
+    /// This applies to all windows and has no effect in browsers without `visualViewport`.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`visualViewport.scale`]: https://developer.mozilla.org/en-US/docs/Web/API/VisualViewport/scale
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn set_pinch_zoom_scaling(&self, enabled: bool);
+
+    /// Returns [`true`] if the scale factor includes the pinch-zoom scale of the page.
+    ///
+    /// See [`set_pinch_zoom_scaling()`](Self::set_pinch_zoom_scaling).
+    fn pinch_zoom_scaling(&self) -> bool;
+
+    /// Asks the user for permission to use the [Window Management API], which exposes all
+    /// screens connected to the device.
+    ///
+    /// Until permission is granted, [`ActiveEventLoop::available_monitors()`] only returns the
+    /// screen the browser window is on, without a name or position. Afterwards, all screens are
+    /// returned, [`ApplicationHandler::monitor_connected()`] and
+    /// [`ApplicationHandler::monitor_disconnected()`] are emitted when screens are added or
+    /// removed, and [`Fullscreen::Borderless`] with a monitor shows the canvas on that screen.
+    ///
+    /// The returned future resolves to [`ExternalError::NotSupported`] if the browser doesn't
+    /// support the Window Management API, which is currently only available in Chromium-based
+    /// browsers, and to [`ExternalError::Os`] if the user denied permission. In both cases the
+    /// monitor APIs keep working as before. Browsers might require a user interaction, like a
+    /// click, before asking.
+    ///
+    /// [Window Management API]: https://developer.mozilla.org/en-US/docs/Web/API/Window_Management_API
+    /// [`ApplicationHandler::monitor_connected()`]: crate::application::ApplicationHandler::monitor_connected
+    /// [`ApplicationHandler::monitor_disconnected()`]: crate::application::ApplicationHandler::monitor_disconnected
+    /// [`Fullscreen::Borderless`]: crate::window::Fullscreen::Borderless
+    fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture;
+
+    /// Returns [`true`] if the user prefers reduced motion, reported by the
+    /// [`prefers-reduced-motion`] media query.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed()`] is emitted when this changes.
+    /// Returns [`false`] if the browser doesn't support the media query.
+    ///
+    /// [`prefers-reduced-motion`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-reduced-motion
+    /// [`ApplicationHandler::accessibility_settings_changed()`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    fn reduced_motion(&self) -> bool;
+
+    /// Returns the contrast the user prefers, reported by the [`prefers-contrast`] media query.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed()`] is emitted when this changes.
+    /// Returns [`ContrastPreference::NoPreference`] if the browser doesn't support the media
+    /// query.
+    ///
+    /// [`prefers-contrast`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-contrast
+    /// [`ApplicationHandler::accessibility_settings_changed()`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    fn contrast_preference(&self) -> ContrastPreference;
 }
 
 impl EventLoopExtWebSys for EventLoop {
     fn spawn_app<A: ApplicationHandler + 'static>(self, app: A) {
-        self.event_loop.spawn_app(app);
+        let user_events = self.user_events.clone();
+        self.event_loop.spawn_app(UserEventHandler::new(app, user_events));
     }
 
     fn set_poll_strategy(&self, strategy: PollStrategy) {
@@ -228,6 +876,34 @@ impl EventLoopExtWebSys for EventLoop {
     fn wait_until_strategy(&self) -> WaitUntilStrategy {
         self.event_loop.wait_until_strategy()
     }
+
+    fn set_hidden_poll_interval(&self, interval: Option<Duration>) {
+        self.event_loop.set_hidden_poll_interval(interval);
+    }
+
+    fn hidden_poll_interval(&self) -> Option<Duration> {
+        self.event_loop.hidden_poll_interval()
+    }
+
+    fn set_pinch_zoom_scaling(&self, enabled: bool) {
+        self.event_loop.set_pinch_zoom_scaling(enabled);
+    }
+
+    fn pinch_zoom_scaling(&self) -> bool {
+        self.event_loop.pinch_zoom_scaling()
+    }
+
+    fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture {
+        MonitorPermissionFuture(self.event_loop.request_detailed_monitor_permission())
+    }
+
+    fn reduced_motion(&self) -> bool {
+        self.event_loop.reduced_motion()
+    }
+
+    fn contrast_preference(&self) -> ContrastPreference {
+        self.event_loop.contrast_preference()
+    }
 }
 
 pub trait ActiveEventLoopExtWebSys {
@@ -259,6 +935,91 @@ pub trait ActiveEventLoopExtWebSys {
     /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
     fn wait_until_strategy(&self) -> WaitUntilStrategy;
 
+    /// Sets the interval [`ControlFlow::Poll`] is slowed down to while the page is hidden, or
+    /// [`None`] to keep polling with the [`PollStrategy`]. The timer is scheduled with the
+    /// [`WaitUntilStrategy`].
+    ///
+    /// While the page is hidden, [`WindowEvent::RedrawRequested`] is held back and emitted once
+    /// per window when the page becomes visible again.
+    ///
+    /// The default is one second, which matches how much browsers throttle timers in hidden tabs.
+    ///
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn set_hidden_poll_interval(&self, interval: Option<Duration>);
+
+    /// Gets the interval [`ControlFlow::Poll`] is slowed down to while the page is hidden.
+    ///
+    /// See [`set_hidden_poll_interval()`](Self::set_hidden_poll_interval).
+    ///
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    fn hidden_poll_interval(&self) -> Option<Duration>;
+
+    /// Sets whether the scale factor includes the pinch-zoom scale of the page, reported by
+    /// [`visualViewport.scale`].
+    ///
+    /// Pinch-zoom doesn't change `devicePixelRatio`, so by default the canvas is only upscaled by
+    /// the browser and gets blurry. When enabled, [`WindowEvent::ScaleFactorChanged`] is emitted
+    /// while the user pinch-zooms and all sizes and positions in physical pixels account for the
+    /// zoom, so rendering at the reported [`Window::inner_size()`] stays sharp. With
+    /// [`WindowAttributesExtWebSys::with_auto_parent_size()`] the backing store of the canvas is
+    /// resized accordingly. Use [`WindowExtWebSys::visual_viewport()`] to find out which part of
+    /// the canvas is visible.
+    ///
+    /// This applies to all windows and has no effect in browsers without `visualViewport`.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`visualViewport.scale`]: https://developer.mozilla.org/en-US/docs/Web/API/VisualViewport/scale
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn set_pinch_zoom_scaling(&self, enabled: bool);
+
+    /// Returns [`true`] if the scale factor includes the pinch-zoom scale of the page.
+    ///
+    /// See [`set_pinch_zoom_scaling()`](Self::set_pinch_zoom_scaling).
+    fn pinch_zoom_scaling(&self) -> bool;
+
+    /// Asks the user for permission to use the [Window Management API], which exposes all
+    /// screens connected to the device.
+    ///
+    /// Until permission is granted, [`ActiveEventLoop::available_monitors()`] only returns the
+    /// screen the browser window is on, without a name or position. Afterwards, all screens are
+    /// returned, [`ApplicationHandler::monitor_connected()`] and
+    /// [`ApplicationHandler::monitor_disconnected()`] are emitted when screens are added or
+    /// removed, and [`Fullscreen::Borderless`] with a monitor shows the canvas on that screen.
+    ///
+    /// The returned future resolves to [`ExternalError::NotSupported`] if the browser doesn't
+    /// support the Window Management API, which is currently only available in Chromium-based
+    /// browsers, and to [`ExternalError::Os`] if the user denied permission. In both cases the
+    /// monitor APIs keep working as before. Browsers might require a user interaction, like a
+    /// click, before asking.
+    ///
+    /// [Window Management API]: https://developer.mozilla.org/en-US/docs/Web/API/Window_Management_API
+    /// [`ApplicationHandler::monitor_connected()`]: crate::application::ApplicationHandler::monitor_connected
+    /// [`ApplicationHandler::monitor_disconnected()`]: crate::application::ApplicationHandler::monitor_disconnected
+    /// [`Fullscreen::Borderless`]: crate::window::Fullscreen::Borderless
+    fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture;
+
+    /// Returns [`true`] if the user prefers reduced motion, reported by the
+    /// [`prefers-reduced-motion`] media query.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed()`] is emitted when this changes.
+    /// Returns [`false`] if the browser doesn't support the media query.
+    ///
+    /// [`prefers-reduced-motion`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-reduced-motion
+    /// [`ApplicationHandler::accessibility_settings_changed()`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    fn reduced_motion(&self) -> bool;
+
+    /// Returns the contrast the user prefers, reported by the [`prefers-contrast`] media query.
+    ///
+    /// [`ApplicationHandler::accessibility_settings_changed()`] is emitted when this changes.
+    /// Returns [`ContrastPreference::NoPreference`] if the browser doesn't support the media
+    /// query.
+    ///
+    /// [`prefers-contrast`]: https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-contrast
+    /// [`ApplicationHandler::accessibility_settings_changed()`]: crate::application::ApplicationHandler::accessibility_settings_changed
+    fn contrast_preference(&self) -> ContrastPreference;
+
     /// Async version of [`ActiveEventLoop::create_custom_cursor()`] which waits until the
     /// cursor has completely finished loading.
     fn create_custom_cursor_async(&self, source: CustomCursorSource) -> CustomCursorFuture;
@@ -289,6 +1050,41 @@ impl ActiveEventLoopExtWebSys for ActiveEventLoop {
     fn wait_until_strategy(&self) -> WaitUntilStrategy {
         self.p.wait_until_strategy()
     }
+
+    #[inline]
+    fn set_hidden_poll_interval(&self, interval: Option<Duration>) {
+        self.p.set_hidden_poll_interval(interval);
+    }
+
+    #[inline]
+    fn hidden_poll_interval(&self) -> Option<Duration> {
+        self.p.hidden_poll_interval()
+    }
+
+    #[inline]
+    fn set_pinch_zoom_scaling(&self, enabled: bool) {
+        self.p.set_pinch_zoom_scaling(enabled);
+    }
+
+    #[inline]
+    fn pinch_zoom_scaling(&self) -> bool {
+        self.p.pinch_zoom_scaling()
+    }
+
+    #[inline]
+    fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture {
+        MonitorPermissionFuture(self.p.request_detailed_monitor_permission())
+    }
+
+    #[inline]
+    fn reduced_motion(&self) -> bool {
+        self.p.reduced_motion()
+    }
+
+    #[inline]
+    fn contrast_preference(&self) -> ContrastPreference {
+        self.p.contrast_preference()
+    }
 }
 
 /// Strategy used for [`ControlFlow::Poll`][crate::event_loop::ControlFlow::Poll].
@@ -438,3 +1234,350 @@ impl Display for CustomCursorError {
         }
     }
 }
+
+#[cfg(not(web_platform))]
+struct PlatformKeyboardLockFuture;
+
+/// Future returned by [`WindowExtWebSys::set_keyboard_lock()`].
+#[derive(Debug)]
+pub struct KeyboardLockFuture(pub(crate) PlatformKeyboardLockFuture);
+
+impl Future for KeyboardLockFuture {
+    type Output = Result<(), ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+#[cfg(not(web_platform))]
+struct PlatformMonitorPermissionFuture;
+
+/// Future returned by [`EventLoopExtWebSys::request_detailed_monitor_permission()`] and
+/// [`ActiveEventLoopExtWebSys::request_detailed_monitor_permission()`].
+#[derive(Debug)]
+pub struct MonitorPermissionFuture(pub(crate) PlatformMonitorPermissionFuture);
+
+impl Future for MonitorPermissionFuture {
+    type Output = Result<(), ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+#[cfg(not(web_platform))]
+struct PlatformOrientationLockFuture;
+
+/// Future returned by [`WindowExtWebSys::lock_orientation()`].
+#[derive(Debug)]
+pub struct OrientationLockFuture(pub(crate) PlatformOrientationLockFuture);
+
+impl Future for OrientationLockFuture {
+    type Output = Result<(), OrientationLockError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// An error produced by [`OrientationLockFuture`].
+#[derive(Clone, Debug)]
+pub enum OrientationLockError {
+    /// The browser or device doesn't support locking the orientation, or not to the requested
+    /// one.
+    NotSupported,
+    /// The browser didn't allow the lock, most commonly because the page isn't fullscreen.
+    NotAllowed(String),
+    /// The orientation was locked or unlocked again before the lock was applied.
+    Aborted,
+    /// Any other error reported by the browser.
+    Other(String),
+}
+
+impl Display for OrientationLockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "locking the orientation isn't supported"),
+            Self::NotAllowed(error) => write!(f, "locking the orientation isn't allowed: {error}"),
+            Self::Aborted => write!(f, "the orientation lock was aborted"),
+            Self::Other(error) => write!(f, "failed to lock the orientation: {error}"),
+        }
+    }
+}
+
+impl Error for OrientationLockError {}
+
+/// The part of the page that is visible on screen, see [`WindowExtWebSys::visual_viewport()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisualViewport {
+    /// The pinch-zoom scale, `1` if the page isn't zoomed in.
+    pub scale: f64,
+    /// The top-left corner of the visible area, relative to the canvas.
+    pub position: PhysicalPosition<f64>,
+    /// The size of the visible area.
+    pub size: PhysicalSize<f64>,
+}
+
+/// The element to make fullscreen instead of the canvas, see
+/// [`WindowExtWebSys::set_fullscreen_element()`].
+#[derive(Clone, Debug)]
+pub enum FullscreenElement {
+    /// An element, usually an ancestor of the canvas so that the canvas stays visible.
+    Element(HtmlElement),
+    /// The first element matching the CSS selector at the time fullscreen is requested. The
+    /// canvas is made fullscreen if no element matches.
+    Selector(String),
+}
+
+/// Whether the browser shows its navigation UI while fullscreen, see the [`navigationUI`]
+/// option.
+///
+/// [`navigationUI`]: https://developer.mozilla.org/en-US/docs/Web/API/Element/requestFullscreen#navigationui
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FullscreenNavigationUi {
+    /// The browser decides.
+    #[default]
+    Auto,
+    /// Keep showing the navigation UI, e.g. the address bar on mobile browsers.
+    Show,
+    /// Hide the navigation UI to give as much space as possible to the page.
+    Hide,
+}
+
+/// Why the canvas lost focus, see [`WindowExtWebSys::focus_loss()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FocusLoss {
+    /// The browser window lost focus, e.g. because the user switched to another application or
+    /// browser tab. The canvas regains focus once the browser window does.
+    Window,
+    /// The focus moved to another element of the page, or was removed from the canvas.
+    Page,
+}
+
+/// How the canvas is positioned, see [`WindowExtWebSys::set_position_mode()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PositionMode {
+    /// Sets the `left` and `top` CSS properties of the canvas, alongside `position: fixed`.
+    ///
+    /// Every change causes the browser to recompute the layout of the page, and
+    /// [`Window::outer_position()`] reads the position back from the layout.
+    #[default]
+    Absolute,
+    /// Sets `position: fixed` with `left` and `top` at `0` once, and moves the canvas with the
+    /// [`transform`] CSS property, overriding any other transform of the canvas.
+    ///
+    /// This doesn't affect the layout of the page, making it suitable for moving the canvas every
+    /// frame, e.g. while dragging. Changes are applied once per animation frame, and
+    /// [`Window::outer_position()`] returns the last requested position.
+    ///
+    /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+    Transform,
+}
+
+/// How the deltas of [`WindowEvent::MouseWheel`] are reported, see
+/// [`WindowExtWebSys::set_wheel_delta_policy()`].
+///
+/// [`WindowEvent::MouseWheel`]: crate::event::WindowEvent::MouseWheel
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WheelDeltaPolicy {
+    /// Report deltas in the unit the browser reported them in: pixels as
+    /// [`MouseScrollDelta::PixelDelta`], lines and pages as [`MouseScrollDelta::LineDelta`].
+    ///
+    /// [`MouseScrollDelta::PixelDelta`]: crate::event::MouseScrollDelta::PixelDelta
+    /// [`MouseScrollDelta::LineDelta`]: crate::event::MouseScrollDelta::LineDelta
+    #[default]
+    Raw,
+    /// Report all deltas as [`MouseScrollDelta::PixelDelta`], converting lines and pages with
+    /// the given heights in logical pixels.
+    ///
+    /// [`MouseScrollDelta::PixelDelta`]: crate::event::MouseScrollDelta::PixelDelta
+    NormalizeToPixels {
+        /// The height of a line in logical pixels.
+        line_height: f64,
+        /// The height of a page in logical pixels.
+        page_height: f64,
+    },
+}
+
+/// The unit the browser reported the deltas of a wheel event in, see [`deltaMode`].
+///
+/// [`deltaMode`]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaMode
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WheelDeltaMode {
+    Pixel,
+    Line,
+    Page,
+}
+
+/// Details about a wheel event, see [`WindowExtWebSys::last_wheel_details()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WheelDetails {
+    /// The unit the browser reported the deltas in.
+    pub delta_mode: WheelDeltaMode,
+    /// Whether `ctrlKey` was set without the Control key being pressed, which is how browsers
+    /// report pinch gestures on trackpads.
+    ///
+    /// Key presses are only known while the canvas has focus, so holding the Control key while
+    /// scrolling over an unfocused canvas is reported as synthesized as well.
+    pub synthesized_ctrl: bool,
+}
+
+#[cfg(not(web_platform))]
+#[derive(Clone, Debug, PartialEq)]
+struct PlatformDroppedFile;
+
+/// A file dropped onto a canvas, see [`WindowExtWebSys::dropped_files()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DroppedFile(pub(crate) PlatformDroppedFile);
+
+impl DroppedFile {
+    /// The name of the file, without any path.
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    /// The MIME type of the file as guessed by the browser, empty if unknown.
+    pub fn mime_type(&self) -> &str {
+        self.0.mime_type()
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.0.size()
+    }
+
+    /// Only returns the [`File`](web_sys::File) if called from the main thread.
+    #[cfg(web_platform)]
+    pub fn file(&self) -> Option<web_sys::File> {
+        self.0.file()
+    }
+
+    /// Reads the content of the file. This works from any thread.
+    pub fn read(&self) -> DroppedFileReadFuture {
+        DroppedFileReadFuture(self.0.read())
+    }
+}
+
+#[cfg(not(web_platform))]
+struct PlatformDroppedFileReadFuture;
+
+/// Future returned by [`DroppedFile::read()`].
+///
+/// Resolves to [`ExternalError::Os`] if the browser failed to read the file, e.g. because it was
+/// deleted in the meantime.
+#[derive(Debug)]
+pub struct DroppedFileReadFuture(pub(crate) PlatformDroppedFileReadFuture);
+
+impl Future for DroppedFileReadFuture {
+    type Output = Result<Vec<u8>, ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// Main thread side of a canvas that is rendered to from a worker.
+///
+/// Transfers control of the canvas to an [`OffscreenCanvas`] and posts it to the worker, which
+/// can pick it up with [`WorkerCanvas::from_message()`]. Afterwards input, focus and size changes
+/// of the canvas are forwarded to the worker for as long as the shim is alive.
+///
+/// The canvas should already be inserted into the document, so its size can be observed.
+///
+/// [`OffscreenCanvas`]: web_sys::OffscreenCanvas
+#[cfg(web_platform)]
+pub struct WorkerCanvasShim(
+    // Only kept alive to keep forwarding events.
+    #[allow(dead_code)] crate::platform_impl::WorkerCanvasShim,
+);
+
+#[cfg(web_platform)]
+impl WorkerCanvasShim {
+    /// Posts the canvas to `worker` and starts forwarding its events.
+    pub fn new(canvas: HtmlCanvasElement, worker: &web_sys::Worker) -> Result<Self, OsError> {
+        crate::platform_impl::WorkerCanvasShim::new(canvas, worker).map(Self)
+    }
+}
+
+#[cfg(web_platform)]
+impl fmt::Debug for WorkerCanvasShim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerCanvasShim").finish_non_exhaustive()
+    }
+}
+
+/// Worker side of a canvas posted by [`WorkerCanvasShim`].
+///
+/// This is not a [`Window`]: there is no [`EventLoop`] in the worker, instead events are delivered
+/// to the handler set with [`WorkerCanvas::set_event_handler()`] while the worker is idle. The
+/// application is responsible for resizing the [`OffscreenCanvas`] on [`WindowEvent::Resized`].
+///
+/// [`OffscreenCanvas`]: web_sys::OffscreenCanvas
+/// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+#[cfg(web_platform)]
+pub struct WorkerCanvas(crate::platform_impl::WorkerCanvas);
+
+#[cfg(web_platform)]
+impl WorkerCanvas {
+    /// Picks up the canvas from the `data` of a message event received by the worker.
+    ///
+    /// Returns [`None`] if the message wasn't posted by [`WorkerCanvasShim`].
+    pub fn from_message(data: &wasm_bindgen::JsValue) -> Option<Self> {
+        crate::platform_impl::WorkerCanvas::from_message(data).map(Self)
+    }
+
+    /// The canvas to render to.
+    pub fn offscreen_canvas(&self) -> &web_sys::OffscreenCanvas {
+        self.0.offscreen_canvas()
+    }
+
+    /// The size of the canvas on the page, as last reported by [`WindowEvent::Resized`].
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        self.0.inner_size()
+    }
+
+    /// The scale factor of the page the canvas is on.
+    pub fn scale_factor(&self) -> f64 {
+        self.0.scale_factor()
+    }
+
+    /// Sets the handler receiving the events of the canvas, replacing the previous one.
+    pub fn set_event_handler(&self, handler: impl FnMut(WindowEvent) + 'static) {
+        self.0.set_event_handler(Box::new(handler))
+    }
+
+    /// Requests a [`WindowEvent::RedrawRequested`] on the next animation frame of the worker.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    pub fn request_redraw(&self) {
+        self.0.request_redraw()
+    }
+}
+
+#[cfg(web_platform)]
+impl fmt::Debug for WorkerCanvas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerCanvas").finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(web_platform, feature = "rwh_06"))]
+impl rwh_06::HasWindowHandle for WorkerCanvas {
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        let raw = self.0.raw_window_handle_rwh_06();
+        // SAFETY: The `OffscreenCanvas` lives as long as `self`.
+        Ok(unsafe { rwh_06::WindowHandle::borrow_raw(raw) })
+    }
+}
+
+#[cfg(all(web_platform, feature = "rwh_06"))]
+impl rwh_06::HasDisplayHandle for WorkerCanvas {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        let raw = rwh_06::RawDisplayHandle::Web(rwh_06::WebDisplayHandle::new());
+        // SAFETY: The web display handle doesn't point to anything.
+        Ok(unsafe { rwh_06::DisplayHandle::borrow_raw(raw) })
+    }
+}
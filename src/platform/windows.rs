@@ -54,7 +54,7 @@ pub enum BackdropType {
 /// Describes a color used by Windows
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub struct Color(u32);
+pub struct Color(pub(crate) u32);
 
 impl Color {
     // Special constant only valid for the window border and therefore modeled using Option<Color>
@@ -75,6 +75,37 @@ impl Default for Color {
     }
 }
 
+/// Describes how the system composes a layered window, see
+/// [`WindowExtWindows::set_layered_mode`].
+///
+/// For a detailed explanation, see [`SetLayeredWindowAttributes docs`].
+///
+/// [`SetLayeredWindowAttributes docs`]: https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setlayeredwindowattributes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayeredMode {
+    /// The window is not layered, unless [`Window::set_opacity`] or
+    /// [`Window::set_cursor_hittest`] need it.
+    ///
+    /// [`Window::set_opacity`]: crate::window::Window::set_opacity
+    /// [`Window::set_cursor_hittest`]: crate::window::Window::set_cursor_hittest
+    #[default]
+    None,
+
+    /// Corresponds to `LWA_COLORKEY`.
+    ///
+    /// The pixels of this color are fully transparent, and the cursor events over them go to the
+    /// windows below. [`Color::SYSTEM_DEFAULT`] is not a valid key.
+    ColorKey(Color),
+
+    /// Corresponds to `LWA_ALPHA`.
+    ///
+    /// The whole window is blended with this constant alpha, which is multiplied with the
+    /// opacity set with [`Window::set_opacity`].
+    ///
+    /// [`Window::set_opacity`]: crate::window::Window::set_opacity
+    Alpha(u8),
+}
+
 /// Describes how the corners of a window should look like.
 ///
 /// For a detailed explanation, see [`DWM_WINDOW_CORNER_PREFERENCE docs`].
@@ -303,6 +334,26 @@ pub trait WindowExtWindows {
     /// Supported starting with Windows 11 Build 22000.
     fn set_corner_preference(&self, preference: CornerPreference);
 
+    /// Sets how the system composes the window as a layered window.
+    ///
+    /// The mode is applied with `SetLayeredWindowAttributes` and takes effect immediately, without
+    /// recreating the window. A constant alpha or a color key composed by the system is cheaper
+    /// than the per-pixel alpha of [`WindowAttributes::with_transparent`], and the two are not
+    /// meant to be combined. The window can't be drawn with `UpdateLayeredWindow` while a mode
+    /// is set, and windows created with
+    /// [`WindowAttributesExtWindows::with_no_redirection_bitmap`] are not composed with it.
+    ///
+    /// The pixels of a [`LayeredMode::ColorKey`] let the cursor events through whatever is set
+    /// with [`Window::set_cursor_hittest`], which still makes the whole window click-through
+    /// when disabled.
+    ///
+    /// [`WindowAttributes::with_transparent`]: crate::window::WindowAttributes::with_transparent
+    /// [`Window::set_cursor_hittest`]: crate::window::Window::set_cursor_hittest
+    fn set_layered_mode(&self, mode: LayeredMode);
+
+    /// Returns the mode set with [`WindowExtWindows::set_layered_mode`].
+    fn layered_mode(&self) -> LayeredMode;
+
     /// Get the raw window handle for this [`Window`] without checking for thread affinity.
     ///
     /// Window handles in Win32 have a property called "thread affinity" that ties them to their
@@ -407,6 +458,16 @@ impl WindowExtWindows for Window {
         self.window.set_corner_preference(preference)
     }
 
+    #[inline]
+    fn set_layered_mode(&self, mode: LayeredMode) {
+        self.window.set_layered_mode(mode)
+    }
+
+    #[inline]
+    fn layered_mode(&self) -> LayeredMode {
+        self.window.layered_mode()
+    }
+
     #[cfg(feature = "rwh_06")]
     unsafe fn window_handle_any_thread(
         &self,
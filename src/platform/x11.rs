@@ -95,7 +95,7 @@ pub trait ActiveEventLoopExtX11 {
 impl ActiveEventLoopExtX11 for ActiveEventLoop {
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.p.is_wayland()
+        self.p.is_x11()
     }
 }
 
@@ -108,7 +108,7 @@ pub trait EventLoopExtX11 {
 impl EventLoopExtX11 for EventLoop {
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.event_loop.is_wayland()
+        self.event_loop.is_x11()
     }
 }
 
@@ -0,0 +1,870 @@
+//! Calls into the Java `Activity` through JNI, for features that `android-activity` doesn't
+//! expose.
+
+use android_activity::AndroidApp;
+use jni::objects::{JObject, JObjectArray};
+use jni::strings::JNIStr;
+use jni::{jni_sig, jni_str, Env, JavaVM};
+use tracing::warn;
+
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize};
+use crate::platform::android::{
+    DisplayCutout, DisplayCutoutMode, GameMode, PipParams, SystemBarsBehavior, TrimLevel,
+};
+
+/// `Build.VERSION_CODES.M`, which introduced `Display.Mode`.
+const API_23: i32 = 23;
+/// `Build.VERSION_CODES.N`, which introduced the sustained performance mode and multi-window.
+const API_24: i32 = 24;
+/// `Build.VERSION_CODES.O`, which introduced `PictureInPictureParams`.
+const API_26: i32 = 26;
+/// `Build.VERSION_CODES.P`, which introduced display cutouts.
+const API_28: i32 = 28;
+/// `Build.VERSION_CODES.Q`, which introduced the bounding rectangles of display cutouts per edge.
+const API_29: i32 = 29;
+/// `Build.VERSION_CODES.R`, which introduced `WindowInsets.Type`.
+pub(crate) const API_30: i32 = 30;
+/// `Build.VERSION_CODES.S`, which introduced `GameManager`.
+const API_31: i32 = 31;
+
+/// `View.SYSTEM_UI_FLAG_LAYOUT_STABLE | SYSTEM_UI_FLAG_LAYOUT_HIDE_NAVIGATION |
+/// SYSTEM_UI_FLAG_LAYOUT_FULLSCREEN`, which lay out the window behind the system bars before
+/// `Window.setDecorFitsSystemWindows()` was added.
+const SYSTEM_UI_FLAGS_EDGE_TO_EDGE: i32 = 0x100 | 0x200 | 0x400;
+/// `WindowManager.LayoutParams.FLAG_KEEP_SCREEN_ON`.
+const FLAG_KEEP_SCREEN_ON: i32 = 0x80;
+
+const SYSTEM_UI_FLAG_HIDE_NAVIGATION: i32 = 0x2;
+const SYSTEM_UI_FLAG_FULLSCREEN: i32 = 0x4;
+const SYSTEM_UI_FLAG_IMMERSIVE: i32 = 0x800;
+const SYSTEM_UI_FLAG_IMMERSIVE_STICKY: i32 = 0x1000;
+const SYSTEM_UI_FLAGS_FULLSCREEN: i32 = SYSTEM_UI_FLAG_HIDE_NAVIGATION
+    | SYSTEM_UI_FLAG_FULLSCREEN
+    | SYSTEM_UI_FLAG_IMMERSIVE
+    | SYSTEM_UI_FLAG_IMMERSIVE_STICKY;
+
+/// Settings of the Java window, which are lost when the activity is recreated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct WindowSettings {
+    pub display_cutout_mode: DisplayCutoutMode,
+    pub edge_to_edge: bool,
+    /// Whether the system bars are hidden.
+    pub fullscreen: bool,
+    pub system_bars_behavior: SystemBarsBehavior,
+    pub keep_screen_on: bool,
+    pub sustained_performance_mode: bool,
+}
+
+/// The insets of the window, in physical pixels relative to the window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct WindowInsets {
+    /// The system bars and the display cutout.
+    pub safe_area: PhysicalInsets<u32>,
+    /// The on-screen keyboard.
+    pub ime: PhysicalInsets<u32>,
+    pub display_cutout: Option<DisplayCutout>,
+}
+
+/// Whether the activity is shown in one of the multi-window modes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct WindowModes {
+    pub picture_in_picture: bool,
+    pub multi_window: bool,
+}
+
+/// A mode of the display, i.e. a `Display.Mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct DisplayMode {
+    pub id: i32,
+    pub size: PhysicalSize<u32>,
+    pub refresh_rate_millihertz: u32,
+}
+
+/// Runs `f` with the `Activity` on the current thread, which is attached to the JVM if it isn't
+/// already. Returns [`None`] and logs the error if a JNI call failed.
+pub(crate) fn with_activity<T>(
+    app: &AndroidApp,
+    f: impl FnOnce(&mut Env<'_>, &JObject<'_>) -> jni::errors::Result<T>,
+) -> Option<T> {
+    // SAFETY: `android-activity` keeps the `JavaVM` and a global reference to the `Activity`
+    // alive for as long as the `AndroidApp` exists.
+    let vm = unsafe { JavaVM::from_raw(app.vm_as_ptr().cast()) };
+    vm.attach_current_thread(|env| {
+        let activity = unsafe { JObject::from_raw(env, app.activity_as_ptr().cast()) };
+        f(env, &activity)
+    })
+    .map_err(|error| warn!("JNI call on the Activity failed: {error}"))
+    .ok()
+}
+
+/// Like [`with_activity()`], but runs `f` on the Java main thread, which owns the views of the
+/// activity.
+pub(crate) fn with_activity_on_main_thread(
+    app: &AndroidApp,
+    f: impl 'static + Send + FnOnce(&mut Env<'_>, &JObject<'_>) -> jni::errors::Result<()>,
+) {
+    let app_clone = app.clone();
+    app.run_on_java_main_thread(Box::new(move || {
+        with_activity(&app_clone, f);
+    }));
+}
+
+fn window<'local>(
+    env: &mut Env<'local>,
+    activity: &JObject<'_>,
+) -> jni::errors::Result<JObject<'local>> {
+    env.call_method(activity, jni_str!("getWindow"), jni_sig!(() -> android.view.Window), &[])?.l()
+}
+
+fn decor_view<'local>(
+    env: &mut Env<'local>,
+    window: &JObject<'_>,
+) -> jni::errors::Result<JObject<'local>> {
+    env.call_method(window, jni_str!("getDecorView"), jni_sig!(() -> android.view.View), &[])?.l()
+}
+
+/// Returns [`None`] if the window isn't attached yet.
+pub(crate) fn window_insets(app: &AndroidApp) -> Option<WindowInsets> {
+    let sdk_version = app.config().sdk_version();
+    with_activity(app, |env, activity| {
+        let window = window(env, activity)?;
+        let decor_view = decor_view(env, &window)?;
+        let insets = env
+            .call_method(
+                &decor_view,
+                jni_str!("getRootWindowInsets"),
+                jni_sig!(() -> android.view.WindowInsets),
+                &[],
+            )?
+            .l()?;
+        if insets.is_null() {
+            return Ok(None);
+        }
+
+        let display_cutout = display_cutout(env, &insets, sdk_version)?;
+
+        if sdk_version >= API_30 {
+            let mut type_mask = |name| -> jni::errors::Result<i32> {
+                env.call_static_method(
+                    jni_str!("android/view/WindowInsets$Type"),
+                    name,
+                    jni_sig!("()I"),
+                    &[],
+                )?
+                .i()
+            };
+            let system_bars = type_mask(jni_str!("systemBars"))?;
+            let cutout = type_mask(jni_str!("displayCutout"))?;
+            let ime = type_mask(jni_str!("ime"))?;
+
+            // These are zero for a display cutout that the window doesn't touch, e.g. in
+            // multi-window mode.
+            return Ok(Some(WindowInsets {
+                safe_area: insets_of_type(env, &insets, system_bars | cutout)?,
+                ime: insets_of_type(env, &insets, ime)?,
+                display_cutout,
+            }));
+        }
+
+        let mut inset = |name| -> jni::errors::Result<u32> {
+            Ok(env.call_method(&insets, name, jni_sig!("()I"), &[])?.i()?.max(0) as u32)
+        };
+        let mut safe_area = PhysicalInsets::new(
+            inset(jni_str!("getSystemWindowInsetTop"))?,
+            inset(jni_str!("getSystemWindowInsetLeft"))?,
+            inset(jni_str!("getSystemWindowInsetBottom"))?,
+            inset(jni_str!("getSystemWindowInsetRight"))?,
+        );
+
+        // Before API 30 the keyboard is only included in the system window insets, the stable
+        // insets are the same without it. The keyboard is always at the bottom.
+        let ime = PhysicalInsets::new(
+            0,
+            0,
+            safe_area.bottom.saturating_sub(inset(jni_str!("getStableInsetBottom"))?),
+            0,
+        );
+        safe_area.bottom -= ime.bottom;
+
+        if sdk_version >= API_28 {
+            let cutout = env
+                .call_method(
+                    &insets,
+                    jni_str!("getDisplayCutout"),
+                    jni_sig!(() -> android.view.DisplayCutout),
+                    &[],
+                )?
+                .l()?;
+            if !cutout.is_null() {
+                let mut inset = |name| -> jni::errors::Result<u32> {
+                    Ok(env.call_method(&cutout, name, jni_sig!("()I"), &[])?.i()?.max(0) as u32)
+                };
+                safe_area.top = safe_area.top.max(inset(jni_str!("getSafeInsetTop"))?);
+                safe_area.left = safe_area.left.max(inset(jni_str!("getSafeInsetLeft"))?);
+                safe_area.bottom = safe_area.bottom.max(inset(jni_str!("getSafeInsetBottom"))?);
+                safe_area.right = safe_area.right.max(inset(jni_str!("getSafeInsetRight"))?);
+            }
+        }
+
+        Ok(Some(WindowInsets { safe_area, ime, display_cutout }))
+    })
+    .flatten()
+}
+
+/// Returns the display cutout that the window extends into, the bounding rectangles per edge are
+/// only available since API 29.
+fn display_cutout(
+    env: &mut Env<'_>,
+    insets: &JObject<'_>,
+    sdk_version: i32,
+) -> jni::errors::Result<Option<DisplayCutout>> {
+    if sdk_version < API_29 {
+        return Ok(None);
+    }
+    let cutout = env
+        .call_method(
+            insets,
+            jni_str!("getDisplayCutout"),
+            jni_sig!(() -> android.view.DisplayCutout),
+            &[],
+        )?
+        .l()?;
+    if cutout.is_null() {
+        return Ok(None);
+    }
+
+    let waterfall_insets = if sdk_version >= API_30 {
+        let insets = env
+            .call_method(
+                &cutout,
+                jni_str!("getWaterfallInsets"),
+                jni_sig!(() -> android.graphics.Insets),
+                &[],
+            )?
+            .l()?;
+        physical_insets(env, &insets)?
+    } else {
+        PhysicalInsets::new(0, 0, 0, 0)
+    };
+
+    Ok(Some(DisplayCutout {
+        top: bounding_rect(env, &cutout, jni_str!("getBoundingRectTop"))?,
+        left: bounding_rect(env, &cutout, jni_str!("getBoundingRectLeft"))?,
+        bottom: bounding_rect(env, &cutout, jni_str!("getBoundingRectBottom"))?,
+        right: bounding_rect(env, &cutout, jni_str!("getBoundingRectRight"))?,
+        waterfall_insets,
+    }))
+}
+
+/// Calls one of the `DisplayCutout.getBoundingRect*()` methods, which return an empty rectangle
+/// if there is no cutout on that edge.
+fn bounding_rect(
+    env: &mut Env<'_>,
+    cutout: &JObject<'_>,
+    name: &JNIStr,
+) -> jni::errors::Result<Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>> {
+    let rect = env.call_method(cutout, name, jni_sig!(() -> android.graphics.Rect), &[])?.l()?;
+    let mut field =
+        |name| -> jni::errors::Result<i32> { env.get_field(&rect, name, jni_sig!("I"))?.i() };
+    let left = field(jni_str!("left"))?;
+    let top = field(jni_str!("top"))?;
+    let right = field(jni_str!("right"))?;
+    let bottom = field(jni_str!("bottom"))?;
+    Ok((right > left && bottom > top).then(|| {
+        (
+            PhysicalPosition::new(left, top),
+            PhysicalSize::new((right - left) as u32, (bottom - top) as u32),
+        )
+    }))
+}
+
+/// Calls `WindowInsets.getInsets()`, which is only available since API 30.
+fn insets_of_type(
+    env: &mut Env<'_>,
+    insets: &JObject<'_>,
+    type_mask: i32,
+) -> jni::errors::Result<PhysicalInsets<u32>> {
+    let insets = env
+        .call_method(insets, jni_str!("getInsets"), jni_sig!("(I)Landroid/graphics/Insets;"), &[
+            type_mask.into(),
+        ])?
+        .l()?;
+    physical_insets(env, &insets)
+}
+
+/// Reads the fields of an `android.graphics.Insets`.
+fn physical_insets(
+    env: &mut Env<'_>,
+    insets: &JObject<'_>,
+) -> jni::errors::Result<PhysicalInsets<u32>> {
+    let mut field = |name| -> jni::errors::Result<u32> {
+        Ok(env.get_field(insets, name, jni_sig!("I"))?.i()?.max(0) as u32)
+    };
+    Ok(PhysicalInsets::new(
+        field(jni_str!("top"))?,
+        field(jni_str!("left"))?,
+        field(jni_str!("bottom"))?,
+        field(jni_str!("right"))?,
+    ))
+}
+
+/// Applies the settings to the Java window.
+pub(crate) fn apply_window_settings(app: &AndroidApp, settings: WindowSettings) {
+    let sdk_version = app.config().sdk_version();
+    with_activity_on_main_thread(app, move |env, activity| {
+        let window = window(env, activity)?;
+
+        if sdk_version >= API_28 {
+            let mode = match settings.display_cutout_mode {
+                DisplayCutoutMode::Default => 0,
+                DisplayCutoutMode::ShortEdges => 1,
+                DisplayCutoutMode::Never => 2,
+                // `LAYOUT_IN_DISPLAY_CUTOUT_MODE_ALWAYS` was only added in API 30, short edges
+                // is the closest before that.
+                DisplayCutoutMode::Always if sdk_version >= API_30 => 3,
+                DisplayCutoutMode::Always => 1,
+            };
+            let attributes = env
+                .call_method(
+                    &window,
+                    jni_str!("getAttributes"),
+                    jni_sig!("()Landroid/view/WindowManager$LayoutParams;"),
+                    &[],
+                )?
+                .l()?;
+            env.set_field(
+                &attributes,
+                jni_str!("layoutInDisplayCutoutMode"),
+                jni_sig!("I"),
+                mode.into(),
+            )?;
+            env.call_method(
+                &window,
+                jni_str!("setAttributes"),
+                jni_sig!("(Landroid/view/WindowManager$LayoutParams;)V"),
+                &[(&attributes).into()],
+            )?;
+        }
+
+        let method =
+            if settings.keep_screen_on { jni_str!("addFlags") } else { jni_str!("clearFlags") };
+        env.call_method(&window, method, jni_sig!("(I)V"), &[FLAG_KEEP_SCREEN_ON.into()])?;
+
+        if sdk_version >= API_24 {
+            env.call_method(
+                &window,
+                jni_str!("setSustainedPerformanceMode"),
+                jni_sig!("(Z)V"),
+                &[settings.sustained_performance_mode.into()],
+            )?;
+        }
+
+        if sdk_version >= API_30 {
+            env.call_method(&window, jni_str!("setDecorFitsSystemWindows"), jni_sig!("(Z)V"), &[
+                (!settings.edge_to_edge).into(),
+            ])?;
+
+            let controller = env
+                .call_method(
+                    &window,
+                    jni_str!("getInsetsController"),
+                    jni_sig!(() -> android.view.WindowInsetsController),
+                    &[],
+                )?
+                .l()?;
+            if controller.is_null() {
+                return Ok(());
+            }
+            let behavior = match settings.system_bars_behavior {
+                // `BEHAVIOR_SHOW_BARS_BY_TOUCH`, which behaves like `BEHAVIOR_DEFAULT` since
+                // API 31.
+                SystemBarsBehavior::LeanBack => 0,
+                SystemBarsBehavior::Immersive => 1,
+                SystemBarsBehavior::ImmersiveSticky => 2,
+            };
+            env.call_method(&controller, jni_str!("setSystemBarsBehavior"), jni_sig!("(I)V"), &[
+                behavior.into(),
+            ])?;
+            let system_bars = system_bars_type(env)?;
+            let method = if settings.fullscreen { jni_str!("hide") } else { jni_str!("show") };
+            env.call_method(&controller, method, jni_sig!("(I)V"), &[system_bars.into()])?;
+        } else {
+            let decor_view = decor_view(env, &window)?;
+            let flags = env
+                .call_method(&decor_view, jni_str!("getSystemUiVisibility"), jni_sig!("()I"), &[])?
+                .i()?;
+            let mut flags = flags & !(SYSTEM_UI_FLAGS_EDGE_TO_EDGE | SYSTEM_UI_FLAGS_FULLSCREEN);
+            if settings.edge_to_edge {
+                flags |= SYSTEM_UI_FLAGS_EDGE_TO_EDGE;
+            }
+            if settings.fullscreen {
+                flags |= SYSTEM_UI_FLAG_HIDE_NAVIGATION | SYSTEM_UI_FLAG_FULLSCREEN;
+                flags |= match settings.system_bars_behavior {
+                    SystemBarsBehavior::LeanBack => 0,
+                    SystemBarsBehavior::Immersive => SYSTEM_UI_FLAG_IMMERSIVE,
+                    SystemBarsBehavior::ImmersiveSticky => SYSTEM_UI_FLAG_IMMERSIVE_STICKY,
+                };
+            }
+            env.call_method(&decor_view, jni_str!("setSystemUiVisibility"), jni_sig!("(I)V"), &[
+                flags.into(),
+            ])?;
+        }
+
+        Ok(())
+    });
+}
+
+/// `WindowInsets.Type.systemBars()`, which is only available since API 30.
+fn system_bars_type(env: &mut Env<'_>) -> jni::errors::Result<i32> {
+    env.call_static_method(
+        jni_str!("android/view/WindowInsets$Type"),
+        jni_str!("systemBars"),
+        jni_sig!("()I"),
+        &[],
+    )?
+    .i()
+}
+
+/// Returns whether the system bars are hidden, which they aren't anymore after the user revealed
+/// them in [`SystemBarsBehavior::LeanBack`] or [`SystemBarsBehavior::Immersive`].
+///
+/// Returns [`None`] if the window isn't attached yet.
+pub(crate) fn system_bars_hidden(app: &AndroidApp) -> Option<bool> {
+    let sdk_version = app.config().sdk_version();
+    with_activity(app, |env, activity| {
+        let window = window(env, activity)?;
+        let decor_view = decor_view(env, &window)?;
+
+        if sdk_version >= API_30 {
+            let insets = env
+                .call_method(
+                    &decor_view,
+                    jni_str!("getRootWindowInsets"),
+                    jni_sig!(() -> android.view.WindowInsets),
+                    &[],
+                )?
+                .l()?;
+            if insets.is_null() {
+                return Ok(None);
+            }
+            let system_bars = system_bars_type(env)?;
+            let visible = env
+                .call_method(&insets, jni_str!("isVisible"), jni_sig!("(I)Z"), &[
+                    system_bars.into()
+                ])?
+                .z()?;
+            return Ok(Some(!visible));
+        }
+
+        let flags = env
+            .call_method(&decor_view, jni_str!("getSystemUiVisibility"), jni_sig!("()I"), &[])?
+            .i()?;
+        Ok(Some(flags & SYSTEM_UI_FLAG_FULLSCREEN != 0))
+    })
+    .flatten()
+}
+
+fn display<'local>(
+    env: &mut Env<'local>,
+    activity: &JObject<'_>,
+) -> jni::errors::Result<JObject<'local>> {
+    let window_manager = env
+        .call_method(
+            activity,
+            jni_str!("getWindowManager"),
+            jni_sig!(() -> android.view.WindowManager),
+            &[],
+        )?
+        .l()?;
+    env.call_method(
+        &window_manager,
+        jni_str!("getDefaultDisplay"),
+        jni_sig!(() -> android.view.Display),
+        &[],
+    )?
+    .l()
+}
+
+fn display_mode(env: &mut Env<'_>, mode: &JObject<'_>) -> jni::errors::Result<DisplayMode> {
+    let mut int = |name| -> jni::errors::Result<i32> {
+        env.call_method(mode, name, jni_sig!("()I"), &[])?.i()
+    };
+    let id = int(jni_str!("getModeId"))?;
+    let width = int(jni_str!("getPhysicalWidth"))?.max(0) as u32;
+    let height = int(jni_str!("getPhysicalHeight"))?.max(0) as u32;
+    let refresh_rate =
+        env.call_method(mode, jni_str!("getRefreshRate"), jni_sig!("()F"), &[])?.f()?;
+    Ok(DisplayMode {
+        id,
+        size: PhysicalSize::new(width, height),
+        refresh_rate_millihertz: (refresh_rate * 1000.0).round() as u32,
+    })
+}
+
+/// Returns the current mode of the display, [`None`] before API 23.
+pub(crate) fn current_display_mode(app: &AndroidApp) -> Option<DisplayMode> {
+    if app.config().sdk_version() < API_23 {
+        return None;
+    }
+    with_activity(app, |env, activity| {
+        let display = display(env, activity)?;
+        let mode = env
+            .call_method(
+                &display,
+                jni_str!("getMode"),
+                jni_sig!("()Landroid/view/Display$Mode;"),
+                &[],
+            )?
+            .l()?;
+        display_mode(env, &mode)
+    })
+}
+
+/// Returns the modes that the display supports, [`None`] before API 23.
+pub(crate) fn supported_display_modes(app: &AndroidApp) -> Option<Vec<DisplayMode>> {
+    if app.config().sdk_version() < API_23 {
+        return None;
+    }
+    with_activity(app, |env, activity| {
+        let display = display(env, activity)?;
+        let modes = env
+            .call_method(
+                &display,
+                jni_str!("getSupportedModes"),
+                jni_sig!("()[Landroid/view/Display$Mode;"),
+                &[],
+            )?
+            .l()?;
+        let modes = env.cast_local::<JObjectArray<'_>>(modes)?;
+        (0..modes.len(env)?)
+            .map(|index| {
+                let mode = modes.get_element(env, index)?;
+                display_mode(env, &mode)
+            })
+            .collect()
+    })
+}
+
+/// Returns the refresh rate of the display in mHz, which is available on all API levels.
+pub(crate) fn refresh_rate_millihertz(app: &AndroidApp) -> Option<u32> {
+    with_activity(app, |env, activity| {
+        let display = display(env, activity)?;
+        let refresh_rate =
+            env.call_method(&display, jni_str!("getRefreshRate"), jni_sig!("()F"), &[])?.f()?;
+        Ok((refresh_rate * 1000.0).round() as u32)
+    })
+}
+
+/// Returns whether the device supports the sustained performance mode, which is never the case
+/// before API 24.
+pub(crate) fn sustained_performance_mode_supported(app: &AndroidApp) -> bool {
+    if app.config().sdk_version() < API_24 {
+        return false;
+    }
+    with_activity(app, |env, activity| {
+        // `Context.POWER_SERVICE`.
+        let name = env.new_string("power")?;
+        let power_manager = env
+            .call_method(
+                activity,
+                jni_str!("getSystemService"),
+                jni_sig!((java.lang.String) -> java.lang.Object),
+                &[(&name).into()],
+            )?
+            .l()?;
+        env.call_method(
+            &power_manager,
+            jni_str!("isSustainedPerformanceModeSupported"),
+            jni_sig!("()Z"),
+            &[],
+        )?
+        .z()
+    })
+    .unwrap_or(false)
+}
+
+/// Returns whether the battery saver is enabled, which is available on all API levels.
+pub(crate) fn power_save_mode(app: &AndroidApp) -> bool {
+    with_activity(app, |env, activity| {
+        // `Context.POWER_SERVICE`.
+        let name = env.new_string("power")?;
+        let power_manager = env
+            .call_method(
+                activity,
+                jni_str!("getSystemService"),
+                jni_sig!((java.lang.String) -> java.lang.Object),
+                &[(&name).into()],
+            )?
+            .l()?;
+        env.call_method(&power_manager, jni_str!("isPowerSaveMode"), jni_sig!("()Z"), &[])?.z()
+    })
+    .unwrap_or(false)
+}
+
+/// Returns the last level that was passed to `onTrimMemory()`, which `android-activity` doesn't
+/// report with the callback.
+pub(crate) fn last_trim_level(app: &AndroidApp) -> Option<TrimLevel> {
+    let level = with_activity(app, |env, _| {
+        let info = env.new_object(
+            jni_str!("android/app/ActivityManager$RunningAppProcessInfo"),
+            jni_sig!("()V"),
+            &[],
+        )?;
+        env.call_static_method(
+            jni_str!("android/app/ActivityManager"),
+            jni_str!("getMyMemoryState"),
+            jni_sig!("(Landroid/app/ActivityManager$RunningAppProcessInfo;)V"),
+            &[(&info).into()],
+        )?;
+        env.get_field(&info, jni_str!("lastTrimLevel"), jni_sig!("I"))?.i()
+    })?;
+
+    // Levels that are added in the future are reported as the next lower known level.
+    Some(match level {
+        80.. => TrimLevel::Complete,
+        60.. => TrimLevel::Moderate,
+        40.. => TrimLevel::Background,
+        20.. => TrimLevel::UiHidden,
+        15.. => TrimLevel::RunningCritical,
+        10.. => TrimLevel::RunningLow,
+        5.. => TrimLevel::RunningModerate,
+        _ => return None,
+    })
+}
+
+/// Tells the IME where the cursor is, in physical pixels relative to the window, so that it can
+/// position its candidate window and toolbar next to it.
+pub(crate) fn update_cursor_anchor_info(app: &AndroidApp, x: f32, y: f32, height: f32) {
+    with_activity_on_main_thread(app, move |env, activity| {
+        // `Context.INPUT_METHOD_SERVICE`.
+        let name = env.new_string("input_method")?;
+        let input_method_manager = env
+            .call_method(
+                activity,
+                jni_str!("getSystemService"),
+                jni_sig!((java.lang.String) -> java.lang.Object),
+                &[(&name).into()],
+            )?
+            .l()?;
+        // The view that holds the `InputConnection`.
+        let view = env
+            .call_method(
+                activity,
+                jni_str!("getCurrentFocus"),
+                jni_sig!(() -> android.view.View),
+                &[],
+            )?
+            .l()?;
+        if input_method_manager.is_null() || view.is_null() {
+            return Ok(());
+        }
+
+        // The matrix maps the coordinates in the view to the screen.
+        let location = env.new_int_array(2)?;
+        env.call_method(&view, jni_str!("getLocationOnScreen"), jni_sig!("([I)V"), &[
+            (&location).into()
+        ])?;
+        let mut origin = [0; 2];
+        location.get_region(env, 0, &mut origin)?;
+        let matrix = env.new_object(jni_str!("android/graphics/Matrix"), jni_sig!("()V"), &[])?;
+        env.call_method(&matrix, jni_str!("setTranslate"), jni_sig!("(FF)V"), &[
+            (origin[0] as f32).into(),
+            (origin[1] as f32).into(),
+        ])?;
+
+        let builder = env.new_object(
+            jni_str!("android/view/inputmethod/CursorAnchorInfo$Builder"),
+            jni_sig!("()V"),
+            &[],
+        )?;
+        env.call_method(
+            &builder,
+            jni_str!("setMatrix"),
+            jni_sig!(
+                "(Landroid/graphics/Matrix;)Landroid/view/inputmethod/CursorAnchorInfo$Builder;"
+            ),
+            &[(&matrix).into()],
+        )?;
+        // `CursorAnchorInfo.FLAG_HAS_VISIBLE_REGION`.
+        env.call_method(
+            &builder,
+            jni_str!("setInsertionMarkerLocation"),
+            jni_sig!("(FFFFI)Landroid/view/inputmethod/CursorAnchorInfo$Builder;"),
+            &[x.into(), y.into(), (y + height).into(), (y + height).into(), 1.into()],
+        )?;
+        let info = env
+            .call_method(
+                &builder,
+                jni_str!("build"),
+                jni_sig!("()Landroid/view/inputmethod/CursorAnchorInfo;"),
+                &[],
+            )?
+            .l()?;
+
+        env.call_method(
+            &input_method_manager,
+            jni_str!("updateCursorAnchorInfo"),
+            jni_sig!("(Landroid/view/View;Landroid/view/inputmethod/CursorAnchorInfo;)V"),
+            &[(&view).into(), (&info).into()],
+        )?;
+        Ok(())
+    });
+}
+
+/// Returns the multi-window modes of the activity, which are only available since API 24.
+pub(crate) fn window_modes(app: &AndroidApp) -> WindowModes {
+    if app.config().sdk_version() < API_24 {
+        return WindowModes::default();
+    }
+    with_activity(app, |env, activity| {
+        Ok(WindowModes {
+            picture_in_picture: env
+                .call_method(activity, jni_str!("isInPictureInPictureMode"), jni_sig!("()Z"), &[])?
+                .z()?,
+            multi_window: env
+                .call_method(activity, jni_str!("isInMultiWindowMode"), jni_sig!("()Z"), &[])?
+                .z()?,
+        })
+    })
+    .unwrap_or_default()
+}
+
+/// Returns whether the device supports picture-in-picture with `PictureInPictureParams`, which
+/// are only available since API 26.
+pub(crate) fn picture_in_picture_supported(app: &AndroidApp) -> bool {
+    if app.config().sdk_version() < API_26 {
+        return false;
+    }
+    with_activity(app, |env, activity| {
+        let package_manager = env
+            .call_method(
+                activity,
+                jni_str!("getPackageManager"),
+                jni_sig!(() -> android.content.pm.PackageManager),
+                &[],
+            )?
+            .l()?;
+        // `PackageManager.FEATURE_PICTURE_IN_PICTURE`.
+        let feature = env.new_string("android.software.picture_in_picture")?;
+        env.call_method(
+            &package_manager,
+            jni_str!("hasSystemFeature"),
+            jni_sig!((java.lang.String) -> boolean),
+            &[(&feature).into()],
+        )?
+        .z()
+    })
+    .unwrap_or(false)
+}
+
+/// The range of aspect ratios that `PictureInPictureParams.Builder.setAspectRatio()` accepts,
+/// it throws otherwise.
+const MIN_PIP_ASPECT_RATIO: f64 = 1.0 / 2.39;
+const MAX_PIP_ASPECT_RATIO: f64 = 2.39;
+
+pub(crate) fn enter_picture_in_picture(app: &AndroidApp, params: PipParams) {
+    with_activity_on_main_thread(app, move |env, activity| {
+        let builder = env.new_object(
+            jni_str!("android/app/PictureInPictureParams$Builder"),
+            jni_sig!("()V"),
+            &[],
+        )?;
+
+        if let Some((width, height)) =
+            params.aspect_ratio.filter(|&(width, height)| width != 0 && height != 0)
+        {
+            let ratio = width as f64 / height as f64;
+            let (width, height) = if (MIN_PIP_ASPECT_RATIO..=MAX_PIP_ASPECT_RATIO).contains(&ratio)
+            {
+                (width as i32, height as i32)
+            } else {
+                let ratio = ratio.clamp(MIN_PIP_ASPECT_RATIO, MAX_PIP_ASPECT_RATIO);
+                warn!("picture-in-picture aspect ratio {width}:{height} is clamped to {ratio}");
+                ((ratio * 10000.0).round() as i32, 10000)
+            };
+            let rational =
+                env.new_object(jni_str!("android/util/Rational"), jni_sig!("(II)V"), &[
+                    width.into(),
+                    height.into(),
+                ])?;
+            env.call_method(
+                &builder,
+                jni_str!("setAspectRatio"),
+                jni_sig!("(Landroid/util/Rational;)Landroid/app/PictureInPictureParams$Builder;"),
+                &[(&rational).into()],
+            )?;
+        }
+
+        if let Some((position, size)) = params.source_rect_hint {
+            let rect =
+                env.new_object(jni_str!("android/graphics/Rect"), jni_sig!("(IIII)V"), &[
+                    position.x.into(),
+                    position.y.into(),
+                    (position.x + size.width as i32).into(),
+                    (position.y + size.height as i32).into(),
+                ])?;
+            env.call_method(
+                &builder,
+                jni_str!("setSourceRectHint"),
+                jni_sig!("(Landroid/graphics/Rect;)Landroid/app/PictureInPictureParams$Builder;"),
+                &[(&rect).into()],
+            )?;
+        }
+
+        let params = env
+            .call_method(
+                &builder,
+                jni_str!("build"),
+                jni_sig!("()Landroid/app/PictureInPictureParams;"),
+                &[],
+            )?
+            .l()?;
+        let entered = env
+            .call_method(
+                activity,
+                jni_str!("enterPictureInPictureMode"),
+                jni_sig!("(Landroid/app/PictureInPictureParams;)Z"),
+                &[(&params).into()],
+            )?
+            .z()?;
+        if !entered {
+            warn!(
+                "failed to enter picture-in-picture, the activity needs \
+                 `android:supportsPictureInPicture=\"true\"` in the manifest"
+            );
+        }
+        Ok(())
+    });
+}
+
+/// Returns the game mode that the user selected for the application, which is only available
+/// since API 31.
+pub(crate) fn game_mode(app: &AndroidApp) -> GameMode {
+    if app.config().sdk_version() < API_31 {
+        return GameMode::Unsupported;
+    }
+    let mode = with_activity(app, |env, activity| {
+        // `Context.GAME_SERVICE`.
+        let name = env.new_string("game")?;
+        let game_manager = env
+            .call_method(
+                activity,
+                jni_str!("getSystemService"),
+                jni_sig!((java.lang.String) -> java.lang.Object),
+                &[(&name).into()],
+            )?
+            .l()?;
+        if game_manager.is_null() {
+            return Ok(0);
+        }
+        env.call_method(&game_manager, jni_str!("getGameMode"), jni_sig!("()I"), &[])?.i()
+    });
+
+    match mode {
+        Some(1) => GameMode::Standard,
+        Some(2) => GameMode::Performance,
+        Some(3) => GameMode::Battery,
+        Some(4) => GameMode::Custom,
+        _ => GameMode::Unsupported,
+    }
+}
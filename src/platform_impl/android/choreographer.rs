@@ -0,0 +1,270 @@
+//! Redraws driven by [`AChoreographer`], so that they happen once per vsync, and notifications
+//! about changes of the refresh rate.
+//!
+//! [`AChoreographer`]: https://developer.android.com/ndk/reference/group/choreographer
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::rc::Rc;
+
+use android_activity::AndroidAppWaker;
+use tracing::warn;
+
+use super::libandroid::symbol;
+use crate::platform::android::FrameTiming;
+
+type AChoreographer = c_void;
+type AChoreographerFrameCallbackData = c_void;
+type FrameCallback64 = unsafe extern "C" fn(frame_time_nanos: i64, data: *mut c_void);
+type VsyncCallback =
+    unsafe extern "C" fn(callback_data: *const AChoreographerFrameCallbackData, data: *mut c_void);
+type RefreshRateCallback = unsafe extern "C" fn(vsync_period_nanos: i64, data: *mut c_void);
+type RegisterRefreshRateCallback =
+    unsafe extern "C" fn(*mut AChoreographer, Option<RefreshRateCallback>, *mut c_void);
+
+/// The functions of `libandroid.so` that are used, API 29 and later.
+struct Functions {
+    get_instance: unsafe extern "C" fn() -> *mut AChoreographer,
+    post_frame_callback_64:
+        unsafe extern "C" fn(*mut AChoreographer, Option<FrameCallback64>, *mut c_void),
+    vsync: Option<VsyncFunctions>,
+}
+
+/// The functions of `libandroid.so` that are used, API 33 and later.
+struct VsyncFunctions {
+    post_vsync_callback:
+        unsafe extern "C" fn(*mut AChoreographer, Option<VsyncCallback>, *mut c_void),
+    get_frame_time_nanos: unsafe extern "C" fn(*const AChoreographerFrameCallbackData) -> i64,
+    get_preferred_frame_timeline_index:
+        unsafe extern "C" fn(*const AChoreographerFrameCallbackData) -> usize,
+    get_frame_timeline_expected_presentation_time_nanos:
+        unsafe extern "C" fn(*const AChoreographerFrameCallbackData, usize) -> i64,
+    get_frame_timeline_deadline_nanos:
+        unsafe extern "C" fn(*const AChoreographerFrameCallbackData, usize) -> i64,
+}
+
+impl Functions {
+    fn load() -> Option<Self> {
+        // SAFETY: The symbols have the declared signatures.
+        unsafe {
+            let vsync = (|| {
+                Some(VsyncFunctions {
+                    post_vsync_callback: symbol(b"AChoreographer_postVsyncCallback\0")?,
+                    get_frame_time_nanos: symbol(
+                        b"AChoreographerFrameCallbackData_getFrameTimeNanos\0",
+                    )?,
+                    get_preferred_frame_timeline_index: symbol(
+                        b"AChoreographerFrameCallbackData_getPreferredFrameTimelineIndex\0",
+                    )?,
+                    get_frame_timeline_expected_presentation_time_nanos: symbol(
+                        b"AChoreographerFrameCallbackData_getFrameTimelineExpectedPresentationTimeNanos\0",
+                    )?,
+                    get_frame_timeline_deadline_nanos: symbol(
+                        b"AChoreographerFrameCallbackData_getFrameTimelineDeadlineNanos\0",
+                    )?,
+                })
+            })();
+
+            Some(Self {
+                get_instance: symbol(b"AChoreographer_getInstance\0")?,
+                post_frame_callback_64: symbol(b"AChoreographer_postFrameCallback64\0")?,
+                vsync,
+            })
+        }
+    }
+}
+
+/// Requests frame callbacks from the `AChoreographer` of the thread of the event loop.
+pub(crate) struct Choreographer {
+    functions: Rc<Functions>,
+    choreographer: *mut AChoreographer,
+    state: Rc<State>,
+}
+
+struct State {
+    functions: Rc<Functions>,
+    waker: AndroidAppWaker,
+    /// Whether a callback was posted and hasn't been called yet.
+    pending: Cell<bool>,
+    /// Whether the result of the pending callback should be ignored.
+    cancelled: Cell<bool>,
+    frame: Cell<Option<FrameTiming>>,
+}
+
+impl Choreographer {
+    /// Returns [`None`] before API 29, or if the thread has no looper.
+    pub fn new(waker: AndroidAppWaker) -> Option<Self> {
+        let Some(functions) = Functions::load() else {
+            warn!("`AChoreographer` is only available since API 29, redraws aren't synchronized");
+            return None;
+        };
+        let choreographer = unsafe { (functions.get_instance)() };
+        if choreographer.is_null() {
+            warn!("failed to get the `AChoreographer` of the event loop thread");
+            return None;
+        }
+
+        let functions = Rc::new(functions);
+        let state = Rc::new(State {
+            functions: functions.clone(),
+            waker,
+            pending: Cell::new(false),
+            cancelled: Cell::new(false),
+            frame: Cell::new(None),
+        });
+        Some(Self { functions, choreographer, state })
+    }
+
+    /// Requests a frame callback, unless one is already pending or a frame is ready.
+    pub fn request_frame(&self) {
+        self.state.cancelled.set(false);
+        if self.state.pending.get() || self.frame_ready() {
+            return;
+        }
+        self.state.pending.set(true);
+
+        // The callback takes over this reference to the state.
+        let data = Rc::into_raw(self.state.clone()).cast_mut().cast();
+        unsafe {
+            match &self.functions.vsync {
+                Some(vsync) => {
+                    (vsync.post_vsync_callback)(self.choreographer, Some(vsync_callback), data)
+                },
+                None => (self.functions.post_frame_callback_64)(
+                    self.choreographer,
+                    Some(frame_callback),
+                    data,
+                ),
+            }
+        }
+    }
+
+    pub fn frame_ready(&self) -> bool {
+        self.state.frame.get().is_some()
+    }
+
+    /// Takes the timing of the frame that is ready to be drawn.
+    pub fn take_frame(&self) -> Option<FrameTiming> {
+        self.state.frame.take()
+    }
+
+    /// Drops the pending frame, e.g. because the surface was destroyed.
+    ///
+    /// `AChoreographer` has no way to remove a posted callback, it is ignored instead.
+    pub fn cancel(&self) {
+        self.state.cancelled.set(self.state.pending.get());
+        self.state.frame.set(None);
+    }
+}
+
+impl State {
+    fn frame(&self, timing: FrameTiming) {
+        self.pending.set(false);
+        if self.cancelled.replace(false) {
+            return;
+        }
+        self.frame.set(Some(timing));
+        // `AndroidApp::poll_events()` doesn't report callbacks, so wake it up to emit the redraw.
+        self.waker.wake();
+    }
+}
+
+unsafe extern "C" fn frame_callback(frame_time_nanos: i64, data: *mut c_void) {
+    let state = unsafe { Rc::from_raw(data.cast::<State>()) };
+    state.frame(FrameTiming {
+        frame_time_nanos,
+        expected_presentation_time_nanos: None,
+        deadline_nanos: None,
+    });
+}
+
+unsafe extern "C" fn vsync_callback(
+    callback_data: *const AChoreographerFrameCallbackData,
+    data: *mut c_void,
+) {
+    let state = unsafe { Rc::from_raw(data.cast::<State>()) };
+    let Some(vsync) = &state.functions.vsync else {
+        return;
+    };
+
+    let timing = unsafe {
+        let index = (vsync.get_preferred_frame_timeline_index)(callback_data);
+        FrameTiming {
+            frame_time_nanos: (vsync.get_frame_time_nanos)(callback_data),
+            expected_presentation_time_nanos: Some((vsync
+                .get_frame_timeline_expected_presentation_time_nanos)(
+                callback_data, index
+            )),
+            deadline_nanos: Some((vsync.get_frame_timeline_deadline_nanos)(callback_data, index)),
+        }
+    };
+    state.frame(timing);
+}
+
+/// Notifies the event loop when the refresh rate of the display changes, API 30 and later.
+pub(crate) struct RefreshRateObserver {
+    choreographer: *mut AChoreographer,
+    unregister: RegisterRefreshRateCallback,
+    state: Box<RefreshRateState>,
+}
+
+struct RefreshRateState {
+    waker: AndroidAppWaker,
+    changed: Cell<bool>,
+}
+
+impl RefreshRateObserver {
+    pub fn new(waker: AndroidAppWaker) -> Option<Self> {
+        // SAFETY: The symbols have the declared signatures.
+        let (get_instance, register, unregister) = unsafe {
+            (
+                symbol::<unsafe extern "C" fn() -> *mut AChoreographer>(
+                    b"AChoreographer_getInstance\0",
+                )?,
+                symbol::<RegisterRefreshRateCallback>(
+                    b"AChoreographer_registerRefreshRateCallback\0",
+                )?,
+                symbol::<RegisterRefreshRateCallback>(
+                    b"AChoreographer_unregisterRefreshRateCallback\0",
+                )?,
+            )
+        };
+        let choreographer = unsafe { get_instance() };
+        if choreographer.is_null() {
+            return None;
+        }
+
+        let state = Box::new(RefreshRateState { waker, changed: Cell::new(false) });
+        unsafe { register(choreographer, Some(refresh_rate_callback), state.data()) };
+        Some(Self { choreographer, unregister, state })
+    }
+
+    /// Returns whether the refresh rate changed since the last call to [`Self::take_changed()`].
+    pub fn changed(&self) -> bool {
+        self.state.changed.get()
+    }
+
+    pub fn take_changed(&self) -> bool {
+        self.state.changed.replace(false)
+    }
+}
+
+impl Drop for RefreshRateObserver {
+    fn drop(&mut self) {
+        unsafe {
+            (self.unregister)(self.choreographer, Some(refresh_rate_callback), self.state.data())
+        };
+    }
+}
+
+impl RefreshRateState {
+    fn data(&self) -> *mut c_void {
+        (self as *const Self).cast_mut().cast()
+    }
+}
+
+unsafe extern "C" fn refresh_rate_callback(_vsync_period_nanos: i64, data: *mut c_void) {
+    let state = unsafe { &*data.cast::<RefreshRateState>() };
+    state.changed.set(true);
+    state.waker.wake();
+}
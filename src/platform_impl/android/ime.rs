@@ -0,0 +1,156 @@
+//! Text input through the `InputConnection` of `GameActivity`, which reports the whole state of
+//! its text buffer after every change made by the IME.
+
+use android_activity::input::{ImeOptions, InputType, TextInputAction, TextInputState, TextSpan};
+use android_activity::AndroidApp;
+
+use crate::event::Ime;
+use crate::window::ImePurpose;
+
+/// Tells the IME what kind of text is entered, without the extracted text UI that would cover
+/// the window in landscape.
+pub fn set_editor_info(app: &AndroidApp, purpose: ImePurpose) {
+    app.set_ime_editor_info(
+        input_type(purpose),
+        TextInputAction::Unspecified,
+        ImeOptions::IME_FLAG_NO_FULLSCREEN,
+    );
+}
+
+fn input_type(purpose: ImePurpose) -> InputType {
+    match purpose {
+        ImePurpose::Normal => {
+            InputType::TYPE_CLASS_TEXT
+                | InputType::TYPE_TEXT_FLAG_AUTO_CORRECT
+                | InputType::TYPE_TEXT_FLAG_CAP_SENTENCES
+        },
+        ImePurpose::Password => {
+            InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_PASSWORD
+        },
+        ImePurpose::Terminal => {
+            InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_FLAG_NO_SUGGESTIONS
+        },
+        ImePurpose::Email => {
+            InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_EMAIL_ADDRESS
+        },
+        ImePurpose::Number => {
+            InputType::TYPE_CLASS_NUMBER
+                | InputType::TYPE_NUMBER_FLAG_SIGNED
+                | InputType::TYPE_NUMBER_FLAG_DECIMAL
+        },
+    }
+}
+
+/// Translates the changes of the text input state into [`Ime`] events.
+///
+/// The committed text stays in the buffer, so that the IME can take it into account and change
+/// it, e.g. to reconvert a word. Text that the IME removes, e.g. with backspace, is reported as
+/// [`Ime::DeleteSurrounding`].
+#[derive(Debug, Default)]
+pub struct ImeState {
+    /// The committed text.
+    text: String,
+    /// The byte offset of the cursor in `text`.
+    cursor: usize,
+    preedit: String,
+    preedit_cursor: Option<(usize, usize)>,
+}
+
+impl ImeState {
+    pub fn update(&mut self, state: &TextInputState) -> Vec<Ime> {
+        let (text, cursor, preedit, preedit_cursor) = split(state);
+
+        // The committed text changed in a single range around the previous cursor, which is
+        // replaced by the inserted text.
+        let prefix = common_prefix(&self.text, &text).min(self.cursor);
+        let suffix =
+            common_suffix(&self.text[prefix..], &text[prefix..]).min(self.text.len() - self.cursor);
+        let before_bytes = self.cursor - prefix;
+        let after_bytes = self.text.len() - suffix - self.cursor;
+        let inserted = &text[prefix..text.len() - suffix];
+
+        let mut events = Vec::new();
+        let committed_changed = before_bytes != 0 || after_bytes != 0 || !inserted.is_empty();
+        if committed_changed {
+            if !self.preedit.is_empty() {
+                events.push(Ime::Preedit(String::new(), None));
+            }
+            if before_bytes != 0 || after_bytes != 0 {
+                events.push(Ime::DeleteSurrounding { before_bytes, after_bytes });
+            }
+            if !inserted.is_empty() {
+                events.push(Ime::Commit(inserted.to_owned()));
+            }
+        }
+        let preedit_changed = preedit != self.preedit || preedit_cursor != self.preedit_cursor;
+        if !preedit.is_empty() && (committed_changed || preedit_changed)
+            || preedit.is_empty() && !committed_changed && !self.preedit.is_empty()
+        {
+            events.push(Ime::Preedit(preedit.clone(), preedit_cursor));
+        }
+
+        *self = Self { text, cursor, preedit, preedit_cursor };
+        events
+    }
+}
+
+/// Splits the text of the state into the committed text with the cursor in it, and the
+/// composing text with the selection in it, as byte offsets.
+fn split(state: &TextInputState) -> (String, usize, String, Option<(usize, usize)>) {
+    let text = &state.text;
+    let span = |span: TextSpan| {
+        let (start, end) = (span.start.min(span.end), span.start.max(span.end));
+        (byte_offset(text, start), byte_offset(text, end))
+    };
+
+    let (selection_start, selection_end) = span(state.selection);
+    let Some((compose_start, compose_end)) =
+        state.compose_region.map(span).filter(|(start, end)| start != end)
+    else {
+        return (text.clone(), selection_end, String::new(), None);
+    };
+
+    let committed = [&text[..compose_start], &text[compose_end..]].concat();
+    let cursor = if selection_end <= compose_start {
+        selection_end
+    } else if selection_end >= compose_end {
+        selection_end - (compose_end - compose_start)
+    } else {
+        compose_start
+    };
+    let preedit_cursor = (compose_start <= selection_start && selection_end <= compose_end)
+        .then(|| (selection_start - compose_start, selection_end - compose_start));
+
+    (committed, cursor, text[compose_start..compose_end].to_owned(), preedit_cursor)
+}
+
+/// Converts an offset in UTF-16 code units, which `GameTextInput` uses like Java, into a byte
+/// offset.
+fn byte_offset(text: &str, utf16_offset: usize) -> usize {
+    let mut utf16_len = 0;
+    for (index, char) in text.char_indices() {
+        if utf16_len >= utf16_offset {
+            return index;
+        }
+        utf16_len += char.len_utf16();
+    }
+    text.len()
+}
+
+/// Returns the length in bytes of the common prefix, which ends on a character boundary.
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(a.len().min(b.len()), |((index, _), _)| index)
+}
+
+/// Returns the length in bytes of the common suffix, which starts on a character boundary.
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum()
+}
@@ -1,7 +1,11 @@
-use android_activity::input::{KeyAction, KeyEvent, KeyMapChar, Keycode};
+use android_activity::input::{KeyAction, KeyEvent, KeyMapChar, Keycode, MetaState};
 use android_activity::AndroidApp;
 
-use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
+use crate::event::Modifiers;
+use crate::keyboard::{
+    Key, KeyCode, KeyLocation, LockKeys, ModifiersKeys, ModifiersState, NamedKey, NativeKey,
+    NativeKeyCode, PhysicalKey,
+};
 
 pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
     PhysicalKey::Code(match keycode {
@@ -62,6 +66,8 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::NumpadEquals => KeyCode::NumpadEqual,
         Keycode::NumpadComma => KeyCode::NumpadComma,
         Keycode::NumpadDot => KeyCode::NumpadDecimal,
+        Keycode::NumpadLeftParen => KeyCode::NumpadParenLeft,
+        Keycode::NumpadRightParen => KeyCode::NumpadParenRight,
         Keycode::NumLock => KeyCode::NumLock,
 
         Keycode::DpadLeft => KeyCode::ArrowLeft,
@@ -92,6 +98,10 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::MoveHome => KeyCode::Home,
         Keycode::MoveEnd => KeyCode::End,
         Keycode::Insert => KeyCode::Insert,
+        Keycode::CapsLock => KeyCode::CapsLock,
+        Keycode::Menu => KeyCode::ContextMenu,
+        Keycode::Function => KeyCode::Fn,
+        Keycode::Help => KeyCode::Help,
 
         Keycode::Del => KeyCode::Backspace, // Backspace (above Enter)
         Keycode::ForwardDel => KeyCode::Delete, // Delete (below Insert)
@@ -100,6 +110,16 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::Paste => KeyCode::Paste,
         Keycode::Cut => KeyCode::Cut,
 
+        Keycode::Back => KeyCode::BrowserBack,
+        Keycode::Forward => KeyCode::BrowserForward,
+        Keycode::Refresh => KeyCode::BrowserRefresh,
+        Keycode::Search => KeyCode::BrowserSearch,
+        Keycode::Bookmark => KeyCode::BrowserFavorites,
+        Keycode::Explorer => KeyCode::LaunchApp1,
+        Keycode::Calculator => KeyCode::LaunchApp2,
+        Keycode::Envelope => KeyCode::LaunchMail,
+        Keycode::Music => KeyCode::MediaSelect,
+
         Keycode::VolumeUp => KeyCode::AudioVolumeUp,
         Keycode::VolumeDown => KeyCode::AudioVolumeDown,
         Keycode::VolumeMute => KeyCode::AudioVolumeMute,
@@ -108,6 +128,7 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::MediaStop => KeyCode::MediaStop,
         Keycode::MediaNext => KeyCode::MediaTrackNext,
         Keycode::MediaPrevious => KeyCode::MediaTrackPrevious,
+        Keycode::MediaEject => KeyCode::Eject,
 
         Keycode::Plus => KeyCode::Equal,
         Keycode::Minus => KeyCode::Minus,
@@ -131,8 +152,15 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::ScrollLock => KeyCode::ScrollLock,
 
         Keycode::Yen => KeyCode::IntlYen,
+        Keycode::Ro => KeyCode::IntlRo,
         Keycode::Kana => KeyCode::Lang1,
+        Keycode::Eisu => KeyCode::Lang2,
         Keycode::KatakanaHiragana => KeyCode::KanaMode,
+        Keycode::Henkan => KeyCode::Convert,
+        Keycode::Muhenkan => KeyCode::NonConvert,
+        // The Hankaku/Zenkaku key of Japanese keyboards is where the Backquote key is on US
+        // keyboards
+        Keycode::ZenkakuHankaku => KeyCode::Backquote,
 
         Keycode::CtrlLeft => KeyCode::ControlLeft,
         Keycode::CtrlRight => KeyCode::ControlRight,
@@ -150,7 +178,8 @@ pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
         Keycode::RightBracket => KeyCode::BracketRight,
 
         Keycode::Power => KeyCode::Power,
-        Keycode::Sleep => KeyCode::Sleep, // what about SoftSleep?
+        Keycode::Sleep => KeyCode::Sleep,
+        Keycode::SoftSleep => KeyCode::Sleep,
         Keycode::Wakeup => KeyCode::WakeUp,
 
         keycode => return PhysicalKey::Unidentified(NativeKeyCode::Android(keycode.into())),
@@ -474,6 +503,16 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
             Copy => Key::Named(NamedKey::Copy),
             Paste => Key::Named(NamedKey::Paste),
             Refresh => Key::Named(NamedKey::BrowserRefresh),
+            SoftLeft => Key::Named(NamedKey::Soft1),
+            SoftRight => Key::Named(NamedKey::Soft2),
+            Menu => Key::Named(NamedKey::ContextMenu),
+            SoftSleep => Key::Named(NamedKey::Standby),
+            // According to https://developer.android.com/reference/android/view/KeyEvent#KEYCODE_WINDOW
+            Window => Key::Named(NamedKey::PinPToggle),
+            Keycode11 => Key::Named(NamedKey::Key11),
+            Keycode12 => Key::Named(NamedKey::Key12),
+            Yen => Key::Character("¥".into()),
+            Ro => Key::Character("\\".into()),
 
             // -----------------------------------------------------------------
             // Keycodes that don't have a logical Key mapping
@@ -481,11 +520,6 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
             Unknown => Key::Unidentified(native),
 
             // Can be added on demand
-            SoftLeft => Key::Unidentified(native),
-            SoftRight => Key::Unidentified(native),
-
-            Menu => Key::Unidentified(native),
-
             Pictsymbols => Key::Unidentified(native),
             SwitchCharset => Key::Unidentified(native),
 
@@ -508,8 +542,6 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
             ButtonSelect => Key::Unidentified(native),
             ButtonMode => Key::Unidentified(native),
             // -----------------------------------------------------------------
-            Window => Key::Unidentified(native),
-
             Button1 => Key::Unidentified(native),
             Button2 => Key::Unidentified(native),
             Button3 => Key::Unidentified(native),
@@ -527,14 +559,8 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
             Button15 => Key::Unidentified(native),
             Button16 => Key::Unidentified(native),
 
-            Yen => Key::Unidentified(native),
-            Ro => Key::Unidentified(native),
-
             Assist => Key::Unidentified(native),
 
-            Keycode11 => Key::Unidentified(native),
-            Keycode12 => Key::Unidentified(native),
-
             StemPrimary => Key::Unidentified(native),
             Stem1 => Key::Unidentified(native),
             Stem2 => Key::Unidentified(native),
@@ -545,8 +571,6 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
             DpadUpRight => Key::Unidentified(native),
             DpadDownRight => Key::Unidentified(native),
 
-            SoftSleep => Key::Unidentified(native),
-
             SystemNavigationUp => Key::Unidentified(native),
             SystemNavigationDown => Key::Unidentified(native),
             SystemNavigationLeft => Key::Unidentified(native),
@@ -606,3 +630,225 @@ pub fn to_location(keycode: Keycode) -> KeyLocation {
         _ => KeyLocation::Standard,
     }
 }
+
+/// Whether the key is a system key, which the system handles unless the application does, e.g. by
+/// changing the volume, as in `KeyEvent.isSystem()`.
+///
+/// `Back` is handled separately, following `WindowExtAndroid::set_consumes_back()`.
+pub fn is_system_key(keycode: Keycode) -> bool {
+    use android_activity::input::Keycode::*;
+
+    matches!(
+        keycode,
+        Menu | SoftRight
+            | Home
+            | Call
+            | Endcall
+            | VolumeUp
+            | VolumeDown
+            | VolumeMute
+            | Mute
+            | Power
+            | Headsethook
+            | MediaPlay
+            | MediaPause
+            | MediaPlayPause
+            | MediaStop
+            | MediaNext
+            | MediaPrevious
+            | MediaRewind
+            | MediaRecord
+            | MediaFastForward
+            | Camera
+            | Focus
+            | Search
+            | BrightnessDown
+            | BrightnessUp
+            | MediaAudioTrack
+            | SystemNavigationUp
+            | SystemNavigationDown
+            | SystemNavigationLeft
+            | SystemNavigationRight
+    )
+}
+
+/// Whether the key is a button of a gamepad, as in `KeyEvent.isGamepadButton()`.
+///
+/// Gamepad buttons aren't delivered as keyboard input, the system then sends a fallback key for
+/// some of them, e.g. `Back` for `ButtonB`.
+pub fn is_gamepad_button(keycode: Keycode) -> bool {
+    use android_activity::input::Keycode::*;
+
+    matches!(
+        keycode,
+        ButtonA
+            | ButtonB
+            | ButtonC
+            | ButtonX
+            | ButtonY
+            | ButtonZ
+            | ButtonL1
+            | ButtonR1
+            | ButtonL2
+            | ButtonR2
+            | ButtonThumbl
+            | ButtonThumbr
+            | ButtonStart
+            | ButtonSelect
+            | ButtonMode
+            | Button1
+            | Button2
+            | Button3
+            | Button4
+            | Button5
+            | Button6
+            | Button7
+            | Button8
+            | Button9
+            | Button10
+            | Button11
+            | Button12
+            | Button13
+            | Button14
+            | Button15
+            | Button16
+    )
+}
+
+/// Converts the meta state of a key event, as returned by `KeyEvent.getMetaState()`, to the
+/// modifiers.
+pub fn to_modifiers(meta_state: MetaState) -> Modifiers {
+    let mut state = ModifiersState::empty();
+    let mut pressed_mods = ModifiersKeys::empty();
+
+    state.set(ModifiersState::SHIFT, meta_state.shift_on());
+    pressed_mods.set(ModifiersKeys::LSHIFT, meta_state.shift_left_on());
+    pressed_mods.set(ModifiersKeys::RSHIFT, meta_state.shift_right_on());
+
+    state.set(ModifiersState::CONTROL, meta_state.ctrl_on());
+    pressed_mods.set(ModifiersKeys::LCONTROL, meta_state.ctrl_left_on());
+    pressed_mods.set(ModifiersKeys::RCONTROL, meta_state.ctrl_right_on());
+
+    state.set(ModifiersState::ALT, meta_state.alt_on());
+    pressed_mods.set(ModifiersKeys::LALT, meta_state.alt_left_on());
+    pressed_mods.set(ModifiersKeys::RALT, meta_state.alt_right_on());
+
+    state.set(ModifiersState::SUPER, meta_state.meta_on());
+    pressed_mods.set(ModifiersKeys::LSUPER, meta_state.meta_left_on());
+    pressed_mods.set(ModifiersKeys::RSUPER, meta_state.meta_right_on());
+
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(LockKeys::CAPS_LOCK, meta_state.caps_lock_on());
+    lock_keys.set(LockKeys::NUM_LOCK, meta_state.num_lock_on());
+
+    Modifiers { state, pressed_mods, lock_keys }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn physical_keys() {
+        let table = [
+            (Keycode::A, KeyCode::KeyA),
+            (Keycode::Keycode0, KeyCode::Digit0),
+            (Keycode::Numpad5, KeyCode::Numpad5),
+            (Keycode::NumpadDot, KeyCode::NumpadDecimal),
+            (Keycode::NumpadLeftParen, KeyCode::NumpadParenLeft),
+            (Keycode::DpadLeft, KeyCode::ArrowLeft),
+            (Keycode::F12, KeyCode::F12),
+            (Keycode::Del, KeyCode::Backspace),
+            (Keycode::ForwardDel, KeyCode::Delete),
+            (Keycode::MoveHome, KeyCode::Home),
+            (Keycode::CapsLock, KeyCode::CapsLock),
+            (Keycode::Menu, KeyCode::ContextMenu),
+            (Keycode::Function, KeyCode::Fn),
+            (Keycode::Plus, KeyCode::Equal),
+            (Keycode::Grave, KeyCode::Backquote),
+            (Keycode::Sysrq, KeyCode::PrintScreen),
+            (Keycode::Break, KeyCode::Pause),
+            (Keycode::CtrlRight, KeyCode::ControlRight),
+            (Keycode::MetaLeft, KeyCode::SuperLeft),
+            (Keycode::Back, KeyCode::BrowserBack),
+            (Keycode::Search, KeyCode::BrowserSearch),
+            (Keycode::Envelope, KeyCode::LaunchMail),
+            (Keycode::MediaEject, KeyCode::Eject),
+            (Keycode::VolumeMute, KeyCode::AudioVolumeMute),
+            (Keycode::Ro, KeyCode::IntlRo),
+            (Keycode::Henkan, KeyCode::Convert),
+            (Keycode::Muhenkan, KeyCode::NonConvert),
+            (Keycode::Eisu, KeyCode::Lang2),
+            (Keycode::SoftSleep, KeyCode::Sleep),
+        ];
+        for (keycode, code) in table {
+            assert_eq!(to_physical_key(keycode), PhysicalKey::Code(code), "{keycode:?}");
+        }
+
+        assert_eq!(
+            to_physical_key(Keycode::ButtonA),
+            PhysicalKey::Unidentified(NativeKeyCode::Android(Keycode::ButtonA.into()))
+        );
+    }
+
+    #[test]
+    fn logical_keys() {
+        let table = [
+            (Keycode::Enter, Key::Named(NamedKey::Enter)),
+            (Keycode::NumpadEnter, Key::Named(NamedKey::Enter)),
+            (Keycode::Del, Key::Named(NamedKey::Backspace)),
+            (Keycode::ForwardDel, Key::Named(NamedKey::Delete)),
+            (Keycode::Menu, Key::Named(NamedKey::ContextMenu)),
+            (Keycode::SoftLeft, Key::Named(NamedKey::Soft1)),
+            (Keycode::MetaRight, Key::Named(NamedKey::Super)),
+            (Keycode::Function, Key::Named(NamedKey::Fn)),
+            (Keycode::Back, Key::Named(NamedKey::BrowserBack)),
+            (Keycode::MediaPlay, Key::Named(NamedKey::MediaPlay)),
+            (Keycode::Numpad7, Key::Character("7".into())),
+            (Keycode::Yen, Key::Character("¥".into())),
+        ];
+        for (keycode, key) in table {
+            assert_eq!(to_logical(None, keycode), key, "{keycode:?}");
+        }
+
+        assert_eq!(
+            to_logical(Some(KeyMapChar::Unicode('é')), Keycode::E),
+            Key::Character("é".into())
+        );
+        assert_eq!(
+            to_logical(Some(KeyMapChar::CombiningAccent('`')), Keycode::Grave),
+            Key::Dead(Some('`'))
+        );
+    }
+
+    #[test]
+    fn locations() {
+        assert_eq!(to_location(Keycode::ShiftRight), KeyLocation::Right);
+        assert_eq!(to_location(Keycode::MetaLeft), KeyLocation::Left);
+        assert_eq!(to_location(Keycode::NumpadEquals), KeyLocation::Numpad);
+        assert_eq!(to_location(Keycode::A), KeyLocation::Standard);
+    }
+
+    #[test]
+    fn system_keys() {
+        assert!(is_system_key(Keycode::VolumeUp));
+        assert!(is_system_key(Keycode::MediaPlayPause));
+        assert!(!is_system_key(Keycode::Back));
+        assert!(!is_system_key(Keycode::A));
+        assert!(is_gamepad_button(Keycode::ButtonB));
+        assert!(!is_gamepad_button(Keycode::DpadCenter));
+    }
+
+    #[test]
+    fn modifiers() {
+        // `META_SHIFT_ON | META_SHIFT_LEFT_ON | META_CTRL_ON | META_CTRL_RIGHT_ON`.
+        let modifiers = to_modifiers(MetaState(0x1 | 0x40 | 0x1000 | 0x4000));
+        assert_eq!(modifiers.state(), ModifiersState::SHIFT | ModifiersState::CONTROL);
+        assert_eq!(modifiers.pressed_mods, ModifiersKeys::LSHIFT | ModifiersKeys::RCONTROL);
+
+        // `META_CAPS_LOCK_ON` isn't a modifier, only a lock key.
+        let modifiers = to_modifiers(MetaState(0x100000));
+        assert_eq!(modifiers.state(), ModifiersState::empty());
+        assert_eq!(modifiers.lock_keys(), LockKeys::CAPS_LOCK);
+    }
+}
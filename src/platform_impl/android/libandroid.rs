@@ -0,0 +1,75 @@
+//! Functions of `libandroid.so` that are loaded at runtime, because they were added in later API
+//! levels than the oldest one that is supported, and linking to a missing function prevents the
+//! library from loading.
+
+use std::ffi::c_void;
+use std::mem;
+
+use ndk::native_window::NativeWindow;
+use tracing::warn;
+
+use crate::platform::android::FrameRateCompatibility;
+
+/// Looks up a function of `libandroid.so`, `T` must be the function pointer type of the symbol.
+///
+/// `name` must be nul-terminated.
+pub unsafe fn symbol<T>(name: &[u8]) -> Option<T> {
+    // The library is never unloaded, this only increments its reference count.
+    let library = unsafe { libc::dlopen(b"libandroid.so\0".as_ptr().cast(), libc::RTLD_NOW) };
+    if library.is_null() {
+        return None;
+    }
+    let symbol = unsafe { libc::dlsym(library, name.as_ptr().cast()) };
+    // Function pointers have the same size as data pointers on Android.
+    (!symbol.is_null()).then(|| unsafe { mem::transmute_copy::<*mut c_void, T>(&symbol) })
+}
+
+type ANativeWindow = c_void;
+type SetFrameRate =
+    unsafe extern "C" fn(window: *mut ANativeWindow, frame_rate: f32, compatibility: i8) -> i32;
+type SetFrameRateWithChangeStrategy = unsafe extern "C" fn(
+    window: *mut ANativeWindow,
+    frame_rate: f32,
+    compatibility: i8,
+    change_frame_rate_strategy: i8,
+) -> i32;
+
+/// `ANATIVEWINDOW_CHANGE_FRAME_RATE_ONLY_IF_SEAMLESS`.
+const CHANGE_FRAME_RATE_ONLY_IF_SEAMLESS: i8 = 0;
+
+/// Calls `ANativeWindow_setFrameRateWithChangeStrategy()` since API 31, or
+/// `ANativeWindow_setFrameRate()` on API 30, which behaves the same. Returns `false` before
+/// API 30.
+pub fn set_frame_rate(
+    window: &NativeWindow,
+    frame_rate: f32,
+    compatibility: FrameRateCompatibility,
+) -> bool {
+    let compatibility = match compatibility {
+        FrameRateCompatibility::Default => 0,
+        FrameRateCompatibility::FixedSource => 1,
+    };
+
+    // SAFETY: The symbols have the declared signatures, and the window is valid.
+    let result = unsafe {
+        if let Some(set_frame_rate) = symbol::<SetFrameRateWithChangeStrategy>(
+            b"ANativeWindow_setFrameRateWithChangeStrategy\0",
+        ) {
+            set_frame_rate(
+                window.ptr().as_ptr().cast(),
+                frame_rate,
+                compatibility,
+                CHANGE_FRAME_RATE_ONLY_IF_SEAMLESS,
+            )
+        } else if let Some(set_frame_rate) = symbol::<SetFrameRate>(b"ANativeWindow_setFrameRate\0")
+        {
+            set_frame_rate(window.ptr().as_ptr().cast(), frame_rate, compatibility)
+        } else {
+            return false;
+        }
+    };
+    if result != 0 {
+        warn!("failed to set the frame rate of the window to {frame_rate}: {result}");
+    }
+    true
+}
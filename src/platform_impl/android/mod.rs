@@ -1,4 +1,4 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -6,28 +6,44 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction};
+use android_activity::input::{
+    InputEvent, KeyAction, Keycode, MotionAction, TextInputState, TextSpan, ToolType,
+};
 use android_activity::{
     AndroidApp, AndroidAppWaker, ConfigurationRef, InputStatus, MainEvent, Rect,
 };
-use tracing::{debug, trace, warn};
+use tracing::{debug, error, trace, warn};
 
 use crate::application::ApplicationHandler;
 use crate::cursor::Cursor;
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error;
-use crate::error::EventLoopError;
-use crate::event::{self, Force, InnerSizeWriter, StartCause};
-use crate::event_loop::{self, ControlFlow, DeviceEvents};
+use crate::error::{EventLoopError, NotSupportedError};
+use crate::event::{self, AnimationCurve, InnerSizeWriter, SessionEvent, StartCause};
+use crate::event_loop::{self, ControlFlow, DeviceEventSubscription, MotionEventPolicy};
+use crate::keyboard::{self, Key, KeyCode};
+use crate::monitor::{ColorProperties, MonitorHandle as RootMonitorHandle};
+use crate::platform::android::{
+    DisplayCutout, DisplayCutoutMode, FrameRateCompatibility, FrameTiming, GameMode, PipParams,
+    SystemBarsBehavior, TrimLevel, MAX_SAVED_STATE_LEN,
+};
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::Fullscreen;
+use crate::utils::RedrawDamage;
 use crate::window::{
-    self, CursorGrabMode, CustomCursor, CustomCursorSource, ImePurpose, ResizeDirection, Theme,
-    WindowButtons, WindowLevel,
+    self, AspectRatio, CursorGrabMode, CustomCursor, CustomCursorSource, ImeCapabilities,
+    ImePurpose, ResizeDirection, Theme, WindowButtons, WindowLevel,
 };
 
+mod activity;
+mod choreographer;
+mod ime;
 mod keycodes;
+mod libandroid;
+mod performance_hint;
+mod touch;
 
+pub(crate) use self::performance_hint::PerformanceHintSession;
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
@@ -35,6 +51,13 @@ pub(crate) use crate::icon::NoIcon as PlatformIcon;
 
 static HAS_FOCUS: AtomicBool = AtomicBool::new(true);
 
+/// The duration of the animation of the on-screen keyboard since API 30, see
+/// `InsetsController.ANIMATION_DURATION_SYNC_IME_MS`.
+const IME_ANIMATION_DURATION: Duration = Duration::from_millis(285);
+
+/// `MotionEvent.FLAG_CANCELED`, set since API 33 when a pointer that went up was a palm.
+const FLAG_CANCELED: u32 = 0x20;
+
 /// Returns the minimum `Option<Duration>`, taking into account that `None`
 /// equates to an infinite timeout, not a zero timeout (so can't just use
 /// `Option::min`)
@@ -42,6 +65,12 @@ fn min_timeout(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
     a.map_or(b, |a_timeout| b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout))))
 }
 
+/// Precedes the state of the application when the activity saves its state, so that a
+/// recreation is detected even if the application didn't save anything.
+const SAVED_STATE_MARKER: u8 = 0x77;
+
+type SaveStateCallback = Box<dyn FnMut() -> Vec<u8>>;
+
 #[derive(Clone)]
 struct SharedFlagSetter {
     flag: Arc<AtomicBool>,
@@ -104,20 +133,51 @@ pub struct EventLoop {
     loop_running: bool, // Dispatched `NewEvents<Init>`
     running: bool,
     pending_redraw: bool,
+    /// Drives redraws if enabled with `with_choreographer_redraws()`.
+    choreographer: Option<choreographer::Choreographer>,
+    refresh_rate_observer: Option<choreographer::RefreshRateObserver>,
+    /// The last known mode of the display.
+    display_mode: Option<activity::DisplayMode>,
     cause: StartCause,
-    ignore_volume_keys: bool,
+    ignore_system_keys: bool,
     combining_accent: Option<char>,
+    /// The modifiers of the last key event.
+    modifiers: event::Modifiers,
+    /// The last insets that were reported.
+    insets: activity::WindowInsets,
+    insets_changed: bool,
+    /// Whether [`Ime::Enabled`] was emitted, which follows `ime_allowed` of the window.
+    ///
+    /// [`Ime::Enabled`]: event::Ime::Enabled
+    ime_enabled: bool,
+    ime: ime::ImeState,
+    /// A memory warning that arrived while suspended, with the most severe level.
+    pending_memory_warning: Option<Option<TrimLevel>>,
+    /// Whether the saved state should be loaded when the activity is resumed, i.e. the first time
+    /// after it was created.
+    restore_pending: bool,
+    /// The last reported multi-window modes.
+    window_modes: activity::WindowModes,
+    /// The last known game mode.
+    game_mode: Option<GameMode>,
+    /// Whether the battery saver was enabled when last checked.
+    power_saver: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) android_app: Option<AndroidApp>,
-    pub(crate) ignore_volume_keys: bool,
+    pub(crate) ignore_system_keys: bool,
+    pub(crate) choreographer_redraws: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { android_app: Default::default(), ignore_volume_keys: true }
+        Self {
+            android_app: Default::default(),
+            ignore_system_keys: true,
+            choreographer_redraws: false,
+        }
     }
 }
 
@@ -132,6 +192,10 @@ impl EventLoop {
              Android",
         );
         let redraw_flag = SharedFlag::new();
+        let choreographer = attributes
+            .choreographer_redraws
+            .then(|| choreographer::Choreographer::new(android_app.create_waker()))
+            .flatten();
 
         Ok(Self {
             android_app: android_app.clone(),
@@ -145,16 +209,50 @@ impl EventLoop {
                         android_app.create_waker(),
                     ),
                     proxy_wake_up,
+                    ime_allowed: Arc::new(AtomicBool::new(false)),
+                    ime_purpose: Arc::new(Mutex::new(ImePurpose::Normal)),
+                    window_settings: Arc::new(Mutex::new(activity::WindowSettings::default())),
+                    consumes_back: Arc::new(AtomicBool::new(false)),
+                    frame_timing: Arc::new(Mutex::new(None)),
+                    redraw_damage: Arc::default(),
+                    coalesced_touches: Arc::new(AtomicBool::new(false)),
+                    frame_rate: Arc::new(Mutex::new(None)),
+                    memory_trim_level: Cell::new(None),
+                    save_state_callback: RefCell::new(None),
+                    restored_state: RefCell::new(None),
+                    recreated: Cell::new(false),
+                    picture_in_picture: Arc::new(AtomicBool::new(false)),
+                    performance_hint_sessions: Default::default(),
                 },
+                user_event_queue: Default::default(),
                 _marker: PhantomData,
             },
             redraw_flag,
             loop_running: false,
             running: false,
             pending_redraw: false,
+            choreographer,
+            refresh_rate_observer: choreographer::RefreshRateObserver::new(
+                android_app.create_waker(),
+            ),
+            display_mode: None,
             cause: StartCause::Init,
-            ignore_volume_keys: attributes.ignore_volume_keys,
+            ignore_system_keys: attributes.ignore_system_keys,
             combining_accent: None,
+            modifiers: event::Modifiers::default(),
+            insets: activity::WindowInsets {
+                safe_area: PhysicalInsets::new(0, 0, 0, 0),
+                ime: PhysicalInsets::new(0, 0, 0, 0),
+                display_cutout: None,
+            },
+            insets_changed: false,
+            ime_enabled: false,
+            ime: ime::ImeState::default(),
+            pending_memory_warning: None,
+            restore_pending: true,
+            window_modes: activity::WindowModes::default(),
+            game_mode: None,
+            power_saver: None,
         })
     }
 
@@ -176,18 +274,54 @@ impl EventLoop {
 
             match event {
                 MainEvent::InitWindow { .. } => {
+                    // The keyboard is hidden and the settings of the window are reset when the
+                    // activity is recreated.
+                    if self.window_target.p.ime_allowed.load(Ordering::Relaxed) {
+                        let purpose = *self.window_target.p.ime_purpose.lock().unwrap();
+                        ime::set_editor_info(&self.android_app, purpose);
+                        self.android_app.show_soft_input(false);
+                    }
+                    let settings = *self.window_target.p.window_settings.lock().unwrap();
+                    if settings != activity::WindowSettings::default() {
+                        activity::apply_window_settings(&self.android_app, settings);
+                    }
+                    if let (Some((frame_rate, compatibility)), Some(native_window)) = (
+                        *self.window_target.p.frame_rate.lock().unwrap(),
+                        self.android_app.native_window(),
+                    ) {
+                        libandroid::set_frame_rate(&native_window, frame_rate, compatibility);
+                    }
+                    self.insets_changed = true;
+                    self.update_display_mode(app);
                     app.can_create_surfaces(self.window_target());
                 },
                 MainEvent::TerminateWindow { .. } => {
+                    if let Some(choreographer) = &self.choreographer {
+                        choreographer.cancel();
+                    }
                     app.destroy_surfaces(self.window_target());
                 },
-                MainEvent::WindowResized { .. } => resized = true,
+                MainEvent::WindowResized { .. } => {
+                    resized = true;
+                    self.insets_changed = true;
+                },
                 MainEvent::RedrawNeeded { .. } => pending_redraw = true,
                 MainEvent::ContentRectChanged { .. } => {
-                    warn!("TODO: find a way to notify application of content rect change");
+                    // The content rect shrinks when the keyboard is shown with `adjustResize`.
+                    self.insets_changed = true;
                 },
                 MainEvent::GainedFocus => {
                     HAS_FOCUS.store(true, Ordering::Relaxed);
+                    // The Game Dashboard takes the focus while the game mode is changed.
+                    self.update_game_mode(app);
+                    // The quick settings take the focus while the battery saver is toggled.
+                    self.update_power_saver(app);
+                    // The system bars are shown again e.g. when returning from
+                    // picture-in-picture or after a dialog was shown.
+                    let settings = *self.window_target.p.window_settings.lock().unwrap();
+                    if settings.fullscreen {
+                        activity::apply_window_settings(&self.android_app, settings);
+                    }
                     let window_id = window::WindowId(WindowId);
                     let event = event::WindowEvent::Focused(true);
                     app.window_event(self.window_target(), window_id, event);
@@ -195,10 +329,19 @@ impl EventLoop {
                 MainEvent::LostFocus => {
                     HAS_FOCUS.store(false, Ordering::Relaxed);
                     let window_id = window::WindowId(WindowId);
+                    // Modifiers that are released while unfocused aren't reported.
+                    if self.modifiers != event::Modifiers::default() {
+                        self.modifiers = event::Modifiers::default();
+                        let event = event::WindowEvent::ModifiersChanged(self.modifiers);
+                        app.window_event(self.window_target(), window_id, event);
+                    }
                     let event = event::WindowEvent::Focused(false);
                     app.window_event(self.window_target(), window_id, event);
                 },
                 MainEvent::ConfigChanged { .. } => {
+                    // E.g. the display cutout moves when the device is rotated.
+                    self.insets_changed = true;
+                    self.update_display_mode(app);
                     let monitor = MonitorHandle::new(self.android_app.clone());
                     let old_scale_factor = monitor.scale_factor();
                     let scale_factor = monitor.scale_factor();
@@ -218,24 +361,56 @@ impl EventLoop {
                     }
                 },
                 MainEvent::LowMemory => {
-                    app.memory_warning(self.window_target());
+                    // `GameActivity` reports `onTrimMemory()` as well.
+                    let level = activity::last_trim_level(&self.android_app);
+                    let pending = self.pending_memory_warning.flatten();
+                    self.pending_memory_warning = Some(level.max(pending));
                 },
                 MainEvent::Start => {
                     // XXX: how to forward this state to applications?
                     warn!("TODO: forward onStart notification to application");
                 },
-                MainEvent::Resume { .. } => {
+                MainEvent::Resume { loader, .. } => {
                     debug!("App Resumed - is running");
                     self.running = true;
+                    self.window_target.p.performance_hint_sessions.resume();
+                    self.update_game_mode(app);
+                    self.update_power_saver(app);
+                    if std::mem::take(&mut self.restore_pending) {
+                        let state = loader
+                            .load()
+                            .filter(|state| state.first() == Some(&SAVED_STATE_MARKER));
+                        let target = &self.window_target.p;
+                        target.recreated.set(state.is_some());
+                        *target.restored_state.borrow_mut() =
+                            state.filter(|state| state.len() > 1).map(|state| state[1..].to_vec());
+                    }
                 },
-                MainEvent::SaveState { .. } => {
-                    // XXX: how to forward this state to applications?
-                    // XXX: also how do we expose state restoration to apps?
-                    warn!("TODO: forward saveState notification to application");
+                MainEvent::SaveState { saver, .. } => {
+                    let app_state = self
+                        .window_target
+                        .p
+                        .save_state_callback
+                        .borrow_mut()
+                        .as_mut()
+                        .map(|callback| callback())
+                        .unwrap_or_default();
+                    let mut state = vec![SAVED_STATE_MARKER];
+                    if app_state.len() > MAX_SAVED_STATE_LEN {
+                        error!(
+                            "the saved state of {} bytes exceeds the limit of \
+                             {MAX_SAVED_STATE_LEN} bytes and is dropped",
+                            app_state.len(),
+                        );
+                    } else {
+                        state.extend_from_slice(&app_state);
+                    }
+                    saver.store(&state);
                 },
                 MainEvent::Pause => {
                     debug!("App Paused - stopped running");
                     self.running = false;
+                    self.window_target.p.performance_hint_sessions.pause();
                 },
                 MainEvent::Stop => {
                     // XXX: how to forward this state to applications?
@@ -245,19 +420,50 @@ impl EventLoop {
                     // XXX: maybe exit mainloop to drop things before being
                     // killed by the OS?
                     warn!("TODO: forward onDestroy notification to application");
+                    // The loop keeps running if the activity is recreated.
+                    self.restore_pending = true;
                 },
-                MainEvent::InsetsChanged { .. } => {
-                    // XXX: how to forward this state to applications?
-                    warn!("TODO: handle Android InsetsChanged notification");
-                },
+                MainEvent::InsetsChanged { .. } => self.insets_changed = true,
                 unknown => {
                     trace!("Unknown MainEvent {unknown:?} (ignored)");
                 },
             }
+
+            // Changes of the multi-window modes aren't forwarded by `android-activity`, but cause
+            // e.g. a configuration change or a pause.
+            self.update_window_modes(app);
         } else {
             trace!("No main event to handle");
         }
 
+        // Memory warnings are delivered once the application is running again. This doesn't
+        // request a redraw, since the application is likely in the background.
+        if self.is_visible() {
+            if let Some(level) = self.pending_memory_warning.take() {
+                self.window_target.p.memory_trim_level.set(level);
+                app.memory_warning(self.window_target());
+                self.window_target.p.memory_trim_level.set(None);
+            }
+        }
+
+        if self.ime_allowed_changed() {
+            self.ime_enabled = !self.ime_enabled;
+            self.ime = ime::ImeState::default();
+            let event = if self.ime_enabled {
+                // Start from an empty buffer, the text of the application is unknown.
+                self.android_app.set_text_input_state(TextInputState {
+                    text: String::new(),
+                    selection: TextSpan { start: 0, end: 0 },
+                    compose_region: None,
+                });
+                event::Ime::Enabled
+            } else {
+                event::Ime::Disabled
+            };
+            let window_id = window::WindowId(WindowId);
+            app.window_event(self.window_target(), window_id, event::WindowEvent::Ime(event));
+        }
+
         // temporarily decouple `android_app` from `self` so we aren't holding
         // a borrow of `self` while iterating
         let android_app = self.android_app.clone();
@@ -281,7 +487,11 @@ impl EventLoop {
             app.proxy_wake_up(self.window_target());
         }
 
-        if self.running {
+        if self.refresh_rate_observer.as_ref().is_some_and(|observer| observer.take_changed()) {
+            self.update_display_mode(app);
+        }
+
+        if self.is_visible() {
             if resized {
                 let size = if let Some(native_window) = self.android_app.native_window().as_ref() {
                     let width = native_window.width() as _;
@@ -295,12 +505,27 @@ impl EventLoop {
                 app.window_event(self.window_target(), window_id, event);
             }
 
+            if std::mem::take(&mut self.insets_changed) {
+                self.update_insets(app);
+            }
+
             pending_redraw |= self.redraw_flag.get_and_reset();
-            if pending_redraw {
+            let frame = match &self.choreographer {
+                // Wait for the next frame, requesting it if needed.
+                Some(choreographer) if pending_redraw => {
+                    choreographer.request_frame();
+                    choreographer.take_frame().map(Some)
+                },
+                _ => pending_redraw.then_some(None),
+            };
+            if let Some(frame_timing) = frame {
                 pending_redraw = false;
+                *self.window_target.p.frame_timing.lock().unwrap() = frame_timing;
                 let window_id = window::WindowId(WindowId);
                 let event = event::WindowEvent::RedrawRequested;
+                self.window_target.p.redraw_damage.lock().unwrap().deliver();
                 app.window_event(self.window_target(), window_id, event);
+                *self.window_target.p.frame_timing.lock().unwrap() = None;
             }
         }
 
@@ -310,6 +535,123 @@ impl EventLoop {
         self.pending_redraw = pending_redraw;
     }
 
+    /// Emits [`WindowEvent::PictureInPictureChanged`] and [`WindowEvent::MultiWindowChanged`] if
+    /// the respective mode changed.
+    ///
+    /// [`WindowEvent::PictureInPictureChanged`]: event::WindowEvent::PictureInPictureChanged
+    /// [`WindowEvent::MultiWindowChanged`]: event::WindowEvent::MultiWindowChanged
+    fn update_window_modes<A: ApplicationHandler>(&mut self, app: &mut A) {
+        let modes = activity::window_modes(&self.android_app);
+        let previous = std::mem::replace(&mut self.window_modes, modes);
+        let window_id = window::WindowId(WindowId);
+
+        if modes.picture_in_picture != previous.picture_in_picture {
+            self.window_target
+                .p
+                .picture_in_picture
+                .store(modes.picture_in_picture, Ordering::Relaxed);
+            // The safe area is empty in picture-in-picture.
+            self.insets_changed = true;
+            if !modes.picture_in_picture {
+                // Hide the system bars again when returning to fullscreen.
+                let settings = *self.window_target.p.window_settings.lock().unwrap();
+                if settings.fullscreen {
+                    activity::apply_window_settings(&self.android_app, settings);
+                }
+            }
+            let event = event::WindowEvent::PictureInPictureChanged(modes.picture_in_picture);
+            app.window_event(self.window_target(), window_id, event);
+        }
+
+        if modes.multi_window != previous.multi_window {
+            let event = event::WindowEvent::MultiWindowChanged(modes.multi_window);
+            app.window_event(self.window_target(), window_id, event);
+        }
+    }
+
+    /// Emits [`ApplicationHandler::power_state_changed`] if the game mode changed since it was
+    /// last checked.
+    fn update_game_mode<A: ApplicationHandler>(&mut self, app: &mut A) {
+        let mode = activity::game_mode(&self.android_app);
+        if self.game_mode.replace(mode).is_some_and(|previous| previous != mode) {
+            app.power_state_changed(self.window_target());
+        }
+    }
+
+    /// Emits [`SessionEvent::PowerSaverChanged`] if the battery saver was toggled since it was
+    /// last checked.
+    fn update_power_saver<A: ApplicationHandler>(&mut self, app: &mut A) {
+        let enabled = activity::power_save_mode(&self.android_app);
+        if self.power_saver.replace(enabled).is_some_and(|previous| previous != enabled) {
+            app.session_event(self.window_target(), SessionEvent::PowerSaverChanged(enabled));
+        }
+    }
+
+    /// Returns whether the window is shown, i.e. the activity is running or paused in
+    /// picture-in-picture.
+    fn is_visible(&self) -> bool {
+        self.running || self.window_modes.picture_in_picture
+    }
+
+    /// Emits [`ApplicationHandler::monitor_video_mode_changed`] if the mode of the display
+    /// changed since it was last checked.
+    fn update_display_mode<A: ApplicationHandler>(&mut self, app: &mut A) {
+        let Some(mode) = activity::current_display_mode(&self.android_app) else {
+            return;
+        };
+        if self.display_mode.replace(mode).is_some_and(|previous| previous != mode) {
+            let monitor = RootMonitorHandle { inner: MonitorHandle::new(self.android_app.clone()) };
+            app.monitor_video_mode_changed(self.window_target(), monitor);
+        }
+    }
+
+    /// Emits [`WindowEvent::SafeAreaChanged`], [`WindowEvent::DisplayCutoutChanged`] and
+    /// [`WindowEvent::SoftKeyboardFrameChanged`] if the respective insets changed.
+    ///
+    /// [`WindowEvent::SafeAreaChanged`]: event::WindowEvent::SafeAreaChanged
+    /// [`WindowEvent::DisplayCutoutChanged`]: event::WindowEvent::DisplayCutoutChanged
+    /// [`WindowEvent::SoftKeyboardFrameChanged`]: event::WindowEvent::SoftKeyboardFrameChanged
+    fn update_insets<A: ApplicationHandler>(&mut self, app: &mut A) {
+        let Some(mut insets) = activity::window_insets(&self.android_app) else {
+            return;
+        };
+        if self.window_modes.picture_in_picture {
+            insets.safe_area = PhysicalInsets::new(0, 0, 0, 0);
+        }
+        let previous = std::mem::replace(&mut self.insets, insets);
+        let window_id = window::WindowId(WindowId);
+
+        if insets.safe_area != previous.safe_area {
+            let event = event::WindowEvent::SafeAreaChanged(insets.safe_area);
+            app.window_event(self.window_target(), window_id, event);
+        }
+
+        if insets.display_cutout != previous.display_cutout {
+            let event = event::WindowEvent::DisplayCutoutChanged;
+            app.window_event(self.window_target(), window_id, event);
+        }
+
+        if insets.ime == previous.ime {
+            return;
+        }
+
+        // The insets are only reported once the animation started. Since API 30 the system
+        // animates the keyboard with a fixed duration, before that it appears instantly.
+        let (animation_duration, animation_curve) =
+            if self.android_app.config().sdk_version() >= activity::API_30 {
+                (IME_ANIMATION_DURATION, AnimationCurve::Other)
+            } else {
+                (Duration::ZERO, AnimationCurve::Linear)
+            };
+
+        let event = event::WindowEvent::SoftKeyboardFrameChanged {
+            insets: insets.ime,
+            animation_duration,
+            animation_curve,
+        };
+        app.window_event(self.window_target(), window_id, event);
+    }
+
     fn handle_input_event<A: ApplicationHandler>(
         &mut self,
         android_app: &AndroidApp,
@@ -322,10 +664,59 @@ impl EventLoop {
                 let window_id = window::WindowId(WindowId);
                 let device_id = event::DeviceId(DeviceId(motion_event.device_id()));
 
+                let hover_phase = match motion_event.action() {
+                    MotionAction::HoverEnter => Some(event::HoverPhase::Entered),
+                    MotionAction::HoverMove => Some(event::HoverPhase::Moved),
+                    MotionAction::HoverExit => Some(event::HoverPhase::Exited),
+                    _ => None,
+                };
+                if let Some(phase) = hover_phase {
+                    for pointer in motion_event.pointers() {
+                        let tool_type = pointer.tool_type();
+                        let location = PhysicalPosition::new(pointer.x() as _, pointer.y() as _);
+                        // A hovering mouse is a cursor.
+                        if tool_type == ToolType::Mouse {
+                            let event = match phase {
+                                event::HoverPhase::Entered => {
+                                    event::WindowEvent::CursorEntered { device_id }
+                                },
+                                event::HoverPhase::Moved => event::WindowEvent::CursorMoved {
+                                    device_id,
+                                    position: location,
+                                },
+                                event::HoverPhase::Exited => {
+                                    event::WindowEvent::CursorLeft { device_id }
+                                },
+                            };
+                            app.window_event(self.window_target(), window_id, event);
+                            continue;
+                        }
+                        if !matches!(tool_type, ToolType::Stylus | ToolType::Eraser) {
+                            continue;
+                        }
+
+                        let (altitude_angle, azimuth_angle) =
+                            touch::stylus_angles(tool_type, |axis| pointer.axis_value(axis));
+                        let event = event::WindowEvent::StylusHover {
+                            device_id,
+                            phase,
+                            location,
+                            altitude_angle,
+                            azimuth_angle,
+                        };
+                        app.window_event(self.window_target(), window_id, event);
+                    }
+                }
+
                 let phase = match motion_event.action() {
                     MotionAction::Down | MotionAction::PointerDown => {
                         Some(event::TouchPhase::Started)
                     },
+                    MotionAction::Up | MotionAction::PointerUp
+                        if motion_event.flags().0 & FLAG_CANCELED != 0 =>
+                    {
+                        Some(event::TouchPhase::Cancelled)
+                    },
                     MotionAction::Up | MotionAction::PointerUp => Some(event::TouchPhase::Ended),
                     MotionAction::Move => Some(event::TouchPhase::Moved),
                     MotionAction::Cancel => Some(event::TouchPhase::Cancelled),
@@ -335,45 +726,88 @@ impl EventLoop {
                 };
                 if let Some(phase) = phase {
                     let pointers: Box<dyn Iterator<Item = android_activity::input::Pointer<'_>>> =
-                        match phase {
-                            event::TouchPhase::Started | event::TouchPhase::Ended => {
-                                Box::new(std::iter::once(
-                                    motion_event.pointer_at_index(motion_event.pointer_index()),
-                                ))
+                        match motion_event.action() {
+                            MotionAction::Down
+                            | MotionAction::PointerDown
+                            | MotionAction::Up
+                            | MotionAction::PointerUp => Box::new(std::iter::once(
+                                motion_event.pointer_at_index(motion_event.pointer_index()),
+                            )),
+                            _ => Box::new(motion_event.pointers()),
+                        };
+
+                    // The samples that were batched into this event, ordered by time across
+                    // pointers.
+                    if phase == event::TouchPhase::Moved
+                        && self.window_target.p.coalesced_touches.load(Ordering::Relaxed)
+                    {
+                        let history_size = motion_event.pointer_at_index(0).history().len();
+                        for history_index in 0..history_size {
+                            for pointer in motion_event.pointers() {
+                                let tool_type = pointer.tool_type();
+                                if tool_type == ToolType::Palm {
+                                    continue;
+                                }
+                                let Some(sample) = pointer.history().nth(history_index) else {
+                                    continue;
+                                };
+                                let event = event::WindowEvent::Touch(touch::to_touch(
+                                    device_id,
+                                    phase,
+                                    pointer.pointer_id(),
+                                    tool_type,
+                                    |axis| sample.axis_value(axis),
+                                ));
+                                app.window_event(self.window_target(), window_id, event);
+                            }
+                        }
+                    }
+
+                    for pointer in pointers {
+                        let tool_type = pointer.tool_type();
+                        // Palms are reported as fingers until they are recognized, which
+                        // cancels them.
+                        let phase = match phase {
+                            event::TouchPhase::Started | event::TouchPhase::Moved
+                                if tool_type == ToolType::Palm =>
+                            {
+                                continue;
                             },
-                            event::TouchPhase::Moved | event::TouchPhase::Cancelled => {
-                                Box::new(motion_event.pointers())
+                            event::TouchPhase::Ended if tool_type == ToolType::Palm => {
+                                event::TouchPhase::Cancelled
                             },
+                            phase => phase,
                         };
 
-                    for pointer in pointers {
-                        let location =
-                            PhysicalPosition { x: pointer.x() as _, y: pointer.y() as _ };
-                        trace!(
-                            "Input event {device_id:?}, {phase:?}, loc={location:?}, \
-                             pointer={pointer:?}"
-                        );
+                        trace!("Input event {device_id:?}, {phase:?}, pointer={pointer:?}");
 
-                        let event = event::WindowEvent::Touch(event::Touch {
+                        let event = event::WindowEvent::Touch(touch::to_touch(
                             device_id,
                             phase,
-                            location,
-                            id: pointer.pointer_id() as u64,
-                            force: Some(Force::Normalized(pointer.pressure() as f64)),
-                        });
-
+                            pointer.pointer_id(),
+                            tool_type,
+                            |axis| pointer.axis_value(axis),
+                        ));
                         app.window_event(self.window_target(), window_id, event);
                     }
                 }
             },
             InputEvent::KeyEvent(key) => {
                 match key.key_code() {
-                    // Flag keys related to volume as unhandled. While winit does not have a way for
-                    // applications to configure what keys to flag as handled,
-                    // this appears to be a good default until winit
-                    // can be configured.
-                    Keycode::VolumeUp | Keycode::VolumeDown | Keycode::VolumeMute
-                        if self.ignore_volume_keys =>
+                    // Let the system handle gamepad buttons, which then sends a fallback key for
+                    // some of them, flagged with `FLAG_FALLBACK`, e.g. `Back` for `ButtonB`.
+                    keycode if keycodes::is_gamepad_button(keycode) => {
+                        input_status = InputStatus::Unhandled
+                    },
+                    // Flag system keys, e.g. the volume and media keys, as unhandled unless the
+                    // application opted into handling them, so that the system e.g. changes the
+                    // volume. This includes their fallback keys.
+                    keycode if keycodes::is_system_key(keycode) && self.ignore_system_keys => {
+                        input_status = InputStatus::Unhandled
+                    },
+                    // Let the system finish the activity unless the application handles it.
+                    Keycode::Back
+                        if !self.window_target.p.consumes_back.load(Ordering::Relaxed) =>
                     {
                         input_status = InputStatus::Unhandled
                     },
@@ -391,6 +825,14 @@ impl EventLoop {
                         );
 
                         let window_id = window::WindowId(WindowId);
+
+                        let modifiers = keycodes::to_modifiers(key.meta_state());
+                        if modifiers != self.modifiers {
+                            self.modifiers = modifiers;
+                            let event = event::WindowEvent::ModifiersChanged(modifiers);
+                            app.window_event(self.window_target(), window_id, event);
+                        }
+
                         let event = event::WindowEvent::KeyboardInput {
                             device_id: event::DeviceId(DeviceId(key.device_id())),
                             event: event::KeyEvent {
@@ -406,9 +848,30 @@ impl EventLoop {
                         };
 
                         app.window_event(self.window_target(), window_id, event);
+
+                        if keycode == Keycode::Back && key.action() == KeyAction::Up {
+                            let event = event::WindowEvent::BackInvoked;
+                            app.window_event(self.window_target(), window_id, event);
+                        }
                     },
                 }
             },
+            InputEvent::TextEvent(state) => {
+                if !self.ime_enabled {
+                    return input_status;
+                }
+                let window_id = window::WindowId(WindowId);
+                for event in self.ime.update(state) {
+                    app.window_event(
+                        self.window_target(),
+                        window_id,
+                        event::WindowEvent::Ime(event),
+                    );
+                }
+            },
+            InputEvent::TextAction(action) => {
+                trace!("Ignoring text action {action:?}");
+            },
             _ => {
                 warn!("Unknown android_activity input event {event:?}")
             },
@@ -481,10 +944,10 @@ impl EventLoop {
     ) {
         let start = Instant::now();
 
-        self.pending_redraw |= self.redraw_flag.get_and_reset();
-
-        timeout = if self.running
-            && (self.pending_redraw || self.window_target.p.proxy_wake_up.load(Ordering::Relaxed))
+        timeout = if self.is_visible()
+            && (self.redraw_ready()
+                || self.window_target.p.proxy_wake_up.load(Ordering::Relaxed)
+                || self.ime_allowed_changed())
         {
             // If we already have work to do then we don't want to block on the next poll
             Some(Duration::ZERO)
@@ -514,10 +977,15 @@ impl EventLoop {
                     // For now, user_events and redraw_requests are the only reasons to expect
                     // a wake up here so we can ignore the wake up if there are no events/requests.
                     // We also ignore wake ups while suspended.
-                    self.pending_redraw |= self.redraw_flag.get_and_reset();
-                    if !self.running
-                        || (!self.pending_redraw
-                            && !self.window_target.p.proxy_wake_up.load(Ordering::Relaxed))
+                    let refresh_rate_changed = self
+                        .refresh_rate_observer
+                        .as_ref()
+                        .is_some_and(|observer| observer.changed());
+                    if !self.is_visible()
+                        || (!self.redraw_ready()
+                            && !self.window_target.p.proxy_wake_up.load(Ordering::Relaxed)
+                            && !refresh_rate_changed
+                            && !self.ime_allowed_changed())
                     {
                         return;
                     }
@@ -547,6 +1015,29 @@ impl EventLoop {
         });
     }
 
+    /// Returns whether a redraw is pending and can be emitted right away.
+    ///
+    /// With a choreographer, this requests the next frame and returns whether it has arrived.
+    fn redraw_ready(&mut self) -> bool {
+        self.pending_redraw |= self.redraw_flag.get_and_reset();
+        match &self.choreographer {
+            Some(choreographer) if self.pending_redraw => {
+                choreographer.request_frame();
+                choreographer.frame_ready()
+            },
+            _ => self.pending_redraw,
+        }
+    }
+
+    /// Returns whether the window allowed or disallowed the IME since [`Ime::Enabled`] or
+    /// [`Ime::Disabled`] was last emitted.
+    ///
+    /// [`Ime::Enabled`]: event::Ime::Enabled
+    /// [`Ime::Disabled`]: event::Ime::Disabled
+    fn ime_allowed_changed(&self) -> bool {
+        self.window_target.p.ime_allowed.load(Ordering::Relaxed) != self.ime_enabled
+    }
+
     pub fn window_target(&self) -> &event_loop::ActiveEventLoop {
         &self.window_target
     }
@@ -579,6 +1070,27 @@ pub struct ActiveEventLoop {
     exit: Cell<bool>,
     redraw_requester: RedrawRequester,
     proxy_wake_up: Arc<AtomicBool>,
+    /// Shared with the [`Window`] to show the keyboard again after the activity was recreated.
+    ime_allowed: Arc<AtomicBool>,
+    ime_purpose: Arc<Mutex<ImePurpose>>,
+    window_settings: Arc<Mutex<activity::WindowSettings>>,
+    consumes_back: Arc<AtomicBool>,
+    /// The timing of the frame while [`WindowEvent::RedrawRequested`] is emitted.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: event::WindowEvent::RedrawRequested
+    frame_timing: Arc<Mutex<Option<FrameTiming>>>,
+    /// Shared with the [`Window`] since there is only one.
+    redraw_damage: Arc<Mutex<RedrawDamage>>,
+    coalesced_touches: Arc<AtomicBool>,
+    /// Applied again when the surface is recreated.
+    frame_rate: Arc<Mutex<Option<(f32, FrameRateCompatibility)>>>,
+    /// The level of the memory warning while [`ApplicationHandler::memory_warning`] is called.
+    memory_trim_level: Cell<Option<TrimLevel>>,
+    save_state_callback: RefCell<Option<SaveStateCallback>>,
+    restored_state: RefCell<Option<Vec<u8>>>,
+    recreated: Cell<bool>,
+    picture_in_picture: Arc<AtomicBool>,
+    performance_hint_sessions: performance_hint::PerformanceHintSessions,
 }
 
 impl ActiveEventLoop {
@@ -602,7 +1114,21 @@ impl ActiveEventLoop {
     }
 
     #[inline]
-    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+    pub fn listen_device_events(&self, _subscription: DeviceEventSubscription) {}
+
+    pub fn set_motion_event_policy(&self, _policy: MotionEventPolicy) {}
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        MotionEventPolicy::Coalesced
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        keyboard::layout_independent_key(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        keyboard::find_key_code(key, keyboard::layout_independent_keys())
+    }
 
     #[cfg(feature = "rwh_05")]
     #[inline]
@@ -641,6 +1167,36 @@ impl ActiveEventLoop {
     pub(crate) fn owned_display_handle(&self) -> OwnedDisplayHandle {
         OwnedDisplayHandle
     }
+
+    pub(crate) fn memory_trim_level(&self) -> Option<TrimLevel> {
+        self.memory_trim_level.get()
+    }
+
+    pub(crate) fn set_save_state_callback(&self, callback: Option<SaveStateCallback>) {
+        *self.save_state_callback.borrow_mut() = callback;
+    }
+
+    pub(crate) fn restored_state(&self) -> Option<Vec<u8>> {
+        self.restored_state.borrow().clone()
+    }
+
+    pub(crate) fn is_recreated(&self) -> bool {
+        self.recreated.get()
+    }
+
+    pub(crate) fn game_mode(&self) -> GameMode {
+        activity::game_mode(&self.app)
+    }
+
+    pub(crate) fn create_performance_hint_session(
+        &self,
+        thread_ids: &[i32],
+        target_work_duration: Duration,
+    ) -> Result<PerformanceHintSession, NotSupportedError> {
+        self.performance_hint_sessions
+            .create(thread_ids, target_work_duration)
+            .ok_or_else(NotSupportedError::new)
+    }
 }
 
 #[derive(Clone)]
@@ -698,16 +1254,43 @@ pub struct PlatformSpecificWindowAttributes;
 pub(crate) struct Window {
     app: AndroidApp,
     redraw_requester: RedrawRequester,
+    ime_allowed: Arc<AtomicBool>,
+    ime_purpose: Arc<Mutex<ImePurpose>>,
+    /// The last cursor area, in physical pixels, sent again to the IME when it gets allowed.
+    ime_cursor_area: Mutex<Option<(PhysicalPosition<f32>, PhysicalSize<f32>)>>,
+    window_settings: Arc<Mutex<activity::WindowSettings>>,
+    consumes_back: Arc<AtomicBool>,
+    frame_timing: Arc<Mutex<Option<FrameTiming>>>,
+    redraw_damage: Arc<Mutex<RedrawDamage>>,
+    coalesced_touches: Arc<AtomicBool>,
+    frame_rate: Arc<Mutex<Option<(f32, FrameRateCompatibility)>>>,
+    picture_in_picture: Arc<AtomicBool>,
+    /// The last title set, which isn't shown anywhere.
+    title: Mutex<String>,
 }
 
 impl Window {
     pub(crate) fn new(
         el: &ActiveEventLoop,
-        _window_attrs: window::WindowAttributes,
+        window_attrs: window::WindowAttributes,
     ) -> Result<Self, error::OsError> {
         // FIXME this ignores requested window attributes
 
-        Ok(Self { app: el.app.clone(), redraw_requester: el.redraw_requester.clone() })
+        Ok(Self {
+            app: el.app.clone(),
+            redraw_requester: el.redraw_requester.clone(),
+            ime_allowed: el.ime_allowed.clone(),
+            ime_purpose: el.ime_purpose.clone(),
+            ime_cursor_area: Mutex::new(None),
+            window_settings: el.window_settings.clone(),
+            consumes_back: el.consumes_back.clone(),
+            frame_timing: el.frame_timing.clone(),
+            redraw_damage: el.redraw_damage.clone(),
+            coalesced_touches: el.coalesced_touches.clone(),
+            frame_rate: el.frame_rate.clone(),
+            picture_in_picture: el.picture_in_picture.clone(),
+            title: Mutex::new(window_attrs.title),
+        })
     }
 
     pub(crate) fn maybe_queue_on_main(&self, f: impl FnOnce(&Self) + Send + 'static) {
@@ -741,11 +1324,24 @@ impl Window {
     }
 
     pub fn request_redraw(&self) {
+        self.redraw_damage.lock().unwrap().add_full();
+        self.redraw_requester.request_redraw()
+    }
+
+    pub fn request_redraw_with_damage(&self, rects: &[window::Rect<u32>]) {
+        self.redraw_damage.lock().unwrap().add(rects, self.inner_size());
         self.redraw_requester.request_redraw()
     }
 
+    pub fn redraw_damage(&self) -> Option<Vec<window::Rect<u32>>> {
+        let size = self.inner_size();
+        self.redraw_damage.lock().unwrap().current(size).map(<[_]>::to_vec)
+    }
+
     pub fn pre_present_notify(&self) {}
 
+    pub fn set_presentation_feedback(&self, _enabled: bool) {}
+
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
         Err(error::NotSupportedError::new())
     }
@@ -780,12 +1376,22 @@ impl Window {
 
     pub fn set_resize_increments(&self, _increments: Option<Size>) {}
 
-    pub fn set_title(&self, _title: &str) {}
+    pub fn set_aspect_ratio(&self, _aspect_ratio: Option<AspectRatio>) {}
+
+    pub fn set_title(&self, title: &str) {
+        *self.title.lock().unwrap() = title.to_owned();
+    }
 
     pub fn set_transparent(&self, _transparent: bool) {}
 
     pub fn set_blur(&self, _blur: bool) {}
 
+    pub fn set_opacity(&self, _opacity: f32) {}
+
+    pub fn opacity(&self) -> f32 {
+        1.0
+    }
+
     pub fn set_visible(&self, _visibility: bool) {}
 
     pub fn is_visible(&self) -> Option<bool> {
@@ -804,6 +1410,10 @@ impl Window {
         WindowButtons::all()
     }
 
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::empty()
+    }
+
     pub fn set_minimized(&self, _minimized: bool) {}
 
     pub fn is_minimized(&self) -> Option<bool> {
@@ -816,12 +1426,14 @@ impl Window {
         false
     }
 
-    pub fn set_fullscreen(&self, _monitor: Option<Fullscreen>) {
-        warn!("Cannot set fullscreen on Android");
+    pub fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.update_window_settings(|settings| settings.fullscreen = fullscreen.is_some())
     }
 
     pub fn fullscreen(&self) -> Option<Fullscreen> {
-        None
+        let fullscreen = self.window_settings.lock().unwrap().fullscreen
+            && activity::system_bars_hidden(&self.app).unwrap_or(true);
+        fullscreen.then_some(Fullscreen::Borderless(None))
     }
 
     pub fn set_decorations(&self, _decorations: bool) {}
@@ -834,16 +1446,58 @@ impl Window {
 
     pub fn set_window_icon(&self, _window_icon: Option<crate::icon::Icon>) {}
 
-    pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        let scale_factor = self.scale_factor();
+        let position: PhysicalPosition<f32> = position.to_physical(scale_factor);
+        let size: PhysicalSize<f32> = size.to_physical(scale_factor);
+        *self.ime_cursor_area.lock().unwrap() = Some((position, size));
+        if self.ime_allowed.load(Ordering::Relaxed) {
+            activity::update_cursor_anchor_info(&self.app, position.x, position.y, size.height);
+        }
+    }
+
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if self.ime_allowed.swap(allowed, Ordering::Relaxed) == allowed {
+            return;
+        }
+
+        if allowed {
+            ime::set_editor_info(&self.app, *self.ime_purpose.lock().unwrap());
+            self.app.show_soft_input(false);
+            if let Some((position, size)) = *self.ime_cursor_area.lock().unwrap() {
+                activity::update_cursor_anchor_info(&self.app, position.x, position.y, size.height);
+            }
+        } else {
+            self.app.hide_soft_input(false);
+        }
+        // Emit `Ime::Enabled` or `Ime::Disabled`.
+        self.app.create_waker().wake();
+    }
 
-    pub fn set_ime_allowed(&self, _allowed: bool) {}
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        *self.ime_purpose.lock().unwrap() = purpose;
+        if self.ime_allowed.load(Ordering::Relaxed) {
+            ime::set_editor_info(&self.app, purpose);
+        }
+    }
 
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        let capabilities = ImeCapabilities::CURSOR_AREA | ImeCapabilities::PURPOSE;
+        #[cfg(feature = "android-game-activity")]
+        let capabilities = capabilities | ImeCapabilities::PREEDIT;
+        Some(capabilities)
+    }
 
     pub fn focus_window(&self) {}
 
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
+    pub fn set_progress(&self, _state: window::ProgressState) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    pub fn set_badge_count(&self, _count: Option<u64>) {}
+
     pub fn set_cursor(&self, _: Cursor) {}
 
     pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
@@ -860,6 +1514,13 @@ impl Window {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    pub fn set_transient_parent(
+        &self,
+        _parent: Option<&Window>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     pub fn drag_resize_window(
         &self,
         _direction: ResizeDirection,
@@ -867,6 +1528,13 @@ impl Window {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    pub fn start_drag_and_drop(
+        &self,
+        _payload: window::DragPayload,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
@@ -942,6 +1610,110 @@ impl Window {
         self.app.content_rect()
     }
 
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        if self.picture_in_picture.load(Ordering::Relaxed) {
+            return PhysicalInsets::new(0, 0, 0, 0);
+        }
+        activity::window_insets(&self.app)
+            .map_or(PhysicalInsets::new(0, 0, 0, 0), |insets| insets.safe_area)
+    }
+
+    pub fn display_cutout_mode(&self) -> DisplayCutoutMode {
+        self.window_settings.lock().unwrap().display_cutout_mode
+    }
+
+    pub fn set_display_cutout_mode(&self, mode: DisplayCutoutMode) {
+        self.update_window_settings(|settings| settings.display_cutout_mode = mode)
+    }
+
+    pub fn display_cutout(&self) -> Option<DisplayCutout> {
+        activity::window_insets(&self.app).and_then(|insets| insets.display_cutout)
+    }
+
+    pub fn is_edge_to_edge(&self) -> bool {
+        self.window_settings.lock().unwrap().edge_to_edge
+    }
+
+    pub fn set_edge_to_edge(&self, edge_to_edge: bool) {
+        self.update_window_settings(|settings| settings.edge_to_edge = edge_to_edge)
+    }
+
+    pub fn system_bars_behavior(&self) -> SystemBarsBehavior {
+        self.window_settings.lock().unwrap().system_bars_behavior
+    }
+
+    pub fn set_system_bars_behavior(&self, behavior: SystemBarsBehavior) {
+        self.update_window_settings(|settings| settings.system_bars_behavior = behavior)
+    }
+
+    pub fn is_keep_screen_on(&self) -> bool {
+        self.window_settings.lock().unwrap().keep_screen_on
+    }
+
+    pub fn set_keep_screen_on(&self, keep_screen_on: bool) {
+        self.update_window_settings(|settings| settings.keep_screen_on = keep_screen_on)
+    }
+
+    pub fn is_sustained_performance_mode(&self) -> bool {
+        self.window_settings.lock().unwrap().sustained_performance_mode
+    }
+
+    pub fn set_sustained_performance_mode(&self, enabled: bool) -> Result<(), NotSupportedError> {
+        if enabled && !activity::sustained_performance_mode_supported(&self.app) {
+            return Err(NotSupportedError::new());
+        }
+        self.update_window_settings(|settings| settings.sustained_performance_mode = enabled);
+        Ok(())
+    }
+
+    pub fn frame_timing(&self) -> Option<FrameTiming> {
+        *self.frame_timing.lock().unwrap()
+    }
+
+    pub fn set_coalesced_touches(&self, enabled: bool) {
+        self.coalesced_touches.store(enabled, Ordering::Relaxed)
+    }
+
+    pub fn set_frame_rate(
+        &self,
+        frame_rate: f32,
+        compatibility: FrameRateCompatibility,
+    ) -> Result<(), NotSupportedError> {
+        if self.app.config().sdk_version() < activity::API_30 {
+            return Err(NotSupportedError::new());
+        }
+        *self.frame_rate.lock().unwrap() = Some((frame_rate, compatibility));
+        if let Some(native_window) = self.app.native_window() {
+            libandroid::set_frame_rate(&native_window, frame_rate, compatibility);
+        }
+        Ok(())
+    }
+
+    pub fn enter_picture_in_picture(&self, params: PipParams) -> Result<(), NotSupportedError> {
+        if !activity::picture_in_picture_supported(&self.app) {
+            return Err(NotSupportedError::new());
+        }
+        activity::enter_picture_in_picture(&self.app, params);
+        Ok(())
+    }
+
+    fn update_window_settings(&self, f: impl FnOnce(&mut activity::WindowSettings)) {
+        let mut settings = self.window_settings.lock().unwrap();
+        let previous = *settings;
+        f(&mut settings);
+        if *settings != previous {
+            activity::apply_window_settings(&self.app, *settings);
+        }
+    }
+
+    pub fn consumes_back(&self) -> bool {
+        self.consumes_back.load(Ordering::Relaxed)
+    }
+
+    pub fn set_consumes_back(&self, consumes: bool) {
+        self.consumes_back.store(consumes, Ordering::Relaxed)
+    }
+
     pub fn set_theme(&self, _theme: Option<Theme>) {}
 
     pub fn theme(&self) -> Option<Theme> {
@@ -955,7 +1727,7 @@ impl Window {
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        self.title.lock().unwrap().clone()
     }
 
     pub fn reset_dead_keys(&self) {}
@@ -971,6 +1743,10 @@ impl Display for OsError {
     }
 }
 
+pub(crate) fn platform_error(_error: &OsError) -> Option<error::PlatformError> {
+    None
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct MonitorHandle {
     app: AndroidApp,
@@ -1012,19 +1788,29 @@ impl MonitorHandle {
     }
 
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
-        // FIXME no way to get real refresh rate for now.
+        activity::refresh_rate_millihertz(&self.app)
+    }
+
+    pub fn color_properties(&self) -> Option<ColorProperties> {
         None
     }
 
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
-        let size = self.size().into();
-        // FIXME this is not the real refresh rate
-        // (it is guaranteed to support 32 bit color though)
-        std::iter::once(VideoModeHandle {
-            size,
+        // Android is guaranteed to support 32 bit color.
+        let modes = activity::supported_display_modes(&self.app).unwrap_or_else(|| {
+            // Before API 23 only the current mode is known.
+            vec![activity::DisplayMode {
+                id: 0,
+                size: self.size(),
+                refresh_rate_millihertz: self.refresh_rate_millihertz().unwrap_or(60000),
+            }]
+        });
+        let monitor = self.clone();
+        modes.into_iter().map(move |mode| VideoModeHandle {
+            size: mode.size.into(),
             bit_depth: 32,
-            refresh_rate_millihertz: 60000,
-            monitor: self.clone(),
+            refresh_rate_millihertz: mode.refresh_rate_millihertz,
+            monitor: monitor.clone(),
         })
     }
 }
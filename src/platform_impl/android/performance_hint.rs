@@ -0,0 +1,183 @@
+//! Sessions of the [`APerformanceHint`] API (ADPF), which adjusts the clocks of the CPU to the
+//! work that the threads of a session report, API 33 and later.
+//!
+//! Sessions are closed while the activity is paused, and created again when it resumes.
+//!
+//! [`APerformanceHint`]: https://developer.android.com/ndk/reference/group/a-performance-hint
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::libandroid::symbol;
+
+type APerformanceHintManager = c_void;
+type APerformanceHintSession = c_void;
+
+/// The functions of `libandroid.so` that are used.
+struct Functions {
+    get_manager: unsafe extern "C" fn() -> *mut APerformanceHintManager,
+    create_session: unsafe extern "C" fn(
+        *mut APerformanceHintManager,
+        *const i32,
+        usize,
+        i64,
+    ) -> *mut APerformanceHintSession,
+    update_target_work_duration: unsafe extern "C" fn(*mut APerformanceHintSession, i64) -> i32,
+    report_actual_work_duration: unsafe extern "C" fn(*mut APerformanceHintSession, i64) -> i32,
+    close_session: unsafe extern "C" fn(*mut APerformanceHintSession),
+}
+
+impl Functions {
+    fn get() -> Option<&'static Self> {
+        static FUNCTIONS: OnceLock<Option<Functions>> = OnceLock::new();
+        // SAFETY: The symbols have the declared signatures.
+        FUNCTIONS
+            .get_or_init(|| unsafe {
+                Some(Self {
+                    get_manager: symbol(b"APerformanceHint_getManager\0")?,
+                    create_session: symbol(b"APerformanceHint_createSession\0")?,
+                    update_target_work_duration: symbol(
+                        b"APerformanceHint_updateTargetWorkDuration\0",
+                    )?,
+                    report_actual_work_duration: symbol(
+                        b"APerformanceHint_reportActualWorkDuration\0",
+                    )?,
+                    close_session: symbol(b"APerformanceHint_closeSession\0")?,
+                })
+            })
+            .as_ref()
+    }
+}
+
+pub(crate) struct PerformanceHintSession {
+    state: Arc<Mutex<SessionState>>,
+}
+
+struct SessionState {
+    functions: &'static Functions,
+    /// Null while the activity is paused.
+    session: *mut APerformanceHintSession,
+    thread_ids: Vec<i32>,
+    target_work_duration_nanos: i64,
+}
+
+// SAFETY: The session is only used behind the mutex.
+unsafe impl Send for SessionState {}
+
+impl PerformanceHintSession {
+    /// Returns [`None`] before API 33, or if the device doesn't support performance hints.
+    fn new(thread_ids: &[i32], target_work_duration: Duration) -> Option<Self> {
+        let mut state = SessionState {
+            functions: Functions::get()?,
+            session: std::ptr::null_mut(),
+            thread_ids: thread_ids.to_vec(),
+            target_work_duration_nanos: nanos(target_work_duration),
+        };
+        state.open();
+        if state.session.is_null() {
+            return None;
+        }
+        Some(Self { state: Arc::new(Mutex::new(state)) })
+    }
+
+    pub fn update_target_work_duration(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.target_work_duration_nanos = nanos(duration);
+        if !state.session.is_null() {
+            let result = unsafe {
+                (state.functions.update_target_work_duration)(
+                    state.session,
+                    state.target_work_duration_nanos,
+                )
+            };
+            if result != 0 {
+                warn!("failed to update the target work duration: {result}");
+            }
+        }
+    }
+
+    pub fn report_actual_work_duration(&self, duration: Duration) {
+        let state = self.state.lock().unwrap();
+        if !state.session.is_null() {
+            let result = unsafe {
+                (state.functions.report_actual_work_duration)(state.session, nanos(duration))
+            };
+            if result != 0 {
+                warn!("failed to report the actual work duration: {result}");
+            }
+        }
+    }
+}
+
+impl Drop for PerformanceHintSession {
+    fn drop(&mut self) {
+        self.state.lock().unwrap().close();
+    }
+}
+
+impl SessionState {
+    fn open(&mut self) {
+        let manager = unsafe { (self.functions.get_manager)() };
+        if manager.is_null() {
+            return;
+        }
+        self.session = unsafe {
+            (self.functions.create_session)(
+                manager,
+                self.thread_ids.as_ptr(),
+                self.thread_ids.len(),
+                self.target_work_duration_nanos,
+            )
+        };
+    }
+
+    fn close(&mut self) {
+        if !self.session.is_null() {
+            unsafe { (self.functions.close_session)(self.session) };
+            self.session = std::ptr::null_mut();
+        }
+    }
+}
+
+/// The sessions created by the event loop, which follow the lifecycle of the activity.
+#[derive(Default)]
+pub(crate) struct PerformanceHintSessions {
+    sessions: RefCell<Vec<Weak<Mutex<SessionState>>>>,
+}
+
+impl PerformanceHintSessions {
+    pub fn create(
+        &self,
+        thread_ids: &[i32],
+        target_work_duration: Duration,
+    ) -> Option<PerformanceHintSession> {
+        let session = PerformanceHintSession::new(thread_ids, target_work_duration)?;
+        let mut sessions = self.sessions.borrow_mut();
+        sessions.retain(|state| state.strong_count() > 0);
+        sessions.push(Arc::downgrade(&session.state));
+        Some(session)
+    }
+
+    pub fn pause(&self) {
+        for state in self.sessions.borrow().iter().filter_map(Weak::upgrade) {
+            state.lock().unwrap().close();
+        }
+    }
+
+    pub fn resume(&self) {
+        for state in self.sessions.borrow().iter().filter_map(Weak::upgrade) {
+            let mut state = state.lock().unwrap();
+            if state.session.is_null() {
+                state.open();
+            }
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> i64 {
+    duration.as_nanos().try_into().unwrap_or(i64::MAX)
+}
@@ -0,0 +1,58 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use android_activity::input::{Axis, ToolType};
+
+use crate::dpi::PhysicalPosition;
+use crate::event::{DeviceId, Force, Touch, TouchPhase, TouchTool};
+
+pub fn to_tool(tool_type: ToolType) -> TouchTool {
+    match tool_type {
+        ToolType::Stylus => TouchTool::Stylus,
+        ToolType::Eraser => TouchTool::Eraser,
+        ToolType::Mouse => TouchTool::Mouse,
+        _ => TouchTool::Finger,
+    }
+}
+
+/// Returns the altitude and azimuth (in radians) of a stylus, [`None`] for other tools.
+///
+/// `AXIS_TILT` is the angle between the stylus and the normal of the screen, and
+/// `AXIS_ORIENTATION` is the direction of the stylus, clockwise from the top of the screen.
+pub fn stylus_angles(
+    tool_type: ToolType,
+    axis_value: impl Fn(Axis) -> f32,
+) -> (Option<f64>, Option<f64>) {
+    if !matches!(tool_type, ToolType::Stylus | ToolType::Eraser) {
+        return (None, None);
+    }
+
+    let altitude_angle = FRAC_PI_2 - axis_value(Axis::Tilt) as f64;
+    let azimuth_angle = (axis_value(Axis::Orientation) as f64 - FRAC_PI_2).rem_euclid(TAU);
+    (Some(altitude_angle), Some(azimuth_angle))
+}
+
+/// Builds a [`Touch`] from the axes of a pointer, or of one of its historical samples.
+pub fn to_touch(
+    device_id: DeviceId,
+    phase: TouchPhase,
+    pointer_id: i32,
+    tool_type: ToolType,
+    axis_value: impl Fn(Axis) -> f32,
+) -> Touch {
+    let location = PhysicalPosition::new(axis_value(Axis::X) as f64, axis_value(Axis::Y) as f64);
+    let (altitude_angle, azimuth_angle) = stylus_angles(tool_type, &axis_value);
+
+    Touch {
+        device_id,
+        phase,
+        location,
+        force: Some(Force::Normalized(axis_value(Axis::Pressure) as f64)),
+        tool: to_tool(tool_type),
+        azimuth_angle,
+        altitude_angle,
+        twist_angle: None,
+        is_estimated: false,
+        is_predicted: false,
+        id: pointer_id as u64,
+    }
+}
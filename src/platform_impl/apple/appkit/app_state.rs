@@ -1,24 +1,36 @@
 use std::cell::{Cell, RefCell};
-use std::mem;
+use std::collections::VecDeque;
 use std::rc::Weak;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::Instant;
-
-use objc2::rc::Retained;
-use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate};
-use objc2_foundation::{MainThreadMarker, NSNotification, NSObject, NSObjectProtocol};
+use std::{mem, ptr};
+
+use objc2::rc::{Retained, Weak as WeakId};
+use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2_app_kit::{
+    NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate, NSWorkspace,
+    NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification, NSWorkspaceDidWakeNotification,
+    NSWorkspaceWillSleepNotification,
+};
+use objc2_foundation::{
+    ns_string, MainThreadMarker, NSDistributedNotificationCenter, NSNotification, NSObject,
+    NSObjectProtocol,
+};
 
 use crate::application::ApplicationHandler;
-use crate::event::{StartCause, WindowEvent};
-use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow};
+use crate::event::{SessionEvent, StartCause, WindowEvent};
+use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow, UserEventQueue};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::window::WindowId as RootWindowId;
 
+use super::super::process_info;
 use super::event_handler::EventHandler;
 use super::event_loop::{stop_app_immediately, ActiveEventLoop, PanicInfo};
+use super::menu;
+use super::monitor::{self, MonitorHandle};
 use super::observer::{EventLoopWaker, RunLoop};
-use super::{menu, WindowId};
+use super::view::WinitView;
 
 #[derive(Debug)]
 pub(super) struct AppState {
@@ -27,6 +39,7 @@ pub(super) struct AppState {
     activate_ignoring_other_apps: bool,
     run_loop: RunLoop,
     proxy_wake_up: Arc<AtomicBool>,
+    user_event_queue: Arc<UserEventQueue>,
     event_handler: EventHandler,
     stop_on_launch: Cell<bool>,
     stop_before_wait: Cell<bool>,
@@ -42,7 +55,11 @@ pub(super) struct AppState {
     waker: RefCell<EventLoopWaker>,
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
-    pending_redraw: RefCell<Vec<WindowId>>,
+    pending_redraw: RefCell<Vec<WeakId<WinitView>>>,
+    /// The monitors known before the screen parameters last changed.
+    monitors: RefCell<VecDeque<MonitorHandle>>,
+    /// Whether low power mode was enabled when the power state last changed.
+    low_power_mode: Cell<bool>,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
@@ -73,6 +90,58 @@ declare_class!(
         fn app_will_terminate(&self, notification: &NSNotification) {
             self.will_terminate(notification)
         }
+
+        #[method(applicationDidChangeScreenParameters:)]
+        fn app_did_change_screen_parameters(&self, _notification: &NSNotification) {
+            trace_scope!("applicationDidChangeScreenParameters:");
+            self.monitors_changed();
+        }
+    }
+
+    unsafe impl ApplicationDelegate {
+        // Observed through `NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification`.
+        #[method(accessibilityDisplayOptionsDidChange:)]
+        fn accessibility_display_options_did_change(&self, _notification: &NSNotification) {
+            trace_scope!("accessibilityDisplayOptionsDidChange:");
+            self.maybe_queue_with_handler(|app, event_loop| {
+                app.accessibility_settings_changed(event_loop)
+            });
+        }
+
+        // Observed through `TISNotifySelectedKeyboardInputSourceChanged`.
+        #[method(selectedKeyboardInputSourceDidChange:)]
+        fn selected_keyboard_input_source_did_change(&self, _notification: &NSNotification) {
+            trace_scope!("selectedKeyboardInputSourceDidChange:");
+            self.maybe_queue_with_handler(|app, event_loop| app.keyboard_layout_changed(event_loop));
+        }
+
+        // Observed through `NSWorkspaceWillSleepNotification`.
+        #[method(workspaceWillSleep:)]
+        fn workspace_will_sleep(&self, _notification: &NSNotification) {
+            trace_scope!("workspaceWillSleep:");
+            self.session_event(SessionEvent::Suspending);
+        }
+
+        // Observed through `NSWorkspaceDidWakeNotification`.
+        #[method(workspaceDidWake:)]
+        fn workspace_did_wake(&self, _notification: &NSNotification) {
+            trace_scope!("workspaceDidWake:");
+            self.session_event(SessionEvent::Resumed);
+        }
+
+        // Observed through `com.apple.screenIsLocked`.
+        #[method(screenIsLocked:)]
+        fn screen_is_locked(&self, _notification: &NSNotification) {
+            trace_scope!("screenIsLocked:");
+            self.session_event(SessionEvent::SessionLocked);
+        }
+
+        // Observed through `com.apple.screenIsUnlocked`.
+        #[method(screenIsUnlocked:)]
+        fn screen_is_unlocked(&self, _notification: &NSNotification) {
+            trace_scope!("screenIsUnlocked:");
+            self.session_event(SessionEvent::SessionUnlocked);
+        }
     }
 );
 
@@ -86,6 +155,7 @@ impl ApplicationDelegate {
         let this = mtm.alloc().set_ivars(AppState {
             activation_policy,
             proxy_wake_up: Arc::new(AtomicBool::new(false)),
+            user_event_queue: Default::default(),
             default_menu,
             activate_ignoring_other_apps,
             run_loop: RunLoop::main(mtm),
@@ -102,6 +172,8 @@ impl ApplicationDelegate {
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
             pending_redraw: RefCell::new(vec![]),
+            monitors: RefCell::new(monitor::available_monitors()),
+            low_power_mode: Cell::new(process_info::is_low_power_mode_enabled()),
         });
         unsafe { msg_send_id![super(this), init] }
     }
@@ -129,6 +201,57 @@ impl ApplicationDelegate {
             menu::initialize(&app);
         }
 
+        // The notifications are posted to the workspace's notification center, not the default one.
+        let workspace_center = unsafe { NSWorkspace::sharedWorkspace().notificationCenter() };
+        let workspace_notifications = unsafe {
+            [
+                (
+                    sel!(accessibilityDisplayOptionsDidChange:),
+                    NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification,
+                ),
+                (sel!(workspaceWillSleep:), NSWorkspaceWillSleepNotification),
+                (sel!(workspaceDidWake:), NSWorkspaceDidWakeNotification),
+            ]
+        };
+        for (selector, name) in workspace_notifications {
+            unsafe {
+                workspace_center.addObserver_selector_name_object(self, selector, Some(name), None)
+            };
+        }
+
+        // The input source and screen lock notifications are only posted to the distributed
+        // notification center.
+        let distributed_center = unsafe { NSDistributedNotificationCenter::defaultCenter() };
+        let distributed_notifications = [
+            (
+                sel!(selectedKeyboardInputSourceDidChange:),
+                ns_string!("com.apple.Carbon.TISNotifySelectedKeyboardInputSourceChanged"),
+            ),
+            (sel!(screenIsLocked:), ns_string!("com.apple.screenIsLocked")),
+            (sel!(screenIsUnlocked:), ns_string!("com.apple.screenIsUnlocked")),
+        ];
+        for (selector, name) in distributed_notifications {
+            unsafe {
+                distributed_center.addObserver_selector_name_object(
+                    self,
+                    selector,
+                    Some(name),
+                    None,
+                )
+            };
+        }
+
+        let this = self.retain();
+        process_info::observe_power_state(move || {
+            this.maybe_queue_with_handler(|app, event_loop| app.power_state_changed(event_loop));
+
+            // The same notification reports low power mode, only forward its changes.
+            let low_power_mode = process_info::is_low_power_mode_enabled();
+            if this.ivars().low_power_mode.replace(low_power_mode) != low_power_mode {
+                this.session_event(SessionEvent::PowerSaverChanged(low_power_mode));
+            }
+        });
+
         self.ivars().waker.borrow_mut().start();
 
         self.set_is_running(true);
@@ -150,6 +273,33 @@ impl ApplicationDelegate {
         }
     }
 
+    /// Emit the monitors that were connected or disconnected since the last change.
+    fn monitors_changed(&self) {
+        // The handles compare the UUID of the displays, which persists across mode changes.
+        let monitors = monitor::available_monitors();
+        let previous = self.ivars().monitors.replace(monitors.clone());
+        let disconnected: Vec<_> =
+            previous.iter().filter(|monitor| !monitors.contains(monitor)).cloned().collect();
+        let connected: Vec<_> =
+            monitors.into_iter().filter(|monitor| !previous.contains(monitor)).collect();
+        if disconnected.is_empty() && connected.is_empty() {
+            return;
+        }
+
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            for monitor in disconnected {
+                app.monitor_disconnected(event_loop, RootMonitorHandle { inner: monitor });
+            }
+            for monitor in connected {
+                app.monitor_connected(event_loop, RootMonitorHandle { inner: monitor });
+            }
+        });
+    }
+
+    fn session_event(&self, event: SessionEvent) {
+        self.maybe_queue_with_handler(move |app, event_loop| app.session_event(event_loop, event));
+    }
+
     fn will_terminate(&self, _notification: &NSNotification) {
         trace_scope!("applicationWillTerminate:");
         // TODO: Notify every window that it will be destroyed, like done in iOS?
@@ -182,6 +332,10 @@ impl ApplicationDelegate {
         self.ivars().proxy_wake_up.clone()
     }
 
+    pub fn user_event_queue(&self) -> Arc<UserEventQueue> {
+        self.ivars().user_event_queue.clone()
+    }
+
     /// If `pump_events` is called to progress the event loop then we
     /// bootstrap the event loop via `-[NSApplication run]` but will use
     /// `CFRunLoopRunInMode` for subsequent calls to `pump_events`.
@@ -253,11 +407,14 @@ impl ApplicationDelegate {
         self.ivars().control_flow.get()
     }
 
-    pub fn handle_redraw(&self, window_id: WindowId) {
+    pub(super) fn handle_redraw(&self, view: &WinitView) {
         let mtm = MainThreadMarker::from(self);
         // Redraw request might come out of order from the OS.
         // -> Don't go back into the event handler when our callstack originates from there
         if !self.ivars().event_handler.in_use() {
+            let Some(window_id) = view.deliver_redraw() else {
+                return;
+            };
             self.with_handler(|app, event_loop| {
                 app.window_event(event_loop, RootWindowId(window_id), WindowEvent::RedrawRequested);
             });
@@ -272,10 +429,13 @@ impl ApplicationDelegate {
         }
     }
 
-    pub fn queue_redraw(&self, window_id: WindowId) {
+    pub(super) fn queue_redraw(&self, view: &WinitView) {
         let mut pending_redraw = self.ivars().pending_redraw.borrow_mut();
-        if !pending_redraw.contains(&window_id) {
-            pending_redraw.push(window_id);
+        // The views are weakly referenced, as they strongly reference the delegate.
+        let queued =
+            pending_redraw.iter().filter_map(WeakId::load).any(|pending| ptr::eq(&*pending, view));
+        if !queued {
+            pending_redraw.push(WeakId::new(view));
         }
         self.ivars().run_loop.wakeup();
     }
@@ -371,7 +531,10 @@ impl ApplicationDelegate {
         }
 
         let redraw = mem::take(&mut *self.ivars().pending_redraw.borrow_mut());
-        for window_id in redraw {
+        for view in redraw.iter().filter_map(WeakId::load) {
+            let Some(window_id) = view.deliver_redraw() else {
+                continue;
+            };
             self.with_handler(|app, event_loop| {
                 app.window_event(event_loop, RootWindowId(window_id), WindowEvent::RedrawRequested);
             });
@@ -1,6 +1,7 @@
 use std::ffi::c_uchar;
 use std::slice;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 use objc2::rc::Retained;
 use objc2::runtime::Sel;
@@ -15,16 +16,36 @@ use crate::cursor::{CursorImage, OnlyCursorImageSource};
 use crate::window::CursorIcon;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct CustomCursor(pub(crate) Retained<NSCursor>);
+pub enum CustomCursor {
+    Image(Retained<NSCursor>),
+    Animation(Arc<CursorAnimation>),
+}
+
+/// The frames of an animated cursor, which are swapped by the view while the mouse is inside it.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct CursorAnimation {
+    pub frames: Vec<(Retained<NSCursor>, Duration)>,
+}
 
 // SAFETY: NSCursor is immutable and thread-safe
 // TODO(madsmtm): Put this logic in objc2-app-kit itself
 unsafe impl Send for CustomCursor {}
 unsafe impl Sync for CustomCursor {}
+unsafe impl Send for CursorAnimation {}
+unsafe impl Sync for CursorAnimation {}
 
 impl CustomCursor {
     pub(crate) fn new(cursor: OnlyCursorImageSource) -> CustomCursor {
-        Self(cursor_from_image(&cursor.0))
+        match cursor {
+            OnlyCursorImageSource::Image(image) => Self::Image(cursor_from_image(&image)),
+            OnlyCursorImageSource::Animation(frames) => {
+                let frames = frames
+                    .iter()
+                    .map(|frame| (cursor_from_image(&frame.image), frame.duration))
+                    .collect();
+                Self::Animation(Arc::new(CursorAnimation { frames }))
+            },
+        }
     }
 }
 
@@ -184,7 +205,10 @@ pub(crate) fn invisible_cursor() -> Retained<NSCursor> {
 
     // Cache this for efficiency
     static CURSOR: OnceLock<CustomCursor> = OnceLock::new();
-    CURSOR.get_or_init(|| CustomCursor(new_invisible())).0.clone()
+    match CURSOR.get_or_init(|| CustomCursor::Image(new_invisible())) {
+        CustomCursor::Image(cursor) => cursor.clone(),
+        CustomCursor::Animation(_) => unreachable!(),
+    }
 }
 
 pub(crate) fn cursor_from_icon(icon: CursorIcon) -> Retained<NSCursor> {
@@ -0,0 +1,92 @@
+//! The progress indicator and the badge shown on the application's dock tile.
+
+use std::cell::{Cell, RefCell};
+
+use objc2::rc::Retained;
+use objc2_app_kit::{
+    NSApplication, NSDockTile, NSImageView, NSProgressIndicator, NSProgressIndicatorStyle, NSView,
+};
+use objc2_foundation::{MainThreadMarker, NSPoint, NSRect, NSSize, NSString};
+
+use crate::window::ProgressState;
+
+thread_local! {
+    /// The state currently shown on the dock tile.
+    static STATE: Cell<ProgressState> = const { Cell::new(ProgressState::None) };
+    /// The progress indicator, only present while the dock tile shows progress.
+    static INDICATOR: RefCell<Option<Retained<NSProgressIndicator>>> = const { RefCell::new(None) };
+    /// The count currently shown in the badge of the dock tile.
+    static BADGE_COUNT: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Show the progress on the dock tile, which is shared by all the windows of the application.
+pub(crate) fn set_progress(mtm: MainThreadMarker, state: ProgressState) {
+    if STATE.with(|current| current.replace(state)) == state {
+        return;
+    }
+
+    let app = NSApplication::sharedApplication(mtm);
+    let dock_tile = unsafe { app.dockTile() };
+
+    INDICATOR.with(|indicator| {
+        let mut indicator = indicator.borrow_mut();
+        if state == ProgressState::None {
+            // Restore the default dock tile, which draws the application icon.
+            if indicator.take().is_some() {
+                unsafe { dock_tile.setContentView(None) };
+            }
+            return;
+        }
+
+        let indicator = indicator.get_or_insert_with(|| create_indicator(mtm, &app, &dock_tile));
+        unsafe {
+            indicator.setIndeterminate(state == ProgressState::Indeterminate);
+            indicator.setDoubleValue(state.value().unwrap_or(0.0));
+        }
+    });
+
+    unsafe { dock_tile.display() };
+}
+
+/// Show the count in the badge of the dock tile, which is shared by all the windows of the
+/// application.
+pub(crate) fn set_badge_count(mtm: MainThreadMarker, count: Option<u64>) {
+    if BADGE_COUNT.with(|current| current.replace(count)) == count {
+        return;
+    }
+
+    let dock_tile = unsafe { NSApplication::sharedApplication(mtm).dockTile() };
+    let label = count.map(|count| NSString::from_str(&count.to_string()));
+    unsafe { dock_tile.setBadgeLabel(label.as_deref()) };
+}
+
+/// Replace the content of the dock tile with the application icon and a progress bar.
+fn create_indicator(
+    mtm: MainThreadMarker,
+    app: &NSApplication,
+    dock_tile: &NSDockTile,
+) -> Retained<NSProgressIndicator> {
+    let size = unsafe { dock_tile.size() };
+
+    let content_view = unsafe {
+        NSImageView::initWithFrame(mtm.alloc(), NSRect::new(NSPoint::new(0.0, 0.0), size))
+    };
+    unsafe { content_view.setImage(app.applicationIconImage().as_deref()) };
+
+    let frame = NSRect::new(
+        NSPoint::new(size.width * 0.1, size.height * 0.05),
+        NSSize::new(size.width * 0.8, size.height * 0.15),
+    );
+    let indicator = unsafe { NSProgressIndicator::initWithFrame(mtm.alloc(), frame) };
+    unsafe {
+        indicator.setStyle(NSProgressIndicatorStyle::Bar);
+        indicator.setMinValue(0.0);
+        indicator.setMaxValue(1.0);
+        content_view.addSubview(&indicator);
+    }
+
+    let content_view: &NSView = &content_view;
+    unsafe { dock_tile.setContentView(Some(content_view)) };
+
+    indicator
+}
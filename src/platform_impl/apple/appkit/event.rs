@@ -10,8 +10,8 @@ use smol_str::SmolStr;
 use super::ffi;
 use crate::event::{ElementState, KeyEvent, Modifiers};
 use crate::keyboard::{
-    Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
-    PhysicalKey,
+    self, Key, KeyCode, KeyLocation, LockKeys, ModifiersKeys, ModifiersState, NamedKey, NativeKey,
+    NativeKeyCode, PhysicalKey,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -239,6 +239,33 @@ pub fn code_to_key(key: PhysicalKey, scancode: u16) -> Key {
     })
 }
 
+/// The key produced by the physical key without modifiers in the current input source, with dead
+/// keys reported as the character they produce on their own.
+pub(crate) fn key_from_key_code(code: KeyCode) -> Key {
+    let Some(scancode) = physicalkey_to_scancode(PhysicalKey::Code(code)) else {
+        return keyboard::layout_independent_key(code);
+    };
+    let key = match code_to_key(PhysicalKey::Code(code), scancode as u16) {
+        // `UCKeyTranslate` doesn't touch the dead key state when asked to ignore dead keys.
+        Key::Unidentified(_) => get_modifierless_char(scancode as u16),
+        key => key,
+    };
+    match key {
+        Key::Character(ref text) if !text.chars().any(char::is_control) => key,
+        Key::Named(_) => key,
+        _ => keyboard::layout_independent_key(code),
+    }
+}
+
+/// The physical key producing the key without modifiers, see [`key_from_key_code`].
+pub(crate) fn key_code_from_key(key: Key) -> Option<KeyCode> {
+    let keys = (0..0x80).filter_map(|scancode| match scancode_to_physicalkey(scancode) {
+        PhysicalKey::Code(code) => Some((code, key_from_key_code(code))),
+        PhysicalKey::Unidentified(_) => None,
+    });
+    keyboard::find_key_code(key, keys.chain(keyboard::layout_independent_keys()))
+}
+
 pub fn code_to_location(key: PhysicalKey) -> KeyLocation {
     let code = match key {
         PhysicalKey::Code(code) => code,
@@ -314,7 +341,10 @@ pub(super) fn ralt_pressed(event: &NSEvent) -> bool {
 }
 
 pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
-    let flags = unsafe { event.modifierFlags() };
+    flags_mods(unsafe { event.modifierFlags() })
+}
+
+pub(super) fn flags_mods(flags: NSEventModifierFlags) -> Modifiers {
     let mut state = ModifiersState::empty();
     let mut pressed_mods = ModifiersKeys::empty();
 
@@ -341,7 +371,14 @@ pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
     pressed_mods.set(ModifiersKeys::LSUPER, flags.contains(NX_DEVICELCMDKEYMASK));
     pressed_mods.set(ModifiersKeys::RSUPER, flags.contains(NX_DEVICERCMDKEYMASK));
 
-    Modifiers { state, pressed_mods }
+    // There is no num lock on macOS.
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(
+        LockKeys::CAPS_LOCK,
+        flags.contains(NSEventModifierFlags::NSEventModifierFlagCapsLock),
+    );
+
+    Modifiers { state, pressed_mods, lock_keys }
 }
 
 pub(super) fn dummy_event() -> Option<Retained<NSEvent>> {
@@ -18,18 +18,23 @@ use core_foundation::runloop::{
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::ProtocolObject;
 use objc2::{msg_send_id, ClassType};
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSWindow};
+use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSEvent, NSWindow, NSWorkspace};
 use objc2_foundation::{MainThreadMarker, NSObjectProtocol};
 
+use super::super::process_info;
 use super::app::WinitApplication;
 use super::app_state::ApplicationDelegate;
 use super::cursor::CustomCursor;
-use super::event::dummy_event;
+use super::event::{self, dummy_event};
 use super::monitor::{self, MonitorHandle};
 use super::observer::setup_control_flow_observers;
 use crate::application::ApplicationHandler;
 use crate::error::EventLoopError;
-use crate::event_loop::{ActiveEventLoop as RootWindowTarget, ControlFlow, DeviceEvents};
+use crate::event_loop::{
+    ActiveEventLoop as RootWindowTarget, ControlFlow, DeviceEventSubscription, MotionEventPolicy,
+    ThermalState,
+};
+use crate::keyboard::{Key, KeyCode};
 use crate::platform::macos::ActivationPolicy;
 use crate::platform::pump_events::PumpStatus;
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource};
@@ -77,8 +82,9 @@ impl ActiveEventLoop {
 
     pub(super) fn new_root(delegate: Retained<ApplicationDelegate>) -> RootWindowTarget {
         let mtm = MainThreadMarker::from(&*delegate);
+        let user_event_queue = delegate.user_event_queue();
         let p = Self { delegate, mtm };
-        RootWindowTarget { p, _marker: PhantomData }
+        RootWindowTarget { p, user_event_queue, _marker: PhantomData }
     }
 
     pub(super) fn app_delegate(&self) -> &ApplicationDelegate {
@@ -101,7 +107,28 @@ impl ActiveEventLoop {
     }
 
     #[inline]
-    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+    pub fn listen_device_events(&self, _subscription: DeviceEventSubscription) {}
+
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        let enabled = policy == MotionEventPolicy::Coalesced;
+        unsafe { NSEvent::setMouseCoalescingEnabled(enabled) };
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        if unsafe { NSEvent::isMouseCoalescingEnabled() } {
+            MotionEventPolicy::Coalesced
+        } else {
+            MotionEventPolicy::Unfiltered
+        }
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        event::key_from_key_code(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        event::key_code_from_key(key)
+    }
 
     #[cfg(feature = "rwh_05")]
     #[inline]
@@ -156,6 +183,26 @@ impl ActiveEventLoop {
     pub(crate) fn allows_automatic_window_tabbing(&self) -> bool {
         NSWindow::allowsAutomaticWindowTabbing(self.mtm)
     }
+
+    pub(crate) fn should_reduce_motion(&self) -> bool {
+        unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion() }
+    }
+
+    pub(crate) fn should_increase_contrast(&self) -> bool {
+        unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldIncreaseContrast() }
+    }
+
+    pub(crate) fn should_reduce_transparency(&self) -> bool {
+        unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceTransparency() }
+    }
+
+    pub(crate) fn is_low_power_mode_enabled(&self) -> bool {
+        process_info::is_low_power_mode_enabled()
+    }
+
+    pub(crate) fn thermal_state(&self) -> ThermalState {
+        process_info::thermal_state()
+    }
 }
 
 pub struct EventLoop {
@@ -232,6 +279,7 @@ impl EventLoop {
             app,
             delegate: delegate.clone(),
             window_target: RootWindowTarget {
+                user_event_queue: delegate.user_event_queue(),
                 p: ActiveEventLoop { delegate, mtm },
                 _marker: PhantomData,
             },
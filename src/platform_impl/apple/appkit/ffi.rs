@@ -196,6 +196,9 @@ extern "C" {
 
     pub fn LMGetKbdType() -> u8;
 
+    pub fn EnableSecureEventInput() -> OSStatus;
+    pub fn DisableSecureEventInput() -> OSStatus;
+
     #[allow(non_snake_case)]
     pub fn UCKeyTranslate(
         keyLayoutPtr: *const UCKeyboardLayout,
@@ -4,6 +4,7 @@ mod util;
 mod app;
 mod app_state;
 mod cursor;
+mod dock;
 mod event;
 mod event_handler;
 mod event_loop;
@@ -25,6 +26,7 @@ pub(crate) use self::event_loop::{
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
 pub(crate) use self::window::WindowId;
 pub(crate) use self::window_delegate::PlatformSpecificWindowAttributes;
+use crate::error::PlatformError;
 use crate::event::DeviceId as RootDeviceId;
 
 pub(crate) use self::cursor::CustomCursor as PlatformCustomCursor;
@@ -59,3 +61,13 @@ impl fmt::Display for OsError {
         }
     }
 }
+
+/// The error code of Core Graphics, if any.
+pub(crate) fn platform_error(error: &OsError) -> Option<PlatformError> {
+    match error {
+        OsError::CGError(code) => {
+            Some(PlatformError::new("CGError", Some((*code).into()), error.to_string()))
+        },
+        OsError::CreationError(_) => None,
+    }
+}
@@ -10,12 +10,14 @@ use core_graphics::display::{
     CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGDisplayCopyDisplayMode,
 };
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
-use objc2_app_kit::NSScreen;
+use objc2::runtime::{AnyObject, NSObjectProtocol};
+use objc2::sel;
+use objc2_app_kit::{NSBitsPerSampleFromDepth, NSDisplayGamut, NSScreen};
 use objc2_foundation::{ns_string, run_on_main, MainThreadMarker, NSNumber, NSPoint, NSRect};
 
 use super::ffi;
 use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::monitor::{ColorProperties, ColorSpace};
 
 #[derive(Clone)]
 pub struct VideoModeHandle {
@@ -211,6 +213,32 @@ impl MonitorHandle {
         })
     }
 
+    pub fn edr_headroom(&self) -> f32 {
+        run_on_main(|mtm| match self.ns_screen(mtm) {
+            Some(screen) => edr_headroom(&screen),
+            None => 1.0,
+        })
+    }
+
+    pub fn max_potential_edr_headroom(&self) -> f32 {
+        run_on_main(|mtm| {
+            let Some(screen) = self.ns_screen(mtm) else {
+                return 1.0;
+            };
+            // Only available since macOS 10.15.
+            if !screen
+                .respondsToSelector(sel!(maximumPotentialExtendedDynamicRangeColorComponentValue))
+            {
+                return 1.0;
+            }
+            unsafe { screen.maximumPotentialExtendedDynamicRangeColorComponentValue() as f32 }
+        })
+    }
+
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        run_on_main(|mtm| self.ns_screen(mtm).map(|screen| color_properties(&screen)))
+    }
+
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         unsafe {
             let current_display_mode = NativeDisplayMode(CGDisplayCopyDisplayMode(self.0) as _);
@@ -307,6 +335,33 @@ impl MonitorHandle {
     }
 }
 
+/// The current EDR headroom of the screen, `1.0` when the screen doesn't support EDR.
+pub(crate) fn edr_headroom(screen: &NSScreen) -> f32 {
+    unsafe { screen.maximumExtendedDynamicRangeColorComponentValue() as f32 }
+}
+
+/// The color properties of the screen, only the gamut of the color space is known.
+pub(crate) fn color_properties(screen: &NSScreen) -> ColorProperties {
+    // Only available since macOS 10.15.
+    let hdr_enabled = screen
+        .respondsToSelector(sel!(maximumPotentialExtendedDynamicRangeColorComponentValue))
+        && unsafe { screen.maximumPotentialExtendedDynamicRangeColorComponentValue() } > 1.0;
+    let color_space = if unsafe { screen.canRepresentDisplayGamut(NSDisplayGamut::P3) } {
+        ColorSpace::DisplayP3
+    } else {
+        ColorSpace::Srgb
+    };
+    let bits_per_channel = unsafe { NSBitsPerSampleFromDepth(screen.depth()) };
+
+    ColorProperties {
+        hdr_enabled,
+        max_luminance_nits: None,
+        sdr_white_level_nits: None,
+        color_space: Some(color_space),
+        bits_per_channel: u8::try_from(bits_per_channel).ok().filter(|&bits| bits > 0),
+    }
+}
+
 pub(crate) fn get_display_id(screen: &NSScreen) -> u32 {
     let key = ns_string!("NSScreenNumber");
 
@@ -17,11 +17,12 @@ use core_foundation::runloop::{
     kCFRunLoopExit, CFRunLoopActivity, CFRunLoopAddObserver, CFRunLoopAddTimer, CFRunLoopGetMain,
     CFRunLoopObserverCallBack, CFRunLoopObserverContext, CFRunLoopObserverCreate,
     CFRunLoopObserverRef, CFRunLoopRef, CFRunLoopTimerCreate, CFRunLoopTimerInvalidate,
-    CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate, CFRunLoopWakeUp,
+    CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate, CFRunLoopTimerSetTolerance, CFRunLoopWakeUp,
 };
 use objc2_foundation::MainThreadMarker;
 use tracing::error;
 
+use super::super::run_loop_timer::timer_tolerance;
 use super::app_state::ApplicationDelegate;
 use super::event_loop::{stop_app_on_panic, PanicInfo};
 use super::ffi;
@@ -300,6 +301,7 @@ impl EventLoopWaker {
                         let duration = instant - now;
                         let fsecs = duration.subsec_nanos() as f64 / 1_000_000_000.0
                             + duration.as_secs() as f64;
+                        CFRunLoopTimerSetTolerance(self.timer, timer_tolerance(fsecs));
                         CFRunLoopTimerSetNextFireDate(self.timer, current + fsecs)
                     }
                 }
@@ -2,46 +2,53 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::ptr;
+use std::sync::Arc;
 
 use objc2::rc::{Retained, WeakId};
 use objc2::runtime::{AnyObject, Sel};
-use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
 use objc2_app_kit::{
-    NSApplication, NSCursor, NSEvent, NSEventPhase, NSResponder, NSTextInputClient,
-    NSTrackingRectTag, NSView, NSViewFrameDidChangeNotification,
+    NSAllRomanInputSourcesLocaleIdentifier, NSApplication, NSCursor, NSDragOperation,
+    NSDraggingContext, NSDraggingSession, NSDraggingSource, NSEvent, NSEventModifierFlags,
+    NSEventPhase, NSEventType, NSResponder, NSTextInputClient, NSTrackingRectTag, NSView,
+    NSViewFrameDidChangeNotification,
 };
 use objc2_foundation::{
     MainThreadMarker, NSArray, NSAttributedString, NSAttributedStringKey, NSCopying,
     NSMutableAttributedString, NSNotFound, NSNotificationCenter, NSObject, NSObjectProtocol,
-    NSPoint, NSRange, NSRect, NSSize, NSString, NSUInteger,
+    NSPoint, NSRange, NSRect, NSRunLoop, NSRunLoopCommonModes, NSSize, NSString, NSTimer,
+    NSUInteger,
 };
 
 use super::app_state::ApplicationDelegate;
-use super::cursor::{default_cursor, invisible_cursor};
+use super::cursor::{default_cursor, invisible_cursor, CursorAnimation};
 use super::event::{
-    code_to_key, code_to_location, create_key_event, event_mods, lalt_pressed, ralt_pressed,
-    scancode_to_physicalkey,
+    code_to_key, code_to_location, create_key_event, event_mods, flags_mods, lalt_pressed,
+    ralt_pressed, scancode_to_physicalkey,
 };
 use super::window::WinitWindow;
-use super::DEVICE_ID;
-use crate::dpi::{LogicalPosition, LogicalSize};
+use super::{WindowId, DEVICE_ID};
+use crate::dpi::{LogicalInsets, LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition};
 use crate::event::{
-    DeviceEvent, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, TouchPhase,
-    WindowEvent,
+    DeviceEvent, DragAction, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta,
+    TouchPhase, WindowEvent,
 };
 use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey};
 use crate::platform::macos::OptionAsAlt;
+use crate::utils::RedrawDamage;
 use crate::window::WindowId as RootWindowId;
 
 #[derive(Debug)]
 struct CursorState {
     visible: bool,
     cursor: Retained<NSCursor>,
+    /// The animation `cursor` is a frame of, along with the index of that frame.
+    animation: Option<(Arc<CursorAnimation>, usize)>,
 }
 
 impl Default for CursorState {
     fn default() -> Self {
-        Self { visible: true, cursor: default_cursor() }
+        Self { visible: true, cursor: default_cursor(), animation: None }
     }
 }
 
@@ -62,6 +69,19 @@ enum ImeState {
     Committed,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+enum MomentumScroll {
+    /// No momentum scroll is in progress.
+    #[default]
+    Idle,
+
+    /// A momentum scroll is in progress.
+    Active,
+
+    /// The momentum scroll was cancelled, so its remaining events must be ignored.
+    Cancelled,
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq)]
     struct ModLocationMask: u8 {
@@ -115,6 +135,10 @@ pub struct ViewState {
     app_delegate: Retained<ApplicationDelegate>,
 
     cursor_state: RefCell<CursorState>,
+    /// Timer showing the next frame of an animated cursor, only running while the mouse is inside
+    /// the view.
+    cursor_animation_timer: RefCell<Option<Retained<NSTimer>>>,
+    mouse_inside: Cell<bool>,
     ime_position: Cell<NSPoint>,
     ime_size: Cell<NSSize>,
     modifiers: Cell<Modifiers>,
@@ -132,14 +156,36 @@ pub struct ViewState {
     /// to the application, even during IME
     forward_key_to_app: Cell<bool>,
 
+    /// The state of the momentum scroll following a touchpad scroll gesture.
+    momentum_scroll: Cell<MomentumScroll>,
+
+    /// True while secure event input is enabled for the window, in which case IME composition
+    /// is suppressed.
+    secure_input: Cell<bool>,
+
     marked_text: RefCell<Retained<NSMutableAttributedString>>,
     accepts_first_mouse: bool,
+    /// Whether mouse events over the titlebar are delivered to the view.
+    titlebar_pointer_events: Cell<bool>,
 
     // Weak reference because the window keeps a strong reference to the view
     _ns_window: WeakId<WinitWindow>,
 
     /// The state of the `Option` as `Alt`.
     option_as_alt: Cell<OptionAsAlt>,
+
+    /// Whether key equivalents (<kbd>Cmd</kbd> shortcuts) are offered to the menu bar.
+    key_equivalents_handled_by_menu: Cell<bool>,
+
+    /// Key code of a key equivalent that was offered to the menu, but hasn't reached `keyDown:`
+    /// yet. If it's still set once the key is released, the menu consumed the key press.
+    pending_key_equivalent: Cell<Option<u16>>,
+
+    /// The last reported safe area, `None` until the view was first resized.
+    safe_area: Cell<Option<PhysicalInsets<u32>>>,
+
+    /// The damage of the requested redraws.
+    redraw_damage: RefCell<RedrawDamage>,
 }
 
 declare_class!(
@@ -198,20 +244,43 @@ declare_class!(
             let logical_size = LogicalSize::new(rect.size.width as f64, rect.size.height as f64);
             let size = logical_size.to_physical::<u32>(self.scale_factor());
             self.queue_event(WindowEvent::Resized(size));
+
+            // The safe area changes with the size, e.g. when the titlebar covers the content.
+            self.emit_safe_area_event();
         }
 
         #[method(drawRect:)]
         fn draw_rect(&self, _rect: NSRect) {
             trace_scope!("drawRect:");
 
-            // It's a workaround for https://github.com/rust-windowing/winit/issues/2640, don't replace with `self.window_id()`.
-            if let Some(window) = self.ivars()._ns_window.load() {
-                self.ivars().app_delegate.handle_redraw(window.id());
-            }
+            self.ivars().app_delegate.handle_redraw(self);
 
             // This is a direct subclass of NSView, no need to call superclass' drawRect:
         }
 
+        #[method(cursorAnimationTimerDidFire:)]
+        fn cursor_animation_timer_did_fire(&self, _: Option<&AnyObject>) {
+            trace_scope!("cursorAnimationTimerDidFire:");
+            // The timer doesn't repeat, so it's invalid now.
+            self.ivars().cursor_animation_timer.take();
+
+            let mut cursor_state = self.ivars().cursor_state.borrow_mut();
+            let Some((animation, frame)) = &mut cursor_state.animation else {
+                return;
+            };
+            *frame = (*frame + 1) % animation.frames.len();
+            let cursor = animation.frames[*frame].0.clone();
+            cursor_state.cursor = cursor.clone();
+            let visible = cursor_state.visible;
+            drop(cursor_state);
+
+            if visible {
+                unsafe { cursor.set() };
+            }
+            self.window().invalidateCursorRectsForView(self);
+            self.start_cursor_animation();
+        }
+
         #[method(acceptsFirstResponder)]
         fn accepts_first_responder(&self) -> bool {
             trace_scope!("acceptsFirstResponder");
@@ -296,6 +365,12 @@ declare_class!(
                 )
             };
 
+            // Composition is not allowed during secure input.
+            if self.ivars().secure_input.get() {
+                self.inputContext().expect("input context").discardMarkedText();
+                return;
+            }
+
             // Update marked text.
             *self.ivars().marked_text.borrow_mut() = marked_text;
 
@@ -432,6 +507,39 @@ declare_class!(
         }
     }
 
+    unsafe impl NSObjectProtocol for WinitView {}
+
+    unsafe impl NSDraggingSource for WinitView {
+        #[method(draggingSession:sourceOperationMaskForDraggingContext:)]
+        fn dragging_session_source_operation_mask(
+            &self,
+            _session: &NSDraggingSession,
+            _context: NSDraggingContext,
+        ) -> NSDragOperation {
+            trace_scope!("draggingSession:sourceOperationMaskForDraggingContext:");
+            NSDragOperation::Copy | NSDragOperation::Move
+        }
+
+        /// Invoked when the drag started with `start_drag_and_drop` was dropped or cancelled
+        #[method(draggingSession:endedAtPoint:operation:)]
+        fn dragging_session_ended(
+            &self,
+            _session: &NSDraggingSession,
+            _screen_point: NSPoint,
+            operation: NSDragOperation,
+        ) {
+            trace_scope!("draggingSession:endedAtPoint:operation:");
+            let action = if operation.contains(NSDragOperation::Move) {
+                Some(DragAction::Move)
+            } else if operation.contains(NSDragOperation::Copy) {
+                Some(DragAction::Copy)
+            } else {
+                None
+            };
+            self.queue_event(WindowEvent::DragSourceFinished { action });
+        }
+    }
+
     unsafe impl WinitView {
         #[method(keyDown:)]
         fn key_down(&self, event: &NSEvent) {
@@ -447,6 +555,11 @@ declare_class!(
                 }
             }
 
+            // The key equivalent wasn't consumed by the menu.
+            if self.ivars().pending_key_equivalent.get() == Some(unsafe { event.keyCode() }) {
+                self.ivars().pending_key_equivalent.set(None);
+            }
+
             // Get the characters from the event.
             let old_ime_state = self.ivars().ime_state.get();
             self.ivars().forward_key_to_app.set(false);
@@ -458,7 +571,7 @@ declare_class!(
             // we must send the `KeyboardInput` event during IME if it triggered
             // `doCommandBySelector`. (doCommandBySelector means that the keyboard input
             // is not handled by IME and should be handled by the application)
-            if self.ivars().ime_allowed.get() {
+            if self.ivars().ime_allowed.get() && !self.ivars().secure_input.get() {
                 let events_for_nsview = NSArray::from_slice(&[&*event]);
                 unsafe { self.interpretKeyEvents(&events_for_nsview) };
 
@@ -496,6 +609,12 @@ declare_class!(
         fn key_up(&self, event: &NSEvent) {
             trace_scope!("keyUp:");
 
+            // The key press was consumed by a menu item, so don't report its release either.
+            if self.ivars().pending_key_equivalent.get() == Some(unsafe { event.keyCode() }) {
+                self.ivars().pending_key_equivalent.set(None);
+                return;
+            }
+
             let event = replace_event(event, self.option_as_alt());
             self.update_modifiers(&event, false);
 
@@ -512,6 +631,29 @@ declare_class!(
             }
         }
 
+        // Key equivalents are offered to the view hierarchy of the key window before the menu
+        // bar. By claiming them here, we make sure they're delivered to `keyDown:` exactly once,
+        // and that AppKit doesn't beep when no menu item matches.
+        #[method(performKeyEquivalent:)]
+        fn perform_key_equivalent(&self, event: &NSEvent) -> bool {
+            trace_scope!("performKeyEquivalent:");
+
+            let is_key_equivalent = unsafe { event.r#type() } == NSEventType::KeyDown
+                && unsafe { event.modifierFlags() }
+                    .contains(NSEventModifierFlags::NSEventModifierFlagCommand);
+
+            if !is_key_equivalent {
+                false
+            } else if self.ivars().key_equivalents_handled_by_menu.get() {
+                self.ivars().pending_key_equivalent.set(Some(unsafe { event.keyCode() }));
+                false
+            } else {
+                self.ivars().pending_key_equivalent.set(None);
+                unsafe { msg_send![self, keyDown: event] }
+                true
+            }
+        }
+
         #[method(flagsChanged:)]
         fn flags_changed(&self, event: &NSEvent) {
             trace_scope!("flagsChanged:");
@@ -640,6 +782,8 @@ declare_class!(
         #[method(mouseEntered:)]
         fn mouse_entered(&self, _event: &NSEvent) {
             trace_scope!("mouseEntered:");
+            self.ivars().mouse_inside.set(true);
+            self.start_cursor_animation();
             self.queue_event(WindowEvent::CursorEntered {
                 device_id: DEVICE_ID,
             });
@@ -648,6 +792,8 @@ declare_class!(
         #[method(mouseExited:)]
         fn mouse_exited(&self, _event: &NSEvent) {
             trace_scope!("mouseExited:");
+            self.ivars().mouse_inside.set(false);
+            self.stop_cursor_animation();
 
             self.queue_event(WindowEvent::CursorLeft {
                 device_id: DEVICE_ID,
@@ -670,14 +816,38 @@ declare_class!(
                 }
             };
 
+            let momentum_phase = unsafe { event.momentumPhase() };
+            let momentum_phase = if momentum_phase.is_empty() {
+                None
+            } else if self.ivars().momentum_scroll.get() == MomentumScroll::Cancelled {
+                // Drop the rest of a momentum scroll that was cancelled by losing focus.
+                if momentum_phase.intersects(NSEventPhase::Ended | NSEventPhase::Cancelled) {
+                    self.ivars().momentum_scroll.set(MomentumScroll::Idle);
+                }
+                return;
+            } else {
+                #[allow(non_upper_case_globals)]
+                let momentum_phase = match momentum_phase {
+                    NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
+                    NSEventPhase::Ended => TouchPhase::Ended,
+                    NSEventPhase::Cancelled => TouchPhase::Cancelled,
+                    _ => TouchPhase::Moved,
+                };
+                self.ivars().momentum_scroll.set(match momentum_phase {
+                    TouchPhase::Ended | TouchPhase::Cancelled => MomentumScroll::Idle,
+                    _ => MomentumScroll::Active,
+                });
+                Some(momentum_phase)
+            };
+
             // The "momentum phase," if any, has higher priority than touch phase (the two should
             // be mutually exclusive anyhow, which is why the API is rather incoherent). If no momentum
             // phase is recorded (or rather, the started/ended cases of the momentum phase) then we
             // report the touch phase.
             #[allow(non_upper_case_globals)]
-            let phase = match unsafe { event.momentumPhase() } {
-                NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
-                NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
+            let phase = match momentum_phase {
+                Some(TouchPhase::Started) => TouchPhase::Started,
+                Some(TouchPhase::Ended | TouchPhase::Cancelled) => TouchPhase::Ended,
                 _ => match unsafe { event.phase() } {
                     NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
                     NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
@@ -694,6 +864,7 @@ declare_class!(
                 device_id: DEVICE_ID,
                 delta,
                 phase,
+                momentum_phase,
             });
         }
 
@@ -777,6 +948,15 @@ declare_class!(
             trace_scope!("acceptsFirstMouse:");
             self.ivars().accepts_first_mouse
         }
+
+        // When the application handles clicks in the titlebar, it decides itself whether to move
+        // the window with `drag_window`.
+        #[method(mouseDownCanMoveWindow)]
+        fn mouse_down_can_move_window(&self) -> bool {
+            trace_scope!("mouseDownCanMoveWindow");
+            !self.ivars().titlebar_pointer_events.get()
+                && unsafe { msg_send![super(self), mouseDownCanMoveWindow] }
+        }
     }
 );
 
@@ -785,12 +965,16 @@ impl WinitView {
         app_delegate: &ApplicationDelegate,
         window: &WinitWindow,
         accepts_first_mouse: bool,
+        titlebar_pointer_events: bool,
         option_as_alt: OptionAsAlt,
+        key_equivalents_handled_by_menu: bool,
     ) -> Retained<Self> {
         let mtm = MainThreadMarker::from(window);
         let this = mtm.alloc().set_ivars(ViewState {
             app_delegate: app_delegate.retain(),
             cursor_state: Default::default(),
+            cursor_animation_timer: Default::default(),
+            mouse_inside: Default::default(),
             ime_position: Default::default(),
             ime_size: Default::default(),
             modifiers: Default::default(),
@@ -800,10 +984,17 @@ impl WinitView {
             input_source: Default::default(),
             ime_allowed: Default::default(),
             forward_key_to_app: Default::default(),
+            momentum_scroll: Default::default(),
+            secure_input: Default::default(),
             marked_text: Default::default(),
             accepts_first_mouse,
+            titlebar_pointer_events: Cell::new(titlebar_pointer_events),
             _ns_window: WeakId::new(&window.retain()),
             option_as_alt: Cell::new(option_as_alt),
+            key_equivalents_handled_by_menu: Cell::new(key_equivalents_handled_by_menu),
+            pending_key_equivalent: Default::default(),
+            safe_area: Default::default(),
+            redraw_damage: Default::default(),
         });
         let this: Retained<Self> = unsafe { msg_send_id![super(this), init] };
 
@@ -843,6 +1034,41 @@ impl WinitView {
         self.window().backingScaleFactor() as f64
     }
 
+    /// The safe area of the view, which excludes the titlebar when the content view extends
+    /// under it and the camera housing when the window is fullscreen on such a screen.
+    pub(super) fn safe_area(&self) -> PhysicalInsets<u32> {
+        // Only available since macOS 11.
+        if !self.respondsToSelector(sel!(safeAreaInsets)) {
+            return PhysicalInsets::new(0, 0, 0, 0);
+        }
+
+        let insets = unsafe { self.safeAreaInsets() };
+        LogicalInsets::new(insets.top, insets.left, insets.bottom, insets.right)
+            .to_physical(self.scale_factor())
+    }
+
+    pub(super) fn redraw_damage(&self) -> &RefCell<RedrawDamage> {
+        &self.ivars().redraw_damage
+    }
+
+    /// Prepares the view for `RedrawRequested`, returning its window if it still has one.
+    pub(super) fn deliver_redraw(&self) -> Option<WindowId> {
+        // It's a workaround for https://github.com/rust-windowing/winit/issues/2640, don't
+        // replace with `self.window()`.
+        let window = self.ivars()._ns_window.load()?;
+        self.ivars().redraw_damage.borrow_mut().deliver();
+        Some(window.id())
+    }
+
+    /// Emit [`WindowEvent::SafeAreaChanged`] if the safe area changed since it was last checked.
+    pub(super) fn emit_safe_area_event(&self) {
+        let safe_area = self.safe_area();
+        let previous = self.ivars().safe_area.replace(Some(safe_area));
+        if previous.is_some_and(|previous| previous != safe_area) {
+            self.queue_event(WindowEvent::SafeAreaChanged(safe_area));
+        }
+    }
+
     fn is_ime_enabled(&self) -> bool {
         !matches!(self.ivars().ime_state.get(), ImeState::Disabled)
     }
@@ -859,9 +1085,56 @@ impl WinitView {
         self.ivars().cursor_state.borrow().cursor.clone()
     }
 
-    pub(super) fn set_cursor_icon(&self, icon: Retained<NSCursor>) {
+    pub(super) fn cursor_animation(&self) -> Option<Arc<CursorAnimation>> {
+        let cursor_state = self.ivars().cursor_state.borrow();
+        cursor_state.animation.as_ref().map(|(animation, _)| animation.clone())
+    }
+
+    /// Set the cursor, which is the first frame of `animation` if the cursor is animated.
+    pub(super) fn set_cursor_icon(
+        &self,
+        icon: Retained<NSCursor>,
+        animation: Option<Arc<CursorAnimation>>,
+    ) {
+        self.stop_cursor_animation();
         let mut cursor_state = self.ivars().cursor_state.borrow_mut();
         cursor_state.cursor = icon;
+        cursor_state.animation = animation.map(|animation| (animation, 0));
+        drop(cursor_state);
+        self.start_cursor_animation();
+    }
+
+    /// Schedule the next frame of an animated cursor, if the mouse is inside the view.
+    fn start_cursor_animation(&self) {
+        if !self.ivars().mouse_inside.get()
+            || self.ivars().cursor_animation_timer.borrow().is_some()
+        {
+            return;
+        }
+
+        let duration = match &self.ivars().cursor_state.borrow().animation {
+            Some((animation, frame)) => animation.frames[*frame].1,
+            None => return,
+        };
+
+        let timer = unsafe {
+            NSTimer::timerWithTimeInterval_target_selector_userInfo_repeats(
+                duration.as_secs_f64(),
+                self,
+                sel!(cursorAnimationTimerDidFire:),
+                None,
+                false,
+            )
+        };
+        // Use the common modes, so that the animation continues while AppKit tracks the mouse.
+        unsafe { NSRunLoop::mainRunLoop().addTimer_forMode(&timer, NSRunLoopCommonModes) };
+        *self.ivars().cursor_animation_timer.borrow_mut() = Some(timer);
+    }
+
+    pub(super) fn stop_cursor_animation(&self) {
+        if let Some(timer) = self.ivars().cursor_animation_timer.take() {
+            unsafe { timer.invalidate() };
+        }
     }
 
     /// Set whether the cursor should be visible or not.
@@ -895,6 +1168,29 @@ impl WinitView {
         }
     }
 
+    pub(super) fn set_secure_input(&self, secure_input: bool) {
+        if self.ivars().secure_input.get() == secure_input {
+            return;
+        }
+        self.ivars().secure_input.set(secure_input);
+
+        let input_context = self.inputContext().expect("input context");
+        if secure_input {
+            // Finish any ongoing composition, and only allow input sources without an IME.
+            if unsafe { self.hasMarkedText() } {
+                *self.ivars().marked_text.borrow_mut() = NSMutableAttributedString::new();
+                input_context.discardMarkedText();
+                self.ivars().ime_state.set(ImeState::Ground);
+                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            }
+            let locales =
+                NSArray::from_id_slice(&[unsafe { NSAllRomanInputSourcesLocaleIdentifier }.copy()]);
+            unsafe { input_context.setAllowedInputSourceLocales(Some(&locales)) };
+        } else {
+            unsafe { input_context.setAllowedInputSourceLocales(None) };
+        }
+    }
+
     pub(super) fn set_ime_cursor_area(&self, position: NSPoint, size: NSSize) {
         self.ivars().ime_position.set(position);
         self.ivars().ime_size.set(size);
@@ -902,14 +1198,38 @@ impl WinitView {
         input_context.invalidateCharacterCoordinates();
     }
 
+    /// Cancel the ongoing momentum scroll, if any, and ignore its remaining events.
+    pub(super) fn cancel_momentum_scroll(&self) {
+        if self.ivars().momentum_scroll.get() != MomentumScroll::Active {
+            return;
+        }
+        self.ivars().momentum_scroll.set(MomentumScroll::Cancelled);
+
+        self.queue_event(WindowEvent::MouseWheel {
+            device_id: DEVICE_ID,
+            delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 0.0)),
+            phase: TouchPhase::Ended,
+            momentum_phase: Some(TouchPhase::Cancelled),
+        });
+    }
+
     /// Reset modifiers and emit a synthetic ModifiersChanged event if deemed necessary.
     pub(super) fn reset_modifiers(&self) {
-        if !self.ivars().modifiers.get().state().is_empty() {
+        if self.ivars().modifiers.get() != Modifiers::default() {
             self.ivars().modifiers.set(Modifiers::default());
             self.queue_event(WindowEvent::ModifiersChanged(self.ivars().modifiers.get()));
         }
     }
 
+    /// Emit the current modifiers, like caps lock which may have been toggled while the window
+    /// wasn't focused. No key event is emitted for them.
+    pub(super) fn refresh_modifiers(&self) {
+        let modifiers = flags_mods(unsafe { NSEvent::modifierFlags_class() });
+        if self.ivars().modifiers.replace(modifiers) != modifiers {
+            self.queue_event(WindowEvent::ModifiersChanged(modifiers));
+        }
+    }
+
     pub(super) fn set_option_as_alt(&self, value: OptionAsAlt) {
         self.ivars().option_as_alt.set(value)
     }
@@ -918,6 +1238,38 @@ impl WinitView {
         self.ivars().option_as_alt.get()
     }
 
+    pub(super) fn set_titlebar_pointer_events(&self, value: bool) {
+        self.ivars().titlebar_pointer_events.set(value)
+    }
+
+    pub(super) fn titlebar_pointer_events(&self) -> bool {
+        self.ivars().titlebar_pointer_events.get()
+    }
+
+    /// Handle a mouse event over the titlebar, as if it had been sent to the view.
+    pub(super) fn handle_titlebar_event(&self, event: &NSEvent) {
+        match unsafe { event.r#type() } {
+            NSEventType::LeftMouseDown => unsafe { msg_send![self, mouseDown: event] },
+            NSEventType::LeftMouseUp => unsafe { msg_send![self, mouseUp: event] },
+            NSEventType::LeftMouseDragged => unsafe { msg_send![self, mouseDragged: event] },
+            NSEventType::RightMouseDown => unsafe { msg_send![self, rightMouseDown: event] },
+            NSEventType::RightMouseUp => unsafe { msg_send![self, rightMouseUp: event] },
+            NSEventType::RightMouseDragged => unsafe { msg_send![self, rightMouseDragged: event] },
+            NSEventType::OtherMouseDown => unsafe { msg_send![self, otherMouseDown: event] },
+            NSEventType::OtherMouseUp => unsafe { msg_send![self, otherMouseUp: event] },
+            NSEventType::OtherMouseDragged => unsafe { msg_send![self, otherMouseDragged: event] },
+            _ => (),
+        }
+    }
+
+    pub(super) fn set_key_equivalents_handled_by_menu(&self, value: bool) {
+        self.ivars().key_equivalents_handled_by_menu.set(value)
+    }
+
+    pub(super) fn key_equivalents_handled_by_menu(&self) -> bool {
+        self.ivars().key_equivalents_handled_by_menu.get()
+    }
+
     /// Update modifiers if `event` has something different
     fn update_modifiers(&self, ns_event: &NSEvent, is_flags_changed_event: bool) {
         use ElementState::{Pressed, Released};
@@ -1034,6 +1386,8 @@ impl WinitView {
 
     fn mouse_click(&self, event: &NSEvent, button_state: ElementState) {
         let button = mouse_button(event);
+        // The release has the click count of the press it ends.
+        let click_count = unsafe { event.clickCount() }.max(1) as u32;
 
         self.update_modifiers(event, false);
 
@@ -1041,6 +1395,7 @@ impl WinitView {
             device_id: DEVICE_ID,
             state: button_state,
             button,
+            click_count,
         });
     }
 
@@ -1,11 +1,12 @@
 #![allow(clippy::unnecessary_cast)]
 
 use objc2::rc::{autoreleasepool, Retained};
-use objc2::{declare_class, mutability, ClassType, DeclaredClass};
-use objc2_app_kit::{NSResponder, NSWindow};
-use objc2_foundation::{MainThreadBound, MainThreadMarker, NSObject};
+use objc2::{declare_class, msg_send, mutability, ClassType, DeclaredClass};
+use objc2_app_kit::{NSEvent, NSEventType, NSResponder, NSWindow, NSWindowButton};
+use objc2_foundation::{MainThreadBound, MainThreadMarker, NSObject, NSObjectProtocol};
 
 use super::event_loop::ActiveEventLoop;
+use super::view::WinitView;
 use super::window_delegate::WindowDelegate;
 use crate::error::OsError as RootOsError;
 use crate::window::WindowAttributes;
@@ -49,6 +50,10 @@ impl Window {
         self.delegate.get_on_main(|delegate| f(delegate))
     }
 
+    pub(crate) fn ns_window(&self, mtm: MainThreadMarker) -> &WinitWindow {
+        self.window.get(mtm)
+    }
+
     #[cfg(feature = "rwh_06")]
     #[inline]
     pub(crate) fn raw_window_handle_rwh_06(
@@ -116,6 +121,16 @@ declare_class!(
             trace_scope!("canBecomeKeyWindow");
             true
         }
+
+        // Mouse events over the titlebar are normally consumed by the titlebar itself, so we
+        // forward them to our view directly if requested.
+        #[method(sendEvent:)]
+        fn send_event(&self, event: &NSEvent) {
+            match self.titlebar_event_target(event) {
+                Some(view) => view.handle_titlebar_event(event),
+                None => unsafe { msg_send![super(self), sendEvent: event] },
+            }
+        }
     }
 );
 
@@ -123,4 +138,60 @@ impl WinitWindow {
     pub(super) fn id(&self) -> WindowId {
         WindowId(self as *const Self as usize)
     }
+
+    /// Returns the view that should receive `event` instead of the titlebar, if any.
+    fn titlebar_event_target(&self, event: &NSEvent) -> Option<Retained<WinitView>> {
+        if !matches!(
+            unsafe { event.r#type() },
+            NSEventType::LeftMouseDown
+                | NSEventType::LeftMouseUp
+                | NSEventType::LeftMouseDragged
+                | NSEventType::RightMouseDown
+                | NSEventType::RightMouseUp
+                | NSEventType::RightMouseDragged
+                | NSEventType::OtherMouseDown
+                | NSEventType::OtherMouseUp
+                | NSEventType::OtherMouseDragged
+        ) {
+            return None;
+        }
+
+        let view = self.contentView()?;
+        if !view.is_kind_of::<WinitView>() {
+            return None;
+        }
+        // SAFETY: Just checked that the view is a `WinitView`.
+        let view: Retained<WinitView> = unsafe { Retained::cast(view) };
+        if !view.titlebar_pointer_events() {
+            return None;
+        }
+
+        // The titlebar is the part of the window not covered by the content layout rect.
+        let point = unsafe { event.locationInWindow() };
+        if point.y < unsafe { self.contentLayoutRect() }.size.height {
+            return None;
+        }
+
+        // Leave the traffic lights to AppKit.
+        let over_button = [
+            NSWindowButton::NSWindowCloseButton,
+            NSWindowButton::NSWindowMiniaturizeButton,
+            NSWindowButton::NSWindowZoomButton,
+        ]
+        .into_iter()
+        .filter_map(|button| self.standardWindowButton(button))
+        .filter(|button| !unsafe { button.isHidden() })
+        .any(|button| {
+            let frame = button.convertRect_toView(button.bounds(), None);
+            frame.origin.x <= point.x
+                && point.x < frame.origin.x + frame.size.width
+                && frame.origin.y <= point.y
+                && point.y < frame.origin.y + frame.size.height
+        });
+        if over_button {
+            return None;
+        }
+
+        Some(view)
+    }
 }
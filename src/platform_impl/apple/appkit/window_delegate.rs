@@ -2,44 +2,54 @@
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::c_void;
+use std::path::PathBuf;
 use std::ptr;
 use std::sync::{Arc, Mutex};
 
 use core_graphics::display::{CGDisplay, CGPoint};
 use monitor::VideoModeHandle;
-use objc2::rc::{autoreleasepool, Retained};
+use objc2::rc::{autoreleasepool, Retained, Weak};
 use objc2::runtime::{AnyObject, ProtocolObject};
-use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
 use objc2_app_kit::{
     NSAppKitVersionNumber, NSAppKitVersionNumber10_12, NSAppearance, NSAppearanceCustomization,
-    NSAppearanceNameAqua, NSApplication, NSApplicationPresentationOptions, NSBackingStoreType,
-    NSColor, NSDraggingDestination, NSFilenamesPboardType, NSPasteboard,
-    NSRequestUserAttentionType, NSScreen, NSView, NSWindowButton, NSWindowDelegate,
-    NSWindowFullScreenButton, NSWindowLevel, NSWindowOcclusionState, NSWindowOrderingMode,
-    NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode, NSWindowTitleVisibility,
+    NSAppearanceNameAqua, NSApplication, NSApplicationDidChangeScreenParametersNotification,
+    NSApplicationPresentationOptions, NSBackingStoreType, NSColor, NSDraggingDestination,
+    NSDraggingItem, NSEvent, NSFilenamesPboardType, NSPasteboard, NSPasteboardTypeString,
+    NSRequestUserAttentionType, NSScreen, NSView, NSWindow, NSWindowButton,
+    NSWindowCollectionBehavior, NSWindowDelegate, NSWindowFullScreenButton, NSWindowLevel,
+    NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask,
+    NSWindowTabbingMode, NSWindowTitleVisibility, NSWorkspace,
 };
 use objc2_foundation::{
     ns_string, CGFloat, MainThreadMarker, NSArray, NSCopying, NSDictionary, NSKeyValueChangeKey,
-    NSKeyValueChangeNewKey, NSKeyValueChangeOldKey, NSKeyValueObservingOptions, NSObject,
-    NSObjectNSDelayedPerforming, NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint,
-    NSRect, NSSize, NSString,
+    NSKeyValueChangeNewKey, NSKeyValueChangeOldKey, NSKeyValueObservingOptions,
+    NSNotificationCenter, NSObject, NSObjectNSDelayedPerforming,
+    NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint, NSRect, NSRunLoop,
+    NSRunLoopCommonModes, NSSize, NSString, NSTimer, NSUserDefaults, NSURL,
 };
 use tracing::{trace, warn};
 
 use super::app_state::ApplicationDelegate;
-use super::cursor::cursor_from_icon;
+use super::cursor::{cursor_from_icon, CustomCursor};
 use super::monitor::{self, flip_window_screen_coordinates, get_display_id};
 use super::observer::RunLoop;
 use super::view::WinitView;
-use super::window::WinitWindow;
-use super::{ffi, Fullscreen, MonitorHandle, OsError, WindowId};
-use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use super::window::{Window, WinitWindow};
+use super::{dock, ffi, Fullscreen, MonitorHandle, OsError, WindowId};
+use crate::dpi::{
+    LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size,
+};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event::{InnerSizeWriter, WindowEvent};
-use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
+use crate::event::{DragDropEvent, DragDropItem, DragDropKinds, InnerSizeWriter, WindowEvent};
+use crate::monitor::{ColorProperties, MonitorHandle as RootMonitorHandle};
+use crate::platform::macos::{
+    CollectionBehavior, FullscreenStyle, OptionAsAlt, PresentationOptions, WindowExtMacOS,
+};
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId as RootWindowId, WindowLevel,
+    AspectRatio, Cursor, CursorGrabMode, DragPayload, Icon, ImeCapabilities, ImePurpose,
+    ProgressState, Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
+    WindowButtons, WindowId as RootWindowId, WindowLevel,
 };
 
 #[derive(Clone, Debug)]
@@ -53,8 +63,12 @@ pub struct PlatformSpecificWindowAttributes {
     pub disallow_hidpi: bool,
     pub has_shadow: bool,
     pub accepts_first_mouse: bool,
+    pub titlebar_pointer_events: bool,
     pub tabbing_identifier: Option<String>,
     pub option_as_alt: OptionAsAlt,
+    pub key_equivalents_handled_by_menu: bool,
+    pub frame_autosave_name: Option<String>,
+    pub fullscreen_style: FullscreenStyle,
 }
 
 impl Default for PlatformSpecificWindowAttributes {
@@ -70,8 +84,12 @@ impl Default for PlatformSpecificWindowAttributes {
             disallow_hidpi: false,
             has_shadow: true,
             accepts_first_mouse: true,
+            titlebar_pointer_events: false,
             tabbing_identifier: None,
             option_as_alt: Default::default(),
+            key_equivalents_handled_by_menu: true,
+            frame_autosave_name: None,
+            fullscreen_style: Default::default(),
         }
     }
 }
@@ -87,12 +105,20 @@ pub(crate) struct State {
     //
     // This is expressed in native screen coordinates.
     previous_position: Cell<Option<NSPoint>>,
+    /// Timer polling the window position while the user drags the window, since
+    /// `windowDidMove:` is only emitted once the drag ends.
+    move_timer: RefCell<Option<Retained<NSTimer>>>,
 
     // Used to prevent redundant events.
     previous_scale_factor: Cell<f64>,
+    previous_edr_headroom: Cell<f32>,
+    previous_monitor: RefCell<Option<MonitorHandle>>,
+    previous_color_properties: Cell<Option<ColorProperties>>,
 
     /// The current resize increments for the window content.
     resize_increments: Cell<NSSize>,
+    /// The aspect ratio for the window content, which takes over the resize increments.
+    aspect_ratio: Cell<Option<AspectRatio>>,
     /// Whether the window is showing decorations.
     decorations: Cell<bool>,
     resizable: Cell<bool>,
@@ -117,9 +143,33 @@ pub(crate) struct State {
     // or windowWillExitFullScreen and windowDidExitFullScreen.
     // We must not toggle fullscreen when this is true.
     in_fullscreen_transition: Cell<bool>,
+    fullscreen_style: Cell<FullscreenStyle>,
+    /// True if the current borderless fullscreen was entered with `FullscreenStyle::Fast`.
+    in_fast_fullscreen: Cell<bool>,
+    fullscreen_presentation_options: Cell<Option<PresentationOptions>>,
+    /// Presentation options saved before applying `fullscreen_presentation_options`, and
+    /// restored when the window resigns key.
+    presentation_opts_before_fullscreen_options: Cell<Option<NSApplicationPresentationOptions>>,
     standard_frame: Cell<Option<NSRect>>,
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
+    /// The collection behavior requested by the user, re-applied after fullscreen transitions
+    /// since AppKit may rewrite it.
+    collection_behavior: Cell<Option<NSWindowCollectionBehavior>>,
+    ime_purpose: Cell<ImePurpose>,
+    /// Whether we currently hold secure event input. `EnableSecureEventInput` is reference
+    /// counted system-wide, so calls must always be balanced.
+    secure_input_enabled: Cell<bool>,
+    /// The progress set on the dock tile by this window.
+    progress: Cell<ProgressState>,
+    /// The count set on the badge of the dock tile by this window.
+    badge_count: Cell<Option<u64>>,
+    /// The window is only added as a child window of its transient parent while it is visible,
+    /// since adding it orders it in.
+    transient_parent: RefCell<Option<Weak<NSWindow>>>,
+    /// The kinds of data offered by the drag over the window, empty when there is none.
+    drag_kinds: Cell<DragDropKinds>,
+    drag_position: Cell<PhysicalPosition<f64>>,
 }
 
 declare_class!(
@@ -154,6 +204,19 @@ declare_class!(
                 // be called after the window closes.
                 self.window().setDelegate(None);
             });
+            // The timers retain the delegate and the view.
+            self.stop_move_timer();
+            self.view().stop_cursor_animation();
+            // Secure input is system-wide, so it must not outlive the window.
+            self.set_secure_input(false);
+            // The dock tile outlives the window, so clear the progress it has shown.
+            if self.ivars().progress.replace(ProgressState::None) != ProgressState::None {
+                dock::set_progress(MainThreadMarker::from(self), ProgressState::None);
+            }
+            if self.ivars().badge_count.take().is_some() {
+                dock::set_badge_count(MainThreadMarker::from(self), None);
+            }
+            self.revert_fullscreen_presentation_options();
             self.queue_event(WindowEvent::Destroyed);
         }
 
@@ -168,8 +231,15 @@ declare_class!(
         fn window_will_start_live_resize(&self, _: Option<&AnyObject>) {
             trace_scope!("windowWillStartLiveResize:");
 
-            let increments = self.ivars().resize_increments.get();
-            self.set_resize_increments_inner(increments);
+            // The content aspect ratio and resize increments are mutually exclusive in AppKit,
+            // setting either clears the other.
+            if let Some(aspect_ratio) = self.ivars().aspect_ratio.get() {
+                let ratio = NSSize::new(aspect_ratio.width() as _, aspect_ratio.height() as _);
+                unsafe { self.window().setContentAspectRatio(ratio) };
+            } else {
+                let increments = self.ivars().resize_increments.get();
+                self.set_resize_increments_inner(increments);
+            }
         }
 
         #[method(windowDidEndLiveResize:)]
@@ -178,6 +248,12 @@ declare_class!(
             self.set_resize_increments_inner(NSSize::new(1., 1.));
         }
 
+        #[method(windowWillMove:)]
+        fn window_will_move(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowWillMove:");
+            self.start_move_timer();
+        }
+
         // This won't be triggered if the move was part of a resize.
         #[method(windowDidMove:)]
         fn window_did_move(&self, _: Option<&AnyObject>) {
@@ -188,6 +264,9 @@ declare_class!(
         #[method(windowDidChangeBackingProperties:)]
         fn window_did_change_backing_properties(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidChangeBackingProperties:");
+            // The monitor change has to be reported before the scale factor change it causes.
+            self.emit_monitor_changed_event();
+
             let scale_factor = self.scale_factor();
             if scale_factor == self.ivars().previous_scale_factor.get() {
                 return;
@@ -206,7 +285,10 @@ declare_class!(
             trace_scope!("windowDidBecomeKey:");
             // TODO: center the cursor if the window had mouse grab when it
             // lost focus
+            self.update_secure_input();
+            self.apply_fullscreen_presentation_options();
             self.queue_event(WindowEvent::Focused(true));
+            self.view().refresh_modifiers();
         }
 
         #[method(windowDidResignKey:)]
@@ -220,6 +302,9 @@ declare_class!(
             // easily fall out of synchrony with reality.  This requires us to emit
             // a synthetic ModifiersChanged event when we lose focus.
             self.view().reset_modifiers();
+            self.view().cancel_momentum_scroll();
+            self.update_secure_input();
+            self.revert_fullscreen_presentation_options();
 
             self.queue_event(WindowEvent::Focused(false));
         }
@@ -254,6 +339,8 @@ declare_class!(
         #[method(windowWillExitFullScreen:)]
         fn window_will_exit_fullscreen(&self, _: Option<&AnyObject>) {
             trace_scope!("windowWillExitFullScreen:");
+            // AppKit restores the presentation options itself when leaving fullscreen.
+            self.ivars().presentation_opts_before_fullscreen_options.set(None);
 
             self.ivars().in_fullscreen_transition.set(true);
         }
@@ -290,9 +377,14 @@ declare_class!(
             trace_scope!("windowDidEnterFullScreen:");
             self.ivars().initial_fullscreen.set(false);
             self.ivars().in_fullscreen_transition.set(false);
+            self.restore_collection_behavior();
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
+            } else {
+                self.apply_fullscreen_presentation_options();
             }
+            // The camera housing is only excluded once the window covers the whole screen.
+            self.view().emit_safe_area_event();
         }
 
         /// Invoked when exited fullscreen
@@ -302,9 +394,11 @@ declare_class!(
 
             self.restore_state_from_fullscreen();
             self.ivars().in_fullscreen_transition.set(false);
+            self.restore_collection_behavior();
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
+            self.view().emit_safe_area_event();
         }
 
         /// Invoked when fail to enter fullscreen
@@ -353,11 +447,39 @@ declare_class!(
         fn window_did_change_screen(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidChangeScreen:");
             let is_simple_fullscreen = self.ivars().is_simple_fullscreen.get();
-            if is_simple_fullscreen {
+            if is_simple_fullscreen || self.ivars().in_fast_fullscreen.get() {
                 if let Some(screen) = self.window().screen() {
                     self.window().setFrame_display(screen.frame(), true);
                 }
             }
+            self.emit_monitor_changed_event();
+            // `MonitorChanged` already covers the new color properties.
+            self.ivars()
+                .previous_color_properties
+                .set(self.window().screen().map(|screen| monitor::color_properties(&screen)));
+            self.emit_edr_headroom_event();
+            self.view().emit_safe_area_event();
+        }
+
+        #[method(moveTimerDidFire:)]
+        fn move_timer_did_fire(&self, _: Option<&AnyObject>) {
+            trace_scope!("moveTimerDidFire:");
+            self.emit_move_event();
+            // The drag ends when the mouse button is released.
+            if unsafe { NSEvent::pressedMouseButtons() } == 0 {
+                self.stop_move_timer();
+            }
+        }
+    }
+
+    unsafe impl WindowDelegate {
+        // Observed through `NSApplicationDidChangeScreenParametersNotification`, which is also
+        // posted when the EDR headroom of a screen changes.
+        #[method(screenParametersDidChange:)]
+        fn screen_parameters_did_change(&self, _: Option<&AnyObject>) {
+            trace_scope!("screenParametersDidChange:");
+            self.emit_edr_headroom_event();
+            self.emit_color_properties_event();
         }
     }
 
@@ -367,18 +489,42 @@ declare_class!(
         fn dragging_entered(&self, sender: &NSObject) -> bool {
             trace_scope!("draggingEntered:");
 
-            use std::path::PathBuf;
-
             let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            let paths = dragged_paths(&pb);
 
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::HoveredFile(path));
+            paths.iter().for_each(|path| {
+                #[allow(deprecated)]
+                self.queue_event(WindowEvent::HoveredFile(path.clone()));
             });
 
-            true
+            let kinds = if !paths.is_empty() {
+                DragDropKinds::PATHS
+            } else if unsafe { pb.stringForType(NSPasteboardTypeString) }.is_some() {
+                DragDropKinds::TEXT
+            } else {
+                DragDropKinds::empty()
+            };
+            self.ivars().drag_kinds.set(kinds);
+            if !kinds.is_empty() {
+                let position = self.drag_position(sender);
+                self.ivars().drag_position.set(position);
+                self.queue_event(WindowEvent::DragDrop(DragDropEvent::Entered { kinds, position }));
+            }
+
+            !kinds.is_empty()
+        }
+
+        /// Invoked periodically as the image is held within the destination area
+        #[method(draggingUpdated:)]
+        fn dragging_updated(&self, sender: &NSObject) -> bool {
+            trace_scope!("draggingUpdated:");
+
+            let accepted = !self.ivars().drag_kinds.get().is_empty();
+            if accepted {
+                self.update_drag_position(sender);
+            }
+
+            accepted
         }
 
         /// Invoked when the image is released
@@ -393,17 +539,29 @@ declare_class!(
         fn perform_drag_operation(&self, sender: &NSObject) -> bool {
             trace_scope!("performDragOperation:");
 
-            use std::path::PathBuf;
-
             let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            let paths = dragged_paths(&pb);
 
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::DroppedFile(path));
+            paths.iter().for_each(|path| {
+                #[allow(deprecated)]
+                self.queue_event(WindowEvent::DroppedFile(path.clone()));
             });
 
+            let kinds = self.ivars().drag_kinds.replace(DragDropKinds::empty());
+            if !kinds.is_empty() {
+                self.update_drag_position(sender);
+
+                let items = if kinds == DragDropKinds::TEXT {
+                    unsafe { pb.stringForType(NSPasteboardTypeString) }
+                        .map(|text| DragDropItem::Text(text.to_string()))
+                        .into_iter()
+                        .collect()
+                } else {
+                    paths.into_iter().map(DragDropItem::Path).collect()
+                };
+                self.queue_event(WindowEvent::DragDrop(DragDropEvent::Dropped { items }));
+            }
+
             true
         }
 
@@ -417,7 +575,12 @@ declare_class!(
         #[method(draggingExited:)]
         fn dragging_exited(&self, _sender: Option<&NSObject>) {
             trace_scope!("draggingExited:");
+            #[allow(deprecated)]
             self.queue_event(WindowEvent::HoveredFileCancelled);
+
+            if !self.ivars().drag_kinds.replace(DragDropKinds::empty()).is_empty() {
+                self.queue_event(WindowEvent::DragDrop(DragDropEvent::Left));
+            }
         }
     }
 
@@ -478,6 +641,10 @@ impl Drop for WindowDelegate {
         unsafe {
             self.window().removeObserver_forKeyPath(self, ns_string!("effectiveAppearance"));
         }
+        // Normally released in `windowWillClose:`, but make sure to never leak it.
+        if self.ivars().secure_input_enabled.get() {
+            unsafe { ffi::DisableSecureEventInput() };
+        }
     }
 }
 
@@ -621,11 +788,22 @@ fn new_window(
             window.center();
         }
 
+        if let Some(name) = &attrs.platform_specific.frame_autosave_name {
+            let name = NSString::from_str(name);
+            // Explicitly requested positions and sizes take precedence over the saved frame.
+            if attrs.position.is_none() && attrs.inner_size.is_none() && screen.is_none() {
+                unsafe { window.setFrameUsingName(&name) };
+            }
+            unsafe { window.setFrameAutosaveName(&name) };
+        }
+
         let view = WinitView::new(
             app_delegate,
             &window,
             attrs.platform_specific.accepts_first_mouse,
+            attrs.platform_specific.titlebar_pointer_events,
             attrs.platform_specific.option_as_alt,
+            attrs.platform_specific.key_equivalents_handled_by_menu,
         );
 
         // The default value of `setWantsBestResolutionOpenGLSurface:` was `false` until
@@ -654,10 +832,10 @@ fn new_window(
         }
 
         // register for drag and drop operations.
-        window
-            .registerForDraggedTypes(&NSArray::from_id_slice(&[
-                unsafe { NSFilenamesPboardType }.copy()
-            ]));
+        window.registerForDraggedTypes(&NSArray::from_id_slice(&[
+            unsafe { NSFilenamesPboardType }.copy(),
+            unsafe { NSPasteboardTypeString }.copy(),
+        ]));
 
         Some(window)
     })
@@ -703,6 +881,17 @@ impl WindowDelegate {
             };
 
         let scale_factor = window.backingScaleFactor() as _;
+        let edr_headroom =
+            window.screen().map(|screen| monitor::edr_headroom(&screen)).unwrap_or(1.0);
+        let previous_monitor =
+            window.screen().map(|screen| MonitorHandle::new(get_display_id(&screen)));
+        let transient_parent = attrs.transient_parent.and_then(|parent| {
+            NSApplication::sharedApplication(mtm)
+                .windows()
+                .into_iter()
+                .find(|window| Retained::as_ptr(window) as usize == parent.0 .0)
+        });
+        let color_properties = window.screen().map(|screen| monitor::color_properties(&screen));
 
         if let Some(appearance) = theme_to_appearance(attrs.preferred_theme) {
             unsafe { window.setAppearance(Some(&appearance)) };
@@ -712,19 +901,36 @@ impl WindowDelegate {
             app_delegate: app_delegate.retain(),
             window: window.retain(),
             previous_position: Cell::new(None),
+            move_timer: RefCell::new(None),
             previous_scale_factor: Cell::new(scale_factor),
+            previous_edr_headroom: Cell::new(edr_headroom),
+            previous_monitor: RefCell::new(previous_monitor),
+            previous_color_properties: Cell::new(color_properties),
             resize_increments: Cell::new(resize_increments),
+            aspect_ratio: Cell::new(attrs.aspect_ratio),
             decorations: Cell::new(attrs.decorations),
             resizable: Cell::new(attrs.resizable),
             maximized: Cell::new(attrs.maximized),
             save_presentation_opts: Cell::new(None),
             initial_fullscreen: Cell::new(attrs.fullscreen.is_some()),
+            fullscreen_style: Cell::new(attrs.platform_specific.fullscreen_style),
+            in_fast_fullscreen: Cell::new(false),
+            fullscreen_presentation_options: Cell::new(None),
+            presentation_opts_before_fullscreen_options: Cell::new(None),
             fullscreen: RefCell::new(None),
             target_fullscreen: RefCell::new(None),
             in_fullscreen_transition: Cell::new(false),
             standard_frame: Cell::new(None),
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
+            collection_behavior: Cell::new(None),
+            ime_purpose: Cell::new(ImePurpose::Normal),
+            secure_input_enabled: Cell::new(false),
+            progress: Cell::new(ProgressState::None),
+            badge_count: Cell::new(None),
+            transient_parent: RefCell::new(transient_parent.as_deref().map(Weak::from)),
+            drag_kinds: Cell::new(DragDropKinds::empty()),
+            drag_position: Cell::new(PhysicalPosition::default()),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send_id![super(delegate), init] };
 
@@ -749,6 +955,17 @@ impl WindowDelegate {
             )
         };
 
+        // Listen for changes to the screen parameters, such as the EDR headroom.
+        let notification_center = unsafe { NSNotificationCenter::defaultCenter() };
+        unsafe {
+            notification_center.addObserver_selector_name_object(
+                &delegate,
+                sel!(screenParametersDidChange:),
+                Some(NSApplicationDidChangeScreenParametersNotification),
+                None,
+            )
+        };
+
         if attrs.blur {
             delegate.set_blur(attrs.blur);
         }
@@ -787,6 +1004,8 @@ impl WindowDelegate {
             delegate.set_maximized(attrs.maximized);
         }
 
+        delegate.update_transient_parent();
+
         Ok(delegate)
     }
 
@@ -806,6 +1025,21 @@ impl WindowDelegate {
         self.window().id()
     }
 
+    /// The position of the drag in the content view.
+    fn drag_position(&self, sender: &NSObject) -> PhysicalPosition<f64> {
+        let location: NSPoint = unsafe { msg_send![sender, draggingLocation] };
+        // The view is flipped, so this is relative to the upper-left corner.
+        let point = self.view().convertPoint_fromView(location, None);
+        LogicalPosition::new(point.x as f64, point.y as f64).to_physical(self.scale_factor())
+    }
+
+    fn update_drag_position(&self, sender: &NSObject) {
+        let position = self.drag_position(sender);
+        if self.ivars().drag_position.replace(position) != position {
+            self.queue_event(WindowEvent::DragDrop(DragDropEvent::Moved { position }));
+        }
+    }
+
     pub(crate) fn queue_event(&self, event: WindowEvent) {
         let window_id = RootWindowId(self.window().id());
         self.ivars().app_delegate.maybe_queue_with_handler(move |app, event_loop| {
@@ -849,6 +1083,76 @@ impl WindowDelegate {
         self.queue_event(WindowEvent::Moved(position));
     }
 
+    fn start_move_timer(&self) {
+        if self.ivars().move_timer.borrow().is_some() {
+            return;
+        }
+
+        // Poll at most once per display refresh.
+        let screen = self.window().screen();
+        let frames_per_second = screen
+            .filter(|screen| screen.respondsToSelector(sel!(maximumFramesPerSecond)))
+            .map(|screen| unsafe { screen.maximumFramesPerSecond() })
+            .filter(|&fps| fps > 0)
+            .unwrap_or(60);
+
+        let timer = unsafe {
+            NSTimer::timerWithTimeInterval_target_selector_userInfo_repeats(
+                1.0 / frames_per_second as f64,
+                self,
+                sel!(moveTimerDidFire:),
+                None,
+                true,
+            )
+        };
+        // Use the common modes, so that the timer also fires while AppKit is tracking the drag.
+        unsafe { NSRunLoop::mainRunLoop().addTimer_forMode(&timer, NSRunLoopCommonModes) };
+        *self.ivars().move_timer.borrow_mut() = Some(timer);
+    }
+
+    fn stop_move_timer(&self) {
+        if let Some(timer) = self.ivars().move_timer.take() {
+            unsafe { timer.invalidate() };
+        }
+    }
+
+    fn emit_edr_headroom_event(&self) {
+        let edr_headroom =
+            self.window().screen().map(|screen| monitor::edr_headroom(&screen)).unwrap_or(1.0);
+        if edr_headroom == self.ivars().previous_edr_headroom.get() {
+            return;
+        }
+        self.ivars().previous_edr_headroom.set(edr_headroom);
+
+        self.queue_event(WindowEvent::EdrHeadroomChanged(edr_headroom));
+    }
+
+    fn emit_color_properties_event(&self) {
+        let color_properties =
+            self.window().screen().map(|screen| monitor::color_properties(&screen));
+        if color_properties == self.ivars().previous_color_properties.get() {
+            return;
+        }
+        self.ivars().previous_color_properties.set(color_properties);
+
+        self.queue_event(WindowEvent::ColorPropertiesChanged(color_properties));
+    }
+
+    fn emit_monitor_changed_event(&self) {
+        let Some(monitor) = self.current_monitor_inner() else {
+            return;
+        };
+        let old = self.ivars().previous_monitor.replace(Some(monitor.clone()));
+        if old.as_ref() == Some(&monitor) {
+            return;
+        }
+
+        self.queue_event(WindowEvent::MonitorChanged {
+            old: old.map(|inner| RootMonitorHandle { inner }),
+            new: RootMonitorHandle { inner: monitor },
+        });
+    }
+
     fn set_style_mask(&self, mask: NSWindowStyleMask) {
         self.window().setStyleMask(mask);
         // If we don't do this, key handling will break
@@ -894,11 +1198,62 @@ impl WindowDelegate {
         }
     }
 
+    pub fn set_opacity(&self, opacity: f32) {
+        unsafe { self.window().setAlphaValue(opacity as CGFloat) };
+    }
+
+    pub fn opacity(&self) -> f32 {
+        unsafe { self.window().alphaValue() as f32 }
+    }
+
     pub fn set_visible(&self, visible: bool) {
         match visible {
             true => self.window().makeKeyAndOrderFront(None),
             false => self.window().orderOut(None),
         }
+        // Hidden child windows would be shown again along with their parent.
+        self.update_transient_parent();
+    }
+
+    pub fn set_transient_parent(&self, parent: Option<&Window>) -> Result<(), ExternalError> {
+        let mtm = MainThreadMarker::from(self);
+        let parent: Option<Retained<NSWindow>> =
+            parent.map(|parent| Retained::into_super(parent.ns_window(mtm).retain()));
+
+        // Walk the parents of the new parent to reject cycles.
+        let window: &NSWindow = self.window();
+        let mut ancestor = parent.clone();
+        while let Some(current) = ancestor {
+            if ptr::eq(&*current, window) {
+                return Err(ExternalError::Ignored);
+            }
+            ancestor = unsafe { current.parentWindow() };
+        }
+
+        *self.ivars().transient_parent.borrow_mut() = parent.as_deref().map(Weak::from);
+        self.update_transient_parent();
+        Ok(())
+    }
+
+    fn update_transient_parent(&self) {
+        let window: &NSWindow = self.window();
+        if let Some(current) = unsafe { window.parentWindow() } {
+            unsafe { current.removeChildWindow(window) };
+        }
+
+        let parent = self.ivars().transient_parent.borrow().as_ref().and_then(Weak::load);
+        let Some(parent) = parent.filter(|_| window.isVisible()) else {
+            return;
+        };
+        // Don't create cycles with windows whose parent was set while they were hidden.
+        let mut ancestor = Some(parent.clone());
+        while let Some(current) = ancestor {
+            if ptr::eq(&*current, window) {
+                return;
+            }
+            ancestor = unsafe { current.parentWindow() };
+        }
+        unsafe { parent.addChildWindow_ordered(window, NSWindowOrderingMode::NSWindowAbove) };
     }
 
     #[inline]
@@ -907,12 +1262,38 @@ impl WindowDelegate {
     }
 
     pub fn request_redraw(&self) {
-        self.ivars().app_delegate.queue_redraw(self.window().id());
+        let view = self.view();
+        view.redraw_damage().borrow_mut().add_full();
+        self.ivars().app_delegate.queue_redraw(&view);
+    }
+
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let view = self.view();
+        view.redraw_damage().borrow_mut().add(rects, self.inner_size());
+        let scale_factor = self.scale_factor();
+        for rect in rects {
+            // The view is flipped, so its origin is at the top-left like ours.
+            let position = rect.position.to_logical::<CGFloat>(scale_factor);
+            let size = rect.size.to_logical::<CGFloat>(scale_factor);
+            let rect = NSRect::new(
+                NSPoint::new(position.x, position.y),
+                NSSize::new(size.width, size.height),
+            );
+            unsafe { view.setNeedsDisplayInRect(rect) };
+        }
+        self.ivars().app_delegate.queue_redraw(&view);
+    }
+
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let size = self.inner_size();
+        self.view().redraw_damage().borrow().current(size).map(<[_]>::to_vec)
     }
 
     #[inline]
     pub fn pre_present_notify(&self) {}
 
+    pub fn set_presentation_feedback(&self, _enabled: bool) {}
+
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let position = flip_window_screen_coordinates(self.window().frame());
         Ok(LogicalPosition::new(position.x, position.y).to_physical(self.scale_factor()))
@@ -947,6 +1328,11 @@ impl WindowDelegate {
         logical.to_physical(self.scale_factor())
     }
 
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        self.view().safe_area()
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
@@ -1018,6 +1404,11 @@ impl WindowDelegate {
         );
     }
 
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        // XXX like the resize increments, the aspect ratio is only used during live resizes.
+        self.ivars().aspect_ratio.set(aspect_ratio);
+    }
+
     pub(crate) fn set_resize_increments_inner(&self, size: NSSize) {
         // It was concluded (#2411) that there is never a use-case for
         // "outer" resize increments, hence we set "inner" ones here.
@@ -1096,19 +1487,36 @@ impl WindowDelegate {
         buttons
     }
 
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::all()
+    }
+
     pub fn set_cursor(&self, cursor: Cursor) {
         let view = self.view();
 
-        let cursor = match cursor {
-            Cursor::Icon(icon) => cursor_from_icon(icon),
-            Cursor::Custom(cursor) => cursor.inner.0,
+        let (cursor, animation) = match cursor {
+            Cursor::Icon(icon) => (cursor_from_icon(icon), None),
+            Cursor::Custom(cursor) => match cursor.inner {
+                CustomCursor::Image(cursor) => (cursor, None),
+                CustomCursor::Animation(animation) => {
+                    if view
+                        .cursor_animation()
+                        .is_some_and(|current| Arc::ptr_eq(&current, &animation))
+                    {
+                        return;
+                    }
+                    (animation.frames[0].0.clone(), Some(animation))
+                },
+            },
         };
 
-        if view.cursor_icon() == cursor {
+        if animation.is_none() && view.cursor_animation().is_none() && view.cursor_icon() == cursor
+        {
             return;
         }
 
-        view.set_cursor_icon(cursor);
+        view.set_cursor_icon(cursor, animation);
         self.window().invalidateCursorRectsForView(&view);
     }
 
@@ -1172,6 +1580,64 @@ impl WindowDelegate {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        let mtm = MainThreadMarker::from(self);
+        if unsafe { NSEvent::pressedMouseButtons() } == 0 {
+            return Err(ExternalError::Ignored);
+        }
+        let event =
+            NSApplication::sharedApplication(mtm).currentEvent().ok_or(ExternalError::Ignored)?;
+
+        // The items are shown at the cursor, with the icon of the file for paths.
+        let view = self.view();
+        let location = view.convertPoint_fromView(unsafe { event.locationInWindow() }, None);
+        let size = NSSize::new(32.0, 32.0);
+        let frame = NSRect::new(
+            NSPoint::new(location.x - size.width / 2.0, location.y - size.height / 2.0),
+            size,
+        );
+
+        let items: Vec<_> = match payload {
+            DragPayload::Paths(paths) => paths
+                .iter()
+                .map(|path| {
+                    let path = NSString::from_str(&path.to_string_lossy());
+                    let url = unsafe { NSURL::fileURLWithPath(&path) };
+                    let item = unsafe {
+                        NSDraggingItem::initWithPasteboardWriter(
+                            NSDraggingItem::alloc(),
+                            ProtocolObject::from_ref(&*url),
+                        )
+                    };
+                    let icon = unsafe { NSWorkspace::sharedWorkspace().iconForFile(&path) };
+                    let icon: &AnyObject = &icon;
+                    unsafe { item.setDraggingFrame_contents(frame, Some(icon)) };
+                    item
+                })
+                .collect(),
+            DragPayload::Text(text) => {
+                let text = NSString::from_str(&text);
+                let item = unsafe {
+                    NSDraggingItem::initWithPasteboardWriter(
+                        NSDraggingItem::alloc(),
+                        ProtocolObject::from_ref(&*text),
+                    )
+                };
+                unsafe { item.setDraggingFrame_contents(frame, None) };
+                vec![item]
+            },
+        };
+
+        unsafe {
+            view.beginDraggingSessionWithItems_event_source(
+                &NSArray::from_vec(items),
+                &event,
+                ProtocolObject::from_ref(&*view),
+            )
+        };
+        Ok(())
+    }
+
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
@@ -1212,6 +1678,12 @@ impl WindowDelegate {
         }
     }
 
+    fn restore_collection_behavior(&self) {
+        if let Some(behavior) = self.ivars().collection_behavior.get() {
+            unsafe { self.window().setCollectionBehavior(behavior) };
+        }
+    }
+
     /// This is called when the window is exiting fullscreen, whether by the
     /// user clicking on the green fullscreen button or programmatically by
     /// `toggleFullScreen:`
@@ -1308,6 +1780,23 @@ impl WindowDelegate {
             return;
         }
 
+        let fast_borderless = self.ivars().fullscreen_style.get() == FullscreenStyle::Fast
+            && matches!(fullscreen, Some(Fullscreen::Borderless(_)));
+
+        // Leaving fast fullscreen is immediate, so the remaining transition is the same as when
+        // starting out windowed.
+        let old_fullscreen = if self.ivars().in_fast_fullscreen.get() {
+            self.exit_fast_fullscreen();
+            None
+        } else if fast_borderless && old_fullscreen.is_some() {
+            // Leave native fullscreen first, and enter fast fullscreen once that's done.
+            self.ivars().target_fullscreen.replace(Some(fullscreen));
+            self.set_fullscreen(None);
+            return;
+        } else {
+            old_fullscreen
+        };
+
         // If the fullscreen is on a different monitor, we must move the window
         // to that monitor before we toggle fullscreen (as `toggleFullScreen`
         // does not take a screen parameter, but uses the current screen)
@@ -1332,6 +1821,13 @@ impl WindowDelegate {
             }
         }
 
+        if fast_borderless {
+            self.enter_fast_fullscreen();
+            self.ivars().fullscreen.replace(fullscreen);
+            self.apply_fullscreen_presentation_options();
+            return;
+        }
+
         if let Some(Fullscreen::Exclusive(ref video_mode)) = fullscreen {
             // Note: `enterFullScreenMode:withOptions:` seems to do the exact
             // same thing as we're doing here (captures the display, sets the
@@ -1481,6 +1977,120 @@ impl WindowDelegate {
         };
     }
 
+    fn enter_fast_fullscreen(&self) {
+        let mtm = MainThreadMarker::from(self);
+        let app = NSApplication::sharedApplication(mtm);
+
+        // Remember the original window's settings
+        self.ivars().standard_frame.set(Some(self.window().frame()));
+        self.ivars().saved_style.set(Some(self.window().styleMask()));
+        self.ivars().save_presentation_opts.set(Some(app.presentationOptions()));
+        self.ivars().maximized.set(self.is_zoomed());
+        self.ivars().in_fast_fullscreen.set(true);
+
+        app.setPresentationOptions(
+            NSApplicationPresentationOptions::NSApplicationPresentationHideDock
+                | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar,
+        );
+
+        // Hide the titlebar, and cover the whole screen
+        self.toggle_style_mask(NSWindowStyleMask::Titled, false);
+        if let Some(screen) = self.window().screen() {
+            self.window().setFrame_display(screen.frame(), true);
+        }
+
+        // Fullscreen windows can't be resized, minimized, or moved
+        self.toggle_style_mask(NSWindowStyleMask::Miniaturizable, false);
+        self.toggle_style_mask(NSWindowStyleMask::Resizable, false);
+        self.window().setMovable(false);
+    }
+
+    /// Apply the user's fullscreen presentation options, if the window is key and in borderless
+    /// fullscreen.
+    fn apply_fullscreen_presentation_options(&self) {
+        let Some(options) = self.ivars().fullscreen_presentation_options.get() else {
+            return;
+        };
+        if !matches!(*self.ivars().fullscreen.borrow(), Some(Fullscreen::Borderless(_)))
+            || self.ivars().in_fullscreen_transition.get()
+            || !self.window().isKeyWindow()
+        {
+            return;
+        }
+
+        let mtm = MainThreadMarker::from(self);
+        let app = NSApplication::sharedApplication(mtm);
+        let current = app.presentationOptions();
+        let saved = self.ivars().presentation_opts_before_fullscreen_options.get();
+        let base = saved.unwrap_or(current);
+        if saved.is_none() {
+            self.ivars().presentation_opts_before_fullscreen_options.set(Some(current));
+        }
+
+        // Keep unrelated options, such as `NSApplicationPresentationFullScreen`.
+        let mut new_options = base
+            & !(NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock
+                | NSApplicationPresentationOptions::NSApplicationPresentationHideDock
+                | NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar
+                | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar
+                | NSApplicationPresentationOptions::NSApplicationPresentationDisableProcessSwitching
+                | NSApplicationPresentationOptions::NSApplicationPresentationDisableHideApplication);
+        if options.contains(PresentationOptions::AUTO_HIDE_DOCK) {
+            new_options |= NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock;
+        }
+        if options.contains(PresentationOptions::AUTO_HIDE_MENU_BAR) {
+            new_options |=
+                NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
+        }
+        if options.contains(PresentationOptions::DISABLE_PROCESS_SWITCHING) {
+            new_options |=
+                NSApplicationPresentationOptions::NSApplicationPresentationDisableProcessSwitching;
+        }
+        if options.contains(PresentationOptions::DISABLE_HIDE_APPLICATION) {
+            new_options |=
+                NSApplicationPresentationOptions::NSApplicationPresentationDisableHideApplication;
+        }
+        // AppKit raises an exception for these without the dock being hidden too.
+        if options.intersects(
+            PresentationOptions::AUTO_HIDE_MENU_BAR
+                | PresentationOptions::DISABLE_PROCESS_SWITCHING,
+        ) {
+            new_options |= NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock;
+        }
+
+        app.setPresentationOptions(new_options);
+    }
+
+    /// Restore the presentation options from before `apply_fullscreen_presentation_options`.
+    fn revert_fullscreen_presentation_options(&self) {
+        if let Some(options) = self.ivars().presentation_opts_before_fullscreen_options.take() {
+            let mtm = MainThreadMarker::from(self);
+            NSApplication::sharedApplication(mtm).setPresentationOptions(options);
+        }
+    }
+
+    fn exit_fast_fullscreen(&self) {
+        let mtm = MainThreadMarker::from(self);
+        let app = NSApplication::sharedApplication(mtm);
+
+        self.ivars().in_fast_fullscreen.set(false);
+        self.ivars().fullscreen.replace(None);
+        // The options from before entering fullscreen are restored below.
+        self.ivars().presentation_opts_before_fullscreen_options.set(None);
+
+        let mask = self.saved_style();
+        self.set_style_mask(mask);
+
+        if let Some(presentation_opts) = self.ivars().save_presentation_opts.take() {
+            app.setPresentationOptions(presentation_opts);
+        }
+
+        let frame = self.ivars().standard_frame.get().unwrap_or(DEFAULT_STANDARD_FRAME);
+        self.window().setFrame_display(frame, true);
+        self.window().setMovable(true);
+        self.set_maximized(self.ivars().maximized.get());
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         if decorations == self.ivars().decorations.get() {
@@ -1560,7 +2170,35 @@ impl WindowDelegate {
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.ivars().ime_purpose.set(purpose);
+        self.update_secure_input();
+    }
+
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        Some(ImeCapabilities::all())
+    }
+
+    /// Enable secure event input while a focused window has the password IME purpose.
+    fn update_secure_input(&self) {
+        let secure_input =
+            self.ivars().ime_purpose.get() == ImePurpose::Password && self.window().isKeyWindow();
+        self.set_secure_input(secure_input);
+    }
+
+    fn set_secure_input(&self, secure_input: bool) {
+        if self.ivars().secure_input_enabled.replace(secure_input) == secure_input {
+            return;
+        }
+
+        if secure_input {
+            unsafe { ffi::EnableSecureEventInput() };
+        } else {
+            unsafe { ffi::DisableSecureEventInput() };
+        }
+        self.view().set_secure_input(secure_input);
+    }
 
     #[inline]
     pub fn focus_window(&self) {
@@ -1587,6 +2225,17 @@ impl WindowDelegate {
         }
     }
 
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        self.ivars().progress.set(state);
+        dock::set_progress(MainThreadMarker::from(self), state);
+        Ok(())
+    }
+
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        self.ivars().badge_count.set(count);
+        dock::set_badge_count(MainThreadMarker::from(self), count);
+    }
+
     #[inline]
     // Allow directly accessing the current monitor internally without unwrapping.
     pub(crate) fn current_monitor_inner(&self) -> Option<MonitorHandle> {
@@ -1824,6 +2473,102 @@ impl WindowExtMacOS for WindowDelegate {
     fn option_as_alt(&self) -> OptionAsAlt {
         self.view().option_as_alt()
     }
+
+    fn set_key_equivalents_handled_by_menu(&self, handled_by_menu: bool) {
+        self.view().set_key_equivalents_handled_by_menu(handled_by_menu);
+    }
+
+    fn key_equivalents_handled_by_menu(&self) -> bool {
+        self.view().key_equivalents_handled_by_menu()
+    }
+
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        let behavior = NSWindowCollectionBehavior(behavior.bits() as _);
+        self.ivars().collection_behavior.set(Some(behavior));
+        unsafe { self.window().setCollectionBehavior(behavior) };
+    }
+
+    fn collection_behavior(&self) -> CollectionBehavior {
+        let behavior = unsafe { self.window().collectionBehavior() };
+        CollectionBehavior::from_bits_truncate(behavior.0 as u32)
+    }
+
+    fn set_moves_to_active_space(&self, moves_to_active_space: bool) {
+        // Work on the native value, to preserve flags that `CollectionBehavior` doesn't expose.
+        let mut behavior = unsafe { self.window().collectionBehavior() };
+        if moves_to_active_space {
+            behavior.remove(NSWindowCollectionBehavior::CanJoinAllSpaces);
+            behavior.insert(NSWindowCollectionBehavior::MoveToActiveSpace);
+        } else {
+            behavior.remove(NSWindowCollectionBehavior::MoveToActiveSpace);
+        }
+        self.ivars().collection_behavior.set(Some(behavior));
+        unsafe { self.window().setCollectionBehavior(behavior) };
+    }
+
+    #[inline]
+    fn set_frame_autosave_name(&self, name: &str) {
+        unsafe { self.window().setFrameAutosaveName(&NSString::from_str(name)) };
+    }
+
+    #[inline]
+    fn set_titlebar_pointer_events(&self, titlebar_pointer_events: bool) {
+        self.view().set_titlebar_pointer_events(titlebar_pointer_events);
+    }
+
+    #[inline]
+    fn titlebar_pointer_events(&self) -> bool {
+        self.view().titlebar_pointer_events()
+    }
+
+    fn perform_titlebar_double_click_action(&self) {
+        // Follow the "Double-click a window's title bar to" setting in System Settings.
+        let action = unsafe {
+            NSUserDefaults::standardUserDefaults()
+                .stringForKey(ns_string!("AppleActionOnDoubleClick"))
+        };
+        match action.as_ref().map(|action| action.to_string()).as_deref() {
+            Some("None") => {},
+            Some("Minimize") => unsafe { self.window().performMiniaturize(None) },
+            _ => unsafe { self.window().performZoom(None) },
+        }
+    }
+
+    fn set_fullscreen_style(&self, style: FullscreenStyle) {
+        if self.ivars().fullscreen_style.replace(style) == style
+            || self.ivars().in_fullscreen_transition.get()
+        {
+            return;
+        }
+
+        // Re-enter borderless fullscreen using the new style.
+        let fullscreen = self.ivars().fullscreen.borrow().clone();
+        if let Some(Fullscreen::Borderless(_)) = fullscreen {
+            if self.ivars().in_fast_fullscreen.get() {
+                self.set_fullscreen(None);
+                self.set_fullscreen(fullscreen);
+            } else {
+                // Leaving native fullscreen is animated, so re-enter once it's done.
+                self.ivars().target_fullscreen.replace(Some(fullscreen));
+                self.set_fullscreen(None);
+            }
+        }
+    }
+
+    #[inline]
+    fn fullscreen_style(&self) -> FullscreenStyle {
+        self.ivars().fullscreen_style.get()
+    }
+
+    fn set_fullscreen_presentation_options(&self, options: PresentationOptions) {
+        self.ivars().fullscreen_presentation_options.set(Some(options));
+        self.apply_fullscreen_presentation_options();
+    }
+
+    #[inline]
+    fn fullscreen_presentation_options(&self) -> Option<PresentationOptions> {
+        self.ivars().fullscreen_presentation_options.get()
+    }
 }
 
 const DEFAULT_STANDARD_FRAME: NSRect =
@@ -1865,3 +2610,12 @@ fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
         None
     }
 }
+
+/// The paths of the files offered by a drag, if any.
+fn dragged_paths(pb: &NSPasteboard) -> Vec<PathBuf> {
+    let Some(filenames) = pb.propertyListForType(unsafe { NSFilenamesPboardType }) else {
+        return Vec::new();
+    };
+    let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+    filenames.into_iter().map(|file| PathBuf::from(file.to_string())).collect()
+}
@@ -2,6 +2,8 @@
 
 #[cfg(target_os = "macos")]
 mod appkit;
+mod process_info;
+mod run_loop_timer;
 #[cfg(not(target_os = "macos"))]
 mod uikit;
 
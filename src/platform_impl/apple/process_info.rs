@@ -0,0 +1,67 @@
+//! Power and thermal state of the system, which AppKit and UIKit both get from `NSProcessInfo`.
+
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+use block2::RcBlock;
+use objc2::runtime::NSObjectProtocol;
+use objc2::sel;
+use objc2_foundation::{
+    NSNotification, NSNotificationCenter, NSOperationQueue, NSProcessInfo,
+    NSProcessInfoPowerStateDidChangeNotification, NSProcessInfoThermalState,
+    NSProcessInfoThermalStateDidChangeNotification,
+};
+
+use crate::event_loop::ThermalState;
+
+/// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1617047-lowpowermodeenabled?language=objc>
+pub(crate) fn is_low_power_mode_enabled() -> bool {
+    let process_info = NSProcessInfo::processInfo();
+    // Only available since macOS 12.
+    process_info.respondsToSelector(sel!(isLowPowerModeEnabled))
+        && unsafe { process_info.isLowPowerModeEnabled() }
+}
+
+/// <https://developer.apple.com/documentation/foundation/nsprocessinfo/1417480-thermalstate?language=objc>
+pub(crate) fn thermal_state() -> ThermalState {
+    let process_info = NSProcessInfo::processInfo();
+    // Only available since iOS 11.
+    if !process_info.respondsToSelector(sel!(thermalState)) {
+        return ThermalState::Nominal;
+    }
+
+    match unsafe { process_info.thermalState() } {
+        NSProcessInfoThermalState::Fair => ThermalState::Fair,
+        NSProcessInfoThermalState::Serious => ThermalState::Serious,
+        NSProcessInfoThermalState::Critical => ThermalState::Critical,
+        _ => ThermalState::Nominal,
+    }
+}
+
+/// Call `handler` whenever low power mode is toggled or the thermal state changes.
+///
+/// The notifications are posted on arbitrary threads, so they are observed on the main queue,
+/// which makes `handler` run on the main thread. The observers are never removed.
+pub(crate) fn observe_power_state(handler: impl Fn() + 'static) {
+    let handler = Rc::new(handler);
+    let center = unsafe { NSNotificationCenter::defaultCenter() };
+    let queue = unsafe { NSOperationQueue::mainQueue() };
+    let names = unsafe {
+        [
+            NSProcessInfoPowerStateDidChangeNotification,
+            NSProcessInfoThermalStateDidChangeNotification,
+        ]
+    };
+    for name in names {
+        let handler = Rc::clone(&handler);
+        let block = RcBlock::new(move |_: NonNull<NSNotification>| handler());
+        unsafe {
+            center.addObserverForName_object_queue_usingBlock(
+                Some(name),
+                None,
+                Some(&queue),
+                &block,
+            )
+        };
+    }
+}
@@ -0,0 +1,20 @@
+//! Scheduling of the `CFRunLoopTimer` that AppKit and UIKit use to wake up the event loop.
+
+use std::time::Duration;
+
+use core_foundation::date::CFTimeInterval;
+
+/// Deadlines closer than this wake up the run loop as precisely as possible.
+const PRECISE_WAIT_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// The tolerance of a timer that fires in `interval` seconds.
+///
+/// Far deadlines allow the system to coalesce the timer with others to save power, like it does
+/// for the timers of `NSTimer`.
+pub(crate) fn timer_tolerance(interval: CFTimeInterval) -> CFTimeInterval {
+    if interval < PRECISE_WAIT_THRESHOLD.as_secs_f64() {
+        0.0
+    } else {
+        interval / 10.0
+    }
+}
@@ -1,9 +1,30 @@
-use objc2::{declare_class, mutability, ClassType, DeclaredClass};
-use objc2_foundation::{MainThreadMarker, NSObject};
-use objc2_ui_kit::UIApplication;
+use std::cell::Cell;
 
+use block2::Block;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyObject, Bool, ProtocolObject};
+use objc2::{declare_class, mutability, sel, ClassType, DeclaredClass};
+use objc2_foundation::{
+    MainThreadMarker, NSArray, NSDictionary, NSNotification, NSNotificationCenter, NSObject,
+    NSUserActivity, NSURL,
+};
+use objc2_ui_kit::{
+    UIApplication, UIApplicationLaunchOptionsBluetoothCentralsKey,
+    UIApplicationLaunchOptionsBluetoothPeripheralsKey, UIApplicationLaunchOptionsKey,
+    UIApplicationLaunchOptionsLocationKey, UIApplicationLaunchOptionsRemoteNotificationKey,
+    UIApplicationLaunchOptionsShortcutItemKey, UIApplicationLaunchOptionsURLKey,
+    UIApplicationLaunchOptionsUserActivityTypeKey, UIApplicationOpenURLOptionsKey,
+    UIApplicationShortcutItem, UIKeyboardWillChangeFrameNotification,
+    UIKeyboardWillHideNotification, UISceneConfiguration, UISceneConnectionOptions, UISceneSession,
+    UIScreen, UIScreenBrightnessDidChangeNotification, UIScreenDidConnectNotification,
+    UIScreenDidDisconnectNotification, UIScreenModeDidChangeNotification, UIUserActivityRestoring,
+};
+
+use super::super::process_info;
 use super::app_state::{self, send_occluded_event_for_all_windows, EventWrapper};
-use crate::event::Event;
+use super::scene_delegate::SceneDelegate;
+use crate::event::{Event, SessionEvent};
+use crate::platform::ios::LaunchOptions;
 
 declare_class!(
     pub struct AppDelegate;
@@ -19,8 +40,69 @@ declare_class!(
     // UIApplicationDelegate protocol
     unsafe impl AppDelegate {
         #[method(application:didFinishLaunchingWithOptions:)]
-        fn did_finish_launching(&self, _application: &UIApplication, _: *mut NSObject) -> bool {
-            app_state::did_finish_launching(MainThreadMarker::new().unwrap());
+        fn did_finish_launching(
+            &self,
+            _application: &UIApplication,
+            options: Option<&NSDictionary<UIApplicationLaunchOptionsKey, AnyObject>>,
+        ) -> bool {
+            let center = unsafe { NSNotificationCenter::defaultCenter() };
+            unsafe {
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(screenDidConnect:),
+                    Some(UIScreenDidConnectNotification),
+                    None,
+                );
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(screenDidDisconnect:),
+                    Some(UIScreenDidDisconnectNotification),
+                    None,
+                );
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(screenModeDidChange:),
+                    Some(UIScreenModeDidChangeNotification),
+                    None,
+                );
+                // The EDR headroom follows the brightness of the screen.
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(screenBrightnessDidChange:),
+                    Some(UIScreenBrightnessDidChangeNotification),
+                    None,
+                );
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(keyboardWillChangeFrame:),
+                    Some(UIKeyboardWillChangeFrameNotification),
+                    None,
+                );
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(keyboardWillHide:),
+                    Some(UIKeyboardWillHideNotification),
+                    None,
+                );
+            }
+
+            // The same notification reports low power mode, only forward its changes.
+            let low_power_mode = Cell::new(process_info::is_low_power_mode_enabled());
+            process_info::observe_power_state(move || {
+                let mtm = MainThreadMarker::new().unwrap();
+                app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::PowerStateChanged));
+
+                let enabled = process_info::is_low_power_mode_enabled();
+                if low_power_mode.replace(enabled) != enabled {
+                    let event = Event::SessionEvent(SessionEvent::PowerSaverChanged(enabled));
+                    app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(event));
+                }
+            });
+
+            let launch_options = options.map(launch_options).unwrap_or_default();
+            app_state::did_finish_launching(MainThreadMarker::new().unwrap(), launch_options);
+            // URLs, user activities and quick actions in the launch options are delivered
+            // afterwards through the methods below.
             true
         }
 
@@ -38,12 +120,14 @@ declare_class!(
 
         #[method(applicationWillEnterForeground:)]
         fn will_enter_foreground(&self, application: &UIApplication) {
+            app_state::will_enter_foreground(MainThreadMarker::from(application));
             send_occluded_event_for_all_windows(application, false);
         }
 
         #[method(applicationDidEnterBackground:)]
         fn did_enter_background(&self, application: &UIApplication) {
             send_occluded_event_for_all_windows(application, true);
+            app_state::did_enter_background(MainThreadMarker::from(application));
         }
 
         #[method(applicationWillTerminate:)]
@@ -51,10 +135,152 @@ declare_class!(
             app_state::terminated(application);
         }
 
+        // The protected data is only unavailable while the device is locked.
+        #[method(applicationProtectedDataWillBecomeUnavailable:)]
+        fn protected_data_will_become_unavailable(&self, _application: &UIApplication) {
+            let mtm = MainThreadMarker::new().unwrap();
+            let event = Event::SessionEvent(SessionEvent::SessionLocked);
+            app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(event))
+        }
+
+        #[method(applicationProtectedDataDidBecomeAvailable:)]
+        fn protected_data_did_become_available(&self, _application: &UIApplication) {
+            let mtm = MainThreadMarker::new().unwrap();
+            let event = Event::SessionEvent(SessionEvent::SessionUnlocked);
+            app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(event))
+        }
+
         #[method(applicationDidReceiveMemoryWarning:)]
         fn did_receive_memory_warning(&self, _application: &UIApplication) {
             let mtm = MainThreadMarker::new().unwrap();
             app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::MemoryWarning))
         }
+
+        #[method(application:openURL:options:)]
+        fn open_url(
+            &self,
+            _application: &UIApplication,
+            url: &NSURL,
+            _options: &NSDictionary<UIApplicationOpenURLOptionsKey, AnyObject>,
+        ) -> bool {
+            app_state::urls_opened(MainThreadMarker::new().unwrap(), vec![url.retain()]);
+            true
+        }
+
+        #[method(application:continueUserActivity:restorationHandler:)]
+        fn continue_user_activity(
+            &self,
+            _application: &UIApplication,
+            user_activity: &NSUserActivity,
+            _restoration_handler: &Block<dyn Fn(*mut NSArray<ProtocolObject<dyn UIUserActivityRestoring>>)>,
+        ) -> bool {
+            app_state::continue_user_activity(MainThreadMarker::new().unwrap(), user_activity)
+        }
+
+        #[method(application:performActionForShortcutItem:completionHandler:)]
+        fn perform_action_for_shortcut_item(
+            &self,
+            _application: &UIApplication,
+            shortcut_item: &UIApplicationShortcutItem,
+            completion_handler: &Block<dyn Fn(Bool)>,
+        ) {
+            app_state::perform_shortcut_item(MainThreadMarker::new().unwrap(), shortcut_item);
+            completion_handler.call((Bool::YES,));
+        }
+    }
+
+    // UIApplicationDelegate scene configuration, only called for scene-based applications
+    unsafe impl AppDelegate {
+        #[method_id(application:configurationForConnectingSceneSession:options:)]
+        fn configuration_for_connecting_scene_session(
+            &self,
+            _application: &UIApplication,
+            session: &UISceneSession,
+            _options: &UISceneConnectionOptions,
+        ) -> Retained<UISceneConfiguration> {
+            let mtm = MainThreadMarker::new().unwrap();
+            unsafe {
+                let configuration =
+                    UISceneConfiguration::configurationWithName_sessionRole(None, &session.role(), mtm);
+                configuration.setDelegateClass(Some(SceneDelegate::class()));
+                configuration
+            }
+        }
+    }
+
+    // UIScreen notifications
+    unsafe impl AppDelegate {
+        #[method(screenDidConnect:)]
+        fn screen_did_connect(&self, notification: &NSNotification) {
+            if let Some(screen) = screen_from_notification(notification) {
+                app_state::screen_connected(MainThreadMarker::new().unwrap(), screen);
+            }
+        }
+
+        #[method(screenDidDisconnect:)]
+        fn screen_did_disconnect(&self, notification: &NSNotification) {
+            if let Some(screen) = screen_from_notification(notification) {
+                app_state::screen_disconnected(MainThreadMarker::new().unwrap(), screen);
+            }
+        }
+
+        #[method(screenModeDidChange:)]
+        fn screen_mode_did_change(&self, notification: &NSNotification) {
+            if let Some(screen) = screen_from_notification(notification) {
+                app_state::screen_mode_changed(MainThreadMarker::new().unwrap(), screen);
+            }
+        }
+
+        #[method(screenBrightnessDidChange:)]
+        fn screen_brightness_did_change(&self, notification: &NSNotification) {
+            if let Some(screen) = screen_from_notification(notification) {
+                app_state::screen_brightness_changed(MainThreadMarker::new().unwrap(), &screen);
+            }
+        }
+    }
+
+    // UIKeyboard notifications
+    unsafe impl AppDelegate {
+        #[method(keyboardWillChangeFrame:)]
+        fn keyboard_will_change_frame(&self, notification: &NSNotification) {
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::keyboard_frame_will_change(mtm, notification, false);
+        }
+
+        #[method(keyboardWillHide:)]
+        fn keyboard_will_hide(&self, notification: &NSNotification) {
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::keyboard_frame_will_change(mtm, notification, true);
+        }
     }
 );
+
+fn screen_from_notification(notification: &NSNotification) -> Option<Retained<UIScreen>> {
+    let object = unsafe { notification.object() }?;
+    // SAFETY: The object of `UIScreenDidConnectNotification`,
+    // `UIScreenDidDisconnectNotification`, `UIScreenModeDidChangeNotification` and
+    // `UIScreenBrightnessDidChangeNotification` is the affected `UIScreen`.
+    Some(unsafe { Retained::cast(object) })
+}
+
+fn launch_options(
+    options: &NSDictionary<UIApplicationLaunchOptionsKey, AnyObject>,
+) -> LaunchOptions {
+    let keys = unsafe {
+        [
+            (UIApplicationLaunchOptionsURLKey, LaunchOptions::URL),
+            (UIApplicationLaunchOptionsShortcutItemKey, LaunchOptions::SHORTCUT_ITEM),
+            (UIApplicationLaunchOptionsUserActivityTypeKey, LaunchOptions::USER_ACTIVITY),
+            (UIApplicationLaunchOptionsRemoteNotificationKey, LaunchOptions::REMOTE_NOTIFICATION),
+            (UIApplicationLaunchOptionsLocationKey, LaunchOptions::LOCATION),
+            (UIApplicationLaunchOptionsBluetoothCentralsKey, LaunchOptions::BLUETOOTH_CENTRALS),
+            (
+                UIApplicationLaunchOptionsBluetoothPeripheralsKey,
+                LaunchOptions::BLUETOOTH_PERIPHERALS,
+            ),
+        ]
+    };
+    keys.into_iter()
+        .filter(|(key, _)| unsafe { options.objectForKey(key) }.is_some())
+        .fold(LaunchOptions::empty(), |launch_options, (_, flag)| launch_options | flag)
+}
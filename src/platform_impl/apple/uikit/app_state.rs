@@ -1,32 +1,47 @@
 #![deny(unused_results)]
+#![allow(clippy::unnecessary_cast)]
 
 use std::cell::{RefCell, RefMut};
-use std::collections::HashSet;
+use std::collections::{HashMap, VecDeque};
 use std::os::raw::c_void;
+use std::ptr::NonNull;
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex, OnceLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{fmt, mem, ptr};
 
+use block2::RcBlock;
 use core_foundation::base::CFRelease;
 use core_foundation::date::CFAbsoluteTimeGetCurrent;
 use core_foundation::runloop::{
     kCFRunLoopCommonModes, CFRunLoopAddTimer, CFRunLoopGetMain, CFRunLoopRef, CFRunLoopTimerCreate,
     CFRunLoopTimerInvalidate, CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate,
+    CFRunLoopTimerSetTolerance,
 };
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
-use objc2::{msg_send, sel};
+use objc2::runtime::{AnyObject, ProtocolObject};
+use objc2::{msg_send, sel, ClassType};
 use objc2_foundation::{
-    CGRect, CGSize, MainThreadMarker, NSInteger, NSObjectProtocol, NSOperatingSystemVersion,
-    NSProcessInfo,
+    ns_string, CGRect, CGSize, MainThreadMarker, NSBundle, NSError, NSInteger, NSNotification,
+    NSNumber, NSObject, NSObjectProtocol, NSOperatingSystemVersion, NSProcessInfo, NSSecureCoding,
+    NSString, NSUserActivity, NSUserActivityTypeBrowsingWeb, NSValue, NSURL,
+};
+use objc2_ui_kit::{
+    UIApplication, UIApplicationShortcutItem, UICoordinateSpace,
+    UIKeyboardAnimationCurveUserInfoKey, UIKeyboardAnimationDurationUserInfoKey,
+    UIKeyboardFrameEndUserInfoKey, UIScene, UIScreen, UIView, UIViewAnimationCurve, UIWindow,
+    UIWindowScene,
 };
-use objc2_ui_kit::{UIApplication, UICoordinateSpace, UIView, UIWindow};
 
-use super::window::WinitUIWindow;
-use crate::dpi::PhysicalSize;
-use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
+use super::super::run_loop_timer::timer_tolerance;
+use super::monitor::MonitorHandle;
+use super::view::WinitView;
+use super::window::{WindowId, WinitUIWindow};
+use crate::dpi::{LogicalInsets, PhysicalInsets, PhysicalSize};
+use crate::event::{AnimationCurve, Event, InnerSizeWriter, ShortcutItem, StartCause, WindowEvent};
 use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::platform::ios::LaunchOptions;
 use crate::window::WindowId as RootWindowId;
 
 macro_rules! bug {
@@ -70,7 +85,7 @@ pub(crate) enum EventWrapper {
 
 #[derive(Debug)]
 pub struct ScaleFactorChanged {
-    pub(super) window: Retained<WinitUIWindow>,
+    pub(super) view: Retained<WinitView>,
     pub(super) suggested_size: PhysicalSize<u32>,
     pub(super) scale_factor: f64,
 }
@@ -92,6 +107,9 @@ impl Event {
     }
 }
 
+/// The views of the GPU-backed windows that requested a redraw.
+type GpuRedraws = HashMap<WindowId, Retained<WinitView>>;
+
 // this is the state machine for the app lifecycle
 #[derive(Debug)]
 #[must_use = "dropping `AppStateImpl` without inspecting it is probably a bug"]
@@ -99,23 +117,23 @@ enum AppStateImpl {
     NotLaunched {
         queued_windows: Vec<Retained<WinitUIWindow>>,
         queued_events: Vec<EventWrapper>,
-        queued_gpu_redraws: HashSet<Retained<WinitUIWindow>>,
+        queued_gpu_redraws: GpuRedraws,
     },
     Launching {
         queued_windows: Vec<Retained<WinitUIWindow>>,
         queued_events: Vec<EventWrapper>,
         queued_handler: EventLoopHandler,
-        queued_gpu_redraws: HashSet<Retained<WinitUIWindow>>,
+        queued_gpu_redraws: GpuRedraws,
     },
     ProcessingEvents {
         handler: EventLoopHandler,
-        queued_gpu_redraws: HashSet<Retained<WinitUIWindow>>,
+        queued_gpu_redraws: GpuRedraws,
         active_control_flow: ControlFlow,
     },
     // special state to deal with reentrancy and prevent mutable aliasing.
     InUserCallback {
         queued_events: Vec<EventWrapper>,
-        queued_gpu_redraws: HashSet<Retained<WinitUIWindow>>,
+        queued_gpu_redraws: GpuRedraws,
     },
     ProcessingRedraws {
         handler: EventLoopHandler,
@@ -137,6 +155,63 @@ pub(crate) struct AppState {
     control_flow: ControlFlow,
     waker: EventLoopWaker,
     proxy_wake_up: Arc<AtomicBool>,
+    scenes: SceneState,
+    surfaces: SurfaceState,
+    launch_options: LaunchOptions,
+}
+
+/// Bookkeeping for scene-based applications, see [`uses_scenes`].
+#[derive(Default)]
+struct SceneState {
+    /// Windows that were created before a scene was available to show them in.
+    pending_windows: VecDeque<Retained<WinitUIWindow>>,
+    /// Scenes connected by the system (e.g. state restoration or "Open in New Window") that no
+    /// window has been created for yet.
+    unclaimed_scenes: VecDeque<Retained<UIWindowScene>>,
+    /// The number of currently connected scenes.
+    connected: usize,
+    /// The number of currently active scenes, used to derive `Resumed` and `Suspended`.
+    active: usize,
+}
+
+/// Bookkeeping for `DestroySurfaces` and `CreateSurfaces` around the application entering the
+/// background, where it must not use the GPU.
+#[derive(Default)]
+struct SurfaceState {
+    /// The number of scenes currently in the foreground.
+    foreground_scenes: usize,
+    /// Whether `DestroySurfaces` was sent without `CreateSurfaces` having been sent since.
+    destroyed: bool,
+    /// Redraws that were requested while the surfaces were destroyed.
+    deferred_gpu_redraws: GpuRedraws,
+}
+
+impl SurfaceState {
+    fn entered_background(&mut self) -> Option<Event> {
+        (!mem::replace(&mut self.destroyed, true)).then_some(Event::DestroySurfaces)
+    }
+
+    fn will_enter_foreground(&mut self) -> Option<Event> {
+        mem::replace(&mut self.destroyed, false).then_some(Event::CreateSurfaces)
+    }
+
+    fn scene_entered_background(&mut self) -> Option<Event> {
+        self.foreground_scenes = self.foreground_scenes.saturating_sub(1);
+        if self.foreground_scenes == 0 {
+            self.entered_background()
+        } else {
+            None
+        }
+    }
+
+    fn scene_will_enter_foreground(&mut self) -> Option<Event> {
+        self.foreground_scenes += 1;
+        if self.foreground_scenes == 1 {
+            self.will_enter_foreground()
+        } else {
+            None
+        }
+    }
 }
 
 impl AppState {
@@ -156,11 +231,14 @@ impl AppState {
                     app_state: Some(AppStateImpl::NotLaunched {
                         queued_windows: Vec::new(),
                         queued_events: Vec::new(),
-                        queued_gpu_redraws: HashSet::new(),
+                        queued_gpu_redraws: GpuRedraws::new(),
                     }),
                     control_flow: ControlFlow::default(),
                     waker,
                     proxy_wake_up: Arc::new(AtomicBool::new(false)),
+                    scenes: SceneState::default(),
+                    surfaces: SurfaceState::default(),
+                    launch_options: LaunchOptions::empty(),
                 });
             }
             init_guard(&mut guard);
@@ -346,7 +424,7 @@ impl AppState {
         UserCallbackTransitionResult::Success { handler, active_control_flow, processing_redraws }
     }
 
-    fn main_events_cleared_transition(&mut self) -> HashSet<Retained<WinitUIWindow>> {
+    fn main_events_cleared_transition(&mut self) -> GpuRedraws {
         let (handler, queued_gpu_redraws, active_control_flow) = match self.take_state() {
             AppStateImpl::ProcessingEvents { handler, queued_gpu_redraws, active_control_flow } => {
                 (handler, queued_gpu_redraws, active_control_flow)
@@ -409,6 +487,10 @@ impl AppState {
         self.proxy_wake_up.clone()
     }
 
+    pub(crate) fn launch_options(&self) -> LaunchOptions {
+        self.launch_options
+    }
+
     pub(crate) fn set_control_flow(&mut self, control_flow: ControlFlow) {
         self.control_flow = control_flow;
     }
@@ -435,17 +517,82 @@ pub(crate) fn set_key_window(mtm: MainThreadMarker, window: &Retained<WinitUIWin
         },
     }
     drop(this);
-    window.makeKeyAndVisible();
+    show_window(mtm, window);
 }
 
-pub(crate) fn queue_gl_or_metal_redraw(mtm: MainThreadMarker, window: Retained<WinitUIWindow>) {
+/// Whether the application is scene-based, i.e. runs on iOS 13 or newer and declares a
+/// `UIApplicationSceneManifest` in its `Info.plist`.
+///
+/// When this is not the case, UIKit uses the legacy application lifecycle and windows are shown
+/// directly on the main screen.
+pub(crate) fn uses_scenes() -> bool {
+    static USES_SCENES: OnceLock<bool> = OnceLock::new();
+    *USES_SCENES.get_or_init(|| {
+        os_capabilities().scenes
+            && unsafe {
+                NSBundle::mainBundle()
+                    .objectForInfoDictionaryKey(ns_string!("UIApplicationSceneManifest"))
+            }
+            .is_some()
+    })
+}
+
+/// Make the window visible, attaching it to a scene first if the application is scene-based.
+fn show_window(mtm: MainThreadMarker, window: &Retained<WinitUIWindow>) {
+    if !uses_scenes() {
+        window.makeKeyAndVisible();
+        return;
+    }
+
+    let mut this = AppState::get_mut(mtm);
+    if let Some(scene) = this.scenes.unclaimed_scenes.pop_front() {
+        drop(this);
+        unsafe { window.setWindowScene(Some(&scene)) };
+        window.makeKeyAndVisible();
+        return;
+    }
+
+    // The window is shown once its scene connects, see `scene_will_connect`. The first scene is
+    // connected by the system, every further window needs a new scene session.
+    let request_scene = !this.scenes.pending_windows.is_empty() || this.scenes.connected > 0;
+    this.scenes.pending_windows.push_back(window.clone());
+    drop(this);
+
+    if request_scene {
+        let application = UIApplication::sharedApplication(mtm);
+        if !unsafe { application.supportsMultipleScenes() } {
+            tracing::warn!(
+                "Creating more than one window requires `UIApplicationSupportsMultipleScenes` in \
+                 the application's `Info.plist`"
+            );
+        }
+        let error_handler = RcBlock::new(|error: NonNull<NSError>| {
+            let error = unsafe { error.as_ref() };
+            tracing::warn!(
+                "failed to request a new scene session: {}",
+                error.localizedDescription()
+            );
+        });
+        #[allow(deprecated)]
+        unsafe {
+            application.requestSceneSessionActivation_userActivity_options_errorHandler(
+                None,
+                None,
+                None,
+                Some(&error_handler),
+            )
+        };
+    }
+}
+
+pub(crate) fn queue_gl_or_metal_redraw(mtm: MainThreadMarker, view: &WinitView) {
     let mut this = AppState::get_mut(mtm);
     match this.state_mut() {
         &mut AppStateImpl::NotLaunched { ref mut queued_gpu_redraws, .. }
         | &mut AppStateImpl::Launching { ref mut queued_gpu_redraws, .. }
         | &mut AppStateImpl::ProcessingEvents { ref mut queued_gpu_redraws, .. }
         | &mut AppStateImpl::InUserCallback { ref mut queued_gpu_redraws, .. } => {
-            let _ = queued_gpu_redraws.insert(window);
+            let _ = queued_gpu_redraws.insert(view.id(), view.retain());
         },
         s @ &mut AppStateImpl::ProcessingRedraws { .. }
         | s @ &mut AppStateImpl::Waiting { .. }
@@ -460,13 +607,25 @@ pub(crate) fn will_launch(mtm: MainThreadMarker, queued_handler: EventLoopHandle
     AppState::get_mut(mtm).will_launch_transition(queued_handler)
 }
 
-pub fn did_finish_launching(mtm: MainThreadMarker) {
+pub(crate) fn has_launched(mtm: MainThreadMarker) -> bool {
+    AppState::get_mut(mtm).has_launched()
+}
+
+/// Launch the event loop inside an application that was launched by someone else, whose main
+/// loop then drives the event loop.
+pub(crate) fn launch_in_host(mtm: MainThreadMarker, handler: EventLoopHandler) {
+    will_launch(mtm, handler);
+    did_finish_launching(mtm, LaunchOptions::empty());
+}
+
+pub fn did_finish_launching(mtm: MainThreadMarker, launch_options: LaunchOptions) {
     let mut this = AppState::get_mut(mtm);
     let windows = match this.state_mut() {
         AppStateImpl::Launching { queued_windows, .. } => mem::take(queued_windows),
         s => bug!("unexpected state {:?}", s),
     };
 
+    this.launch_options = launch_options;
     this.waker.start();
 
     // have to drop RefMut because the window setup code below can trigger new events
@@ -491,7 +650,7 @@ pub fn did_finish_launching(mtm: MainThreadMarker) {
         window.setRootViewController(None);
         window.setRootViewController(controller.as_deref());
 
-        window.makeKeyAndVisible();
+        show_window(mtm, &window);
     }
 
     let (windows, events) = AppState::get_mut(mtm).did_finish_launching_transition();
@@ -507,7 +666,7 @@ pub fn did_finish_launching(mtm: MainThreadMarker) {
     // the above window dance hack, could possibly trigger new windows to be created.
     // we can just set those windows up normally, as they were created after didFinishLaunching
     for window in windows {
-        window.makeKeyAndVisible();
+        show_window(mtm, &window);
     }
 }
 
@@ -692,30 +851,371 @@ pub(crate) fn send_occluded_event_for_all_windows(application: &UIApplication, o
     handle_nonuser_events(mtm, events);
 }
 
-pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
+pub(crate) fn scene_will_connect(mtm: MainThreadMarker, scene: &UIScene) {
+    if !scene.is_kind_of::<UIWindowScene>() {
+        return;
+    }
+    // SAFETY: We just checked that the scene is a `UIWindowScene`
+    let scene = unsafe { Retained::cast::<UIWindowScene>(scene.retain()) };
+
     let mut this = AppState::get_mut(mtm);
-    if !this.has_launched() || this.has_terminated() {
+    this.scenes.connected += 1;
+    match this.scenes.pending_windows.pop_front() {
+        Some(window) => {
+            drop(this);
+            unsafe { window.setWindowScene(Some(&scene)) };
+            window.makeKeyAndVisible();
+        },
+        None => {
+            // The system connected a scene on its own, e.g. when the user opened a new window
+            // from the app switcher. Ask the application to create a window for it.
+            this.scenes.unclaimed_scenes.push_back(scene);
+            let launched = this.has_launched();
+            drop(this);
+            if launched {
+                handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::CreateSurfaces));
+            }
+        },
+    }
+}
+
+pub(crate) fn scene_did_disconnect(mtm: MainThreadMarker, scene: &UIScene) {
+    if !scene.is_kind_of::<UIWindowScene>() {
         return;
     }
-    match this.state_mut() {
-        AppStateImpl::ProcessingEvents { .. } => {},
-        _ => bug!("`ProcessingRedraws` happened unexpectedly"),
-    };
+    // SAFETY: We just checked that the scene is a `UIWindowScene`
+    let scene = unsafe { &*(scene as *const UIScene).cast::<UIWindowScene>() };
+
+    let mut this = AppState::get_mut(mtm);
+    this.scenes.connected = this.scenes.connected.saturating_sub(1);
+    this.scenes.unclaimed_scenes.retain(|unclaimed| !ptr::eq::<UIWindowScene>(&**unclaimed, scene));
     drop(this);
 
-    handle_user_events(mtm);
+    // The windows of a disconnected scene can no longer be displayed, so hide them and tell the
+    // application that they are gone.
+    let mut events = Vec::new();
+    for window in winit_windows_in_scene(scene) {
+        window.setHidden(true);
+        events.push(EventWrapper::StaticEvent(Event::WindowEvent {
+            window_id: RootWindowId(window.id()),
+            event: WindowEvent::Destroyed,
+        }));
+    }
+    handle_nonuser_events(mtm, events);
+}
 
+pub(crate) fn scene_activation_changed(mtm: MainThreadMarker, active: bool) {
     let mut this = AppState::get_mut(mtm);
-    let redraw_events: Vec<EventWrapper> = this
-        .main_events_cleared_transition()
+    let event = if active {
+        this.scenes.active += 1;
+        (this.scenes.active == 1).then_some(Event::Resumed)
+    } else {
+        this.scenes.active = this.scenes.active.saturating_sub(1);
+        (this.scenes.active == 0).then_some(Event::Suspended)
+    };
+    drop(this);
+
+    if let Some(event) = event {
+        handle_nonuser_event(mtm, EventWrapper::StaticEvent(event));
+    }
+}
+
+/// Send `DestroySurfaces` when the application entered the background.
+///
+/// This is sent synchronously, so that the application has stopped rendering by the time
+/// control is returned to UIKit.
+pub(crate) fn did_enter_background(mtm: MainThreadMarker) {
+    let event = AppState::get_mut(mtm).surfaces.entered_background();
+    send_surface_event(mtm, event);
+}
+
+/// Send `CreateSurfaces` when the application returns from the background, followed by the
+/// redraws that were requested in the meantime.
+pub(crate) fn will_enter_foreground(mtm: MainThreadMarker) {
+    let event = AppState::get_mut(mtm).surfaces.will_enter_foreground();
+    send_surface_event(mtm, event);
+}
+
+pub(crate) fn scene_did_enter_background(mtm: MainThreadMarker, scene: &UIScene) {
+    send_occluded_event_for_scene(mtm, scene, true);
+    let event = AppState::get_mut(mtm).surfaces.scene_entered_background();
+    send_surface_event(mtm, event);
+}
+
+pub(crate) fn scene_will_enter_foreground(mtm: MainThreadMarker, scene: &UIScene) {
+    let event = AppState::get_mut(mtm).surfaces.scene_will_enter_foreground();
+    send_surface_event(mtm, event);
+    send_occluded_event_for_scene(mtm, scene, false);
+}
+
+fn send_surface_event(mtm: MainThreadMarker, event: Option<Event>) {
+    let Some(event) = event else {
+        return;
+    };
+    let recreated = matches!(event, Event::CreateSurfaces);
+    handle_nonuser_event(mtm, EventWrapper::StaticEvent(event));
+
+    if recreated {
+        let redraws = mem::take(&mut AppState::get_mut(mtm).surfaces.deferred_gpu_redraws);
+        for view in redraws.into_values() {
+            queue_gl_or_metal_redraw(mtm, &view);
+        }
+    }
+}
+
+fn send_occluded_event_for_scene(mtm: MainThreadMarker, scene: &UIScene, occluded: bool) {
+    if !scene.is_kind_of::<UIWindowScene>() {
+        return;
+    }
+    // SAFETY: We just checked that the scene is a `UIWindowScene`
+    let scene = unsafe { &*(scene as *const UIScene).cast::<UIWindowScene>() };
+
+    let events = winit_windows_in_scene(scene)
         .into_iter()
         .map(|window| {
             EventWrapper::StaticEvent(Event::WindowEvent {
                 window_id: RootWindowId(window.id()),
-                event: WindowEvent::RedrawRequested,
+                event: WindowEvent::Occluded(occluded),
             })
         })
+        .collect::<Vec<_>>();
+    handle_nonuser_events(mtm, events);
+}
+
+fn winit_windows_in_scene(scene: &UIWindowScene) -> Vec<Retained<WinitUIWindow>> {
+    let windows = unsafe { scene.windows() };
+    windows
+        .iter()
+        .filter(|window| window.is_kind_of::<WinitUIWindow>())
+        // SAFETY: We just checked that the window is a `winit` window
+        .map(|window| unsafe { Retained::cast::<WinitUIWindow>(window.retain()) })
+        .collect()
+}
+
+pub(crate) fn screen_connected(mtm: MainThreadMarker, screen: Retained<UIScreen>) {
+    let monitor = RootMonitorHandle { inner: MonitorHandle::new(screen) };
+    handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::MonitorConnected(monitor)));
+}
+
+pub(crate) fn screen_disconnected(mtm: MainThreadMarker, screen: Retained<UIScreen>) {
+    let application = UIApplication::sharedApplication(mtm);
+
+    // Windows on the disconnected screen can no longer be displayed, so hide them and ask the
+    // application to close them.
+    let mut events = Vec::new();
+    #[allow(deprecated)]
+    for window in application.windows().iter() {
+        if window.is_kind_of::<WinitUIWindow>() && window.screen() == screen {
+            // SAFETY: We just checked that the window is a `winit` window
+            let window = unsafe {
+                let ptr: *const UIWindow = window;
+                let ptr: *const WinitUIWindow = ptr.cast();
+                &*ptr
+            };
+            window.setHidden(true);
+            events.push(EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id: RootWindowId(window.id()),
+                event: WindowEvent::CloseRequested,
+            }));
+        }
+    }
+
+    let monitor = RootMonitorHandle { inner: MonitorHandle::new(screen) };
+    events.push(EventWrapper::StaticEvent(Event::MonitorDisconnected(monitor)));
+    handle_nonuser_events(mtm, events);
+}
+
+pub(crate) fn screen_mode_changed(mtm: MainThreadMarker, screen: Retained<UIScreen>) {
+    let application = UIApplication::sharedApplication(mtm);
+
+    // The bounds of the screen follow its mode, keep the windows on it covering the whole screen.
+    let bounds = screen.bounds();
+    #[allow(deprecated)]
+    for window in application.windows().iter() {
+        if window.is_kind_of::<WinitUIWindow>() && window.screen() == screen {
+            window.setFrame(bounds);
+        }
+    }
+
+    let monitor = RootMonitorHandle { inner: MonitorHandle::new(screen) };
+    handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::MonitorVideoModeChanged(monitor)));
+}
+
+pub(crate) fn screen_brightness_changed(mtm: MainThreadMarker, screen: &UIScreen) {
+    let application = UIApplication::sharedApplication(mtm);
+    #[allow(deprecated)]
+    for window in application.windows().iter() {
+        if window.is_kind_of::<WinitUIWindow>() && &*window.screen() == screen {
+            // SAFETY: We just checked that the window is a `winit` window
+            let window = unsafe {
+                let ptr: *const UIWindow = window;
+                let ptr: *const WinitUIWindow = ptr.cast();
+                &*ptr
+            };
+            if let Some(view) = window.winit_view() {
+                view.update_edr_headroom();
+            }
+        }
+    }
+}
+
+/// Deliver URLs the application was asked to open, e.g. from `application:openURL:options:`.
+pub(crate) fn urls_opened(mtm: MainThreadMarker, urls: Vec<Retained<NSURL>>) {
+    let urls: Vec<String> = urls
+        .iter()
+        .filter_map(|url| unsafe { url.absoluteString() })
+        .map(|url| url.to_string())
         .collect();
+    if !urls.is_empty() {
+        handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::UrlsOpened(urls)));
+    }
+}
+
+/// Deliver the URL of a universal link, returns whether the activity was one.
+pub(crate) fn continue_user_activity(
+    mtm: MainThreadMarker,
+    user_activity: &NSUserActivity,
+) -> bool {
+    if unsafe { &*user_activity.activityType() != NSUserActivityTypeBrowsingWeb } {
+        return false;
+    }
+    match unsafe { user_activity.webpageURL() } {
+        Some(url) => {
+            urls_opened(mtm, vec![url]);
+            true
+        },
+        None => false,
+    }
+}
+
+/// Deliver a quick action chosen from the home screen.
+pub(crate) fn perform_shortcut_item(
+    mtm: MainThreadMarker,
+    shortcut_item: &UIApplicationShortcutItem,
+) {
+    let user_info = unsafe { shortcut_item.userInfo() }.map_or_else(HashMap::new, |dictionary| {
+        let (keys, values) = dictionary.to_vecs();
+        keys.into_iter()
+            .zip(values)
+            .filter_map(|(key, value)| {
+                // SAFETY: The values of the user info are property list objects, which are
+                // `NSObject`s.
+                let value = unsafe {
+                    &*(value as *const ProtocolObject<dyn NSSecureCoding>).cast::<NSObject>()
+                };
+                // SAFETY: Checked that the value is an `NSString`.
+                value.is_kind_of::<NSString>().then(|| {
+                    let value = unsafe { &*(value as *const NSObject).cast::<NSString>() };
+                    (key.to_string(), value.to_string())
+                })
+            })
+            .collect()
+    });
+
+    let item = ShortcutItem { item_type: unsafe { shortcut_item.r#type() }.to_string(), user_info };
+    handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::ShortcutItemActivated(item)));
+}
+
+/// Notify all windows about the keyboard frame change described by a
+/// `UIKeyboardWillChangeFrameNotification` or `UIKeyboardWillHideNotification`.
+pub(crate) fn keyboard_frame_will_change(
+    mtm: MainThreadMarker,
+    notification: &NSNotification,
+    hiding: bool,
+) {
+    let user_info = unsafe { notification.userInfo() };
+    let get =
+        |key: &NSString| user_info.as_ref().and_then(|info| unsafe { info.objectForKey(key) });
+
+    // SAFETY: The values of these keys are documented to be `NSValue` and `NSNumber`.
+    let frame = if hiding {
+        None
+    } else {
+        get(unsafe { UIKeyboardFrameEndUserInfoKey })
+            .and_then(|value| unsafe { Retained::cast::<NSValue>(value) }.get_rect())
+    };
+    let animation_duration = get(unsafe { UIKeyboardAnimationDurationUserInfoKey })
+        .map(|value| unsafe { Retained::cast::<NSNumber>(value) }.doubleValue())
+        .unwrap_or(0.0);
+    let animation_curve = get(unsafe { UIKeyboardAnimationCurveUserInfoKey })
+        .map(|value| {
+            UIViewAnimationCurve(unsafe { Retained::cast::<NSNumber>(value) }.integerValue())
+        })
+        .unwrap_or(UIViewAnimationCurve::EaseInOut);
+    let animation_curve = match animation_curve {
+        UIViewAnimationCurve::Linear => AnimationCurve::Linear,
+        UIViewAnimationCurve::EaseIn => AnimationCurve::EaseIn,
+        UIViewAnimationCurve::EaseOut => AnimationCurve::EaseOut,
+        UIViewAnimationCurve::EaseInOut => AnimationCurve::EaseInOut,
+        _ => AnimationCurve::Other,
+    };
+
+    let mut events = Vec::new();
+    #[allow(deprecated)]
+    for window in UIApplication::sharedApplication(mtm).windows().iter() {
+        if window.is_kind_of::<WinitUIWindow>() {
+            // SAFETY: We just checked that the window is a `winit` window
+            let window = unsafe {
+                let ptr: *const UIWindow = window;
+                let ptr: *const WinitUIWindow = ptr.cast();
+                &*ptr
+            };
+            let insets = frame.map(|frame| keyboard_insets(window, frame)).unwrap_or_default();
+            events.push(EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id: RootWindowId(window.id()),
+                event: WindowEvent::SoftKeyboardFrameChanged {
+                    insets,
+                    animation_duration: Duration::from_secs_f64(animation_duration.max(0.0)),
+                    animation_curve,
+                },
+            }));
+        }
+    }
+    handle_nonuser_events(mtm, events);
+}
+
+/// The part of the window covered by a keyboard with the given frame in screen coordinates.
+fn keyboard_insets(window: &WinitUIWindow, frame: CGRect) -> PhysicalInsets<u32> {
+    let screen = window.screen();
+    // The frame is in the coordinate space of the screen, which follows the interface
+    // orientation, so this also works while the device is rotated.
+    let frame = window.convertRect_fromCoordinateSpace(frame, &screen.coordinateSpace());
+    let bounds = window.bounds();
+
+    // Only a keyboard that is docked to the bottom of the window covers it, undocked and floating
+    // keyboards are drawn above the content instead.
+    let docked = frame.size.width > 0.0 && frame.max().y >= bounds.max().y;
+    let top = frame.min().y.max(bounds.min().y);
+    if !docked || top >= bounds.max().y {
+        return PhysicalInsets::default();
+    }
+
+    let bottom = bounds.max().y - top;
+    LogicalInsets::new(0.0, 0.0, bottom as f64, 0.0).to_physical(screen.scale() as f64)
+}
+
+pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
+    let mut this = AppState::get_mut(mtm);
+    if !this.has_launched() || this.has_terminated() {
+        return;
+    }
+    match this.state_mut() {
+        AppStateImpl::ProcessingEvents { .. } => {},
+        _ => bug!("`ProcessingRedraws` happened unexpectedly"),
+    };
+    drop(this);
+
+    handle_user_events(mtm);
+
+    let mut this = AppState::get_mut(mtm);
+    let mut redraws = this.main_events_cleared_transition();
+    if this.surfaces.destroyed {
+        // Rendering in the background gets the application terminated, so hold on to the
+        // redraws until the surfaces have been recreated.
+        this.surfaces.deferred_gpu_redraws.extend(redraws.drain());
+    }
+    let redraw_events: Vec<EventWrapper> =
+        redraws.into_values().map(|view| view.redraw_event()).collect();
     drop(this);
 
     handle_nonuser_events(mtm, redraw_events);
@@ -755,16 +1255,20 @@ pub(crate) fn terminated(application: &UIApplication) {
 }
 
 fn handle_hidpi_proxy(handler: &mut EventLoopHandler, event: ScaleFactorChanged) {
-    let ScaleFactorChanged { suggested_size, scale_factor, window } = event;
+    let ScaleFactorChanged { suggested_size, scale_factor, view } = event;
     let new_inner_size = Arc::new(Mutex::new(suggested_size));
     let event = Event::WindowEvent {
-        window_id: RootWindowId(window.id()),
+        window_id: RootWindowId(view.id()),
         event: WindowEvent::ScaleFactorChanged {
             scale_factor,
             inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
         },
     };
     handler.handle_event(event);
+    // Embedded views are sized by the application that embeds them.
+    let Some(window) = view.winit_window() else {
+        return;
+    };
     let (view, screen_frame) = get_view_and_screen_frame(&window);
     let physical_size = *new_inner_size.lock().unwrap();
     drop(new_inner_size);
@@ -837,6 +1341,7 @@ impl EventLoopWaker {
                 let duration = instant - now;
                 let fsecs =
                     duration.subsec_nanos() as f64 / 1_000_000_000.0 + duration.as_secs() as f64;
+                CFRunLoopTimerSetTolerance(self.timer, timer_tolerance(fsecs));
                 CFRunLoopTimerSetNextFireDate(self.timer, current + fsecs)
             }
         }
@@ -895,10 +1400,53 @@ os_capabilities! {
     /// <https://developer.apple.com/documentation/uikit/uiscreen/2806814-maximumframespersecond?language=objc>
     maximum_frames_per_second_err_msg: "-[UIScreen maximumFramesPerSecond]",
     maximum_frames_per_second: 10-3,
+    /// <https://developer.apple.com/documentation/quartzcore/cadisplaylink/1648421-preferredframespersecond?language=objc>
+    preferred_frames_per_second_err_msg: "-[CADisplayLink preferredFramesPerSecond]",
+    preferred_frames_per_second: 10-0,
+    /// <https://developer.apple.com/documentation/quartzcore/cadisplaylink/3875343-preferredframeraterange?language=objc>
+    #[allow(unused)] // error message unused
+    preferred_frame_rate_range_err_msg: "-[CADisplayLink preferredFrameRateRange]",
+    preferred_frame_rate_range: 15-0,
+    /// <https://developer.apple.com/documentation/uikit/uiviewcontroller/4047535-setneedsupdateofsupportedinterfa?language=objc>
+    #[allow(unused)] // error message unused
+    supported_interface_orientations_update_err_msg: "-[UIViewController setNeedsUpdateOfSupportedInterfaceOrientations]",
+    supported_interface_orientations_update: 16-0,
     /// <https://developer.apple.com/documentation/uikit/uitouch/1618110-force?language=objc>
     #[allow(unused)] // error message unused
     force_touch_err_msg: "-[UITouch force]",
     force_touch: 9-0,
+    /// <https://developer.apple.com/documentation/uikit/uitouch/1618109-estimatedpropertiesexpectingupda?language=objc>
+    #[allow(unused)] // error message unused
+    estimated_touch_properties_err_msg: "-[UITouch estimatedPropertiesExpectingUpdates]",
+    estimated_touch_properties: 9-1,
+    /// <https://developer.apple.com/documentation/uikit/uievent/1613808-coalescedtouchesfortouch?language=objc>
+    coalesced_touches_err_msg: "-[UIEvent coalescedTouchesForTouch:]",
+    coalesced_touches: 9-0,
+    /// <https://developer.apple.com/documentation/uikit/uipress/3526315-key?language=objc>
+    #[allow(unused)] // error message unused
+    hardware_keyboard_err_msg: "-[UIPress key]",
+    hardware_keyboard: 13-4,
+    /// <https://developer.apple.com/documentation/uikit/uihovergesturerecognizer/4044396-zoffset?language=objc>
+    stylus_hover_err_msg: "-[UIHoverGestureRecognizer zOffset]",
+    stylus_hover: 16-1,
+    /// <https://developer.apple.com/documentation/uikit/uipointerinteraction?language=objc>
+    pointer_err_msg: "UIPointerInteraction",
+    pointer: 13-4,
+    /// <https://developer.apple.com/documentation/uikit/uihovergesturerecognizer/4169349-altitudeangle?language=objc>
+    #[allow(unused)] // error message unused
+    stylus_hover_pose_err_msg: "-[UIHoverGestureRecognizer altitudeAngle]",
+    stylus_hover_pose: 16-4,
+    /// <https://developer.apple.com/documentation/uikit/uiwindowscene?language=objc>
+    #[allow(unused)] // error message unused
+    scenes_err_msg: "-[UIWindow windowScene]",
+    scenes: 13-0,
+    /// <https://developer.apple.com/documentation/uikit/uiview/3238086-overrideuserinterfacestyle?language=objc>
+    user_interface_style_err_msg: "-[UIView overrideUserInterfaceStyle]",
+    user_interface_style: 13-0,
+    /// <https://developer.apple.com/documentation/uikit/uiscreen/3974283-currentedrheadroom?language=objc>
+    #[allow(unused)] // error message unused
+    edr_headroom_err_msg: "-[UIScreen currentEDRHeadroom]",
+    edr_headroom: 16-0,
 }
 
 fn meets_requirements(
@@ -928,3 +1476,48 @@ pub fn os_capabilities() -> OSCapabilities {
     static OS_CAPABILITIES: OnceLock<OSCapabilities> = OnceLock::new();
     OS_CAPABILITIES.get_or_init(|| OSCapabilities::from_os_version(get_version())).clone()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_destroy(event: Option<Event>) -> bool {
+        matches!(event, Some(Event::DestroySurfaces))
+    }
+
+    fn is_create(event: Option<Event>) -> bool {
+        matches!(event, Some(Event::CreateSurfaces))
+    }
+
+    #[test]
+    fn surfaces_follow_application_lifecycle() {
+        let mut surfaces = SurfaceState::default();
+
+        // The initial `CreateSurfaces` is sent when launching.
+        assert!(surfaces.will_enter_foreground().is_none());
+
+        assert!(is_destroy(surfaces.entered_background()));
+        assert!(surfaces.destroyed);
+        assert!(surfaces.entered_background().is_none());
+
+        assert!(is_create(surfaces.will_enter_foreground()));
+        assert!(!surfaces.destroyed);
+        assert!(surfaces.will_enter_foreground().is_none());
+    }
+
+    #[test]
+    fn surfaces_follow_last_foreground_scene() {
+        let mut surfaces = SurfaceState::default();
+
+        assert!(surfaces.scene_will_enter_foreground().is_none());
+        assert!(surfaces.scene_will_enter_foreground().is_none());
+
+        // Surfaces are only destroyed once no scene is left in the foreground.
+        assert!(surfaces.scene_entered_background().is_none());
+        assert!(is_destroy(surfaces.scene_entered_background()));
+
+        // And recreated as soon as the first scene returns.
+        assert!(is_create(surfaces.scene_will_enter_foreground()));
+        assert!(surfaces.scene_will_enter_foreground().is_none());
+    }
+}
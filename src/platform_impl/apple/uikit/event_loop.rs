@@ -1,9 +1,12 @@
+use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::ffi::{c_char, c_int, c_void};
 use std::marker::PhantomData;
 use std::ptr::{self, NonNull};
+use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use core_foundation::base::{CFIndex, CFRelease};
 use core_foundation::runloop::{
@@ -17,11 +20,18 @@ use objc2::{msg_send_id, ClassType};
 use objc2_foundation::{MainThreadMarker, NSString};
 use objc2_ui_kit::{UIApplication, UIApplicationMain, UIScreen};
 
+use super::super::process_info;
 use super::app_state::EventLoopHandler;
 use crate::application::ApplicationHandler;
 use crate::error::EventLoopError;
 use crate::event::Event;
-use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEventSubscription,
+    MotionEventPolicy, ThermalState,
+};
+use crate::keyboard::{self, Key, KeyCode};
+use crate::platform::ios::LaunchOptions;
+use crate::platform::pump_events::PumpStatus;
 use crate::window::{CustomCursor, CustomCursorSource};
 
 use super::app_delegate::AppDelegate;
@@ -53,7 +63,21 @@ impl ActiveEventLoop {
     }
 
     #[inline]
-    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+    pub fn listen_device_events(&self, _subscription: DeviceEventSubscription) {}
+
+    pub fn set_motion_event_policy(&self, _policy: MotionEventPolicy) {}
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        MotionEventPolicy::Coalesced
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        keyboard::layout_independent_key(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        keyboard::find_key_code(key, keyboard::layout_independent_keys())
+    }
 
     #[cfg(feature = "rwh_05")]
     #[inline]
@@ -90,6 +114,18 @@ impl ActiveEventLoop {
     pub(crate) fn owned_display_handle(&self) -> OwnedDisplayHandle {
         OwnedDisplayHandle
     }
+
+    pub(crate) fn is_low_power_mode_enabled(&self) -> bool {
+        process_info::is_low_power_mode_enabled()
+    }
+
+    pub(crate) fn thermal_state(&self) -> ThermalState {
+        process_info::thermal_state()
+    }
+
+    pub(crate) fn launch_options(&self) -> LaunchOptions {
+        AppState::get_mut(self.mtm).launch_options()
+    }
 }
 
 #[derive(Clone)]
@@ -131,15 +167,30 @@ fn map_user_event<A: ApplicationHandler>(
         Event::Suspended => app.suspended(window_target),
         Event::Resumed => app.resumed(window_target),
         Event::CreateSurfaces => app.can_create_surfaces(window_target),
+        Event::DestroySurfaces => app.destroy_surfaces(window_target),
         Event::AboutToWait => app.about_to_wait(window_target),
         Event::LoopExiting => app.exiting(window_target),
         Event::MemoryWarning => app.memory_warning(window_target),
+        Event::PowerStateChanged => app.power_state_changed(window_target),
+        Event::AccessibilitySettingsChanged => app.accessibility_settings_changed(window_target),
+        Event::KeyboardLayoutChanged => app.keyboard_layout_changed(window_target),
+        Event::SessionEvent(event) => app.session_event(window_target, event),
+        Event::MonitorConnected(monitor) => app.monitor_connected(window_target, monitor),
+        Event::MonitorDisconnected(monitor) => app.monitor_disconnected(window_target, monitor),
+        Event::MonitorVideoModeChanged(monitor) => {
+            app.monitor_video_mode_changed(window_target, monitor)
+        },
+        Event::UrlsOpened(urls) => app.urls_opened(window_target, urls),
+        Event::ShortcutItemActivated(item) => app.shortcut_item_activated(window_target, item),
     }
 }
 
 pub struct EventLoop {
     mtm: MainThreadMarker,
     window_target: RootActiveEventLoop,
+    observers: [CFRunLoopObserverRef; 3],
+    // Events received while the host application runs the main loop, see `pump_app_events`.
+    pumped_events: Rc<RefCell<VecDeque<Event>>>,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -162,11 +213,17 @@ impl EventLoop {
         }
 
         // this line sets up the main run loop before `UIApplicationMain`
-        setup_control_flow_observers();
+        let observers = setup_control_flow_observers();
 
         Ok(EventLoop {
             mtm,
-            window_target: RootActiveEventLoop { p: ActiveEventLoop { mtm }, _marker: PhantomData },
+            window_target: RootActiveEventLoop {
+                p: ActiveEventLoop { mtm },
+                user_event_queue: Default::default(),
+                _marker: PhantomData,
+            },
+            observers,
+            pumped_events: Rc::new(RefCell::new(VecDeque::new())),
         })
     }
 
@@ -213,6 +270,57 @@ impl EventLoop {
         unreachable!()
     }
 
+    pub fn pump_app_events<A: ApplicationHandler>(
+        &mut self,
+        _timeout: Option<Duration>,
+        app: &mut A,
+    ) -> PumpStatus {
+        // The host application runs the main loop, so events can't be waited for here. Instead,
+        // the event loop buffers the events it observes until they are dispatched by the next
+        // call.
+        if !app_state::has_launched(self.mtm) {
+            let application: Option<Retained<UIApplication>> =
+                unsafe { msg_send_id![UIApplication::class(), sharedApplication] };
+            assert!(
+                application.is_some(),
+                "`EventLoop::pump_app_events` requires the application to be launched with \
+                 `UIApplicationMain` on iOS\nNote: use `EventLoop::run_app` if winit should \
+                 launch the application",
+            );
+
+            // The host application's scroll views run the main loop in
+            // `UITrackingRunLoopMode`, which the event loop has to follow as well.
+            let main_loop = unsafe { CFRunLoopGetMain() };
+            for observer in self.observers {
+                unsafe { CFRunLoopAddObserver(main_loop, observer, kCFRunLoopCommonModes) };
+            }
+
+            let pumped_events = Rc::clone(&self.pumped_events);
+            let handler = EventLoopHandler {
+                handler: Box::new(move |event, _| pumped_events.borrow_mut().push_back(event)),
+                event_loop: RootActiveEventLoop {
+                    p: ActiveEventLoop { mtm: self.mtm },
+                    user_event_queue: Arc::clone(&self.window_target.user_event_queue),
+                    _marker: PhantomData,
+                },
+            };
+            app_state::launch_in_host(self.mtm, handler);
+        }
+
+        let mut handler = map_user_event(app, AppState::get_mut(self.mtm).proxy_wake_up());
+        loop {
+            // Handling an event may buffer new ones, don't hold on to the queue meanwhile.
+            let event = self.pumped_events.borrow_mut().pop_front();
+            match event {
+                Some(event) => handler(event, &self.window_target),
+                None => break,
+            }
+        }
+
+        // It is not possible to quit an iOS app programmatically.
+        PumpStatus::Continue
+    }
+
     pub fn window_target(&self) -> &RootActiveEventLoop {
         &self.window_target
     }
@@ -281,7 +389,7 @@ impl EventLoopProxy {
     }
 }
 
-fn setup_control_flow_observers() {
+fn setup_control_flow_observers() -> [CFRunLoopObserverRef; 3] {
     unsafe {
         // begin is queued with the highest priority to ensure it is processed before other
         // observers
@@ -369,5 +477,7 @@ fn setup_control_flow_observers() {
             ptr::null_mut(),
         );
         CFRunLoopAddObserver(main_loop, end_observer, kCFRunLoopDefaultMode);
+
+        [begin_observer, main_end_observer, end_observer]
     }
 }
@@ -0,0 +1,398 @@
+use objc2_ui_kit::{UIKey, UIKeyModifierFlags, UIKeyboardHIDUsage};
+use smol_str::SmolStr;
+
+use super::KeyEventExtra;
+use crate::event::{ElementState, KeyEvent};
+use crate::keyboard::{
+    Key, KeyCode, KeyLocation, LockKeys, ModifiersKeys, ModifiersState, NamedKey, NativeKey,
+    NativeKeyCode, PhysicalKey,
+};
+
+/// The prefix of the strings UIKit reports as the characters of special keys, e.g.
+/// `UIKeyInputUpArrow`.
+const SPECIAL_KEY_INPUT_PREFIX: &str = "UIKeyInput";
+
+/// Create `KeyEvent` for the given `UIKey`.
+///
+/// The globe key of Apple keyboards is reported as [`KeyCode::Fn`] and [`NamedKey::Fn`], and a
+/// press that only starts composing a character, e.g. a dead key, is reported as
+/// [`Key::Dead`] without text.
+///
+/// UIKit doesn't report key repeats, so the event is never marked as a repeat.
+pub(crate) fn create_key_event(key: &UIKey, state: ElementState) -> KeyEvent {
+    let usage = unsafe { key.keyCode() };
+    let physical_key = hid_usage_to_physicalkey(usage);
+
+    let text_with_all_modifiers = characters(&unsafe { key.characters() }.to_string());
+    let key_from_code = code_to_key(physical_key, usage);
+    let logical_key = if !matches!(key_from_code, Key::Unidentified(_)) {
+        key_from_code
+    } else {
+        let modifiers = unsafe { key.modifierFlags() };
+        let has_ctrl = modifiers.contains(UIKeyModifierFlags::UIKeyModifierControl);
+        let has_cmd = modifiers.contains(UIKeyModifierFlags::UIKeyModifierCommand);
+        let characters_ignoring_modifiers =
+            characters(&unsafe { key.charactersIgnoringModifiers() }.to_string());
+
+        match (text_with_all_modifiers, characters_ignoring_modifiers) {
+            // Same as on macOS, only ctrl and cmd are ignored, so that e.g. alt+8 on a German
+            // layout still produces "{".
+            (Some(text), _) if !has_ctrl && !has_cmd => Key::Character(text),
+            (_, Some(text)) => Key::Character(text),
+            (Some(text), None) => Key::Character(text),
+            // The press only started composing a character.
+            (None, None) if !matches!(physical_key, PhysicalKey::Unidentified(_)) => {
+                Key::Dead(None)
+            },
+            (None, None) => key_from_code,
+        }
+    };
+
+    let text =
+        if state == ElementState::Pressed { logical_key.to_text().map(SmolStr::new) } else { None };
+
+    KeyEvent {
+        location: code_to_location(physical_key),
+        logical_key,
+        physical_key,
+        repeat: false,
+        state,
+        text,
+        platform_specific: KeyEventExtra {},
+    }
+}
+
+/// Create a `KeyEvent` for a key of the on-screen keyboard, which UIKit only reports through
+/// the text input system.
+pub(crate) fn on_screen_key_event(key: NamedKey, code: KeyCode, state: ElementState) -> KeyEvent {
+    KeyEvent {
+        location: KeyLocation::Standard,
+        logical_key: Key::Named(key),
+        physical_key: PhysicalKey::Code(code),
+        repeat: false,
+        state,
+        text: None,
+        platform_specific: KeyEventExtra {},
+    }
+}
+
+fn characters(string: &str) -> Option<SmolStr> {
+    if string.is_empty() || string.starts_with(SPECIAL_KEY_INPUT_PREFIX) {
+        None
+    } else {
+        Some(SmolStr::new(string))
+    }
+}
+
+/// The modifiers reported along with a key, without distinguishing between left and right.
+pub(crate) fn modifiers_state(key: &UIKey) -> ModifiersState {
+    let flags = unsafe { key.modifierFlags() };
+    let mut state = ModifiersState::empty();
+    state.set(ModifiersState::SHIFT, flags.contains(UIKeyModifierFlags::UIKeyModifierShift));
+    state.set(ModifiersState::CONTROL, flags.contains(UIKeyModifierFlags::UIKeyModifierControl));
+    state.set(ModifiersState::ALT, flags.contains(UIKeyModifierFlags::UIKeyModifierAlternate));
+    state.set(ModifiersState::SUPER, flags.contains(UIKeyModifierFlags::UIKeyModifierCommand));
+    state
+}
+
+/// The lock keys that are on, UIKit doesn't report num lock.
+pub(crate) fn lock_keys(key: &UIKey) -> LockKeys {
+    let flags = unsafe { key.modifierFlags() };
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(LockKeys::CAPS_LOCK, flags.contains(UIKeyModifierFlags::UIKeyModifierAlphaShift));
+    lock_keys
+}
+
+/// Each modifier, along with the keys on either side of the keyboard controlling it.
+pub(crate) const MODIFIER_KEYS: [(ModifiersState, ModifiersKeys); 4] = [
+    (ModifiersState::SHIFT, ModifiersKeys::LSHIFT.union(ModifiersKeys::RSHIFT)),
+    (ModifiersState::CONTROL, ModifiersKeys::LCONTROL.union(ModifiersKeys::RCONTROL)),
+    (ModifiersState::ALT, ModifiersKeys::LALT.union(ModifiersKeys::RALT)),
+    (ModifiersState::SUPER, ModifiersKeys::LSUPER.union(ModifiersKeys::RSUPER)),
+];
+
+/// The modifier key corresponding to the given key, if it is one.
+pub(crate) fn modifier_key(physical_key: PhysicalKey) -> Option<ModifiersKeys> {
+    match physical_key {
+        PhysicalKey::Code(KeyCode::ShiftLeft) => Some(ModifiersKeys::LSHIFT),
+        PhysicalKey::Code(KeyCode::ShiftRight) => Some(ModifiersKeys::RSHIFT),
+        PhysicalKey::Code(KeyCode::ControlLeft) => Some(ModifiersKeys::LCONTROL),
+        PhysicalKey::Code(KeyCode::ControlRight) => Some(ModifiersKeys::RCONTROL),
+        PhysicalKey::Code(KeyCode::AltLeft) => Some(ModifiersKeys::LALT),
+        PhysicalKey::Code(KeyCode::AltRight) => Some(ModifiersKeys::RALT),
+        PhysicalKey::Code(KeyCode::SuperLeft) => Some(ModifiersKeys::LSUPER),
+        PhysicalKey::Code(KeyCode::SuperRight) => Some(ModifiersKeys::RSUPER),
+        _ => None,
+    }
+}
+
+fn code_to_key(key: PhysicalKey, usage: UIKeyboardHIDUsage) -> Key {
+    let code = match key {
+        PhysicalKey::Code(code) => code,
+        PhysicalKey::Unidentified(code) => return Key::Unidentified(code.into()),
+    };
+
+    Key::Named(match code {
+        KeyCode::Enter => NamedKey::Enter,
+        KeyCode::Tab => NamedKey::Tab,
+        KeyCode::Space => NamedKey::Space,
+        KeyCode::Backspace => NamedKey::Backspace,
+        KeyCode::Escape => NamedKey::Escape,
+        KeyCode::SuperRight => NamedKey::Super,
+        KeyCode::SuperLeft => NamedKey::Super,
+        KeyCode::ShiftLeft => NamedKey::Shift,
+        KeyCode::AltLeft => NamedKey::Alt,
+        KeyCode::ControlLeft => NamedKey::Control,
+        KeyCode::ShiftRight => NamedKey::Shift,
+        KeyCode::AltRight => NamedKey::Alt,
+        KeyCode::ControlRight => NamedKey::Control,
+        KeyCode::CapsLock => NamedKey::CapsLock,
+        KeyCode::Fn => NamedKey::Fn,
+
+        KeyCode::NumLock => NamedKey::NumLock,
+        KeyCode::AudioVolumeUp => NamedKey::AudioVolumeUp,
+        KeyCode::AudioVolumeDown => NamedKey::AudioVolumeDown,
+        KeyCode::AudioVolumeMute => NamedKey::AudioVolumeMute,
+
+        KeyCode::NumpadEnter => NamedKey::Enter,
+
+        KeyCode::F1 => NamedKey::F1,
+        KeyCode::F2 => NamedKey::F2,
+        KeyCode::F3 => NamedKey::F3,
+        KeyCode::F4 => NamedKey::F4,
+        KeyCode::F5 => NamedKey::F5,
+        KeyCode::F6 => NamedKey::F6,
+        KeyCode::F7 => NamedKey::F7,
+        KeyCode::F8 => NamedKey::F8,
+        KeyCode::F9 => NamedKey::F9,
+        KeyCode::F10 => NamedKey::F10,
+        KeyCode::F11 => NamedKey::F11,
+        KeyCode::F12 => NamedKey::F12,
+        KeyCode::F13 => NamedKey::F13,
+        KeyCode::F14 => NamedKey::F14,
+        KeyCode::F15 => NamedKey::F15,
+        KeyCode::F16 => NamedKey::F16,
+        KeyCode::F17 => NamedKey::F17,
+        KeyCode::F18 => NamedKey::F18,
+        KeyCode::F19 => NamedKey::F19,
+        KeyCode::F20 => NamedKey::F20,
+        KeyCode::F21 => NamedKey::F21,
+        KeyCode::F22 => NamedKey::F22,
+        KeyCode::F23 => NamedKey::F23,
+        KeyCode::F24 => NamedKey::F24,
+
+        KeyCode::PrintScreen => NamedKey::PrintScreen,
+        KeyCode::ScrollLock => NamedKey::ScrollLock,
+        KeyCode::Pause => NamedKey::Pause,
+        KeyCode::Insert => NamedKey::Insert,
+        KeyCode::Home => NamedKey::Home,
+        KeyCode::PageUp => NamedKey::PageUp,
+        KeyCode::Delete => NamedKey::Delete,
+        KeyCode::End => NamedKey::End,
+        KeyCode::PageDown => NamedKey::PageDown,
+        KeyCode::ArrowLeft => NamedKey::ArrowLeft,
+        KeyCode::ArrowRight => NamedKey::ArrowRight,
+        KeyCode::ArrowDown => NamedKey::ArrowDown,
+        KeyCode::ArrowUp => NamedKey::ArrowUp,
+        KeyCode::ContextMenu => NamedKey::ContextMenu,
+        KeyCode::Help => NamedKey::Help,
+        KeyCode::Power => NamedKey::Power,
+        _ => return Key::Unidentified(NativeKey::Ios(usage.0 as u16)),
+    })
+}
+
+fn code_to_location(key: PhysicalKey) -> KeyLocation {
+    let code = match key {
+        PhysicalKey::Code(code) => code,
+        PhysicalKey::Unidentified(_) => return KeyLocation::Standard,
+    };
+
+    match code {
+        KeyCode::SuperRight => KeyLocation::Right,
+        KeyCode::SuperLeft => KeyLocation::Left,
+        KeyCode::ShiftLeft => KeyLocation::Left,
+        KeyCode::AltLeft => KeyLocation::Left,
+        KeyCode::ControlLeft => KeyLocation::Left,
+        KeyCode::ShiftRight => KeyLocation::Right,
+        KeyCode::AltRight => KeyLocation::Right,
+        KeyCode::ControlRight => KeyLocation::Right,
+
+        KeyCode::NumLock => KeyLocation::Numpad,
+        KeyCode::NumpadDecimal => KeyLocation::Numpad,
+        KeyCode::NumpadMultiply => KeyLocation::Numpad,
+        KeyCode::NumpadAdd => KeyLocation::Numpad,
+        KeyCode::NumpadDivide => KeyLocation::Numpad,
+        KeyCode::NumpadEnter => KeyLocation::Numpad,
+        KeyCode::NumpadSubtract => KeyLocation::Numpad,
+        KeyCode::NumpadEqual => KeyLocation::Numpad,
+        KeyCode::NumpadComma => KeyLocation::Numpad,
+        KeyCode::Numpad0 => KeyLocation::Numpad,
+        KeyCode::Numpad1 => KeyLocation::Numpad,
+        KeyCode::Numpad2 => KeyLocation::Numpad,
+        KeyCode::Numpad3 => KeyLocation::Numpad,
+        KeyCode::Numpad4 => KeyLocation::Numpad,
+        KeyCode::Numpad5 => KeyLocation::Numpad,
+        KeyCode::Numpad6 => KeyLocation::Numpad,
+        KeyCode::Numpad7 => KeyLocation::Numpad,
+        KeyCode::Numpad8 => KeyLocation::Numpad,
+        KeyCode::Numpad9 => KeyLocation::Numpad,
+
+        _ => KeyLocation::Standard,
+    }
+}
+
+/// Converts a USB HID usage from the keyboard page into a `PhysicalKey`.
+///
+/// <https://developer.apple.com/documentation/uikit/uikeyboardhidusage?language=objc>
+fn hid_usage_to_physicalkey(usage: UIKeyboardHIDUsage) -> PhysicalKey {
+    PhysicalKey::Code(match usage.0 {
+        // UIKit reports the globe key of Apple keyboards, which lives on a vendor page, with
+        // the otherwise unused "error undefined" usage.
+        0x03 => KeyCode::Fn,
+        0x04 => KeyCode::KeyA,
+        0x05 => KeyCode::KeyB,
+        0x06 => KeyCode::KeyC,
+        0x07 => KeyCode::KeyD,
+        0x08 => KeyCode::KeyE,
+        0x09 => KeyCode::KeyF,
+        0x0a => KeyCode::KeyG,
+        0x0b => KeyCode::KeyH,
+        0x0c => KeyCode::KeyI,
+        0x0d => KeyCode::KeyJ,
+        0x0e => KeyCode::KeyK,
+        0x0f => KeyCode::KeyL,
+        0x10 => KeyCode::KeyM,
+        0x11 => KeyCode::KeyN,
+        0x12 => KeyCode::KeyO,
+        0x13 => KeyCode::KeyP,
+        0x14 => KeyCode::KeyQ,
+        0x15 => KeyCode::KeyR,
+        0x16 => KeyCode::KeyS,
+        0x17 => KeyCode::KeyT,
+        0x18 => KeyCode::KeyU,
+        0x19 => KeyCode::KeyV,
+        0x1a => KeyCode::KeyW,
+        0x1b => KeyCode::KeyX,
+        0x1c => KeyCode::KeyY,
+        0x1d => KeyCode::KeyZ,
+        0x1e => KeyCode::Digit1,
+        0x1f => KeyCode::Digit2,
+        0x20 => KeyCode::Digit3,
+        0x21 => KeyCode::Digit4,
+        0x22 => KeyCode::Digit5,
+        0x23 => KeyCode::Digit6,
+        0x24 => KeyCode::Digit7,
+        0x25 => KeyCode::Digit8,
+        0x26 => KeyCode::Digit9,
+        0x27 => KeyCode::Digit0,
+        0x28 => KeyCode::Enter,
+        0x29 => KeyCode::Escape,
+        0x2a => KeyCode::Backspace,
+        0x2b => KeyCode::Tab,
+        0x2c => KeyCode::Space,
+        0x2d => KeyCode::Minus,
+        0x2e => KeyCode::Equal,
+        0x2f => KeyCode::BracketLeft,
+        0x30 => KeyCode::BracketRight,
+        0x31 => KeyCode::Backslash,
+        // The non-US "#" key occupies the position of the backslash key on ISO keyboards.
+        0x32 => KeyCode::Backslash,
+        0x33 => KeyCode::Semicolon,
+        0x34 => KeyCode::Quote,
+        0x35 => KeyCode::Backquote,
+        0x36 => KeyCode::Comma,
+        0x37 => KeyCode::Period,
+        0x38 => KeyCode::Slash,
+        0x39 => KeyCode::CapsLock,
+        0x3a => KeyCode::F1,
+        0x3b => KeyCode::F2,
+        0x3c => KeyCode::F3,
+        0x3d => KeyCode::F4,
+        0x3e => KeyCode::F5,
+        0x3f => KeyCode::F6,
+        0x40 => KeyCode::F7,
+        0x41 => KeyCode::F8,
+        0x42 => KeyCode::F9,
+        0x43 => KeyCode::F10,
+        0x44 => KeyCode::F11,
+        0x45 => KeyCode::F12,
+        0x46 => KeyCode::PrintScreen,
+        0x47 => KeyCode::ScrollLock,
+        0x48 => KeyCode::Pause,
+        0x49 => KeyCode::Insert,
+        0x4a => KeyCode::Home,
+        0x4b => KeyCode::PageUp,
+        0x4c => KeyCode::Delete,
+        0x4d => KeyCode::End,
+        0x4e => KeyCode::PageDown,
+        0x4f => KeyCode::ArrowRight,
+        0x50 => KeyCode::ArrowLeft,
+        0x51 => KeyCode::ArrowDown,
+        0x52 => KeyCode::ArrowUp,
+        0x53 => KeyCode::NumLock,
+        0x54 => KeyCode::NumpadDivide,
+        0x55 => KeyCode::NumpadMultiply,
+        0x56 => KeyCode::NumpadSubtract,
+        0x57 => KeyCode::NumpadAdd,
+        0x58 => KeyCode::NumpadEnter,
+        0x59 => KeyCode::Numpad1,
+        0x5a => KeyCode::Numpad2,
+        0x5b => KeyCode::Numpad3,
+        0x5c => KeyCode::Numpad4,
+        0x5d => KeyCode::Numpad5,
+        0x5e => KeyCode::Numpad6,
+        0x5f => KeyCode::Numpad7,
+        0x60 => KeyCode::Numpad8,
+        0x61 => KeyCode::Numpad9,
+        0x62 => KeyCode::Numpad0,
+        0x63 => KeyCode::NumpadDecimal,
+        0x64 => KeyCode::IntlBackslash,
+        0x65 => KeyCode::ContextMenu,
+        0x66 => KeyCode::Power,
+        0x67 => KeyCode::NumpadEqual,
+        0x68 => KeyCode::F13,
+        0x69 => KeyCode::F14,
+        0x6a => KeyCode::F15,
+        0x6b => KeyCode::F16,
+        0x6c => KeyCode::F17,
+        0x6d => KeyCode::F18,
+        0x6e => KeyCode::F19,
+        0x6f => KeyCode::F20,
+        0x70 => KeyCode::F21,
+        0x71 => KeyCode::F22,
+        0x72 => KeyCode::F23,
+        0x73 => KeyCode::F24,
+        0x74 => KeyCode::Open,
+        0x75 => KeyCode::Help,
+        0x77 => KeyCode::Select,
+        0x79 => KeyCode::Again,
+        0x7a => KeyCode::Undo,
+        0x7b => KeyCode::Cut,
+        0x7c => KeyCode::Copy,
+        0x7d => KeyCode::Paste,
+        0x7e => KeyCode::Find,
+        0x7f => KeyCode::AudioVolumeMute,
+        0x80 => KeyCode::AudioVolumeUp,
+        0x81 => KeyCode::AudioVolumeDown,
+        0x85 => KeyCode::NumpadComma,
+        0x87 => KeyCode::IntlRo,
+        0x88 => KeyCode::KanaMode,
+        0x89 => KeyCode::IntlYen,
+        0x8a => KeyCode::Convert,
+        0x8b => KeyCode::NonConvert,
+        0x90 => KeyCode::Lang1,
+        0x91 => KeyCode::Lang2,
+        0x92 => KeyCode::Lang3,
+        0x93 => KeyCode::Lang4,
+        0x94 => KeyCode::Lang5,
+        0xe0 => KeyCode::ControlLeft,
+        0xe1 => KeyCode::ShiftLeft,
+        0xe2 => KeyCode::AltLeft,
+        0xe3 => KeyCode::SuperLeft,
+        0xe4 => KeyCode::ControlRight,
+        0xe5 => KeyCode::ShiftRight,
+        0xe6 => KeyCode::AltRight,
+        0xe7 => KeyCode::SuperRight,
+        _ => return PhysicalKey::Unidentified(NativeKeyCode::Ios(usage.0 as u16)),
+    })
+}
@@ -3,13 +3,17 @@
 mod app_delegate;
 mod app_state;
 mod event_loop;
+mod keyboard;
 mod monitor;
+mod scene_delegate;
+mod text_input;
 mod view;
 mod view_controller;
 mod window;
 
 use std::fmt;
 
+use crate::error::PlatformError;
 use crate::event::DeviceId as RootDeviceId;
 
 pub(crate) use self::event_loop::{
@@ -17,7 +21,7 @@ pub(crate) use self::event_loop::{
     PlatformSpecificEventLoopAttributes,
 };
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
-pub(crate) use self::window::{PlatformSpecificWindowAttributes, Window, WindowId};
+pub(crate) use self::window::{ParentView, PlatformSpecificWindowAttributes, Window, WindowId};
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
@@ -50,3 +54,7 @@ impl fmt::Display for OsError {
         write!(f, "os error")
     }
 }
+
+pub(crate) fn platform_error(error: &OsError) -> Option<PlatformError> {
+    match *error {}
+}
@@ -5,13 +5,18 @@ use std::{fmt, hash, ptr};
 
 use objc2::mutability::IsRetainable;
 use objc2::rc::Retained;
-use objc2::Message;
+use objc2::runtime::NSObjectProtocol;
+use objc2::{sel, Message};
 use objc2_foundation::{run_on_main, MainThreadBound, MainThreadMarker, NSInteger};
-use objc2_ui_kit::{UIScreen, UIScreenMode};
+use objc2_ui_kit::{
+    UIDisplayGamut, UIScreen, UIScreenMode, UIScreenOverscanCompensation, UITraitEnvironment,
+};
 
 use super::app_state;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::error::NotSupportedError;
+use crate::monitor::{ColorProperties, ColorSpace, VideoModeHandle as RootVideoModeHandle};
+use crate::platform::ios::DisplayGamut;
 
 // Workaround for `MainThreadBound` implementing almost no traits
 #[derive(Debug)]
@@ -102,13 +107,13 @@ impl Clone for MonitorHandle {
 
 impl hash::Hash for MonitorHandle {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        (self as *const Self).hash(state);
+        self.ui_screen_ptr().hash(state);
     }
 }
 
 impl PartialEq for MonitorHandle {
     fn eq(&self, other: &Self) -> bool {
-        ptr::eq(self, other)
+        ptr::eq(self.ui_screen_ptr(), other.ui_screen_ptr())
     }
 }
 
@@ -123,7 +128,7 @@ impl PartialOrd for MonitorHandle {
 impl Ord for MonitorHandle {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         // TODO: Make a better ordering
-        (self as *const Self).cmp(&(other as *const Self))
+        self.ui_screen_ptr().cmp(&other.ui_screen_ptr())
     }
 }
 
@@ -182,6 +187,22 @@ impl MonitorHandle {
         Some(self.ui_screen.get_on_main(|ui_screen| refresh_rate_millihertz(ui_screen)))
     }
 
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        // Whether the screen supports HDR is only known since iOS 16.
+        let max_potential_edr_headroom = self.max_potential_edr_headroom()?;
+        let color_space = match self.display_gamut() {
+            DisplayGamut::Srgb => ColorSpace::Srgb,
+            DisplayGamut::P3 => ColorSpace::DisplayP3,
+        };
+        Some(ColorProperties {
+            hdr_enabled: max_potential_edr_headroom > 1.0,
+            max_luminance_nits: None,
+            sdr_white_level_nits: None,
+            color_space: Some(color_space),
+            bits_per_channel: None,
+        })
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         run_on_main(|mtm| {
             let ui_screen = self.ui_screen(mtm);
@@ -203,6 +224,67 @@ impl MonitorHandle {
         self.ui_screen.get(mtm)
     }
 
+    fn ui_screen_ptr(&self) -> *const UIScreen {
+        // SAFETY: Marker only used to get the pointer
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        Retained::as_ptr(self.ui_screen(mtm))
+    }
+
+    pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
+        run_on_main(|mtm| {
+            let ui_screen = self.ui_screen(mtm);
+            ui_screen.currentMode().map(|mode| VideoModeHandle::new(ui_screen.clone(), mode, mtm))
+        })
+    }
+
+    /// Apply `video_mode` to the screen, which must not be the built-in display.
+    pub(crate) fn set_video_mode(
+        &self,
+        video_mode: &VideoModeHandle,
+    ) -> Result<(), NotSupportedError> {
+        run_on_main(|mtm| {
+            let ui_screen = self.ui_screen(mtm);
+            #[allow(deprecated)]
+            let is_main_screen = *ui_screen == UIScreen::mainScreen(mtm);
+            if is_main_screen || video_mode.monitor != *self {
+                return Err(NotSupportedError::new());
+            }
+            ui_screen.setCurrentMode(Some(video_mode.screen_mode(mtm)));
+            // Otherwise the mode is scaled down to leave room for the overscan of TVs, resulting
+            // in black bars around the image.
+            ui_screen.setOverscanCompensation(UIScreenOverscanCompensation::None);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn display_gamut(&self) -> DisplayGamut {
+        let gamut = self.ui_screen.get_on_main(|ui_screen| {
+            let trait_collection = ui_screen.traitCollection();
+            // Only available since iOS 10.
+            if trait_collection.respondsToSelector(sel!(displayGamut)) {
+                unsafe { trait_collection.displayGamut() }
+            } else {
+                UIDisplayGamut::Unspecified
+            }
+        });
+        match gamut {
+            UIDisplayGamut::P3 => DisplayGamut::P3,
+            _ => DisplayGamut::Srgb,
+        }
+    }
+
+    pub(crate) fn edr_headroom(&self) -> Option<f32> {
+        self.ui_screen.get_on_main(|ui_screen| edr_headroom(ui_screen))
+    }
+
+    pub(crate) fn max_potential_edr_headroom(&self) -> Option<f32> {
+        if !app_state::os_capabilities().edr_headroom {
+            return None;
+        }
+        Some(self.ui_screen.get_on_main(|ui_screen| unsafe { ui_screen.potentialEDRHeadroom() })
+            as f32)
+    }
+
     pub fn preferred_video_mode(&self) -> VideoModeHandle {
         run_on_main(|mtm| {
             VideoModeHandle::new(
@@ -238,6 +320,14 @@ fn refresh_rate_millihertz(uiscreen: &UIScreen) -> u32 {
     refresh_rate_millihertz as u32 * 1000
 }
 
+/// The current EDR headroom of the screen, if the OS supports querying it.
+pub(crate) fn edr_headroom(ui_screen: &UIScreen) -> Option<f32> {
+    if !app_state::os_capabilities().edr_headroom {
+        return None;
+    }
+    Some(unsafe { ui_screen.currentEDRHeadroom() } as f32)
+}
+
 pub fn uiscreens(mtm: MainThreadMarker) -> VecDeque<MonitorHandle> {
     #[allow(deprecated)]
     UIScreen::screens(mtm).into_iter().map(MonitorHandle::new).collect()
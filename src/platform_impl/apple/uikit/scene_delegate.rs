@@ -0,0 +1,100 @@
+use block2::Block;
+use objc2::runtime::Bool;
+use objc2::{declare_class, mutability, ClassType, DeclaredClass};
+use objc2_foundation::{MainThreadMarker, NSObject, NSObjectProtocol, NSSet, NSUserActivity};
+use objc2_ui_kit::{
+    UIApplicationShortcutItem, UIOpenURLContext, UIResponder, UIScene, UISceneConnectionOptions,
+    UISceneDelegate, UISceneSession, UIWindowScene, UIWindowSceneDelegate,
+};
+
+use super::app_state;
+
+declare_class!(
+    pub struct SceneDelegate;
+
+    unsafe impl ClassType for SceneDelegate {
+        #[inherits(NSObject)]
+        type Super = UIResponder;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitSceneDelegate";
+    }
+
+    impl DeclaredClass for SceneDelegate {}
+
+    unsafe impl NSObjectProtocol for SceneDelegate {}
+
+    unsafe impl UISceneDelegate for SceneDelegate {
+        #[method(scene:willConnectToSession:options:)]
+        fn will_connect_to_session(
+            &self,
+            scene: &UIScene,
+            _session: &UISceneSession,
+            options: &UISceneConnectionOptions,
+        ) {
+            let mtm = MainThreadMarker::from(self);
+            app_state::scene_will_connect(mtm, scene);
+
+            // Scene-based applications receive what they were launched or activated for here,
+            // instead of through the application delegate.
+            let url_contexts = unsafe { options.URLContexts() };
+            let urls = url_contexts.iter().map(|context| unsafe { context.URL() }).collect();
+            app_state::urls_opened(mtm, urls);
+            for user_activity in unsafe { options.userActivities() }.iter() {
+                app_state::continue_user_activity(mtm, user_activity);
+            }
+            if let Some(shortcut_item) = unsafe { options.shortcutItem() } {
+                app_state::perform_shortcut_item(mtm, &shortcut_item);
+            }
+        }
+
+        #[method(sceneDidDisconnect:)]
+        fn did_disconnect(&self, scene: &UIScene) {
+            app_state::scene_did_disconnect(MainThreadMarker::from(self), scene);
+        }
+
+        #[method(sceneDidBecomeActive:)]
+        fn did_become_active(&self, _scene: &UIScene) {
+            app_state::scene_activation_changed(MainThreadMarker::from(self), true);
+        }
+
+        #[method(sceneWillResignActive:)]
+        fn will_resign_active(&self, _scene: &UIScene) {
+            app_state::scene_activation_changed(MainThreadMarker::from(self), false);
+        }
+
+        #[method(sceneWillEnterForeground:)]
+        fn will_enter_foreground(&self, scene: &UIScene) {
+            app_state::scene_will_enter_foreground(MainThreadMarker::from(self), scene);
+        }
+
+        #[method(sceneDidEnterBackground:)]
+        fn did_enter_background(&self, scene: &UIScene) {
+            app_state::scene_did_enter_background(MainThreadMarker::from(self), scene);
+        }
+
+        #[method(scene:openURLContexts:)]
+        fn open_url_contexts(&self, _scene: &UIScene, url_contexts: &NSSet<UIOpenURLContext>) {
+            let mtm = MainThreadMarker::from(self);
+            let urls = url_contexts.iter().map(|context| unsafe { context.URL() }).collect();
+            app_state::urls_opened(mtm, urls);
+        }
+
+        #[method(scene:continueUserActivity:)]
+        fn continue_user_activity(&self, _scene: &UIScene, user_activity: &NSUserActivity) {
+            app_state::continue_user_activity(MainThreadMarker::from(self), user_activity);
+        }
+    }
+
+    unsafe impl UIWindowSceneDelegate for SceneDelegate {
+        #[method(windowScene:performActionForShortcutItem:completionHandler:)]
+        fn perform_action_for_shortcut_item(
+            &self,
+            _window_scene: &UIWindowScene,
+            shortcut_item: &UIApplicationShortcutItem,
+            completion_handler: &Block<dyn Fn(Bool)>,
+        ) {
+            app_state::perform_shortcut_item(MainThreadMarker::from(self), shortcut_item);
+            completion_handler.call((Bool::YES,));
+        }
+    }
+);
@@ -0,0 +1,95 @@
+//! Positions and ranges in the text document of `WinitView`.
+//!
+//! Winit doesn't know the text the application is editing, so the document the view exposes
+//! through `UITextInput` only consists of the text that is currently being composed. Positions
+//! are offsets into that text, counted in UTF-16 code units like `NSString` does.
+
+use objc2::rc::Retained;
+use objc2::runtime::NSObjectProtocol;
+use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
+use objc2_foundation::{MainThreadMarker, NSObject, NSRange};
+use objc2_ui_kit::{UITextPosition, UITextRange};
+
+declare_class!(
+    struct WinitTextPosition;
+
+    unsafe impl ClassType for WinitTextPosition {
+        #[inherits(NSObject)]
+        type Super = UITextPosition;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitTextPosition";
+    }
+
+    impl DeclaredClass for WinitTextPosition {
+        type Ivars = usize;
+    }
+);
+
+declare_class!(
+    struct WinitTextRange;
+
+    unsafe impl ClassType for WinitTextRange {
+        #[inherits(NSObject)]
+        type Super = UITextRange;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitTextRange";
+    }
+
+    impl DeclaredClass for WinitTextRange {
+        type Ivars = NSRange;
+    }
+
+    unsafe impl WinitTextRange {
+        #[method(isEmpty)]
+        fn is_empty(&self) -> bool {
+            self.ivars().length == 0
+        }
+
+        #[method_id(start)]
+        fn start(&self) -> Retained<UITextPosition> {
+            text_position(MainThreadMarker::from(self), self.ivars().location)
+        }
+
+        #[method_id(end)]
+        fn end(&self) -> Retained<UITextPosition> {
+            text_position(MainThreadMarker::from(self), self.ivars().end())
+        }
+    }
+);
+
+/// Create the position at `offset`.
+pub(crate) fn text_position(mtm: MainThreadMarker, offset: usize) -> Retained<UITextPosition> {
+    let this = mtm.alloc::<WinitTextPosition>().set_ivars(offset);
+    let this: Retained<WinitTextPosition> = unsafe { msg_send_id![super(this), init] };
+    Retained::into_super(this)
+}
+
+/// Create the range between the offsets `a` and `b`, in either order.
+pub(crate) fn text_range(mtm: MainThreadMarker, a: usize, b: usize) -> Retained<UITextRange> {
+    let range = NSRange::new(a.min(b), a.abs_diff(b));
+    let this = mtm.alloc::<WinitTextRange>().set_ivars(range);
+    let this: Retained<WinitTextRange> = unsafe { msg_send_id![super(this), init] };
+    Retained::into_super(this)
+}
+
+/// The offset of a position created by [`text_position`].
+pub(crate) fn position_offset(position: &UITextPosition) -> usize {
+    if position.is_kind_of::<WinitTextPosition>() {
+        // SAFETY: We just checked that the position is a `WinitTextPosition`
+        let position = unsafe { &*(position as *const UITextPosition).cast::<WinitTextPosition>() };
+        *position.ivars()
+    } else {
+        0
+    }
+}
+
+/// The offsets of a range created by [`text_range`].
+pub(crate) fn range_offsets(range: &UITextRange) -> NSRange {
+    if range.is_kind_of::<WinitTextRange>() {
+        // SAFETY: We just checked that the range is a `WinitTextRange`
+        let range = unsafe { &*(range as *const UITextRange).cast::<WinitTextRange>() };
+        *range.ivars()
+    } else {
+        NSRange::new(0, 0)
+    }
+}
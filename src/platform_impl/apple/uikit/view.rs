@@ -1,37 +1,124 @@
 #![allow(clippy::unnecessary_cast)]
 use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::time::{Duration, Instant};
 
-use objc2::rc::Retained;
-use objc2::runtime::{NSObjectProtocol, ProtocolObject};
+use objc2::rc::{Retained, WeakId};
+use objc2::runtime::{AnyObject, NSObjectProtocol, ProtocolObject};
 use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
-use objc2_foundation::{CGFloat, CGPoint, CGRect, MainThreadMarker, NSObject, NSSet};
+use objc2_foundation::{
+    CGFloat, CGPoint, CGRect, CGSize, MainThreadMarker, NSArray, NSAttributedStringKey,
+    NSComparisonResult, NSDictionary, NSInteger, NSMutableString, NSObject, NSRange, NSRunLoop,
+    NSRunLoopCommonModes, NSSet, NSString,
+};
+use objc2_quartz_core::{CACurrentMediaTime, CADisplayLink, CAFrameRateRange};
 use objc2_ui_kit::{
-    UICoordinateSpace, UIEvent, UIForceTouchCapability, UIGestureRecognizer,
-    UIGestureRecognizerDelegate, UIGestureRecognizerState, UIPanGestureRecognizer,
-    UIPinchGestureRecognizer, UIResponder, UIRotationGestureRecognizer, UITapGestureRecognizer,
-    UITouch, UITouchPhase, UITouchType, UITraitEnvironment, UIView,
+    NSWritingDirection, UIAxis, UIContentSizeCategory,
+    UIContentSizeCategoryAccessibilityExtraExtraExtraLarge,
+    UIContentSizeCategoryAccessibilityExtraExtraLarge,
+    UIContentSizeCategoryAccessibilityExtraLarge, UIContentSizeCategoryAccessibilityLarge,
+    UIContentSizeCategoryAccessibilityMedium, UIContentSizeCategoryExtraExtraExtraLarge,
+    UIContentSizeCategoryExtraExtraLarge, UIContentSizeCategoryExtraLarge,
+    UIContentSizeCategoryExtraSmall, UIContentSizeCategoryLarge, UIContentSizeCategoryMedium,
+    UIContentSizeCategorySmall, UICoordinateSpace, UIEvent, UIEventButtonMask,
+    UIForceTouchCapability, UIGestureRecognizer, UIGestureRecognizerDelegate,
+    UIGestureRecognizerState, UIHoverGestureRecognizer, UIInterfaceOrientation, UIKey, UIKeyInput,
+    UIKeyboardType, UIPanGestureRecognizer, UIPinchGestureRecognizer, UIPointerInteraction,
+    UIPointerInteractionDelegate, UIPointerRegion, UIPointerShape, UIPointerStyle, UIPress,
+    UIPressesEvent, UIResponder, UIRotationGestureRecognizer, UIScreen, UIScrollTypeMask,
+    UIScrollView, UIScrollViewDelegate, UITapGestureRecognizer, UITextAutocapitalizationType,
+    UITextAutocorrectionType, UITextInput, UITextInputDelegate, UITextInputStringTokenizer,
+    UITextInputTokenizer, UITextInputTraits, UITextLayoutDirection, UITextPosition, UITextRange,
+    UITextSelectionRect, UITextSpellCheckingType, UITextStorageDirection, UITouch, UITouchPhase,
+    UITouchType, UITraitCollection, UITraitEnvironment, UIUserInterfaceStyle, UIView,
 };
 
 use super::app_state::{self, EventWrapper};
-use super::window::WinitUIWindow;
-use super::DEVICE_ID;
-use crate::dpi::PhysicalPosition;
-use crate::event::{Event, Force, Touch, TouchPhase, WindowEvent};
-use crate::window::{WindowAttributes, WindowId as RootWindowId};
+use super::text_input::{position_offset, range_offsets, text_position, text_range};
+use super::view_controller::interface_orientation;
+use super::window::{WindowId, WinitUIWindow};
+use super::{keyboard, monitor, DEVICE_ID};
+use crate::dpi::{LogicalInsets, PhysicalInsets, PhysicalPosition};
+use crate::event::{
+    ElementState, Event, Force, HoverPhase, Ime, KeyEvent, Modifiers, MouseButton,
+    MouseScrollDelta, PresentationFeedback, Touch, TouchPhase, TouchTool, WindowEvent,
+};
+use crate::keyboard::{KeyCode, NamedKey, PhysicalKey};
+use crate::utils::RedrawDamage;
+use crate::window::{CursorIcon, ImePurpose, Theme, WindowAttributes, WindowId as RootWindowId};
 
 pub struct WinitViewState {
     pinch_gesture_recognizer: RefCell<Option<Retained<UIPinchGestureRecognizer>>>,
     doubletap_gesture_recognizer: RefCell<Option<Retained<UITapGestureRecognizer>>>,
     rotation_gesture_recognizer: RefCell<Option<Retained<UIRotationGestureRecognizer>>>,
     pan_gesture_recognizer: RefCell<Option<Retained<UIPanGestureRecognizer>>>,
+    hover_gesture_recognizer: RefCell<Option<Retained<UIHoverGestureRecognizer>>>,
+    pointer_hover_gesture_recognizer: RefCell<Option<Retained<UIHoverGestureRecognizer>>>,
+    scroll_gesture_recognizer: RefCell<Option<Retained<UIPanGestureRecognizer>>>,
+    pointer_interaction: RefCell<Option<Retained<UIPointerInteraction>>>,
+
+    // whether the hover currently tracked by the hover gesture recognizer is a stylus
+    stylus_hovering: Cell<bool>,
+    // whether the hover currently tracked by the pointer hover gesture recognizer is a pointer
+    pointer_hovering: Cell<bool>,
+    // the buttons of the clicks of a trackpad or mouse that are in progress, by touch
+    pointer_buttons: RefCell<HashMap<u64, MouseButton>>,
+    cursor_icon: Cell<CursorIcon>,
+    cursor_visible: Cell<bool>,
+    // whether to deliver coalesced and predicted touches
+    coalesced_touches: Cell<bool>,
 
     // for iOS delta references the start of the Gesture
     rotation_last_delta: Cell<CGFloat>,
     pinch_last_delta: Cell<CGFloat>,
     pan_last_delta: Cell<CGPoint>,
+    scroll_last_translation: Cell<CGPoint>,
+
+    display_link: RefCell<Option<Retained<CADisplayLink>>>,
+    preferred_frame_rate_range: Cell<Option<CAFrameRateRange>>,
+
+    // times the presentation of frames while presentation feedback is enabled
+    presentation_link: RefCell<Option<Retained<CADisplayLink>>>,
+    presentation_feedback: Cell<bool>,
+    // the number of `pre_present_notify` calls, and those waiting for the next frame
+    present_generation: Cell<u64>,
+    pending_presents: RefCell<Vec<u64>>,
+    // the generation and the target timestamp of the last presented frame
+    last_presented: Cell<Option<(u64, f64)>>,
+    // the damage of the requested redraws
+    redraw_damage: RefCell<RedrawDamage>,
+
+    interface_orientation: Cell<UIInterfaceOrientation>,
+    safe_area_insets: Cell<Option<PhysicalInsets<u32>>>,
+    edr_headroom: Cell<Option<f32>>,
+
+    modifiers: Cell<Modifiers>,
+    pressed_keys: RefCell<HashSet<PhysicalKey>>,
+
+    ime_allowed: Cell<bool>,
+    ime_purpose: Cell<ImePurpose>,
+    // in the coordinate space of the view
+    ime_cursor_area: Cell<CGRect>,
+    // the text that is currently being composed, the view's whole `UITextInput` document
+    marked_text: RefCell<Retained<NSMutableString>>,
+    // the selection within the marked text
+    selected_range: Cell<NSRange>,
+    input_delegate: RefCell<Option<Retained<ProtocolObject<dyn UITextInputDelegate>>>>,
+    tokenizer: RefCell<Option<Retained<UITextInputStringTokenizer>>>,
+
+    // never scrolled, only used to be notified of taps on the status bar
+    status_bar_scroll_view: RefCell<Option<Retained<UIScrollView>>>,
+
+    // whether the view was embedded into a view of the application instead of its own window
+    embedded: bool,
+    // Weak reference because the window keeps a strong reference to the view, `None` for
+    // embedded views
+    ui_window: RefCell<Option<WeakId<WinitUIWindow>>>,
 }
 
 declare_class!(
+    #[derive(Debug)]
     pub(crate) struct WinitView;
 
     unsafe impl ClassType for WinitView {
@@ -49,14 +136,7 @@ declare_class!(
         #[method(drawRect:)]
         fn draw_rect(&self, rect: CGRect) {
             let mtm = MainThreadMarker::new().unwrap();
-            let window = self.window().unwrap();
-            app_state::handle_nonuser_event(
-                mtm,
-                EventWrapper::StaticEvent(Event::WindowEvent {
-                    window_id: RootWindowId(window.id()),
-                    event: WindowEvent::RedrawRequested,
-                }),
-            );
+            app_state::handle_nonuser_event(mtm, self.redraw_event());
             let _: () = unsafe { msg_send![super(self), drawRect: rect] };
         }
 
@@ -65,8 +145,14 @@ declare_class!(
             let mtm = MainThreadMarker::new().unwrap();
             let _: () = unsafe { msg_send![super(self), layoutSubviews] };
 
-            let window = self.window().unwrap();
-            let window_bounds = window.bounds();
+            let Some(window) = self.window() else {
+                // Embedded views may be laid out before the host adds them to a window.
+                return;
+            };
+            // Embedded views are sized by the host application, other views cover their window.
+            let embedded = self.ivars().embedded;
+            let outer_view: &UIView = if embedded { self } else { &window };
+            let window_bounds = outer_view.bounds();
             let screen = window.screen();
             let screen_space = screen.coordinateSpace();
             let screen_frame = self.convertRect_toCoordinateSpace(window_bounds, &screen_space);
@@ -81,16 +167,171 @@ declare_class!(
             // The view frame will be in portrait and the window bounds in landscape. So apply the
             // window bounds to the view frame to make it consistent.
             let view_frame = self.frame();
-            if view_frame != window_bounds {
+            if !embedded && view_frame != window_bounds {
                 self.setFrame(window_bounds);
             }
 
-            app_state::handle_nonuser_event(
+            if let Some(scroll_view) = &*self.ivars().status_bar_scroll_view.borrow() {
+                let width = self.bounds().size.width;
+                scroll_view.setFrame(CGRect::new(CGPoint::ZERO, CGSize { width, height: 0.0 }));
+            }
+
+            // Report orientation changes together with the resize caused by them.
+            #[allow(deprecated)]
+            let orientation = window
+                .rootViewController()
+                .map(|controller| unsafe { controller.interfaceOrientation() })
+                .unwrap_or(UIInterfaceOrientation::Unknown);
+            let previous_orientation = self.ivars().interface_orientation.replace(orientation);
+            let orientation_changed = previous_orientation != UIInterfaceOrientation::Unknown
+                && previous_orientation != orientation;
+
+            let window_id = RootWindowId(self.id());
+            let orientation_event = interface_orientation(orientation)
+                .filter(|_| orientation_changed)
+                .map(|orientation| {
+                    EventWrapper::StaticEvent(Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::InterfaceOrientationChanged(orientation),
+                    })
+                });
+            let resized_event = EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Resized(size),
+            });
+
+            // `safeAreaInsetsDidChange` requests a layout, so changes of the safe area that
+            // don't resize the window are also reported here, at most once per frame.
+            let safe_area_insets = app_state::os_capabilities().safe_area.then(|| {
+                let insets = outer_view.safeAreaInsets();
+                LogicalInsets::new(insets.top, insets.left, insets.bottom, insets.right)
+                    .to_physical(scale_factor as f64)
+            });
+            let previous_safe_area_insets =
+                self.ivars().safe_area_insets.replace(safe_area_insets);
+            let safe_area_event = safe_area_insets
+                .filter(|_| previous_safe_area_insets.is_some())
+                .filter(|insets| previous_safe_area_insets != Some(*insets))
+                .map(|insets| {
+                    EventWrapper::StaticEvent(Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::SafeAreaChanged(insets),
+                    })
+                });
+
+            app_state::handle_nonuser_events(
                 mtm,
+                orientation_event
+                    .into_iter()
+                    .chain(std::iter::once(resized_event))
+                    .chain(safe_area_event),
+            );
+
+            // The view is laid out again when the window moves to another screen.
+            self.update_edr_headroom();
+        }
+
+        #[method(didMoveToWindow)]
+        fn did_move_to_window(&self) {
+            let _: () = unsafe { msg_send![super(self), didMoveToWindow] };
+            self.update_display_link();
+            self.update_presentation_link();
+        }
+
+        #[method(displayLinkDidFire:)]
+        fn display_link_did_fire(&self, _display_link: &CADisplayLink) {
+            let mtm = MainThreadMarker::new().unwrap();
+            if self.window().is_some() {
+                app_state::handle_nonuser_event(mtm, self.redraw_event());
+            }
+        }
+
+        #[method(presentationLinkDidFire:)]
+        fn presentation_link_did_fire(&self, display_link: &CADisplayLink) {
+            let mtm = MainThreadMarker::new().unwrap();
+            let generations = mem::take(&mut *self.ivars().pending_presents.borrow_mut());
+            unsafe { display_link.setPaused(true) };
+
+            // The frames committed since the last frame are shown at the next one, where only
+            // the last of them is visible.
+            let (timestamp, target_timestamp) =
+                unsafe { (display_link.timestamp(), display_link.targetTimestamp()) };
+            let refresh = target_timestamp - timestamp;
+            let Some(&last) = generations.last() else {
+                return;
+            };
+            let previous = self.ivars().last_presented.replace(Some((last, target_timestamp)));
+            let delayed = previous.is_some_and(|(generation, previous_timestamp)| {
+                generation + 1 == last && target_timestamp - previous_timestamp > refresh * 1.5
+            });
+
+            let window_id = RootWindowId(self.id());
+            let events = generations.into_iter().map(|generation| {
+                let presented = generation == last;
+                let feedback = PresentationFeedback {
+                    generation,
+                    presentation_time: media_time_to_instant(target_timestamp)
+                        .filter(|_| presented),
+                    refresh_interval: (refresh > 0.0)
+                        .then(|| Duration::from_secs_f64(refresh))
+                        .filter(|_| presented),
+                    missed: !presented || delayed,
+                };
                 EventWrapper::StaticEvent(Event::WindowEvent {
-                    window_id: RootWindowId(window.id()),
-                    event: WindowEvent::Resized(size),
-                }),
+                    window_id,
+                    event: WindowEvent::FramePresented(feedback),
+                })
+            });
+            app_state::handle_nonuser_events(mtm, events);
+        }
+
+        #[method(safeAreaInsetsDidChange)]
+        fn safe_area_insets_did_change(&self) {
+            let _: () = unsafe { msg_send![super(self), safeAreaInsetsDidChange] };
+            // Trigger `layoutSubviews`, so that the application is notified of the new safe area,
+            // e.g. after the status bar was hidden.
+            unsafe { self.setNeedsLayout() };
+        }
+
+        #[method(traitCollectionDidChange:)]
+        fn trait_collection_did_change(&self, previous: Option<&UITraitCollection>) {
+            let mtm = MainThreadMarker::new().unwrap();
+            let _: () = unsafe { msg_send![super(self), traitCollectionDidChange: previous] };
+
+            // Nothing to compare against when the view is first added to a window.
+            let (Some(window), Some(previous)) = (self.window(), previous) else {
+                return;
+            };
+            let outer_view: &UIView = if self.ivars().embedded { self } else { &window };
+            let window_id = RootWindowId(self.id());
+            let current = self.traitCollection();
+
+            // Only windows following the system report theme changes, like on other platforms.
+            let style = unsafe { current.userInterfaceStyle() };
+            let theme_event = (app_state::os_capabilities().user_interface_style
+                && style != unsafe { previous.userInterfaceStyle() }
+                && unsafe { outer_view.overrideUserInterfaceStyle() }
+                    == UIUserInterfaceStyle::Unspecified)
+                .then(|| {
+                    EventWrapper::StaticEvent(Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::ThemeChanged(theme(style)),
+                    })
+                });
+
+            let category = unsafe { current.preferredContentSizeCategory() };
+            let text_scale_factor_event = (category
+                != unsafe { previous.preferredContentSizeCategory() })
+            .then(|| {
+                EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::TextScaleFactorChanged(text_scale_factor(&category)),
+                })
+            });
+
+            app_state::handle_nonuser_events(
+                mtm,
+                theme_event.into_iter().chain(text_scale_factor_event),
             );
         }
 
@@ -127,12 +368,12 @@ declare_class!(
                 width: screen_frame.size.width as f64,
                 height: screen_frame.size.height as f64,
             };
-            let window_id = RootWindowId(window.id());
+            let window_id = RootWindowId(self.id());
             app_state::handle_nonuser_events(
                 mtm,
                 std::iter::once(EventWrapper::ScaleFactorChanged(
                     app_state::ScaleFactorChanged {
-                        window,
+                        view: self.retain(),
                         scale_factor,
                         suggested_size: size.to_physical(scale_factor),
                     },
@@ -147,29 +388,66 @@ declare_class!(
         }
 
         #[method(touchesBegan:withEvent:)]
-        fn touches_began(&self, touches: &NSSet<UITouch>, _event: Option<&UIEvent>) {
-            self.handle_touches(touches)
+        fn touches_began(&self, touches: &NSSet<UITouch>, event: Option<&UIEvent>) {
+            self.handle_touches(touches, event)
         }
 
         #[method(touchesMoved:withEvent:)]
-        fn touches_moved(&self, touches: &NSSet<UITouch>, _event: Option<&UIEvent>) {
-            self.handle_touches(touches)
+        fn touches_moved(&self, touches: &NSSet<UITouch>, event: Option<&UIEvent>) {
+            self.handle_touches(touches, event)
         }
 
         #[method(touchesEnded:withEvent:)]
-        fn touches_ended(&self, touches: &NSSet<UITouch>, _event: Option<&UIEvent>) {
-            self.handle_touches(touches)
+        fn touches_ended(&self, touches: &NSSet<UITouch>, event: Option<&UIEvent>) {
+            self.handle_touches(touches, event)
         }
 
         #[method(touchesCancelled:withEvent:)]
-        fn touches_cancelled(&self, touches: &NSSet<UITouch>, _event: Option<&UIEvent>) {
-            self.handle_touches(touches)
+        fn touches_cancelled(&self, touches: &NSSet<UITouch>, event: Option<&UIEvent>) {
+            self.handle_touches(touches, event)
+        }
+
+        #[method(canBecomeFirstResponder)]
+        fn can_become_first_responder(&self) -> bool {
+            true
+        }
+
+        #[method_id(inputView)]
+        fn input_view(&self) -> Option<Retained<UIView>> {
+            if self.ivars().ime_allowed.get() {
+                // Show the system keyboard.
+                None
+            } else {
+                // An empty input view keeps the on-screen keyboard hidden while the view is the
+                // first responder for hardware keyboard presses.
+                let mtm = MainThreadMarker::from(self);
+                Some(UIView::initWithFrame(mtm.alloc(), CGRect::ZERO))
+            }
+        }
+
+        #[method(pressesBegan:withEvent:)]
+        fn presses_began(&self, presses: &NSSet<UIPress>, event: Option<&UIPressesEvent>) {
+            if !self.handle_presses(presses, ElementState::Pressed) {
+                let _: () = unsafe { msg_send![super(self), pressesBegan: presses, withEvent: event] };
+            }
+        }
+
+        #[method(pressesEnded:withEvent:)]
+        fn presses_ended(&self, presses: &NSSet<UIPress>, event: Option<&UIPressesEvent>) {
+            if !self.handle_presses(presses, ElementState::Released) {
+                let _: () = unsafe { msg_send![super(self), pressesEnded: presses, withEvent: event] };
+            }
+        }
+
+        #[method(pressesCancelled:withEvent:)]
+        fn presses_cancelled(&self, presses: &NSSet<UIPress>, event: Option<&UIPressesEvent>) {
+            if !self.handle_presses(presses, ElementState::Released) {
+                let _: () = unsafe { msg_send![super(self), pressesCancelled: presses, withEvent: event] };
+            }
         }
 
         #[method(pinchGesture:)]
         fn pinch_gesture(&self, recognizer: &UIPinchGestureRecognizer) {
-            let window = self.window().unwrap();
-
             let (phase, delta) = match recognizer.state() {
                 UIGestureRecognizerState::Began => {
                     self.ivars().pinch_last_delta.set(recognizer.scale());
@@ -192,7 +470,7 @@ declare_class!(
             };
 
             let gesture_event = EventWrapper::StaticEvent(Event::WindowEvent {
-                window_id: RootWindowId(window.id()),
+                window_id: RootWindowId(self.id()),
                 event: WindowEvent::PinchGesture {
                     device_id: DEVICE_ID,
                     delta: delta as f64,
@@ -206,11 +484,9 @@ declare_class!(
 
         #[method(doubleTapGesture:)]
         fn double_tap_gesture(&self, recognizer: &UITapGestureRecognizer) {
-            let window = self.window().unwrap();
-
             if recognizer.state() == UIGestureRecognizerState::Ended {
                 let gesture_event = EventWrapper::StaticEvent(Event::WindowEvent {
-                    window_id: RootWindowId(window.id()),
+                    window_id: RootWindowId(self.id()),
                     event: WindowEvent::DoubleTapGesture {
                         device_id: DEVICE_ID,
                     },
@@ -221,10 +497,134 @@ declare_class!(
             }
         }
 
+        #[method(hoverGesture:)]
+        fn hover_gesture(&self, recognizer: &UIHoverGestureRecognizer) {
+            let phase = match recognizer.state() {
+                UIGestureRecognizerState::Began => {
+                    // The recognizer also tracks the pointer of a trackpad or mouse, which
+                    // doesn't hover above the screen.
+                    self.ivars().stylus_hovering.set(unsafe { recognizer.zOffset() } > 0.0);
+                    HoverPhase::Entered
+                }
+                UIGestureRecognizerState::Changed => HoverPhase::Moved,
+                UIGestureRecognizerState::Ended
+                | UIGestureRecognizerState::Cancelled
+                | UIGestureRecognizerState::Failed => HoverPhase::Exited,
+                state => panic!("unexpected recognizer state: {:?}", state),
+            };
+
+            if !self.ivars().stylus_hovering.get() {
+                return;
+            }
+            if phase == HoverPhase::Exited {
+                self.ivars().stylus_hovering.set(false);
+            }
+
+            let logical_location = unsafe { recognizer.locationInView(None) };
+            let location = PhysicalPosition::from_logical::<(f64, f64), f64>(
+                (logical_location.x as _, logical_location.y as _),
+                self.contentScaleFactor() as f64,
+            );
+            let (altitude_angle, azimuth_angle) = if app_state::os_capabilities().stylus_hover_pose {
+                unsafe {
+                    (
+                        Some(recognizer.altitudeAngle() as f64),
+                        Some(recognizer.azimuthAngleInView(Some(self)) as f64),
+                    )
+                }
+            } else {
+                (None, None)
+            };
+
+            let hover_event = EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id: RootWindowId(self.id()),
+                event: WindowEvent::StylusHover {
+                    device_id: DEVICE_ID,
+                    phase,
+                    location,
+                    altitude_angle,
+                    azimuth_angle,
+                },
+            });
+
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::handle_nonuser_event(mtm, hover_event);
+        }
+
+        #[method(pointerHoverGesture:)]
+        fn pointer_hover_gesture(&self, recognizer: &UIHoverGestureRecognizer) {
+            let state = recognizer.state();
+            if state == UIGestureRecognizerState::Began {
+                // A hovering stylus is reported by `hoverGesture:` instead.
+                let os_capabilities = app_state::os_capabilities();
+                let is_stylus = os_capabilities.stylus_hover && unsafe { recognizer.zOffset() } > 0.0;
+                self.ivars().pointer_hovering.set(!is_stylus);
+            }
+            if !self.ivars().pointer_hovering.get() {
+                return;
+            }
+
+            let location = unsafe { recognizer.locationInView(Some(self)) };
+            let cursor_moved = WindowEvent::CursorMoved {
+                device_id: DEVICE_ID,
+                position: self.physical_position(location),
+            };
+            let events = match state {
+                UIGestureRecognizerState::Began => vec![
+                    WindowEvent::CursorEntered { device_id: DEVICE_ID },
+                    cursor_moved,
+                ],
+                UIGestureRecognizerState::Changed => vec![cursor_moved],
+                UIGestureRecognizerState::Ended
+                | UIGestureRecognizerState::Cancelled
+                | UIGestureRecognizerState::Failed => {
+                    self.ivars().pointer_hovering.set(false);
+                    vec![WindowEvent::CursorLeft { device_id: DEVICE_ID }]
+                }
+                state => panic!("unexpected recognizer state: {:?}", state),
+            };
+
+            self.handle_window_events(events);
+        }
+
+        #[method(scrollGesture:)]
+        fn scroll_gesture(&self, recognizer: &UIPanGestureRecognizer) {
+            let translation = recognizer.translationInView(Some(self));
+            let last_translation = self.ivars().scroll_last_translation.replace(translation);
+            let (phase, dx, dy) = match recognizer.state() {
+                UIGestureRecognizerState::Began => {
+                    (TouchPhase::Started, translation.x, translation.y)
+                }
+                UIGestureRecognizerState::Changed => {
+                    (TouchPhase::Moved, translation.x - last_translation.x, translation.y - last_translation.y)
+                }
+                UIGestureRecognizerState::Ended => {
+                    (TouchPhase::Ended, translation.x - last_translation.x, translation.y - last_translation.y)
+                }
+                UIGestureRecognizerState::Cancelled | UIGestureRecognizerState::Failed => {
+                    (TouchPhase::Cancelled, 0.0, 0.0)
+                }
+                state => panic!("unexpected recognizer state: {:?}", state),
+            };
+
+            // The content follows the fingers, like with natural scrolling on macOS.
+            let delta = self.physical_position(CGPoint { x: dx, y: dy });
+            let scroll_event = EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id: RootWindowId(self.id()),
+                event: WindowEvent::MouseWheel {
+                    device_id: DEVICE_ID,
+                    delta: MouseScrollDelta::PixelDelta(delta),
+                    phase,
+                    momentum_phase: None,
+                },
+            });
+
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::handle_nonuser_event(mtm, scroll_event);
+        }
+
         #[method(rotationGesture:)]
         fn rotation_gesture(&self, recognizer: &UIRotationGestureRecognizer) {
-            let window = self.window().unwrap();
-
             let (phase, delta) = match recognizer.state() {
                 UIGestureRecognizerState::Began => {
                     self.ivars().rotation_last_delta.set(0.0);
@@ -252,7 +652,7 @@ declare_class!(
 
             // Make delta negative to match macos, convert to degrees
             let gesture_event = EventWrapper::StaticEvent(Event::WindowEvent {
-                window_id: RootWindowId(window.id()),
+                window_id: RootWindowId(self.id()),
                 event: WindowEvent::RotationGesture {
                     device_id: DEVICE_ID,
                     delta: -delta.to_degrees() as _,
@@ -266,8 +666,6 @@ declare_class!(
 
         #[method(panGesture:)]
         fn pan_gesture(&self, recognizer: &UIPanGestureRecognizer) {
-            let window = self.window().unwrap();
-
             let translation = recognizer.translationInView(Some(self));
 
             let (phase, dx, dy) = match recognizer.state() {
@@ -303,7 +701,7 @@ declare_class!(
 
 
             let gesture_event = EventWrapper::StaticEvent(Event::WindowEvent {
-                window_id: RootWindowId(window.id()),
+                window_id: RootWindowId(self.id()),
                 event: WindowEvent::PanGesture {
                     device_id: DEVICE_ID,
                     delta: PhysicalPosition::new(dx as _, dy as _),
@@ -324,6 +722,367 @@ declare_class!(
             true
         }
     }
+
+    unsafe impl UIPointerInteractionDelegate for WinitView {
+        #[method_id(pointerInteraction:styleForRegion:)]
+        fn pointer_style_for_region(&self, _interaction: &UIPointerInteraction, _region: &UIPointerRegion) -> Option<Retained<UIPointerStyle>> {
+            self.pointer_style()
+        }
+    }
+
+    unsafe impl UIScrollViewDelegate for WinitView {
+        #[method(scrollViewShouldScrollToTop:)]
+        fn scroll_view_should_scroll_to_top(&self, _scroll_view: &UIScrollView) -> bool {
+            let mtm = MainThreadMarker::new().unwrap();
+            if let Some(window) = self.window() {
+                #[allow(deprecated)]
+                let main_screen = UIScreen::mainScreen(mtm);
+                if window.isKeyWindow() && window.screen() == main_screen {
+                    app_state::handle_nonuser_event(
+                        mtm,
+                        EventWrapper::StaticEvent(Event::WindowEvent {
+                            window_id: RootWindowId(self.id()),
+                            event: WindowEvent::StatusBarTapped,
+                        }),
+                    );
+                }
+            }
+            false
+        }
+    }
+
+    unsafe impl UITextInputTraits for WinitView {
+        #[method(keyboardType)]
+        fn keyboard_type(&self) -> UIKeyboardType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Terminal => UIKeyboardType::ASCIICapable,
+                ImePurpose::Email => UIKeyboardType::EmailAddress,
+                ImePurpose::Number => UIKeyboardType::NumberPad,
+                _ => UIKeyboardType::Default,
+            }
+        }
+
+        #[method(isSecureTextEntry)]
+        fn is_secure_text_entry(&self) -> bool {
+            self.ivars().ime_purpose.get() == ImePurpose::Password
+        }
+
+        #[method(autocapitalizationType)]
+        fn autocapitalization_type(&self) -> UITextAutocapitalizationType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal => UITextAutocapitalizationType::Sentences,
+                _ => UITextAutocapitalizationType::None,
+            }
+        }
+
+        #[method(autocorrectionType)]
+        fn autocorrection_type(&self) -> UITextAutocorrectionType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal => UITextAutocorrectionType::Default,
+                _ => UITextAutocorrectionType::No,
+            }
+        }
+
+        #[method(spellCheckingType)]
+        fn spell_checking_type(&self) -> UITextSpellCheckingType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal => UITextSpellCheckingType::Default,
+                _ => UITextSpellCheckingType::No,
+            }
+        }
+    }
+
+    unsafe impl UIKeyInput for WinitView {
+        #[method(hasText)]
+        fn has_text(&self) -> bool {
+            self.is_composing()
+        }
+
+        #[method(insertText:)]
+        fn insert_text(&self, text: &NSString) {
+            let mut events = Vec::new();
+            // The text replaces the marked text.
+            if self.clear_marked_text() {
+                events.push(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            }
+            let text = text.to_string();
+            if text == "\n" {
+                // The return key of the on-screen keyboard is only reported as text, while the
+                // press of a hardware key was already reported.
+                if !self.is_key_pressed(KeyCode::Enter) && !self.is_key_pressed(KeyCode::NumpadEnter) {
+                    events.extend(on_screen_key_events(NamedKey::Enter, KeyCode::Enter));
+                }
+            } else if !text.is_empty() {
+                events.push(WindowEvent::Ime(Ime::Commit(text)));
+            }
+            self.handle_window_events(events);
+        }
+
+        #[method(deleteBackward)]
+        fn delete_backward(&self) {
+            if self.is_composing() {
+                let selected_range = self.ivars().selected_range.get();
+                let range = if selected_range.length > 0 {
+                    selected_range
+                } else if selected_range.location > 0 {
+                    unsafe {
+                        self.ivars()
+                            .marked_text
+                            .borrow()
+                            .rangeOfComposedCharacterSequenceAtIndex(selected_range.location - 1)
+                    }
+                } else {
+                    return;
+                };
+                unsafe { self.ivars().marked_text.borrow_mut().deleteCharactersInRange(range) };
+                self.ivars().selected_range.set(NSRange::new(range.location, 0));
+                self.handle_window_events([self.preedit_event()]);
+            } else if !self.is_key_pressed(KeyCode::Backspace) {
+                // Same as for the return key, only the on-screen keyboard needs to be reported.
+                self.handle_window_events(on_screen_key_events(NamedKey::Backspace, KeyCode::Backspace));
+            }
+        }
+    }
+
+    unsafe impl UITextInput for WinitView {
+        #[method_id(textInRange:)]
+        fn text_in_range(&self, range: &UITextRange) -> Option<Retained<NSString>> {
+            let range = self.clamp_range(range_offsets(range));
+            Some(unsafe { self.ivars().marked_text.borrow().substringWithRange(range) })
+        }
+
+        #[method(replaceRange:withText:)]
+        fn replace_range_with_text(&self, range: &UITextRange, text: &NSString) {
+            if self.is_composing() {
+                let range = self.clamp_range(range_offsets(range));
+                unsafe {
+                    self.ivars().marked_text.borrow_mut().replaceCharactersInRange_withString(range, text)
+                };
+                self.ivars().selected_range.set(NSRange::new(range.location + text.length(), 0));
+                self.handle_window_events([self.preedit_event()]);
+            } else if text.length() > 0 {
+                self.handle_window_events([WindowEvent::Ime(Ime::Commit(text.to_string()))]);
+            }
+        }
+
+        #[method_id(selectedTextRange)]
+        fn selected_text_range(&self) -> Option<Retained<UITextRange>> {
+            let selected_range = self.ivars().selected_range.get();
+            Some(text_range(
+                MainThreadMarker::from(self),
+                selected_range.location,
+                selected_range.end(),
+            ))
+        }
+
+        #[method(setSelectedTextRange:)]
+        fn set_selected_text_range(&self, range: Option<&UITextRange>) {
+            let Some(range) = range else {
+                return;
+            };
+            if self.is_composing() {
+                self.ivars().selected_range.set(self.clamp_range(range_offsets(range)));
+                self.handle_window_events([self.preedit_event()]);
+            }
+        }
+
+        #[method_id(markedTextRange)]
+        fn marked_text_range(&self) -> Option<Retained<UITextRange>> {
+            self.is_composing()
+                .then(|| text_range(MainThreadMarker::from(self), 0, self.document_len()))
+        }
+
+        #[method_id(markedTextStyle)]
+        fn marked_text_style(&self) -> Option<Retained<NSDictionary<NSAttributedStringKey, AnyObject>>> {
+            None
+        }
+
+        #[method(setMarkedTextStyle:)]
+        fn set_marked_text_style(&self, _style: Option<&NSDictionary<NSAttributedStringKey, AnyObject>>) {}
+
+        #[method(setMarkedText:selectedRange:)]
+        fn set_marked_text(&self, marked_text: Option<&NSString>, selected_range: NSRange) {
+            let marked_text = NSMutableString::from_str(&marked_text.map(|text| text.to_string()).unwrap_or_default());
+            *self.ivars().marked_text.borrow_mut() = marked_text;
+            self.ivars().selected_range.set(self.clamp_range(selected_range));
+            self.handle_window_events([self.preedit_event()]);
+        }
+
+        #[method(unmarkText)]
+        fn unmark_text(&self) {
+            let text = self.ivars().marked_text.borrow().to_string();
+            if self.clear_marked_text() {
+                self.handle_window_events([
+                    WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                    WindowEvent::Ime(Ime::Commit(text)),
+                ]);
+            }
+        }
+
+        #[method_id(beginningOfDocument)]
+        fn beginning_of_document(&self) -> Retained<UITextPosition> {
+            text_position(MainThreadMarker::from(self), 0)
+        }
+
+        #[method_id(endOfDocument)]
+        fn end_of_document(&self) -> Retained<UITextPosition> {
+            text_position(MainThreadMarker::from(self), self.document_len())
+        }
+
+        #[method_id(textRangeFromPosition:toPosition:)]
+        fn text_range_from_position_to_position(
+            &self,
+            from_position: &UITextPosition,
+            to_position: &UITextPosition,
+        ) -> Option<Retained<UITextRange>> {
+            Some(text_range(
+                MainThreadMarker::from(self),
+                position_offset(from_position),
+                position_offset(to_position),
+            ))
+        }
+
+        #[method_id(positionFromPosition:offset:)]
+        fn position_from_position_offset(
+            &self,
+            position: &UITextPosition,
+            offset: NSInteger,
+        ) -> Option<Retained<UITextPosition>> {
+            self.offset_position(position, offset)
+        }
+
+        #[method_id(positionFromPosition:inDirection:offset:)]
+        fn position_from_position_in_direction_offset(
+            &self,
+            position: &UITextPosition,
+            direction: UITextLayoutDirection,
+            offset: NSInteger,
+        ) -> Option<Retained<UITextPosition>> {
+            // The document is a single line.
+            match direction {
+                UITextLayoutDirection::Left | UITextLayoutDirection::Up => {
+                    self.offset_position(position, -offset)
+                }
+                _ => self.offset_position(position, offset),
+            }
+        }
+
+        #[method(comparePosition:toPosition:)]
+        fn compare_position_to_position(
+            &self,
+            position: &UITextPosition,
+            other: &UITextPosition,
+        ) -> NSComparisonResult {
+            position_offset(position).cmp(&position_offset(other)).into()
+        }
+
+        #[method(offsetFromPosition:toPosition:)]
+        fn offset_from_position_to_position(&self, from: &UITextPosition, to_position: &UITextPosition) -> NSInteger {
+            position_offset(to_position) as NSInteger - position_offset(from) as NSInteger
+        }
+
+        #[method_id(inputDelegate)]
+        fn input_delegate(&self) -> Option<Retained<ProtocolObject<dyn UITextInputDelegate>>> {
+            let input_delegate = self.ivars().input_delegate.borrow();
+            input_delegate.as_deref().and_then(|input_delegate| unsafe {
+                Retained::retain(input_delegate as *const ProtocolObject<_> as *mut ProtocolObject<_>)
+            })
+        }
+
+        #[method(setInputDelegate:)]
+        fn set_input_delegate(&self, input_delegate: Option<&ProtocolObject<dyn UITextInputDelegate>>) {
+            // The delegate is owned by the text input system, which resets it when the view
+            // resigns first responder.
+            let input_delegate = input_delegate.and_then(|input_delegate| unsafe {
+                Retained::retain(input_delegate as *const ProtocolObject<_> as *mut ProtocolObject<_>)
+            });
+            *self.ivars().input_delegate.borrow_mut() = input_delegate;
+        }
+
+        #[method_id(tokenizer)]
+        fn tokenizer(&self) -> Retained<ProtocolObject<dyn UITextInputTokenizer>> {
+            let mut tokenizer = self.ivars().tokenizer.borrow_mut();
+            let tokenizer = tokenizer.get_or_insert_with(|| {
+                let mtm = MainThreadMarker::from(self);
+                unsafe { UITextInputStringTokenizer::initWithTextInput(mtm.alloc(), self) }
+            });
+            ProtocolObject::from_retained(tokenizer.clone())
+        }
+
+        #[method_id(positionWithinRange:farthestInDirection:)]
+        fn position_within_range_farthest_in_direction(
+            &self,
+            range: &UITextRange,
+            direction: UITextLayoutDirection,
+        ) -> Option<Retained<UITextPosition>> {
+            let range = range_offsets(range);
+            let offset = match direction {
+                UITextLayoutDirection::Left | UITextLayoutDirection::Up => range.location,
+                _ => range.end(),
+            };
+            Some(text_position(MainThreadMarker::from(self), offset))
+        }
+
+        #[method_id(characterRangeByExtendingPosition:inDirection:)]
+        fn character_range_by_extending_position_in_direction(
+            &self,
+            position: &UITextPosition,
+            direction: UITextLayoutDirection,
+        ) -> Option<Retained<UITextRange>> {
+            let offset = position_offset(position);
+            let other = match direction {
+                UITextLayoutDirection::Left | UITextLayoutDirection::Up => offset.saturating_sub(1),
+                _ => (offset + 1).min(self.document_len()),
+            };
+            Some(text_range(MainThreadMarker::from(self), offset, other))
+        }
+
+        #[method(baseWritingDirectionForPosition:inDirection:)]
+        fn base_writing_direction_for_position_in_direction(
+            &self,
+            _position: &UITextPosition,
+            _direction: UITextStorageDirection,
+        ) -> NSWritingDirection {
+            NSWritingDirection::Natural
+        }
+
+        #[method(setBaseWritingDirection:forRange:)]
+        fn set_base_writing_direction_for_range(&self, _writing_direction: NSWritingDirection, _range: &UITextRange) {}
+
+        #[method(firstRectForRange:)]
+        fn first_rect_for_range(&self, _range: &UITextRange) -> CGRect {
+            self.ivars().ime_cursor_area.get()
+        }
+
+        #[method(caretRectForPosition:)]
+        fn caret_rect_for_position(&self, _position: &UITextPosition) -> CGRect {
+            self.ivars().ime_cursor_area.get()
+        }
+
+        #[method_id(selectionRectsForRange:)]
+        fn selection_rects_for_range(&self, _range: &UITextRange) -> Retained<NSArray<UITextSelectionRect>> {
+            NSArray::new()
+        }
+
+        #[method_id(closestPositionToPoint:)]
+        fn closest_position_to_point(&self, _point: CGPoint) -> Option<Retained<UITextPosition>> {
+            Some(text_position(MainThreadMarker::from(self), self.document_len()))
+        }
+
+        #[method_id(closestPositionToPoint:withinRange:)]
+        fn closest_position_to_point_within_range(
+            &self,
+            _point: CGPoint,
+            range: &UITextRange,
+        ) -> Option<Retained<UITextPosition>> {
+            Some(text_position(MainThreadMarker::from(self), range_offsets(range).end()))
+        }
+
+        #[method_id(characterRangeAtPoint:)]
+        fn character_range_at_point(&self, _point: CGPoint) -> Option<Retained<UITextRange>> {
+            None
+        }
+    }
 );
 
 impl WinitView {
@@ -337,15 +1096,77 @@ impl WinitView {
             doubletap_gesture_recognizer: RefCell::new(None),
             rotation_gesture_recognizer: RefCell::new(None),
             pan_gesture_recognizer: RefCell::new(None),
+            hover_gesture_recognizer: RefCell::new(None),
+            pointer_hover_gesture_recognizer: RefCell::new(None),
+            scroll_gesture_recognizer: RefCell::new(None),
+            pointer_interaction: RefCell::new(None),
+
+            stylus_hovering: Cell::new(false),
+            pointer_hovering: Cell::new(false),
+            pointer_buttons: RefCell::new(HashMap::new()),
+            cursor_icon: Cell::new(CursorIcon::Default),
+            cursor_visible: Cell::new(true),
+            coalesced_touches: Cell::new(false),
 
             rotation_last_delta: Cell::new(0.0),
             pinch_last_delta: Cell::new(0.0),
             pan_last_delta: Cell::new(CGPoint { x: 0.0, y: 0.0 }),
+            scroll_last_translation: Cell::new(CGPoint { x: 0.0, y: 0.0 }),
+
+            display_link: RefCell::new(None),
+            preferred_frame_rate_range: Cell::new(None),
+
+            presentation_link: RefCell::new(None),
+            presentation_feedback: Cell::new(false),
+            present_generation: Cell::new(0),
+            pending_presents: RefCell::new(Vec::new()),
+            last_presented: Cell::new(None),
+            redraw_damage: RefCell::new(RedrawDamage::default()),
+
+            interface_orientation: Cell::new(UIInterfaceOrientation::Unknown),
+            safe_area_insets: Cell::new(None),
+            edr_headroom: Cell::new(None),
+
+            modifiers: Cell::new(Modifiers::default()),
+            pressed_keys: RefCell::new(HashSet::new()),
+
+            ime_allowed: Cell::new(false),
+            ime_purpose: Cell::new(ImePurpose::Normal),
+            ime_cursor_area: Cell::new(CGRect::ZERO),
+            marked_text: RefCell::new(NSMutableString::new()),
+            selected_range: Cell::new(NSRange::new(0, 0)),
+            input_delegate: RefCell::new(None),
+            tokenizer: RefCell::new(None),
+
+            status_bar_scroll_view: RefCell::new(None),
+
+            embedded: window_attributes.platform_specific.parent_view.is_some(),
+            ui_window: RefCell::new(None),
         });
         let this: Retained<Self> = unsafe { msg_send_id![super(this), initWithFrame: frame] };
 
         this.setMultipleTouchEnabled(true);
 
+        // When the status bar is tapped, UIKit asks the scroll view of the key window that has
+        // `scrollsToTop` set whether it should scroll to the top. A scroll view without height
+        // that isn't at the top is enough to be asked, and doesn't receive any touches.
+        //
+        // UIKit doesn't ask anyone if there is more than one such scroll view, so embedded views
+        // leave the status bar to the scroll views of the application.
+        if !this.ivars().embedded {
+            let scroll_view = unsafe { UIScrollView::initWithFrame(mtm.alloc(), CGRect::ZERO) };
+            unsafe {
+                scroll_view.setScrollsToTop(true);
+                scroll_view.setContentSize(CGSize { width: 2.0, height: 2.0 });
+                scroll_view.setContentOffset(CGPoint { x: 2.0, y: 2.0 });
+                scroll_view.setDelegate(Some(ProtocolObject::from_ref(&*this)));
+                this.addSubview(&scroll_view);
+            }
+            *this.ivars().status_bar_scroll_view.borrow_mut() = Some(scroll_view);
+        }
+
+        this.track_pointer();
+
         if let Some(scale_factor) = window_attributes.platform_specific.scale_factor {
             this.setContentScaleFactor(scale_factor as _);
         }
@@ -353,9 +1174,145 @@ impl WinitView {
         this
     }
 
-    fn window(&self) -> Option<Retained<WinitUIWindow>> {
-        // SAFETY: `WinitView`s are always installed in a `WinitUIWindow`
-        (**self).window().map(|window| unsafe { Retained::cast(window) })
+    /// Set the window the view is the content of, which happens after the view was created.
+    pub(crate) fn set_winit_window(&self, window: &WinitUIWindow) {
+        *self.ivars().ui_window.borrow_mut() = Some(WeakId::new(window));
+    }
+
+    /// The `WinitUIWindow` the view is the content of, `None` for embedded views.
+    pub(crate) fn winit_window(&self) -> Option<Retained<WinitUIWindow>> {
+        self.ivars().ui_window.borrow().as_ref().and_then(WeakId::load)
+    }
+
+    /// The id of the window the view belongs to.
+    ///
+    /// Embedded views don't have a window of their own, so the view itself identifies them.
+    pub(crate) fn id(&self) -> WindowId {
+        match self.winit_window() {
+            Some(window) => window.id(),
+            None => WindowId::from(AsRef::<AnyObject>::as_ref(self)),
+        }
+    }
+
+    pub(crate) fn redraw_damage(&self) -> &RefCell<RedrawDamage> {
+        &self.ivars().redraw_damage
+    }
+
+    /// The `RedrawRequested` event of the view, with the damage requested so far.
+    pub(crate) fn redraw_event(&self) -> EventWrapper {
+        self.ivars().redraw_damage.borrow_mut().deliver();
+        EventWrapper::StaticEvent(Event::WindowEvent {
+            window_id: RootWindowId(self.id()),
+            event: WindowEvent::RedrawRequested,
+        })
+    }
+
+    pub(crate) fn set_preferred_frame_rate_range(&self, range: Option<CAFrameRateRange>) {
+        self.ivars().preferred_frame_rate_range.set(range);
+        self.update_display_link();
+    }
+
+    /// (Re)create the display link driving `RedrawRequested`, on the screen that currently
+    /// hosts the view.
+    pub(crate) fn update_display_link(&self) {
+        if let Some(display_link) = self.ivars().display_link.take() {
+            unsafe { display_link.invalidate() };
+        }
+
+        let Some(range) = self.ivars().preferred_frame_rate_range.get() else {
+            return;
+        };
+        let Some(window) = self.window() else {
+            // Created once the view is added to a window.
+            return;
+        };
+
+        let display_link = match unsafe {
+            window.screen().displayLinkWithTarget_selector(self, sel!(displayLinkDidFire:))
+        } {
+            Some(display_link) => display_link,
+            None => return,
+        };
+
+        let os_capabilities = app_state::os_capabilities();
+        if os_capabilities.preferred_frame_rate_range {
+            unsafe { display_link.setPreferredFrameRateRange(range) };
+        } else if os_capabilities.preferred_frames_per_second {
+            #[allow(deprecated)]
+            unsafe {
+                display_link.setPreferredFramesPerSecond(range.preferred as NSInteger)
+            };
+        } else {
+            os_capabilities.preferred_frames_per_second_err_msg("using the native frame rate");
+        }
+
+        unsafe {
+            display_link.addToRunLoop_forMode(&NSRunLoop::mainRunLoop(), NSRunLoopCommonModes)
+        };
+        *self.ivars().display_link.borrow_mut() = Some(display_link);
+    }
+
+    pub(crate) fn pre_present_notify(&self) {
+        let generation = self.ivars().present_generation.get() + 1;
+        self.ivars().present_generation.set(generation);
+        if self.ivars().presentation_feedback.get() {
+            self.ivars().pending_presents.borrow_mut().push(generation);
+            if let Some(presentation_link) = &*self.ivars().presentation_link.borrow() {
+                unsafe { presentation_link.setPaused(false) };
+            }
+        }
+    }
+
+    pub(crate) fn set_presentation_feedback(&self, enabled: bool) {
+        self.ivars().presentation_feedback.set(enabled);
+        self.ivars().pending_presents.borrow_mut().clear();
+        self.ivars().last_presented.set(None);
+        self.update_presentation_link();
+    }
+
+    /// (Re)create the display link timing the presentation of frames, on the screen that
+    /// currently hosts the view.
+    pub(crate) fn update_presentation_link(&self) {
+        if let Some(presentation_link) = self.ivars().presentation_link.take() {
+            unsafe { presentation_link.invalidate() };
+        }
+
+        if !self.ivars().presentation_feedback.get() {
+            return;
+        }
+        let Some(window) = self.window() else {
+            // Created once the view is added to a window.
+            return;
+        };
+
+        let presentation_link = match unsafe {
+            window.screen().displayLinkWithTarget_selector(self, sel!(presentationLinkDidFire:))
+        } {
+            Some(presentation_link) => presentation_link,
+            None => return,
+        };
+
+        // Only runs while frames wait for their presentation.
+        unsafe {
+            presentation_link.setPaused(self.ivars().pending_presents.borrow().is_empty());
+            presentation_link.addToRunLoop_forMode(&NSRunLoop::mainRunLoop(), NSRunLoopCommonModes)
+        };
+        *self.ivars().presentation_link.borrow_mut() = Some(presentation_link);
+    }
+
+    /// Emit `EdrHeadroomChanged` if the EDR headroom of the screen hosting the view has changed
+    /// since it was last checked.
+    pub(crate) fn update_edr_headroom(&self) {
+        let Some(window) = self.window() else {
+            return;
+        };
+        let edr_headroom = monitor::edr_headroom(&window.screen());
+        let previous_edr_headroom = self.ivars().edr_headroom.replace(edr_headroom);
+        if let (Some(previous), Some(edr_headroom)) = (previous_edr_headroom, edr_headroom) {
+            if previous != edr_headroom {
+                self.handle_window_events([WindowEvent::EdrHeadroomChanged(edr_headroom)]);
+            }
+        }
     }
 
     pub(crate) fn recognize_pinch_gesture(&self, should_recognize: bool) {
@@ -447,69 +1404,611 @@ impl WinitView {
         }
     }
 
-    fn handle_touches(&self, touches: &NSSet<UITouch>) {
-        let window = self.window().unwrap();
+    pub(crate) fn recognize_stylus_hover(&self, should_recognize: bool) {
+        let mtm = MainThreadMarker::from(self);
+        if should_recognize {
+            let os_capabilities = app_state::os_capabilities();
+            if !os_capabilities.stylus_hover {
+                os_capabilities.stylus_hover_err_msg("ignoring");
+                return;
+            }
+            if self.ivars().hover_gesture_recognizer.borrow().is_none() {
+                let hover = unsafe {
+                    UIHoverGestureRecognizer::initWithTarget_action(
+                        mtm.alloc(),
+                        Some(self),
+                        Some(sel!(hoverGesture:)),
+                    )
+                };
+                hover.setDelegate(Some(ProtocolObject::from_ref(self)));
+                self.addGestureRecognizer(&hover);
+                self.ivars().hover_gesture_recognizer.replace(Some(hover));
+            }
+        } else if let Some(recognizer) = self.ivars().hover_gesture_recognizer.take() {
+            self.removeGestureRecognizer(&recognizer);
+            self.ivars().stylus_hovering.set(false);
+        }
+    }
+
+    /// Report the hover, clicks and scrolling of trackpads and mice, and show the cursor for
+    /// them.
+    fn track_pointer(&self) {
+        let mtm = MainThreadMarker::from(self);
+        let os_capabilities = app_state::os_capabilities();
+        if !os_capabilities.pointer {
+            os_capabilities.pointer_err_msg("trackpads and mice are reported as touches");
+            return;
+        }
+
+        let hover = unsafe {
+            UIHoverGestureRecognizer::initWithTarget_action(
+                mtm.alloc(),
+                Some(self),
+                Some(sel!(pointerHoverGesture:)),
+            )
+        };
+        hover.setDelegate(Some(ProtocolObject::from_ref(self)));
+        self.addGestureRecognizer(&hover);
+        self.ivars().pointer_hover_gesture_recognizer.replace(Some(hover));
+
+        // Without any touches, the pan gesture recognizer only recognizes scrolling.
+        let scroll = unsafe {
+            UIPanGestureRecognizer::initWithTarget_action(
+                mtm.alloc(),
+                Some(self),
+                Some(sel!(scrollGesture:)),
+            )
+        };
+        scroll.setDelegate(Some(ProtocolObject::from_ref(self)));
+        scroll.setMaximumNumberOfTouches(0);
+        unsafe {
+            scroll.setAllowedScrollTypesMask(
+                UIScrollTypeMask::Continuous | UIScrollTypeMask::Discrete,
+            )
+        };
+        self.addGestureRecognizer(&scroll);
+        self.ivars().scroll_gesture_recognizer.replace(Some(scroll));
+
+        let interaction = unsafe {
+            UIPointerInteraction::initWithDelegate(
+                mtm.alloc(),
+                Some(ProtocolObject::from_ref(self)),
+            )
+        };
+        unsafe { self.addInteraction(ProtocolObject::from_ref(&*interaction)) };
+        self.ivars().pointer_interaction.replace(Some(interaction));
+    }
+
+    pub(crate) fn set_cursor_icon(&self, icon: CursorIcon) {
+        self.ivars().cursor_icon.set(icon);
+        self.update_pointer_style();
+    }
+
+    pub(crate) fn set_cursor_visible(&self, visible: bool) {
+        self.ivars().cursor_visible.set(visible);
+        self.update_pointer_style();
+    }
+
+    /// The pointer style for the cursor icon, `None` for the default pointer.
+    fn pointer_style(&self) -> Option<Retained<UIPointerStyle>> {
+        let mtm = MainThreadMarker::from(self);
+        if !self.ivars().cursor_visible.get() {
+            return Some(unsafe { UIPointerStyle::hiddenPointerStyle(mtm) });
+        }
+        // Only the text cursors have a matching pointer shape, the default pointer is used for
+        // all other icons.
+        let axis = match self.ivars().cursor_icon.get() {
+            CursorIcon::Text => UIAxis::Vertical,
+            CursorIcon::VerticalText => UIAxis::Horizontal,
+            _ => return None,
+        };
+        let shape =
+            unsafe { UIPointerShape::beamWithPreferredLength_axis(TEXT_BEAM_LENGTH, axis, mtm) };
+        Some(unsafe { UIPointerStyle::styleWithShape_constrainedAxes(&shape, UIAxis::Neither) })
+    }
+
+    fn update_pointer_style(&self) {
+        if let Some(interaction) = &*self.ivars().pointer_interaction.borrow() {
+            // Makes UIKit ask for the style again.
+            unsafe { interaction.invalidate() };
+        }
+    }
+
+    pub(crate) fn set_coalesced_touches(&self, enabled: bool) {
+        let os_capabilities = app_state::os_capabilities();
+        if enabled && !os_capabilities.coalesced_touches {
+            os_capabilities.coalesced_touches_err_msg("ignoring");
+            return;
+        }
+        self.ivars().coalesced_touches.set(enabled);
+    }
+
+    /// Emit keyboard events for the presses of hardware keyboard keys, returns whether the
+    /// presses were consumed.
+    ///
+    /// While IME is allowed, the presses are never consumed, so that they reach the text input
+    /// system, which reports their text.
+    fn handle_presses(&self, presses: &NSSet<UIPress>, state: ElementState) -> bool {
+        if !app_state::os_capabilities().hardware_keyboard {
+            return false;
+        }
+        let mtm = MainThreadMarker::from(self);
+        let window_id = RootWindowId(self.id());
+        let ime_allowed = self.ivars().ime_allowed.get();
+        let composing = self.is_composing();
+
+        let mut events = Vec::new();
+        for press in presses {
+            let Some(key) = (unsafe { press.key(mtm) }) else {
+                continue;
+            };
+            let mut event = keyboard::create_key_event(&key, state);
+            // UIKit doesn't repeat presses itself, but a press of a key that is still held
+            // down, e.g. when a keyboard repeats in hardware, is a repeat.
+            let mut pressed_keys = self.ivars().pressed_keys.borrow_mut();
+            event.repeat = match state {
+                ElementState::Pressed => !pressed_keys.insert(event.physical_key),
+                ElementState::Released => {
+                    pressed_keys.remove(&event.physical_key);
+                    false
+                },
+            };
+            drop(pressed_keys);
+
+            let modifiers = self.update_modifiers(&key, event.physical_key, state);
+            let modifiers_changed = modifiers.map(|modifiers| {
+                EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::ModifiersChanged(modifiers),
+                })
+            });
+            let is_modifier = keyboard::modifier_key(event.physical_key).is_some();
+            if ime_allowed {
+                // The text is reported by `insertText:` instead.
+                event.text = None;
+                if composing && !is_modifier {
+                    // The key is part of the composition.
+                    events.extend(modifiers_changed);
+                    continue;
+                }
+            }
+            let keyboard_input = EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::KeyboardInput {
+                    device_id: DEVICE_ID,
+                    event,
+                    is_synthetic: false,
+                },
+            });
+
+            // Same as on macOS, the modifiers change after the press of a modifier key, and
+            // before the press of any other key.
+            if is_modifier {
+                events.push(keyboard_input);
+                events.extend(modifiers_changed);
+            } else {
+                events.extend(modifiers_changed);
+                events.push(keyboard_input);
+            }
+        }
+
+        let handled = !events.is_empty() && !ime_allowed;
+        app_state::handle_nonuser_events(mtm, events);
+        handled
+    }
+
+    fn is_key_pressed(&self, code: KeyCode) -> bool {
+        self.ivars().pressed_keys.borrow().contains(&PhysicalKey::Code(code))
+    }
+
+    pub(crate) fn set_ime_allowed(&self, allowed: bool) {
+        if self.ivars().ime_allowed.replace(allowed) == allowed {
+            return;
+        }
+
+        let mut events = Vec::new();
+        if allowed {
+            if !unsafe { self.isFirstResponder() } {
+                unsafe { self.becomeFirstResponder() };
+            }
+            events.push(WindowEvent::Ime(Ime::Enabled));
+        } else {
+            if self.is_composing() {
+                // Let the text input system know that the composition was discarded.
+                let input_delegate = unsafe { self.inputDelegate() };
+                let text_input = ProtocolObject::from_ref(self);
+                if let Some(input_delegate) = &input_delegate {
+                    unsafe { input_delegate.textWillChange(Some(text_input)) };
+                }
+                self.clear_marked_text();
+                if let Some(input_delegate) = &input_delegate {
+                    unsafe { input_delegate.textDidChange(Some(text_input)) };
+                }
+                events.push(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            }
+            events.push(WindowEvent::Ime(Ime::Disabled));
+        }
+        // Show or hide the on-screen keyboard, see `inputView`.
+        unsafe { self.reloadInputViews() };
+        self.handle_window_events(events);
+    }
+
+    pub(crate) fn set_ime_purpose(&self, purpose: ImePurpose) {
+        if self.ivars().ime_purpose.replace(purpose) != purpose
+            && unsafe { self.isFirstResponder() }
+        {
+            // Pick up the new text input traits.
+            unsafe { self.reloadInputViews() };
+        }
+    }
+
+    pub(crate) fn set_ime_cursor_area(&self, area: CGRect) {
+        self.ivars().ime_cursor_area.set(area);
+    }
+
+    fn is_composing(&self) -> bool {
+        self.document_len() > 0
+    }
+
+    /// The length of the `UITextInput` document, i.e. of the marked text.
+    fn document_len(&self) -> usize {
+        self.ivars().marked_text.borrow().length()
+    }
+
+    fn clamp_range(&self, range: NSRange) -> NSRange {
+        let len = self.document_len();
+        let location = range.location.min(len);
+        NSRange::new(location, range.length.min(len - location))
+    }
+
+    fn offset_position(
+        &self,
+        position: &UITextPosition,
+        offset: NSInteger,
+    ) -> Option<Retained<UITextPosition>> {
+        let offset = (position_offset(position) as NSInteger).checked_add(offset)?;
+        let offset =
+            usize::try_from(offset).ok().filter(|offset| *offset <= self.document_len())?;
+        Some(text_position(MainThreadMarker::from(self), offset))
+    }
+
+    /// Clear the marked text, returns whether there was any.
+    fn clear_marked_text(&self) -> bool {
+        let composing = self.is_composing();
+        self.ivars().marked_text.borrow_mut().setString(&NSString::new());
+        self.ivars().selected_range.set(NSRange::new(0, 0));
+        composing
+    }
+
+    fn preedit_event(&self) -> WindowEvent {
+        let marked_text = self.ivars().marked_text.borrow();
+        let cursor_range = if marked_text.length() == 0 {
+            // An empty string means that there's no preedit.
+            None
+        } else {
+            // Convert the selected range from UTF-16 indices to UTF-8 indices.
+            let selected_range = self.ivars().selected_range.get();
+            let sub_string_a = unsafe { marked_text.substringToIndex(selected_range.location) };
+            let sub_string_b = unsafe { marked_text.substringToIndex(selected_range.end()) };
+            Some((sub_string_a.len(), sub_string_b.len()))
+        };
+        WindowEvent::Ime(Ime::Preedit(marked_text.to_string(), cursor_range))
+    }
+
+    fn handle_window_events(&self, events: impl IntoIterator<Item = WindowEvent>) {
+        let mtm = MainThreadMarker::from(self);
+        let window_id = RootWindowId(self.id());
+        app_state::handle_nonuser_events(
+            mtm,
+            events
+                .into_iter()
+                .map(|event| EventWrapper::StaticEvent(Event::WindowEvent { window_id, event })),
+        );
+    }
+
+    /// Update the modifiers with the ones of `key`, returns the new modifiers if they changed.
+    fn update_modifiers(
+        &self,
+        key: &UIKey,
+        physical_key: PhysicalKey,
+        state: ElementState,
+    ) -> Option<Modifiers> {
+        let prev_modifiers = self.ivars().modifiers.get();
+        let mut modifiers = Modifiers {
+            state: keyboard::modifiers_state(key),
+            pressed_mods: prev_modifiers.pressed_mods,
+            lock_keys: keyboard::lock_keys(key),
+        };
+
+        let modifier_key = keyboard::modifier_key(physical_key);
+        if let Some(modifier_key) = modifier_key {
+            modifiers.pressed_mods.set(modifier_key, state == ElementState::Pressed);
+        }
+        for (modifier, keys) in keyboard::MODIFIER_KEYS {
+            if modifier_key.is_some_and(|modifier_key| keys.contains(modifier_key)) {
+                // The flags of the press of a modifier key don't reliably include the change
+                // made by that press, so rely on the keys we know are pressed instead.
+                modifiers.state.set(modifier, modifiers.pressed_mods.intersects(keys));
+            } else if !modifiers.state.contains(modifier) {
+                // The keys were released while the view wasn't the first responder.
+                modifiers.pressed_mods.remove(keys);
+            }
+        }
+
+        self.ivars().modifiers.set(modifiers);
+        (modifiers != prev_modifiers).then_some(modifiers)
+    }
+
+    /// Reset the keyboard state and emit a synthetic `ModifiersChanged` event if needed.
+    pub(crate) fn reset_keyboard_state(&self) {
+        self.ivars().pressed_keys.borrow_mut().clear();
+        if self.ivars().modifiers.replace(Modifiers::default()) != Modifiers::default() {
+            let mtm = MainThreadMarker::from(self);
+            app_state::handle_nonuser_event(
+                mtm,
+                EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id: RootWindowId(self.id()),
+                    event: WindowEvent::ModifiersChanged(Modifiers::default()),
+                }),
+            );
+        }
+    }
+
+    fn handle_touches(&self, touches: &NSSet<UITouch>, event: Option<&UIEvent>) {
+        let window_id = RootWindowId(self.id());
         let mut touch_events = Vec::new();
-        let os_supports_force = app_state::os_capabilities().force_touch;
+        let button_mask = event.map(|event| unsafe { event.buttonMask() });
+        let event = event.filter(|_| self.ivars().coalesced_touches.get());
         for touch in touches {
-            let logical_location = touch.locationInView(None);
-            let touch_type = touch.r#type();
-            let force = if os_supports_force {
-                let trait_collection = self.traitCollection();
-                let touch_capability = trait_collection.forceTouchCapability();
-                // Both the OS _and_ the device need to be checked for force touch support.
-                if touch_capability == UIForceTouchCapability::Available
-                    || touch_type == UITouchType::Pencil
-                {
-                    let force = touch.force();
-                    let max_possible_force = touch.maximumPossibleForce();
-                    let altitude_angle: Option<f64> = if touch_type == UITouchType::Pencil {
-                        let angle = touch.altitudeAngle();
-                        Some(angle as _)
-                    } else {
-                        None
-                    };
-                    Some(Force::Calibrated {
-                        force: force as _,
-                        max_possible_force: max_possible_force as _,
-                        altitude_angle,
-                    })
+            if touch.r#type() == UITouchType::IndirectPointer {
+                touch_events.extend(self.pointer_events(window_id, touch, button_mask));
+                continue;
+            }
+
+            let coalesced =
+                event.and_then(|event| unsafe { event.coalescedTouchesForTouch(touch) });
+            match coalesced {
+                // The coalesced touches end with a copy of `touch` itself.
+                Some(coalesced) if !coalesced.is_empty() => touch_events.extend(
+                    coalesced
+                        .iter()
+                        .map(|coalesced| self.touch_event(window_id, touch, coalesced, false)),
+                ),
+                _ => touch_events.push(self.touch_event(window_id, touch, touch, false)),
+            }
+
+            if !matches!(touch.phase(), UITouchPhase::Began | UITouchPhase::Moved) {
+                continue;
+            }
+            if let Some(predicted) =
+                event.and_then(|event| unsafe { event.predictedTouchesForTouch(touch) })
+            {
+                touch_events.extend(
+                    predicted
+                        .iter()
+                        .map(|predicted| self.touch_event(window_id, touch, predicted, true)),
+                );
+            }
+        }
+        let mtm = MainThreadMarker::new().unwrap();
+        app_state::handle_nonuser_events(mtm, touch_events);
+    }
+
+    /// Converts the click of a trackpad or mouse into mouse events.
+    ///
+    /// `button_mask` contains the buttons of the event the touch is part of, which includes the
+    /// buttons of clicks that are already in progress.
+    fn pointer_events(
+        &self,
+        window_id: RootWindowId,
+        touch: &UITouch,
+        button_mask: Option<UIEventButtonMask>,
+    ) -> Vec<EventWrapper> {
+        let touch_id = touch as *const UITouch as u64;
+        let position = self.physical_position(touch.locationInView(Some(self)));
+        let click_count = touch.tapCount().max(1) as u32;
+        let mut events = vec![WindowEvent::CursorMoved { device_id: DEVICE_ID, position }];
+
+        let mut pointer_buttons = self.ivars().pointer_buttons.borrow_mut();
+        match touch.phase() {
+            UITouchPhase::Began => {
+                let button_mask = button_mask.unwrap_or(UIEventButtonMask::Primary).0;
+                let pressed = pointer_buttons.values().copied().collect::<Vec<_>>();
+                let button = (0..NSInteger::BITS as u16)
+                    .filter(|index| button_mask & (1 << index) != 0)
+                    .map(mouse_button)
+                    .find(|button| !pressed.contains(button))
+                    .unwrap_or(MouseButton::Left);
+                if pointer_buttons.insert(touch_id, button).is_none() {
+                    events.push(WindowEvent::MouseInput {
+                        device_id: DEVICE_ID,
+                        state: ElementState::Pressed,
+                        button,
+                        click_count,
+                    });
+                }
+            },
+            UITouchPhase::Ended | UITouchPhase::Cancelled => {
+                if let Some(button) = pointer_buttons.remove(&touch_id) {
+                    events.push(WindowEvent::MouseInput {
+                        device_id: DEVICE_ID,
+                        state: ElementState::Released,
+                        button,
+                        click_count,
+                    });
+                }
+            },
+            _ => {},
+        }
+
+        events
+            .into_iter()
+            .map(|event| EventWrapper::StaticEvent(Event::WindowEvent { window_id, event }))
+            .collect()
+    }
+
+    /// Converts a point in the coordinate space of the view to physical pixels.
+    fn physical_position(&self, point: CGPoint) -> PhysicalPosition<f64> {
+        PhysicalPosition::from_logical::<(f64, f64), f64>(
+            (point.x as _, point.y as _),
+            self.contentScaleFactor() as f64,
+        )
+    }
+
+    /// Converts `sample`, which is either `touch` itself or one of its coalesced or predicted
+    /// touches, into a touch event.
+    fn touch_event(
+        &self,
+        window_id: RootWindowId,
+        touch: &UITouch,
+        sample: &UITouch,
+        is_predicted: bool,
+    ) -> EventWrapper {
+        let os_capabilities = app_state::os_capabilities();
+        let logical_location = sample.locationInView(Some(self));
+        let touch_type = sample.r#type();
+        let force = if os_capabilities.force_touch {
+            let trait_collection = self.traitCollection();
+            let touch_capability = trait_collection.forceTouchCapability();
+            // Both the OS _and_ the device need to be checked for force touch support.
+            if touch_capability == UIForceTouchCapability::Available
+                || touch_type == UITouchType::Pencil
+            {
+                let force = sample.force();
+                let max_possible_force = sample.maximumPossibleForce();
+                let altitude_angle: Option<f64> = if touch_type == UITouchType::Pencil {
+                    let angle = sample.altitudeAngle();
+                    Some(angle as _)
                 } else {
                     None
-                }
+                };
+                Some(Force::Calibrated {
+                    force: force as _,
+                    max_possible_force: max_possible_force as _,
+                    altitude_angle,
+                })
             } else {
                 None
-            };
-            let touch_id = touch as *const UITouch as u64;
-            let phase = touch.phase();
-            let phase = match phase {
+            }
+        } else {
+            None
+        };
+        let (tool, azimuth_angle, altitude_angle) = if touch_type == UITouchType::Pencil {
+            (
+                TouchTool::Stylus,
+                Some(sample.azimuthAngleInView(Some(self)) as f64),
+                Some(sample.altitudeAngle() as f64),
+            )
+        } else {
+            (TouchTool::Finger, None, None)
+        };
+        let is_estimated = os_capabilities.estimated_touch_properties
+            && unsafe { sample.estimatedPropertiesExpectingUpdates() }.0 != 0;
+        // Coalesced and predicted touches are distinct objects, identify them by the touch they
+        // belong to.
+        let touch_id = touch as *const UITouch as u64;
+        let phase = if is_predicted {
+            TouchPhase::Moved
+        } else {
+            let phase = sample.phase();
+            match phase {
                 UITouchPhase::Began => TouchPhase::Started,
                 UITouchPhase::Moved => TouchPhase::Moved,
                 // 2 is UITouchPhase::Stationary and is not expected here
                 UITouchPhase::Ended => TouchPhase::Ended,
                 UITouchPhase::Cancelled => TouchPhase::Cancelled,
                 _ => panic!("unexpected touch phase: {phase:?}"),
-            };
+            }
+        };
 
-            let physical_location = {
-                let scale_factor = self.contentScaleFactor();
-                PhysicalPosition::from_logical::<(f64, f64), f64>(
-                    (logical_location.x as _, logical_location.y as _),
-                    scale_factor as f64,
-                )
-            };
-            touch_events.push(EventWrapper::StaticEvent(Event::WindowEvent {
-                window_id: RootWindowId(window.id()),
-                event: WindowEvent::Touch(Touch {
-                    device_id: DEVICE_ID,
-                    id: touch_id,
-                    location: physical_location,
-                    force,
-                    phase,
-                }),
-            }));
-        }
-        let mtm = MainThreadMarker::new().unwrap();
-        app_state::handle_nonuser_events(mtm, touch_events);
+        let physical_location = {
+            let scale_factor = self.contentScaleFactor();
+            PhysicalPosition::from_logical::<(f64, f64), f64>(
+                (logical_location.x as _, logical_location.y as _),
+                scale_factor as f64,
+            )
+        };
+        EventWrapper::StaticEvent(Event::WindowEvent {
+            window_id,
+            event: WindowEvent::Touch(Touch {
+                device_id: DEVICE_ID,
+                id: touch_id,
+                location: physical_location,
+                force,
+                tool,
+                azimuth_angle,
+                altitude_angle,
+                twist_angle: None,
+                is_estimated,
+                is_predicted,
+                phase,
+            }),
+        })
+    }
+}
+
+/// The press and release of a key of the on-screen keyboard.
+fn on_screen_key_events(key: NamedKey, code: KeyCode) -> [WindowEvent; 2] {
+    let keyboard_input = |event: KeyEvent| WindowEvent::KeyboardInput {
+        device_id: DEVICE_ID,
+        event,
+        is_synthetic: false,
+    };
+    [
+        keyboard_input(keyboard::on_screen_key_event(key, code, ElementState::Pressed)),
+        keyboard_input(keyboard::on_screen_key_event(key, code, ElementState::Released)),
+    ]
+}
+
+/// The preferred length of the beam shown for text cursors, in points.
+const TEXT_BEAM_LENGTH: CGFloat = 20.0;
+
+/// The mouse button of the bit at `index` in a `UIEventButtonMask`.
+fn mouse_button(index: u16) -> MouseButton {
+    match index {
+        0 => MouseButton::Left,
+        1 => MouseButton::Right,
+        2 => MouseButton::Middle,
+        3 => MouseButton::Back,
+        4 => MouseButton::Forward,
+        index => MouseButton::Other(index),
+    }
+}
+
+pub(crate) fn theme(style: UIUserInterfaceStyle) -> Theme {
+    match style {
+        UIUserInterfaceStyle::Dark => Theme::Dark,
+        _ => Theme::Light,
+    }
+}
+
+/// The size of the body text style in `category`, relative to its size in the default category.
+pub(crate) fn text_scale_factor(category: &UIContentSizeCategory) -> f64 {
+    let sizes = unsafe {
+        [
+            (UIContentSizeCategoryExtraSmall, 14.0),
+            (UIContentSizeCategorySmall, 15.0),
+            (UIContentSizeCategoryMedium, 16.0),
+            (UIContentSizeCategoryLarge, 17.0),
+            (UIContentSizeCategoryExtraLarge, 19.0),
+            (UIContentSizeCategoryExtraExtraLarge, 21.0),
+            (UIContentSizeCategoryExtraExtraExtraLarge, 23.0),
+            (UIContentSizeCategoryAccessibilityMedium, 28.0),
+            (UIContentSizeCategoryAccessibilityLarge, 33.0),
+            (UIContentSizeCategoryAccessibilityExtraLarge, 40.0),
+            (UIContentSizeCategoryAccessibilityExtraExtraLarge, 47.0),
+            (UIContentSizeCategoryAccessibilityExtraExtraExtraLarge, 53.0),
+        ]
+    };
+    sizes.into_iter().find(|(other, _)| *other == category).map_or(1.0, |(_, size)| size / 17.0)
+}
+
+/// Convert a timestamp of `CACurrentMediaTime` to an `Instant`.
+fn media_time_to_instant(timestamp: f64) -> Option<Instant> {
+    let delta = timestamp - CACurrentMediaTime();
+    if delta >= 0.0 {
+        Instant::now().checked_add(Duration::from_secs_f64(delta))
+    } else {
+        Instant::now().checked_sub(Duration::from_secs_f64(-delta))
     }
 }
@@ -1,20 +1,29 @@
 use std::cell::Cell;
 
+use block2::RcBlock;
 use objc2::rc::Retained;
 use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
 use objc2_foundation::{MainThreadMarker, NSObject};
 use objc2_ui_kit::{
-    UIDevice, UIInterfaceOrientationMask, UIRectEdge, UIResponder, UIStatusBarStyle,
-    UIUserInterfaceIdiom, UIView, UIViewController,
+    UIDevice, UIInterfaceOrientation, UIInterfaceOrientationMask, UIRectEdge, UIResponder,
+    UIStatusBarAnimation, UIStatusBarStyle, UIUserInterfaceIdiom, UIView, UIViewController,
+    UIViewControllerTransitionCoordinator,
 };
 
 use super::app_state::{self};
-use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
-use crate::window::WindowAttributes;
+use crate::platform::ios::{
+    OrientationMask, ScreenEdge, StatusBarAnimation, StatusBarStyle, ValidOrientations,
+};
+use crate::window::{InterfaceOrientation, WindowAttributes};
+
+// Duration of the status bar show/hide animation, matches
+// `UINavigationControllerHideShowBarDuration`.
+const STATUS_BAR_ANIMATION_DURATION: f64 = 0.33;
 
 pub struct ViewControllerState {
     prefers_status_bar_hidden: Cell<bool>,
     preferred_status_bar_style: Cell<UIStatusBarStyle>,
+    preferred_status_bar_update_animation: Cell<UIStatusBarAnimation>,
     prefers_home_indicator_auto_hidden: Cell<bool>,
     supported_orientations: Cell<UIInterfaceOrientationMask>,
     preferred_screen_edges_deferring_system_gestures: Cell<UIRectEdge>,
@@ -50,6 +59,11 @@ declare_class!(
             self.ivars().preferred_status_bar_style.get()
         }
 
+        #[method(preferredStatusBarUpdateAnimation)]
+        fn preferred_status_bar_update_animation(&self) -> UIStatusBarAnimation {
+            self.ivars().preferred_status_bar_update_animation.get()
+        }
+
         #[method(prefersHomeIndicatorAutoHidden)]
         fn prefers_home_indicator_auto_hidden(&self) -> bool {
             self.ivars().prefers_home_indicator_auto_hidden.get()
@@ -72,7 +86,7 @@ declare_class!(
 impl WinitViewController {
     pub(crate) fn set_prefers_status_bar_hidden(&self, val: bool) {
         self.ivars().prefers_status_bar_hidden.set(val);
-        self.setNeedsStatusBarAppearanceUpdate();
+        self.update_status_bar_appearance();
     }
 
     pub(crate) fn set_preferred_status_bar_style(&self, val: StatusBarStyle) {
@@ -82,7 +96,38 @@ impl WinitViewController {
             StatusBarStyle::DarkContent => UIStatusBarStyle::DarkContent,
         };
         self.ivars().preferred_status_bar_style.set(val);
-        self.setNeedsStatusBarAppearanceUpdate();
+        self.update_status_bar_appearance();
+    }
+
+    pub(crate) fn set_preferred_status_bar_update_animation(&self, val: StatusBarAnimation) {
+        let val = match val {
+            StatusBarAnimation::None => UIStatusBarAnimation::None,
+            StatusBarAnimation::Fade => UIStatusBarAnimation::Fade,
+            StatusBarAnimation::Slide => UIStatusBarAnimation::Slide,
+        };
+        self.ivars().preferred_status_bar_update_animation.set(val);
+    }
+
+    fn update_status_bar_appearance(&self) {
+        let this = self.retain();
+        let update = RcBlock::new(move || this.setNeedsStatusBarAppearanceUpdate());
+
+        // If we're in the middle of a transition (such as a rotation), update alongside it, so
+        // that the status bar and the safe area don't jump while the interface is animating.
+        if let Some(coordinator) = unsafe { self.transitionCoordinator() } {
+            let animation = RcBlock::new(move |_| update.call(()));
+            unsafe { coordinator.animateAlongsideTransition_completion(Some(&animation), None) };
+        } else if self.ivars().preferred_status_bar_update_animation.get()
+            != UIStatusBarAnimation::None
+        {
+            // The status bar only animates when it is updated inside an animation block.
+            let mtm = MainThreadMarker::from(self);
+            unsafe {
+                UIView::animateWithDuration_animations(STATUS_BAR_ANIMATION_DURATION, &update, mtm)
+            };
+        } else {
+            self.setNeedsStatusBarAppearanceUpdate();
+        }
     }
 
     pub(crate) fn set_prefers_home_indicator_auto_hidden(&self, val: bool) {
@@ -95,6 +140,10 @@ impl WinitViewController {
         }
     }
 
+    pub(crate) fn home_indicator_auto_hidden(&self) -> bool {
+        self.ivars().prefers_home_indicator_auto_hidden.get()
+    }
+
     pub(crate) fn set_preferred_screen_edges_deferring_system_gestures(&self, val: ScreenEdge) {
         let val = {
             assert_eq!(val.bits() & !ScreenEdge::ALL.bits(), 0, "invalid `ScreenEdge`");
@@ -109,6 +158,23 @@ impl WinitViewController {
         }
     }
 
+    pub(crate) fn screen_edges_deferring_system_gestures(&self) -> ScreenEdge {
+        let val = self.ivars().preferred_screen_edges_deferring_system_gestures.get();
+        ScreenEdge::from_bits_truncate(val.0 as u8)
+    }
+
+    /// Ask the system to query the preferences of the view controller again.
+    ///
+    /// Used when the window moved to another screen, where the system may not have picked up the
+    /// current values yet.
+    pub(crate) fn reapply_system_preferences(&self) {
+        self.setNeedsStatusBarAppearanceUpdate();
+        self.set_prefers_home_indicator_auto_hidden(self.home_indicator_auto_hidden());
+        self.set_preferred_screen_edges_deferring_system_gestures(
+            self.screen_edges_deferring_system_gestures(),
+        );
+    }
+
     pub(crate) fn set_supported_interface_orientations(
         &self,
         mtm: MainThreadMarker,
@@ -128,9 +194,54 @@ impl WinitViewController {
                     | UIInterfaceOrientationMask::PortraitUpsideDown
             },
         };
+        self.set_supported_interface_orientation_mask(mask);
+    }
+
+    pub(crate) fn set_supported_orientation_mask(&self, orientations: OrientationMask) {
+        let mut mask = UIInterfaceOrientationMask::empty();
+        for (flag, orientation) in [
+            (OrientationMask::PORTRAIT, UIInterfaceOrientationMask::Portrait),
+            (OrientationMask::PORTRAIT_UPSIDE_DOWN, UIInterfaceOrientationMask::PortraitUpsideDown),
+            (OrientationMask::LANDSCAPE_LEFT, UIInterfaceOrientationMask::LandscapeLeft),
+            (OrientationMask::LANDSCAPE_RIGHT, UIInterfaceOrientationMask::LandscapeRight),
+        ] {
+            if orientations.contains(flag) {
+                mask |= orientation;
+            }
+        }
+
+        if mask.is_empty() {
+            // UIKit raises an exception if no orientation is supported.
+            tracing::warn!("ignoring empty `OrientationMask`");
+            return;
+        }
+        self.set_supported_interface_orientation_mask(mask);
+    }
+
+    fn set_supported_interface_orientation_mask(&self, mask: UIInterfaceOrientationMask) {
+        // If we're in the middle of a rotation, apply the new orientations once it has finished,
+        // so that the rotation isn't interrupted and the interface settles in a known state.
+        if let Some(coordinator) = unsafe { self.transitionCoordinator() } {
+            let this = self.retain();
+            let completion =
+                RcBlock::new(move |_| this.set_supported_interface_orientation_mask(mask));
+            unsafe { coordinator.animateAlongsideTransition_completion(None, Some(&completion)) };
+            return;
+        }
+
         self.ivars().supported_orientations.set(mask);
+        if app_state::os_capabilities().supported_interface_orientations_update {
+            unsafe { self.setNeedsUpdateOfSupportedInterfaceOrientations() };
+        } else {
+            let mtm = MainThreadMarker::from(self);
+            #[allow(deprecated)]
+            UIViewController::attemptRotationToDeviceOrientation(mtm);
+        }
+    }
+
+    pub(crate) fn interface_orientation(&self) -> Option<InterfaceOrientation> {
         #[allow(deprecated)]
-        UIViewController::attemptRotationToDeviceOrientation(mtm);
+        interface_orientation(unsafe { self.interfaceOrientation() })
     }
 
     pub(crate) fn new(
@@ -142,6 +253,7 @@ impl WinitViewController {
         let this = mtm.alloc().set_ivars(ViewControllerState {
             prefers_status_bar_hidden: Cell::new(false),
             preferred_status_bar_style: Cell::new(UIStatusBarStyle::Default),
+            preferred_status_bar_update_animation: Cell::new(UIStatusBarAnimation::None),
             prefers_home_indicator_auto_hidden: Cell::new(false),
             supported_orientations: Cell::new(UIInterfaceOrientationMask::All),
             preferred_screen_edges_deferring_system_gestures: Cell::new(UIRectEdge::empty()),
@@ -156,11 +268,19 @@ impl WinitViewController {
             window_attributes.platform_specific.preferred_status_bar_style,
         );
 
-        this.set_supported_interface_orientations(
-            mtm,
-            window_attributes.platform_specific.valid_orientations,
+        // Set after the initial status bar state, so that it doesn't animate on creation.
+        this.set_preferred_status_bar_update_animation(
+            window_attributes.platform_specific.preferred_status_bar_update_animation,
         );
 
+        match window_attributes.platform_specific.supported_interface_orientations {
+            Some(orientations) => this.set_supported_orientation_mask(orientations),
+            None => this.set_supported_interface_orientations(
+                mtm,
+                window_attributes.platform_specific.valid_orientations,
+            ),
+        }
+
         this.set_prefers_home_indicator_auto_hidden(
             window_attributes.platform_specific.prefers_home_indicator_hidden,
         );
@@ -174,3 +294,17 @@ impl WinitViewController {
         this
     }
 }
+
+pub(crate) fn interface_orientation(
+    orientation: UIInterfaceOrientation,
+) -> Option<InterfaceOrientation> {
+    match orientation {
+        UIInterfaceOrientation::Portrait => Some(InterfaceOrientation::Portrait),
+        UIInterfaceOrientation::PortraitUpsideDown => {
+            Some(InterfaceOrientation::PortraitUpsideDown)
+        },
+        UIInterfaceOrientation::LandscapeLeft => Some(InterfaceOrientation::LandscapeLeft),
+        UIInterfaceOrientation::LandscapeRight => Some(InterfaceOrientation::LandscapeRight),
+        _ => None,
+    }
+}
@@ -1,6 +1,9 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::ptr::NonNull;
 
 use objc2::rc::Retained;
 use objc2::runtime::{AnyObject, NSObject};
@@ -8,24 +11,32 @@ use objc2::{class, declare_class, msg_send, msg_send_id, mutability, ClassType,
 use objc2_foundation::{
     CGFloat, CGPoint, CGRect, CGSize, MainThreadBound, MainThreadMarker, NSObjectProtocol,
 };
+use objc2_quartz_core::CAFrameRateRange;
 use objc2_ui_kit::{
     UIApplication, UICoordinateSpace, UIResponder, UIScreen, UIScreenOverscanCompensation,
-    UIViewController, UIWindow,
+    UITraitEnvironment, UIUserInterfaceStyle, UIView, UIViewAutoresizing, UIViewController,
+    UIWindow,
 };
 use tracing::{debug, warn};
 
 use super::app_state::EventWrapper;
-use super::view::WinitView;
+use super::view::{text_scale_factor, theme, WinitView};
 use super::view_controller::WinitViewController;
 use super::{app_state, monitor, ActiveEventLoop, Fullscreen, MonitorHandle};
 use crate::cursor::Cursor;
-use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{
+    LogicalInsets, LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize,
+    Position, Size,
+};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Event, WindowEvent};
 use crate::icon::Icon;
-use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
+use crate::platform::ios::{
+    OrientationMask, ScreenEdge, StatusBarAnimation, StatusBarStyle, ValidOrientations,
+};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
+    AspectRatio, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose, InterfaceOrientation,
+    ProgressState, Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
     WindowButtons, WindowId as RootWindowId, WindowLevel,
 };
 
@@ -54,11 +65,18 @@ declare_class!(
                 }),
             );
             let _: () = unsafe { msg_send![super(self), becomeKeyWindow] };
+            // Receive the presses of hardware keyboards.
+            if let Some(view) = self.winit_view() {
+                unsafe { view.becomeFirstResponder() };
+            }
         }
 
         #[method(resignKeyWindow)]
         fn resign_key_window(&self) {
             let mtm = MainThreadMarker::new().unwrap();
+            if let Some(view) = self.winit_view() {
+                view.reset_keyboard_state();
+            }
             app_state::handle_nonuser_event(
                 mtm,
                 EventWrapper::StaticEvent(Event::WindowEvent {
@@ -85,9 +103,10 @@ impl WinitUIWindow {
         match window_attributes.fullscreen.clone().map(Into::into) {
             Some(Fullscreen::Exclusive(ref video_mode)) => {
                 let monitor = video_mode.monitor();
-                let screen = monitor.ui_screen(mtm);
-                screen.setCurrentMode(Some(video_mode.screen_mode(mtm)));
-                this.setScreen(screen);
+                if monitor.set_video_mode(video_mode).is_err() {
+                    warn!("video modes can only be set on external displays, ignoring");
+                }
+                this.setScreen(monitor.ui_screen(mtm));
             },
             Some(Fullscreen::Borderless(Some(ref monitor))) => {
                 let screen = monitor.ui_screen(mtm);
@@ -102,18 +121,48 @@ impl WinitUIWindow {
     pub(crate) fn id(&self) -> WindowId {
         (self as *const Self as usize as u64).into()
     }
+
+    pub(crate) fn winit_view(&self) -> Option<Retained<WinitView>> {
+        let view = self.rootViewController()?.view()?;
+        if view.is_kind_of::<WinitView>() {
+            // SAFETY: Just checked that the view is a `WinitView`.
+            Some(unsafe { Retained::cast(view) })
+        } else {
+            None
+        }
+    }
 }
 
 pub struct Inner {
-    window: Retained<WinitUIWindow>,
-    view_controller: Retained<WinitViewController>,
+    // `None` if the view is embedded into a view of the application.
+    window: Option<Retained<WinitUIWindow>>,
+    view_controller: Option<Retained<WinitViewController>>,
     view: Retained<WinitView>,
     gl_or_metal_backed: bool,
+    /// The count set on the badge of the application icon by this window.
+    badge_count: Cell<Option<u64>>,
+    /// The last title set, which isn't shown anywhere.
+    title: RefCell<String>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // The display link retains the view, break the cycle.
+        self.view.set_preferred_frame_rate_range(None);
+        if self.window.is_none() {
+            unsafe { self.view.removeFromSuperview() };
+        }
+        // The badge outlives the window, so clear the count it has shown.
+        if self.badge_count.get().is_some() {
+            self.set_badge_count(None);
+        }
+    }
 }
 
 impl Inner {
-    pub fn set_title(&self, _title: &str) {
-        debug!("`Window::set_title` is ignored on iOS")
+    pub fn set_title(&self, title: &str) {
+        debug!("`Window::set_title` is ignored on iOS");
+        *self.title.borrow_mut() = title.to_owned();
     }
 
     pub fn set_transparent(&self, _transparent: bool) {
@@ -124,8 +173,16 @@ impl Inner {
         debug!("`Window::set_blur` is ignored on iOS")
     }
 
+    pub fn set_opacity(&self, opacity: f32) {
+        unsafe { self.outer_view().setAlpha(opacity as CGFloat) }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        unsafe { self.outer_view().alpha() as f32 }
+    }
+
     pub fn set_visible(&self, visible: bool) {
-        self.window.setHidden(!visible)
+        self.outer_view().setHidden(!visible)
     }
 
     pub fn is_visible(&self) -> Option<bool> {
@@ -134,6 +191,21 @@ impl Inner {
     }
 
     pub fn request_redraw(&self) {
+        self.view.redraw_damage().borrow_mut().add_full();
+        self.schedule_redraw();
+    }
+
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        self.view.redraw_damage().borrow_mut().add(rects, self.inner_size());
+        self.schedule_redraw();
+    }
+
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let size = self.inner_size();
+        self.view.redraw_damage().borrow().current(size).map(<[_]>::to_vec)
+    }
+
+    fn schedule_redraw(&self) {
         if self.gl_or_metal_backed {
             let mtm = MainThreadMarker::new().unwrap();
             // `setNeedsDisplay` does nothing on UIViews which are directly backed by CAEAGLLayer or
@@ -145,13 +217,19 @@ impl Inner {
             // confirmed via testing.
             //
             // https://developer.apple.com/documentation/uikit/uiview/1622437-setneedsdisplay?language=objc
-            app_state::queue_gl_or_metal_redraw(mtm, self.window.clone());
+            app_state::queue_gl_or_metal_redraw(mtm, &self.view);
         } else {
             self.view.setNeedsDisplay();
         }
     }
 
-    pub fn pre_present_notify(&self) {}
+    pub fn pre_present_notify(&self) {
+        self.view.pre_present_notify();
+    }
+
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        self.view.set_presentation_feedback(enabled);
+    }
 
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let safe_area = self.safe_area_screen_space();
@@ -178,7 +256,10 @@ impl Inner {
             size: screen_frame.size,
         };
         let bounds = self.rect_from_screen_space(new_screen_frame);
-        self.window.setBounds(bounds);
+        match &self.window {
+            Some(window) => window.setBounds(bounds),
+            None => warn!("`Window::set_outer_position` is ignored for embedded views"),
+        }
     }
 
     pub fn inner_size(&self) -> PhysicalSize<u32> {
@@ -205,6 +286,16 @@ impl Inner {
         Some(self.inner_size())
     }
 
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        if !app_state::os_capabilities().safe_area {
+            return PhysicalInsets::new(0, 0, 0, 0);
+        }
+
+        let insets = self.outer_view().safeAreaInsets();
+        LogicalInsets::new(insets.top, insets.left, insets.bottom, insets.right)
+            .to_physical(self.scale_factor())
+    }
+
     pub fn set_min_inner_size(&self, _dimensions: Option<Size>) {
         warn!("`Window::set_min_inner_size` is ignored on iOS")
     }
@@ -222,6 +313,10 @@ impl Inner {
         warn!("`Window::set_resize_increments` is ignored on iOS")
     }
 
+    pub fn set_aspect_ratio(&self, _aspect_ratio: Option<AspectRatio>) {
+        warn!("`Window::set_aspect_ratio` is ignored on iOS")
+    }
+
     pub fn set_resizable(&self, _resizable: bool) {
         warn!("`Window::set_resizable` is ignored on iOS")
     }
@@ -242,12 +337,20 @@ impl Inner {
         WindowButtons::all()
     }
 
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::empty()
+    }
+
     pub fn scale_factor(&self) -> f64 {
         self.view.contentScaleFactor() as _
     }
 
-    pub fn set_cursor(&self, _cursor: Cursor) {
-        debug!("`Window::set_cursor` ignored on iOS")
+    pub fn set_cursor(&self, cursor: Cursor) {
+        match cursor {
+            Cursor::Icon(icon) => self.view.set_cursor_icon(icon),
+            Cursor::Custom(_) => debug!("custom cursors are not supported on iOS"),
+        }
     }
 
     pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
@@ -258,18 +361,26 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
-    pub fn set_cursor_visible(&self, _visible: bool) {
-        debug!("`Window::set_cursor_visible` is ignored on iOS")
+    pub fn set_cursor_visible(&self, visible: bool) {
+        self.view.set_cursor_visible(visible)
     }
 
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn set_transient_parent(&self, _parent: Option<&Window>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn start_drag_and_drop(&self, _payload: DragPayload) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
@@ -297,11 +408,16 @@ impl Inner {
 
     pub(crate) fn set_fullscreen(&self, monitor: Option<Fullscreen>) {
         let mtm = MainThreadMarker::new().unwrap();
+        let (Some(window), Some(view_controller)) = (&self.window, &self.view_controller) else {
+            warn!("`Window::set_fullscreen` is ignored for embedded views");
+            return;
+        };
         let uiscreen = match &monitor {
             Some(Fullscreen::Exclusive(video_mode)) => {
-                let uiscreen = video_mode.monitor.ui_screen(mtm);
-                uiscreen.setCurrentMode(Some(video_mode.screen_mode(mtm)));
-                uiscreen.clone()
+                if video_mode.monitor.set_video_mode(video_mode).is_err() {
+                    warn!("video modes can only be set on external displays, ignoring");
+                }
+                video_mode.monitor.ui_screen(mtm).clone()
             },
             Some(Fullscreen::Borderless(Some(monitor))) => monitor.ui_screen(mtm).clone(),
             Some(Fullscreen::Borderless(None)) => {
@@ -314,13 +430,16 @@ impl Inner {
         };
 
         // this is pretty slow on iOS, so avoid doing it if we can
-        let current = self.window.screen();
+        let current = window.screen();
         if uiscreen != current {
-            self.window.setScreen(&uiscreen);
+            window.setScreen(&uiscreen);
+            view_controller.reapply_system_preferences();
+            self.view.update_display_link();
+            self.view.update_presentation_link();
         }
 
         let bounds = uiscreen.bounds();
-        self.window.setFrame(bounds);
+        window.setFrame(bounds);
 
         // For external displays, we must disable overscan compensation or
         // the displayed image will have giant black bars surrounding it on
@@ -330,6 +449,7 @@ impl Inner {
 
     pub(crate) fn fullscreen(&self) -> Option<Fullscreen> {
         let mtm = MainThreadMarker::new().unwrap();
+        self.window.as_ref()?;
         let monitor = self.current_monitor_inner();
         let uiscreen = monitor.ui_screen(mtm);
         let screen_space_bounds = self.screen_frame();
@@ -361,16 +481,27 @@ impl Inner {
         warn!("`Window::set_window_icon` is ignored on iOS")
     }
 
-    pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {
-        warn!("`Window::set_ime_cursor_area` is ignored on iOS")
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        let scale_factor = self.scale_factor();
+        let position = position.to_logical::<f64>(scale_factor);
+        let size = size.to_logical::<f64>(scale_factor);
+        // The view covers the whole window, so window coordinates are view coordinates.
+        self.view.set_ime_cursor_area(CGRect {
+            origin: CGPoint { x: position.x as _, y: position.y as _ },
+            size: CGSize { width: size.width as _, height: size.height as _ },
+        });
+    }
+
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.view.set_ime_allowed(allowed);
     }
 
-    pub fn set_ime_allowed(&self, _allowed: bool) {
-        warn!("`Window::set_ime_allowed` is ignored on iOS")
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.view.set_ime_purpose(purpose);
     }
 
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {
-        warn!("`Window::set_ime_allowed` is ignored on iOS")
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        Some(ImeCapabilities::all())
     }
 
     pub fn focus_window(&self) {
@@ -381,9 +512,27 @@ impl Inner {
         warn!("`Window::request_user_attention` is ignored on iOS")
     }
 
+    pub fn set_progress(&self, _state: ProgressState) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        if self.badge_count.replace(count) == count {
+            return;
+        }
+
+        let application = UIApplication::sharedApplication(MainThreadMarker::new().unwrap());
+        let count = count.map_or(0, |count| count.min(isize::MAX as u64) as isize);
+        // `UNUserNotificationCenter::setBadgeCount` is only available since iOS 16.
+        #[allow(deprecated)]
+        unsafe {
+            application.setApplicationIconBadgeNumber(count)
+        };
+    }
+
     // Allow directly accessing the current monitor internally without unwrapping.
     fn current_monitor_inner(&self) -> MonitorHandle {
-        MonitorHandle::new(self.window.screen())
+        MonitorHandle::new(self.screen())
     }
 
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
@@ -400,24 +549,24 @@ impl Inner {
     }
 
     pub fn id(&self) -> WindowId {
-        self.window.id()
+        self.view.id()
     }
 
     #[cfg(feature = "rwh_04")]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
         let mut window_handle = rwh_04::UiKitHandle::empty();
-        window_handle.ui_window = Retained::as_ptr(&self.window) as _;
+        window_handle.ui_window = self.ui_window_ptr();
         window_handle.ui_view = Retained::as_ptr(&self.view) as _;
-        window_handle.ui_view_controller = Retained::as_ptr(&self.view_controller) as _;
+        window_handle.ui_view_controller = self.ui_view_controller_ptr();
         rwh_04::RawWindowHandle::UiKit(window_handle)
     }
 
     #[cfg(feature = "rwh_05")]
     pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
         let mut window_handle = rwh_05::UiKitWindowHandle::empty();
-        window_handle.ui_window = Retained::as_ptr(&self.window) as _;
+        window_handle.ui_window = self.ui_window_ptr();
         window_handle.ui_view = Retained::as_ptr(&self.view) as _;
-        window_handle.ui_view_controller = Retained::as_ptr(&self.view_controller) as _;
+        window_handle.ui_view_controller = self.ui_view_controller_ptr();
         rwh_05::RawWindowHandle::UiKit(window_handle)
     }
 
@@ -432,30 +581,44 @@ impl Inner {
             let ui_view = Retained::as_ptr(&self.view) as _;
             std::ptr::NonNull::new(ui_view).expect("Retained<T> should never be null")
         });
-        window_handle.ui_view_controller =
-            std::ptr::NonNull::new(Retained::as_ptr(&self.view_controller) as _);
+        window_handle.ui_view_controller = std::ptr::NonNull::new(self.ui_view_controller_ptr());
         rwh_06::RawWindowHandle::UiKit(window_handle)
     }
 
     pub fn theme(&self) -> Option<Theme> {
-        warn!("`Window::theme` is ignored on iOS");
-        None
+        if app_state::os_capabilities().user_interface_style {
+            Some(theme(unsafe { self.outer_view().traitCollection().userInterfaceStyle() }))
+        } else {
+            Some(Theme::Light)
+        }
     }
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
     pub fn has_focus(&self) -> bool {
-        self.window.isKeyWindow()
+        match &self.window {
+            Some(window) => window.isKeyWindow(),
+            None => unsafe { self.view.isFirstResponder() },
+        }
     }
 
     #[inline]
-    pub fn set_theme(&self, _theme: Option<Theme>) {
-        warn!("`Window::set_theme` is ignored on iOS");
+    pub fn set_theme(&self, theme: Option<Theme>) {
+        let os_capabilities = app_state::os_capabilities();
+        if os_capabilities.user_interface_style {
+            let style = match theme {
+                Some(Theme::Light) => UIUserInterfaceStyle::Light,
+                Some(Theme::Dark) => UIUserInterfaceStyle::Dark,
+                None => UIUserInterfaceStyle::Unspecified,
+            };
+            unsafe { self.outer_view().setOverrideUserInterfaceStyle(style) };
+        } else {
+            os_capabilities.user_interface_style_err_msg("ignoring");
+        }
     }
 
     pub fn title(&self) -> String {
-        warn!("`Window::title` is ignored on iOS");
-        String::new()
+        self.title.borrow().clone()
     }
 
     pub fn reset_dead_keys(&self) {
@@ -483,6 +646,12 @@ impl Window {
 
         // TODO: transparency, visible
 
+        if let Some(parent_view) = &window_attributes.platform_specific.parent_view {
+            // SAFETY: The caller of `with_parent_view` guarantees that this is a valid `UIView`.
+            let parent_view = unsafe { &*parent_view.0.as_ptr().cast::<UIView>() };
+            return Ok(Window::new_embedded(mtm, &window_attributes, parent_view));
+        }
+
         #[allow(deprecated)]
         let main_screen = UIScreen::mainScreen(mtm);
         let fullscreen = window_attributes.fullscreen.clone().map(Into::into);
@@ -513,6 +682,7 @@ impl Window {
 
         let view_controller = WinitViewController::new(mtm, &window_attributes, &view);
         let window = WinitUIWindow::new(mtm, &window_attributes, frame, &view_controller);
+        view.set_winit_window(&window);
 
         app_state::set_key_window(mtm, &window);
 
@@ -533,7 +703,7 @@ impl Window {
             app_state::handle_nonuser_events(
                 mtm,
                 std::iter::once(EventWrapper::ScaleFactorChanged(app_state::ScaleFactorChanged {
-                    window: window.clone(),
+                    view: view.clone(),
                     scale_factor,
                     suggested_size: size.to_physical(scale_factor),
                 }))
@@ -546,10 +716,47 @@ impl Window {
             );
         }
 
-        let inner = Inner { window, view_controller, view, gl_or_metal_backed };
+        let inner = Inner {
+            window: Some(window),
+            view_controller: Some(view_controller),
+            view,
+            gl_or_metal_backed,
+            badge_count: Cell::new(None),
+            title: RefCell::new(window_attributes.title),
+        };
         Ok(Window { inner: MainThreadBound::new(inner, mtm) })
     }
 
+    /// Create the view as a subview covering `parent_view`, without a window of its own.
+    fn new_embedded(
+        mtm: MainThreadMarker,
+        window_attributes: &WindowAttributes,
+        parent_view: &UIView,
+    ) -> Window {
+        let view = WinitView::new(mtm, window_attributes, parent_view.bounds());
+
+        let gl_or_metal_backed =
+            view.isKindOfClass(class!(CAMetalLayer)) || view.isKindOfClass(class!(CAEAGLLayer));
+
+        view.setHidden(!window_attributes.visible);
+        unsafe {
+            view.setAutoresizingMask(
+                UIViewAutoresizing::FlexibleWidth | UIViewAutoresizing::FlexibleHeight,
+            );
+            parent_view.addSubview(&view);
+        }
+
+        let inner = Inner {
+            window: None,
+            view_controller: None,
+            view,
+            gl_or_metal_backed,
+            badge_count: Cell::new(None),
+            title: RefCell::new(window_attributes.title.clone()),
+        };
+        Window { inner: MainThreadBound::new(inner, mtm) }
+    }
+
     pub(crate) fn maybe_queue_on_main(&self, f: impl FnOnce(&Inner) + Send + 'static) {
         // For now, don't actually do queuing, since it may be less predictable
         self.maybe_wait_on_main(f)
@@ -592,26 +799,115 @@ impl Inner {
     }
 
     pub fn set_valid_orientations(&self, valid_orientations: ValidOrientations) {
-        self.view_controller.set_supported_interface_orientations(
-            MainThreadMarker::new().unwrap(),
-            valid_orientations,
-        );
+        if let Some(view_controller) = self.view_controller("WindowExtIOS::set_valid_orientations")
+        {
+            view_controller.set_supported_interface_orientations(
+                MainThreadMarker::new().unwrap(),
+                valid_orientations,
+            );
+        }
+    }
+
+    pub fn set_supported_interface_orientations(&self, orientations: OrientationMask) {
+        if let Some(view_controller) =
+            self.view_controller("WindowExtIOS::set_supported_interface_orientations")
+        {
+            view_controller.set_supported_orientation_mask(orientations);
+        }
+    }
+
+    pub fn interface_orientation(&self) -> Option<InterfaceOrientation> {
+        self.view_controller.as_ref()?.interface_orientation()
+    }
+
+    pub fn text_scale_factor(&self) -> f64 {
+        let category =
+            unsafe { self.outer_view().traitCollection().preferredContentSizeCategory() };
+        text_scale_factor(&category)
     }
 
     pub fn set_prefers_home_indicator_hidden(&self, hidden: bool) {
-        self.view_controller.set_prefers_home_indicator_auto_hidden(hidden);
+        if let Some(view_controller) =
+            self.view_controller("WindowExtIOS::set_prefers_home_indicator_hidden")
+        {
+            view_controller.set_prefers_home_indicator_auto_hidden(hidden);
+        }
+    }
+
+    pub fn prefers_home_indicator_hidden(&self) -> bool {
+        self.view_controller
+            .as_ref()
+            .is_some_and(|view_controller| view_controller.home_indicator_auto_hidden())
     }
 
     pub fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge) {
-        self.view_controller.set_preferred_screen_edges_deferring_system_gestures(edges);
+        if let Some(view_controller) = self
+            .view_controller("WindowExtIOS::set_preferred_screen_edges_deferring_system_gestures")
+        {
+            view_controller.set_preferred_screen_edges_deferring_system_gestures(edges);
+        }
+    }
+
+    pub fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge {
+        self.view_controller.as_ref().map_or(ScreenEdge::NONE, |view_controller| {
+            view_controller.screen_edges_deferring_system_gestures()
+        })
     }
 
     pub fn set_prefers_status_bar_hidden(&self, hidden: bool) {
-        self.view_controller.set_prefers_status_bar_hidden(hidden);
+        if let Some(view_controller) =
+            self.view_controller("WindowExtIOS::set_prefers_status_bar_hidden")
+        {
+            view_controller.set_prefers_status_bar_hidden(hidden);
+        }
     }
 
     pub fn set_preferred_status_bar_style(&self, status_bar_style: StatusBarStyle) {
-        self.view_controller.set_preferred_status_bar_style(status_bar_style);
+        if let Some(view_controller) =
+            self.view_controller("WindowExtIOS::set_preferred_status_bar_style")
+        {
+            view_controller.set_preferred_status_bar_style(status_bar_style);
+        }
+    }
+
+    pub fn set_status_bar_update_animation(&self, animation: StatusBarAnimation) {
+        if let Some(view_controller) =
+            self.view_controller("WindowExtIOS::set_status_bar_update_animation")
+        {
+            view_controller.set_preferred_status_bar_update_animation(animation);
+        }
+    }
+
+    pub fn set_preferred_frame_rate_range(&self, min: f32, preferred: f32, max: f32) {
+        if (min, preferred, max) == (0.0, 0.0, 0.0) {
+            self.view.set_preferred_frame_rate_range(None);
+            return;
+        }
+
+        let maximum = self.maximum_frame_rate();
+        let clamp = |value: f32| if value.is_nan() { maximum } else { value.clamp(1.0, maximum) };
+        let min_clamped = clamp(min);
+        let max_clamped = clamp(max).max(min_clamped);
+        let range = CAFrameRateRange {
+            minimum: min_clamped,
+            maximum: max_clamped,
+            preferred: clamp(preferred).clamp(min_clamped, max_clamped),
+        };
+        if (range.minimum, range.preferred, range.maximum) != (min, preferred, max) {
+            debug!(
+                "clamped frame rate range {min}..={max} (preferred {preferred}) to {}..={} \
+                 (preferred {})",
+                range.minimum, range.maximum, range.preferred,
+            );
+        }
+
+        self.view.set_preferred_frame_rate_range(Some(range));
+    }
+
+    pub fn maximum_frame_rate(&self) -> f32 {
+        let refresh_rate_millihertz =
+            self.current_monitor_inner().refresh_rate_millihertz().unwrap_or(60_000);
+        refresh_rate_millihertz as f32 / 1000.0
     }
 
     pub fn recognize_pinch_gesture(&self, should_recognize: bool) {
@@ -638,27 +934,81 @@ impl Inner {
     pub fn recognize_rotation_gesture(&self, should_recognize: bool) {
         self.view.recognize_rotation_gesture(should_recognize);
     }
+
+    pub fn recognize_stylus_hover(&self, should_recognize: bool) {
+        self.view.recognize_stylus_hover(should_recognize);
+    }
+
+    pub fn set_coalesced_touches(&self, enabled: bool) {
+        self.view.set_coalesced_touches(enabled);
+    }
 }
 
 impl Inner {
+    /// The view covering the whole window, which is the view itself if it is embedded.
+    fn outer_view(&self) -> &UIView {
+        match &self.window {
+            Some(window) => window,
+            None => &self.view,
+        }
+    }
+
+    fn screen(&self) -> Retained<UIScreen> {
+        if let Some(window) = &self.window {
+            return window.screen();
+        }
+        match self.view.window() {
+            Some(window) => window.screen(),
+            // Embedded views are shown on the main screen until the host adds them to a window.
+            #[allow(deprecated)]
+            None => UIScreen::mainScreen(MainThreadMarker::new().unwrap()),
+        }
+    }
+
+    #[cfg(any(feature = "rwh_04", feature = "rwh_05"))]
+    fn ui_window_ptr(&self) -> *mut c_void {
+        match &self.window {
+            Some(window) => Retained::as_ptr(window) as _,
+            None => self
+                .view
+                .window()
+                .map_or(std::ptr::null_mut(), |window| Retained::as_ptr(&window) as _),
+        }
+    }
+
+    fn ui_view_controller_ptr(&self) -> *mut c_void {
+        self.view_controller
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |view_controller| Retained::as_ptr(view_controller) as _)
+    }
+
+    /// The view controller of the window, or a warning that `method` is ignored if the view is
+    /// embedded.
+    fn view_controller(&self, method: &str) -> Option<&WinitViewController> {
+        if self.view_controller.is_none() {
+            warn!("`{method}` is ignored for embedded views");
+        }
+        self.view_controller.as_deref()
+    }
+
     fn screen_frame(&self) -> CGRect {
-        self.rect_to_screen_space(self.window.bounds())
+        self.rect_to_screen_space(self.outer_view().bounds())
     }
 
     fn rect_to_screen_space(&self, rect: CGRect) -> CGRect {
-        let screen_space = self.window.screen().coordinateSpace();
-        self.window.convertRect_toCoordinateSpace(rect, &screen_space)
+        let screen_space = self.screen().coordinateSpace();
+        self.outer_view().convertRect_toCoordinateSpace(rect, &screen_space)
     }
 
     fn rect_from_screen_space(&self, rect: CGRect) -> CGRect {
-        let screen_space = self.window.screen().coordinateSpace();
-        self.window.convertRect_fromCoordinateSpace(rect, &screen_space)
+        let screen_space = self.screen().coordinateSpace();
+        self.outer_view().convertRect_fromCoordinateSpace(rect, &screen_space)
     }
 
     fn safe_area_screen_space(&self) -> CGRect {
-        let bounds = self.window.bounds();
+        let bounds = self.outer_view().bounds();
         if app_state::os_capabilities().safe_area {
-            let safe_area = self.window.safeAreaInsets();
+            let safe_area = self.outer_view().safeAreaInsets();
             let safe_bounds = CGRect {
                 origin: CGPoint {
                     x: bounds.origin.x + safe_area.left,
@@ -729,8 +1079,19 @@ impl From<&AnyObject> for WindowId {
 pub struct PlatformSpecificWindowAttributes {
     pub scale_factor: Option<f64>,
     pub valid_orientations: ValidOrientations,
+    pub supported_interface_orientations: Option<OrientationMask>,
     pub prefers_home_indicator_hidden: bool,
     pub prefers_status_bar_hidden: bool,
     pub preferred_status_bar_style: StatusBarStyle,
+    pub preferred_status_bar_update_animation: StatusBarAnimation,
     pub preferred_screen_edges_deferring_system_gestures: ScreenEdge,
+    pub parent_view: Option<ParentView>,
 }
+
+/// A `UIView` of the application to embed the window's view into.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParentView(pub NonNull<c_void>);
+
+// SAFETY: The view is only accessed on the main thread, when the window is created.
+unsafe impl Send for ParentView {}
+unsafe impl Sync for ParentView {}
@@ -0,0 +1,23 @@
+//! Conversion of the timestamps of the system clocks that compositors report.
+
+use std::mem::MaybeUninit;
+use std::time::{Duration, Instant};
+
+/// Convert a timestamp of the clock `clock_id` to an [`Instant`].
+pub fn to_instant(clock_id: libc::clockid_t, timestamp: Duration) -> Option<Instant> {
+    let now = unsafe {
+        let mut now = MaybeUninit::<libc::timespec>::uninit();
+        if libc::clock_gettime(clock_id, now.as_mut_ptr()) != 0 {
+            return None;
+        }
+        now.assume_init()
+    };
+    let now_timestamp = Duration::new(now.tv_sec as u64, now.tv_nsec as u32);
+
+    let instant = Instant::now();
+    if timestamp <= now_timestamp {
+        instant.checked_sub(now_timestamp - timestamp)
+    } else {
+        instant.checked_add(timestamp - now_timestamp)
+    }
+}
@@ -0,0 +1,407 @@
+//! Progress and count reporting through the `com.canonical.Unity.LauncherEntry` DBus interface.
+//!
+//! The interface only requires emitting an `Update` signal on the session bus, so instead of
+//! depending on a complete DBus implementation we speak the small subset of the wire protocol
+//! needed for that: the `EXTERNAL` authentication, the `Hello` call and the signal itself.
+//!
+//! See <https://wiki.ubuntu.com/Unity/LauncherAPI> for the interface description and
+//! <https://dbus.freedesktop.org/doc/dbus-specification.html> for the wire format.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{env, io};
+
+use crate::platform_impl::ApplicationName;
+use crate::window::ProgressState;
+
+const MESSAGE_METHOD_CALL: u8 = 1;
+const MESSAGE_SIGNAL: u8 = 4;
+
+const FIELD_PATH: u8 = 1;
+const FIELD_INTERFACE: u8 = 2;
+const FIELD_MEMBER: u8 = 3;
+const FIELD_DESTINATION: u8 = 6;
+const FIELD_SIGNATURE: u8 = 8;
+
+/// The session bus connection, shared by all windows.
+///
+/// It is kept open for the whole lifetime of the application, since launchers drop the
+/// progress and count of an entry once its sender disconnects from the bus.
+static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// The launcher entry of a window.
+#[derive(Debug)]
+pub struct LauncherEntry {
+    /// The `application://<desktop file id>` uri identifying the entry.
+    app_uri: String,
+    /// The last state sent to the launcher.
+    state: Mutex<EntryState>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct EntryState {
+    progress: ProgressState,
+    count: Option<u64>,
+}
+
+impl LauncherEntry {
+    /// Create the launcher entry from the application name of the window, or from the name of
+    /// the binary when there's none.
+    pub fn new(name: Option<&ApplicationName>) -> Self {
+        let desktop_file_id = match name {
+            Some(name) => name.general.clone(),
+            None => env::args_os()
+                .next()
+                .as_ref()
+                .and_then(|path| Path::new(path).file_name())
+                .and_then(|bin_name| bin_name.to_str())
+                .map(|bin_name| bin_name.to_owned())
+                .unwrap_or_default(),
+        };
+
+        Self {
+            app_uri: format!("application://{desktop_file_id}.desktop"),
+            state: Mutex::new(EntryState::default()),
+        }
+    }
+
+    pub fn set_progress(&self, progress: ProgressState) -> io::Result<()> {
+        self.update(|state| state.progress = progress)
+    }
+
+    pub fn set_count(&self, count: Option<u64>) -> io::Result<()> {
+        self.update(|state| state.count = count)
+    }
+
+    fn update(&self, f: impl FnOnce(&mut EntryState)) -> io::Result<()> {
+        let mut current = self.state.lock().unwrap();
+        let mut state = *current;
+        f(&mut state);
+        if *current == state {
+            return Ok(());
+        }
+
+        let body = update_body(&self.app_uri, state);
+
+        let mut connection = CONNECTION.lock().unwrap();
+        if connection.is_none() {
+            *connection = Some(Connection::open()?);
+        }
+
+        let conn = connection.as_mut().unwrap();
+        if let Err(err) = conn.emit_update(&body) {
+            // Try to reconnect on the next update.
+            *connection = None;
+            return Err(err);
+        }
+
+        *current = state;
+        Ok(())
+    }
+}
+
+impl Drop for LauncherEntry {
+    fn drop(&mut self) {
+        if let Err(err) = self.update(|state| *state = EntryState::default()) {
+            tracing::warn!("Failed to clear the launcher entry: {err}");
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Connection {
+    stream: UnixStream,
+    serial: u32,
+}
+
+impl Connection {
+    fn open() -> io::Result<Self> {
+        let mut stream = connect_session_bus()?;
+
+        // The uid is sent as the hex encoding of its decimal representation.
+        let uid = rustix::process::getuid().as_raw().to_string();
+        let uid: String = uid.bytes().map(|byte| format!("{byte:02x}")).collect();
+        stream.write_all(format!("\0AUTH EXTERNAL {uid}\r\n").as_bytes())?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        if !line.starts_with("OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("DBus authentication failed: {}", line.trim_end()),
+            ));
+        }
+        stream.write_all(b"BEGIN\r\n")?;
+
+        let mut connection = Self { stream, serial: 0 };
+
+        // The bus requires `Hello` to be the first message, but we don't care about its reply.
+        let hello = connection.message(
+            MESSAGE_METHOD_CALL,
+            &[
+                (FIELD_PATH, b'o', "/org/freedesktop/DBus"),
+                (FIELD_INTERFACE, b's', "org.freedesktop.DBus"),
+                (FIELD_MEMBER, b's', "Hello"),
+                (FIELD_DESTINATION, b's', "org.freedesktop.DBus"),
+            ],
+            &[],
+        );
+        connection.stream.write_all(&hello)?;
+
+        Ok(connection)
+    }
+
+    fn emit_update(&mut self, body: &[u8]) -> io::Result<()> {
+        let path = format!("/com/canonical/unity/launcherentry/{}", std::process::id());
+        let update = self.message(
+            MESSAGE_SIGNAL,
+            &[
+                (FIELD_PATH, b'o', &path),
+                (FIELD_INTERFACE, b's', "com.canonical.Unity.LauncherEntry"),
+                (FIELD_MEMBER, b's', "Update"),
+                (FIELD_SIGNATURE, b'g', "sa{sv}"),
+            ],
+            body,
+        );
+        self.stream.write_all(&update)
+    }
+
+    fn message(&mut self, kind: u8, fields: &[(u8, u8, &str)], body: &[u8]) -> Vec<u8> {
+        self.serial += 1;
+        encode_message(kind, self.serial, fields, body)
+    }
+}
+
+fn connect_session_bus() -> io::Result<UnixStream> {
+    let not_found = || io::Error::new(io::ErrorKind::NotFound, "no DBus session bus address");
+
+    let addresses = match env::var("DBUS_SESSION_BUS_ADDRESS") {
+        Ok(addresses) => addresses,
+        Err(_) => {
+            let runtime_dir = env::var_os("XDG_RUNTIME_DIR").ok_or_else(not_found)?;
+            return UnixStream::connect(PathBuf::from(runtime_dir).join("bus"));
+        },
+    };
+
+    let mut last_err = not_found();
+    for address in addresses.split(';') {
+        let Some(params) = address.strip_prefix("unix:") else {
+            continue;
+        };
+
+        for param in params.split(',') {
+            let result = if let Some(path) = param.strip_prefix("path=") {
+                UnixStream::connect(unescape(path))
+            } else if let Some(name) = param.strip_prefix("abstract=") {
+                connect_abstract(&unescape(name))
+            } else {
+                continue;
+            };
+
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_err = err,
+            }
+        }
+    }
+
+    Err(last_err)
+}
+
+#[cfg(target_os = "linux")]
+fn connect_abstract(name: &str) -> io::Result<UnixStream> {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::SocketAddr;
+
+    UnixStream::connect_addr(&SocketAddr::from_abstract_name(name)?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn connect_abstract(_name: &str) -> io::Result<UnixStream> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "abstract sockets are only available on Linux"))
+}
+
+/// Decode the `%xx` escapes of a DBus address value.
+fn unescape(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex: Vec<u8> = iter.by_ref().take(2).collect();
+            match std::str::from_utf8(&hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                Some(byte) => bytes.push(byte),
+                None => {
+                    bytes.push(byte);
+                    bytes.extend_from_slice(&hex);
+                },
+            }
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Build the body of the `Update` signal, with the `sa{sv}` signature.
+fn update_body(app_uri: &str, state: EntryState) -> Vec<u8> {
+    let visible = state.progress != ProgressState::None;
+    let progress = state.progress.value().unwrap_or(0.0);
+    let urgent = matches!(state.progress, ProgressState::Error(_));
+    // The count is signed on the bus.
+    let count = state.count.map_or(0, |count| count.min(i64::MAX as u64) as i64);
+
+    let mut body = Vec::new();
+    put_string(&mut body, app_uri);
+
+    let length_offset = start_array(&mut body);
+    let start = body.len();
+
+    align(&mut body, 8);
+    put_string(&mut body, "progress");
+    put_signature(&mut body, "d");
+    align(&mut body, 8);
+    body.extend_from_slice(&progress.to_le_bytes());
+
+    align(&mut body, 8);
+    put_string(&mut body, "count");
+    put_signature(&mut body, "x");
+    align(&mut body, 8);
+    body.extend_from_slice(&count.to_le_bytes());
+
+    for (key, value) in [
+        ("progress-visible", visible),
+        ("count-visible", state.count.is_some()),
+        ("urgent", urgent),
+    ] {
+        align(&mut body, 8);
+        put_string(&mut body, key);
+        put_signature(&mut body, "b");
+        put_u32(&mut body, value as u32);
+    }
+
+    end_array(&mut body, length_offset, start);
+    body
+}
+
+/// Encode a little endian message with the given header fields, given as `(code, type, value)`.
+fn encode_message(kind: u8, serial: u32, fields: &[(u8, u8, &str)], body: &[u8]) -> Vec<u8> {
+    let mut message = vec![b'l', kind, 0, 1];
+    put_u32(&mut message, body.len() as u32);
+    put_u32(&mut message, serial);
+
+    let length_offset = start_array(&mut message);
+    let start = message.len();
+    for &(code, ty, value) in fields {
+        align(&mut message, 8);
+        message.push(code);
+        put_signature(&mut message, std::str::from_utf8(&[ty]).unwrap());
+        if ty == b'g' {
+            put_signature(&mut message, value);
+        } else {
+            put_string(&mut message, value);
+        }
+    }
+    end_array(&mut message, length_offset, start);
+
+    align(&mut message, 8);
+    message.extend_from_slice(body);
+    message
+}
+
+fn align(buf: &mut Vec<u8>, alignment: usize) {
+    let padding = (alignment - buf.len() % alignment) % alignment;
+    buf.resize(buf.len() + padding, 0);
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    align(buf, 4);
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_string(buf: &mut Vec<u8>, value: &str) {
+    put_u32(buf, value.len() as u32);
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+fn put_signature(buf: &mut Vec<u8>, value: &str) {
+    buf.push(value.len() as u8);
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+/// Write a placeholder for the length of an array of 8 byte aligned elements, returning its
+/// offset and aligning the buffer for the first element.
+fn start_array(buf: &mut Vec<u8>) -> usize {
+    put_u32(buf, 0);
+    let offset = buf.len() - 4;
+    align(buf, 8);
+    offset
+}
+
+fn end_array(buf: &mut [u8], length_offset: usize, start: usize) {
+    let length = (buf.len() - start) as u32;
+    buf[length_offset..length_offset + 4].copy_from_slice(&length.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_hello() {
+        let message = encode_message(
+            MESSAGE_METHOD_CALL,
+            1,
+            &[(FIELD_PATH, b'o', "/org/freedesktop/DBus"), (FIELD_MEMBER, b's', "Hello")],
+            &[],
+        );
+
+        let mut expected = vec![b'l', 1, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0];
+        // Fields array length.
+        expected.extend_from_slice(&46u32.to_le_bytes());
+        expected.extend_from_slice(&[1, 1, b'o', 0]);
+        expected.extend_from_slice(&21u32.to_le_bytes());
+        expected.extend_from_slice(b"/org/freedesktop/DBus\0");
+        expected.extend_from_slice(&[0; 2]);
+        expected.extend_from_slice(&[3, 1, b's', 0]);
+        expected.extend_from_slice(&5u32.to_le_bytes());
+        expected.extend_from_slice(b"Hello\0");
+        expected.extend_from_slice(&[0; 2]);
+
+        assert_eq!(message, expected);
+        assert_eq!(message.len() % 8, 0);
+    }
+
+    #[test]
+    fn update_body_layout() {
+        let state = EntryState { progress: ProgressState::Error(2.0), count: Some(u64::MAX) };
+        let body = update_body("application://a.desktop", state);
+
+        // The app uri, then the dictionary starting on the next 8 byte boundary.
+        assert_eq!(&body[..4], &23u32.to_le_bytes());
+        assert_eq!(&body[4..28], b"application://a.desktop\0");
+        let length = u32::from_le_bytes(body[28..32].try_into().unwrap()) as usize;
+        assert_eq!(body.len(), 32 + length);
+
+        // The progress is clamped.
+        let key_length = u32::from_le_bytes(body[32..36].try_into().unwrap()) as usize;
+        assert_eq!(&body[36..36 + key_length], b"progress");
+        assert_eq!(&body[48..56], &1.0f64.to_le_bytes());
+
+        // The count follows, clamped to the signed range.
+        assert_eq!(&body[60..66], b"count\0");
+        assert_eq!(&body[72..80], &i64::MAX.to_le_bytes());
+
+        // `urgent` is the last entry and is set for errors.
+        assert_eq!(&body[body.len() - 4..], &1u32.to_le_bytes());
+    }
+
+    #[test]
+    fn unescape_address() {
+        assert_eq!(unescape("/run/user/1000/bus"), "/run/user/1000/bus");
+        assert_eq!(unescape("/tmp/dbus%2dabc"), "/tmp/dbus-abc");
+    }
+}
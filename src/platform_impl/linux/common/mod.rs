@@ -1 +1,11 @@
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod clock;
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod launcher_entry;
+#[cfg(all(feature = "dbus", any(x11_platform, wayland_platform)))]
+pub mod session;
+#[cfg(any(x11_platform, wayland_platform))]
+pub mod uri_list;
+// The headless backend only uses the scancode conversions.
+#[cfg_attr(not(any(x11_platform, wayland_platform)), allow(dead_code, unused_imports))]
 pub mod xkb;
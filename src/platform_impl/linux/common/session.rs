@@ -0,0 +1,132 @@
+//! Session notifications from `systemd-logind` and `power-profiles-daemon` on the system bus.
+//!
+//! Unlike the launcher entry, listening for signals needs a complete DBus implementation, so
+//! this is only available with the `dbus` feature. The bus is served by a background thread
+//! which forwards the events to the event loop through a calloop channel.
+//!
+//! See <https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.login1.html>
+//! and <https://gitlab.freedesktop.org/upower/power-profiles-daemon> for the interfaces.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use calloop::channel::{self, Channel, Sender};
+use dbus::blocking::stdintf::org_freedesktop_dbus::{Properties, PropertiesPropertiesChanged};
+use dbus::blocking::Connection;
+use dbus::message::{MatchRule, SignalArgs};
+use dbus::{arg, Path};
+
+use crate::event::SessionEvent;
+
+const LOGIN1_NAME: &str = "org.freedesktop.login1";
+const LOGIN1_PATH: &str = "/org/freedesktop/login1";
+const LOGIN1_MANAGER: &str = "org.freedesktop.login1.Manager";
+const LOGIN1_SESSION: &str = "org.freedesktop.login1.Session";
+
+const POWER_PROFILES_NAME: &str = "net.hadess.PowerProfiles";
+const POWER_PROFILES_PATH: &str = "/net/hadess/PowerProfiles";
+const POWER_SAVER_PROFILE: &str = "power-saver";
+
+/// How long a single blocking call may take, and how often the stop flag is checked.
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Monitors the session on a background thread until dropped.
+#[derive(Debug)]
+pub struct SessionMonitor {
+    stopped: Arc<AtomicBool>,
+}
+
+impl SessionMonitor {
+    /// Start the monitor, returning the channel the [`SessionEvent`]s are delivered through.
+    pub fn new() -> (Self, Channel<SessionEvent>) {
+        let (sender, channel) = channel::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_stopped = stopped.clone();
+        let result = thread::Builder::new().name("winit session monitor".into()).spawn(move || {
+            if let Err(error) = listen(&sender, &thread_stopped) {
+                tracing::warn!("Failed to monitor the session: {error}");
+            }
+        });
+        if let Err(error) = result {
+            tracing::warn!("Failed to spawn the session monitor: {error}");
+        }
+
+        (Self { stopped }, channel)
+    }
+}
+
+impl Drop for SessionMonitor {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+fn listen(sender: &Sender<SessionEvent>, stopped: &AtomicBool) -> Result<(), dbus::Error> {
+    let connection = Connection::new_system()?;
+
+    let rule = MatchRule::new_signal(LOGIN1_MANAGER, "PrepareForSleep")
+        .with_sender(LOGIN1_NAME)
+        .with_path(LOGIN1_PATH);
+    let sleep_sender = sender.clone();
+    connection.add_match(rule, move |(start,): (bool,), _, _| {
+        let event = if start { SessionEvent::Suspending } else { SessionEvent::Resumed };
+        sleep_sender.send(event).is_ok()
+    })?;
+
+    // The lock signals are emitted on the object of the session, not on the manager.
+    let manager = connection.with_proxy(LOGIN1_NAME, LOGIN1_PATH, TIMEOUT);
+    let session: Result<(Path<'static>,), _> =
+        manager.method_call(LOGIN1_MANAGER, "GetSessionByPID", (std::process::id(),));
+    match session {
+        Ok((session,)) => {
+            for (member, event) in
+                [("Lock", SessionEvent::SessionLocked), ("Unlock", SessionEvent::SessionUnlocked)]
+            {
+                let rule = MatchRule::new_signal(LOGIN1_SESSION, member)
+                    .with_sender(LOGIN1_NAME)
+                    .with_path(session.clone());
+                let sender = sender.clone();
+                connection.add_match(rule, move |(): (), _, _| sender.send(event).is_ok())?;
+            }
+        },
+        Err(error) => tracing::warn!("Failed to find the login session: {error}"),
+    }
+
+    // The daemon is optional, the battery saver is just not reported without it.
+    let power_profiles = connection.with_proxy(POWER_PROFILES_NAME, POWER_PROFILES_PATH, TIMEOUT);
+    match power_profiles.get::<String>(POWER_PROFILES_NAME, "ActiveProfile") {
+        Ok(profile) => {
+            let mut power_saver = profile == POWER_SAVER_PROFILE;
+            let rule = PropertiesPropertiesChanged::match_rule(
+                Some(&POWER_PROFILES_NAME.into()),
+                Some(&POWER_PROFILES_PATH.into()),
+            )
+            .static_clone();
+            let sender = sender.clone();
+            connection.add_match(rule, move |changed: PropertiesPropertiesChanged, _, _| {
+                if changed.interface_name != POWER_PROFILES_NAME {
+                    return true;
+                }
+                let profile =
+                    arg::prop_cast::<String>(&changed.changed_properties, "ActiveProfile");
+                match profile.map(|profile| profile == POWER_SAVER_PROFILE) {
+                    Some(enabled) if enabled != power_saver => {
+                        power_saver = enabled;
+                        sender.send(SessionEvent::PowerSaverChanged(enabled)).is_ok()
+                    },
+                    _ => true,
+                }
+            })?;
+        },
+        Err(error) => tracing::debug!("Power profiles are not available: {error}"),
+    }
+
+    while !stopped.load(Ordering::Relaxed) {
+        connection.process(TIMEOUT)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! The `text/uri-list` format used to drag files.
+
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+
+use percent_encoding::{percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// The characters kept as is in the paths, as recommended by RFC 3986.
+const PATH: &AsciiSet =
+    &NON_ALPHANUMERIC.remove(b'/').remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+/// Encode the paths as `file://` URIs, one per line.
+pub fn encode(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("file://{}\r\n", percent_encode(path.as_os_str().as_bytes(), PATH)))
+        .collect()
+}
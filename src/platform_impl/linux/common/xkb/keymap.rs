@@ -1,7 +1,7 @@
 //! XKB keymap.
 
 use std::ffi::c_char;
-use std::ops::Deref;
+use std::ops::{Deref, RangeInclusive};
 use std::ptr::{self, NonNull};
 
 #[cfg(x11_platform)]
@@ -854,6 +854,40 @@ pub fn keysym_to_key(keysym: u32) -> Key {
     })
 }
 
+/// The character that the dead key produces on its own, which is also what pressing it twice
+/// produces with the usual compose tables.
+fn dead_keysym_to_char(keysym: u32) -> Option<char> {
+    use xkbcommon_dl::keysyms;
+    Some(match keysym {
+        keysyms::dead_grave => '`',
+        keysyms::dead_acute => '´',
+        keysyms::dead_circumflex => '^',
+        keysyms::dead_tilde => '~',
+        keysyms::dead_macron => '¯',
+        keysyms::dead_breve => '˘',
+        keysyms::dead_abovedot => '˙',
+        keysyms::dead_diaeresis => '¨',
+        keysyms::dead_abovering => '˚',
+        keysyms::dead_doubleacute => '˝',
+        keysyms::dead_caron => 'ˇ',
+        keysyms::dead_cedilla => '¸',
+        keysyms::dead_ogonek => '˛',
+        keysyms::dead_iota => 'ͺ',
+        keysyms::dead_voiced_sound => '゛',
+        keysyms::dead_semivoiced_sound => '゜',
+        keysyms::dead_stroke => '/',
+        keysyms::dead_belowcomma => ',',
+        keysyms::dead_currency => '¤',
+        keysyms::dead_lowline => '_',
+        _ => return None,
+    })
+}
+
+/// Whether the keysym is one of the dead keys, `dead_grave..dead_longsolidusoverlay`.
+fn is_dead_keysym(keysym: u32) -> bool {
+    (0xfe50..=0xfe93).contains(&keysym)
+}
+
 pub fn keysym_location(keysym: u32) -> KeyLocation {
     use xkbcommon_dl::keysyms;
     match keysym {
@@ -972,7 +1006,7 @@ impl XkbKeymap {
     }
 
     pub fn first_keysym_by_level(
-        &mut self,
+        &self,
         layout: xkb_layout_index_t,
         keycode: xkb_keycode_t,
     ) -> xkb_keysym_t {
@@ -995,12 +1029,62 @@ impl XkbKeymap {
         }
     }
 
+    /// The key that the keycode produces without modifiers in the layout.
+    ///
+    /// Dead keys are replaced by the character they produce on their own. The layout wraps
+    /// around for keys that have fewer layouts, like xkb does by default.
+    pub fn key_in_layout(&self, layout: xkb_layout_index_t, keycode: xkb_keycode_t) -> Key {
+        let num_layouts =
+            unsafe { (XKBH.xkb_keymap_num_layouts_for_key)(self.keymap.as_ptr(), keycode) };
+        if num_layouts == 0 {
+            return Key::Unidentified(NativeKey::Unidentified);
+        }
+
+        let keysym = self.first_keysym_by_level(layout % num_layouts, keycode);
+        match keysym_to_key(keysym) {
+            Key::Unidentified(native) => {
+                if is_dead_keysym(keysym) {
+                    return match dead_keysym_to_char(keysym) {
+                        Some(ch) => Key::Character(ch.to_string().into()),
+                        None => Key::Dead(None),
+                    };
+                }
+
+                let ch = unsafe { (XKBH.xkb_keysym_to_utf32)(keysym) };
+                match char::from_u32(ch).filter(|ch| *ch != '\0' && !ch.is_control()) {
+                    Some(ch) => Key::Character(ch.to_string().into()),
+                    None => Key::Unidentified(native),
+                }
+            },
+            key => key,
+        }
+    }
+
+    /// The keycodes of the keys in the keymap.
+    pub fn keycodes(&self) -> RangeInclusive<xkb_keycode_t> {
+        unsafe {
+            (XKBH.xkb_keymap_min_keycode)(self.keymap.as_ptr())
+                ..=(XKBH.xkb_keymap_max_keycode)(self.keymap.as_ptr())
+        }
+    }
+
     /// Check whether the given key repeats.
     pub fn key_repeats(&mut self, keycode: xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.keymap.as_ptr(), keycode) == 1 }
     }
 }
 
+impl Clone for XkbKeymap {
+    fn clone(&self) -> Self {
+        unsafe { (XKBH.xkb_keymap_ref)(self.keymap.as_ptr()) };
+        Self {
+            keymap: self.keymap,
+            _mods_indices: self._mods_indices,
+            _core_keyboard_id: self._core_keyboard_id,
+        }
+    }
+}
+
 impl Drop for XkbKeymap {
     fn drop(&mut self) {
         unsafe {
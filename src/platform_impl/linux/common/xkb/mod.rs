@@ -9,14 +9,14 @@ use smol_str::SmolStr;
 use std::os::unix::io::OwnedFd;
 use tracing::warn;
 use xkbcommon_dl::{
-    self as xkb, xkb_compose_status, xkb_context, xkb_context_flags, xkbcommon_compose_handle,
-    xkbcommon_handle, XkbCommon, XkbCommonCompose,
+    self as xkb, xkb_compose_status, xkb_context, xkb_context_flags, xkb_layout_index_t,
+    xkbcommon_compose_handle, xkbcommon_handle, XkbCommon, XkbCommonCompose,
 };
 #[cfg(x11_platform)]
 use {x11_dl::xlib_xcb::xcb_connection_t, xkbcommon_dl::x11::xkbcommon_x11_handle};
 
 use crate::event::{ElementState, KeyEvent};
-use crate::keyboard::{Key, KeyLocation};
+use crate::keyboard::{self, Key, KeyCode, KeyLocation, NativeKey, PhysicalKey};
 use crate::platform_impl::KeyEventExtra;
 
 mod compose;
@@ -150,6 +150,13 @@ impl Context {
         self.keymap = keymap;
     }
 
+    /// The active keyboard layout, or `None` while the keymap is unknown.
+    pub fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        let keymap = self.keymap.as_ref()?.clone();
+        let layout = self.state.as_ref()?.effective_layout();
+        Some(KeyboardLayout { keymap, layout })
+    }
+
     /// Key builder context with the user provided xkb state.
     pub fn key_context(&mut self) -> Option<KeyContext<'_>> {
         let state = self.state.as_mut()?;
@@ -176,6 +183,42 @@ impl Context {
     }
 }
 
+/// A keymap and the layout selected in it, to map keys without touching the state of the
+/// keyboard.
+#[derive(Debug, Clone)]
+pub struct KeyboardLayout {
+    keymap: XkbKeymap,
+    layout: xkb_layout_index_t,
+}
+
+impl KeyboardLayout {
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        match keymap::physicalkey_to_scancode(PhysicalKey::Code(code)) {
+            Some(scancode) => self.keymap.key_in_layout(self.layout, scancode + 8),
+            None => Key::Unidentified(NativeKey::Unidentified),
+        }
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        let keys = self.keymap.keycodes().filter_map(|keycode| {
+            match keymap::raw_keycode_to_physicalkey(keycode) {
+                PhysicalKey::Code(code) => {
+                    Some((code, self.keymap.key_in_layout(self.layout, keycode)))
+                },
+                PhysicalKey::Unidentified(_) => None,
+            }
+        });
+        keyboard::find_key_code(key, keys)
+    }
+}
+
+impl PartialEq for KeyboardLayout {
+    fn eq(&self, other: &Self) -> bool {
+        // A keymap that is still referenced can't share its address with a new one.
+        self.keymap.as_ptr() == other.keymap.as_ptr() && self.layout == other.layout
+    }
+}
+
 pub struct KeyContext<'a> {
     pub state: &'a mut XkbState,
     pub keymap: &'a mut XkbKeymap,
@@ -52,6 +52,16 @@ impl XkbState {
         unsafe { (XKBH.xkb_state_key_get_layout)(self.state.as_ptr(), key) }
     }
 
+    /// The effective layout, i.e. the layout group selected by the user.
+    pub fn effective_layout(&self) -> xkb_layout_index_t {
+        unsafe {
+            (XKBH.xkb_state_serialize_layout)(
+                self.state.as_ptr(),
+                xkb_state_component::XKB_STATE_LAYOUT_EFFECTIVE,
+            )
+        }
+    }
+
     #[cfg(x11_platform)]
     pub fn depressed_modifiers(&mut self) -> xkb::xkb_mod_mask_t {
         unsafe {
@@ -187,3 +197,13 @@ impl From<ModifiersState> for crate::keyboard::ModifiersState {
         to_mods
     }
 }
+
+impl From<ModifiersState> for crate::event::Modifiers {
+    fn from(mods: ModifiersState) -> crate::event::Modifiers {
+        let mut modifiers =
+            crate::event::Modifiers::from(crate::keyboard::ModifiersState::from(mods));
+        modifiers.lock_keys.set(crate::keyboard::LockKeys::CAPS_LOCK, mods.caps_lock);
+        modifiers.lock_keys.set(crate::keyboard::LockKeys::NUM_LOCK, mods.num_lock);
+        modifiers
+    }
+}
@@ -0,0 +1,466 @@
+//! The event loop, dispatching the events queued by the windows and the drivers.
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::state::{root_window_id, PendingEvent, Shared};
+use super::MonitorHandle;
+use crate::application::ApplicationHandler;
+use crate::cursor::OnlyCursorImage;
+use crate::error::EventLoopError;
+use crate::event::{DeviceEvent, InnerSizeWriter, StartCause, WindowEvent};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEventSubscription, MotionEventPolicy,
+};
+use crate::keyboard::{Key, KeyCode};
+use crate::platform::pump_events::PumpStatus;
+use crate::platform_impl::platform::{min_timeout, OsError};
+use crate::platform_impl::{ActiveEventLoop as PlatformActiveEventLoop, PlatformCustomCursor};
+use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource};
+
+pub struct EventLoop {
+    /// Has `run` or `run_on_demand` been called or a call to `pump_events` that starts the loop
+    loop_running: bool,
+
+    window_target: RootActiveEventLoop,
+
+    /// Calloop's event loop, waiting for the wake-ups of the drivers and the proxies.
+    event_loop: calloop::EventLoop<'static, ()>,
+}
+
+impl EventLoop {
+    pub fn new() -> Result<EventLoop, EventLoopError> {
+        let map_err =
+            |error| EventLoopError::Os(os_error!(OsError::HeadlessError(Arc::new(error))));
+        let event_loop = calloop::EventLoop::<()>::try_new().map_err(map_err)?;
+
+        // The wake-ups only interrupt the wait, the events are in the shared state.
+        let (waker, waker_source) =
+            calloop::ping::make_ping().map_err(|error| map_err(calloop::Error::IoError(error)))?;
+        event_loop
+            .handle()
+            .insert_source(waker_source, |_, _, _| {})
+            .map_err(|error| map_err(error.error))?;
+
+        let shared = Shared::new(waker);
+        let window_target = ActiveEventLoop {
+            proxy: EventLoopProxy {
+                shared: shared.clone(),
+                wake_up: Arc::new(AtomicBool::new(false)),
+            },
+            shared,
+            control_flow: Cell::new(ControlFlow::default()),
+            motion_event_policy: Cell::new(MotionEventPolicy::default()),
+            exit: Cell::new(None),
+        };
+
+        Ok(EventLoop {
+            loop_running: false,
+            window_target: RootActiveEventLoop {
+                p: PlatformActiveEventLoop::Headless(window_target),
+                user_event_queue: Default::default(),
+                _marker: PhantomData,
+            },
+            event_loop,
+        })
+    }
+
+    pub fn run_app<A: ApplicationHandler>(mut self, app: &mut A) -> Result<(), EventLoopError> {
+        self.run_app_on_demand(app)
+    }
+
+    pub fn run_app_on_demand<A: ApplicationHandler>(
+        &mut self,
+        app: &mut A,
+    ) -> Result<(), EventLoopError> {
+        loop {
+            match self.pump_app_events(None, app) {
+                PumpStatus::Exit(0) => break Ok(()),
+                PumpStatus::Exit(code) => break Err(EventLoopError::ExitFailure(code)),
+                PumpStatus::Continue => continue,
+            }
+        }
+    }
+
+    pub fn pump_app_events<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        app: &mut A,
+    ) -> PumpStatus {
+        if !self.loop_running {
+            self.loop_running = true;
+
+            // Run the initial loop iteration.
+            self.single_iteration(app, StartCause::Init);
+        }
+
+        // Consider the possibility that the `StartCause::Init` iteration could
+        // request to Exit.
+        if !self.window_target.p.exiting() {
+            self.poll_events_with_timeout(timeout, app);
+        }
+        if let Some(code) = self.window_target.p.exit_code() {
+            self.loop_running = false;
+
+            app.exiting(&self.window_target);
+
+            PumpStatus::Exit(code)
+        } else {
+            PumpStatus::Continue
+        }
+    }
+
+    fn poll_events_with_timeout<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        app: &mut A,
+    ) {
+        let start = Instant::now();
+        let pump_deadline = timeout.and_then(|timeout| start.checked_add(timeout));
+
+        let cause = loop {
+            // The deadlines of the control flow are measured with the clock of the event loop,
+            // which the drivers can advance.
+            let control_flow_timeout = match self.window_target.p.control_flow() {
+                ControlFlow::Wait => None,
+                ControlFlow::Poll => Some(Duration::ZERO),
+                ControlFlow::WaitUntil(deadline) => {
+                    Some(deadline.saturating_duration_since(self.shared().lock().now()))
+                },
+            };
+            let pump_timeout =
+                pump_deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            let timeout = if self.has_pending_events() {
+                Some(Duration::ZERO)
+            } else {
+                min_timeout(control_flow_timeout, pump_timeout)
+            };
+
+            if let Err(error) = self.event_loop.dispatch(timeout, &mut ()) {
+                tracing::error!("Error dispatching event loop: {}", error);
+                self.window_target.p.set_exit_code(1);
+                return;
+            }
+
+            let cause = match self.window_target.p.control_flow() {
+                ControlFlow::Poll => StartCause::Poll,
+                ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+                ControlFlow::WaitUntil(deadline) => {
+                    if self.shared().lock().now() < deadline {
+                        StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                    } else {
+                        StartCause::ResumeTimeReached { start, requested_resume: deadline }
+                    }
+                },
+            };
+
+            // Reduce spurious wake-ups, e.g. from advancing the time not far enough.
+            let pump_expired = pump_deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if matches!(cause, StartCause::WaitCancelled { .. })
+                && !self.has_pending_events()
+                && !pump_expired
+            {
+                continue;
+            }
+
+            break cause;
+        };
+
+        self.single_iteration(app, cause);
+    }
+
+    /// Whether there are events to dispatch without waiting.
+    fn has_pending_events(&self) -> bool {
+        let state = self.shared().lock();
+        !state.events.is_empty()
+            || state.windows.values().any(|window| window.redraw_requested && window.visible)
+            || self.proxy_wake_up()
+    }
+
+    fn single_iteration<A: ApplicationHandler>(&mut self, app: &mut A, cause: StartCause) {
+        app.new_events(&self.window_target, cause);
+
+        if cause == StartCause::Init {
+            app.can_create_surfaces(&self.window_target);
+        }
+
+        if self.take_proxy_wake_up() {
+            app.proxy_wake_up(&self.window_target);
+        }
+
+        // Only the events queued so far are dispatched, the ones queued by the handlers are
+        // dispatched in the next iteration.
+        let events = mem::take(&mut self.shared().lock().events);
+        let events = match self.motion_event_policy() {
+            MotionEventPolicy::Coalesced => coalesce_motion(events),
+            MotionEventPolicy::Unfiltered => events.into(),
+        };
+        for event in events {
+            match event {
+                PendingEvent::Window(window_id, event) => {
+                    app.window_event(&self.window_target, root_window_id(window_id), event)
+                },
+                PendingEvent::Device(event) => self.device_event(app, event),
+                PendingEvent::ScaleFactor(window_id, scale_factor) => {
+                    self.scale_factor_changed(app, window_id, scale_factor)
+                },
+                PendingEvent::KeyboardLayoutChanged => {
+                    app.keyboard_layout_changed(&self.window_target)
+                },
+                PendingEvent::Session(event) => app.session_event(&self.window_target, event),
+            }
+        }
+
+        let redraws: Vec<_> = self
+            .shared()
+            .lock()
+            .windows
+            .iter_mut()
+            .filter(|(_, window)| window.visible)
+            .filter_map(|(window_id, window)| {
+                let redraw_requested = mem::take(&mut window.redraw_requested);
+                if redraw_requested {
+                    window.redraw_damage.deliver();
+                }
+                redraw_requested.then_some(*window_id)
+            })
+            .collect();
+        for window_id in redraws {
+            app.window_event(
+                &self.window_target,
+                root_window_id(window_id),
+                WindowEvent::RedrawRequested,
+            );
+        }
+
+        // This is always the last event we dispatch before poll again
+        app.about_to_wait(&self.window_target);
+    }
+
+    fn device_event<A: ApplicationHandler>(&self, app: &mut A, event: DeviceEvent) {
+        app.device_event(&self.window_target, super::state::device_id(), event);
+    }
+
+    fn scale_factor_changed<A: ApplicationHandler>(
+        &self,
+        app: &mut A,
+        window_id: crate::platform_impl::WindowId,
+        scale_factor: f64,
+    ) {
+        let new_size = {
+            let mut state = self.shared().lock();
+            let Some(window) = state.windows.get_mut(&window_id) else {
+                return;
+            };
+            // The logical size is kept by default.
+            let size = window.size.to_logical::<f64>(window.scale_factor);
+            window.scale_factor = scale_factor;
+            size.to_physical(scale_factor)
+        };
+
+        let new_size = Arc::new(Mutex::new(new_size));
+        let event = WindowEvent::ScaleFactorChanged {
+            scale_factor,
+            inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_size)),
+        };
+        app.window_event(&self.window_target, root_window_id(window_id), event);
+
+        // The resize is dispatched right away, like on the other backends.
+        let new_size = *new_size.lock().unwrap();
+        let new_size = {
+            let mut state = self.shared().lock();
+            let Some(window) = state.windows.get_mut(&window_id) else {
+                return;
+            };
+            window.size = window.constrain(new_size);
+            window.redraw_requested = true;
+            window.size
+        };
+        app.window_event(
+            &self.window_target,
+            root_window_id(window_id),
+            WindowEvent::Resized(new_size),
+        );
+    }
+
+    fn motion_event_policy(&self) -> MotionEventPolicy {
+        match &self.window_target.p {
+            PlatformActiveEventLoop::Headless(window_target) => window_target.motion_event_policy(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    fn shared(&self) -> &Shared {
+        match &self.window_target.p {
+            PlatformActiveEventLoop::Headless(window_target) => &window_target.shared,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    fn proxy_wake_up(&self) -> bool {
+        match &self.window_target.p {
+            PlatformActiveEventLoop::Headless(window_target) => {
+                window_target.proxy.wake_up.load(Ordering::Relaxed)
+            },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    fn take_proxy_wake_up(&self) -> bool {
+        match &self.window_target.p {
+            PlatformActiveEventLoop::Headless(window_target) => {
+                window_target.proxy.wake_up.swap(false, Ordering::Relaxed)
+            },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn window_target(&self) -> &RootActiveEventLoop {
+        &self.window_target
+    }
+}
+
+/// Drops the cursor motions that are directly followed by another one of the same window.
+fn coalesce_motion(events: VecDeque<PendingEvent>) -> Vec<PendingEvent> {
+    let mut coalesced: Vec<PendingEvent> = Vec::with_capacity(events.len());
+    for event in events {
+        if let (
+            Some(PendingEvent::Window(
+                last_window,
+                WindowEvent::CursorMoved { device_id: last, .. },
+            )),
+            PendingEvent::Window(window_id, WindowEvent::CursorMoved { device_id, .. }),
+        ) = (coalesced.last(), &event)
+        {
+            if last_window == window_id && last == device_id {
+                coalesced.pop();
+            }
+        }
+        coalesced.push(event);
+    }
+    coalesced
+}
+
+impl AsFd for EventLoop {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.event_loop.as_fd()
+    }
+}
+
+impl AsRawFd for EventLoop {
+    fn as_raw_fd(&self) -> RawFd {
+        self.event_loop.as_raw_fd()
+    }
+}
+
+#[derive(Clone)]
+pub struct EventLoopProxy {
+    shared: Arc<Shared>,
+    wake_up: Arc<AtomicBool>,
+}
+
+impl EventLoopProxy {
+    pub fn wake_up(&self) {
+        self.wake_up.store(true, Ordering::Relaxed);
+        self.shared.update(|_| ());
+    }
+}
+
+pub struct ActiveEventLoop {
+    pub(crate) shared: Arc<Shared>,
+    proxy: EventLoopProxy,
+    control_flow: Cell<ControlFlow>,
+    motion_event_policy: Cell<MotionEventPolicy>,
+    exit: Cell<Option<i32>>,
+}
+
+impl ActiveEventLoop {
+    pub(crate) fn create_proxy(&self) -> EventLoopProxy {
+        self.proxy.clone()
+    }
+
+    pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.control_flow.set(control_flow)
+    }
+
+    pub(crate) fn control_flow(&self) -> ControlFlow {
+        self.control_flow.get()
+    }
+
+    pub(crate) fn exit(&self) {
+        self.exit.set(Some(0))
+    }
+
+    pub(crate) fn clear_exit(&self) {
+        self.exit.set(None)
+    }
+
+    pub(crate) fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    pub(crate) fn set_exit_code(&self, code: i32) {
+        self.exit.set(Some(code))
+    }
+
+    pub(crate) fn exit_code(&self) -> Option<i32> {
+        self.exit.get()
+    }
+
+    pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
+        std::iter::once(MonitorHandle)
+    }
+
+    pub fn primary_monitor(&self) -> Option<MonitorHandle> {
+        Some(MonitorHandle)
+    }
+
+    pub fn listen_device_events(&self, _subscription: DeviceEventSubscription) {}
+
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        self.motion_event_policy.set(policy)
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.motion_event_policy.get()
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        self.shared.lock().key_from_key_code(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        self.shared.lock().key_code_from_key(key)
+    }
+
+    pub(crate) fn create_custom_cursor(&self, cursor: CustomCursorSource) -> RootCustomCursor {
+        RootCustomCursor {
+            inner: PlatformCustomCursor::Headless(OnlyCursorImage(Arc::new(cursor.inner))),
+        }
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        panic!("the headless backend has no raw display handle")
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
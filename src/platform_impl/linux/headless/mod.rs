@@ -0,0 +1,86 @@
+//! Headless backend, keeping the windows in memory and taking its input from a
+//! [`HeadlessDriver`](crate::platform::headless::HeadlessDriver).
+
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+
+pub use event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
+pub(crate) use state::Shared;
+pub use window::Window;
+
+mod event_loop;
+mod state;
+mod window;
+
+/// The size of the monitor and the default size of the windows.
+const MONITOR_SIZE: PhysicalSize<u32> = PhysicalSize::new(1920, 1080);
+const REFRESH_RATE_MILLIHERTZ: u32 = 60_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeviceId;
+
+/// The only monitor of the headless backend.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonitorHandle;
+
+impl MonitorHandle {
+    #[inline]
+    pub fn name(&self) -> Option<String> {
+        Some(String::from("Headless"))
+    }
+
+    #[cfg(any(x11_platform, wayland_platform))]
+    #[inline]
+    pub fn native_identifier(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    pub fn size(&self) -> PhysicalSize<u32> {
+        MONITOR_SIZE
+    }
+
+    #[inline]
+    pub fn position(&self) -> PhysicalPosition<i32> {
+        PhysicalPosition::new(0, 0)
+    }
+
+    #[inline]
+    pub fn refresh_rate_millihertz(&self) -> Option<u32> {
+        Some(REFRESH_RATE_MILLIHERTZ)
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        1.0
+    }
+
+    #[inline]
+    pub fn video_modes(&self) -> impl Iterator<Item = crate::platform_impl::VideoModeHandle> {
+        std::iter::once(crate::platform_impl::VideoModeHandle::Headless(VideoModeHandle))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoModeHandle;
+
+impl VideoModeHandle {
+    #[inline]
+    pub fn size(&self) -> PhysicalSize<u32> {
+        MONITOR_SIZE
+    }
+
+    #[inline]
+    pub fn bit_depth(&self) -> u16 {
+        32
+    }
+
+    #[inline]
+    pub fn refresh_rate_millihertz(&self) -> u32 {
+        REFRESH_RATE_MILLIHERTZ
+    }
+
+    #[inline]
+    pub fn monitor(&self) -> MonitorHandle {
+        MonitorHandle
+    }
+}
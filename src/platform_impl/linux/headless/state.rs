@@ -0,0 +1,480 @@
+//! The state of the windows and of the input devices, shared by the event loop, its windows and
+//! the drivers.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use smol_str::SmolStr;
+
+use super::DeviceId;
+use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::event::{
+    DeviceEvent, DeviceId as RootDeviceId, ElementState, KeyEvent, Modifiers, MouseButton,
+    SessionEvent, WindowEvent,
+};
+use crate::keyboard::{
+    self, Key, KeyCode, KeyLocation, LockKeys, ModifiersKeys, ModifiersState, PhysicalKey,
+};
+use crate::platform_impl::platform::{DeviceId as PlatformDeviceId, KeyEventExtra, WindowId};
+use crate::platform_impl::Fullscreen;
+use crate::utils::{ClickCounter, ClickThresholds, RedrawDamage};
+use crate::window::{
+    ImePurpose, Rect, Theme, WindowAttributes, WindowButtons, WindowId as RootWindowId,
+};
+
+pub(crate) struct Shared {
+    state: Mutex<State>,
+
+    /// Wakes up the event loop to dispatch new events.
+    waker: calloop::ping::Ping,
+}
+
+impl Shared {
+    pub fn new(waker: calloop::ping::Ping) -> Arc<Self> {
+        Arc::new(Self { state: Mutex::new(State::default()), waker })
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Changes the state and wakes up the event loop for the events it queued.
+    pub fn update<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        let result = f(&mut self.lock());
+        self.waker.ping();
+        result
+    }
+}
+
+/// An event waiting for the event loop to dispatch it.
+// Most of the events are window events, boxing them wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum PendingEvent {
+    Window(WindowId, WindowEvent),
+    Device(DeviceEvent),
+    /// The scale factor changed, which is dispatched with an `InnerSizeWriter`.
+    ScaleFactor(WindowId, f64),
+    KeyboardLayoutChanged,
+    Session(SessionEvent),
+}
+
+#[derive(Default)]
+pub(crate) struct State {
+    next_window_id: u64,
+
+    pub windows: BTreeMap<WindowId, WindowState>,
+    pub events: VecDeque<PendingEvent>,
+
+    /// How far the clock of the event loop is ahead of the real one.
+    time_offset: Duration,
+
+    modifiers: Modifiers,
+    lock_keys: LockKeys,
+    pressed_keys: HashSet<KeyCode>,
+    pointer: Option<(WindowId, PhysicalPosition<f64>)>,
+    click_counter: ClickCounter,
+    focused: Option<WindowId>,
+
+    /// The keys of the layout set by the driver, or `None` for the layout-independent keys only.
+    keyboard_layout: Option<Vec<(KeyCode, Key)>>,
+}
+
+pub(crate) struct WindowState {
+    pub title: String,
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<u32>,
+    pub min_size: Option<Size>,
+    pub max_size: Option<Size>,
+    pub resize_increments: Option<Size>,
+    pub scale_factor: f64,
+    pub visible: bool,
+    pub resizable: bool,
+    pub enabled_buttons: WindowButtons,
+    pub maximized: bool,
+    pub minimized: bool,
+    pub decorated: bool,
+    pub fullscreen: Option<Fullscreen>,
+    pub theme: Option<Theme>,
+    pub opacity: f32,
+    pub ime_allowed: bool,
+    pub ime_cursor_area: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    pub ime_purpose: ImePurpose,
+    pub redraw_requested: bool,
+    pub redraw_damage: RedrawDamage,
+}
+
+impl WindowState {
+    /// Clamps `size` to the size constraints of the window.
+    pub fn constrain(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+        let min = self.min_size.map(|size| size.to_physical::<u32>(self.scale_factor));
+        let max = self.max_size.map(|size| size.to_physical::<u32>(self.scale_factor));
+        let width = size
+            .width
+            .max(min.map_or(0, |min| min.width))
+            .min(max.map_or(u32::MAX, |max| max.width));
+        let height = size
+            .height
+            .max(min.map_or(0, |min| min.height))
+            .min(max.map_or(u32::MAX, |max| max.height));
+        PhysicalSize::new(width.max(1), height.max(1))
+    }
+}
+
+impl State {
+    /// The time of the event loop, which the drivers can advance.
+    pub fn now(&self) -> Instant {
+        Instant::now() + self.time_offset
+    }
+
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.time_offset += duration;
+    }
+
+    pub fn window_ids(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    pub fn create_window(&mut self, attributes: &WindowAttributes) -> WindowId {
+        self.next_window_id += 1;
+        let window_id = WindowId::from(self.next_window_id);
+
+        let scale_factor = 1.0;
+        let mut window = WindowState {
+            title: attributes.title.clone(),
+            position: attributes
+                .position
+                .map_or(PhysicalPosition::new(0, 0), |position| position.to_physical(scale_factor)),
+            size: attributes
+                .inner_size
+                .map_or(PhysicalSize::new(800, 600), |size| size.to_physical(scale_factor)),
+            min_size: attributes.min_inner_size,
+            max_size: attributes.max_inner_size,
+            resize_increments: attributes.resize_increments,
+            scale_factor,
+            visible: attributes.visible,
+            resizable: attributes.resizable,
+            enabled_buttons: attributes.enabled_buttons,
+            maximized: attributes.maximized,
+            minimized: false,
+            decorated: attributes.decorations,
+            fullscreen: attributes.fullscreen.clone().map(Into::into),
+            theme: attributes.preferred_theme,
+            opacity: 1.0,
+            ime_allowed: false,
+            ime_cursor_area: None,
+            ime_purpose: ImePurpose::Normal,
+            redraw_requested: true,
+            redraw_damage: RedrawDamage::default(),
+        };
+        window.size = window.constrain(window.size);
+        self.windows.insert(window_id, window);
+
+        if attributes.visible && attributes.active {
+            self.focus(Some(window_id));
+        }
+
+        window_id
+    }
+
+    /// Removes the window, after the events queued for it so far.
+    pub fn destroy_window(&mut self, window_id: WindowId) {
+        if self.focused == Some(window_id) {
+            self.focused = None;
+        }
+        if self.pointer.is_some_and(|(pointer_window, _)| pointer_window == window_id) {
+            self.pointer = None;
+        }
+        self.windows.remove(&window_id);
+        self.events.push_back(PendingEvent::Window(window_id, WindowEvent::Destroyed));
+    }
+
+    pub fn window_event(&mut self, window_id: WindowId, event: WindowEvent) {
+        if self.windows.contains_key(&window_id) {
+            self.events.push_back(PendingEvent::Window(window_id, event));
+        }
+    }
+
+    pub fn device_event(&mut self, event: DeviceEvent) {
+        self.events.push_back(PendingEvent::Device(event));
+    }
+
+    pub fn request_redraw(&mut self, window_id: WindowId) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.redraw_requested = true;
+            window.redraw_damage.add_full();
+        }
+    }
+
+    pub fn request_redraw_with_damage(&mut self, window_id: WindowId, rects: &[Rect<u32>]) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            window.redraw_requested = true;
+            window.redraw_damage.add(rects, window.size);
+        }
+    }
+
+    /// Resizes the window within its constraints, returning the new size.
+    pub fn resize(
+        &mut self,
+        window_id: WindowId,
+        size: PhysicalSize<u32>,
+    ) -> Option<PhysicalSize<u32>> {
+        let window = self.windows.get_mut(&window_id)?;
+        let size = window.constrain(size);
+        if window.size != size {
+            window.size = size;
+            window.redraw_requested = true;
+            self.window_event(window_id, WindowEvent::Resized(size));
+        }
+        Some(size)
+    }
+
+    pub fn move_window(&mut self, window_id: WindowId, position: PhysicalPosition<i32>) {
+        if let Some(window) = self.windows.get_mut(&window_id) {
+            if window.position != position {
+                window.position = position;
+                self.window_event(window_id, WindowEvent::Moved(position));
+            }
+        }
+    }
+
+    pub fn set_scale_factor(&mut self, window_id: WindowId, scale_factor: f64) {
+        if self.windows.contains_key(&window_id) {
+            self.events.push_back(PendingEvent::ScaleFactor(window_id, scale_factor));
+        }
+    }
+
+    pub fn has_focus(&self, window_id: WindowId) -> bool {
+        self.focused == Some(window_id)
+    }
+
+    pub fn focus(&mut self, window_id: Option<WindowId>) {
+        let window_id = window_id.filter(|window_id| self.windows.contains_key(window_id));
+        if self.focused == window_id {
+            return;
+        }
+
+        if let Some(old) = self.focused.take() {
+            // Keys and modifiers are released when the focus leaves.
+            if self.modifiers != Modifiers::default() {
+                self.window_event(old, WindowEvent::ModifiersChanged(Modifiers::default()));
+            }
+            self.modifiers = Modifiers::default();
+            self.pressed_keys.clear();
+            self.window_event(old, WindowEvent::Focused(false));
+        }
+        if let Some(new) = window_id {
+            self.window_event(new, WindowEvent::Focused(true));
+            // The lock keys stay on while the focus is elsewhere.
+            self.modifiers.lock_keys = self.lock_keys;
+            if !self.lock_keys.is_empty() {
+                self.window_event(new, WindowEvent::ModifiersChanged(self.modifiers));
+            }
+        }
+        self.focused = window_id;
+    }
+
+    pub fn key(
+        &mut self,
+        code: KeyCode,
+        logical_key: Key,
+        text: Option<SmolStr>,
+        state: ElementState,
+    ) {
+        let Some(window_id) = self.focused else {
+            return;
+        };
+
+        let repeat = match state {
+            ElementState::Pressed => !self.pressed_keys.insert(code),
+            ElementState::Released => {
+                self.pressed_keys.remove(&code);
+                false
+            },
+        };
+        let text = text.filter(|_| state == ElementState::Pressed);
+
+        let event = KeyEvent {
+            physical_key: PhysicalKey::Code(code),
+            logical_key: logical_key.clone(),
+            text: text.clone(),
+            location: key_location(code),
+            state,
+            repeat,
+            platform_specific: KeyEventExtra {
+                text_with_all_modifiers: text,
+                key_without_modifiers: logical_key,
+            },
+        };
+        self.window_event(window_id, WindowEvent::KeyboardInput {
+            device_id: device_id(),
+            event,
+            is_synthetic: false,
+        });
+
+        let mut modifiers = self.modifiers;
+        if let Some(key) = modifier_key(code) {
+            modifiers.pressed_mods = match state {
+                ElementState::Pressed => modifiers.pressed_mods | key,
+                ElementState::Released => modifiers.pressed_mods - key,
+            };
+            modifiers.state = modifiers_state(modifiers.pressed_mods);
+        }
+        if let Some(key) = lock_key(code).filter(|_| state == ElementState::Pressed && !repeat) {
+            self.lock_keys.toggle(key);
+            modifiers.lock_keys = self.lock_keys;
+        }
+        if modifiers != self.modifiers {
+            self.modifiers = modifiers;
+            self.window_event(window_id, WindowEvent::ModifiersChanged(self.modifiers));
+        }
+    }
+
+    pub fn set_keyboard_layout(&mut self, keys: Vec<(KeyCode, Key)>) {
+        self.keyboard_layout = Some(keys);
+        self.events.push_back(PendingEvent::KeyboardLayoutChanged);
+    }
+
+    pub fn session_event(&mut self, event: SessionEvent) {
+        self.events.push_back(PendingEvent::Session(event));
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        let key = self.keyboard_layout.iter().flatten().find(|(key_code, _)| *key_code == code);
+        match key {
+            Some((_, key)) => keyboard::standalone_key(key.clone()),
+            None => keyboard::layout_independent_key(code),
+        }
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        let layout = self.keyboard_layout.iter().flatten().cloned();
+        keyboard::find_key_code(key, layout.chain(keyboard::layout_independent_keys()))
+    }
+
+    pub fn move_pointer(&mut self, target: Option<(WindowId, PhysicalPosition<f64>)>) {
+        let target = target.filter(|(window_id, _)| self.windows.contains_key(window_id));
+        let old_window = self.pointer.map(|(window_id, _)| window_id);
+        let new_window = target.map(|(window_id, _)| window_id);
+
+        if old_window != new_window {
+            self.click_counter = ClickCounter::default();
+            if let Some(old) = old_window {
+                self.window_event(old, WindowEvent::CursorLeft { device_id: device_id() });
+            }
+            if let Some(new) = new_window {
+                self.window_event(new, WindowEvent::CursorEntered { device_id: device_id() });
+            }
+        }
+        if let Some((window_id, position)) = target {
+            if self.pointer != target {
+                self.click_counter.moved(position, ClickThresholds::default());
+                self.window_event(window_id, WindowEvent::CursorMoved {
+                    device_id: device_id(),
+                    position,
+                });
+            }
+        }
+
+        self.pointer = target;
+    }
+
+    pub fn mouse_input(&mut self, state: ElementState, button: MouseButton) {
+        if let Some((window_id, position)) = self.pointer {
+            let click_count = match state {
+                ElementState::Pressed => {
+                    let now = self.now();
+                    self.click_counter.press(button, position, now, ClickThresholds::default())
+                },
+                ElementState::Released => self.click_counter.release(button),
+            };
+            self.window_event(window_id, WindowEvent::MouseInput {
+                device_id: device_id(),
+                state,
+                button,
+                click_count,
+            });
+        }
+    }
+}
+
+pub(crate) fn device_id() -> RootDeviceId {
+    RootDeviceId(PlatformDeviceId::Headless(DeviceId))
+}
+
+pub(crate) fn root_window_id(window_id: WindowId) -> RootWindowId {
+    RootWindowId(window_id)
+}
+
+fn modifier_key(code: KeyCode) -> Option<ModifiersKeys> {
+    match code {
+        KeyCode::ShiftLeft => Some(ModifiersKeys::LSHIFT),
+        KeyCode::ShiftRight => Some(ModifiersKeys::RSHIFT),
+        KeyCode::ControlLeft => Some(ModifiersKeys::LCONTROL),
+        KeyCode::ControlRight => Some(ModifiersKeys::RCONTROL),
+        KeyCode::AltLeft => Some(ModifiersKeys::LALT),
+        KeyCode::AltRight => Some(ModifiersKeys::RALT),
+        KeyCode::SuperLeft => Some(ModifiersKeys::LSUPER),
+        KeyCode::SuperRight => Some(ModifiersKeys::RSUPER),
+        _ => None,
+    }
+}
+
+fn lock_key(code: KeyCode) -> Option<LockKeys> {
+    match code {
+        KeyCode::CapsLock => Some(LockKeys::CAPS_LOCK),
+        KeyCode::NumLock => Some(LockKeys::NUM_LOCK),
+        _ => None,
+    }
+}
+
+fn modifiers_state(pressed_mods: ModifiersKeys) -> ModifiersState {
+    let mut state = ModifiersState::empty();
+    state.set(
+        ModifiersState::SHIFT,
+        pressed_mods.intersects(ModifiersKeys::LSHIFT | ModifiersKeys::RSHIFT),
+    );
+    state.set(
+        ModifiersState::CONTROL,
+        pressed_mods.intersects(ModifiersKeys::LCONTROL | ModifiersKeys::RCONTROL),
+    );
+    state.set(
+        ModifiersState::ALT,
+        pressed_mods.intersects(ModifiersKeys::LALT | ModifiersKeys::RALT),
+    );
+    state.set(
+        ModifiersState::SUPER,
+        pressed_mods.intersects(ModifiersKeys::LSUPER | ModifiersKeys::RSUPER),
+    );
+    state
+}
+
+fn key_location(code: KeyCode) -> KeyLocation {
+    match code {
+        KeyCode::ShiftLeft | KeyCode::ControlLeft | KeyCode::AltLeft | KeyCode::SuperLeft => {
+            KeyLocation::Left
+        },
+        KeyCode::ShiftRight | KeyCode::ControlRight | KeyCode::AltRight | KeyCode::SuperRight => {
+            KeyLocation::Right
+        },
+        KeyCode::NumLock
+        | KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::NumpadAdd
+        | KeyCode::NumpadDecimal
+        | KeyCode::NumpadDivide
+        | KeyCode::NumpadEnter
+        | KeyCode::NumpadEqual
+        | KeyCode::NumpadMultiply
+        | KeyCode::NumpadSubtract => KeyLocation::Numpad,
+        _ => KeyLocation::Standard,
+    }
+}
@@ -0,0 +1,414 @@
+//! Windows kept in memory.
+
+use std::sync::Arc;
+
+use super::state::{Shared, WindowState};
+use super::{ActiveEventLoop, MonitorHandle};
+use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
+use crate::event::{Ime, WindowEvent};
+use crate::platform_impl::{Fullscreen, PlatformIcon, WindowId};
+use crate::window::{
+    AspectRatio, Cursor, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose, ProgressState,
+    Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+};
+
+pub struct Window {
+    window_id: WindowId,
+    shared: Arc<Shared>,
+}
+
+impl Window {
+    pub(crate) fn new(
+        event_loop: &ActiveEventLoop,
+        attributes: WindowAttributes,
+    ) -> Result<Self, RootOsError> {
+        let shared = event_loop.shared.clone();
+        let window_id = shared.update(|state| state.create_window(&attributes));
+        Ok(Self { window_id, shared })
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut WindowState) -> R) -> R {
+        let mut state = self.shared.lock();
+        f(state.windows.get_mut(&self.window_id).expect("the window was destroyed"))
+    }
+
+    #[inline]
+    pub fn id(&self) -> WindowId {
+        self.window_id
+    }
+
+    #[inline]
+    pub fn set_title(&self, title: &str) {
+        self.with_state(|window| window.title = title.to_owned());
+    }
+
+    #[inline]
+    pub fn title(&self) -> String {
+        self.with_state(|window| window.title.clone())
+    }
+
+    #[inline]
+    pub fn set_transparent(&self, _transparent: bool) {}
+
+    #[inline]
+    pub fn set_blur(&self, _blur: bool) {}
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.with_state(|window| window.opacity = opacity.clamp(0.0, 1.0));
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.with_state(|window| window.opacity)
+    }
+
+    #[inline]
+    pub fn set_visible(&self, visible: bool) {
+        self.shared.update(|state| {
+            if let Some(window) = state.windows.get_mut(&self.window_id) {
+                window.visible = visible;
+                window.redraw_requested |= visible;
+            }
+            if !visible && state.has_focus(self.window_id) {
+                state.focus(None);
+            }
+        });
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        Some(self.with_state(|window| window.visible))
+    }
+
+    #[inline]
+    pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        Ok(self.with_state(|window| window.position))
+    }
+
+    #[inline]
+    pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        self.outer_position()
+    }
+
+    #[inline]
+    pub fn set_outer_position(&self, position: Position) {
+        let position = position.to_physical(self.scale_factor());
+        self.shared.update(|state| state.move_window(self.window_id, position));
+    }
+
+    #[inline]
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        self.with_state(|window| window.size)
+    }
+
+    #[inline]
+    pub fn outer_size(&self) -> PhysicalSize<u32> {
+        self.inner_size()
+    }
+
+    #[inline]
+    pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = size.to_physical(self.scale_factor());
+        self.shared.update(|state| state.resize(self.window_id, size))
+    }
+
+    #[cfg(any(x11_platform, wayland_platform))]
+    #[inline]
+    pub(crate) fn request_activation_token(
+        &self,
+    ) -> Result<crate::event_loop::AsyncRequestSerial, NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn set_min_inner_size(&self, dimensions: Option<Size>) {
+        self.with_state(|window| window.min_size = dimensions);
+        self.request_inner_size(self.inner_size().into());
+    }
+
+    #[inline]
+    pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
+        self.with_state(|window| window.max_size = dimensions);
+        self.request_inner_size(self.inner_size().into());
+    }
+
+    #[inline]
+    pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        self.with_state(|window| {
+            window.resize_increments.map(|increments| increments.to_physical(window.scale_factor))
+        })
+    }
+
+    #[inline]
+    pub fn set_resize_increments(&self, increments: Option<Size>) {
+        self.with_state(|window| window.resize_increments = increments);
+    }
+
+    #[inline]
+    pub fn set_aspect_ratio(&self, _aspect_ratio: Option<AspectRatio>) {}
+
+    #[inline]
+    pub fn set_resizable(&self, resizable: bool) {
+        self.with_state(|window| window.resizable = resizable);
+    }
+
+    #[inline]
+    pub fn is_resizable(&self) -> bool {
+        self.with_state(|window| window.resizable)
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.with_state(|window| window.enabled_buttons = buttons);
+    }
+
+    #[inline]
+    pub fn enabled_buttons(&self) -> WindowButtons {
+        self.with_state(|window| window.enabled_buttons)
+    }
+
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::all()
+    }
+
+    #[inline]
+    pub fn set_cursor(&self, _cursor: Cursor) {}
+
+    #[inline]
+    pub fn set_cursor_grab(&self, _mode: CursorGrabMode) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_cursor_visible(&self, _visible: bool) {}
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn start_drag_and_drop(&self, _payload: DragPayload) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: Position) {}
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.with_state(|window| window.scale_factor)
+    }
+
+    #[inline]
+    pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
+        let position = position.to_physical(self.scale_factor());
+        self.shared.update(|state| state.move_pointer(Some((self.window_id, position))));
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_maximized(&self, maximized: bool) {
+        self.with_state(|window| window.maximized = maximized);
+    }
+
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.with_state(|window| window.maximized)
+    }
+
+    #[inline]
+    pub fn set_minimized(&self, minimized: bool) {
+        self.with_state(|window| window.minimized = minimized);
+    }
+
+    #[inline]
+    pub fn is_minimized(&self) -> Option<bool> {
+        Some(self.with_state(|window| window.minimized))
+    }
+
+    #[inline]
+    pub(crate) fn fullscreen(&self) -> Option<Fullscreen> {
+        self.with_state(|window| window.fullscreen.clone())
+    }
+
+    #[inline]
+    pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.with_state(|window| window.fullscreen = fullscreen);
+    }
+
+    #[inline]
+    pub fn set_decorations(&self, decorations: bool) {
+        self.with_state(|window| window.decorated = decorations);
+    }
+
+    #[inline]
+    pub fn is_decorated(&self) -> bool {
+        self.with_state(|window| window.decorated)
+    }
+
+    #[inline]
+    pub fn set_window_level(&self, _level: WindowLevel) {}
+
+    #[inline]
+    pub fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
+
+    #[inline]
+    pub fn set_transient_parent(&self, _parent: Option<&Window>) -> Result<(), ExternalError> {
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        self.with_state(|window| {
+            let position = position.to_physical(window.scale_factor);
+            let size = size.to_physical(window.scale_factor);
+            window.ime_cursor_area = Some((position, size));
+        });
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.shared.update(|state| {
+            let Some(window) = state.windows.get_mut(&self.window_id) else {
+                return;
+            };
+            if window.ime_allowed != allowed {
+                window.ime_allowed = allowed;
+                let ime = if allowed { Ime::Enabled } else { Ime::Disabled };
+                state.window_event(self.window_id, WindowEvent::Ime(ime));
+            }
+        });
+    }
+
+    #[inline]
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.with_state(|window| window.ime_purpose = purpose);
+    }
+
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        Some(ImeCapabilities::all())
+    }
+
+    #[inline]
+    pub fn focus_window(&self) {
+        self.shared.update(|state| state.focus(Some(self.window_id)));
+    }
+
+    #[inline]
+    pub fn has_focus(&self) -> bool {
+        self.shared.lock().has_focus(self.window_id)
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {}
+
+    #[inline]
+    pub fn set_progress(&self, _state: ProgressState) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, _count: Option<u64>) {}
+
+    #[inline]
+    pub fn request_redraw(&self) {
+        self.shared.update(|state| state.request_redraw(self.window_id));
+    }
+
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        self.shared.update(|state| state.request_redraw_with_damage(self.window_id, rects));
+    }
+
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        self.with_state(|window| window.redraw_damage.current(window.size).map(<[_]>::to_vec))
+    }
+
+    #[inline]
+    pub fn pre_present_notify(&self) {}
+
+    #[inline]
+    pub fn set_presentation_feedback(&self, _enabled: bool) {}
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        Some(MonitorHandle)
+    }
+
+    #[inline]
+    pub fn available_monitors(&self) -> Vec<MonitorHandle> {
+        vec![MonitorHandle]
+    }
+
+    #[inline]
+    pub fn primary_monitor(&self) -> Option<MonitorHandle> {
+        Some(MonitorHandle)
+    }
+
+    #[cfg(feature = "rwh_04")]
+    #[inline]
+    pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
+        panic!("headless windows have no raw window handle")
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_window_handle_rwh_05(&self) -> rwh_05::RawWindowHandle {
+        panic!("headless windows have no raw window handle")
+    }
+
+    #[cfg(feature = "rwh_05")]
+    #[inline]
+    pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
+        panic!("the headless backend has no raw display handle")
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+
+    #[cfg(feature = "rwh_06")]
+    #[inline]
+    pub fn raw_display_handle_rwh_06(
+        &self,
+    ) -> Result<rwh_06::RawDisplayHandle, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+
+    #[inline]
+    pub fn set_theme(&self, theme: Option<Theme>) {
+        self.with_state(|window| window.theme = theme);
+    }
+
+    #[inline]
+    pub fn theme(&self) -> Option<Theme> {
+        self.with_state(|window| window.theme)
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {}
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        self.shared.update(|state| state.destroy_window(self.window_id));
+    }
+}
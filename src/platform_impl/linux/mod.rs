@@ -1,7 +1,7 @@
 #![cfg(free_unix)]
 
-#[cfg(all(not(x11_platform), not(wayland_platform)))]
-compile_error!("Please select a feature to build for unix: `x11`, `wayland`");
+#[cfg(all(not(x11_platform), not(wayland_platform), not(headless_platform)))]
+compile_error!("Please select a feature to build for unix: `x11`, `wayland`, `headless`");
 
 use std::collections::VecDeque;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
@@ -19,16 +19,22 @@ use smol_str::SmolStr;
 
 #[cfg(x11_platform)]
 use self::x11::{X11Error, XConnection, XError, XNotSupported};
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::{EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event_loop::{AsyncRequestSerial, ControlFlow, DeviceEvents};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{
+    EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError, PlatformError,
+};
+#[cfg(any(x11_platform, wayland_platform))]
+use crate::event_loop::AsyncRequestSerial;
+use crate::event_loop::{ControlFlow, DeviceEventSubscription, MotionEventPolicy};
 use crate::icon::Icon;
-use crate::keyboard::Key;
+use crate::keyboard::{Key, KeyCode};
+use crate::monitor::ColorProperties;
 #[cfg(x11_platform)]
 use crate::platform::x11::{WindowType as XWindowType, XlibErrorHook};
 use crate::window::{
-    ActivationToken, Cursor, CursorGrabMode, CustomCursor, CustomCursorSource, ImePurpose,
-    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+    ActivationToken, AspectRatio, Cursor, CursorGrabMode, CustomCursor, CustomCursorSource,
+    DragPayload, ImeCapabilities, ImePurpose, ProgressState, Rect, ResizeDirection, Theme,
+    UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
 };
 
 pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
@@ -37,6 +43,8 @@ pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
 pub(crate) use crate::platform_impl::Fullscreen;
 
 pub(crate) mod common;
+#[cfg(headless_platform)]
+pub(crate) mod headless;
 #[cfg(wayland_platform)]
 pub(crate) mod wayland;
 #[cfg(x11_platform)]
@@ -48,6 +56,8 @@ pub(crate) enum Backend {
     X,
     #[cfg(wayland_platform)]
     Wayland,
+    #[cfg(headless_platform)]
+    Headless,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
@@ -63,12 +73,14 @@ pub struct ApplicationName {
 }
 
 impl ApplicationName {
+    #[cfg(any(x11_platform, wayland_platform))]
     pub fn new(general: String, instance: String) -> Self {
         Self { general, instance }
     }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(not(any(x11_platform, wayland_platform)), allow(dead_code))]
 pub struct PlatformSpecificWindowAttributes {
     pub name: Option<ApplicationName>,
     pub activation_token: Option<ActivationToken>,
@@ -114,11 +126,14 @@ pub(crate) static X11_BACKEND: Lazy<Mutex<Result<Arc<XConnection>, XNotSupported
 
 #[derive(Debug, Clone)]
 pub enum OsError {
+    #[cfg_attr(not(any(x11_platform, wayland_platform)), allow(dead_code))]
     Misc(&'static str),
     #[cfg(x11_platform)]
     XError(Arc<X11Error>),
     #[cfg(wayland_platform)]
     WaylandError(Arc<wayland::WaylandError>),
+    #[cfg(headless_platform)]
+    HeadlessError(Arc<calloop::Error>),
 }
 
 impl fmt::Display for OsError {
@@ -129,15 +144,41 @@ impl fmt::Display for OsError {
             OsError::XError(ref e) => fmt::Display::fmt(e, _f),
             #[cfg(wayland_platform)]
             OsError::WaylandError(ref e) => fmt::Display::fmt(e, _f),
+            #[cfg(headless_platform)]
+            OsError::HeadlessError(ref e) => fmt::Display::fmt(e, _f),
         }
     }
 }
 
+/// The error reported by the platform for the given backend error.
+pub(crate) fn platform_error(error: &OsError) -> Option<PlatformError> {
+    match error {
+        OsError::Misc(_) => None,
+        #[cfg(x11_platform)]
+        OsError::XError(error) => error.platform_error(),
+        #[cfg(wayland_platform)]
+        OsError::WaylandError(error) => error.platform_error(),
+        #[cfg(headless_platform)]
+        OsError::HeadlessError(error) => match &**error {
+            calloop::Error::IoError(error) => io_platform_error(error),
+            _ => None,
+        },
+    }
+}
+
+/// The `errno` of an IO error, if it came from the OS.
+fn io_platform_error(error: &std::io::Error) -> Option<PlatformError> {
+    let code = error.raw_os_error()?;
+    Some(PlatformError::new("errno", Some(code.into()), error.to_string()))
+}
+
 pub(crate) enum Window {
     #[cfg(x11_platform)]
     X(x11::Window),
     #[cfg(wayland_platform)]
     Wayland(wayland::Window),
+    #[cfg(headless_platform)]
+    Headless(headless::Window),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -167,6 +208,8 @@ pub enum DeviceId {
     X(x11::DeviceId),
     #[cfg(wayland_platform)]
     Wayland(wayland::DeviceId),
+    #[cfg(headless_platform)]
+    Headless(headless::DeviceId),
 }
 
 impl DeviceId {
@@ -175,6 +218,8 @@ impl DeviceId {
         return DeviceId::Wayland(unsafe { wayland::DeviceId::dummy() });
         #[cfg(all(not(wayland_platform), x11_platform))]
         return DeviceId::X(unsafe { x11::DeviceId::dummy() });
+        #[cfg(all(not(wayland_platform), not(x11_platform), headless_platform))]
+        return DeviceId::Headless(headless::DeviceId);
     }
 }
 
@@ -184,6 +229,8 @@ pub enum MonitorHandle {
     X(x11::MonitorHandle),
     #[cfg(wayland_platform)]
     Wayland(wayland::MonitorHandle),
+    #[cfg(headless_platform)]
+    Headless(headless::MonitorHandle),
 }
 
 /// `x11_or_wayland!(match expr; Enum(foo) => foo.something())`
@@ -202,6 +249,8 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $enum2::X($x),
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $enum2::Wayland($x),
+            #[cfg(headless_platform)]
+            $enum::Headless($($c1)*) => $enum2::Headless($x),
         }
     };
     (match $what:expr; $enum:ident ( $($c1:tt)* ) => $x:expr) => {
@@ -210,6 +259,8 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $x,
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $x,
+            #[cfg(headless_platform)]
+            $enum::Headless($($c1)*) => $x,
         }
     };
 }
@@ -220,6 +271,7 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.name())
     }
 
+    #[cfg(any(x11_platform, wayland_platform))]
     #[inline]
     pub fn native_identifier(&self) -> u32 {
         x11_or_wayland!(match self; MonitorHandle(m) => m.native_identifier())
@@ -240,6 +292,11 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.refresh_rate_millihertz())
     }
 
+    #[inline]
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        None
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
@@ -257,6 +314,8 @@ pub enum VideoModeHandle {
     X(x11::VideoModeHandle),
     #[cfg(wayland_platform)]
     Wayland(wayland::VideoModeHandle),
+    #[cfg(headless_platform)]
+    Headless(headless::VideoModeHandle),
 }
 
 impl VideoModeHandle {
@@ -296,6 +355,10 @@ impl Window {
             ActiveEventLoop::X(ref window_target) => {
                 x11::Window::new(window_target, attribs).map(Window::X)
             },
+            #[cfg(headless_platform)]
+            ActiveEventLoop::Headless(ref window_target) => {
+                headless::Window::new(window_target, attribs).map(Window::Headless)
+            },
         }
     }
 
@@ -327,6 +390,16 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_blur(blur));
     }
 
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        x11_or_wayland!(match self; Window(w) => w.set_opacity(opacity));
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        x11_or_wayland!(match self; Window(w) => w.opacity())
+    }
+
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         x11_or_wayland!(match self; Window(w) => w.set_visible(visible))
@@ -362,11 +435,17 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.outer_size())
     }
 
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         x11_or_wayland!(match self; Window(w) => w.request_inner_size(size))
     }
 
+    #[cfg(any(x11_platform, wayland_platform))]
     #[inline]
     pub(crate) fn request_activation_token(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
         x11_or_wayland!(match self; Window(w) => w.request_activation_token())
@@ -392,6 +471,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_resize_increments(increments))
     }
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        x11_or_wayland!(match self; Window(w) => w.set_aspect_ratio(aspect_ratio))
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         x11_or_wayland!(match self; Window(w) => w.set_resizable(resizable))
@@ -412,6 +496,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.enabled_buttons())
     }
 
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        x11_or_wayland!(match self; Window(w) => w.supported_buttons())
+    }
+
     #[inline]
     pub fn set_cursor(&self, cursor: Cursor) {
         x11_or_wayland!(match self; Window(w) => w.set_cursor(cursor))
@@ -437,6 +526,11 @@ impl Window {
         x11_or_wayland!(match self; Window(window) => window.drag_resize_window(direction))
     }
 
+    #[inline]
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(window) => window.start_drag_and_drop(payload))
+    }
+
     #[inline]
     pub fn show_window_menu(&self, position: Position) {
         x11_or_wayland!(match self; Window(w) => w.show_window_menu(position))
@@ -507,6 +601,31 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_window_icon(window_icon.map(|icon| icon.inner)))
     }
 
+    #[inline]
+    pub fn set_transient_parent(&self, parent: Option<&Window>) -> Result<(), ExternalError> {
+        match (self, parent) {
+            #[cfg(x11_platform)]
+            (Window::X(w), Some(Window::X(parent))) => w.set_transient_parent(Some(parent)),
+            #[cfg(x11_platform)]
+            (Window::X(w), None) => w.set_transient_parent(None),
+            #[cfg(wayland_platform)]
+            (Window::Wayland(w), Some(Window::Wayland(parent))) => {
+                w.set_transient_parent(Some(parent))
+            },
+            #[cfg(wayland_platform)]
+            (Window::Wayland(w), None) => w.set_transient_parent(None),
+            #[cfg(headless_platform)]
+            (Window::Headless(w), Some(Window::Headless(parent))) => {
+                w.set_transient_parent(Some(parent))
+            },
+            #[cfg(headless_platform)]
+            (Window::Headless(w), None) => w.set_transient_parent(None),
+            // Windows from different backends can't be related.
+            #[allow(unreachable_patterns)]
+            _ => Err(ExternalError::Ignored),
+        }
+    }
+
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
         x11_or_wayland!(match self; Window(w) => w.set_ime_cursor_area(position, size))
@@ -527,6 +646,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_ime_purpose(purpose))
     }
 
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        x11_or_wayland!(match self; Window(w) => w.ime_capabilities())
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         x11_or_wayland!(match self; Window(w) => w.focus_window())
@@ -536,16 +660,41 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.request_user_attention(request_type))
     }
 
+    #[inline]
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        x11_or_wayland!(match self; Window(w) => w.set_progress(state))
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        x11_or_wayland!(match self; Window(w) => w.set_badge_count(count))
+    }
+
     #[inline]
     pub fn request_redraw(&self) {
         x11_or_wayland!(match self; Window(w) => w.request_redraw())
     }
 
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        x11_or_wayland!(match self; Window(w) => w.request_redraw_with_damage(rects))
+    }
+
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        x11_or_wayland!(match self; Window(w) => w.redraw_damage())
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         x11_or_wayland!(match self; Window(w) => w.pre_present_notify())
     }
 
+    #[inline]
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        x11_or_wayland!(match self; Window(w) => w.set_presentation_feedback(enabled))
+    }
+
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         Some(x11_or_wayland!(match self; Window(w) => w.current_monitor()?; as MonitorHandle))
@@ -562,6 +711,10 @@ impl Window {
             Window::Wayland(ref window) => {
                 window.available_monitors().into_iter().map(MonitorHandle::Wayland).collect()
             },
+            #[cfg(headless_platform)]
+            Window::Headless(ref window) => {
+                window.available_monitors().into_iter().map(MonitorHandle::Headless).collect()
+            },
         }
     }
 
@@ -638,6 +791,8 @@ pub(crate) enum PlatformCustomCursor {
     Wayland(wayland::CustomCursor),
     #[cfg(x11_platform)]
     X(x11::CustomCursor),
+    #[cfg(headless_platform)]
+    Headless(crate::cursor::OnlyCursorImage),
 }
 
 /// Hooks for X11 errors.
@@ -696,6 +851,8 @@ pub enum EventLoop {
     Wayland(Box<wayland::EventLoop>),
     #[cfg(x11_platform)]
     X(x11::EventLoop),
+    #[cfg(headless_platform)]
+    Headless(headless::EventLoop),
 }
 
 #[derive(Clone)]
@@ -704,6 +861,8 @@ pub enum EventLoopProxy {
     X(x11::EventLoopProxy),
     #[cfg(wayland_platform)]
     Wayland(wayland::EventLoopProxy),
+    #[cfg(headless_platform)]
+    Headless(headless::EventLoopProxy),
 }
 
 impl EventLoop {
@@ -740,7 +899,11 @@ impl EventLoop {
             // X11 is present.
             #[cfg(x11_platform)]
             (None, _, true) => Backend::X,
+            // The headless backend is the only one.
+            #[cfg(all(headless_platform, not(x11_platform), not(wayland_platform)))]
+            (None, ..) => Backend::Headless,
             // No backend is present.
+            #[cfg(any(x11_platform, wayland_platform))]
             (_, wayland_display, x11_display) => {
                 let msg = if wayland_display && !cfg!(wayland_platform) {
                     "DISPLAY is not set; note: enable the `winit/wayland` feature to support \
@@ -761,6 +924,8 @@ impl EventLoop {
             Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
             #[cfg(x11_platform)]
             Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
+            #[cfg(headless_platform)]
+            Backend::Headless => headless::EventLoop::new().map(EventLoop::Headless),
         }
     }
 
@@ -779,12 +944,22 @@ impl EventLoop {
         Ok(EventLoop::X(x11::EventLoop::new(xconn)))
     }
 
+    #[cfg(wayland_platform)]
     #[inline]
     pub fn is_wayland(&self) -> bool {
         match *self {
-            #[cfg(wayland_platform)]
             EventLoop::Wayland(_) => true,
-            #[cfg(x11_platform)]
+            #[cfg(any(x11_platform, headless_platform))]
+            _ => false,
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn is_x11(&self) -> bool {
+        match *self {
+            EventLoop::X(_) => true,
+            #[cfg(any(wayland_platform, headless_platform))]
             _ => false,
         }
     }
@@ -836,6 +1011,8 @@ pub enum ActiveEventLoop {
     Wayland(wayland::ActiveEventLoop),
     #[cfg(x11_platform)]
     X(x11::ActiveEventLoop),
+    #[cfg(headless_platform)]
+    Headless(headless::ActiveEventLoop),
 }
 
 impl ActiveEventLoop {
@@ -843,16 +1020,36 @@ impl ActiveEventLoop {
         x11_or_wayland!(match self; ActiveEventLoop(evlp) => evlp.create_proxy(); as EventLoopProxy)
     }
 
+    #[cfg(wayland_platform)]
     #[inline]
     pub fn is_wayland(&self) -> bool {
         match *self {
-            #[cfg(wayland_platform)]
             ActiveEventLoop::Wayland(_) => true,
-            #[cfg(x11_platform)]
+            #[cfg(any(x11_platform, headless_platform))]
+            _ => false,
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn is_x11(&self) -> bool {
+        match *self {
+            ActiveEventLoop::X(_) => true,
+            #[cfg(any(wayland_platform, headless_platform))]
             _ => false,
         }
     }
 
+    #[cfg(headless_platform)]
+    #[inline]
+    pub(crate) fn headless_shared(&self) -> Option<&Arc<headless::Shared>> {
+        match *self {
+            ActiveEventLoop::Headless(ref evlp) => Some(&evlp.shared),
+            #[cfg(any(x11_platform, wayland_platform))]
+            _ => None,
+        }
+    }
+
     pub fn create_custom_cursor(&self, cursor: CustomCursorSource) -> CustomCursor {
         x11_or_wayland!(match self; ActiveEventLoop(evlp) => evlp.create_custom_cursor(cursor))
     }
@@ -868,6 +1065,10 @@ impl ActiveEventLoop {
             ActiveEventLoop::X(ref evlp) => {
                 evlp.available_monitors().map(MonitorHandle::X).collect()
             },
+            #[cfg(headless_platform)]
+            ActiveEventLoop::Headless(ref evlp) => {
+                evlp.available_monitors().map(MonitorHandle::Headless).collect()
+            },
         }
     }
 
@@ -879,8 +1080,28 @@ impl ActiveEventLoop {
     }
 
     #[inline]
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        x11_or_wayland!(match self; Self(evlp) => evlp.listen_device_events(allowed))
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        x11_or_wayland!(match self; Self(evlp) => evlp.listen_device_events(subscription))
+    }
+
+    #[inline]
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        x11_or_wayland!(match self; Self(evlp) => evlp.set_motion_event_policy(policy))
+    }
+
+    #[inline]
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        x11_or_wayland!(match self; Self(evlp) => evlp.motion_event_policy())
+    }
+
+    #[inline]
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        x11_or_wayland!(match self; Self(evlp) => evlp.key_from_key_code(code))
+    }
+
+    #[inline]
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        x11_or_wayland!(match self; Self(evlp) => evlp.key_code_from_key(key))
     }
 
     #[cfg(feature = "rwh_05")]
@@ -923,6 +1144,8 @@ impl ActiveEventLoop {
             Self::X(conn) => OwnedDisplayHandle::X(conn.x_connection().clone()),
             #[cfg(wayland_platform)]
             Self::Wayland(conn) => OwnedDisplayHandle::Wayland(conn.connection.clone()),
+            #[cfg(headless_platform)]
+            Self::Headless(_) => OwnedDisplayHandle::Headless,
         }
     }
 
@@ -944,6 +1167,8 @@ pub(crate) enum OwnedDisplayHandle {
     X(Arc<XConnection>),
     #[cfg(wayland_platform)]
     Wayland(wayland_client::Connection),
+    #[cfg(headless_platform)]
+    Headless,
 }
 
 impl OwnedDisplayHandle {
@@ -967,6 +1192,9 @@ impl OwnedDisplayHandle {
                 wayland_handle.display = conn.display().id().as_ptr() as *mut _;
                 wayland_handle.into()
             },
+
+            #[cfg(headless_platform)]
+            Self::Headless => panic!("the headless backend has no raw display handle"),
         }
     }
 
@@ -994,6 +1222,9 @@ impl OwnedDisplayHandle {
                 )
                 .into())
             },
+
+            #[cfg(headless_platform)]
+            Self::Headless => Err(rwh_06::HandleError::NotSupported),
         }
     }
 }
@@ -1021,3 +1252,50 @@ fn is_main_thread() -> bool {
 fn is_main_thread() -> bool {
     std::thread::current().name() == Some("main")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(x11_platform)]
+    fn x11_platform_error() {
+        let error = XError {
+            description: "BadWindow (invalid Window parameter)".to_owned(),
+            error_code: 3,
+            request_code: 0,
+            minor_code: 0,
+        };
+        let error = platform_error(&OsError::XError(Arc::new(X11Error::Xlib(error)))).unwrap();
+        assert_eq!((error.domain(), error.code()), ("X11", Some(3)));
+        assert_eq!(error.message(), "BadWindow (invalid Window parameter)");
+
+        let error = X11Error::MissingExtension("Xinput");
+        assert_eq!(platform_error(&OsError::XError(Arc::new(error))), None);
+    }
+
+    #[test]
+    #[cfg(wayland_platform)]
+    fn wayland_platform_error() {
+        use sctk::reexports::client::backend::protocol::ProtocolError;
+        use sctk::reexports::client::backend::WaylandError as WireError;
+
+        let error = ProtocolError {
+            code: 4,
+            object_id: 3,
+            object_interface: "xdg_wm_base".to_owned(),
+            message: "xdg_surface has never been configured".to_owned(),
+        };
+        let error = wayland::WaylandError::Wire(WireError::Protocol(error));
+        let error = platform_error(&OsError::WaylandError(Arc::new(error))).unwrap();
+        assert_eq!((error.domain(), error.code()), ("xdg_wm_base", Some(4)));
+        assert_eq!(error.message(), "xdg_surface has never been configured");
+    }
+
+    #[test]
+    fn io_platform_error_has_errno() {
+        let error = io_platform_error(&std::io::Error::from_raw_os_error(13)).unwrap();
+        assert_eq!((error.domain(), error.code()), ("errno", Some(13)));
+        assert_eq!(platform_error(&OsError::Misc("misc")), None);
+    }
+}
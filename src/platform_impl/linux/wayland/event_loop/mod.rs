@@ -18,11 +18,20 @@ use crate::cursor::OnlyCursorImage;
 use crate::dpi::LogicalSize;
 use crate::error::{EventLoopError, OsError as RootOsError};
 use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
-use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEventKinds, DeviceEventSubscription,
+    DeviceEvents, MotionEventPolicy,
+};
+use crate::keyboard::{self, Key, KeyCode};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
+#[cfg(feature = "dbus")]
+use crate::platform_impl::common::session::SessionMonitor;
+use crate::platform_impl::common::xkb::KeyboardLayout;
 use crate::platform_impl::platform::min_timeout;
 use crate::platform_impl::{
-    ActiveEventLoop as PlatformActiveEventLoop, OsError, PlatformCustomCursor,
+    ActiveEventLoop as PlatformActiveEventLoop, MonitorHandle as PlatformMonitorHandle, OsError,
+    PlatformCustomCursor,
 };
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource};
 
@@ -32,9 +41,10 @@ pub mod sink;
 pub use proxy::EventLoopProxy;
 use sink::EventSink;
 
+use super::seat::WinitSeatState;
 use super::state::{WindowCompositorUpdate, WinitState};
-use super::window::state::FrameCallbackState;
-use super::{logical_to_physical_rounded, DeviceId, WaylandError, WindowId};
+use super::window::state::{schedule_cursor_animation, FrameCallbackState};
+use super::{logical_to_physical_rounded, DeviceId, MonitorHandle, WaylandError, WindowId};
 
 type WaylandDispatcher = calloop::Dispatcher<'static, WaylandSource<WinitState>, WinitState>;
 
@@ -57,6 +67,10 @@ pub struct EventLoop {
     /// Event loop window target.
     window_target: RootActiveEventLoop,
 
+    /// Monitors the session on the system bus, stopped when the event loop is dropped.
+    #[cfg(feature = "dbus")]
+    _session_monitor: SessionMonitor,
+
     // XXX drop after everything else, just to be safe.
     /// Calloop's event loop.
     event_loop: calloop::EventLoop<'static, WinitState>,
@@ -86,11 +100,15 @@ impl EventLoop {
         // races with the server.
         map_err!(event_queue.roundtrip(&mut winit_state), WaylandError::Dispatch)?;
 
+        // The outputs announced during the initial roundtrip were already connected.
+        winit_state.events_sink.drain();
+
         // Register Wayland source.
         let wayland_source = WaylandSource::new(connection.clone(), event_queue);
         let wayland_dispatcher =
             calloop::Dispatcher::new(wayland_source, |_, queue, winit_state: &mut WinitState| {
                 let result = queue.dispatch_pending(winit_state);
+                winit_state.queue_monitor_changes();
                 if result.is_ok()
                     && (!winit_state.events_sink.is_empty()
                         || !winit_state.window_compositor_updates.is_empty())
@@ -132,6 +150,23 @@ impl EventLoop {
             .map_err(|error| error.error);
         map_err!(result, WaylandError::Calloop)?;
 
+        // Forward the session events from the system bus.
+        #[cfg(feature = "dbus")]
+        let session_monitor = {
+            let (session_monitor, session_channel) = SessionMonitor::new();
+            let result = event_loop
+                .handle()
+                .insert_source(session_channel, |event, _, winit_state: &mut WinitState| {
+                    if let calloop::channel::Event::Msg(event) = event {
+                        winit_state.events_sink.push_session_event(event);
+                        winit_state.dispatched_events = true;
+                    }
+                })
+                .map_err(|error| error.error);
+            map_err!(result, WaylandError::Calloop)?;
+            session_monitor
+        };
+
         let window_target = ActiveEventLoop {
             connection: connection.clone(),
             wayland_dispatcher: wayland_dispatcher.clone(),
@@ -153,8 +188,11 @@ impl EventLoop {
             event_loop,
             window_target: RootActiveEventLoop {
                 p: PlatformActiveEventLoop::Wayland(window_target),
+                user_event_queue: Default::default(),
                 _marker: PhantomData,
             },
+            #[cfg(feature = "dbus")]
+            _session_monitor: session_monitor,
         };
 
         Ok(event_loop)
@@ -317,6 +355,26 @@ impl EventLoop {
 
         for mut compositor_update in compositor_updates.drain(..) {
             let window_id = compositor_update.window_id;
+
+            // The monitor change is delivered before the scale change it causes.
+            if compositor_update.monitor_changed {
+                let change = self.with_state(|state| {
+                    let windows = state.windows.get_mut();
+                    let mut window = windows.get(&window_id).unwrap().lock().unwrap();
+                    window.take_monitor_change()
+                });
+
+                if let Some((old, new)) = change {
+                    let monitor = |output| RootMonitorHandle {
+                        inner: PlatformMonitorHandle::Wayland(MonitorHandle::new(output)),
+                    };
+                    let root_window_id = crate::window::WindowId(window_id);
+                    let event =
+                        WindowEvent::MonitorChanged { old: old.map(monitor), new: monitor(new) };
+                    app.window_event(&self.window_target, root_window_id, event);
+                }
+            }
+
             if compositor_update.scale_changed {
                 let (physical_size, scale_factor) = self.with_state(|state| {
                     let windows = state.windows.get_mut();
@@ -418,7 +476,18 @@ impl EventLoop {
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.window_target, device_id, event)
                 },
-                _ => unreachable!("event which is neither device nor window event."),
+                Event::MonitorConnected(monitor) => {
+                    app.monitor_connected(&self.window_target, monitor)
+                },
+                Event::MonitorDisconnected(monitor) => {
+                    app.monitor_disconnected(&self.window_target, monitor)
+                },
+                Event::KeyboardLayoutChanged => app.keyboard_layout_changed(&self.window_target),
+                Event::SessionEvent(event) => app.session_event(&self.window_target, event),
+                _ => unreachable!(
+                    "event which is neither device, window, monitor, keyboard layout nor session \
+                     event."
+                ),
             }
         }
 
@@ -429,6 +498,16 @@ impl EventLoop {
 
         for window_id in window_ids.iter() {
             let event = self.with_state(|state| {
+                // The drag is started here, since the data devices are owned by the state.
+                let pending_drag = state
+                    .windows
+                    .get_mut()
+                    .get(window_id)
+                    .and_then(|window| window.lock().unwrap().take_pending_drag());
+                if let Some(payload) = pending_drag {
+                    state.start_drag(*window_id, payload);
+                }
+
                 let window_requests = state.window_requests.get_mut();
                 if window_requests.get(window_id).unwrap().take_closed() {
                     mem::drop(window_requests.remove(window_id));
@@ -445,12 +524,16 @@ impl EventLoop {
 
                 // Reset the frame callbacks state.
                 window.frame_callback_reset();
-                let mut redraw_requested =
-                    window_requests.get(window_id).unwrap().take_redraw_requested();
+                let window_requests = window_requests.get(window_id).unwrap();
+                let mut redraw_requested = window_requests.take_redraw_requested();
 
                 // Redraw the frame while at it.
                 redraw_requested |= window.refresh_frame();
 
+                if redraw_requested {
+                    window_requests.redraw_damage.lock().unwrap().deliver();
+                }
+
                 redraw_requested.then_some(WindowEvent::RedrawRequested)
             });
 
@@ -473,7 +556,12 @@ impl EventLoop {
         for window_id in window_ids.drain(..) {
             wake_up |= self.with_state(|state| match state.windows.get_mut().get_mut(&window_id) {
                 Some(window) => {
-                    let refresh = window.lock().unwrap().refresh_frame();
+                    let mut window = window.lock().unwrap();
+                    if let Some(delay) = window.start_cursor_animation() {
+                        schedule_cursor_animation(&state.loop_handle, window_id, delay);
+                    }
+
+                    let refresh = window.refresh_frame();
                     if refresh {
                         state
                             .window_requests
@@ -501,6 +589,8 @@ impl EventLoop {
                 },
                 #[cfg(x11_platform)]
                 PlatformActiveEventLoop::X(_) => unreachable!(),
+                #[cfg(headless_platform)]
+                PlatformActiveEventLoop::Headless(_) => unreachable!(),
             }
         }
 
@@ -517,6 +607,8 @@ impl EventLoop {
     fn with_state<'a, U: 'a, F: FnOnce(&'a mut WinitState) -> U>(&'a mut self, callback: F) -> U {
         let state = match &mut self.window_target.p {
             PlatformActiveEventLoop::Wayland(window_target) => window_target.state.get_mut(),
+            #[cfg(headless_platform)]
+            PlatformActiveEventLoop::Headless(_) => unreachable!(),
             #[cfg(x11_platform)]
             _ => unreachable!(),
         };
@@ -527,6 +619,8 @@ impl EventLoop {
     fn loop_dispatch<D: Into<Option<std::time::Duration>>>(&mut self, timeout: D) -> IOResult<()> {
         let state = match &mut self.window_target.p {
             PlatformActiveEventLoop::Wayland(window_target) => window_target.state.get_mut(),
+            #[cfg(headless_platform)]
+            PlatformActiveEventLoop::Headless(_) => unreachable!(),
             #[cfg(feature = "x11")]
             _ => unreachable!(),
         };
@@ -540,6 +634,8 @@ impl EventLoop {
     fn roundtrip(&mut self) -> Result<usize, RootOsError> {
         let state = match &mut self.window_target.p {
             PlatformActiveEventLoop::Wayland(window_target) => window_target.state.get_mut(),
+            #[cfg(headless_platform)]
+            PlatformActiveEventLoop::Headless(_) => unreachable!(),
             #[cfg(feature = "x11")]
             _ => unreachable!(),
         };
@@ -640,12 +736,47 @@ impl ActiveEventLoop {
         self.exit.get()
     }
 
-    #[inline]
-    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+    /// The keyboard layout of the first seat with a keymap.
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.state.borrow().seats.values().find_map(WinitSeatState::keyboard_layout)
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        match self.keyboard_layout() {
+            Some(layout) => layout.key_from_key_code(code),
+            None => keyboard::layout_independent_key(code),
+        }
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        match self.keyboard_layout() {
+            Some(layout) => layout.key_code_from_key(key),
+            None => keyboard::find_key_code(key, keyboard::layout_independent_keys()),
+        }
+    }
+
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        if subscription.kinds().intersects(!DeviceEventKinds::MOTION) {
+            tracing::debug!("Wayland can only capture the raw motion");
+        }
+
+        // The relative pointer only reports the motion over our surfaces, from any device.
+        let enabled = subscription.when() != DeviceEvents::Never
+            && subscription.kinds().contains(DeviceEventKinds::MOTION);
+        self.state.borrow_mut().set_relative_pointer_enabled(enabled, &self.queue_handle);
+    }
+
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        self.state.borrow_mut().events_sink.motion_event_policy = policy;
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.state.borrow().events_sink.motion_event_policy
+    }
 
     pub(crate) fn create_custom_cursor(&self, cursor: CustomCursorSource) -> RootCustomCursor {
         RootCustomCursor {
-            inner: PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::from(cursor.inner.0))),
+            inner: PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::new(cursor.inner))),
         }
     }
 
@@ -2,7 +2,10 @@
 
 use std::vec::Drain;
 
+#[cfg(feature = "dbus")]
+use crate::event::SessionEvent;
 use crate::event::{DeviceEvent, DeviceId as RootDeviceId, Event, WindowEvent};
+use crate::event_loop::MotionEventPolicy;
 use crate::platform_impl::platform::DeviceId as PlatformDeviceId;
 use crate::window::WindowId as RootWindowId;
 
@@ -13,6 +16,9 @@ use super::{DeviceId, WindowId};
 #[derive(Default)]
 pub struct EventSink {
     pub(crate) window_events: Vec<Event>,
+
+    /// Whether the consecutive cursor motions of a window are merged while they're queued.
+    pub(crate) motion_event_policy: MotionEventPolicy,
 }
 
 impl EventSink {
@@ -38,7 +44,41 @@ impl EventSink {
     /// Add new window event to a queue.
     #[inline]
     pub fn push_window_event(&mut self, event: WindowEvent, window_id: WindowId) {
-        self.window_events.push(Event::WindowEvent { event, window_id: RootWindowId(window_id) });
+        let window_id = RootWindowId(window_id);
+        if let (
+            MotionEventPolicy::Coalesced,
+            WindowEvent::CursorMoved { device_id, .. },
+            Some(Event::WindowEvent {
+                window_id: last_window_id,
+                event: WindowEvent::CursorMoved { device_id: last_device_id, .. },
+            }),
+        ) = (self.motion_event_policy, &event, self.window_events.last())
+        {
+            if *last_window_id == window_id && last_device_id == device_id {
+                self.window_events.pop();
+            }
+        }
+
+        self.window_events.push(Event::WindowEvent { event, window_id });
+    }
+
+    /// Add new monitor event to a queue.
+    #[inline]
+    pub(crate) fn push_monitor_event(&mut self, event: Event) {
+        self.window_events.push(event);
+    }
+
+    /// Report that the keyboard layout changed.
+    #[inline]
+    pub(crate) fn push_keyboard_layout_changed(&mut self) {
+        self.window_events.push(Event::KeyboardLayoutChanged);
+    }
+
+    /// Add new session event to a queue.
+    #[cfg(feature = "dbus")]
+    #[inline]
+    pub(crate) fn push_session_event(&mut self, event: SessionEvent) {
+        self.window_events.push(Event::SessionEvent(event));
     }
 
     #[inline]
@@ -9,6 +9,7 @@ use sctk::reexports::client::{self, ConnectError, DispatchError, Proxy};
 
 pub(super) use crate::cursor::OnlyCursorImage as CustomCursor;
 use crate::dpi::{LogicalSize, PhysicalSize};
+use crate::error::PlatformError;
 pub use crate::platform_impl::platform::{OsError, WindowId};
 pub use event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
 pub use output::{MonitorHandle, VideoModeHandle};
@@ -55,6 +56,30 @@ impl Display for WaylandError {
     }
 }
 
+impl WaylandError {
+    /// The error as reported by the compositor or the connection.
+    pub(crate) fn platform_error(&self) -> Option<PlatformError> {
+        let error = match self {
+            WaylandError::Wire(error) | WaylandError::Dispatch(DispatchError::Backend(error)) => {
+                error
+            },
+            WaylandError::Calloop(calloop::Error::IoError(error)) => {
+                return super::io_platform_error(error)
+            },
+            _ => return None,
+        };
+
+        match error {
+            client::backend::WaylandError::Protocol(error) => Some(PlatformError::new(
+                error.object_interface.clone(),
+                Some(error.code.into()),
+                error.message.clone(),
+            )),
+            client::backend::WaylandError::Io(error) => super::io_platform_error(error),
+        }
+    }
+}
+
 impl From<WaylandError> for OsError {
     fn from(value: WaylandError) -> Self {
         Self::WaylandError(Arc::new(value))
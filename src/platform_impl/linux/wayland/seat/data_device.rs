@@ -0,0 +1,395 @@
+//! Drag and drop handling.
+
+use std::io::{ErrorKind, Read, Write};
+use std::path::PathBuf;
+
+use percent_encoding::percent_decode;
+use sctk::data_device_manager::data_device::{DataDeviceData, DataDeviceHandler};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::{DataSourceHandler, DragSource};
+use sctk::data_device_manager::WritePipe;
+use sctk::reexports::calloop::PostAction;
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::shell::WaylandSurface;
+use tracing::warn;
+
+use crate::dpi::{LogicalPosition, PhysicalPosition};
+use crate::event::{DragAction, DragDropEvent, DragDropItem, DragDropKinds, WindowEvent};
+use crate::platform_impl::common::uri_list;
+use crate::platform_impl::wayland::seat::WinitPointerDataExt;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+use crate::window::DragPayload;
+
+/// The mime type used for paths.
+const URI_LIST_MIME_TYPE: &str = "text/uri-list";
+
+/// The mime types used for text, by order of preference.
+const TEXT_MIME_TYPES: [&str; 3] = ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
+
+/// The drag currently over one of our windows.
+#[derive(Debug)]
+pub struct DragState {
+    window_id: WindowId,
+    mime_type: String,
+    position: PhysicalPosition<f64>,
+    dropped: bool,
+}
+
+/// The drag started from one of our windows.
+#[derive(Debug)]
+pub struct DragSourceState {
+    source: DragSource,
+    window_id: WindowId,
+    /// The data sent for any of the offered mime types.
+    data: Vec<u8>,
+    action: Option<DragAction>,
+}
+
+impl WinitState {
+    /// Get the data device for the seat, which delivers the drag and drop events.
+    pub fn bind_data_device(&mut self, queue_handle: &QueueHandle<Self>, seat: &WlSeat) {
+        let (Some(manager), Some(seat_state)) =
+            (self.data_device_manager_state.as_ref(), self.seats.get_mut(&seat.id()))
+        else {
+            return;
+        };
+
+        seat_state.data_device = Some(manager.get_data_device(queue_handle, seat));
+    }
+
+    /// Start dragging `payload` out of the window, with the button held by a pointer over it.
+    pub fn start_drag(&mut self, window_id: WindowId, payload: DragPayload) {
+        // Only a single drag may be in progress.
+        if let Some(drag_source) = self.drag_source.take() {
+            self.finish_drag_source(drag_source, None);
+        }
+
+        match self.create_drag_source(window_id, payload) {
+            Some(drag_source) => self.drag_source = Some(drag_source),
+            None => self
+                .events_sink
+                .push_window_event(WindowEvent::DragSourceFinished { action: None }, window_id),
+        }
+    }
+
+    fn create_drag_source(
+        &self,
+        window_id: WindowId,
+        payload: DragPayload,
+    ) -> Option<DragSourceState> {
+        let Some(manager) = self.data_device_manager_state.as_ref() else {
+            warn!("Drag and drop is not supported by the compositor");
+            return None;
+        };
+
+        // The button may have been released before the event loop got to start the drag.
+        let (pointer, data_device) = self.seats.values().find_map(|seat_state| {
+            let pointer = seat_state.pointer.as_ref()?;
+            let data = pointer.pointer().winit_data();
+            (data.focused_window() == Some(window_id) && data.is_button_held())
+                .then_some((pointer, seat_state.data_device.as_ref()?))
+        })?;
+
+        let (mime_types, data) = match payload {
+            DragPayload::Paths(paths) => (&[URI_LIST_MIME_TYPE][..], uri_list::encode(&paths)),
+            DragPayload::Text(text) => (&TEXT_MIME_TYPES[..], text),
+        };
+
+        let windows = self.windows.borrow();
+        let window = windows.get(&window_id)?.lock().unwrap();
+        let source = manager.create_drag_and_drop_source(
+            &window.queue_handle,
+            mime_types.iter().copied(),
+            DndAction::Copy | DndAction::Move,
+        );
+        let serial = pointer.pointer().winit_data().latest_button_serial();
+        source.start_drag(data_device, window.window.wl_surface(), None, serial);
+
+        Some(DragSourceState { source, window_id, data: data.into_bytes(), action: None })
+    }
+
+    fn finish_drag_source(&mut self, drag_source: DragSourceState, action: Option<DragAction>) {
+        self.events_sink
+            .push_window_event(WindowEvent::DragSourceFinished { action }, drag_source.window_id);
+    }
+
+    fn drag_source(&mut self, source: &WlDataSource) -> Option<&mut DragSourceState> {
+        self.drag_source.as_mut().filter(|drag_source| drag_source.source.inner() == source)
+    }
+
+    fn drag_position(
+        &self,
+        offer: &DragOffer,
+        window_id: WindowId,
+    ) -> Option<PhysicalPosition<f64>> {
+        let scale_factor = self.windows.borrow().get(&window_id)?.lock().unwrap().scale_factor();
+        Some(LogicalPosition::new(offer.x, offer.y).to_physical(scale_factor))
+    }
+}
+
+impl DataDeviceHandler for WinitState {
+    fn enter(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let data = data_device.data::<DataDeviceData>().unwrap();
+        let Some(offer) = data.drag_offer() else {
+            return;
+        };
+
+        let window_id = wayland::make_wid(&offer.surface);
+        let mime_type = offer.with_mime_types(|mime_types| {
+            if mime_types.iter().any(|mime_type| mime_type == URI_LIST_MIME_TYPE) {
+                return Some(URI_LIST_MIME_TYPE);
+            }
+            TEXT_MIME_TYPES.into_iter().find(|text| mime_types.iter().any(|mime| mime == text))
+        });
+
+        let (Some(mime_type), Some(position)) = (mime_type, self.drag_position(&offer, window_id))
+        else {
+            offer.accept_mime_type(offer.serial, None);
+            offer.set_actions(DndAction::empty(), DndAction::empty());
+            return;
+        };
+
+        offer.accept_mime_type(offer.serial, Some(mime_type.to_owned()));
+        offer.set_actions(DndAction::Copy, DndAction::Copy);
+
+        let kinds = if mime_type == URI_LIST_MIME_TYPE {
+            DragDropKinds::PATHS
+        } else {
+            DragDropKinds::TEXT
+        };
+
+        let seat_state = self.seats.get_mut(&data.seat().id()).unwrap();
+        seat_state.drag = Some(DragState {
+            window_id,
+            mime_type: mime_type.to_owned(),
+            position,
+            dropped: false,
+        });
+
+        self.events_sink.push_window_event(
+            WindowEvent::DragDrop(DragDropEvent::Entered { kinds, position }),
+            window_id,
+        );
+    }
+
+    fn leave(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let data = data_device.data::<DataDeviceData>().unwrap();
+        let seat_state = self.seats.get_mut(&data.seat().id()).unwrap();
+
+        // The leave event is also sent once the data was dropped.
+        if let Some(drag) = seat_state.drag.take().filter(|drag| !drag.dropped) {
+            self.events_sink
+                .push_window_event(WindowEvent::DragDrop(DragDropEvent::Left), drag.window_id);
+        }
+    }
+
+    fn motion(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let data = data_device.data::<DataDeviceData>().unwrap();
+        let Some(offer) = data.drag_offer() else {
+            return;
+        };
+
+        let Some(window_id) = self
+            .seats
+            .get(&data.seat().id())
+            .and_then(|seat_state| seat_state.drag.as_ref())
+            .map(|drag| drag.window_id)
+        else {
+            return;
+        };
+
+        let Some(position) = self.drag_position(&offer, window_id) else {
+            return;
+        };
+
+        let drag = self.seats.get_mut(&data.seat().id()).unwrap().drag.as_mut().unwrap();
+        if drag.position != position {
+            drag.position = position;
+            self.events_sink.push_window_event(
+                WindowEvent::DragDrop(DragDropEvent::Moved { position }),
+                window_id,
+            );
+        }
+    }
+
+    fn selection(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {}
+
+    fn drop_performed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        data_device: &WlDataDevice,
+    ) {
+        let data = data_device.data::<DataDeviceData>().unwrap();
+        let Some(offer) = data.drag_offer() else {
+            return;
+        };
+
+        let seat_state = self.seats.get_mut(&data.seat().id()).unwrap();
+        let Some(drag) = seat_state.drag.as_mut() else {
+            return;
+        };
+        drag.dropped = true;
+
+        let window_id = drag.window_id;
+        let mime_type = drag.mime_type.clone();
+        let pipe = match offer.receive(mime_type.clone()) {
+            Ok(pipe) => pipe,
+            Err(err) => {
+                warn!("Failed to receive the dropped data: {err}");
+                let event = DragDropEvent::Dropped { items: Vec::new() };
+                self.events_sink.push_window_event(WindowEvent::DragDrop(event), window_id);
+                offer.finish();
+                offer.destroy();
+                return;
+            },
+        };
+
+        // Read the data without blocking, the source may take some time to write it.
+        let mut buffer = Vec::new();
+        let result = self.loop_handle.insert_source(pipe, move |_, file, state| {
+            let mut chunk = [0; 4096];
+            match unsafe { file.get_mut() }.read(&mut chunk) {
+                Ok(0) => (),
+                Ok(len) => {
+                    buffer.extend_from_slice(&chunk[..len]);
+                    return PostAction::Continue;
+                },
+                Err(err) if err.kind() == ErrorKind::Interrupted => return PostAction::Continue,
+                Err(err) => {
+                    warn!("Failed to read the dropped data: {err}");
+                    buffer.clear();
+                },
+            }
+
+            // Required to handle the wakeups from the pipe.
+            state.dispatched_events = true;
+
+            let items = parse_items(&mime_type, &buffer);
+            let event = DragDropEvent::Dropped { items };
+            state.events_sink.push_window_event(WindowEvent::DragDrop(event), window_id);
+
+            offer.finish();
+            offer.destroy();
+            PostAction::Remove
+        });
+
+        if let Err(err) = result {
+            warn!("Failed to read the dropped data: {err}");
+        }
+    }
+}
+
+impl DataOfferHandler for WinitState {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for WinitState {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        _: String,
+        mut pipe: WritePipe,
+    ) {
+        // All the offered mime types use the same data.
+        let Some(drag_source) = self.drag_source(source) else {
+            return;
+        };
+
+        if let Err(err) = pipe.write_all(&drag_source.data) {
+            warn!("Failed to send the dragged data: {err}");
+        }
+    }
+
+    fn cancelled(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        if self.drag_source(source).is_some() {
+            let drag_source = self.drag_source.take().unwrap();
+            self.finish_drag_source(drag_source, None);
+        }
+    }
+
+    fn dnd_dropped(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_finished(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        if let Some(drag_source) = self.drag_source(source) {
+            let action = drag_source.action;
+            let drag_source = self.drag_source.take().unwrap();
+            self.finish_drag_source(drag_source, action);
+        }
+    }
+
+    fn action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        action: DndAction,
+    ) {
+        if let Some(drag_source) = self.drag_source(source) {
+            drag_source.action = if action.contains(DndAction::Move) {
+                Some(DragAction::Move)
+            } else if action.contains(DndAction::Copy) {
+                Some(DragAction::Copy)
+            } else {
+                None
+            };
+        }
+    }
+}
+
+/// Parse the data received with `mime_type`.
+fn parse_items(mime_type: &str, data: &[u8]) -> Vec<DragDropItem> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    if mime_type != URI_LIST_MIME_TYPE {
+        let text = String::from_utf8_lossy(data).trim_end_matches('\0').to_owned();
+        return vec![DragDropItem::Text(text)];
+    }
+
+    // Only local files are supported, the format is `file://host/path` with an empty or local
+    // host, and comments start with `#`.
+    String::from_utf8_lossy(data)
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|uri| uri.strip_prefix("file://"))
+        .filter_map(|path| path.find('/').map(|start| &path[start..]))
+        .filter_map(|path| percent_decode(path.as_bytes()).decode_utf8().ok())
+        .map(|path| DragDropItem::Path(PathBuf::from(path.into_owned())))
+        .collect()
+}
+
+sctk::delegate_data_device!(WinitState);
@@ -13,8 +13,7 @@ use sctk::reexports::client::protocol::wl_keyboard::{
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 
-use crate::event::{ElementState, WindowEvent};
-use crate::keyboard::ModifiersState;
+use crate::event::{ElementState, Modifiers, WindowEvent};
 
 use crate::platform_impl::common::xkb::Context;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
@@ -45,6 +44,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     WlKeymapFormat::XkbV1 => {
                         let context = &mut seat_state.keyboard_state.as_mut().unwrap().xkb_context;
                         context.set_keymap_from_fd(fd, size as usize);
+                        state.events_sink.push_keyboard_layout_changed();
                     },
                     _ => unreachable!(),
                 },
@@ -83,7 +83,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                        WindowEvent::ModifiersChanged(seat_state.modifiers),
                         window_id,
                     );
                 }
@@ -115,9 +115,11 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 *data.window_id.lock().unwrap() = None;
 
                 if !focused {
-                    // Notify that no modifiers are being pressed.
+                    // Notify that no modifiers are being pressed, and start over with the next
+                    // window.
+                    seat_state.modifiers = Modifiers::default();
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(ModifiersState::empty().into()),
+                        WindowEvent::ModifiersChanged(Modifiers::default()),
                         window_id,
                     );
 
@@ -222,8 +224,13 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     None => return,
                 };
 
+                let layout = xkb_state.effective_layout();
                 xkb_state.update_modifiers(mods_depressed, mods_latched, mods_locked, 0, 0, group);
-                seat_state.modifiers = xkb_state.modifiers().into();
+                let modifiers = xkb_state.modifiers().into();
+                let changed = std::mem::replace(&mut seat_state.modifiers, modifiers) != modifiers;
+                if xkb_state.effective_layout() != layout {
+                    state.events_sink.push_keyboard_layout_changed();
+                }
 
                 // HACK: part of the workaround from `WlKeyboardEvent::Enter`.
                 let window_id = match *data.window_id.lock().unwrap() {
@@ -234,10 +241,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     },
                 };
 
-                state.events_sink.push_window_event(
-                    WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
-                    window_id,
-                );
+                // The modifiers are also sent for changes we don't report, like when releasing
+                // caps lock.
+                if changed {
+                    state.events_sink.push_window_event(
+                        WindowEvent::ModifiersChanged(seat_state.modifiers),
+                        window_id,
+                    );
+                }
             },
             WlKeyboardEvent::RepeatInfo { rate, delay } => {
                 let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use ahash::AHashMap;
 
+use sctk::data_device_manager::data_device::DataDevice;
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
@@ -14,15 +15,17 @@ use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 
-use crate::event::WindowEvent;
-use crate::keyboard::ModifiersState;
+use crate::event::{Modifiers, WindowEvent};
+use crate::platform_impl::common::xkb::KeyboardLayout;
 use crate::platform_impl::wayland::state::WinitState;
 
+mod data_device;
 mod keyboard;
 mod pointer;
 mod text_input;
 mod touch;
 
+pub use data_device::DragSourceState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
 pub use text_input::{TextInputState, ZwpTextInputV3Ext};
@@ -52,16 +55,27 @@ pub struct WinitSeatState {
     keyboard_state: Option<KeyboardState>,
 
     /// The current modifiers state on the seat.
-    modifiers: ModifiersState,
+    modifiers: Modifiers,
 
     /// Whether we have pending modifiers.
     modifiers_pending: bool,
+
+    /// The data device bound on the seat.
+    data_device: Option<DataDevice>,
+
+    /// The drag currently over one of our windows.
+    drag: Option<data_device::DragState>,
 }
 
 impl WinitSeatState {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The active keyboard layout, or `None` when the seat has no keyboard or keymap yet.
+    pub fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.keyboard_state.as_ref()?.xkb_context.keyboard_layout()
+    }
 }
 
 impl SeatHandler for WinitState {
@@ -103,13 +117,16 @@ impl SeatHandler for WinitState {
                     )
                     .expect("failed to create pointer with present capability.");
 
-                seat_state.relative_pointer = self.relative_pointer.as_ref().map(|manager| {
-                    manager.get_relative_pointer(
-                        themed_pointer.pointer(),
-                        queue_handle,
-                        sctk::globals::GlobalData,
-                    )
-                });
+                seat_state.relative_pointer =
+                    self.relative_pointer.as_ref().filter(|manager| manager.enabled).map(
+                        |manager| {
+                            manager.get_relative_pointer(
+                                themed_pointer.pointer(),
+                                queue_handle,
+                                sctk::globals::GlobalData,
+                            )
+                        },
+                    );
 
                 let themed_pointer = Arc::new(themed_pointer);
 
@@ -185,10 +202,11 @@ impl SeatHandler for WinitState {
     fn new_seat(
         &mut self,
         _connection: &Connection,
-        _queue_handle: &QueueHandle<Self>,
+        queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
         self.seats.insert(seat.id(), WinitSeatState::new());
+        self.bind_data_device(queue_handle, &seat);
     }
 
     fn remove_seat(
@@ -203,6 +221,33 @@ impl SeatHandler for WinitState {
 }
 
 impl WinitState {
+    /// Bind or release the relative pointers of all the seats.
+    pub fn set_relative_pointer_enabled(
+        &mut self,
+        enabled: bool,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        let manager = match self.relative_pointer.as_mut() {
+            Some(manager) if manager.enabled != enabled => manager,
+            _ => return,
+        };
+        manager.enabled = enabled;
+
+        for seat_state in self.seats.values_mut() {
+            if !enabled {
+                if let Some(relative_pointer) = seat_state.relative_pointer.take() {
+                    relative_pointer.destroy();
+                }
+            } else if let Some(pointer) = seat_state.pointer.as_ref() {
+                seat_state.relative_pointer = Some(manager.get_relative_pointer(
+                    pointer.pointer(),
+                    queue_handle,
+                    sctk::globals::GlobalData,
+                ));
+            }
+        }
+    }
+
     fn on_keyboard_destroy(&mut self, seat: &ObjectId) {
         for (window_id, window) in self.windows.get_mut() {
             let mut window = window.lock().unwrap();
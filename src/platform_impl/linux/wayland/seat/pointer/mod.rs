@@ -2,7 +2,7 @@
 
 use std::ops::Deref;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use sctk::reexports::client::delegate_dispatch;
 use sctk::reexports::client::protocol::wl_pointer::WlPointer;
@@ -29,6 +29,7 @@ use crate::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, Wind
 
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, DeviceId, WindowId};
+use crate::utils::{ClickCounter, ClickThresholds};
 
 pub mod relative_pointer;
 
@@ -118,7 +119,11 @@ impl PointerHandler for WinitState {
                     }
 
                     // Set the currently focused surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = Some(window_id);
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.surface = Some(window_id);
+                    pointer_data.pressed_buttons = 0;
+                    pointer_data.click_counter = ClickCounter::default();
+                    drop(pointer_data);
 
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved { device_id, position },
@@ -131,12 +136,19 @@ impl PointerHandler for WinitState {
                     }
 
                     // Remove the active surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = None;
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.surface = None;
+                    pointer_data.pressed_buttons = 0;
+                    drop(pointer_data);
 
                     self.events_sink
                         .push_window_event(WindowEvent::CursorLeft { device_id }, window_id);
                 },
                 PointerEventKind::Motion { .. } => {
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.click_counter.moved(position, ClickThresholds::default());
+                    drop(pointer_data);
+
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved { device_id, position },
                         window_id,
@@ -144,17 +156,35 @@ impl PointerHandler for WinitState {
                 },
                 ref kind @ PointerEventKind::Press { button, serial, .. }
                 | ref kind @ PointerEventKind::Release { button, serial, .. } => {
-                    // Update the last button serial.
-                    pointer.winit_data().inner.lock().unwrap().latest_button_serial = serial;
-
                     let button = wayland_button_to_winit(button);
                     let state = if matches!(kind, PointerEventKind::Press { .. }) {
                         ElementState::Pressed
                     } else {
                         ElementState::Released
                     };
+
+                    // Update the last button serial.
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.latest_button_serial = serial;
+                    pointer_data.pressed_buttons = match state {
+                        ElementState::Pressed => pointer_data.pressed_buttons + 1,
+                        ElementState::Released => pointer_data.pressed_buttons.saturating_sub(1),
+                    };
+
+                    // The compositors don't share their double-click settings.
+                    let click_count = match state {
+                        ElementState::Pressed => pointer_data.click_counter.press(
+                            button,
+                            position,
+                            Instant::now(),
+                            ClickThresholds::default(),
+                        ),
+                        ElementState::Released => pointer_data.click_counter.release(button),
+                    };
+                    drop(pointer_data);
+
                     self.events_sink.push_window_event(
-                        WindowEvent::MouseInput { device_id, state, button },
+                        WindowEvent::MouseInput { device_id, state, button, click_count },
                         window_id,
                     );
                 },
@@ -199,7 +229,7 @@ impl PointerHandler for WinitState {
                     };
 
                     self.events_sink.push_window_event(
-                        WindowEvent::MouseWheel { device_id, delta, phase },
+                        WindowEvent::MouseWheel { device_id, delta, phase, momentum_phase: None },
                         window_id,
                     )
                 },
@@ -291,6 +321,11 @@ impl WinitPointerData {
         self.sctk_data.latest_button_serial().unwrap_or_default()
     }
 
+    /// Whether a button is held on the active window.
+    pub fn is_button_held(&self) -> bool {
+        self.inner.lock().unwrap().pressed_buttons > 0
+    }
+
     /// Last enter serial.
     pub fn latest_enter_serial(&self) -> u32 {
         self.sctk_data.latest_enter_serial().unwrap_or_default()
@@ -321,11 +356,17 @@ pub struct WinitPointerDataInner {
     /// Serial of the last button event.
     latest_button_serial: u32,
 
+    /// The number of buttons held since the pointer entered the surface.
+    pressed_buttons: u32,
+
     /// Currently focused window.
     surface: Option<WindowId>,
 
     /// Current axis phase.
     phase: TouchPhase,
+
+    /// The consecutive clicks on the focused surface.
+    click_counter: ClickCounter,
 }
 
 impl Drop for WinitPointerDataInner {
@@ -347,7 +388,9 @@ impl Default for WinitPointerDataInner {
             locked_pointer: None,
             confined_pointer: None,
             latest_button_serial: 0,
+            pressed_buttons: 0,
             phase: TouchPhase::Ended,
+            click_counter: ClickCounter::default(),
         }
     }
 }
@@ -18,6 +18,9 @@ use crate::platform_impl::wayland::state::WinitState;
 /// Wrapper around the relative pointer.
 pub struct RelativePointerState {
     manager: ZwpRelativePointerManagerV1,
+
+    /// Whether the relative pointers should be bound, to capture the raw motion.
+    pub enabled: bool,
 }
 
 impl RelativePointerState {
@@ -27,7 +30,7 @@ impl RelativePointerState {
         queue_handle: &QueueHandle<WinitState>,
     ) -> Result<Self, BindError> {
         let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
-        Ok(Self { manager })
+        Ok(Self { manager, enabled: true })
     }
 }
 
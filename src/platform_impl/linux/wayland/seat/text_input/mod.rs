@@ -74,6 +74,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 if window.ime_allowed() {
                     text_input.enable();
                     text_input.set_content_type_by_purpose(window.ime_purpose());
+                    window.apply_ime_cursor_area(text_input);
                     text_input.commit();
                     state.events_sink.push_window_event(WindowEvent::Ime(Ime::Enabled), window_id);
                 }
@@ -163,6 +164,8 @@ impl ZwpTextInputV3Ext for ZwpTextInputV3 {
             ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
             ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
             ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
+            ImePurpose::Email => (ContentHint::None, ContentPurpose::Email),
+            ImePurpose::Number => (ContentHint::None, ContentPurpose::Number),
         };
         self.set_content_type(hint, purpose);
     }
@@ -8,7 +8,7 @@ use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::seat::touch::{TouchData, TouchHandler};
 
 use crate::dpi::LogicalPosition;
-use crate::event::{Touch, TouchPhase, WindowEvent};
+use crate::event::{Touch, TouchPhase, TouchTool, WindowEvent};
 
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, DeviceId};
@@ -46,6 +46,12 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Started,
                 location: location.to_physical(scale_factor),
                 force: None,
+                tool: TouchTool::Finger,
+                azimuth_angle: None,
+                altitude_angle: None,
+                twist_angle: None,
+                is_estimated: false,
+                is_predicted: false,
                 id: id as u64,
             }),
             window_id,
@@ -83,6 +89,12 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Ended,
                 location: touch_point.location.to_physical(scale_factor),
                 force: None,
+                tool: TouchTool::Finger,
+                azimuth_angle: None,
+                altitude_angle: None,
+                twist_angle: None,
+                is_estimated: false,
+                is_predicted: false,
                 id: id as u64,
             }),
             window_id,
@@ -122,6 +134,12 @@ impl TouchHandler for WinitState {
                 phase: TouchPhase::Moved,
                 location: touch_point.location.to_physical(scale_factor),
                 force: None,
+                tool: TouchTool::Finger,
+                azimuth_angle: None,
+                altitude_angle: None,
+                twist_angle: None,
+                is_estimated: false,
+                is_predicted: false,
                 id: id as u64,
             }),
             window_id,
@@ -148,6 +166,12 @@ impl TouchHandler for WinitState {
                     phase: TouchPhase::Cancelled,
                     location,
                     force: None,
+                    tool: TouchTool::Finger,
+                    azimuth_angle: None,
+                    altitude_angle: None,
+                    twist_angle: None,
+                    is_estimated: false,
+                    is_predicted: false,
                     id: id as u64,
                 }),
                 window_id,
@@ -12,6 +12,7 @@ use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 
 use sctk::compositor::{CompositorHandler, CompositorState};
+use sctk::data_device_manager::DataDeviceManagerState;
 use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::pointer::ThemedPointer;
@@ -23,19 +24,22 @@ use sctk::shm::slot::SlotPool;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
+use crate::event::Event;
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
-    WinitPointerDataExt, WinitSeatState,
+    DragSourceState, PointerConstraintsState, RelativePointerState, TextInputState,
+    WinitPointerData, WinitPointerDataExt, WinitSeatState,
 };
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
+use crate::platform_impl::wayland::types::wp_presentation::PresentationManager;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
 use crate::platform_impl::wayland::window::{WindowRequests, WindowState};
 use crate::platform_impl::wayland::{WaylandError, WindowId};
-use crate::platform_impl::OsError;
+use crate::platform_impl::{MonitorHandle as PlatformMonitorHandle, OsError};
 
 /// Winit's Wayland state.
 pub struct WinitState {
@@ -78,6 +82,12 @@ pub struct WinitState {
     /// Currently handled seats.
     pub seats: AHashMap<ObjectId, WinitSeatState>,
 
+    /// The data device manager used for drag and drop.
+    pub data_device_manager_state: Option<DataDeviceManagerState>,
+
+    /// The drag started from one of our windows.
+    pub drag_source: Option<DragSourceState>,
+
     /// Currently present cursor surfaces.
     pub pointer_surfaces: AHashMap<ObjectId, Arc<ThemedPointer<WinitPointerData>>>,
 
@@ -109,6 +119,9 @@ pub struct WinitState {
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// Presentation time manager.
+    pub presentation_manager: Option<PresentationManager>,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
@@ -147,7 +160,8 @@ impl WinitState {
         let seat_state = SeatState::new(globals, queue_handle);
 
         let mut seats = AHashMap::default();
-        for seat in seat_state.seats() {
+        let present_seats: Vec<_> = seat_state.seats().collect();
+        for seat in &present_seats {
             seats.insert(seat.id(), WinitSeatState::new());
         }
 
@@ -161,7 +175,7 @@ impl WinitState {
         let shm = Shm::bind(globals, queue_handle).map_err(WaylandError::Bind)?;
         let custom_cursor_pool = Arc::new(Mutex::new(SlotPool::new(2, &shm).unwrap()));
 
-        Ok(Self {
+        let mut state = Self {
             registry_state,
             compositor_state: Arc::new(compositor_state),
             subcompositor_state: subcompositor_state.map(Arc::new),
@@ -180,8 +194,11 @@ impl WinitState {
             viewporter_state,
             fractional_scaling_manager,
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            presentation_manager: PresentationManager::new(globals, queue_handle).ok(),
 
             seats,
+            data_device_manager_state: DataDeviceManagerState::bind(globals, queue_handle).ok(),
+            drag_source: None,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
@@ -196,7 +213,13 @@ impl WinitState {
             // Make it true by default.
             dispatched_events: true,
             proxy_wake_up: false,
-        })
+        };
+
+        for seat in &present_seats {
+            state.bind_data_device(queue_handle, seat);
+        }
+
+        Ok(state)
     }
 
     pub fn scale_factor_changed(
@@ -242,6 +265,27 @@ impl WinitState {
         }
     }
 
+    /// Queue the windows whose surface entered or left outputs, which sctk doesn't report.
+    pub fn queue_monitor_changes(&mut self) {
+        for (window_id, window) in self.windows.get_mut().iter() {
+            if !window.lock().unwrap().monitor_changed() {
+                continue;
+            }
+
+            let updates = &mut self.window_compositor_updates;
+            let pos = if let Some(pos) =
+                updates.iter().position(|update| update.window_id == *window_id)
+            {
+                pos
+            } else {
+                updates.push(WindowCompositorUpdate::new(*window_id));
+                updates.len() - 1
+            };
+
+            updates[pos].monitor_changed = true;
+        }
+    }
+
     pub fn queue_close(updates: &mut Vec<WindowCompositorUpdate>, window_id: WindowId) {
         let pos = if let Some(pos) = updates.iter().position(|update| update.window_id == window_id)
         {
@@ -316,7 +360,11 @@ impl OutputHandler for WinitState {
     }
 
     fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: WlOutput) {
-        self.monitors.lock().unwrap().push(MonitorHandle::new(output));
+        let monitor = MonitorHandle::new(output);
+        self.monitors.lock().unwrap().push(monitor.clone());
+        self.events_sink.push_monitor_event(Event::MonitorConnected(RootMonitorHandle {
+            inner: PlatformMonitorHandle::Wayland(monitor),
+        }));
     }
 
     fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, updated: WlOutput) {
@@ -333,7 +381,10 @@ impl OutputHandler for WinitState {
         let mut monitors = self.monitors.lock().unwrap();
         let removed = MonitorHandle::new(removed);
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
-            monitors.remove(pos);
+            let removed = monitors.remove(pos);
+            self.events_sink.push_monitor_event(Event::MonitorDisconnected(RootMonitorHandle {
+                inner: PlatformMonitorHandle::Wayland(removed),
+            }));
         }
     }
 }
@@ -404,11 +455,20 @@ pub struct WindowCompositorUpdate {
 
     /// Close the window.
     pub close_window: bool,
+
+    /// The window moved to another monitor.
+    pub monitor_changed: bool,
 }
 
 impl WindowCompositorUpdate {
     fn new(window_id: WindowId) -> Self {
-        Self { window_id, resized: false, scale_changed: false, close_window: false }
+        Self {
+            window_id,
+            resized: false,
+            scale_changed: false,
+            close_window: false,
+            monitor_changed: false,
+        }
     }
 }
 
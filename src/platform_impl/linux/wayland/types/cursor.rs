@@ -1,14 +1,17 @@
+use std::time::Duration;
+
 use cursor_icon::CursorIcon;
 
 use sctk::reexports::client::protocol::wl_shm::Format;
 use sctk::shm::slot::{Buffer, SlotPool};
 
-use crate::cursor::CursorImage;
+use crate::cursor::{CursorAnimationFrame, CursorImage};
 
 #[derive(Debug)]
 pub enum SelectedCursor {
     Named(CursorIcon),
     Custom(CustomCursor),
+    Animation(CursorAnimation),
 }
 
 impl Default for SelectedCursor {
@@ -57,3 +60,30 @@ impl CustomCursor {
         }
     }
 }
+
+/// A custom cursor whose frames are swapped by a timer while the pointer is over the window.
+#[derive(Debug)]
+pub struct CursorAnimation {
+    pub frames: Vec<(CustomCursor, Duration)>,
+    /// The index of the frame that is currently shown.
+    pub current: usize,
+}
+
+impl CursorAnimation {
+    pub(crate) fn new(pool: &mut SlotPool, frames: &[CursorAnimationFrame]) -> Self {
+        let frames = frames
+            .iter()
+            .map(|frame| (CustomCursor::new(pool, &frame.image), frame.duration))
+            .collect();
+        Self { frames, current: 0 }
+    }
+
+    pub fn current_frame(&self) -> &(CustomCursor, Duration) {
+        &self.frames[self.current]
+    }
+
+    /// Move to the next frame, wrapping around at the end of the animation.
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1) % self.frames.len();
+    }
+}
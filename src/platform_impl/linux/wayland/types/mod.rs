@@ -3,5 +3,6 @@
 pub mod cursor;
 pub mod kwin_blur;
 pub mod wp_fractional_scaling;
+pub mod wp_presentation;
 pub mod wp_viewporter;
 pub mod xdg_activation;
@@ -0,0 +1,144 @@
+//! Handling of the presentation time.
+
+use std::time::Duration;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation::{
+    Event as PresentationEvent, WpPresentation,
+};
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation_feedback::{
+    Event as FeedbackEvent, WpPresentationFeedback,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::event::{PresentationFeedback, WindowEvent};
+use crate::platform_impl::common::clock;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::WindowId;
+
+/// Presentation time manager.
+#[derive(Debug)]
+pub struct PresentationManager {
+    manager: WpPresentation,
+
+    /// The clock of the presentation timestamps.
+    clock_id: Option<libc::clockid_t>,
+}
+
+/// The frame the feedback was requested for.
+#[derive(Debug)]
+pub struct PresentationFeedbackData {
+    window_id: WindowId,
+    generation: u64,
+}
+
+impl PresentationManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager, clock_id: None })
+    }
+
+    pub fn presentation(&self) -> &WpPresentation {
+        &self.manager
+    }
+}
+
+/// Request the feedback for the next frame committed to the `surface`.
+pub fn request_feedback(
+    presentation: &WpPresentation,
+    surface: &WlSurface,
+    queue_handle: &QueueHandle<WinitState>,
+    window_id: WindowId,
+    generation: u64,
+) -> WpPresentationFeedback {
+    presentation.feedback(surface, queue_handle, PresentationFeedbackData { window_id, generation })
+}
+
+impl Dispatch<WpPresentation, GlobalData, WinitState> for PresentationManager {
+    fn event(
+        state: &mut WinitState,
+        _: &WpPresentation,
+        event: <WpPresentation as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        if let PresentationEvent::ClockId { clk_id } = event {
+            if let Some(manager) = state.presentation_manager.as_mut() {
+                manager.clock_id = Some(clk_id as libc::clockid_t);
+            }
+        }
+    }
+}
+
+impl Dispatch<WpPresentationFeedback, PresentationFeedbackData, WinitState>
+    for PresentationManager
+{
+    fn event(
+        state: &mut WinitState,
+        _: &WpPresentationFeedback,
+        event: <WpPresentationFeedback as Proxy>::Event,
+        data: &PresentationFeedbackData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let feedback = match event {
+            FeedbackEvent::Presented {
+                tv_sec_hi,
+                tv_sec_lo,
+                tv_nsec,
+                refresh,
+                seq_hi,
+                seq_lo,
+                ..
+            } => {
+                let timestamp = Duration::new((tv_sec_hi as u64) << 32 | tv_sec_lo as u64, tv_nsec);
+                let presentation_time = state
+                    .presentation_manager
+                    .as_ref()
+                    .and_then(|manager| manager.clock_id)
+                    .and_then(|clock_id| clock::to_instant(clock_id, timestamp));
+                let refresh_interval = (refresh != 0).then(|| Duration::from_nanos(refresh as u64));
+
+                // Without a constant refresh rate the sequence isn't counting refreshes.
+                let seq = (seq_hi as u64) << 32 | seq_lo as u64;
+                let missed = match state.windows.get_mut().get(&data.window_id) {
+                    Some(window) => {
+                        let mut window = window.lock().unwrap();
+                        let previous = window.frame_presented(data.generation, seq);
+                        refresh_interval.is_some()
+                            && previous.is_some_and(|(generation, previous_seq)| {
+                                generation + 1 == data.generation && seq > previous_seq + 1
+                            })
+                    },
+                    None => return,
+                };
+
+                PresentationFeedback {
+                    generation: data.generation,
+                    presentation_time,
+                    refresh_interval,
+                    missed,
+                }
+            },
+            FeedbackEvent::Discarded => PresentationFeedback {
+                generation: data.generation,
+                presentation_time: None,
+                refresh_interval: None,
+                missed: true,
+            },
+            _ => return,
+        };
+
+        state.events_sink.push_window_event(WindowEvent::FramePresented(feedback), data.window_id);
+    }
+}
+
+delegate_dispatch!(WinitState: [WpPresentation: GlobalData] => PresentationManager);
+delegate_dispatch!(WinitState: [WpPresentationFeedback: PresentationFeedbackData] => PresentationManager);
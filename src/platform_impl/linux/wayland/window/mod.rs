@@ -1,7 +1,7 @@
 //! The Wayland window.
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
@@ -18,12 +18,14 @@ use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::platform_impl::common::launcher_entry::LauncherEntry;
 use crate::platform_impl::{
     Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
 };
+use crate::utils::RedrawDamage;
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    AspectRatio, Cursor, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose, ProgressState,
+    Rect, ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
 };
 
 use super::event_loop::sink::EventSink;
@@ -112,6 +114,7 @@ impl Window {
             size,
             window.clone(),
             attributes.preferred_theme,
+            LauncherEntry::new(attributes.platform_specific.name.as_ref()),
         );
 
         // Set transparency hint.
@@ -139,6 +142,9 @@ impl Window {
 
         // Non-resizable implies that the min and max sizes are set to the same value.
         window_state.set_resizable(attributes.resizable);
+        window_state.set_enabled_buttons(attributes.enabled_buttons);
+
+        window_state.set_aspect_ratio(attributes.aspect_ratio);
 
         // Set startup mode.
         match attributes.fullscreen.map(Into::into) {
@@ -151,6 +157,8 @@ impl Window {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
+                    #[cfg(headless_platform)]
+                    PlatformMonitorHandle::Headless(_) => None,
                 });
 
                 window.set_fullscreen(output.as_ref())
@@ -171,6 +179,16 @@ impl Window {
             xdg_activation.activate(token._token, &surface);
         }
 
+        // Set the parent before the initial commit, so the compositor knows about it from the
+        // start.
+        if let Some(parent) = attributes
+            .transient_parent
+            .and_then(|parent| state.windows.get_mut().get(&parent.0).cloned())
+        {
+            window.set_parent(Some(&parent.lock().unwrap().window));
+            window_state.transient_parent = Some(Arc::downgrade(&parent));
+        }
+
         // XXX Do initial commit.
         window.commit();
 
@@ -181,6 +199,7 @@ impl Window {
 
         let window_requests = WindowRequests {
             redraw_requested: AtomicBool::new(true),
+            redraw_damage: Default::default(),
             closed: AtomicBool::new(false),
         };
         let window_requests = Arc::new(window_requests);
@@ -271,6 +290,24 @@ impl Window {
 
     #[inline]
     pub fn request_redraw(&self) {
+        self.window_requests.redraw_damage.lock().unwrap().add_full();
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let size = self.inner_size();
+        self.window_requests.redraw_damage.lock().unwrap().add(rects, size);
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let size = self.inner_size();
+        self.window_requests.redraw_damage.lock().unwrap().current(size).map(<[_]>::to_vec)
+    }
+
+    fn schedule_redraw(&self) {
         // NOTE: try to not wake up the loop when the event was already scheduled and not yet
         // processed by the loop, because if at this point the value was `true` it could only
         // mean that the loop still haven't dispatched the value to the client and will do
@@ -287,7 +324,12 @@ impl Window {
 
     #[inline]
     pub fn pre_present_notify(&self) {
-        self.window_state.lock().unwrap().request_frame_callback();
+        self.window_state.lock().unwrap().pre_present_notify();
+    }
+
+    #[inline]
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        self.window_state.lock().unwrap().set_presentation_feedback(enabled);
     }
 
     #[inline]
@@ -335,6 +377,11 @@ impl Window {
         warn!("`set_resize_increments` is not implemented for Wayland");
     }
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        self.window_state.lock().unwrap().set_aspect_ratio(aspect_ratio);
+    }
+
     #[inline]
     pub fn set_transparent(&self, transparent: bool) {
         self.window_state.lock().unwrap().set_transparent(transparent);
@@ -377,14 +424,21 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        if self.window_state.lock().unwrap().set_enabled_buttons(buttons) {
+            // NOTE: Requires commit to be applied.
+            self.request_redraw();
+        }
     }
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        // TODO(kchibisov) v5 of the xdg_shell allows that.
-        WindowButtons::all()
+        self.window_state.lock().unwrap().enabled_buttons()
+    }
+
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        self.window_state.lock().unwrap().supported_buttons()
     }
 
     #[inline]
@@ -397,6 +451,14 @@ impl Window {
         self.window_state.lock().unwrap().set_blur(blur);
     }
 
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {}
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        1.0
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorate: bool) {
         self.window_state.lock().unwrap().set_decorate(decorate)
@@ -413,6 +475,27 @@ impl Window {
     #[inline]
     pub(crate) fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
 
+    pub fn set_transient_parent(&self, parent: Option<&Window>) -> Result<(), ExternalError> {
+        if let Some(parent) = parent {
+            // Walk up the chain of the new parent to ensure we don't create a cycle.
+            let mut ancestor = Some(parent.window_state.clone());
+            while let Some(current) = ancestor {
+                if Arc::ptr_eq(&current, &self.window_state) {
+                    return Err(ExternalError::Ignored);
+                }
+                ancestor =
+                    current.lock().unwrap().transient_parent.as_ref().and_then(Weak::upgrade);
+            }
+        }
+
+        self.window.set_parent(parent.map(|parent| &parent.window));
+        self.window_state.lock().unwrap().transient_parent =
+            parent.map(|parent| Arc::downgrade(&parent.window_state));
+        self.event_loop_awakener.ping();
+
+        Ok(())
+    }
+
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
         // You can't unminimize the window on Wayland.
@@ -475,6 +558,8 @@ impl Window {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
+                    #[cfg(headless_platform)]
+                    PlatformMonitorHandle::Headless(_) => None,
                 });
 
                 self.window.set_fullscreen(output.as_ref())
@@ -489,7 +574,11 @@ impl Window {
 
         match cursor {
             Cursor::Icon(icon) => window_state.set_cursor(icon),
-            Cursor::Custom(cursor) => window_state.set_custom_cursor(cursor),
+            Cursor::Custom(cursor) => {
+                window_state.set_custom_cursor(cursor);
+                // Wake up the event loop to start the timer of an animated cursor.
+                self.event_loop_awakener.ping();
+            },
         }
     }
 
@@ -524,6 +613,21 @@ impl Window {
         xdg_activation_token.commit();
     }
 
+    #[inline]
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        self.window_state.lock().unwrap().launcher_entry.set_progress(state).map_err(|err| {
+            warn!("Failed to update the launcher entry: {err}");
+            NotSupportedError::new()
+        })
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        if let Err(err) = self.window_state.lock().unwrap().launcher_entry.set_count(count) {
+            warn!("Failed to update the launcher entry: {err}");
+        }
+    }
+
     pub fn request_activation_token(&self) -> Result<AsyncRequestSerial, NotSupportedError> {
         let xdg_activation = match self.xdg_activation.as_ref() {
             Some(xdg_activation) => xdg_activation,
@@ -562,6 +666,14 @@ impl Window {
         self.window_state.lock().unwrap().drag_window()
     }
 
+    #[inline]
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        self.window_state.lock().unwrap().start_drag_and_drop(payload)?;
+        // The drag is started by the event loop.
+        self.event_loop_awakener.ping();
+        Ok(())
+    }
+
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
         let surface = self.window.wl_surface();
@@ -581,13 +693,11 @@ impl Window {
 
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
-        let window_state = self.window_state.lock().unwrap();
-        if window_state.ime_allowed() {
-            let scale_factor = window_state.scale_factor();
-            let position = position.to_logical(scale_factor);
-            let size = size.to_logical(scale_factor);
-            window_state.set_ime_cursor_area(position, size);
-        }
+        let mut window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        let position = position.to_logical(scale_factor);
+        let size = size.to_logical(scale_factor);
+        window_state.set_ime_cursor_area(position, size);
     }
 
     #[inline]
@@ -606,6 +716,11 @@ impl Window {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        self.window_state.lock().unwrap().text_input_supported().then_some(ImeCapabilities::all())
+    }
+
     #[inline]
     pub fn focus_window(&self) {}
 
@@ -711,6 +826,9 @@ pub struct WindowRequests {
 
     /// Redraw Requested.
     pub redraw_requested: AtomicBool,
+
+    /// The damage of the requested redraws.
+    pub(crate) redraw_damage: Mutex<RedrawDamage>,
 }
 
 impl WindowRequests {
@@ -5,17 +5,22 @@ use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
 use ahash::HashSet;
+use calloop::timer::{TimeoutAction, Timer};
+use calloop::LoopHandle;
 use tracing::{info, warn};
 
 use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_shm::WlShm;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
-    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
+    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowManagerCapabilities,
+    WindowState as XdgWindowState,
 };
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation::WpPresentation;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
@@ -30,14 +35,19 @@ use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
-use crate::cursor::CustomCursor as RootCustomCursor;
+use crate::cursor::{CustomCursor as RootCustomCursor, OnlyCursorImageSource};
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
-use crate::error::{ExternalError, NotSupportedError};
-use crate::platform_impl::wayland::logical_to_physical_rounded;
-use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
+use crate::error::{ExternalError, NotSupportedError, PlatformError};
+use crate::platform_impl::common::launcher_entry::LauncherEntry;
+use crate::platform_impl::wayland::types::cursor::{CursorAnimation, CustomCursor, SelectedCursor};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::wp_presentation;
+use crate::platform_impl::wayland::{logical_to_physical_rounded, make_wid};
 use crate::platform_impl::{PlatformCustomCursor, WindowId};
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
+use crate::window::{
+    AspectRatio, AspectRatioAxis, CursorGrabMode, CursorIcon, DragPayload, ImePurpose,
+    ResizeDirection, Theme, WindowButtons,
+};
 
 use crate::platform_impl::wayland::seat::{
     PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
@@ -71,9 +81,21 @@ pub struct WindowState {
 
     selected_cursor: SelectedCursor,
 
+    /// Whether the timer swapping the frames of an animated cursor is registered.
+    cursor_animation_running: bool,
+
     /// Whether the cursor is visible.
     pub cursor_visible: bool,
 
+    /// The launcher entry used to show the progress.
+    pub launcher_entry: LauncherEntry,
+
+    /// The monitor last reported with `WindowEvent::MonitorChanged`.
+    monitor: Option<WlOutput>,
+
+    /// The window this one is transient for.
+    pub transient_parent: Option<Weak<Mutex<WindowState>>>,
+
     /// Pointer constraints to lock/confine pointer.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -89,6 +111,9 @@ pub struct WindowState {
     /// Whether the frame is resizable.
     resizable: bool,
 
+    /// The buttons shown in the frame.
+    enabled_buttons: WindowButtons,
+
     // NOTE: we can't use simple counter, since it's racy when seat getting destroyed and new
     // is created, since add/removed stuff could be delivered a bit out of order.
     /// Seats that has keyboard focus on that window.
@@ -112,9 +137,15 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The current IME cursor area, kept while the IME isn't allowed.
+    ime_cursor_area: Option<(LogicalPosition<u32>, LogicalSize<u32>)>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
+    /// Whether the compositor supports `zwp_text_input_v3`.
+    text_input_supported: bool,
+
     /// The inner size of the window, as in without client side decorations.
     size: LogicalSize<u32>,
 
@@ -128,6 +159,9 @@ pub struct WindowState {
     min_inner_size: LogicalSize<u32>,
     max_inner_size: Option<LogicalSize<u32>>,
 
+    /// The aspect ratio to keep when the user resizes the window.
+    aspect_ratio: Option<AspectRatio>,
+
     /// The size of the window when no states were applied to it. The primary use for it
     /// is to fallback to original window size, before it was maximized, if the compositor
     /// sends `None` for the new size in the configure.
@@ -140,6 +174,17 @@ pub struct WindowState {
     /// The state of the frame callback.
     frame_callback_state: FrameCallbackState,
 
+    /// The number of `pre_present_notify` calls.
+    present_generation: u64,
+
+    /// Whether to request the presentation feedback of the frames.
+    presentation_feedback: bool,
+
+    /// The generation and the refresh sequence of the last presented frame.
+    last_presented: Option<(u64, u64)>,
+
+    presentation: Option<WpPresentation>,
+
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
@@ -150,6 +195,9 @@ pub struct WindowState {
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
+    /// The drag to start from the event loop, which owns the data devices.
+    pending_drag: Option<DragPayload>,
+
     /// The underlying SCTK window.
     pub window: Window,
 
@@ -170,6 +218,7 @@ impl WindowState {
         initial_size: Size,
         window: Window,
         theme: Option<Theme>,
+        launcher_entry: LauncherEntry,
     ) -> Self {
         let compositor = winit_state.compositor_state.clone();
         let pointer_constraints = winit_state.pointer_constraints.clone();
@@ -190,17 +239,32 @@ impl WindowState {
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
             selected_cursor: Default::default(),
+            cursor_animation_running: false,
             cursor_visible: true,
             decorate: true,
+            enabled_buttons: WindowButtons::all(),
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
+            present_generation: 0,
+            presentation_feedback: false,
+            last_presented: None,
+            presentation: winit_state
+                .presentation_manager
+                .as_ref()
+                .map(|manager| manager.presentation().clone()),
             seat_focus: Default::default(),
             has_pending_move: None,
+            pending_drag: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_cursor_area: None,
             last_configure: None,
+            launcher_entry,
+            monitor: None,
+            transient_parent: None,
             max_inner_size: None,
+            aspect_ratio: None,
             min_inner_size: MIN_WINDOW_SIZE,
             pointer_constraints,
             pointers: Default::default(),
@@ -213,6 +277,7 @@ impl WindowState {
             stateless_size: initial_size.to_logical(1.),
             initial_size: Some(initial_size),
             text_inputs: Vec::new(),
+            text_input_supported: winit_state.text_input_state.is_some(),
             theme,
             title: String::default(),
             transparent: false,
@@ -237,6 +302,24 @@ impl WindowState {
         self.frame_callback_state
     }
 
+    /// The monitor of the window, which is the first output the surface entered among those it
+    /// is still on, so it only changes once the surface completely left the previous one.
+    fn current_output(&self) -> Option<WlOutput> {
+        self.window.wl_surface().data::<SurfaceData>()?.outputs().next()
+    }
+
+    /// Whether the window moved to another monitor since the last [`Self::take_monitor_change`].
+    pub fn monitor_changed(&self) -> bool {
+        self.current_output().is_some_and(|output| self.monitor.as_ref() != Some(&output))
+    }
+
+    /// Take the previous and the new monitor of the window, if it changed.
+    pub fn take_monitor_change(&mut self) -> Option<(Option<WlOutput>, WlOutput)> {
+        let output =
+            self.current_output().filter(|output| self.monitor.as_ref() != Some(output))?;
+        Some((self.monitor.replace(output.clone()), output))
+    }
+
     /// The frame callback was received, but not yet sent to the user.
     pub fn frame_callback_received(&mut self) {
         self.frame_callback_state = FrameCallbackState::Received;
@@ -259,6 +342,35 @@ impl WindowState {
         }
     }
 
+    /// Count the frame about to be presented, and request its presentation feedback if enabled.
+    pub fn pre_present_notify(&mut self) {
+        self.present_generation += 1;
+        if let Some(presentation) =
+            self.presentation.as_ref().filter(|_| self.presentation_feedback)
+        {
+            let surface = self.window.wl_surface();
+            wp_presentation::request_feedback(
+                presentation,
+                surface,
+                &self.queue_handle,
+                make_wid(surface),
+                self.present_generation,
+            );
+        }
+
+        self.request_frame_callback();
+    }
+
+    /// Set whether to request the presentation feedback of the frames.
+    pub fn set_presentation_feedback(&mut self, enabled: bool) {
+        self.presentation_feedback = enabled;
+    }
+
+    /// Record the frame that was presented at the refresh `seq`, returning the previous one.
+    pub fn frame_presented(&mut self, generation: u64, seq: u64) -> Option<(u64, u64)> {
+        self.last_presented.replace((generation, seq))
+    }
+
     pub fn configure(
         &mut self,
         configure: WindowConfigure,
@@ -306,10 +418,12 @@ impl WindowState {
         }
 
         let stateless = Self::is_stateless(&configure);
+        let wm_capabilities = frame_capabilities(configure.capabilities, self.enabled_buttons);
 
         let (mut new_size, constrain) = if let Some(frame) = self.frame.as_mut() {
             // Configure the window states.
             frame.update_state(configure.state);
+            frame.update_wm_capabilities(wm_capabilities);
 
             match configure.new_size {
                 (Some(width), Some(height)) => {
@@ -340,6 +454,19 @@ impl WindowState {
                 .unwrap_or(new_size.height);
         }
 
+        // Keep the aspect ratio only when the size isn't dictated by the window state.
+        if let Some(aspect_ratio) = self.aspect_ratio.filter(|_| stateless) {
+            let min_size = self.inner_bound(self.min_inner_size);
+            let max_size = self.max_inner_size.map(|size| self.inner_bound(size));
+            let size = aspect_ratio.constrain(
+                PhysicalSize::new(new_size.width, new_size.height),
+                AspectRatioAxis::Fit,
+                Some(min_size),
+                max_size,
+            );
+            new_size = LogicalSize::new(size.width, size.height);
+        }
+
         let new_state = configure.state;
         let old_state = self.last_configure.as_ref().map(|configure| configure.state);
 
@@ -417,6 +544,26 @@ impl WindowState {
         Ok(())
     }
 
+    /// Request a drag of `payload` out of the window.
+    pub fn start_drag_and_drop(&mut self, payload: DragPayload) -> Result<(), ExternalError> {
+        let button_held = self
+            .pointers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .any(|pointer| pointer.pointer().winit_data().is_button_held());
+        if !button_held {
+            return Err(ExternalError::Ignored);
+        }
+
+        self.pending_drag = Some(payload);
+        Ok(())
+    }
+
+    /// Take the drag requested with [`Self::start_drag_and_drop`].
+    pub fn take_pending_drag(&mut self) -> Option<DragPayload> {
+        self.pending_drag.take()
+    }
+
     /// Tells whether the window should be closed.
     #[allow(clippy::too_many_arguments)]
     pub fn frame_click(
@@ -522,6 +669,42 @@ impl WindowState {
         true
     }
 
+    /// Set the buttons shown in the frame, returns whether the frame has to be redrawn.
+    pub fn set_enabled_buttons(&mut self, buttons: WindowButtons) -> bool {
+        if self.enabled_buttons == buttons {
+            return false;
+        }
+
+        self.enabled_buttons = buttons;
+        let capabilities = self
+            .last_configure
+            .as_ref()
+            .map_or(WindowManagerCapabilities::all(), |configure| configure.capabilities);
+
+        match self.frame.as_mut() {
+            Some(frame) => {
+                frame.update_wm_capabilities(frame_capabilities(capabilities, buttons));
+                true
+            },
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub fn enabled_buttons(&self) -> WindowButtons {
+        self.enabled_buttons
+    }
+
+    /// The buttons that can be disabled, only the frame drawn by us can hide them.
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        if self.frame.is_some() {
+            WindowButtons::MINIMIZE | WindowButtons::MAXIMIZE
+        } else {
+            WindowButtons::empty()
+        }
+    }
+
     /// Whether the window is focused by any seat.
     #[inline]
     pub fn has_focus(&self) -> bool {
@@ -607,10 +790,7 @@ impl WindowState {
     /// Reload the cursor style on the given window.
     pub fn reload_cursor_style(&mut self) {
         if self.cursor_visible {
-            match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
-            }
+            self.apply_selected_cursor();
         } else {
             self.set_cursor_visible(self.cursor_visible);
         }
@@ -711,18 +891,82 @@ impl WindowState {
                 tracing::error!("passed a X11 cursor to Wayland backend");
                 return;
             },
+            #[cfg(headless_platform)]
+            RootCustomCursor { inner: PlatformCustomCursor::Headless(_) } => {
+                tracing::error!("passed a headless cursor to Wayland backend");
+                return;
+            },
         };
 
-        let cursor = {
+        self.selected_cursor = {
             let mut pool = self.custom_cursor_pool.lock().unwrap();
-            CustomCursor::new(&mut pool, &cursor)
+            match &*cursor {
+                OnlyCursorImageSource::Image(image) => {
+                    SelectedCursor::Custom(CustomCursor::new(&mut pool, image))
+                },
+                OnlyCursorImageSource::Animation(frames) => {
+                    SelectedCursor::Animation(CursorAnimation::new(&mut pool, frames))
+                },
+            }
         };
 
         if self.cursor_visible {
-            self.apply_custom_cursor(&cursor);
+            self.apply_selected_cursor();
+        }
+    }
+
+    /// Apply the selected cursor to the pointers over the window.
+    fn apply_selected_cursor(&mut self) {
+        match &self.selected_cursor {
+            SelectedCursor::Named(icon) => self.set_cursor(*icon),
+            SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
+            SelectedCursor::Animation(animation) => {
+                self.apply_custom_cursor(&animation.current_frame().0)
+            },
+        }
+    }
+
+    /// Mark the timer of an animated cursor as running if it should be started, returning the
+    /// delay until the next frame.
+    ///
+    /// The timer is registered with [`schedule_cursor_animation`] and stops by itself once the
+    /// cursor isn't animated or the pointers left the window.
+    pub fn start_cursor_animation(&mut self) -> Option<Duration> {
+        match &self.selected_cursor {
+            SelectedCursor::Animation(animation)
+                if !self.cursor_animation_running
+                    && self.cursor_visible
+                    && !self.pointers.is_empty() =>
+            {
+                self.cursor_animation_running = true;
+                Some(animation.current_frame().1)
+            },
+            _ => None,
+        }
+    }
+
+    /// Show the next frame of an animated cursor, returning the duration until the one after it.
+    ///
+    /// Returns `None` when the animation should stop.
+    fn advance_cursor_animation(&mut self) -> Option<Duration> {
+        if let SelectedCursor::Animation(animation) = &mut self.selected_cursor {
+            animation.advance();
         }
 
-        self.selected_cursor = SelectedCursor::Custom(cursor);
+        match &self.selected_cursor {
+            SelectedCursor::Animation(animation)
+                if self.cursor_visible && !self.pointers.is_empty() =>
+            {
+                let (cursor, delay) = animation.current_frame();
+                self.apply_custom_cursor(cursor);
+                Some(*delay)
+            },
+            _ => {
+                // The timer is dropped by returning `None`.
+                self.cursor_animation_running = false;
+                None
+            },
+        }
     }
 
     fn apply_custom_cursor(&self, cursor: &CustomCursor) {
@@ -786,6 +1030,28 @@ impl WindowState {
         self.window.set_max_size(size.map(Into::into));
     }
 
+    /// Set the aspect ratio applied to the sizes suggested by the compositor.
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: Option<AspectRatio>) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
+    /// Remove the borders from the min or max size, which include them.
+    fn inner_bound(&self, size: LogicalSize<u32>) -> PhysicalSize<u32> {
+        match self.frame.as_ref() {
+            Some(frame) => {
+                let (width, height) = frame.subtract_borders(
+                    NonZeroU32::new(size.width).unwrap_or(NonZeroU32::MIN),
+                    NonZeroU32::new(size.height).unwrap_or(NonZeroU32::MIN),
+                );
+                PhysicalSize::new(
+                    width.map_or(1, NonZeroU32::get),
+                    height.map_or(1, NonZeroU32::get),
+                )
+            },
+            None => PhysicalSize::new(size.width, size.height),
+        }
+    }
+
     /// Set the CSD theme.
     pub fn set_theme(&mut self, theme: Option<Theme>) {
         self.theme = theme;
@@ -824,7 +1090,7 @@ impl WindowState {
         let pointer_constraints = match self.pointer_constraints.as_ref() {
             Some(pointer_constraints) => pointer_constraints,
             None if mode == CursorGrabMode::None => return Ok(()),
-            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+            None => return Err(pointer_constraints_not_supported()),
         };
 
         // Replace the current mode.
@@ -870,7 +1136,7 @@ impl WindowState {
     /// Set the position of the cursor.
     pub fn set_cursor_position(&self, position: LogicalPosition<f64>) -> Result<(), ExternalError> {
         if self.pointer_constraints.is_none() {
-            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+            return Err(pointer_constraints_not_supported());
         }
 
         // Position can be set only for locked cursor.
@@ -892,10 +1158,7 @@ impl WindowState {
         self.cursor_visible = cursor_visible;
 
         if self.cursor_visible {
-            match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
-            }
+            self.apply_selected_cursor();
         } else {
             for pointer in self.pointers.iter().filter_map(|pointer| pointer.upgrade()) {
                 let latest_enter_serial = pointer.pointer().winit_data().latest_enter_serial();
@@ -952,6 +1215,7 @@ impl WindowState {
             if allowed {
                 text_input.enable();
                 text_input.set_content_type_by_purpose(self.ime_purpose);
+                self.apply_ime_cursor_area(text_input);
             } else {
                 text_input.disable();
             }
@@ -962,18 +1226,32 @@ impl WindowState {
     }
 
     /// Set the IME position.
-    pub fn set_ime_cursor_area(&self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+    ///
+    /// The position is only sent to the text inputs while the IME is allowed.
+    pub fn set_ime_cursor_area(&mut self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+        self.ime_cursor_area = Some((position, size));
+        if !self.ime_allowed {
+            return;
+        }
+
         // FIXME: This won't fly unless user will have a way to request IME window per seat, since
         // the ime windows will be overlapping, but winit doesn't expose API to specify for
         // which seat we're setting IME position.
-        let (x, y) = (position.x as i32, position.y as i32);
-        let (width, height) = (size.width as i32, size.height as i32);
         for text_input in self.text_inputs.iter() {
-            text_input.set_cursor_rectangle(x, y, width, height);
+            self.apply_ime_cursor_area(text_input);
             text_input.commit();
         }
     }
 
+    /// Send the IME cursor area to the text input, without committing it.
+    pub fn apply_ime_cursor_area(&self, text_input: &ZwpTextInputV3) {
+        if let Some((position, size)) = self.ime_cursor_area {
+            let (x, y) = (position.x as i32, position.y as i32);
+            let (width, height) = (size.width as i32, size.height as i32);
+            text_input.set_cursor_rectangle(x, y, width, height);
+        }
+    }
+
     /// Set the IME purpose.
     pub fn set_ime_purpose(&mut self, purpose: ImePurpose) {
         self.ime_purpose = purpose;
@@ -984,6 +1262,11 @@ impl WindowState {
         }
     }
 
+    /// Whether the compositor supports text input, and thus IME.
+    pub fn text_input_supported(&self) -> bool {
+        self.text_input_supported
+    }
+
     /// Get the IME purpose.
     pub fn ime_purpose(&self) -> ImePurpose {
         self.ime_purpose
@@ -1024,23 +1307,21 @@ impl WindowState {
     /// Set the window title to a new value.
     ///
     /// This will automatically truncate the title to something meaningful.
-    pub fn set_title(&mut self, mut title: String) {
+    pub fn set_title(&mut self, title: String) {
         // Truncate the title to at most 1024 bytes, so that it does not blow up the protocol
-        // messages
-        if title.len() > 1024 {
-            let mut new_len = 1024;
-            while !title.is_char_boundary(new_len) {
-                new_len -= 1;
-            }
-            title.truncate(new_len);
+        // messages. The whole title is kept, so that it's returned as it was set.
+        let mut len = title.len().min(1024);
+        while !title.is_char_boundary(len) {
+            len -= 1;
         }
+        let truncated = &title[..len];
 
         // Update the CSD title.
         if let Some(frame) = self.frame.as_mut() {
-            frame.set_title(&title);
+            frame.set_title(truncated);
         }
 
-        self.window.set_title(&title);
+        self.window.set_title(truncated);
         self.title = title;
     }
 
@@ -1074,6 +1355,25 @@ impl WindowState {
     }
 }
 
+/// Register the timer swapping the frames of the animated cursor of the window.
+pub fn schedule_cursor_animation(
+    loop_handle: &LoopHandle<'static, WinitState>,
+    window_id: WindowId,
+    delay: Duration,
+) {
+    let timer = Timer::from_duration(delay);
+    let _ = loop_handle.insert_source(timer, move |_, _, state| {
+        let windows = state.windows.get_mut();
+        match windows
+            .get(&window_id)
+            .and_then(|window| window.lock().unwrap().advance_cursor_animation())
+        {
+            Some(delay) => TimeoutAction::ToDuration(delay),
+            None => TimeoutAction::Drop,
+        }
+    });
+}
+
 impl Drop for WindowState {
     fn drop(&mut self) {
         if let Some(blur) = self.blur.take() {
@@ -1136,6 +1436,30 @@ impl From<ResizeDirection> for XdgResizeEdge {
     }
 }
 
+/// The capabilities to advertise to the frame, hiding the buttons that were disabled.
+fn frame_capabilities(
+    mut capabilities: WindowManagerCapabilities,
+    buttons: WindowButtons,
+) -> WindowManagerCapabilities {
+    if !buttons.contains(WindowButtons::MINIMIZE) {
+        capabilities.remove(WindowManagerCapabilities::MINIMIZE);
+    }
+    if !buttons.contains(WindowButtons::MAXIMIZE) {
+        capabilities.remove(WindowManagerCapabilities::MAXIMIZE);
+    }
+    capabilities
+}
+
+/// The error for grabbing or warping the cursor without the pointer constraints protocol.
+fn pointer_constraints_not_supported() -> ExternalError {
+    let platform = PlatformError::new(
+        "wayland",
+        None,
+        "the compositor doesn't support zwp_pointer_constraints_v1",
+    );
+    ExternalError::NotSupported(NotSupportedError::with_platform_error(platform))
+}
+
 // NOTE: Rust doesn't allow `From<Option<Theme>>`.
 #[cfg(feature = "sctk-adwaita")]
 fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
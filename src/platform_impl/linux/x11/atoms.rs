@@ -55,6 +55,7 @@ atom_manager! {
     _NET_WM_STATE_HIDDEN,
     _NET_WM_STATE_MAXIMIZED_HORZ,
     _NET_WM_STATE_MAXIMIZED_VERT,
+    _NET_WM_WINDOW_OPACITY,
     _NET_WM_WINDOW_TYPE,
 
     // Activation atoms.
@@ -86,10 +87,14 @@ atom_manager! {
     XdndPosition,
     XdndStatus,
     XdndActionPrivate,
+    XdndActionCopy,
+    XdndActionMove,
     XdndSelection,
     XdndFinished,
     XdndTypeList,
     TextUriList: b"text/uri-list",
+    TextPlain: b"text/plain",
+    TextPlainUtf8: b"text/plain;charset=utf-8",
     None: b"None",
 
     // Miscellaneous Atoms
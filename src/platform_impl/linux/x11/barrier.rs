@@ -0,0 +1,122 @@
+//! Cursor confinement through the pointer barriers of the XFixes extension.
+//!
+//! Unlike an active pointer grab, the barriers don't redirect the events of the pointer to the
+//! window, so popup menus and other clients keep working while the cursor is confined.
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xfixes::{self, BarrierDirections, ConnectionExt as _};
+use x11rb::protocol::xproto;
+
+use super::{X11Error, XConnection};
+
+/// A rectangle in root window coordinates, as `(x, y, width, height)`.
+type RootRect = (i32, i32, u32, u32);
+
+/// The pointer barriers confining the cursor to a window.
+#[derive(Debug, Default)]
+pub struct PointerBarriers {
+    /// The barriers along the edges of the rectangle, while the cursor is confined.
+    barriers: Vec<xfixes::Barrier>,
+
+    /// The rectangle the cursor is confined to.
+    rect: Option<RootRect>,
+}
+
+impl PointerBarriers {
+    /// Confine the cursor to `rect`, replacing the barriers when the rectangle changed.
+    ///
+    /// The barriers only let the cursor through towards the inside of the rectangle, so a cursor
+    /// that is outside of it becomes confined once it enters it, like on Wayland.
+    pub fn confine(
+        &mut self,
+        xconn: &XConnection,
+        window: xproto::Window,
+        rect: RootRect,
+    ) -> Result<(), X11Error> {
+        if self.rect == Some(rect) {
+            return Ok(());
+        }
+
+        self.release(xconn)?;
+
+        let conn = xconn.xcb_connection();
+        let mut cookies = Vec::with_capacity(4);
+        for (x1, y1, x2, y2, directions) in edges(rect) {
+            let barrier = conn.generate_id()?;
+            cookies.push(conn.xfixes_create_pointer_barrier(
+                barrier,
+                window,
+                x1,
+                y1,
+                x2,
+                y2,
+                directions,
+                &[],
+            )?);
+            self.barriers.push(barrier);
+        }
+        self.rect = Some(rect);
+
+        for cookie in cookies {
+            cookie.check()?;
+        }
+
+        Ok(())
+    }
+
+    /// Destroy the barriers, releasing the cursor.
+    pub fn release(&mut self, xconn: &XConnection) -> Result<(), X11Error> {
+        if self.rect.take().is_none() {
+            return Ok(());
+        }
+
+        for barrier in self.barriers.drain(..) {
+            xconn.xcb_connection().xfixes_delete_pointer_barrier(barrier)?.ignore_error();
+        }
+
+        xconn.flush_requests()?;
+        Ok(())
+    }
+}
+
+/// The barriers along the left, right, top and bottom edges of the rectangle, with the direction
+/// they let the cursor through.
+fn edges((x, y, width, height): RootRect) -> [(u16, u16, u16, u16, BarrierDirections); 4] {
+    // The root window never has negative coordinates, so clamp the parts outside of it.
+    let coord = |value: i64| value.clamp(0, u16::MAX.into()) as u16;
+    let (left, top) = (coord(x.into()), coord(y.into()));
+    let right = coord(i64::from(x) + i64::from(width));
+    let bottom = coord(i64::from(y) + i64::from(height));
+
+    [
+        (left, top, left, bottom, BarrierDirections::POSITIVE_X),
+        (right, top, right, bottom, BarrierDirections::NEGATIVE_X),
+        (left, top, right, top, BarrierDirections::POSITIVE_Y),
+        (left, bottom, right, bottom, BarrierDirections::NEGATIVE_Y),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_surround_the_rect() {
+        assert_eq!(edges((10, 20, 300, 200)), [
+            (10, 20, 10, 220, BarrierDirections::POSITIVE_X),
+            (310, 20, 310, 220, BarrierDirections::NEGATIVE_X),
+            (10, 20, 310, 20, BarrierDirections::POSITIVE_Y),
+            (10, 220, 310, 220, BarrierDirections::NEGATIVE_Y),
+        ]);
+    }
+
+    #[test]
+    fn edges_are_clamped_to_the_root() {
+        assert_eq!(edges((-50, -10, 100, 40)), [
+            (0, 0, 0, 30, BarrierDirections::POSITIVE_X),
+            (50, 0, 50, 30, BarrierDirections::NEGATIVE_X),
+            (0, 0, 50, 0, BarrierDirections::POSITIVE_Y),
+            (0, 30, 50, 30, BarrierDirections::NEGATIVE_Y),
+        ]);
+    }
+}
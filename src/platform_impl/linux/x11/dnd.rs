@@ -10,6 +10,13 @@ use x11rb::protocol::xproto::{self, ConnectionExt};
 use super::atoms::AtomName::None as DndNone;
 use super::atoms::*;
 use super::{util, CookieResultExt, X11Error, XConnection};
+use crate::dpi::PhysicalPosition;
+use crate::event::{DragAction, DragDropKinds};
+use crate::platform_impl::common::uri_list;
+use crate::window::DragPayload;
+
+/// The latest version of the protocol, which we use with targets supporting it.
+const XDND_VERSION: u32 = 5;
 
 #[derive(Debug, Clone, Copy)]
 pub enum DndState {
@@ -45,20 +52,55 @@ pub struct Dnd {
     pub type_list: Option<Vec<xproto::Atom>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<xproto::Window>,
+    // The origin of the window in root coordinates, and the last position reported to the user,
+    // both populated by the XdndPosition event handler
+    pub window_origin: Option<(i32, i32)>,
+    pub position: Option<PhysicalPosition<f64>>,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
     pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
+    pub text: Option<String>,
 }
 
 impl Dnd {
     pub fn new(xconn: Arc<XConnection>) -> Result<Self, X11Error> {
-        Ok(Dnd { xconn, version: None, type_list: None, source_window: None, result: None })
+        Ok(Dnd {
+            xconn,
+            version: None,
+            type_list: None,
+            source_window: None,
+            window_origin: None,
+            position: None,
+            result: None,
+            text: None,
+        })
     }
 
     pub fn reset(&mut self) {
         self.version = None;
         self.type_list = None;
         self.source_window = None;
+        self.window_origin = None;
+        self.position = None;
         self.result = None;
+        self.text = None;
+    }
+
+    /// The kinds of data offered by the source, according to its type list.
+    pub fn kinds(&self) -> DragDropKinds {
+        match self.data_target() {
+            Some(target) if target == self.xconn.atoms()[TextUriList] => DragDropKinds::PATHS,
+            Some(_) => DragDropKinds::TEXT,
+            None => DragDropKinds::empty(),
+        }
+    }
+
+    /// The type to request the data with, preferring paths over text.
+    pub fn data_target(&self) -> Option<xproto::Atom> {
+        let atoms = self.xconn.atoms();
+        let type_list = self.type_list.as_ref()?;
+        [atoms[TextUriList], atoms[UTF8_STRING], atoms[TextPlainUtf8], atoms[TextPlain]]
+            .into_iter()
+            .find(|target| type_list.contains(target))
     }
 
     pub unsafe fn send_status(
@@ -121,26 +163,31 @@ impl Dnd {
         )
     }
 
-    pub unsafe fn convert_selection(&self, window: xproto::Window, time: xproto::Timestamp) {
+    pub unsafe fn convert_selection(
+        &self,
+        window: xproto::Window,
+        target: xproto::Atom,
+        time: xproto::Timestamp,
+    ) {
         let atoms = self.xconn.atoms();
         self.xconn
             .xcb_connection()
-            .convert_selection(
-                window,
-                atoms[XdndSelection],
-                atoms[TextUriList],
-                atoms[XdndSelection],
-                time,
-            )
+            .convert_selection(window, atoms[XdndSelection], target, atoms[XdndSelection], time)
             .expect_then_ignore_error("Failed to send XdndSelection event")
     }
 
     pub unsafe fn read_data(
         &self,
         window: xproto::Window,
+        target: xproto::Atom,
     ) -> Result<Vec<c_uchar>, util::GetPropertyError> {
         let atoms = self.xconn.atoms();
-        self.xconn.get_property(window, atoms[XdndSelection], atoms[TextUriList])
+        self.xconn.get_property(window, atoms[XdndSelection], target)
+    }
+
+    pub fn parse_text(&self, data: &[c_uchar]) -> String {
+        // Some sources include the terminating null character.
+        String::from_utf8_lossy(data).trim_end_matches('\0').to_owned()
     }
 
     pub fn parse_data(&self, data: &mut [c_uchar]) -> Result<Vec<PathBuf>, DndDataParseError> {
@@ -172,3 +219,214 @@ impl Dnd {
         }
     }
 }
+
+/// A drag started from one of our windows, which we are the XDND source of.
+#[derive(Debug)]
+pub struct DragSource {
+    /// The types the data is offered as.
+    types: Vec<xproto::Atom>,
+    data: Vec<u8>,
+    /// The window supporting XDND under the pointer, with the version of the protocol to use.
+    target: Option<(xproto::Window, u32)>,
+    /// The action of the last `XdndStatus` of the target, `None` if it rejects the drop.
+    action: Option<DragAction>,
+    /// Whether `XdndDrop` was sent, in which case the drag ends with `XdndFinished`.
+    dropped: bool,
+}
+
+impl DragSource {
+    pub fn new(xconn: &XConnection, payload: DragPayload) -> Self {
+        let atoms = xconn.atoms();
+        let (types, data) = match payload {
+            DragPayload::Paths(paths) => (vec![atoms[TextUriList]], uri_list::encode(&paths)),
+            DragPayload::Text(text) => {
+                (vec![atoms[UTF8_STRING], atoms[TextPlainUtf8], atoms[TextPlain]], text)
+            },
+        };
+
+        DragSource { types, data: data.into_bytes(), target: None, action: None, dropped: false }
+    }
+
+    /// The data to send when the selection is converted to `target`.
+    pub fn data(&self, target: xproto::Atom) -> Option<&[u8]> {
+        self.types.contains(&target).then_some(&self.data)
+    }
+
+    pub fn is_dropped(&self) -> bool {
+        self.dropped
+    }
+
+    /// Send the position of the pointer to the window under it, entering and leaving the targets
+    /// as it moves.
+    pub fn motion(
+        &mut self,
+        xconn: &XConnection,
+        root: xproto::Window,
+        this_window: xproto::Window,
+        (root_x, root_y): (i16, i16),
+        time: xproto::Timestamp,
+    ) -> Result<(), X11Error> {
+        if self.dropped {
+            return Ok(());
+        }
+
+        let atoms = xconn.atoms();
+        let target = find_target(xconn, root, root_x, root_y)?;
+        if target.map(|(window, _)| window) != self.target.map(|(window, _)| window) {
+            if let Some((window, _)) = self.target.take() {
+                xconn
+                    .send_client_msg(window, window, atoms[XdndLeave], None, [
+                        this_window,
+                        0,
+                        0,
+                        0,
+                        0,
+                    ])?
+                    .ignore_error();
+            }
+
+            self.action = None;
+            if let Some((window, version)) = target {
+                // All our types fit in the message, so `XdndTypeList` isn't needed.
+                let mut types = self.types.iter().copied().chain(std::iter::repeat(0));
+                xconn
+                    .send_client_msg(window, window, atoms[XdndEnter], None, [
+                        this_window,
+                        version << 24,
+                        types.next().unwrap(),
+                        types.next().unwrap(),
+                        types.next().unwrap(),
+                    ])?
+                    .ignore_error();
+            }
+
+            self.target = target;
+        }
+
+        if let Some((window, _)) = self.target {
+            // The coordinates are packed as `(x << 16) | y`.
+            let coordinates = ((root_x as u16 as u32) << 16) | root_y as u16 as u32;
+            xconn
+                .send_client_msg(window, window, atoms[XdndPosition], None, [
+                    this_window,
+                    0,
+                    coordinates,
+                    time,
+                    atoms[XdndActionCopy],
+                ])?
+                .ignore_error();
+        }
+
+        xconn.flush_requests().map_err(Into::into)
+    }
+
+    /// Handle the `XdndStatus` of `target_window`.
+    pub fn status(
+        &mut self,
+        xconn: &XConnection,
+        target_window: xproto::Window,
+        accepted: bool,
+        action: xproto::Atom,
+    ) {
+        if self.target.map(|(window, _)| window) == Some(target_window) {
+            self.action = accepted.then(|| xdnd_action(xconn, action));
+        }
+    }
+
+    /// Drop onto the target once the button is released, returns whether the drag ended without
+    /// waiting for the target.
+    pub fn release(
+        &mut self,
+        xconn: &XConnection,
+        this_window: xproto::Window,
+        time: xproto::Timestamp,
+    ) -> Result<bool, X11Error> {
+        let atoms = xconn.atoms();
+        let ended = match self.target {
+            Some((window, _)) if self.action.is_some() => {
+                xconn
+                    .send_client_msg(window, window, atoms[XdndDrop], None, [
+                        this_window,
+                        0,
+                        time,
+                        0,
+                        0,
+                    ])?
+                    .ignore_error();
+                self.dropped = true;
+                false
+            },
+            Some((window, _)) => {
+                xconn
+                    .send_client_msg(window, window, atoms[XdndLeave], None, [
+                        this_window,
+                        0,
+                        0,
+                        0,
+                        0,
+                    ])?
+                    .ignore_error();
+                true
+            },
+            None => true,
+        };
+
+        xconn.flush_requests()?;
+        Ok(ended)
+    }
+
+    /// The action reported by the `XdndFinished` of the target.
+    pub fn finished_action(
+        &self,
+        xconn: &XConnection,
+        accepted: bool,
+        action: xproto::Atom,
+    ) -> Option<DragAction> {
+        match self.target {
+            // The result is only reported since version 5.
+            Some((_, version)) if version >= 5 => accepted.then(|| xdnd_action(xconn, action)),
+            _ => self.action,
+        }
+    }
+}
+
+fn xdnd_action(xconn: &XConnection, action: xproto::Atom) -> DragAction {
+    if action == xconn.atoms()[XdndActionMove] {
+        DragAction::Move
+    } else {
+        DragAction::Copy
+    }
+}
+
+/// Find the window supporting XDND at the given root coordinates.
+fn find_target(
+    xconn: &XConnection,
+    root: xproto::Window,
+    root_x: i16,
+    root_y: i16,
+) -> Result<Option<(xproto::Window, u32)>, X11Error> {
+    let atoms = xconn.atoms();
+    let mut window = root;
+    loop {
+        let child = xconn
+            .xcb_connection()
+            .translate_coordinates(root, window, root_x, root_y)?
+            .reply()?
+            .child;
+        if child == x11rb::NONE {
+            return Ok(None);
+        }
+
+        // The top-level windows, which may be nested in the frames of the window manager,
+        // advertise the version they support with `XdndAware`.
+        let version = xconn
+            .get_property::<u32>(child, atoms[XdndAware], xproto::AtomEnum::ATOM.into())
+            .ok()
+            .and_then(|version| version.first().copied());
+        if let Some(version) = version {
+            return Ok(Some((child, version.min(XDND_VERSION))));
+        }
+
+        window = child;
+    }
+}
@@ -3,7 +3,9 @@ use std::collections::{HashMap, VecDeque};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
 use std::slice;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
+use tracing::warn;
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
     XILeaveEvent, XIModifierState, XIRawEvent,
@@ -11,8 +13,10 @@ use x11_dl::xinput2::{
 use x11_dl::xlib::{
     self, Display as XDisplay, Window as XWindow, XAnyEvent, XClientMessageEvent, XConfigureEvent,
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
-    XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
+    XReparentEvent, XSelectionEvent, XSelectionRequestEvent, XVisibilityEvent, XkbAnyEvent,
+    XkbStateRec,
 };
+use x11rb::protocol::present::CompleteNotifyEvent;
 use x11rb::protocol::xinput;
 use x11rb::protocol::xkb::ID as XkbId;
 use x11rb::protocol::xproto::{self, ConnectionExt as _, ModMask};
@@ -21,21 +25,23 @@ use xkbcommon_dl::xkb_mod_mask_t;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::event::{
-    DeviceEvent, ElementState, Event, Ime, InnerSizeWriter, MouseButton, MouseScrollDelta,
-    RawKeyEvent, Touch, TouchPhase, WindowEvent,
+    DeviceEvent, DragDropEvent, DragDropItem, ElementState, Event, Ime, InnerSizeWriter, Modifiers,
+    MouseButton, MouseScrollDelta, RawKeyEvent, Touch, TouchPhase, TouchTool, WindowEvent,
 };
-use crate::event_loop::ActiveEventLoop as RootAEL;
-use crate::keyboard::ModifiersState;
+use crate::event_loop::{ActiveEventLoop as RootAEL, DeviceEventKinds};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::common::xkb::{self, XkbState};
 use crate::platform_impl::platform::common::xkb::Context;
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
 use crate::platform_impl::platform::x11::ActiveEventLoop;
-use crate::platform_impl::platform::ActiveEventLoop as PlatformActiveEventLoop;
+use crate::platform_impl::platform::{
+    ActiveEventLoop as PlatformActiveEventLoop, MonitorHandle as PlatformMonitorHandle,
+};
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
 use crate::platform_impl::x11::{
-    mkdid, mkwid, util, CookieResultExt, Device, DeviceId, DeviceInfo, Dnd, DndState, ImeReceiver,
-    ScrollOrientation, UnownedWindow, WindowId,
+    mkdid, mkwid, present, util, CookieResultExt, Device, DeviceId, DeviceInfo, Dnd, DndState,
+    ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
 };
 
 /// The maximum amount of X modifiers to replay.
@@ -52,6 +58,8 @@ pub struct EventProcessor {
     pub devices: RefCell<HashMap<DeviceId, Device>>,
     pub xi2ext: ExtensionInformation,
     pub xkbext: ExtensionInformation,
+    // The major opcode of the Present extension, if its events are handled.
+    pub present_opcode: Option<u8>,
     pub target: RootAEL,
     pub xkb_context: Context,
     // Number of touch events currently in progress
@@ -65,7 +73,7 @@ pub struct EventProcessor {
     // Currently focused window belonging to this process
     pub active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
-    pub modifiers: Cell<ModifiersState>,
+    pub modifiers: Cell<Modifiers>,
     pub xfiltered_modifiers: VecDeque<c_ulong>,
     pub xmodmap: util::ModifierKeymap,
     pub is_composing: bool,
@@ -165,6 +173,7 @@ impl EventProcessor {
         match event_type {
             xlib::ClientMessage => self.client_message(xev.as_ref(), &mut callback),
             xlib::SelectionNotify => self.selection_notify(xev.as_ref(), &mut callback),
+            xlib::SelectionRequest => self.selection_request(xev.as_ref()),
             xlib::ConfigureNotify => self.configure_notify(xev.as_ref(), &mut callback),
             xlib::ReparentNotify => self.reparent_notify(xev.as_ref()),
             xlib::MapNotify => self.map_notify(xev.as_ref(), &mut callback),
@@ -187,6 +196,12 @@ impl EventProcessor {
                 let xev: GenericEventCookie =
                     match GenericEventCookie::from_event(wt.xconn.clone(), *xev) {
                         Some(xev) if xev.extension() == self.xi2ext.major_opcode => xev,
+                        Some(xev) if Some(xev.extension()) == self.present_opcode => {
+                            if let Some(xev) = present::complete_notify(&xev) {
+                                self.present_complete(&xev, &mut callback);
+                            }
+                            return;
+                        },
                         _ => return,
                     };
 
@@ -285,7 +300,7 @@ impl EventProcessor {
                 }
             },
             _ => {
-                if event_type == self.xkbext.first_event as _ {
+                if event_type == self.xkbext.first_event as c_int {
                     let xev: &XkbAnyEvent = unsafe { &*(xev as *const _ as *const XkbAnyEvent) };
                     self.xkb_event(xev, &mut callback);
                 }
@@ -373,7 +388,7 @@ impl EventProcessor {
     pub fn window_target(window_target: &RootAEL) -> &ActiveEventLoop {
         match &window_target.p {
             PlatformActiveEventLoop::X(target) => target,
-            #[cfg(wayland_platform)]
+            #[cfg(any(wayland_platform, headless_platform))]
             _ => unreachable!(),
         }
     }
@@ -382,7 +397,7 @@ impl EventProcessor {
     pub fn window_target_mut(window_target: &mut RootAEL) -> &mut ActiveEventLoop {
         match &mut window_target.p {
             PlatformActiveEventLoop::X(target) => target,
-            #[cfg(wayland_platform)]
+            #[cfg(any(wayland_platform, headless_platform))]
             _ => unreachable!(),
         }
     }
@@ -429,6 +444,36 @@ impl EventProcessor {
             return;
         }
 
+        if xev.message_type == atoms[XdndStatus] as c_ulong {
+            let target_window = xev.data.get_long(0) as xproto::Window;
+            let accepted = xev.data.get_long(1) & 1 == 1;
+            let action = xev.data.get_long(4) as xproto::Atom;
+            self.with_window(window, |window| {
+                if let Some(drag) = window.shared_state_lock().drag_source.as_mut() {
+                    drag.status(&wt.xconn, target_window, accepted, action);
+                }
+            });
+            return;
+        }
+
+        if xev.message_type == atoms[XdndFinished] as c_ulong {
+            let accepted = xev.data.get_long(1) & 1 == 1;
+            let action = xev.data.get_long(2) as xproto::Atom;
+            let action = self.with_window(window, |window| {
+                let mut shared_state_lock = window.shared_state_lock();
+                let drag =
+                    shared_state_lock.drag_source.as_ref().filter(|drag| drag.is_dropped())?;
+                let action = drag.finished_action(&wt.xconn, accepted, action);
+                shared_state_lock.drag_source = None;
+                Some(action)
+            });
+            if let Some(Some(action)) = action {
+                let event = WindowEvent::DragSourceFinished { action };
+                callback(&self.target, Event::WindowEvent { window_id, event });
+            }
+            return;
+        }
+
         if xev.message_type == atoms[XdndEnter] as c_ulong {
             let source_window = xev.data.get_long(0) as xproto::Window;
             let flags = xev.data.get_long(1);
@@ -449,38 +494,22 @@ impl EventProcessor {
         }
 
         if xev.message_type == atoms[XdndPosition] as c_ulong {
-            // This event occurs every time the mouse moves while a file's being dragged
-            // over our window. We emit HoveredFile in response; while the macOS backend
-            // does that upon a drag entering, XDND doesn't have access to the actual drop
-            // data until this event. For parity with other platforms, we only emit
-            // `HoveredFile` the first time, though if winit's API is later extended to
-            // supply position updates with `HoveredFile` or another event, implementing
-            // that here would be trivial.
+            // This event occurs every time the mouse moves while something's being dragged
+            // over our window. The first one is reported with `DragDropEvent::Entered`, and the
+            // following ones with `DragDropEvent::Moved`. XDND doesn't have access to the actual
+            // drop data until this event, so the deprecated `HoveredFile` is only emitted once
+            // the data is received.
 
             let source_window = xev.data.get_long(0) as xproto::Window;
 
-            // Equivalent to `(x << shift) | y`
-            // where `shift = mem::size_of::<c_short>() * 8`
-            // Note that coordinates are in "desktop space", not "window space"
-            // (in X11 parlance, they're root window coordinates)
-            // let packed_coordinates = xev.data.get_long(2);
-            // let shift = mem::size_of::<libc::c_short>() * 8;
-            // let x = packed_coordinates >> shift;
-            // let y = packed_coordinates & !(x << shift);
-
             // By our own state flow, `version` should never be `None` at this point.
             let version = self.dnd.version.unwrap_or(5);
 
             // Action is specified in versions 2 and up, though we don't need it anyway.
             // let action = xev.data.get_long(4);
 
-            let accepted = if let Some(ref type_list) = self.dnd.type_list {
-                type_list.contains(&atoms[TextUriList])
-            } else {
-                false
-            };
-
-            if !accepted {
+            let kinds = self.dnd.kinds();
+            if kinds.is_empty() {
                 unsafe {
                     self.dnd
                         .send_status(window, source_window, DndState::Rejected)
@@ -491,7 +520,11 @@ impl EventProcessor {
             }
 
             self.dnd.source_window = Some(source_window);
-            if self.dnd.result.is_none() {
+            if let Some(target) = self
+                .dnd
+                .data_target()
+                .filter(|_| self.dnd.result.is_none() && self.dnd.text.is_none())
+            {
                 let time = if version >= 1 {
                     xev.data.get_long(3) as xproto::Timestamp
                 } else {
@@ -504,10 +537,32 @@ impl EventProcessor {
 
                 // This results in the `SelectionNotify` event below
                 unsafe {
-                    self.dnd.convert_selection(window, time);
+                    self.dnd.convert_selection(window, target, time);
                 }
             }
 
+            // The coordinates are packed as `(x << 16) | y`, and are relative to the root window.
+            let packed_coordinates = xev.data.get_long(2);
+            let root_x = (packed_coordinates >> 16) as i16 as i32;
+            let root_y = packed_coordinates as i16 as i32;
+            let (origin_x, origin_y) = *self.dnd.window_origin.get_or_insert_with(|| {
+                wt.xconn
+                    .translate_coords(window, wt.root)
+                    .map(|reply| (reply.dst_x as i32, reply.dst_y as i32))
+                    .unwrap_or_default()
+            });
+            let position =
+                PhysicalPosition::new((root_x - origin_x) as f64, (root_y - origin_y) as f64);
+            let event = match self.dnd.position.replace(position) {
+                None => Some(DragDropEvent::Entered { kinds, position }),
+                Some(previous) if previous != position => Some(DragDropEvent::Moved { position }),
+                Some(_) => None,
+            };
+            if let Some(event) = event {
+                let event = Event::WindowEvent { window_id, event: WindowEvent::DragDrop(event) };
+                callback(&self.target, event);
+            }
+
             unsafe {
                 self.dnd
                     .send_status(window, source_window, DndState::Accepted)
@@ -518,15 +573,23 @@ impl EventProcessor {
 
         if xev.message_type == atoms[XdndDrop] as c_ulong {
             let (source_window, state) = if let Some(source_window) = self.dnd.source_window {
+                let mut items = Vec::new();
                 if let Some(Ok(ref path_list)) = self.dnd.result {
                     for path in path_list {
+                        #[allow(deprecated)]
                         let event = Event::WindowEvent {
                             window_id,
                             event: WindowEvent::DroppedFile(path.clone()),
                         };
                         callback(&self.target, event);
                     }
+                    items.extend(path_list.iter().cloned().map(DragDropItem::Path));
+                } else if let Some(text) = self.dnd.text.take() {
+                    items.push(DragDropItem::Text(text));
                 }
+
+                let event = WindowEvent::DragDrop(DragDropEvent::Dropped { items });
+                callback(&self.target, Event::WindowEvent { window_id, event });
                 (source_window, DndState::Accepted)
             } else {
                 // `source_window` won't be part of our DND state if we already rejected the drop in
@@ -546,9 +609,15 @@ impl EventProcessor {
         }
 
         if xev.message_type == atoms[XdndLeave] as c_ulong {
+            let entered = self.dnd.position.is_some();
             self.dnd.reset();
+            #[allow(deprecated)]
             let event = Event::WindowEvent { window_id, event: WindowEvent::HoveredFileCancelled };
             callback(&self.target, event);
+            if entered {
+                let event = WindowEvent::DragDrop(DragDropEvent::Left);
+                callback(&self.target, Event::WindowEvent { window_id, event });
+            }
         }
     }
 
@@ -571,10 +640,19 @@ impl EventProcessor {
 
         // This is where we receive data from drag and drop
         self.dnd.result = None;
-        if let Ok(mut data) = unsafe { self.dnd.read_data(window) } {
+        self.dnd.text = None;
+        let Some(target) = self.dnd.data_target() else {
+            return;
+        };
+        if target != atoms[TextUriList] {
+            if let Ok(data) = unsafe { self.dnd.read_data(window, target) } {
+                self.dnd.text = Some(self.dnd.parse_text(&data));
+            }
+        } else if let Ok(mut data) = unsafe { self.dnd.read_data(window, target) } {
             let parse_result = self.dnd.parse_data(&mut data);
             if let Ok(ref path_list) = parse_result {
                 for path in path_list {
+                    #[allow(deprecated)]
                     let event = Event::WindowEvent {
                         window_id,
                         event: WindowEvent::HoveredFile(path.clone()),
@@ -586,6 +664,57 @@ impl EventProcessor {
         }
     }
 
+    fn selection_request(&self, xev: &XSelectionRequestEvent) {
+        let wt = Self::window_target(&self.target);
+        let atoms = wt.xconn.atoms();
+
+        // Set the timestamp.
+        wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
+
+        let requestor = xev.requestor as xproto::Window;
+        let target = xev.target as xproto::Atom;
+        // Obsolete clients don't specify the property to store the data in.
+        let property = match xev.property as xproto::Atom {
+            x11rb::NONE => target,
+            property => property,
+        };
+
+        // This is where we send the data of drags started with `start_drag_and_drop`.
+        let sent = xev.selection == atoms[XdndSelection] as c_ulong
+            && self
+                .with_window(xev.owner as xproto::Window, |window| {
+                    let shared_state_lock = window.shared_state_lock();
+                    let data = shared_state_lock.drag_source.as_ref()?.data(target)?;
+                    wt.xconn
+                        .change_property(
+                            requestor,
+                            property,
+                            target,
+                            xproto::PropMode::REPLACE,
+                            data,
+                        )
+                        .ok()
+                        .map(|cookie| cookie.ignore_error())
+                })
+                .flatten()
+                .is_some();
+
+        let event = xproto::SelectionNotifyEvent {
+            response_type: xproto::SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: xev.time as xproto::Timestamp,
+            requestor,
+            selection: xev.selection as xproto::Atom,
+            target,
+            property: if sent { property } else { x11rb::NONE },
+        };
+        wt.xconn
+            .xcb_connection()
+            .send_event(false, requestor, xproto::EventMask::NO_EVENT, event)
+            .expect_then_ignore_error("Failed to send `SelectionNotify` event.");
+        wt.xconn.flush_requests().expect("Failed to flush the `SelectionNotify` event.");
+    }
+
     fn configure_notify<F>(&self, xev: &XConfigureEvent, mut callback: F)
     where
         F: FnMut(&RootAEL, Event),
@@ -674,22 +803,38 @@ impl EventProcessor {
             let (width, height) =
                 shared_state_lock.dpi_adjusted.unwrap_or((xev.width as u32, xev.height as u32));
 
-            let last_scale_factor = shared_state_lock.last_monitor.scale_factor;
-            let new_scale_factor = {
-                let window_rect = util::AaRect::new(new_outer_position, new_inner_size);
-                let monitor = wt
-                    .xconn
-                    .get_monitor_for_window(Some(window_rect))
-                    .expect("Failed to find monitor for window");
-
-                if monitor.is_dummy() {
-                    // Avoid updating monitor using a dummy monitor handle
-                    last_scale_factor
-                } else {
-                    shared_state_lock.last_monitor = monitor.clone();
-                    monitor.scale_factor
-                }
+            let last_monitor = shared_state_lock.last_monitor.clone();
+            let last_scale_factor = last_monitor.scale_factor;
+            let window_rect = util::AaRect::new(new_outer_position, new_inner_size);
+            let monitor = wt
+                .xconn
+                .get_monitor_for_window(Some(window_rect))
+                .expect("Failed to find monitor for window");
+
+            // Avoid updating monitor using a dummy monitor handle
+            let new_scale_factor = if monitor.is_dummy() {
+                last_scale_factor
+            } else {
+                shared_state_lock.last_monitor = monitor.clone();
+                monitor.scale_factor
             };
+
+            // The monitor with the largest overlap only changes once most of the window crossed.
+            if !monitor.is_dummy() && monitor != last_monitor {
+                // Unlock shared state to prevent deadlock in callback below
+                drop(shared_state_lock);
+
+                let old = (!last_monitor.is_dummy())
+                    .then(|| RootMonitorHandle { inner: PlatformMonitorHandle::X(last_monitor) });
+                let new = RootMonitorHandle { inner: PlatformMonitorHandle::X(monitor) };
+                callback(&self.target, Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::MonitorChanged { old, new },
+                });
+
+                shared_state_lock = window.shared_state_lock();
+            }
+
             if last_scale_factor != new_scale_factor {
                 let (new_width, new_height) = window.adjust_for_dpi(
                     last_scale_factor,
@@ -758,6 +903,9 @@ impl EventProcessor {
             let _ = window.set_cursor_hittest(true);
         }
 
+        // Move the barriers of the confined cursor along with the window.
+        window.update_pointer_barriers();
+
         if resized {
             callback(&self.target, Event::WindowEvent {
                 window_id,
@@ -871,6 +1019,23 @@ impl EventProcessor {
         }
     }
 
+    fn present_complete<F>(&self, xev: &CompleteNotifyEvent, mut callback: F)
+    where
+        F: FnMut(&RootAEL, Event),
+    {
+        let feedback = self
+            .with_window(xev.window, |window| window.shared_state_lock().presentation.complete(xev))
+            .flatten();
+
+        if let Some(feedback) = feedback {
+            let event = Event::WindowEvent {
+                window_id: mkwid(xev.window),
+                event: WindowEvent::FramePresented(feedback),
+            };
+            callback(&self.target, event);
+        }
+    }
+
     fn xinput_key_input<F>(&mut self, xev: &mut XKeyEvent, state: ElementState, mut callback: F)
     where
         F: FnMut(&RootAEL, Event),
@@ -1010,10 +1175,9 @@ impl EventProcessor {
 
         let mask = self.xkb_mod_mask_from_core(state);
         xkb_state.update_modifiers(mask, 0, 0, 0, 0, Self::core_keyboard_group(state));
-        let mods: ModifiersState = xkb_state.modifiers().into();
+        let mods: Modifiers = xkb_state.modifiers().into();
 
-        let event =
-            Event::WindowEvent { window_id, event: WindowEvent::ModifiersChanged(mods.into()) };
+        let event = Event::WindowEvent { window_id, event: WindowEvent::ModifiersChanged(mods) };
 
         callback(&self.target, event);
     }
@@ -1023,52 +1187,99 @@ impl EventProcessor {
         F: FnMut(&RootAEL, Event),
     {
         let wt = Self::window_target(&self.target);
-        let window_id = mkwid(event.event as xproto::Window);
+        let xwindow = event.event as xproto::Window;
+        let window_id = mkwid(xwindow);
         let device_id = mkdid(event.deviceid as xinput::DeviceId);
+        let time = event.time as xproto::Timestamp;
 
         // Set the timestamp.
-        wt.xconn.set_timestamp(event.time as xproto::Timestamp);
+        wt.xconn.set_timestamp(time);
 
         // Deliver multi-touch events instead of emulated mouse events.
         if (event.flags & xinput2::XIPointerEmulated) != 0 {
             return;
         }
 
-        let event = match event.detail as u32 {
-            xlib::Button1 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Left }
-            },
-            xlib::Button2 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Middle }
-            },
-
-            xlib::Button3 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Right }
-            },
+        let button = match event.detail as u32 {
+            xlib::Button1 => MouseButton::Left,
+            xlib::Button2 => MouseButton::Middle,
+            xlib::Button3 => MouseButton::Right,
 
             // Suppress emulated scroll wheel clicks, since we handle the real motion events for
             // those. In practice, even clicky scroll wheels appear to be reported by
             // evdev (and XInput2 in turn) as axis motion, so we don't otherwise
             // special-case these button presses.
-            4..=7 => WindowEvent::MouseWheel {
-                device_id,
-                delta: match event.detail {
-                    4 => MouseScrollDelta::LineDelta(0.0, 1.0),
-                    5 => MouseScrollDelta::LineDelta(0.0, -1.0),
-                    6 => MouseScrollDelta::LineDelta(1.0, 0.0),
-                    7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
-                    _ => unreachable!(),
-                },
-                phase: TouchPhase::Moved,
+            4..=7 => {
+                let event = WindowEvent::MouseWheel {
+                    device_id,
+                    delta: match event.detail {
+                        4 => MouseScrollDelta::LineDelta(0.0, 1.0),
+                        5 => MouseScrollDelta::LineDelta(0.0, -1.0),
+                        6 => MouseScrollDelta::LineDelta(1.0, 0.0),
+                        7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
+                        _ => unreachable!(),
+                    },
+                    phase: TouchPhase::Moved,
+                    momentum_phase: None,
+                };
+                callback(&self.target, Event::WindowEvent { window_id, event });
+                return;
             },
-            8 => WindowEvent::MouseInput { device_id, state, button: MouseButton::Back },
+            8 => MouseButton::Back,
 
-            9 => WindowEvent::MouseInput { device_id, state, button: MouseButton::Forward },
-            x => WindowEvent::MouseInput { device_id, state, button: MouseButton::Other(x as u16) },
+            9 => MouseButton::Forward,
+            x => MouseButton::Other(x as u16),
         };
 
-        let event = Event::WindowEvent { window_id, event };
-        callback(&self.target, event);
+        let position = PhysicalPosition::new(event.event_x, event.event_y);
+        let click_count = self
+            .with_window(xwindow, |window_handle| {
+                let click_counter = &mut window_handle.shared_state_lock().click_counter;
+                match state {
+                    ElementState::Pressed => {
+                        click_counter.press(button, position, Instant::now(), wt.click_thresholds)
+                    },
+                    ElementState::Released => click_counter.release(button),
+                }
+            })
+            .unwrap_or(1);
+
+        let event = WindowEvent::MouseInput { device_id, state, button, click_count };
+        callback(&self.target, Event::WindowEvent { window_id, event });
+
+        if state == ElementState::Released {
+            self.drag_source_release(xwindow, time, &mut callback);
+        }
+    }
+
+    /// Drop the drag started from `window` once the button is released, when it is in progress.
+    fn drag_source_release<F>(
+        &self,
+        window: xproto::Window,
+        time: xproto::Timestamp,
+        mut callback: F,
+    ) where
+        F: FnMut(&RootAEL, Event),
+    {
+        let wt = Self::window_target(&self.target);
+        let ended = self.with_window(window, |window_handle| {
+            let mut shared_state_lock = window_handle.shared_state_lock();
+            let drag = shared_state_lock.drag_source.as_mut().filter(|drag| !drag.is_dropped())?;
+            let ended = drag.release(&wt.xconn, window, time).unwrap_or_else(|err| {
+                warn!("failed to drop the dragged data: {err}");
+                true
+            });
+            if ended {
+                shared_state_lock.drag_source = None;
+            }
+
+            Some(ended)
+        });
+
+        if ended == Some(Some(true)) {
+            let event = WindowEvent::DragSourceFinished { action: None };
+            callback(&self.target, Event::WindowEvent { window_id: mkwid(window), event });
+        }
     }
 
     fn xinput2_mouse_motion<F>(&self, event: &XIDeviceEvent, mut callback: F)
@@ -1085,8 +1296,19 @@ impl EventProcessor {
         let window_id = mkwid(window);
         let new_cursor_pos = (event.event_x, event.event_y);
 
-        let cursor_moved = self.with_window(window, |window| {
-            let mut shared_state_lock = window.shared_state_lock();
+        let cursor_moved = self.with_window(window, |window_handle| {
+            let mut shared_state_lock = window_handle.shared_state_lock();
+            if let Some(drag) = shared_state_lock.drag_source.as_mut() {
+                let position = (event.root_x as i16, event.root_y as i16);
+                let time = event.time as xproto::Timestamp;
+                if let Err(err) = drag.motion(&wt.xconn, wt.root, window, position, time) {
+                    warn!("failed to move the dragged data: {err}");
+                }
+            }
+
+            let position = PhysicalPosition::new(event.event_x, event.event_y);
+            shared_state_lock.click_counter.moved(position, wt.click_thresholds);
+
             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
         });
 
@@ -1122,7 +1344,7 @@ impl EventProcessor {
             let x = unsafe { *value };
 
             let event = if let Some(&mut (_, ref mut info)) =
-                physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i as _)
+                physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i)
             {
                 let delta = (x - info.position) / info.increment;
                 info.position = x;
@@ -1134,7 +1356,12 @@ impl EventProcessor {
                     ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
                 };
 
-                WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved }
+                WindowEvent::MouseWheel {
+                    device_id,
+                    delta,
+                    phase: TouchPhase::Moved,
+                    momentum_phase: None,
+                }
             } else {
                 WindowEvent::AxisMotion { device_id, axis: i as u32, value: unsafe { *value } }
             };
@@ -1244,6 +1471,7 @@ impl EventProcessor {
 
         if let Some(window) = self.with_window(window, Arc::clone) {
             window.shared_state_lock().has_focus = true;
+            window.update_pointer_barriers();
         }
 
         let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(true) };
@@ -1321,6 +1549,7 @@ impl EventProcessor {
 
             if let Some(window) = self.with_window(window, Arc::clone) {
                 window.shared_state_lock().has_focus = false;
+                window.update_pointer_barriers();
             }
 
             let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(false) };
@@ -1363,6 +1592,12 @@ impl EventProcessor {
                     phase,
                     location,
                     force: None, // TODO
+                    tool: TouchTool::Finger,
+                    azimuth_angle: None,
+                    altitude_angle: None,
+                    twist_angle: None,
+                    is_estimated: false,
+                    is_predicted: false,
                     id,
                 }),
             };
@@ -1399,6 +1634,10 @@ impl EventProcessor {
 
         let did = mkdid(xev.deviceid as xinput::DeviceId);
 
+        // Motion and scrolling can't be selected separately, drop what wasn't requested.
+        let kinds = wt.device_events().kinds();
+        let motion = kinds.contains(DeviceEventKinds::MOTION);
+
         let mask =
             unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
         let mut value = xev.raw_values;
@@ -1420,16 +1659,18 @@ impl EventProcessor {
                 _ => {},
             }
 
-            let event = Event::DeviceEvent {
-                device_id: did,
-                event: DeviceEvent::Motion { axis: i as u32, value: x },
-            };
-            callback(&self.target, event);
+            if motion {
+                let event = Event::DeviceEvent {
+                    device_id: did,
+                    event: DeviceEvent::Motion { axis: i as u32, value: x },
+                };
+                callback(&self.target, event);
+            }
 
             value = unsafe { value.offset(1) };
         }
 
-        if let Some(mouse_delta) = mouse_delta.consume() {
+        if let Some(mouse_delta) = mouse_delta.consume().filter(|_| motion) {
             let event = Event::DeviceEvent {
                 device_id: did,
                 event: DeviceEvent::MouseMotion { delta: mouse_delta },
@@ -1437,7 +1678,9 @@ impl EventProcessor {
             callback(&self.target, event);
         }
 
-        if let Some(scroll_delta) = scroll_delta.consume() {
+        if let Some(scroll_delta) =
+            scroll_delta.consume().filter(|_| kinds.contains(DeviceEventKinds::WHEEL))
+        {
             let event = Event::DeviceEvent {
                 device_id: did,
                 event: DeviceEvent::MouseWheel {
@@ -1521,6 +1764,7 @@ impl EventProcessor {
                     let xcb = wt.xconn.xcb_connection().get_raw_xcb_connection();
                     self.xkb_context.set_keymap_from_x11(xcb);
                     self.xmodmap.reload_from_x_connection(&wt.xconn);
+                    self.update_keyboard_layout(&mut callback);
 
                     let window_id = match self.active_window.map(super::mkwid) {
                         Some(window_id) => window_id,
@@ -1537,6 +1781,8 @@ impl EventProcessor {
                 let xcb = wt.xconn.xcb_connection().get_raw_xcb_connection();
                 self.xkb_context.set_keymap_from_x11(xcb);
                 self.xmodmap.reload_from_x_connection(&wt.xconn);
+                self.update_keyboard_layout(&mut callback);
+
                 let window_id = match self.active_window.map(super::mkwid) {
                     Some(window_id) => window_id,
                     None => return,
@@ -1562,20 +1808,36 @@ impl EventProcessor {
                         xev.latched_group as u32,
                         xev.locked_group as u32,
                     );
+                    let mods = state.modifiers().into();
+                    self.update_keyboard_layout(&mut callback);
 
                     let window_id = match self.active_window.map(super::mkwid) {
                         Some(window_id) => window_id,
                         None => return,
                     };
 
-                    let mods = state.modifiers().into();
-                    self.send_modifiers(window_id, mods, true, &mut callback);
+                    // The state is also notified when it changes in ways we don't report, like
+                    // when releasing caps lock, so only the changes are sent.
+                    self.send_modifiers(window_id, mods, false, &mut callback);
                 }
             },
             _ => {},
         }
     }
 
+    /// Share the keyboard layout with the event loop, reporting it when it changed.
+    fn update_keyboard_layout<F>(&self, callback: &mut F)
+    where
+        F: FnMut(&RootAEL, Event),
+    {
+        let wt = Self::window_target(&self.target);
+        let layout = self.xkb_context.keyboard_layout();
+        if *wt.keyboard_layout.borrow() != layout {
+            *wt.keyboard_layout.borrow_mut() = layout;
+            callback(&self.target, Event::KeyboardLayoutChanged);
+        }
+    }
+
     pub(crate) fn update_mods_from_xinput2_event<F>(
         &mut self,
         mods: &XIModifierState,
@@ -1594,6 +1856,8 @@ impl EventProcessor {
                 group.latched as u32,
                 group.locked as u32,
             );
+            let mods = state.modifiers();
+            self.update_keyboard_layout(&mut callback);
 
             // NOTE: we use active window since generally sub windows don't have keyboard input,
             // and winit assumes that unfocused window doesn't have modifiers.
@@ -1602,7 +1866,6 @@ impl EventProcessor {
                 None => return,
             };
 
-            let mods = state.modifiers();
             self.send_modifiers(window_id, mods.into(), force, &mut callback);
         }
     }
@@ -1720,7 +1983,7 @@ impl EventProcessor {
     fn send_modifiers<F: FnMut(&RootAEL, Event)>(
         &self,
         window_id: crate::window::WindowId,
-        modifiers: ModifiersState,
+        modifiers: Modifiers,
         force: bool,
         callback: &mut F,
     ) {
@@ -1729,7 +1992,7 @@ impl EventProcessor {
         if self.modifiers.replace(modifiers) != modifiers || force {
             let event = Event::WindowEvent {
                 window_id,
-                event: WindowEvent::ModifiersChanged(self.modifiers.get().into()),
+                event: WindowEvent::ModifiersChanged(self.modifiers.get()),
             };
             callback(&self.target, event);
         }
@@ -1784,7 +2047,6 @@ impl EventProcessor {
         let wt = Self::window_target(&self.target);
         wt.xconn.reload_database().expect("failed to reload Xft database");
 
-        // In the future, it would be quite easy to emit monitor hotplug events.
         let prev_list = {
             let prev_list = wt.xconn.invalidate_cached_monitor_list();
             match prev_list {
@@ -1794,6 +2056,18 @@ impl EventProcessor {
         };
 
         let new_list = wt.xconn.available_monitors().expect("Failed to get monitor list");
+
+        // The monitors are identified by their CRTC, which is disabled when the output is
+        // disconnected.
+        for monitor in prev_list.iter().filter(|monitor| !new_list.contains(monitor)) {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::X(monitor.clone()) };
+            callback(&self.target, Event::MonitorDisconnected(monitor));
+        }
+        for monitor in new_list.iter().filter(|monitor| !prev_list.contains(monitor)) {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::X(monitor.clone()) };
+            callback(&self.target, Event::MonitorConnected(monitor));
+        }
+
         for new_monitor in new_list {
             // Previous list may be empty, in case of disconnecting and
             // reconnecting the only one monitor. We still need to emit events in
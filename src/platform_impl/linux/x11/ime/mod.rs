@@ -115,6 +115,7 @@ impl Ime {
         &mut self,
         window: ffi::Window,
         with_preedit: bool,
+        spot: Option<ffi::XPoint>,
     ) -> Result<bool, ImeContextCreationError> {
         let context = if self.is_destroyed() {
             // Create empty entry in map, so that when IME is rebuilt, this window has a context.
@@ -129,7 +130,7 @@ impl Ime {
                     im.im,
                     style,
                     window,
-                    None,
+                    spot,
                     self.inner.event_sender.clone(),
                 )?
             };
@@ -214,17 +215,19 @@ impl Ime {
             return;
         }
 
+        let mut spot = None;
         if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
             if allowed == context.is_allowed() {
                 return;
             }
+            spot = Some(context.ic_spot);
         }
 
         // Remove context for that window.
         let _ = self.remove_context(window);
 
-        // Create new context supporting IME input.
-        let _ = self.create_context(window, allowed);
+        // Create new context supporting IME input, keeping the spot of the old one.
+        let _ = self.create_context(window, allowed, spot);
     }
 }
 
@@ -26,24 +26,35 @@ use x11rb::x11_utils::X11Error as LogicalError;
 use x11rb::xcb_ffi::ReplyOrIdError;
 
 use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, OsError as RootOsError};
+use crate::error::{EventLoopError, OsError as RootOsError, PlatformError};
+#[cfg(feature = "dbus")]
+use crate::event::SessionEvent;
 use crate::event::{Event, StartCause, WindowEvent};
-use crate::event_loop::{ActiveEventLoop as RootAEL, ControlFlow, DeviceEvents};
+use crate::event_loop::{
+    ActiveEventLoop as RootAEL, ControlFlow, DeviceEventKinds, DeviceEventSubscription,
+    DeviceEvents, MotionEventPolicy,
+};
+use crate::keyboard::{self, Key, KeyCode};
 use crate::platform::pump_events::PumpStatus;
-use crate::platform_impl::common::xkb::Context;
+#[cfg(feature = "dbus")]
+use crate::platform_impl::common::session::SessionMonitor;
+use crate::platform_impl::common::xkb::{Context, KeyboardLayout};
 use crate::platform_impl::platform::{min_timeout, WindowId};
 use crate::platform_impl::{
     ActiveEventLoop as PlatformActiveEventLoop, OsError, PlatformCustomCursor,
 };
+use crate::utils::ClickThresholds;
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, WindowAttributes};
 
 mod activation;
 mod atoms;
+mod barrier;
 mod dnd;
 mod event_processor;
 pub mod ffi;
 mod ime;
 mod monitor;
+mod present;
 mod util;
 mod window;
 mod xdisplay;
@@ -138,7 +149,16 @@ pub struct ActiveEventLoop {
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationToken>,
     event_loop_proxy: EventLoopProxy,
-    device_events: Cell<DeviceEvents>,
+    device_events: Cell<DeviceEventSubscription>,
+    /// Whether one of the windows is focused, for [`DeviceEvents::WhenFocused`].
+    device_events_focus: Cell<bool>,
+    /// The device the raw events are currently selected on.
+    device_events_device: Cell<xinput::DeviceId>,
+    /// The active keyboard layout, kept up to date by the event processor.
+    keyboard_layout: RefCell<Option<KeyboardLayout>>,
+    motion_event_policy: Cell<MotionEventPolicy>,
+    /// The double-click settings, read once when the event loop is created.
+    click_thresholds: ClickThresholds,
 }
 
 pub struct EventLoop {
@@ -150,6 +170,10 @@ pub struct EventLoop {
 
     /// The current state of the event loop.
     state: EventLoopState,
+
+    /// Monitors the session on the system bus, stopped when the event loop is dropped.
+    #[cfg(feature = "dbus")]
+    _session_monitor: SessionMonitor,
 }
 
 type ActivationToken = (WindowId, crate::event_loop::AsyncRequestSerial);
@@ -160,6 +184,10 @@ struct EventLoopState {
 
     /// User requested a wake up.
     proxy_wake_up: bool,
+
+    /// The session events received from the session monitor.
+    #[cfg(feature = "dbus")]
+    session_events: Vec<SessionEvent>,
 }
 
 impl EventLoop {
@@ -211,6 +239,11 @@ impl EventLoop {
         let randr_event_offset =
             xconn.select_xrandr_input(root).expect("Failed to query XRandR extension");
 
+        // Cache the monitors, to compare the list against it when it changes.
+        if let Err(error) = xconn.available_monitors() {
+            warn!("Failed to query the monitors: {error}");
+        }
+
         let xi2ext = xconn
             .xcb_connection()
             .extension_information(xinput::X11_EXTENSION_NAME)
@@ -222,6 +255,8 @@ impl EventLoop {
             .expect("Failed to query XKB extension")
             .expect("X server missing XKB extension");
 
+        let present_opcode = present::register_events(&xconn);
+
         // Check for XInput2 support.
         xconn
             .xcb_connection()
@@ -278,12 +313,37 @@ impl EventLoop {
             .expect("Failed to register the event loop waker source");
         let event_loop_proxy = EventLoopProxy::new(user_waker);
 
+        // Forward the session events from the system bus.
+        #[cfg(feature = "dbus")]
+        let session_monitor = {
+            let (session_monitor, session_channel) = SessionMonitor::new();
+            event_loop
+                .handle()
+                .insert_source(session_channel, |event, _, state| {
+                    if let calloop::channel::Event::Msg(event) = event {
+                        state.session_events.push(event);
+                    }
+                })
+                .expect("Failed to register the session monitor source");
+            session_monitor
+        };
+
         let xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
 
         let mut xmodmap = util::ModifierKeymap::new();
         xmodmap.reload_from_x_connection(&xconn);
 
+        let click_thresholds = xconn
+            .xsettings_screen()
+            .and_then(|xsettings_screen| {
+                xconn
+                    .xsettings_click_thresholds(xsettings_screen)
+                    .map_err(|err| warn!("failed to fetch the double-click settings: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
         let window_target = ActiveEventLoop {
             ime,
             root,
@@ -304,13 +364,21 @@ impl EventLoop {
             },
             event_loop_proxy,
             device_events: Default::default(),
+            device_events_focus: Cell::new(true),
+            device_events_device: Cell::new(ALL_MASTER_DEVICES),
+            keyboard_layout: RefCell::new(xkb_context.keyboard_layout()),
+            motion_event_policy: Cell::new(MotionEventPolicy::default()),
+            click_thresholds,
         };
 
         // Set initial device event filter.
         window_target.update_listen_device_events(true);
 
-        let root_window_target =
-            RootAEL { p: PlatformActiveEventLoop::X(window_target), _marker: PhantomData };
+        let root_window_target = RootAEL {
+            p: PlatformActiveEventLoop::X(window_target),
+            user_event_queue: Default::default(),
+            _marker: PhantomData,
+        };
 
         let event_processor = EventProcessor {
             target: root_window_target,
@@ -320,6 +388,7 @@ impl EventLoop {
             ime_receiver,
             ime_event_receiver,
             xi2ext,
+            present_opcode,
             xfiltered_modifiers: VecDeque::with_capacity(MAX_MOD_REPLAY_LEN),
             xmodmap,
             xkbext,
@@ -361,7 +430,14 @@ impl EventLoop {
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
-            state: EventLoopState { x11_readiness: Readiness::EMPTY, proxy_wake_up: false },
+            state: EventLoopState {
+                x11_readiness: Readiness::EMPTY,
+                proxy_wake_up: false,
+                #[cfg(feature = "dbus")]
+                session_events: Vec::new(),
+            },
+            #[cfg(feature = "dbus")]
+            _session_monitor: session_monitor,
         }
     }
 
@@ -432,6 +508,11 @@ impl EventLoop {
     }
 
     fn has_pending(&mut self) -> bool {
+        #[cfg(feature = "dbus")]
+        if !self.state.session_events.is_empty() {
+            return true;
+        }
+
         self.event_processor.poll()
             || self.state.proxy_wake_up
             || self.redraw_receiver.has_incoming()
@@ -514,6 +595,12 @@ impl EventLoop {
         // Process all pending events
         self.drain_events(app);
 
+        // Empty the session events.
+        #[cfg(feature = "dbus")]
+        for event in mem::take(&mut self.state.session_events) {
+            app.session_event(&self.event_processor.target, event);
+        }
+
         // Empty activation tokens.
         while let Ok((window_id, serial)) = self.activation_receiver.try_recv() {
             let token = self.event_processor.with_window(window_id.0 as xproto::Window, |window| {
@@ -550,6 +637,9 @@ impl EventLoop {
             }
 
             for window_id in windows {
+                self.event_processor.with_window(window_id.0 as xproto::Window, |window| {
+                    window.shared_state_lock().redraw_damage.deliver();
+                });
                 let window_id = crate::window::WindowId(window_id);
                 app.window_event(
                     &self.event_processor.target,
@@ -565,30 +655,36 @@ impl EventLoop {
 
     fn drain_events<A: ApplicationHandler>(&mut self, app: &mut A) {
         let mut xev = MaybeUninit::uninit();
+        let window_target = EventProcessor::window_target(&self.event_processor.target);
+        let coalesce_motion = window_target.motion_event_policy() == MotionEventPolicy::Coalesced;
+        // The last cursor motion, held back until an event other than the motion of the same
+        // window and device comes, or the queue is empty.
+        let mut pending_motion: Option<Event> = None;
 
         while unsafe { self.event_processor.poll_one_event(xev.as_mut_ptr()) } {
             let mut xev = unsafe { xev.assume_init() };
             self.event_processor.process_event(&mut xev, |window_target, event: Event| {
-                if let Event::WindowEvent {
-                    window_id: crate::window::WindowId(wid),
-                    event: WindowEvent::RedrawRequested,
-                } = event
-                {
-                    let window_target = EventProcessor::window_target(window_target);
-                    window_target.redraw_sender.send(wid);
-                } else {
-                    match event {
-                        Event::WindowEvent { window_id, event } => {
-                            app.window_event(window_target, window_id, event)
-                        },
-                        Event::DeviceEvent { device_id, event } => {
-                            app.device_event(window_target, device_id, event)
-                        },
-                        _ => unreachable!("event which is neither device nor window event."),
+                if let Some(source) = motion_source(&event).filter(|_| coalesce_motion) {
+                    if let Some(previous) = pending_motion
+                        .take()
+                        .filter(|previous| motion_source(previous) != Some(source))
+                    {
+                        dispatch_event(app, window_target, previous);
                     }
+                    pending_motion = Some(event);
+                    return;
+                }
+
+                if let Some(motion) = pending_motion.take() {
+                    dispatch_event(app, window_target, motion);
                 }
+                dispatch_event(app, window_target, event);
             });
         }
+
+        if let Some(motion) = pending_motion {
+            dispatch_event(app, &self.event_processor.target, motion);
+        }
     }
 
     fn control_flow(&self) -> ControlFlow {
@@ -612,6 +708,40 @@ impl EventLoop {
     }
 }
 
+fn dispatch_event<A: ApplicationHandler>(app: &mut A, window_target: &RootAEL, event: Event) {
+    match event {
+        Event::WindowEvent {
+            window_id: crate::window::WindowId(wid),
+            event: WindowEvent::RedrawRequested,
+        } => {
+            let window_target = EventProcessor::window_target(window_target);
+            window_target.redraw_sender.send(wid);
+        },
+        Event::WindowEvent { window_id, event } => {
+            app.window_event(window_target, window_id, event)
+        },
+        Event::DeviceEvent { device_id, event } => {
+            app.device_event(window_target, device_id, event)
+        },
+        Event::MonitorConnected(monitor) => app.monitor_connected(window_target, monitor),
+        Event::MonitorDisconnected(monitor) => app.monitor_disconnected(window_target, monitor),
+        Event::KeyboardLayoutChanged => app.keyboard_layout_changed(window_target),
+        _ => unreachable!(
+            "event which is neither device, window, monitor nor keyboard layout event."
+        ),
+    }
+}
+
+/// The window and device of a cursor motion.
+fn motion_source(event: &Event) -> Option<(crate::window::WindowId, crate::event::DeviceId)> {
+    match event {
+        Event::WindowEvent { window_id, event: WindowEvent::CursorMoved { device_id, .. } } => {
+            Some((*window_id, *device_id))
+        },
+        _ => None,
+    }
+}
+
 impl AsFd for EventLoop {
     fn as_fd(&self) -> BorrowedFd<'_> {
         self.event_loop.as_fd()
@@ -647,26 +777,88 @@ impl ActiveEventLoop {
         RootCustomCursor { inner: PlatformCustomCursor::X(CustomCursor::new(self, cursor.inner)) }
     }
 
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        self.device_events.set(allowed);
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        match &*self.keyboard_layout.borrow() {
+            Some(layout) => layout.key_from_key_code(code),
+            None => keyboard::layout_independent_key(code),
+        }
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        match &*self.keyboard_layout.borrow() {
+            Some(layout) => layout.key_code_from_key(key),
+            None => keyboard::find_key_code(key, keyboard::layout_independent_keys()),
+        }
+    }
+
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        let kinds = subscription.kinds();
+        if kinds.intersects(DeviceEventKinds::MOTION | DeviceEventKinds::WHEEL)
+            && !kinds.contains(DeviceEventKinds::MOTION | DeviceEventKinds::WHEEL)
+        {
+            tracing::debug!("X11 can't capture raw motion and wheel separately, capturing both");
+        }
+
+        self.device_events.set(subscription);
+        self.update_listen_device_events(self.device_events_focus.get());
+    }
+
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        self.motion_event_policy.set(policy)
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.motion_event_policy.get()
+    }
+
+    /// The device event subscription requested by the user.
+    pub fn device_events(&self) -> DeviceEventSubscription {
+        self.device_events.get()
     }
 
     /// Update the device event based on window focus.
     pub fn update_listen_device_events(&self, focus: bool) {
-        let device_events = self.device_events.get() == DeviceEvents::Always
-            || (focus && self.device_events.get() == DeviceEvents::WhenFocused);
+        self.device_events_focus.set(focus);
+
+        let subscription = self.device_events.get();
+        let device_events = match subscription.when() {
+            DeviceEvents::Always => true,
+            DeviceEvents::WhenFocused => focus,
+            DeviceEvents::Never => false,
+        };
 
         let mut mask = xinput::XIEventMask::from(0u32);
         if device_events {
-            mask = xinput::XIEventMask::RAW_MOTION
-                | xinput::XIEventMask::RAW_BUTTON_PRESS
-                | xinput::XIEventMask::RAW_BUTTON_RELEASE
-                | xinput::XIEventMask::RAW_KEY_PRESS
-                | xinput::XIEventMask::RAW_KEY_RELEASE;
+            let kinds = subscription.kinds();
+            if kinds.intersects(DeviceEventKinds::MOTION | DeviceEventKinds::WHEEL) {
+                // Scrolling is reported through the valuators of the raw motion.
+                mask |= xinput::XIEventMask::RAW_MOTION;
+            }
+            if kinds.contains(DeviceEventKinds::BUTTON) {
+                mask |=
+                    xinput::XIEventMask::RAW_BUTTON_PRESS | xinput::XIEventMask::RAW_BUTTON_RELEASE;
+            }
+            if kinds.contains(DeviceEventKinds::KEY) {
+                mask |= xinput::XIEventMask::RAW_KEY_PRESS | xinput::XIEventMask::RAW_KEY_RELEASE;
+            }
+        }
+
+        // Select the events on the requested device only, so the server doesn't send the others.
+        let device = match subscription.device() {
+            Some(crate::event::DeviceId(crate::platform_impl::DeviceId::X(DeviceId(device)))) => {
+                device
+            },
+            _ => ALL_MASTER_DEVICES,
+        };
+        let previous = self.device_events_device.replace(device);
+        if previous != device {
+            self.xconn
+                .select_xinput_events(self.root, previous, xinput::XIEventMask::from(0u32))
+                .expect_then_ignore_error("Failed to update device event filter");
         }
 
         self.xconn
-            .select_xinput_events(self.root, ALL_MASTER_DEVICES, mask)
+            .select_xinput_events(self.root, device, mask)
             .expect_then_ignore_error("Failed to update device event filter");
     }
 
@@ -802,6 +994,10 @@ impl Drop for Window {
         let window = self.deref();
         let xconn = &window.xconn;
 
+        if let Err(err) = window.shared_state_lock().pointer_barriers.release(xconn) {
+            warn!("Failed to release the pointer barriers: {err}");
+        }
+
         if let Ok(c) = xconn.xcb_connection().destroy_window(window.id().0 as xproto::Window) {
             c.ignore_error();
         }
@@ -882,6 +1078,25 @@ impl fmt::Display for X11Error {
     }
 }
 
+impl X11Error {
+    /// The error as reported by the X server or the connection.
+    pub(crate) fn platform_error(&self) -> Option<PlatformError> {
+        match self {
+            X11Error::Xlib(e) => {
+                Some(PlatformError::new("X11", Some(e.error_code.into()), e.description.clone()))
+            },
+            X11Error::X11(e) => Some(PlatformError::new(
+                "X11",
+                Some(e.error_code.into()),
+                format!("{:?} in {}", e.error_kind, e.request_name.unwrap_or("unknown request")),
+            )),
+            X11Error::Connect(ConnectError::IoError(e))
+            | X11Error::Connection(ConnectionError::IoError(e)) => super::io_platform_error(e),
+            _ => None,
+        }
+    }
+}
+
 impl std::error::Error for X11Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
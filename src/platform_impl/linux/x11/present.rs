@@ -0,0 +1,166 @@
+//! Presentation feedback through the Present extension.
+
+use std::collections::VecDeque;
+use std::os::raw::c_int;
+use std::ptr;
+use std::time::Duration;
+
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::present::{
+    self, CompleteKind, CompleteMode, CompleteNotifyEvent, ConnectionExt as _,
+};
+use x11rb::protocol::xproto;
+use x11rb::x11_utils::TryParse;
+
+use super::util::cookie::GenericEventCookie;
+use super::{ffi, X11Error, XConnection};
+use crate::event::PresentationFeedback;
+use crate::platform_impl::common::clock;
+
+/// The most presentations to wait for, in case the window isn't presented with the extension.
+const MAX_PENDING: usize = 16;
+
+/// The presentation feedback of a window.
+#[derive(Debug, Default)]
+pub struct PresentationState {
+    /// The event context selecting the completion of the presentations, while enabled.
+    event_id: Option<present::Event>,
+
+    /// The number of `pre_present_notify` calls.
+    generation: u64,
+
+    /// The generations waiting for the completion of their presentation.
+    pending: VecDeque<u64>,
+
+    /// The generation, MSC and UST of the last presented frame.
+    last_presented: Option<(u64, u64, u64)>,
+}
+
+impl PresentationState {
+    /// Count the frame about to be presented.
+    pub fn pre_present_notify(&mut self) {
+        self.generation += 1;
+        if self.event_id.is_some() {
+            if self.pending.len() == MAX_PENDING {
+                self.pending.pop_front();
+            }
+            self.pending.push_back(self.generation);
+        }
+    }
+
+    /// Select or deselect the completion events of the presentations to `window`.
+    pub fn set_enabled(
+        &mut self,
+        xconn: &XConnection,
+        window: xproto::Window,
+        enabled: bool,
+    ) -> Result<(), X11Error> {
+        let conn = xconn.xcb_connection();
+        match (enabled, self.event_id) {
+            (true, None) => {
+                let event_id = conn.generate_id()?;
+                conn.present_select_input(event_id, window, present::EventMask::COMPLETE_NOTIFY)?
+                    .check()?;
+                self.event_id = Some(event_id);
+            },
+            (false, Some(event_id)) => {
+                self.event_id = None;
+                self.pending.clear();
+                self.last_presented = None;
+                conn.present_select_input(event_id, window, present::EventMask::NO_EVENT)?
+                    .check()?;
+            },
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    /// The feedback of the frame the completed presentation belongs to.
+    pub fn complete(&mut self, event: &CompleteNotifyEvent) -> Option<PresentationFeedback> {
+        if event.kind != CompleteKind::PIXMAP || self.event_id != Some(event.event) {
+            return None;
+        }
+
+        let generation = self.pending.pop_front()?;
+        if event.mode == CompleteMode::SKIP {
+            return Some(PresentationFeedback {
+                generation,
+                presentation_time: None,
+                refresh_interval: None,
+                missed: true,
+            });
+        }
+
+        let presentation_time =
+            clock::to_instant(libc::CLOCK_MONOTONIC, Duration::from_micros(event.ust));
+        let previous = self.last_presented.replace((generation, event.msc, event.ust));
+        let (refresh_interval, missed) = match previous {
+            Some((previous, msc, ust)) if event.msc > msc && event.ust > ust => {
+                let refresh = Duration::from_micros((event.ust - ust) / (event.msc - msc));
+                (Some(refresh), previous + 1 == generation && event.msc > msc + 1)
+            },
+            _ => (None, false),
+        };
+
+        Some(PresentationFeedback { generation, presentation_time, refresh_interval, missed })
+    }
+}
+
+/// Register the conversion of the Present events, which Xlib doesn't know about, returning the
+/// major opcode of the extension.
+pub fn register_events(xconn: &XConnection) -> Option<u8> {
+    let conn = xconn.xcb_connection();
+    let extension = conn.extension_information(present::X11_EXTENSION_NAME).ok()??;
+    conn.present_query_version(1, 0).ok()?.reply().ok()?;
+
+    unsafe {
+        (xconn.xlib.XESetWireToEventCookie)(
+            xconn.display,
+            extension.major_opcode as c_int,
+            Some(wire_to_cookie),
+        );
+    }
+
+    Some(extension.major_opcode)
+}
+
+/// Parse the `CompleteNotify` event of the cookie.
+pub fn complete_notify(cookie: &GenericEventCookie) -> Option<CompleteNotifyEvent> {
+    if cookie.evtype() != present::COMPLETE_NOTIFY_EVENT as c_int {
+        return None;
+    }
+
+    // SAFETY: `wire_to_cookie` copied the whole event, which is 40 bytes long.
+    let data: &[u8; 40] = unsafe { cookie.as_event() };
+    CompleteNotifyEvent::try_parse(data).ok().map(|(event, _)| event)
+}
+
+/// Copy the wire event into the cookie data, which Xlib frees with the cookie.
+unsafe extern "C" fn wire_to_cookie(
+    display: *mut ffi::Display,
+    cookie: *mut ffi::XGenericEventCookie,
+    wire: *mut ffi::xEvent,
+) -> c_int {
+    let wire = wire as *const u8;
+    unsafe {
+        let length = ptr::read_unaligned(wire.add(4) as *const u32);
+        let size = 32 + length as usize * 4;
+        let data = libc::malloc(size);
+        if data.is_null() {
+            return ffi::False;
+        }
+        ptr::copy_nonoverlapping(wire, data as *mut u8, size);
+
+        let cookie = &mut *cookie;
+        cookie.type_ = (*wire & 0x7f) as c_int;
+        cookie.serial = 0;
+        cookie.send_event = (*wire & 0x80 != 0) as c_int;
+        cookie.display = display;
+        cookie.extension = *wire.add(1) as c_int;
+        cookie.evtype = ptr::read_unaligned(wire.add(8) as *const u16) as c_int;
+        cookie.data = data;
+    }
+
+    ffi::True
+}
@@ -1,10 +1,12 @@
 use std::ffi::CString;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{iter, slice};
 
 use x11rb::connection::Connection;
 
+use crate::cursor::{CursorImage, OnlyCursorImageSource};
 use crate::platform_impl::PlatformCustomCursorSource;
 use crate::window::CursorIcon;
 
@@ -125,31 +127,63 @@ impl CustomCursor {
         event_loop: &ActiveEventLoop,
         cursor: PlatformCustomCursorSource,
     ) -> CustomCursor {
-        unsafe {
-            let ximage = (event_loop.xconn.xcursor.XcursorImageCreate)(
-                cursor.0.width as i32,
-                cursor.0.height as i32,
-            );
-            if ximage.is_null() {
-                panic!("failed to allocate cursor image");
-            }
-            (*ximage).xhot = cursor.0.hotspot_x as u32;
-            (*ximage).yhot = cursor.0.hotspot_y as u32;
-            (*ximage).delay = 0;
-
-            let dst = slice::from_raw_parts_mut((*ximage).pixels, cursor.0.rgba.len() / 4);
-            for (dst, chunk) in dst.iter_mut().zip(cursor.0.rgba.chunks_exact(4)) {
-                *dst = (chunk[0] as u32) << 16
-                    | (chunk[1] as u32) << 8
-                    | (chunk[2] as u32)
-                    | (chunk[3] as u32) << 24;
+        let xcursor = &event_loop.xconn.xcursor;
+        let cursor = unsafe {
+            match cursor {
+                OnlyCursorImageSource::Image(image) => {
+                    let ximage = create_xcursor_image(xcursor, &image, Duration::ZERO);
+                    let cursor = (xcursor.XcursorImageLoadCursor)(event_loop.xconn.display, ximage);
+                    (xcursor.XcursorImageDestroy)(ximage);
+                    cursor
+                },
+                OnlyCursorImageSource::Animation(frames) => {
+                    let ximages = (xcursor.XcursorImagesCreate)(frames.len() as c_int);
+                    if ximages.is_null() {
+                        panic!("failed to allocate cursor images");
+                    }
+                    for frame in &frames {
+                        let ximage = create_xcursor_image(xcursor, &frame.image, frame.duration);
+                        *(*ximages).images.add((*ximages).nimage as usize) = ximage;
+                        (*ximages).nimage += 1;
+                    }
+                    let cursor =
+                        (xcursor.XcursorImagesLoadCursor)(event_loop.xconn.display, ximages);
+                    // Also destroys the images.
+                    (xcursor.XcursorImagesDestroy)(ximages);
+                    cursor
+                },
             }
+        };
+        Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) }
+    }
+}
 
-            let cursor =
-                (event_loop.xconn.xcursor.XcursorImageLoadCursor)(event_loop.xconn.display, ximage);
-            (event_loop.xconn.xcursor.XcursorImageDestroy)(ximage);
-            Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) }
+/// Creates an `XcursorImage` that is shown for `delay` when it is part of an animation.
+///
+/// The image has to be freed with `XcursorImageDestroy`, unless it is owned by `XcursorImages`.
+unsafe fn create_xcursor_image(
+    xcursor: &ffi::Xcursor,
+    image: &CursorImage,
+    delay: Duration,
+) -> *mut ffi::XcursorImage {
+    unsafe {
+        let ximage = (xcursor.XcursorImageCreate)(image.width as i32, image.height as i32);
+        if ximage.is_null() {
+            panic!("failed to allocate cursor image");
         }
+        (*ximage).xhot = image.hotspot_x as u32;
+        (*ximage).yhot = image.hotspot_y as u32;
+        (*ximage).delay = delay.as_millis().try_into().unwrap_or(u32::MAX);
+
+        let dst = slice::from_raw_parts_mut((*ximage).pixels, image.rgba.len() / 4);
+        for (dst, chunk) in dst.iter_mut().zip(image.rgba.chunks_exact(4)) {
+            *dst = (chunk[0] as u32) << 16
+                | (chunk[1] as u32) << 8
+                | (chunk[2] as u32)
+                | (chunk[3] as u32) << 24;
+        }
+
+        ximage
     }
 }
 
@@ -21,6 +21,10 @@ pub fn hint_is_supported(hint: xproto::Atom) -> bool {
     (*SUPPORTED_HINTS.lock().unwrap()).contains(&hint)
 }
 
+pub fn wm_is_running() -> bool {
+    WM_NAME.lock().unwrap().is_some()
+}
+
 pub fn wm_name_is_one_of(names: &[&str]) -> bool {
     if let Some(ref name) = *WM_NAME.lock().unwrap() {
         names.contains(&name.as_str())
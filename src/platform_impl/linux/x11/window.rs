@@ -7,7 +7,9 @@ use std::{cmp, env};
 
 use tracing::{debug, info, warn};
 use x11rb::connection::Connection;
-use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
+use x11rb::properties::{
+    AspectRatio as HintAspectRatio, WmHints, WmSizeHints, WmSizeHintsSpecification,
+};
 use x11rb::protocol::shape::SK;
 use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
 use x11rb::protocol::xproto::{self, ConnectionExt as _, Rectangle};
@@ -15,10 +17,11 @@ use x11rb::protocol::{randr, xinput};
 
 use crate::cursor::{Cursor, CustomCursor as RootCustomCursor};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
+use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError, PlatformError};
 use crate::event::{Event, InnerSizeWriter, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
 use crate::platform::x11::WindowType;
+use crate::platform_impl::common::launcher_entry::LauncherEntry;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::{
     xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender, X11Error,
@@ -27,11 +30,15 @@ use crate::platform_impl::{
     Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformCustomCursor,
     PlatformIcon, VideoModeHandle as PlatformVideoModeHandle,
 };
+use crate::utils::{ClickCounter, RedrawDamage};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-    WindowButtons, WindowLevel,
+    AspectRatio, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose, ProgressState, Rect,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
 };
 
+use super::barrier::PointerBarriers;
+use super::dnd::DragSource;
+use super::present::PresentationState;
 use super::util::{self, SelectedCursor};
 use super::{
     ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, WindowId, XConnection,
@@ -46,6 +53,7 @@ pub struct SharedState {
     pub inner_position_rel_parent: Option<(i32, i32)>,
     pub is_resizable: bool,
     pub is_decorated: bool,
+    pub enabled_buttons: WindowButtons,
     pub last_monitor: X11MonitorHandle,
     pub dpi_adjusted: Option<(u32, u32)>,
     pub(crate) fullscreen: Option<Fullscreen>,
@@ -64,6 +72,14 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub opacity: f32,
+    // The drag started with `start_drag_and_drop`, until it ends.
+    pub drag_source: Option<DragSource>,
+    pub presentation: PresentationState,
+    pub click_counter: ClickCounter,
+    pub redraw_damage: RedrawDamage,
+    // The barriers confining the cursor, while it's confined and the window is focused.
+    pub pointer_barriers: PointerBarriers,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -85,6 +101,7 @@ impl SharedState {
 
             is_resizable: window_attributes.resizable,
             is_decorated: window_attributes.decorations,
+            enabled_buttons: window_attributes.enabled_buttons,
             cursor_pos: None,
             size: None,
             position: None,
@@ -102,6 +119,12 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            opacity: 1.0,
+            drag_source: None,
+            presentation: PresentationState::default(),
+            click_counter: ClickCounter::default(),
+            redraw_damage: RedrawDamage::default(),
+            pointer_barriers: PointerBarriers::default(),
         })
     }
 }
@@ -125,6 +148,7 @@ pub struct UnownedWindow {
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<super::ActivationToken>,
+    launcher_entry: LauncherEntry,
 }
 
 macro_rules! leap {
@@ -346,6 +370,7 @@ impl UnownedWindow {
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            launcher_entry: LauncherEntry::new(window_attrs.platform_specific.name.as_ref()),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -353,6 +378,9 @@ impl UnownedWindow {
         // act on the wrong title state.
         leap!(window.set_title_inner(&window_attrs.title)).ignore_error();
         leap!(window.set_decorations_inner(window_attrs.decorations)).ignore_error();
+        if window_attrs.enabled_buttons != WindowButtons::all() {
+            leap!(window.set_enabled_buttons_inner(window_attrs.enabled_buttons)).ignore_error();
+        }
 
         if let Some(theme) = window_attrs.preferred_theme {
             leap!(window.set_theme_inner(Some(theme))).ignore_error();
@@ -454,7 +482,7 @@ impl UnownedWindow {
                     .x11
                     .base_size
                     .map(|size| cast_size_to_hint(size, scale_factor)),
-                aspect: None,
+                aspect: window_attrs.aspect_ratio.map(cast_aspect_ratio_to_hint),
                 win_gravity: None,
             };
             leap!(leap!(normal_hints.set(
@@ -464,6 +492,12 @@ impl UnownedWindow {
             ))
             .check());
 
+            // Set the transient parent.
+            if let Some(parent) = window_attrs.transient_parent {
+                let parent = u64::from(parent.0) as xproto::Window;
+                leap!(window.set_transient_parent_inner(Some(parent))).ignore_error();
+            }
+
             // Set window icons
             if let Some(icon) = window_attrs.window_icon {
                 leap!(window.set_icon_inner(icon.inner)).ignore_error();
@@ -523,7 +557,8 @@ impl UnownedWindow {
 
             // Try to create input context for the window.
             if let Some(ime) = event_loop.ime.as_ref() {
-                let result = ime.borrow_mut().create_context(window.xwindow as ffi::Window, false);
+                let result =
+                    ime.borrow_mut().create_context(window.xwindow as ffi::Window, false, None);
                 leap!(result);
             }
 
@@ -754,7 +789,7 @@ impl UnownedWindow {
                     Fullscreen::Borderless(None) => {
                         (None, self.shared_state_lock().last_monitor.clone())
                     },
-                    #[cfg(wayland_platform)]
+                    #[cfg(any(wayland_platform, headless_platform))]
                     _ => unreachable!(),
                 };
 
@@ -1029,6 +1064,36 @@ impl UnownedWindow {
     #[inline]
     pub fn set_blur(&self, _blur: bool) {}
 
+    fn set_opacity_inner(&self, opacity: f32) -> Result<VoidCookie<'_>, X11Error> {
+        let opacity_atom = self.xconn.atoms()[_NET_WM_WINDOW_OPACITY];
+
+        if opacity >= 1.0 {
+            // Compositors treat windows without the property as opaque.
+            Ok(self.xconn.xcb_connection().delete_property(self.xwindow, opacity_atom)?)
+        } else {
+            let opacity = (opacity as f64 * util::Cardinal::MAX as f64).round() as util::Cardinal;
+            self.xconn.change_property(
+                self.xwindow,
+                opacity_atom,
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[opacity],
+            )
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        self.shared_state_lock().opacity = opacity;
+        self.set_opacity_inner(opacity).expect_then_ignore_error("Failed to set window opacity");
+        self.xconn.flush_requests().expect("Failed to set window opacity");
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.shared_state_lock().opacity
+    }
+
     fn set_decorations_inner(&self, decorations: bool) -> Result<VoidCookie<'_>, X11Error> {
         self.shared_state_lock().is_decorated = decorations;
         let mut hints = self.xconn.get_motif_hints(self.xwindow);
@@ -1059,6 +1124,24 @@ impl UnownedWindow {
         self.xconn.set_motif_hints(self.xwindow, &hints)
     }
 
+    fn set_enabled_buttons_inner(
+        &self,
+        buttons: WindowButtons,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let maximizable = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.enabled_buttons = buttons;
+            shared_state_lock.is_resizable && buttons.contains(WindowButtons::MAXIMIZE)
+        };
+        let mut hints = self.xconn.get_motif_hints(self.xwindow);
+
+        hints.set_minimizable(buttons.contains(WindowButtons::MINIMIZE));
+        hints.set_maximizable(maximizable);
+        hints.set_closable(buttons.contains(WindowButtons::CLOSE));
+
+        self.xconn.set_motif_hints(self.xwindow, &hints)
+    }
+
     fn toggle_atom(&self, atom_name: AtomName, enable: bool) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let atom = atoms[atom_name];
@@ -1077,6 +1160,57 @@ impl UnownedWindow {
         self.xconn.flush_requests().expect("Failed to set window-level state");
     }
 
+    fn set_transient_parent_inner(
+        &self,
+        parent: Option<xproto::Window>,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let transient_for_atom = xproto::Atom::from(xproto::AtomEnum::WM_TRANSIENT_FOR);
+        match parent {
+            Some(parent) => self.xconn.change_property(
+                self.xwindow,
+                transient_for_atom,
+                xproto::Atom::from(xproto::AtomEnum::WINDOW),
+                xproto::PropMode::REPLACE,
+                &[parent],
+            ),
+            None => {
+                Ok(self.xconn.xcb_connection().delete_property(self.xwindow, transient_for_atom)?)
+            },
+        }
+    }
+
+    #[inline]
+    pub fn set_transient_parent(
+        &self,
+        parent: Option<&UnownedWindow>,
+    ) -> Result<(), ExternalError> {
+        let parent = parent.map(|parent| parent.xwindow);
+
+        // Walk the transient parents of the new parent to reject cycles.
+        let mut ancestor = parent;
+        while let Some(window) = ancestor {
+            if window == self.xwindow {
+                return Err(ExternalError::Ignored);
+            }
+            ancestor = self
+                .xconn
+                .get_property::<xproto::Window>(
+                    window,
+                    xproto::Atom::from(xproto::AtomEnum::WM_TRANSIENT_FOR),
+                    xproto::Atom::from(xproto::AtomEnum::WINDOW),
+                )
+                .ok()
+                .and_then(|windows| windows.first().copied());
+        }
+
+        self.set_transient_parent_inner(parent)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?
+            .ignore_error();
+        self.xconn.flush_requests().map_err(|err| {
+            ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
+        })
+    }
+
     fn set_icon_inner(&self, icon: PlatformIcon) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let icon_atom = atoms[_NET_WM_ICON];
@@ -1368,6 +1502,14 @@ impl UnownedWindow {
             .expect("Failed to call `XSetWMNormalHints`");
     }
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        self.update_normal_hints(|hints| {
+            hints.aspect = aspect_ratio.map(cast_aspect_ratio_to_hint)
+        })
+        .expect("Failed to call `XSetWMNormalHints`");
+    }
+
     pub(crate) fn adjust_for_dpi(
         &self,
         old_scale_factor: f64,
@@ -1414,9 +1556,13 @@ impl UnownedWindow {
             let window_size = Some(Size::from(self.inner_size()));
             (window_size, window_size)
         };
-        self.shared_state_lock().is_resizable = resizable;
+        let maximizable = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.is_resizable = resizable;
+            resizable && shared_state_lock.enabled_buttons.contains(WindowButtons::MAXIMIZE)
+        };
 
-        self.set_maximizable_inner(resizable)
+        self.set_maximizable_inner(maximizable)
             .expect_then_ignore_error("Failed to call `XSetWMNormalHints`");
 
         let scale_factor = self.scale_factor();
@@ -1435,11 +1581,26 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {}
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.set_enabled_buttons_inner(buttons)
+            .expect_then_ignore_error("Failed to set enabled buttons");
+        self.xconn.flush_requests().expect("Failed to set enabled buttons");
+    }
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        self.shared_state_lock().enabled_buttons
+    }
+
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        // The buttons are disabled through the Motif hints, which are enforced by the window
+        // manager.
+        if util::wm_is_running() {
+            WindowButtons::all()
+        } else {
+            WindowButtons::empty()
+        }
     }
 
     #[allow(dead_code)]
@@ -1481,6 +1642,10 @@ impl UnownedWindow {
             Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::Wayland(_) }) => {
                 tracing::error!("passed a Wayland cursor to X11 backend")
             },
+            #[cfg(headless_platform)]
+            Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::Headless(_) }) => {
+                tracing::error!("passed a headless cursor to X11 backend")
+            },
         }
     }
 
@@ -1498,10 +1663,14 @@ impl UnownedWindow {
             .ungrab_pointer(x11rb::CURRENT_TIME)
             .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
 
+        let barriers = self.xconn.has_pointer_barriers();
         let result = match mode {
             CursorGrabMode::None => self.xconn.flush_requests().map_err(|err| {
                 ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
             }),
+            // The barriers are placed by `update_pointer_barriers` below.
+            CursorGrabMode::Confined if barriers => Ok(()),
+            // Fall back to an active grab on servers without XFixes 5.0.
             CursorGrabMode::Confined => {
                 let result = {
                     self.xconn
@@ -1548,7 +1717,14 @@ impl UnownedWindow {
                     },
                     _ => unreachable!(),
                 }
-                .map_err(|err| ExternalError::Os(os_error!(OsError::Misc(err))))
+                .map_err(|err| {
+                    let status = PlatformError::new(
+                        "X11 GrabStatus",
+                        Some(u8::from(result.status).into()),
+                        err,
+                    );
+                    ExternalError::Os(os_error!(OsError::Misc(err)).with_platform_error(status))
+                })
             },
             CursorGrabMode::Locked => {
                 return Err(ExternalError::NotSupported(NotSupportedError::new()));
@@ -1557,11 +1733,39 @@ impl UnownedWindow {
 
         if result.is_ok() {
             *grabbed_lock = mode;
+            self.set_pointer_barriers(barriers && mode == CursorGrabMode::Confined)
+                .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
         }
 
         result
     }
 
+    /// Place the pointer barriers around the window when the cursor is confined with them and
+    /// the window is focused, and destroy them otherwise.
+    ///
+    /// This must be called when the window is moved, resized, focused or unfocused.
+    pub(crate) fn update_pointer_barriers(&self) {
+        let confined = self.xconn.has_pointer_barriers()
+            && *self.cursor_grabbed_mode.lock().unwrap() == CursorGrabMode::Confined;
+        if let Err(err) = self.set_pointer_barriers(confined) {
+            warn!("Failed to update the pointer barriers: {err}");
+        }
+    }
+
+    fn set_pointer_barriers(&self, confined: bool) -> Result<(), X11Error> {
+        if !confined || !self.shared_state_lock().has_focus {
+            return self.shared_state_lock().pointer_barriers.release(&self.xconn);
+        }
+
+        let (x, y) = self.inner_position_physical();
+        let (width, height) = self.inner_size_physical();
+        self.shared_state_lock().pointer_barriers.confine(
+            &self.xconn,
+            self.xwindow,
+            (x, y, width, height),
+        )
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         #[allow(clippy::mutex_atomic)]
@@ -1655,6 +1859,40 @@ impl UnownedWindow {
         })
     }
 
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        // Buttons 1 to 3, 8 and 9, the others being used for scrolling.
+        const DRAG_BUTTONS: u32 = 0b11_0000_1110;
+
+        let pointer = self
+            .xconn
+            .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        if pointer.buttons.first().map_or(true, |buttons| buttons & DRAG_BUTTONS == 0) {
+            return Err(ExternalError::Ignored);
+        }
+
+        let mut shared_state = self.shared_state_lock();
+        if shared_state.drag_source.as_ref().is_some_and(|drag| !drag.is_dropped()) {
+            return Err(ExternalError::Ignored);
+        }
+
+        // The target gets the data by converting the `XdndSelection`.
+        let atoms = self.xconn.atoms();
+        self.xconn
+            .xcb_connection()
+            .set_selection_owner(self.xwindow, atoms[XdndSelection], self.xconn.timestamp())
+            .map_err(|err| {
+                ExternalError::Os(os_error!(OsError::XError(X11Error::from(err).into())))
+            })?
+            .ignore_error();
+        self.xconn.flush_requests().map_err(|err| {
+            ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
+        })?;
+
+        shared_state.drag_source = Some(DragSource::new(&self.xconn, payload));
+        Ok(())
+    }
+
     /// Initiates a drag operation while the left mouse button is pressed.
     fn drag_initiate(&self, action: isize) -> Result<(), ExternalError> {
         let pointer = self
@@ -1677,6 +1915,10 @@ impl UnownedWindow {
                 ExternalError::Os(os_error!(OsError::XError(X11Error::from(err).into())))
             })?
             .ignore_error();
+        self.shared_state_lock()
+            .pointer_barriers
+            .release(&self.xconn)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
         self.xconn.flush_requests().map_err(|err| {
             ExternalError::Os(os_error!(OsError::XError(X11Error::Xlib(err).into())))
         })?;
@@ -1729,6 +1971,11 @@ impl UnownedWindow {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        Some(ImeCapabilities::CURSOR_AREA | ImeCapabilities::PREEDIT)
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         let atoms = self.xconn.atoms();
@@ -1783,6 +2030,21 @@ impl UnownedWindow {
             .expect_then_ignore_error("Failed to set WM hints");
     }
 
+    #[inline]
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        self.launcher_entry.set_progress(state).map_err(|err| {
+            tracing::warn!("Failed to update the launcher entry: {err}");
+            NotSupportedError::new()
+        })
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        if let Err(err) = self.launcher_entry.set_count(count) {
+            tracing::warn!("Failed to update the launcher entry: {err}");
+        }
+    }
+
     #[inline]
     pub(crate) fn generate_activation_token(&self) -> Result<String, X11Error> {
         // Get the title from the WM_NAME property.
@@ -1816,12 +2078,36 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_redraw(&self) {
+        self.shared_state_lock().redraw_damage.add_full();
+        self.redraw_sender.send(WindowId(self.xwindow as _));
+    }
+
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let size = self.inner_size();
+        self.shared_state_lock().redraw_damage.add(rects, size);
         self.redraw_sender.send(WindowId(self.xwindow as _));
     }
 
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let size = self.inner_size();
+        self.shared_state_lock().redraw_damage.current(size).map(<[_]>::to_vec)
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         // TODO timer
+        self.shared_state_lock().presentation.pre_present_notify();
+    }
+
+    #[inline]
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        if let Err(err) =
+            self.shared_state_lock().presentation.set_enabled(&self.xconn, self.xwindow, enabled)
+        {
+            warn!("Failed to set the presentation feedback: {err}");
+        }
     }
 
     #[cfg(feature = "rwh_04")]
@@ -1888,7 +2174,20 @@ impl UnownedWindow {
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        // The title is read back from the server, since other clients may change it.
+        let atoms = self.xconn.atoms();
+        self.xconn
+            .get_property::<u8>(self.xwindow, atoms[_NET_WM_NAME], atoms[UTF8_STRING])
+            .or_else(|_| {
+                // Fall back to the legacy property, which we also fill with UTF-8.
+                self.xconn.get_property::<u8>(
+                    self.xwindow,
+                    xproto::Atom::from(xproto::AtomEnum::WM_NAME),
+                    xproto::Atom::from(xproto::AtomEnum::STRING),
+                )
+            })
+            .map(|title| String::from_utf8_lossy(&title).into_owned())
+            .unwrap_or_default()
     }
 }
 
@@ -1903,6 +2202,15 @@ fn cast_physical_size_to_hint(size: PhysicalSize<u32>) -> (i32, i32) {
     (cast_dimension_to_hint(width), cast_dimension_to_hint(height))
 }
 
+/// Use the above strategy to cast an aspect ratio into the minimum and maximum hinted ratios.
+fn cast_aspect_ratio_to_hint(aspect_ratio: AspectRatio) -> (HintAspectRatio, HintAspectRatio) {
+    let ratio = HintAspectRatio::new(
+        cast_dimension_to_hint(aspect_ratio.width()),
+        cast_dimension_to_hint(aspect_ratio.height()),
+    );
+    (ratio, ratio)
+}
+
 /// Use the above strategy to cast a size into a hinted size.
 fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
     match size {
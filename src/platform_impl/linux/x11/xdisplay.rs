@@ -11,6 +11,7 @@ use super::ffi;
 use super::monitor::MonitorHandle;
 use x11rb::connection::Connection;
 use x11rb::protocol::randr::ConnectionExt as _;
+use x11rb::protocol::xfixes::ConnectionExt as _;
 use x11rb::protocol::xproto::{self, ConnectionExt};
 use x11rb::resource_manager;
 use x11rb::xcb_ffi::XCBConnection;
@@ -52,6 +53,9 @@ pub struct XConnection {
     /// RandR version.
     randr_version: (u32, u32),
 
+    /// XFixes version, if the extension is available.
+    xfixes_version: Option<(u32, u32)>,
+
     /// Atom for the XSettings screen.
     xsettings_screen: Option<xproto::Atom>,
 
@@ -113,6 +117,13 @@ impl XConnection {
             .reply()
             .expect("failed to query XRandR version");
 
+        // Load the XFixes version, which also has to be queried before using the extension.
+        let xfixes_version = xcb
+            .xfixes_query_version(5, 0)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| (reply.major_version, reply.minor_version));
+
         let xsettings_screen = Self::new_xsettings_screen(&xcb, default_screen);
         if xsettings_screen.is_none() {
             tracing::warn!("error setting XSETTINGS; Xft options won't reload automatically")
@@ -138,6 +149,7 @@ impl XConnection {
             database: RwLock::new(database),
             cursor_cache: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
+            xfixes_version,
             xsettings_screen,
         })
     }
@@ -184,6 +196,12 @@ impl XConnection {
         self.randr_version
     }
 
+    /// Whether the XFixes pointer barriers are supported, which needs version 5.0.
+    #[inline]
+    pub fn has_pointer_barriers(&self) -> bool {
+        self.xfixes_version.is_some_and(|(major, _)| major >= 5)
+    }
+
     /// Get the underlying XCB connection.
     #[inline]
     pub fn xcb_connection(&self) -> &XCBConnection {
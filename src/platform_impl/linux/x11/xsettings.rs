@@ -6,16 +6,20 @@
 
 use std::iter;
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use x11rb::protocol::xproto::{self, ConnectionExt};
 
 use super::atoms::*;
 use super::XConnection;
+use crate::utils::ClickThresholds;
 
 type Result<T> = core::result::Result<T, ParserError>;
 
 const DPI_NAME: &[u8] = b"Xft/DPI";
 const DPI_MULTIPLIER: f64 = 1024.0;
+const DOUBLE_CLICK_TIME_NAME: &[u8] = b"Net/DoubleClickTime";
+const DOUBLE_CLICK_DISTANCE_NAME: &[u8] = b"Net/DoubleClickDistance";
 const LITTLE_ENDIAN: u8 = b'l';
 const BIG_ENDIAN: u8 = b'B';
 
@@ -25,6 +29,35 @@ impl XConnection {
         &self,
         xsettings_screen: xproto::Atom,
     ) -> core::result::Result<Option<f64>, super::X11Error> {
+        let dpi = self.xsettings_integer(xsettings_screen, DPI_NAME)?;
+        Ok(dpi.map(|dpi| dpi as f64 / DPI_MULTIPLIER))
+    }
+
+    /// Get the double-click time and distance from XSettings, with the defaults for the missing
+    /// ones.
+    pub(crate) fn xsettings_click_thresholds(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<ClickThresholds, super::X11Error> {
+        let mut thresholds = ClickThresholds::default();
+        if let Some(time) = self.xsettings_integer(xsettings_screen, DOUBLE_CLICK_TIME_NAME)? {
+            thresholds.interval = Duration::from_millis(time.max(0) as u64);
+        }
+        if let Some(distance) =
+            self.xsettings_integer(xsettings_screen, DOUBLE_CLICK_DISTANCE_NAME)?
+        {
+            thresholds.distance = distance.max(0) as f64;
+        }
+
+        Ok(thresholds)
+    }
+
+    /// Get an integer setting from XSettings.
+    fn xsettings_integer(
+        &self,
+        xsettings_screen: xproto::Atom,
+        name: &[u8],
+    ) -> core::result::Result<Option<i32>, super::X11Error> {
         let atoms = self.atoms();
 
         // Get the current owner of the screen's settings.
@@ -35,23 +68,14 @@ impl XConnection {
             self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])?;
 
         // Parse the property.
-        let dpi_setting = read_settings(&data)?
-            .find(|res| res.as_ref().map_or(true, |s| s.name == DPI_NAME))
+        let setting = read_settings(&data)?
+            .find(|res| res.as_ref().map_or(true, |s| s.name == name))
             .transpose()?;
-        if let Some(dpi_setting) = dpi_setting {
-            let base_dpi = match dpi_setting.data {
-                SettingData::Integer(dpi) => dpi as f64,
-                SettingData::String(_) => {
-                    return Err(ParserError::BadType(SettingType::String).into())
-                },
-                SettingData::Color(_) => {
-                    return Err(ParserError::BadType(SettingType::Color).into())
-                },
-            };
-
-            Ok(Some(base_dpi / DPI_MULTIPLIER))
-        } else {
-            Ok(None)
+        match setting.map(|setting| setting.data) {
+            Some(SettingData::Integer(value)) => Ok(Some(value)),
+            Some(SettingData::String(_)) => Err(ParserError::BadType(SettingType::String).into()),
+            Some(SettingData::Color(_)) => Err(ParserError::BadType(SettingType::Color).into()),
+            None => Ok(None),
         }
     }
 }
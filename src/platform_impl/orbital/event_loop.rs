@@ -13,13 +13,15 @@ use orbclient::{
 use smol_str::SmolStr;
 
 use crate::application::ApplicationHandler;
+use crate::dpi::PhysicalPosition;
 use crate::error::EventLoopError;
 use crate::event::{self, Ime, Modifiers, StartCause};
-use crate::event_loop::{self, ControlFlow, DeviceEvents};
+use crate::event_loop::{self, ControlFlow, DeviceEventSubscription, MotionEventPolicy};
 use crate::keyboard::{
-    Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
-    PhysicalKey,
+    self, Key, KeyCode, KeyLocation, LockKeys, ModifiersKeys, ModifiersState, NamedKey, NativeKey,
+    NativeKeyCode, PhysicalKey,
 };
+use crate::utils::{ClickCounter, ClickThresholds, RedrawDamage};
 use crate::window::{
     CustomCursor as RootCustomCursor, CustomCursorSource, WindowId as RootWindowId,
 };
@@ -167,7 +169,10 @@ bitflags! {
 struct EventState {
     keyboard: KeyboardModifierState,
     mouse: MouseButtonState,
+    cursor_position: PhysicalPosition<f64>,
+    click_counter: ClickCounter,
     resize_opt: Option<(u32, u32)>,
+    redraw_damage: Arc<Mutex<RedrawDamage>>,
 }
 
 impl EventState {
@@ -268,7 +273,8 @@ impl EventState {
         pressed_mods
             .set(ModifiersKeys::RSUPER, self.keyboard.contains(KeyboardModifierState::RSUPER));
 
-        Modifiers { state, pressed_mods }
+        // Orbital doesn't report the lock keys.
+        Modifiers { state, pressed_mods, lock_keys: LockKeys::empty() }
     }
 }
 
@@ -318,6 +324,7 @@ impl EventLoop {
                     wake_socket,
                     user_events_sender,
                 },
+                user_event_queue: Default::default(),
                 _marker: PhantomData,
             },
             user_events_receiver,
@@ -414,6 +421,8 @@ impl EventLoop {
                 );
             },
             EventOption::Mouse(MouseEvent { x, y }) => {
+                event_state.cursor_position = (x, y).into();
+                event_state.click_counter.moved((x, y).into(), ClickThresholds::default());
                 app.window_event(
                     window_target,
                     RootWindowId(window_id),
@@ -432,6 +441,15 @@ impl EventLoop {
             },
             EventOption::Button(ButtonEvent { left, middle, right }) => {
                 while let Some((button, state)) = event_state.mouse(left, middle, right) {
+                    let click_count = match state {
+                        event::ElementState::Pressed => event_state.click_counter.press(
+                            button,
+                            event_state.cursor_position,
+                            Instant::now(),
+                            ClickThresholds::default(),
+                        ),
+                        event::ElementState::Released => event_state.click_counter.release(button),
+                    };
                     app.window_event(
                         window_target,
                         RootWindowId(window_id),
@@ -439,6 +457,7 @@ impl EventLoop {
                             device_id: event::DeviceId(DeviceId),
                             state,
                             button,
+                            click_count,
                         },
                     );
                 }
@@ -451,6 +470,7 @@ impl EventLoop {
                         device_id: event::DeviceId(DeviceId),
                         delta: event::MouseScrollDelta::LineDelta(x as f32, y as f32),
                         phase: event::TouchPhase::Moved,
+                        momentum_phase: None,
                     },
                 );
             },
@@ -511,7 +531,7 @@ impl EventLoop {
             }
 
             // Handle window creates.
-            while let Some(window) = {
+            while let Some((window, redraw_damage)) = {
                 let mut creates = self.window_target.p.creates.lock().unwrap();
                 creates.pop_front()
             } {
@@ -521,7 +541,7 @@ impl EventLoop {
                 let path = window.fpath(&mut buf).expect("failed to read properties");
                 let properties = WindowProperties::new(path);
 
-                self.windows.push((window, EventState::default()));
+                self.windows.push((window, EventState { redraw_damage, ..Default::default() }));
 
                 let window_id = RootWindowId(window_id);
 
@@ -604,6 +624,11 @@ impl EventLoop {
                 let mut redraws = self.window_target.p.redraws.lock().unwrap();
                 redraws.pop_front()
             } {
+                if let Some((_, event_state)) =
+                    self.windows.iter().find(|(window, _)| window.fd as u64 == window_id.fd)
+                {
+                    event_state.redraw_damage.lock().unwrap().deliver();
+                }
                 app.window_event(
                     &self.window_target,
                     RootWindowId(window_id),
@@ -714,7 +739,7 @@ impl Unpin for EventLoopProxy {}
 pub struct ActiveEventLoop {
     control_flow: Cell<ControlFlow>,
     exit: Cell<bool>,
-    pub(super) creates: Mutex<VecDeque<Arc<RedoxSocket>>>,
+    pub(super) creates: Mutex<VecDeque<(Arc<RedoxSocket>, Arc<Mutex<RedrawDamage>>)>>,
     pub(super) redraws: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) event_socket: Arc<RedoxSocket>,
@@ -746,7 +771,21 @@ impl ActiveEventLoop {
     }
 
     #[inline]
-    pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
+    pub fn listen_device_events(&self, _subscription: DeviceEventSubscription) {}
+
+    pub fn set_motion_event_policy(&self, _policy: MotionEventPolicy) {}
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        MotionEventPolicy::Unfiltered
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        keyboard::layout_independent_key(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        keyboard::find_key_code(key, keyboard::layout_independent_keys())
+    }
 
     #[cfg(feature = "rwh_05")]
     #[inline]
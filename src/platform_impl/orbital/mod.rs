@@ -7,7 +7,9 @@ use std::sync::Arc;
 use smol_str::SmolStr;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::PlatformError;
 use crate::keyboard::Key;
+use crate::monitor::ColorProperties;
 
 pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle};
 mod event_loop;
@@ -179,6 +181,11 @@ impl Display for OsError {
     }
 }
 
+/// The `errno` of the failed system call.
+pub(crate) fn platform_error(error: &OsError) -> Option<PlatformError> {
+    Some(PlatformError::new("errno", Some(error.0.errno.into()), error.to_string()))
+}
+
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
@@ -209,6 +216,10 @@ impl MonitorHandle {
         None
     }
 
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        None
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let size = self.size().into();
         // FIXME this is not the real refresh rate
@@ -2,8 +2,9 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 use crate::cursor::Cursor;
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::platform_impl::Fullscreen;
+use crate::utils::RedrawDamage;
 use crate::window::ImePurpose;
 use crate::{error, window};
 
@@ -25,6 +26,8 @@ const ORBITAL_FLAG_TRANSPARENT: char = 't';
 pub struct Window {
     window_socket: Arc<RedoxSocket>,
     redraws: Arc<Mutex<VecDeque<WindowId>>>,
+    /// Shared with the event loop, which delivers it with `RedrawRequested`.
+    redraw_damage: Arc<Mutex<RedrawDamage>>,
     destroys: Arc<Mutex<VecDeque<WindowId>>>,
     wake_socket: Arc<TimeSocket>,
 }
@@ -109,11 +112,12 @@ impl Window {
             .unwrap();
 
         let window_socket = Arc::new(window);
+        let redraw_damage = Arc::new(Mutex::new(RedrawDamage::default()));
 
         // Notify event thread that this window was created, it will send some default events.
         {
             let mut creates = el.creates.lock().unwrap();
-            creates.push_back(window_socket.clone());
+            creates.push_back((window_socket.clone(), redraw_damage.clone()));
         }
 
         el.wake_socket.wake().unwrap();
@@ -121,6 +125,7 @@ impl Window {
         Ok(Self {
             window_socket,
             redraws: el.redraws.clone(),
+            redraw_damage,
             destroys: el.destroys.clone(),
             wake_socket: el.wake_socket.clone(),
         })
@@ -180,6 +185,24 @@ impl Window {
 
     #[inline]
     pub fn request_redraw(&self) {
+        self.redraw_damage.lock().unwrap().add_full();
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[window::Rect<u32>]) {
+        let size = self.inner_size();
+        self.redraw_damage.lock().unwrap().add(rects, size);
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<window::Rect<u32>>> {
+        let size = self.inner_size();
+        self.redraw_damage.lock().unwrap().current(size).map(<[_]>::to_vec)
+    }
+
+    fn schedule_redraw(&self) {
         let window_id = self.id();
         let mut redraws = self.redraws.lock().unwrap();
         if !redraws.contains(&window_id) {
@@ -192,6 +215,9 @@ impl Window {
     #[inline]
     pub fn pre_present_notify(&self) {}
 
+    #[inline]
+    pub fn set_presentation_feedback(&self, _enabled: bool) {}
+
     #[inline]
     pub fn reset_dead_keys(&self) {
         // TODO?
@@ -239,6 +265,11 @@ impl Window {
         self.inner_size()
     }
 
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
     #[inline]
     pub fn set_min_inner_size(&self, _: Option<Size>) {}
 
@@ -266,6 +297,14 @@ impl Window {
     #[inline]
     pub fn set_blur(&self, _blur: bool) {}
 
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {}
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        1.0
+    }
+
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_HIDDEN, !visible);
@@ -284,6 +323,9 @@ impl Window {
     #[inline]
     pub fn set_resize_increments(&self, _increments: Option<Size>) {}
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, _aspect_ratio: Option<window::AspectRatio>) {}
+
     #[inline]
     pub fn set_resizable(&self, resizeable: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_RESIZABLE, resizeable);
@@ -358,12 +400,28 @@ impl Window {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<window::ImeCapabilities> {
+        None
+    }
+
     #[inline]
     pub fn focus_window(&self) {}
 
     #[inline]
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
+    #[inline]
+    pub fn set_progress(
+        &self,
+        _state: window::ProgressState,
+    ) -> Result<(), error::NotSupportedError> {
+        Err(error::NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, _count: Option<u64>) {}
+
     #[inline]
     pub fn set_cursor(&self, _: Cursor) {}
 
@@ -396,6 +454,14 @@ impl Window {
         let _ = self.window_socket.write(format!("M,C,{}", if visible { 1 } else { 0 }).as_bytes());
     }
 
+    #[inline]
+    pub fn set_transient_parent(
+        &self,
+        _parent: Option<&Window>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), error::ExternalError> {
         self.window_socket
@@ -425,6 +491,14 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn start_drag_and_drop(
+        &self,
+        _payload: window::DragPayload,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
@@ -481,6 +555,11 @@ impl Window {
         window::WindowButtons::all()
     }
 
+    #[inline]
+    pub fn supported_buttons(&self) -> window::WindowButtons {
+        window::WindowButtons::empty()
+    }
+
     #[inline]
     pub fn theme(&self) -> Option<window::Theme> {
         None
@@ -10,21 +10,24 @@ use std::task::{ready, Context, Poll, Waker};
 use std::time::Duration;
 
 use cursor_icon::CursorIcon;
-use js_sys::{Array, Object};
+use js_sys::{Array, Object, Reflect};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
     Blob, Document, DomException, HtmlCanvasElement, HtmlImageElement, ImageBitmap,
-    ImageBitmapOptions, ImageBitmapRenderingContext, ImageData, PremultiplyAlpha, Url, Window,
+    ImageBitmapOptions, ImageBitmapRenderingContext, ImageData, OffscreenCanvas, PremultiplyAlpha,
+    Url, Window,
 };
 
 use super::backend::Style;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::r#async::{AbortHandle, Abortable, DropAbortHandle, Notified, Notifier};
 use super::ActiveEventLoop;
-use crate::cursor::{BadImage, Cursor, CursorImage, CustomCursor as RootCustomCursor};
+use crate::cursor::{
+    BadImage, Cursor, CursorAnimationFrame, CursorImage, CustomCursor as RootCustomCursor,
+};
 use crate::platform::web::CustomCursorError;
 
 #[derive(Debug)]
@@ -32,6 +35,7 @@ pub(crate) enum CustomCursorSource {
     Image(CursorImage),
     Url { url: String, hotspot_x: u16, hotspot_y: u16 },
     Animation { duration: Duration, cursors: Vec<RootCustomCursor> },
+    Frames(Vec<CursorAnimationFrame>),
 }
 
 impl CustomCursorSource {
@@ -46,6 +50,12 @@ impl CustomCursorSource {
             rgba, width, height, hotspot_x, hotspot_y,
         )?))
     }
+
+    pub fn from_animation(
+        frames: Vec<CursorAnimationFrame>,
+    ) -> Result<CustomCursorSource, BadImage> {
+        Ok(CustomCursorSource::Frames(frames))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -81,15 +91,32 @@ impl CustomCursor {
                 from_url(UrlType::Plain(url), hotspot_x, hotspot_y),
                 false,
             ),
-            CustomCursorSource::Animation { duration, cursors } => Self::build_spawn(
-                event_loop,
-                from_animation(
-                    event_loop.runner.main_thread(),
-                    duration,
-                    cursors.into_iter().map(|cursor| cursor.inner),
-                ),
-                true,
-            ),
+            CustomCursorSource::Animation { duration, cursors } => {
+                // Every cursor is shown for the same amount of time.
+                let frame_duration = duration / cursors.len() as u32;
+                Self::build_spawn(
+                    event_loop,
+                    from_animation(
+                        event_loop.runner.main_thread(),
+                        cursors.into_iter().map(move |cursor| (cursor.inner, frame_duration)),
+                    ),
+                    true,
+                )
+            },
+            CustomCursorSource::Frames(frames) => {
+                let frames: Vec<_> = frames
+                    .into_iter()
+                    .map(|frame| {
+                        let cursor = Self::new(event_loop, CustomCursorSource::Image(frame.image));
+                        (cursor, frame.duration)
+                    })
+                    .collect();
+                Self::build_spawn(
+                    event_loop,
+                    from_animation(event_loop.runner.main_thread(), frames.into_iter()),
+                    true,
+                )
+            },
         }
     }
 
@@ -503,8 +530,8 @@ fn from_rgba(
 ) -> impl Future<Output = Result<Image, CustomCursorError>> {
     // 1. Create an `ImageData` from the RGBA data.
     // 2. Create an `ImageBitmap` from the `ImageData`.
-    // 3. Draw `ImageBitmap` on an `HTMLCanvasElement`.
-    // 4. Create a `Blob` from the `HTMLCanvasElement`.
+    // 3. Draw `ImageBitmap` on an `OffscreenCanvas`, or an `HTMLCanvasElement` if unsupported.
+    // 4. Create a `Blob` from the canvas.
     // 5. Create an object URL from the `Blob`.
     // 6. Decode the image on an `HTMLImageElement` from the URL.
 
@@ -551,58 +578,30 @@ fn from_rgba(
     );
 
     let CursorImage { width, height, hotspot_x, hotspot_y, .. } = *image;
+    let offscreen = offscreen_canvas(window, width, height);
     async move {
         let bitmap: ImageBitmap =
             bitmap.await.expect("found invalid state in `ImageData`").unchecked_into();
 
-        let canvas: HtmlCanvasElement =
-            document.create_element("canvas").expect("invalid tag name").unchecked_into();
-        #[allow(clippy::disallowed_methods)]
-        canvas.set_width(width as u32);
-        #[allow(clippy::disallowed_methods)]
-        canvas.set_height(height as u32);
-
-        // 3. Draw `ImageBitmap` on an `HTMLCanvasElement`.
-        let context: ImageBitmapRenderingContext = canvas
-            .get_context("bitmaprenderer")
-            .expect("unexpected exception in `HTMLCanvasElement.getContext()`")
-            .expect("`bitmaprenderer` context unsupported")
-            .unchecked_into();
-        context.transfer_from_image_bitmap(&bitmap);
-        drop(bitmap);
-        drop(context);
-
-        // 4. Create a `Blob` from the `HTMLCanvasElement`.
-        //
-        // To keep the `Closure` alive until `HTMLCanvasElement.toBlob()` is done,
-        // we do the whole `Waker` strategy. Commonly on `Drop` the callback is aborted,
-        // but it would increase complexity and isn't possible in this case.
-        // Keep in mind that `HTMLCanvasElement.toBlob()` can call the callback immediately.
-        let value = Rc::new(RefCell::new(None));
-        let waker = Rc::new(RefCell::<Option<Waker>>::new(None));
-        let callback = Closure::once({
-            let value = value.clone();
-            let waker = waker.clone();
-            move |blob: Option<Blob>| {
-                *value.borrow_mut() = Some(blob);
-                if let Some(waker) = waker.borrow_mut().take() {
-                    waker.wake();
-                }
-            }
-        });
-        canvas
-            .to_blob(callback.as_ref().unchecked_ref())
-            .expect("failed with `SecurityError` despite only source coming from memory");
-        let blob = future::poll_fn(|cx| {
-            if let Some(blob) = value.borrow_mut().take() {
-                Poll::Ready(blob)
-            } else {
-                *waker.borrow_mut() = Some(cx.waker().clone());
-                Poll::Pending
-            }
-        })
-        .await;
-        drop(canvas);
+        let blob = match offscreen {
+            Some((canvas, context)) => {
+                // 3. Draw `ImageBitmap` on an `OffscreenCanvas`.
+                context.transfer_from_image_bitmap(&bitmap);
+                drop(bitmap);
+                drop(context);
+
+                // 4. Create a `Blob` from the `OffscreenCanvas`.
+                JsFuture::from(
+                    canvas
+                        .convert_to_blob()
+                        .expect("unexpected exception in `OffscreenCanvas.convertToBlob()`"),
+                )
+                .await
+                .ok()
+                .map(Blob::unchecked_from_js)
+            },
+            None => canvas_to_blob(&document, bitmap, width, height).await,
+        };
 
         let Some(blob) = blob else {
             return Err(CustomCursorError::Blob);
@@ -617,6 +616,76 @@ fn from_rgba(
     }
 }
 
+/// Returns [`None`] if `OffscreenCanvas` or its `bitmaprenderer` context isn't supported.
+fn offscreen_canvas(
+    window: &Window,
+    width: u16,
+    height: u16,
+) -> Option<(OffscreenCanvas, ImageBitmapRenderingContext)> {
+    if !Reflect::has(window, &"OffscreenCanvas".into()).unwrap_or(false) {
+        return None;
+    }
+
+    let canvas = OffscreenCanvas::new(width as u32, height as u32).ok()?;
+    let context = canvas.get_context("bitmaprenderer").ok()??.unchecked_into();
+    Some((canvas, context))
+}
+
+async fn canvas_to_blob(
+    document: &Document,
+    bitmap: ImageBitmap,
+    width: u16,
+    height: u16,
+) -> Option<Blob> {
+    let canvas: HtmlCanvasElement =
+        document.create_element("canvas").expect("invalid tag name").unchecked_into();
+    #[allow(clippy::disallowed_methods)]
+    canvas.set_width(width as u32);
+    #[allow(clippy::disallowed_methods)]
+    canvas.set_height(height as u32);
+
+    // 3. Draw `ImageBitmap` on an `HTMLCanvasElement`.
+    let context: ImageBitmapRenderingContext = canvas
+        .get_context("bitmaprenderer")
+        .expect("unexpected exception in `HTMLCanvasElement.getContext()`")
+        .expect("`bitmaprenderer` context unsupported")
+        .unchecked_into();
+    context.transfer_from_image_bitmap(&bitmap);
+    drop(bitmap);
+    drop(context);
+
+    // 4. Create a `Blob` from the `HTMLCanvasElement`.
+    //
+    // To keep the `Closure` alive until `HTMLCanvasElement.toBlob()` is done,
+    // we do the whole `Waker` strategy. Commonly on `Drop` the callback is aborted,
+    // but it would increase complexity and isn't possible in this case.
+    // Keep in mind that `HTMLCanvasElement.toBlob()` can call the callback immediately.
+    let value = Rc::new(RefCell::new(None));
+    let waker = Rc::new(RefCell::<Option<Waker>>::new(None));
+    let callback = Closure::once({
+        let value = value.clone();
+        let waker = waker.clone();
+        move |blob: Option<Blob>| {
+            *value.borrow_mut() = Some(blob);
+            if let Some(waker) = waker.borrow_mut().take() {
+                waker.wake();
+            }
+        }
+    });
+    canvas
+        .to_blob(callback.as_ref().unchecked_ref())
+        .expect("failed with `SecurityError` despite only source coming from memory");
+    future::poll_fn(|cx| {
+        if let Some(blob) = value.borrow_mut().take() {
+            Poll::Ready(blob)
+        } else {
+            *waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    })
+    .await
+}
+
 async fn from_url(
     url: UrlType,
     hotspot_x: u16,
@@ -649,13 +718,12 @@ async fn from_url(
 #[allow(clippy::await_holding_refcell_ref)] // false-positive
 async fn from_animation(
     main_thread: MainThreadMarker,
-    duration: Duration,
-    cursors: impl ExactSizeIterator<Item = CustomCursor>,
+    frames: impl ExactSizeIterator<Item = (CustomCursor, Duration)>,
 ) -> Result<Animation, CustomCursorError> {
-    let keyframes = Array::new();
-    let mut images = Vec::with_capacity(cursors.len());
+    let mut images = Vec::with_capacity(frames.len());
+    let mut styles = Vec::with_capacity(frames.len());
 
-    for cursor in cursors {
+    for (cursor, duration) in frames {
         let state = cursor.state.get(main_thread).borrow();
 
         match state.deref() {
@@ -675,18 +743,34 @@ async fn from_animation(
             _ => unreachable!("found invalid state"),
         };
 
-        let keyframe: Keyframe = Object::new().unchecked_into();
-        keyframe.set_cursor(style);
-        keyframes.push(&keyframe);
+        styles.push((style.clone(), duration));
         drop(state);
 
         images.push(cursor);
     }
 
-    keyframes.push(&keyframes.get(0));
+    // Each keyframe starts at the offset its frame is shown at, and the `step-end` easing holds
+    // the frame until the next keyframe.
+    let total = styles.iter().map(|(_, duration)| duration.as_secs_f64()).sum::<f64>();
+    let keyframes = Array::new();
+    let mut start = 0.;
+
+    for (style, duration) in &styles {
+        let keyframe: Keyframe = Object::new().unchecked_into();
+        keyframe.set_cursor(style);
+        keyframe.set_offset(if total > 0. { start / total } else { 0. });
+        keyframe.set_easing("step-end");
+        keyframes.push(&keyframe);
+        start += duration.as_secs_f64();
+    }
+
+    let keyframe: Keyframe = Object::new().unchecked_into();
+    keyframe.set_cursor(&styles.last().expect("animation without frames").0);
+    keyframe.set_offset(1.);
+    keyframes.push(&keyframe);
 
     let options: KeyframeAnimationOptions = Object::new().unchecked_into();
-    options.set_duration(duration.as_millis() as f64);
+    options.set_duration(total * 1000.);
     options.set_iterations(f64::INFINITY);
 
     Ok(Animation { keyframes, options, _images: images })
@@ -718,6 +802,12 @@ extern "C" {
     #[wasm_bindgen(method, setter, js_name = cursor)]
     fn set_cursor(this: &Keyframe, value: &str);
 
+    #[wasm_bindgen(method, setter, js_name = offset)]
+    fn set_offset(this: &Keyframe, value: f64);
+
+    #[wasm_bindgen(method, setter, js_name = easing)]
+    fn set_easing(this: &Keyframe, value: &str);
+
     #[derive(Debug)]
     #[wasm_bindgen(extends = Object)]
     type KeyframeAnimationOptions;
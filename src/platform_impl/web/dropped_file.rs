@@ -0,0 +1,103 @@
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use js_sys::Uint8Array;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{DomException, File};
+
+use super::main_thread::MainThreadMarker;
+use super::r#async::{Dispatcher, Notified, Notifier};
+use super::OsError;
+use crate::error::ExternalError;
+
+/// A file dropped onto the canvas.
+#[derive(Clone)]
+pub struct DroppedFile {
+    name: String,
+    mime_type: String,
+    size: u64,
+    file: Arc<Dispatcher<File>>,
+}
+
+impl DroppedFile {
+    pub(crate) fn new(main_thread: MainThreadMarker, file: File) -> Self {
+        Self {
+            name: file.name(),
+            mime_type: file.type_(),
+            size: file.size() as u64,
+            file: Arc::new(Dispatcher::new(main_thread, file).unwrap().0),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn file(&self) -> Option<File> {
+        self.file.value().map(|file| file.clone())
+    }
+
+    pub fn read(&self) -> DroppedFileReadFuture {
+        let notifier = Notifier::new();
+        let notified = notifier.notified();
+
+        self.file.dispatch(move |file| {
+            let promise = file.array_buffer();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = JsFuture::from(promise)
+                    .await
+                    .map(|buffer| Uint8Array::new(&buffer).to_vec())
+                    .map_err(|error| {
+                        error
+                            .dyn_into::<DomException>()
+                            .map(|error| error.message())
+                            .unwrap_or_else(|_| "failed to read file".to_owned())
+                    });
+                notifier.notify(result);
+            });
+        });
+
+        DroppedFileReadFuture(notified)
+    }
+}
+
+impl Debug for DroppedFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DroppedFile")
+            .field("name", &self.name)
+            .field("mime_type", &self.mime_type)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DroppedFile {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.file, &other.file)
+    }
+}
+
+#[derive(Debug)]
+pub struct DroppedFileReadFuture(Notified<Result<Vec<u8>, String>>);
+
+impl Future for DroppedFileReadFuture {
+    type Output = Result<Vec<u8>, ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map_err(|message| ExternalError::Os(os_error!(OsError(message))))
+    }
+}
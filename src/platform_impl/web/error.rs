@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::error::PlatformError;
+
 #[derive(Debug)]
 pub struct OsError(pub String);
 
@@ -8,3 +10,8 @@ impl fmt::Display for OsError {
         write!(f, "{}", self.0)
     }
 }
+
+/// Browsers only report errors as messages.
+pub(crate) fn platform_error(_error: &OsError) -> Option<PlatformError> {
+    None
+}
@@ -1,11 +1,14 @@
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::application::ApplicationHandler;
 use crate::error::EventLoopError;
 use crate::event::Event;
-use crate::event_loop::ActiveEventLoop as RootActiveEventLoop;
+use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ContrastPreference};
 use crate::platform::web::{ActiveEventLoopExtWebSys, PollStrategy, WaitUntilStrategy};
 
+use super::monitor::MonitorPermissionFuture;
 use super::{backend, device, window};
 
 mod proxy;
@@ -25,12 +28,20 @@ pub(crate) struct PlatformSpecificEventLoopAttributes {}
 
 impl EventLoop {
     pub(crate) fn new(_: &PlatformSpecificEventLoopAttributes) -> Result<Self, EventLoopError> {
-        let elw = RootActiveEventLoop { p: ActiveEventLoop::new(), _marker: PhantomData };
+        let elw = RootActiveEventLoop {
+            p: ActiveEventLoop::new(),
+            user_event_queue: Default::default(),
+            _marker: PhantomData,
+        };
         Ok(EventLoop { elw })
     }
 
     pub fn run_app<A: ApplicationHandler>(self, app: &mut A) -> ! {
-        let target = RootActiveEventLoop { p: self.elw.p.clone(), _marker: PhantomData };
+        let target = RootActiveEventLoop {
+            p: self.elw.p.clone(),
+            user_event_queue: Arc::clone(&self.elw.user_event_queue),
+            _marker: PhantomData,
+        };
 
         // SAFETY: Don't use `move` to make sure we leak the `event_handler` and `target`.
         let handler: Box<dyn FnMut(Event)> = Box::new(|event| handle_event(app, &target, event));
@@ -53,7 +64,11 @@ impl EventLoop {
     }
 
     pub fn spawn_app<A: ApplicationHandler + 'static>(self, mut app: A) {
-        let target = RootActiveEventLoop { p: self.elw.p.clone(), _marker: PhantomData };
+        let target = RootActiveEventLoop {
+            p: self.elw.p.clone(),
+            user_event_queue: Arc::clone(&self.elw.user_event_queue),
+            _marker: PhantomData,
+        };
 
         self.elw.p.run(Box::new(move |event| handle_event(&mut app, &target, event)), true);
     }
@@ -77,6 +92,34 @@ impl EventLoop {
     pub fn wait_until_strategy(&self) -> WaitUntilStrategy {
         self.elw.wait_until_strategy()
     }
+
+    pub fn set_hidden_poll_interval(&self, interval: Option<Duration>) {
+        self.elw.set_hidden_poll_interval(interval);
+    }
+
+    pub fn hidden_poll_interval(&self) -> Option<Duration> {
+        self.elw.hidden_poll_interval()
+    }
+
+    pub fn set_pinch_zoom_scaling(&self, enabled: bool) {
+        self.elw.set_pinch_zoom_scaling(enabled);
+    }
+
+    pub fn pinch_zoom_scaling(&self) -> bool {
+        self.elw.pinch_zoom_scaling()
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.elw.reduced_motion()
+    }
+
+    pub fn contrast_preference(&self) -> ContrastPreference {
+        self.elw.contrast_preference()
+    }
+
+    pub fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture {
+        self.elw.p.request_detailed_monitor_permission()
+    }
 }
 
 fn handle_event<A: ApplicationHandler>(app: &mut A, target: &RootActiveEventLoop, event: Event) {
@@ -88,8 +131,18 @@ fn handle_event<A: ApplicationHandler>(app: &mut A, target: &RootActiveEventLoop
         Event::Suspended => app.suspended(target),
         Event::Resumed => app.resumed(target),
         Event::CreateSurfaces => app.can_create_surfaces(target),
+        Event::DestroySurfaces => app.destroy_surfaces(target),
         Event::AboutToWait => app.about_to_wait(target),
         Event::LoopExiting => app.exiting(target),
         Event::MemoryWarning => app.memory_warning(target),
+        Event::PowerStateChanged => app.power_state_changed(target),
+        Event::AccessibilitySettingsChanged => app.accessibility_settings_changed(target),
+        Event::KeyboardLayoutChanged => app.keyboard_layout_changed(target),
+        Event::SessionEvent(event) => app.session_event(target, event),
+        Event::MonitorConnected(monitor) => app.monitor_connected(target, monitor),
+        Event::MonitorDisconnected(monitor) => app.monitor_disconnected(target, monitor),
+        Event::MonitorVideoModeChanged(monitor) => app.monitor_video_mode_changed(target, monitor),
+        Event::UrlsOpened(urls) => app.urls_opened(target, urls),
+        Event::ShortcutItemActivated(item) => app.shortcut_item_activated(target, item),
     }
 }
@@ -1,16 +1,20 @@
 use super::super::main_thread::MainThreadMarker;
+use super::super::monitor::{MonitorHandler, MonitorPermissionError, MonitorPermissionFuture};
 use super::super::DeviceId;
 use super::backend;
 use super::state::State;
 use crate::dpi::PhysicalSize;
 use crate::event::{
-    DeviceEvent, DeviceId as RootDeviceId, ElementState, Event, RawKeyEvent, StartCause,
-    WindowEvent,
+    DeviceEvent, DeviceId as RootDeviceId, ElementState, Event, RawKeyEvent, SessionEvent,
+    StartCause, WindowEvent,
 };
-use crate::event_loop::{ControlFlow, DeviceEvents};
+use crate::event_loop::{
+    ControlFlow, DeviceEventKinds, DeviceEventSubscription, DeviceEvents, MotionEventPolicy,
+};
+use crate::monitor::MonitorHandle;
 use crate::platform::web::{PollStrategy, WaitUntilStrategy};
 use crate::platform_impl::platform::backend::EventListenerHandle;
-use crate::platform_impl::platform::r#async::{DispatchRunner, Waker, WakerSpawner};
+use crate::platform_impl::platform::r#async::{DispatchRunner, Notifier, Waker, WakerSpawner};
 use crate::platform_impl::platform::window::Inner;
 use crate::window::WindowId;
 
@@ -22,7 +26,10 @@ use std::ops::Deref;
 use std::rc::{Rc, Weak};
 use wasm_bindgen::prelude::{wasm_bindgen, Closure};
 use wasm_bindgen::JsCast;
-use web_sys::{Document, KeyboardEvent, PageTransitionEvent, PointerEvent, WheelEvent};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Document, DomException, KeyboardEvent, PageTransitionEvent, PointerEvent, WheelEvent,
+};
 use web_time::{Duration, Instant};
 
 pub struct Shared(Rc<Execution>);
@@ -35,6 +42,11 @@ impl Clone for Shared {
     }
 }
 
+/// See [`EventLoopExtWebSys::set_hidden_poll_interval()`].
+///
+/// [`EventLoopExtWebSys::set_hidden_poll_interval()`]: crate::platform::web::EventLoopExtWebSys::set_hidden_poll_interval
+const DEFAULT_HIDDEN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 type OnEventHandle<T> = RefCell<Option<EventListenerHandle<dyn FnMut(T)>>>;
 
 pub struct Execution {
@@ -43,6 +55,7 @@ pub struct Execution {
     control_flow: Cell<ControlFlow>,
     poll_strategy: Cell<PollStrategy>,
     wait_until_strategy: Cell<WaitUntilStrategy>,
+    hidden_poll_interval: Cell<Option<Duration>>,
     exit: Cell<bool>,
     runner: RefCell<RunnerEnum>,
     suspended: Cell<bool>,
@@ -55,8 +68,13 @@ pub struct Execution {
     all_canvases: RefCell<Vec<(WindowId, Weak<RefCell<backend::Canvas>>, DispatchRunner<Inner>)>>,
     redraw_pending: RefCell<HashSet<WindowId>>,
     destroy_pending: RefCell<VecDeque<WindowId>>,
+    /// Windows requested with `create_window_async()`.
+    window_requests: RefCell<VecDeque<Box<dyn FnOnce()>>>,
+    document_title_owner: Cell<Option<WindowId>>,
+    monitor: MonitorHandler,
     page_transition_event_handle: RefCell<Option<backend::PageTransitionEventHandle>>,
-    device_events: Cell<DeviceEvents>,
+    device_events: Cell<DeviceEventSubscription>,
+    motion_event_policy: Cell<MotionEventPolicy>,
     on_mouse_move: OnEventHandle<PointerEvent>,
     on_wheel: OnEventHandle<WheelEvent>,
     on_mouse_press: OnEventHandle<PointerEvent>,
@@ -64,6 +82,9 @@ pub struct Execution {
     on_key_press: OnEventHandle<KeyboardEvent>,
     on_key_release: OnEventHandle<KeyboardEvent>,
     on_visibility_change: OnEventHandle<web_sys::Event>,
+    on_freeze: OnEventHandle<web_sys::Event>,
+    on_resume: OnEventHandle<web_sys::Event>,
+    on_accessibility_change: RefCell<Option<backend::AccessibilityHandle>>,
 }
 
 enum RunnerEnum {
@@ -149,19 +170,24 @@ impl Shared {
                 control_flow: Cell::new(ControlFlow::default()),
                 poll_strategy: Cell::new(PollStrategy::default()),
                 wait_until_strategy: Cell::new(WaitUntilStrategy::default()),
+                hidden_poll_interval: Cell::new(Some(DEFAULT_HIDDEN_POLL_INTERVAL)),
                 exit: Cell::new(false),
                 runner: RefCell::new(RunnerEnum::Pending),
                 suspended: Cell::new(false),
                 event_loop_recreation: Cell::new(false),
                 events: RefCell::new(VecDeque::new()),
+                monitor: MonitorHandler::new(main_thread, window.clone()),
                 window,
                 document,
                 id: RefCell::new(0),
                 all_canvases: RefCell::new(Vec::new()),
                 redraw_pending: RefCell::new(HashSet::new()),
                 destroy_pending: RefCell::new(VecDeque::new()),
+                window_requests: RefCell::new(VecDeque::new()),
+                document_title_owner: Cell::new(None),
                 page_transition_event_handle: RefCell::new(None),
                 device_events: Cell::default(),
+                motion_event_policy: Cell::default(),
                 on_mouse_move: RefCell::new(None),
                 on_wheel: RefCell::new(None),
                 on_mouse_press: RefCell::new(None),
@@ -169,6 +195,9 @@ impl Shared {
                 on_key_press: RefCell::new(None),
                 on_key_release: RefCell::new(None),
                 on_visibility_change: RefCell::new(None),
+                on_freeze: RefCell::new(None),
+                on_resume: RefCell::new(None),
+                on_accessibility_change: RefCell::new(None),
             }
         }))
     }
@@ -198,6 +227,73 @@ impl Shared {
         self.0.destroy_pending.borrow_mut().push_back(id);
     }
 
+    /// `create` is called right before the next `AboutToWait`.
+    pub fn request_window(&self, create: Box<dyn FnOnce()>) {
+        self.0.window_requests.borrow_mut().push_back(create);
+    }
+
+    /// The window whose title is written to `document.title`, if any.
+    pub fn document_title_owner(&self) -> Option<WindowId> {
+        self.0.document_title_owner.get()
+    }
+
+    pub fn set_document_title_owner(&self, id: Option<WindowId>) {
+        self.0.document_title_owner.set(id)
+    }
+
+    pub fn monitor(&self) -> &MonitorHandler {
+        &self.0.monitor
+    }
+
+    pub(crate) fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture {
+        let notifier = Notifier::new();
+        let notified = notifier.notified();
+
+        let Some(promise) = self.0.monitor.request_details() else {
+            notifier.notify(Err(MonitorPermissionError::NotSupported));
+            return MonitorPermissionFuture(notified);
+        };
+
+        let runner = Rc::downgrade(&self.0);
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = JsFuture::from(promise).await;
+            let Some(runner) = runner.upgrade() else {
+                return;
+            };
+
+            match result {
+                Ok(details) => {
+                    let weak = Rc::downgrade(&runner);
+                    runner.monitor.set_details(details, move || {
+                        if let Some(runner) = weak.upgrade() {
+                            let (connected, disconnected) = runner.monitor.update();
+                            Shared(runner).send_events(
+                                disconnected
+                                    .into_iter()
+                                    .map(|inner| {
+                                        Event::MonitorDisconnected(MonitorHandle { inner })
+                                    })
+                                    .chain(connected.into_iter().map(|inner| {
+                                        Event::MonitorConnected(MonitorHandle { inner })
+                                    })),
+                            );
+                        }
+                    });
+                    notifier.notify(Ok(()));
+                },
+                Err(error) => {
+                    let message = error
+                        .dyn_into::<DomException>()
+                        .map(|error| error.message())
+                        .unwrap_or_else(|_| "permission denied".to_owned());
+                    notifier.notify(Err(MonitorPermissionError::Rejected(message)));
+                },
+            }
+        });
+
+        MonitorPermissionFuture(notified)
+    }
+
     // Set the event callback to use for the event loop runner
     // This the event callback is a fairly thin layer over the user-provided callback that closes
     // over a RootActiveEventLoop reference
@@ -239,10 +335,6 @@ impl Shared {
             self.window().clone(),
             "pointermove",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
-                    return;
-                }
-
                 let pointer_type = event.pointer_type();
 
                 if pointer_type != "mouse" {
@@ -253,6 +345,10 @@ impl Shared {
                 let device_id = RootDeviceId(DeviceId(event.pointer_id()));
 
                 if let Some(button) = backend::event::mouse_button(&event) {
+                    if !runner.device_events(DeviceEventKinds::BUTTON, device_id) {
+                        return;
+                    }
+
                     debug_assert_eq!(
                         pointer_type, "mouse",
                         "expect pointer type of a chorded button event to be a mouse"
@@ -272,6 +368,10 @@ impl Shared {
                     return;
                 }
 
+                if !runner.device_events(DeviceEventKinds::MOTION, device_id) {
+                    return;
+                }
+
                 // pointer move event
                 runner.send_events(backend::event::pointer_move_event(event).flat_map(|event| {
                     let delta = delta.delta(&event);
@@ -303,13 +403,14 @@ impl Shared {
             self.window().clone(),
             "wheel",
             Closure::new(move |event: WheelEvent| {
-                if !runner.device_events() {
+                let device_id = RootDeviceId(DeviceId(0));
+                if !runner.device_events(DeviceEventKinds::WHEEL, device_id) {
                     return;
                 }
 
                 if let Some(delta) = backend::event::mouse_scroll_delta(&window, &event) {
                     runner.send_event(Event::DeviceEvent {
-                        device_id: RootDeviceId(DeviceId(0)),
+                        device_id,
                         event: DeviceEvent::MouseWheel { delta },
                     });
                 }
@@ -320,17 +421,18 @@ impl Shared {
             self.window().clone(),
             "pointerdown",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
+                if event.pointer_type() != "mouse" {
                     return;
                 }
 
-                if event.pointer_type() != "mouse" {
+                let device_id = RootDeviceId(DeviceId(event.pointer_id()));
+                if !runner.device_events(DeviceEventKinds::BUTTON, device_id) {
                     return;
                 }
 
                 let button = backend::event::mouse_button(&event).expect("no mouse button pressed");
                 runner.send_event(Event::DeviceEvent {
-                    device_id: RootDeviceId(DeviceId(event.pointer_id())),
+                    device_id,
                     event: DeviceEvent::Button {
                         button: button.to_id(),
                         state: ElementState::Pressed,
@@ -343,17 +445,18 @@ impl Shared {
             self.window().clone(),
             "pointerup",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
+                if event.pointer_type() != "mouse" {
                     return;
                 }
 
-                if event.pointer_type() != "mouse" {
+                let device_id = RootDeviceId(DeviceId(event.pointer_id()));
+                if !runner.device_events(DeviceEventKinds::BUTTON, device_id) {
                     return;
                 }
 
                 let button = backend::event::mouse_button(&event).expect("no mouse button pressed");
                 runner.send_event(Event::DeviceEvent {
-                    device_id: RootDeviceId(DeviceId(event.pointer_id())),
+                    device_id,
                     event: DeviceEvent::Button {
                         button: button.to_id(),
                         state: ElementState::Released,
@@ -366,12 +469,13 @@ impl Shared {
             self.window().clone(),
             "keydown",
             Closure::new(move |event: KeyboardEvent| {
-                if !runner.device_events() {
+                let device_id = RootDeviceId(unsafe { DeviceId::dummy() });
+                if !runner.device_events(DeviceEventKinds::KEY, device_id) {
                     return;
                 }
 
                 runner.send_event(Event::DeviceEvent {
-                    device_id: RootDeviceId(unsafe { DeviceId::dummy() }),
+                    device_id,
                     event: DeviceEvent::Key(RawKeyEvent {
                         physical_key: backend::event::key_code(&event),
                         state: ElementState::Pressed,
@@ -384,12 +488,13 @@ impl Shared {
             self.window().clone(),
             "keyup",
             Closure::new(move |event: KeyboardEvent| {
-                if !runner.device_events() {
+                let device_id = RootDeviceId(unsafe { DeviceId::dummy() });
+                if !runner.device_events(DeviceEventKinds::KEY, device_id) {
                     return;
                 }
 
                 runner.send_event(Event::DeviceEvent {
-                    device_id: RootDeviceId(unsafe { DeviceId::dummy() }),
+                    device_id,
                     event: DeviceEvent::Key(RawKeyEvent {
                         physical_key: backend::event::key_code(&event),
                         state: ElementState::Released,
@@ -403,27 +508,62 @@ impl Shared {
             self.document().clone(),
             "visibilitychange",
             Closure::new(move |_| {
-                if !runner.0.suspended.get() {
-                    for (id, canvas, _) in &*runner.0.all_canvases.borrow() {
-                        if let Some(canvas) = canvas.upgrade() {
-                            let is_visible = backend::is_visible(runner.document());
-                            // only fire if:
-                            // - not visible and intersects
-                            // - not visible and we don't know if it intersects yet
-                            // - visible and intersects
-                            if let (false, Some(true) | None) | (true, Some(true)) =
-                                (is_visible, canvas.borrow().is_intersecting)
-                            {
-                                runner.send_event(Event::WindowEvent {
-                                    window_id: *id,
-                                    event: WindowEvent::Occluded(!is_visible),
-                                });
-                            }
+                if runner.0.suspended.get() {
+                    return;
+                }
+
+                let is_visible = backend::is_visible(runner.document());
+                let mut events = Vec::new();
+
+                for (id, canvas, _) in &*runner.0.all_canvases.borrow() {
+                    if let Some(canvas) = canvas.upgrade() {
+                        let mut canvas = canvas.borrow_mut();
+
+                        // Animation frames requested while hidden fire when becoming visible
+                        // again, replace them with the redraw that is emitted below.
+                        if is_visible && canvas.cancel_animation_frame() {
+                            runner.0.redraw_pending.borrow_mut().insert(*id);
+                        }
+
+                        // only fire if:
+                        // - not visible and intersects
+                        // - not visible and we don't know if it intersects yet
+                        // - visible and intersects
+                        if let (false, Some(true) | None) | (true, Some(true)) =
+                            (is_visible, canvas.is_intersecting)
+                        {
+                            events.push(Event::WindowEvent {
+                                window_id: *id,
+                                event: WindowEvent::Occluded(!is_visible),
+                            });
                         }
                     }
                 }
+
+                // Always run an iteration, even without events, to flush the redraws held back
+                // while hidden and to switch the schedule of `ControlFlow::Poll`.
+                runner.send_events(events);
             }),
         ));
+        // The page lifecycle events are only supported by Chromium, pages are frozen e.g. in
+        // background tabs or when the device is suspended.
+        let runner = self.clone();
+        *self.0.on_freeze.borrow_mut() = Some(EventListenerHandle::new(
+            self.document().clone(),
+            "freeze",
+            Closure::new(move |_| runner.send_event(Event::SessionEvent(SessionEvent::Suspending))),
+        ));
+        let runner = self.clone();
+        *self.0.on_resume.borrow_mut() = Some(EventListenerHandle::new(
+            self.document().clone(),
+            "resume",
+            Closure::new(move |_| runner.send_event(Event::SessionEvent(SessionEvent::Resumed))),
+        ));
+        let runner = self.clone();
+        *self.0.on_accessibility_change.borrow_mut() =
+            Some(backend::AccessibilityHandle::new(self.window(), move || {
+                runner.send_event(Event::AccessibilitySettingsChanged)
+            }));
     }
 
     // Generate a strictly increasing ID
@@ -564,15 +704,34 @@ impl Shared {
     // `run_until_cleared`, somewhere between emitting `NewEvents` and `AboutToWait`.
     fn process_destroy_pending_windows(&self) {
         while let Some(id) = self.0.destroy_pending.borrow_mut().pop_front() {
-            self.0.all_canvases.borrow_mut().retain(|&(item_id, ..)| item_id != id);
-            self.handle_event(Event::WindowEvent {
-                window_id: id,
-                event: crate::event::WindowEvent::Destroyed,
-            });
+            let removed = {
+                let mut all_canvases = self.0.all_canvases.borrow_mut();
+                let len = all_canvases.len();
+                all_canvases.retain(|&(item_id, ..)| item_id != id);
+                all_canvases.len() != len
+            };
+            // `Destroyed` was already emitted if the page was unloaded.
+            if removed {
+                self.handle_event(Event::WindowEvent {
+                    window_id: id,
+                    event: crate::event::WindowEvent::Destroyed,
+                });
+            }
             self.0.redraw_pending.borrow_mut().remove(&id);
         }
     }
 
+    // Create the windows requested with `create_window_async()`. This should only be called from
+    // `run_until_cleared`, right before emitting `AboutToWait`.
+    fn process_window_requests(&self) {
+        loop {
+            let Some(create) = self.0.window_requests.borrow_mut().pop_front() else {
+                break;
+            };
+            create();
+        }
+    }
+
     // Given the set of new events, run the event loop until the main events and redraw events are
     // cleared
     //
@@ -583,18 +742,40 @@ impl Shared {
         }
         self.process_destroy_pending_windows();
 
-        // Collect all of the redraw events to avoid double-locking the RefCell
-        let redraw_events: Vec<WindowId> = self.0.redraw_pending.borrow_mut().drain().collect();
+        // Collect all of the redraw events to avoid double-locking the RefCell. While the page is
+        // hidden they are held back, so only one is emitted per window when it becomes visible.
+        let redraw_events: Vec<WindowId> = if backend::is_visible(self.document()) {
+            self.0.redraw_pending.borrow_mut().drain().collect()
+        } else {
+            Vec::new()
+        };
         for window_id in redraw_events {
+            let canvas =
+                self.0.all_canvases.borrow().iter().find_map(|(id, canvas, _)| {
+                    (*id == window_id).then(|| canvas.upgrade()).flatten()
+                });
+            if let Some(canvas) = canvas {
+                canvas.borrow().redraw_damage.borrow_mut().deliver();
+            }
             self.handle_event(Event::WindowEvent {
                 window_id,
                 event: WindowEvent::RedrawRequested,
             });
         }
 
+        self.process_window_requests();
+
         self.handle_event(Event::AboutToWait);
 
         self.apply_control_flow();
+
+        // Windows requested while handling `AboutToWait` are created in another iteration.
+        if !self.0.window_requests.borrow().is_empty() {
+            let runner = self.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                runner.send_events(iter::empty::<Event>());
+            });
+        }
         // If the event loop is closed, it has been closed this iteration and now the closing
         // event should be emitted
         if self.is_closed() {
@@ -607,6 +788,10 @@ impl Shared {
         self.apply_control_flow();
         // We don't call `handle_loop_destroyed` here because we don't need to
         // perform cleanup when the web browser is going to destroy the page.
+        let all_canvases = std::mem::take(&mut *self.0.all_canvases.borrow_mut());
+        for (id, ..) in all_canvases {
+            self.handle_event(Event::WindowEvent { window_id: id, event: WindowEvent::Destroyed });
+        }
         self.handle_event(Event::LoopExiting);
     }
 
@@ -673,13 +858,22 @@ impl Shared {
             match self.control_flow() {
                 ControlFlow::Poll => {
                     let cloned = self.clone();
-                    State::Poll {
-                        _request: backend::Schedule::new(
-                            self.poll_strategy(),
-                            self.window(),
-                            move || cloned.poll(),
-                        ),
-                    }
+                    let request = match self.0.hidden_poll_interval.get() {
+                        Some(interval) if !backend::is_visible(self.document()) => {
+                            backend::Schedule::new_with_duration(
+                                self.wait_until_strategy(),
+                                self.window(),
+                                move || cloned.poll(),
+                                interval,
+                            )
+                        },
+                        _ => {
+                            backend::Schedule::new(self.poll_strategy(), self.window(), move || {
+                                cloned.poll()
+                            })
+                        },
+                    };
+                    State::Poll { _request: request }
                 },
                 ControlFlow::Wait => State::Wait { start: Instant::now() },
                 ControlFlow::WaitUntil(end) => {
@@ -719,6 +913,9 @@ impl Shared {
         *self.0.on_key_press.borrow_mut() = None;
         *self.0.on_key_release.borrow_mut() = None;
         *self.0.on_visibility_change.borrow_mut() = None;
+        *self.0.on_freeze.borrow_mut() = None;
+        *self.0.on_resume.borrow_mut() = None;
+        *self.0.on_accessibility_change.borrow_mut() = None;
         // Dropping the `Runner` drops the event handler closure, which will in
         // turn drop all `Window`s moved into the closure.
         *self.0.runner.borrow_mut() = RunnerEnum::Destroyed;
@@ -758,12 +955,25 @@ impl Shared {
         }
     }
 
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        self.0.device_events.set(allowed)
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        self.0.device_events.set(subscription)
     }
 
-    fn device_events(&self) -> bool {
-        match self.0.device_events.get() {
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        self.0.motion_event_policy.set(policy)
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.0.motion_event_policy.get()
+    }
+
+    fn device_events(&self, kind: DeviceEventKinds, device_id: RootDeviceId) -> bool {
+        let subscription = self.0.device_events.get();
+        if !subscription.captures(kind, device_id) {
+            return false;
+        }
+
+        match subscription.when() {
             DeviceEvents::Always => true,
             DeviceEvents::WhenFocused => {
                 self.0.all_canvases.borrow().iter().any(|(_, canvas, _)| {
@@ -814,6 +1024,28 @@ impl Shared {
         self.0.wait_until_strategy.get()
     }
 
+    pub(crate) fn set_hidden_poll_interval(&self, interval: Option<Duration>) {
+        self.0.hidden_poll_interval.set(interval)
+    }
+
+    pub(crate) fn hidden_poll_interval(&self) -> Option<Duration> {
+        self.0.hidden_poll_interval.get()
+    }
+
+    pub(crate) fn set_pinch_zoom_scaling(&self, enabled: bool) {
+        if backend::set_pinch_zoom_scaling(enabled) != enabled {
+            for (_, canvas, _) in &*self.0.all_canvases.borrow() {
+                if let Some(canvas) = canvas.upgrade() {
+                    canvas.borrow().notify_pinch_zoom();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn pinch_zoom_scaling(&self) -> bool {
+        backend::pinch_zoom_scaling()
+    }
+
     pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
         self.0.proxy_spawner.waker()
     }
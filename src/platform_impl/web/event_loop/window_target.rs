@@ -3,37 +3,47 @@ use std::clone::Clone;
 use std::collections::vec_deque::IntoIter as VecDequeIter;
 use std::collections::VecDeque;
 use std::iter;
+use std::path::PathBuf;
 use std::rc::{Rc, Weak};
+use std::time::Duration;
 
 use web_sys::Element;
+use web_time::Instant;
 
-use super::super::monitor::MonitorHandle;
+use super::super::monitor::{MonitorHandle, MonitorPermissionFuture};
+use super::super::window::CreateWindowFuture;
 use super::super::KeyEventExtra;
 use super::device::DeviceId;
 use super::runner::{EventWrapper, Execution};
 use super::window::WindowId;
 use super::{backend, runner, EventLoopProxy};
+use crate::dpi::PhysicalPosition;
 use crate::event::{
-    DeviceId as RootDeviceId, ElementState, Event, KeyEvent, Touch, TouchPhase, WindowEvent,
+    DeviceId as RootDeviceId, DragDropEvent, DragDropItem, DragDropKinds, ElementState, Event,
+    KeyEvent, Modifiers, MouseButton, PresentationFeedback, Touch, TouchPhase, WindowEvent,
 };
-use crate::event_loop::{ControlFlow, DeviceEvents};
-use crate::keyboard::ModifiersState;
-use crate::platform::web::{CustomCursorFuture, PollStrategy, WaitUntilStrategy};
+use crate::event_loop::{
+    ContrastPreference, ControlFlow, DeviceEventSubscription, MotionEventPolicy,
+};
+use crate::keyboard::{self, Key, KeyCode, ModifiersState};
+use crate::platform::web::{CustomCursorFuture, PollStrategy, WaitUntilStrategy, WheelDetails};
 use crate::platform_impl::platform::cursor::CustomCursor;
 use crate::platform_impl::platform::r#async::Waker;
+use crate::utils::{ClickCounter, ClickThresholds};
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, WindowId as RootWindowId,
+    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, WindowAttributes,
+    WindowId as RootWindowId,
 };
 
 #[derive(Default)]
-struct ModifiersShared(Rc<Cell<ModifiersState>>);
+struct ModifiersShared(Rc<Cell<Modifiers>>);
 
 impl ModifiersShared {
-    fn set(&self, new: ModifiersState) {
+    fn set(&self, new: Modifiers) {
         self.0.set(new)
     }
 
-    fn get(&self) -> ModifiersState {
+    fn get(&self) -> Modifiers {
         self.0.get()
     }
 }
@@ -44,6 +54,21 @@ impl Clone for ModifiersShared {
     }
 }
 
+fn click_count(
+    click_counter: &RefCell<ClickCounter>,
+    button: MouseButton,
+    state: ElementState,
+    position: PhysicalPosition<f64>,
+) -> u32 {
+    let mut click_counter = click_counter.borrow_mut();
+    match state {
+        ElementState::Pressed => {
+            click_counter.press(button, position, Instant::now(), ClickThresholds::default())
+        },
+        ElementState::Released => click_counter.release(button),
+    }
+}
+
 #[derive(Clone)]
 pub struct ActiveEventLoop {
     pub(crate) runner: runner::Shared,
@@ -76,6 +101,10 @@ impl ActiveEventLoop {
         RootCustomCursor { inner: CustomCursor::new(self, source.inner) }
     }
 
+    pub fn create_window_async(&self, attributes: WindowAttributes) -> CreateWindowFuture {
+        CreateWindowFuture::new(self, attributes)
+    }
+
     pub fn create_custom_cursor_async(&self, source: CustomCursorSource) -> CustomCursorFuture {
         CustomCursorFuture(CustomCursor::new_async(self, source.inner))
     }
@@ -90,15 +119,17 @@ impl ActiveEventLoop {
 
         let runner = self.runner.clone();
         let has_focus = canvas.has_focus.clone();
+        let focus_loss = canvas.focus_loss.clone();
         let modifiers = self.modifiers.clone();
-        canvas.on_blur(move || {
+        canvas.on_blur(move |loss| {
             has_focus.set(false);
+            focus_loss.set(Some(loss));
 
-            let clear_modifiers = (!modifiers.get().is_empty()).then(|| {
-                modifiers.set(ModifiersState::empty());
+            let clear_modifiers = (modifiers.get() != Modifiers::default()).then(|| {
+                modifiers.set(Modifiers::default());
                 Event::WindowEvent {
                     window_id: RootWindowId(id),
-                    event: WindowEvent::ModifiersChanged(ModifiersState::empty().into()),
+                    event: WindowEvent::ModifiersChanged(Modifiers::default()),
                 }
             });
 
@@ -110,8 +141,12 @@ impl ActiveEventLoop {
 
         let runner = self.runner.clone();
         let has_focus = canvas.has_focus.clone();
-        canvas.on_focus(move || {
+        let focus_visible = canvas.focus_visible.clone();
+        let focus_loss = canvas.focus_loss.clone();
+        canvas.on_focus(move |visible| {
             if !has_focus.replace(true) {
+                focus_visible.set(visible);
+                focus_loss.set(None);
                 runner.send_event(Event::WindowEvent {
                     window_id: RootWindowId(id),
                     event: WindowEvent::Focused(true),
@@ -121,17 +156,14 @@ impl ActiveEventLoop {
 
         // It is possible that at this point the canvas has
         // been focused before the callback can be called.
-        let focused = canvas
-            .document()
-            .active_element()
-            .filter(|element| {
-                let canvas: &Element = canvas.raw();
-                element == canvas
-            })
-            .is_some();
+        let focused = canvas.document().active_element().filter(|element| {
+            let canvas: &Element = canvas.raw();
+            element == canvas
+        });
 
-        if focused {
+        if let Some(element) = focused {
             canvas.has_focus.set(true);
+            canvas.focus_visible.set(element.matches(":focus-visible").unwrap_or(false));
             self.runner.send_event(Event::WindowEvent {
                 window_id: RootWindowId(id),
                 event: WindowEvent::Focused(true),
@@ -146,7 +178,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(active_modifiers),
                     }
                 });
 
@@ -182,7 +214,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(active_modifiers),
                     }
                 });
 
@@ -210,6 +242,14 @@ impl ActiveEventLoop {
             },
         );
 
+        let runner = self.runner.clone();
+        canvas.on_ime(move |ime| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::Ime(ime),
+            })
+        });
+
         let has_focus = canvas.has_focus.clone();
         canvas.on_cursor_leave({
             let runner = self.runner.clone();
@@ -221,7 +261,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(active_modifiers),
                     }
                 });
 
@@ -248,7 +288,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(active_modifiers),
                     }
                 });
 
@@ -265,6 +305,9 @@ impl ActiveEventLoop {
             }
         });
 
+        // Browsers don't count the clicks of the pointer events.
+        let click_counter = Rc::new(RefCell::new(ClickCounter::default()));
+
         canvas.on_cursor_move(
             {
                 let runner = self.runner.clone();
@@ -276,7 +319,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(active_modifiers),
                         })
                     }
                 }
@@ -285,6 +328,7 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let click_counter = click_counter.clone();
 
                 move |active_modifiers, pointer_id, events| {
                     let modifiers =
@@ -292,18 +336,30 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(active_modifiers),
                             }
                         });
 
-                    runner.send_events(modifiers.into_iter().chain(events.flat_map(|position| {
-                        let device_id = RootDeviceId(DeviceId(pointer_id));
+                    // The coalesced events of the browser are only expanded when every position
+                    // was requested.
+                    let positions: Vec<_> = match runner.motion_event_policy() {
+                        MotionEventPolicy::Coalesced => events.last().into_iter().collect(),
+                        MotionEventPolicy::Unfiltered => events.collect(),
+                    };
+                    if let Some(&position) = positions.last() {
+                        click_counter.borrow_mut().moved(position, ClickThresholds::default());
+                    }
 
-                        iter::once(Event::WindowEvent {
-                            window_id: RootWindowId(id),
-                            event: WindowEvent::CursorMoved { device_id, position },
-                        })
-                    })));
+                    runner.send_events(modifiers.into_iter().chain(positions.into_iter().map(
+                        |position| {
+                            let device_id = RootDeviceId(DeviceId(pointer_id));
+
+                            Event::WindowEvent {
+                                window_id: RootWindowId(id),
+                                event: WindowEvent::CursorMoved { device_id, position },
+                            }
+                        },
+                    )));
                 }
             },
             {
@@ -317,18 +373,24 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(active_modifiers),
                             }
                         });
 
                     runner.send_events(modifiers.into_iter().chain(events.map(
-                        |(location, force)| Event::WindowEvent {
+                        |(location, details)| Event::WindowEvent {
                             window_id: RootWindowId(id),
                             event: WindowEvent::Touch(Touch {
                                 id: device_id as u64,
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Moved,
-                                force: Some(force),
+                                force: Some(details.force),
+                                tool: details.tool,
+                                azimuth_angle: details.azimuth_angle,
+                                altitude_angle: details.altitude_angle,
+                                twist_angle: details.twist_angle,
+                                is_estimated: false,
+                                is_predicted: false,
                                 location,
                             }),
                         },
@@ -339,6 +401,7 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let click_counter = click_counter.clone();
 
                 move |active_modifiers,
                       pointer_id,
@@ -350,7 +413,7 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(active_modifiers),
                             }
                         });
 
@@ -361,6 +424,7 @@ impl ActiveEventLoop {
                     } else {
                         ElementState::Released
                     };
+                    let click_count = click_count(&click_counter, button, state, position);
 
                     // A chorded button event may come in without any prior CursorMoved events,
                     // therefore we should send a CursorMoved event to make sure that the
@@ -372,7 +436,12 @@ impl ActiveEventLoop {
                         },
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::MouseInput { device_id, state, button },
+                            event: WindowEvent::MouseInput {
+                                device_id,
+                                state,
+                                button,
+                                click_count,
+                            },
                         },
                     ]));
                 }
@@ -389,7 +458,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(active_modifiers),
                         })
                     }
                 }
@@ -397,17 +466,20 @@ impl ActiveEventLoop {
             {
                 let runner = self.runner.clone();
                 let modifiers = self.modifiers.clone();
+                let click_counter = click_counter.clone();
 
                 move |active_modifiers, pointer_id, position, button| {
                     let modifiers = (modifiers.get() != active_modifiers).then(|| {
                         modifiers.set(active_modifiers);
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(active_modifiers),
                         }
                     });
 
                     let device_id: RootDeviceId = RootDeviceId(DeviceId(pointer_id));
+                    let click_count =
+                        click_count(&click_counter, button, ElementState::Pressed, position);
 
                     // A mouse down event may come in without any prior CursorMoved events,
                     // therefore we should send a CursorMoved event to make sure that the
@@ -423,6 +495,7 @@ impl ActiveEventLoop {
                                 device_id,
                                 state: ElementState::Pressed,
                                 button,
+                                click_count,
                             },
                         },
                     ]));
@@ -432,12 +505,12 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let modifiers = self.modifiers.clone();
 
-                move |active_modifiers, device_id, location, force| {
+                move |active_modifiers, device_id, location, details| {
                     let modifiers = (modifiers.get() != active_modifiers).then(|| {
                         modifiers.set(active_modifiers);
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(active_modifiers),
                         }
                     });
 
@@ -448,7 +521,13 @@ impl ActiveEventLoop {
                                 id: device_id as u64,
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Started,
-                                force: Some(force),
+                                force: Some(details.force),
+                                tool: details.tool,
+                                azimuth_angle: details.azimuth_angle,
+                                altitude_angle: details.altitude_angle,
+                                twist_angle: details.twist_angle,
+                                is_estimated: false,
+                                is_predicted: false,
                                 location,
                             }),
                         },
@@ -468,7 +547,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(active_modifiers),
                         });
                     }
                 }
@@ -477,6 +556,7 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let click_counter = click_counter.clone();
 
                 move |active_modifiers, pointer_id, position, button| {
                     let modifiers =
@@ -484,11 +564,13 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(active_modifiers),
                             }
                         });
 
                     let device_id: RootDeviceId = RootDeviceId(DeviceId(pointer_id));
+                    let click_count =
+                        click_count(&click_counter, button, ElementState::Released, position);
 
                     // A mouse up event may come in without any prior CursorMoved events,
                     // therefore we should send a CursorMoved event to make sure that the
@@ -504,6 +586,7 @@ impl ActiveEventLoop {
                                 device_id,
                                 state: ElementState::Released,
                                 button,
+                                click_count,
                             },
                         },
                     ]));
@@ -514,13 +597,13 @@ impl ActiveEventLoop {
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
 
-                move |active_modifiers, device_id, location, force| {
+                move |active_modifiers, device_id, location, details| {
                     let modifiers =
                         (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(active_modifiers),
                             }
                         });
 
@@ -531,7 +614,13 @@ impl ActiveEventLoop {
                                 id: device_id as u64,
                                 device_id: RootDeviceId(DeviceId(device_id)),
                                 phase: TouchPhase::Ended,
-                                force: Some(force),
+                                force: Some(details.force),
+                                tool: details.tool,
+                                azimuth_angle: details.azimuth_angle,
+                                altitude_angle: details.altitude_angle,
+                                twist_angle: details.twist_angle,
+                                is_estimated: false,
+                                is_predicted: false,
                                 location,
                             }),
                         },
@@ -540,44 +629,100 @@ impl ActiveEventLoop {
             },
         );
 
-        let runner = self.runner.clone();
         let modifiers = self.modifiers.clone();
-        canvas.on_mouse_wheel(move |pointer_id, delta, active_modifiers| {
-            let modifiers_changed =
-                (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
-                    modifiers.set(active_modifiers);
-                    Event::WindowEvent {
-                        window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
-                    }
-                });
+        let last_wheel_details = canvas.last_wheel_details.clone();
+        let wheel_modifiers = Rc::new(move |mode, mut active_modifiers: Modifiers| {
+            // Browsers report pinch gestures on trackpads with `ctrlKey` set, without the Control
+            // key being pressed, which shouldn't be reported as a modifier change.
+            let synthesized_ctrl =
+                active_modifiers.state().control_key() && !modifiers.get().state().control_key();
+            last_wheel_details.set(Some(WheelDetails { delta_mode: mode, synthesized_ctrl }));
+            if synthesized_ctrl {
+                active_modifiers.state.remove(ModifiersState::CONTROL);
+            }
 
-            runner.send_events(modifiers_changed.into_iter().chain(iter::once(
+            (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
+                modifiers.set(active_modifiers);
                 Event::WindowEvent {
                     window_id: RootWindowId(id),
-                    event: WindowEvent::MouseWheel {
-                        device_id: RootDeviceId(DeviceId(pointer_id)),
-                        delta,
-                        phase: TouchPhase::Moved,
-                    },
-                },
-            )));
+                    event: WindowEvent::ModifiersChanged(active_modifiers),
+                }
+            })
         });
 
         let runner = self.runner.clone();
-        canvas.on_touch_cancel(move |device_id, location, force| {
+        let pinch_runner = self.runner.clone();
+        let pinch_modifiers = Rc::clone(&wheel_modifiers);
+        canvas.on_mouse_wheel(
+            move |pointer_id, delta, mode, active_modifiers| {
+                let modifiers_changed = wheel_modifiers(mode, active_modifiers);
+
+                runner.send_events(modifiers_changed.into_iter().chain(iter::once(
+                    Event::WindowEvent {
+                        window_id: RootWindowId(id),
+                        event: WindowEvent::MouseWheel {
+                            device_id: RootDeviceId(DeviceId(pointer_id)),
+                            delta,
+                            phase: TouchPhase::Moved,
+                            momentum_phase: None,
+                        },
+                    },
+                )));
+            },
+            move |pointer_id, delta, mode, active_modifiers| {
+                let modifiers_changed = pinch_modifiers(mode, active_modifiers);
+
+                pinch_runner.send_events(modifiers_changed.into_iter().chain(iter::once(
+                    Event::WindowEvent {
+                        window_id: RootWindowId(id),
+                        event: WindowEvent::PinchGesture {
+                            device_id: RootDeviceId(DeviceId(pointer_id)),
+                            delta,
+                            phase: TouchPhase::Moved,
+                        },
+                    },
+                )));
+            },
+        );
+
+        let runner = self.runner.clone();
+        canvas.on_touch_cancel(move |device_id, location, details| {
             runner.send_event(Event::WindowEvent {
                 window_id: RootWindowId(id),
                 event: WindowEvent::Touch(Touch {
                     id: device_id as u64,
                     device_id: RootDeviceId(DeviceId(device_id)),
                     phase: TouchPhase::Cancelled,
-                    force: Some(force),
+                    force: Some(details.force),
+                    tool: details.tool,
+                    azimuth_angle: details.azimuth_angle,
+                    altitude_angle: details.altitude_angle,
+                    twist_angle: details.twist_angle,
+                    is_estimated: false,
+                    is_predicted: false,
                     location,
                 }),
             });
         });
 
+        // Emitted before the resulting resize is observed.
+        let runner = self.runner.clone();
+        canvas.on_orientation_change(move |orientation| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::InterfaceOrientationChanged(orientation),
+            });
+        });
+
+        // The size of the canvas doesn't necessarily change when an ancestor is made fullscreen.
+        let runner = self.runner.clone();
+        canvas.on_fullscreen_change(move |size| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::Resized(size),
+            });
+        });
+
         let runner = self.runner.clone();
         canvas.on_dark_mode(move |is_dark_mode| {
             let theme = if is_dark_mode { Theme::Dark } else { Theme::Light };
@@ -615,10 +760,33 @@ impl ActiveEventLoop {
                         });
                         runner.request_redraw(RootWindowId(id));
                     }
+
+                    // Moving the canvas in or out of the safe area is only noticed here.
+                    if let Some(safe_area) = canvas.take_safe_area_change() {
+                        runner.send_event(Event::WindowEvent {
+                            window_id: RootWindowId(id),
+                            event: WindowEvent::SafeAreaChanged(safe_area),
+                        });
+                    }
                 }
             },
         );
 
+        // Registered after the `ResizeObserver` of the canvas, so that a change of the viewport is
+        // reported after the resulting `Resized`.
+        let runner = self.runner.clone();
+        let canvas_weak = Rc::downgrade(&canvas_clone);
+        canvas.on_safe_area_change(move || {
+            let Some(canvas) = canvas_weak.upgrade() else { return };
+            let safe_area = canvas.borrow().take_safe_area_change();
+            if let Some(safe_area) = safe_area {
+                runner.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::SafeAreaChanged(safe_area),
+                });
+            }
+        });
+
         let runner = self.runner.clone();
         canvas.on_intersection(move |is_intersecting| {
             // only fire if visible while skipping the first event if it's intersecting
@@ -637,15 +805,134 @@ impl ActiveEventLoop {
         let runner = self.runner.clone();
         canvas.on_animation_frame(move || runner.request_redraw(RootWindowId(id)));
 
+        // Only the last frame drawn before an animation frame is presented.
+        let runner = self.runner.clone();
+        canvas.on_presentation_frame(move |generations, time| {
+            let last = generations.len().saturating_sub(1);
+            runner.send_events(generations.into_iter().enumerate().map(|(index, generation)| {
+                Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::FramePresented(PresentationFeedback {
+                        generation,
+                        presentation_time: time.filter(|_| index == last),
+                        refresh_interval: None,
+                        missed: index != last,
+                    }),
+                }
+            }));
+        });
+
         canvas.on_context_menu();
+
+        // Browsers only expose the names of the files once they are dropped.
+        let runner = self.runner.clone();
+        canvas.on_drag_enter(move |kinds, count, position| {
+            let hovered_files = kinds.contains(DragDropKinds::PATHS).then_some(count.max(1));
+            #[allow(deprecated)]
+            let hovered = iter::repeat_with(|| Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::HoveredFile(PathBuf::new()),
+            })
+            .take(hovered_files.unwrap_or(0));
+
+            runner.send_events(hovered.chain(iter::once(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::DragDrop(DragDropEvent::Entered { kinds, position }),
+            })));
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drag_over(move |position| {
+            runner.send_events([
+                Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::CursorMoved {
+                        device_id: RootDeviceId(DeviceId(0)),
+                        position,
+                    },
+                },
+                Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::DragDrop(DragDropEvent::Moved { position }),
+                },
+            ]);
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drag_leave(move |kinds| {
+            #[allow(deprecated)]
+            let cancelled = kinds.contains(DragDropKinds::PATHS).then_some(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::HoveredFileCancelled,
+            });
+
+            runner.send_events(cancelled.into_iter().chain(iter::once(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::DragDrop(DragDropEvent::Left),
+            })));
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_drop(self.runner.main_thread(), move |position, kinds, names, text| {
+            // E.g. only directories were dropped.
+            #[allow(deprecated)]
+            let cancelled = (kinds.contains(DragDropKinds::PATHS) && names.is_empty()).then_some(
+                Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::HoveredFileCancelled,
+                },
+            );
+
+            let items = names
+                .iter()
+                .map(|name| DragDropItem::Path(PathBuf::from(name)))
+                .chain(text.map(DragDropItem::Text))
+                .collect();
+
+            #[allow(deprecated)]
+            let dropped = names.into_iter().map(|name| Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::DroppedFile(PathBuf::from(name)),
+            });
+
+            runner.send_events(
+                iter::once(Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::CursorMoved {
+                        device_id: RootDeviceId(DeviceId(0)),
+                        position,
+                    },
+                })
+                .chain(dropped)
+                .chain(cancelled)
+                .chain(iter::once(Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::DragDrop(DragDropEvent::Dropped { items }),
+                })),
+            );
+        });
+
+        if canvas.close_requested_on_unload {
+            let runner = self.runner.clone();
+            canvas.on_before_unload(move || {
+                runner.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(id),
+                    event: WindowEvent::CloseRequested,
+                })
+            });
+        }
     }
 
     pub fn available_monitors(&self) -> VecDequeIter<MonitorHandle> {
-        VecDeque::new().into_iter()
+        VecDeque::from(self.runner.monitor().available_monitors()).into_iter()
     }
 
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
-        None
+        self.runner.monitor().primary_monitor()
+    }
+
+    pub(crate) fn request_detailed_monitor_permission(&self) -> MonitorPermissionFuture {
+        self.runner.request_detailed_monitor_permission()
     }
 
     #[cfg(feature = "rwh_05")]
@@ -662,8 +949,24 @@ impl ActiveEventLoop {
         Ok(rwh_06::RawDisplayHandle::Web(rwh_06::WebDisplayHandle::new()))
     }
 
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        self.runner.listen_device_events(allowed)
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        self.runner.listen_device_events(subscription)
+    }
+
+    pub fn set_motion_event_policy(&self, policy: MotionEventPolicy) {
+        self.runner.set_motion_event_policy(policy)
+    }
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        self.runner.motion_event_policy()
+    }
+
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        keyboard::layout_independent_key(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        keyboard::find_key_code(key, keyboard::layout_independent_keys())
     }
 
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -698,6 +1001,30 @@ impl ActiveEventLoop {
         self.runner.wait_until_strategy()
     }
 
+    pub(crate) fn set_hidden_poll_interval(&self, interval: Option<Duration>) {
+        self.runner.set_hidden_poll_interval(interval)
+    }
+
+    pub(crate) fn hidden_poll_interval(&self) -> Option<Duration> {
+        self.runner.hidden_poll_interval()
+    }
+
+    pub(crate) fn set_pinch_zoom_scaling(&self, enabled: bool) {
+        self.runner.set_pinch_zoom_scaling(enabled)
+    }
+
+    pub(crate) fn pinch_zoom_scaling(&self) -> bool {
+        self.runner.pinch_zoom_scaling()
+    }
+
+    pub(crate) fn reduced_motion(&self) -> bool {
+        backend::reduced_motion(self.runner.window())
+    }
+
+    pub(crate) fn contrast_preference(&self) -> ContrastPreference {
+        backend::contrast_preference(self.runner.window())
+    }
+
     pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
         self.runner.waker()
     }
@@ -520,3 +520,19 @@ impl PhysicalKey {
         })
     }
 }
+
+impl KeyCode {
+    /// The inverse of [`PhysicalKey::from_key_code_attribute_value()`], if the key has a `code`
+    /// value on the web.
+    pub(crate) fn to_key_code_attribute_value(self) -> Option<String> {
+        let kcav = match self {
+            KeyCode::SuperLeft => "MetaLeft".to_owned(),
+            KeyCode::SuperRight => "MetaRight".to_owned(),
+            // All other values are named like the variants.
+            key_code => format!("{key_code:?}"),
+        };
+
+        (PhysicalKey::from_key_code_attribute_value(&kcav) == PhysicalKey::Code(self))
+            .then_some(kcav)
+    }
+}
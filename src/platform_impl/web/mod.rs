@@ -23,6 +23,7 @@
 mod r#async;
 mod cursor;
 mod device;
+mod dropped_file;
 mod error;
 mod event_loop;
 mod keyboard;
@@ -30,15 +31,21 @@ mod main_thread;
 mod monitor;
 mod web_sys;
 mod window;
+mod worker;
 
 pub use self::device::DeviceId;
+pub use self::dropped_file::{DroppedFile, DroppedFileReadFuture};
+pub(crate) use self::error::platform_error;
 pub use self::error::OsError;
 pub(crate) use self::event_loop::{
     ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
     PlatformSpecificEventLoopAttributes,
 };
-pub use self::monitor::{MonitorHandle, VideoModeHandle};
-pub use self::window::{PlatformSpecificWindowAttributes, Window, WindowId};
+pub use self::monitor::{MonitorHandle, MonitorPermissionFuture, VideoModeHandle};
+pub use self::window::{
+    KeyboardLockFuture, OrientationLockFuture, PlatformSpecificWindowAttributes, Window, WindowId,
+};
+pub use self::worker::{WorkerCanvas, WorkerCanvasShim};
 
 pub(crate) use self::keyboard::KeyEventExtra;
 use self::web_sys as backend;
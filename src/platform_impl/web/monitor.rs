@@ -1,33 +1,98 @@
-use std::iter::Empty;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::iter::{self, Empty};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use js_sys::{Array, Promise};
+use wasm_bindgen::prelude::{wasm_bindgen, Closure};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Event, EventTarget};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct MonitorHandle;
+use super::backend::EventListenerHandle;
+use super::main_thread::{MainThreadMarker, MainThreadSafe};
+use super::r#async::Notified;
+use super::OsError;
+use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
+use crate::error::{ExternalError, NotSupportedError};
+use crate::monitor::ColorProperties;
+
+/// A snapshot of a screen, taken when the handle was retrieved.
+#[derive(Clone, Debug)]
+pub struct MonitorHandle(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    /// `0` is the screen of the browser window when the Window Management API isn't used.
+    id: u64,
+    name: Option<String>,
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+    screen: Option<MainThreadSafe<ScreenDetailed>>,
+}
 
 impl MonitorHandle {
     pub fn scale_factor(&self) -> f64 {
-        unreachable!()
+        self.0.scale_factor
     }
 
     pub fn position(&self) -> PhysicalPosition<i32> {
-        unreachable!()
+        self.0.position
     }
 
     pub fn name(&self) -> Option<String> {
-        unreachable!()
+        self.0.name.clone()
     }
 
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
-        unreachable!()
+        None
+    }
+
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        None
     }
 
     pub fn size(&self) -> PhysicalSize<u32> {
-        unreachable!()
+        self.0.size
     }
 
     pub fn video_modes(&self) -> Empty<VideoModeHandle> {
-        unreachable!()
+        iter::empty()
+    }
+
+    /// The `ScreenDetailed` to pass to `requestFullscreen()`, if known.
+    pub(crate) fn screen(&self, main_thread: MainThreadMarker) -> Option<&JsValue> {
+        self.0.screen.as_ref().map(|screen| screen.get(main_thread).as_ref())
+    }
+}
+
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.id == other.0.id
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl PartialOrd for MonitorHandle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MonitorHandle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.id.cmp(&other.0.id)
+    }
+}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id.hash(state)
     }
 }
 
@@ -51,3 +116,237 @@ impl VideoModeHandle {
         unreachable!();
     }
 }
+
+/// Keeps track of the screens exposed by the [Window Management API], once permission to use it
+/// was granted. Until then, only the screen of the browser window is known.
+///
+/// [Window Management API]: https://developer.mozilla.org/en-US/docs/Web/API/Window_Management_API
+pub struct MonitorHandler {
+    main_thread: MainThreadMarker,
+    window: web_sys::Window,
+    details: RefCell<Option<Details>>,
+    next_id: Cell<u64>,
+}
+
+struct Details {
+    object: ScreenDetails,
+    /// The known screens with the id assigned to them.
+    screens: Vec<(ScreenDetailed, u64)>,
+    _on_change: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+impl MonitorHandler {
+    pub fn new(main_thread: MainThreadMarker, window: web_sys::Window) -> Self {
+        Self { main_thread, window, details: RefCell::new(None), next_id: Cell::new(1) }
+    }
+
+    /// Returns [`None`] if the Window Management API isn't supported.
+    pub fn request_details(&self) -> Option<Promise> {
+        let window: &WindowExt = self.window.unchecked_ref();
+        if window.has_get_screen_details().is_undefined() {
+            return None;
+        }
+
+        Some(window.get_screen_details())
+    }
+
+    /// Starts using the `ScreenDetails` the promise returned by
+    /// [`request_details()`](Self::request_details) resolved to. `on_change` is called when
+    /// screens are added or removed, and should emit the events returned by
+    /// [`update()`](Self::update).
+    pub fn set_details(&self, details: JsValue, on_change: impl 'static + FnMut()) {
+        let object: ScreenDetails = details.unchecked_into();
+        let mut on_change = on_change;
+        let handle = EventListenerHandle::new(
+            object.clone(),
+            "screenschange",
+            Closure::new(move |_: Event| on_change()),
+        );
+
+        let screens = self.assign_ids(&object, &[]);
+        *self.details.borrow_mut() = Some(Details { object, screens, _on_change: handle });
+    }
+
+    /// Updates the known screens and returns the ones that were connected and disconnected.
+    pub fn update(&self) -> (Vec<MonitorHandle>, Vec<MonitorHandle>) {
+        let mut details = self.details.borrow_mut();
+        let Some(details) = details.as_mut() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let screens = self.assign_ids(&details.object, &details.screens);
+        let connected = screens
+            .iter()
+            .filter(|(_, id)| !details.screens.iter().any(|(_, old)| old == id))
+            .map(|(screen, id)| self.handle(screen, *id))
+            .collect();
+        let disconnected = details
+            .screens
+            .iter()
+            .filter(|(_, id)| !screens.iter().any(|(_, new)| new == id))
+            .map(|(screen, id)| self.handle(screen, *id))
+            .collect();
+        details.screens = screens;
+
+        (connected, disconnected)
+    }
+
+    pub fn available_monitors(&self) -> Vec<MonitorHandle> {
+        match &*self.details.borrow() {
+            Some(details) => {
+                details.screens.iter().map(|(screen, id)| self.handle(screen, *id)).collect()
+            },
+            None => self.current_monitor().into_iter().collect(),
+        }
+    }
+
+    pub fn primary_monitor(&self) -> Option<MonitorHandle> {
+        match &*self.details.borrow() {
+            Some(details) => details
+                .screens
+                .iter()
+                .find(|(screen, _)| screen.is_primary())
+                .map(|(screen, id)| self.handle(screen, *id)),
+            None => self.current_monitor(),
+        }
+    }
+
+    /// The screen the browser window is on.
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        if let Some(details) = &*self.details.borrow() {
+            let current = details.object.current_screen();
+            // `currentScreen` is one of the objects in `screens`.
+            if let Some((screen, id)) =
+                details.screens.iter().find(|(screen, _)| screen == &current)
+            {
+                return Some(self.handle(screen, *id));
+            }
+        }
+
+        let screen = self.window.screen().ok()?;
+        // Pinch-zoom doesn't affect the screen.
+        let scale_factor = self.window.device_pixel_ratio();
+        let size = LogicalSize::new(screen.width().ok()?, screen.height().ok()?);
+        Some(MonitorHandle(Arc::new(Inner {
+            id: 0,
+            name: None,
+            position: PhysicalPosition::default(),
+            size: size.cast::<f64>().to_physical(scale_factor),
+            scale_factor,
+            screen: None,
+        })))
+    }
+
+    fn assign_ids(
+        &self,
+        object: &ScreenDetails,
+        known: &[(ScreenDetailed, u64)],
+    ) -> Vec<(ScreenDetailed, u64)> {
+        object
+            .screens()
+            .iter()
+            .map(|screen| {
+                let screen: ScreenDetailed = screen.unchecked_into();
+                let id = known
+                    .iter()
+                    .find(|(known, _)| known == &screen)
+                    .map(|(_, id)| *id)
+                    .unwrap_or_else(|| {
+                        let id = self.next_id.get();
+                        self.next_id.set(id + 1);
+                        id
+                    });
+                (screen, id)
+            })
+            .collect()
+    }
+
+    fn handle(&self, screen: &ScreenDetailed, id: u64) -> MonitorHandle {
+        let scale_factor = screen.device_pixel_ratio();
+        MonitorHandle(Arc::new(Inner {
+            id,
+            name: Some(screen.label()).filter(|label| !label.is_empty()),
+            position: PhysicalPosition::new(
+                (screen.left() * scale_factor).round() as i32,
+                (screen.top() * scale_factor).round() as i32,
+            ),
+            size: PhysicalSize::new(
+                (screen.width() * scale_factor).round() as u32,
+                (screen.height() * scale_factor).round() as u32,
+            ),
+            scale_factor,
+            screen: Some(MainThreadSafe::new(self.main_thread, screen.clone())),
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum MonitorPermissionError {
+    NotSupported,
+    Rejected(String),
+}
+
+#[derive(Debug)]
+pub struct MonitorPermissionFuture(pub(crate) Notified<Result<(), MonitorPermissionError>>);
+
+impl Future for MonitorPermissionFuture {
+    type Output = Result<(), ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map_err(|error| match error {
+            MonitorPermissionError::NotSupported => {
+                ExternalError::NotSupported(NotSupportedError::new())
+            },
+            MonitorPermissionError::Rejected(message) => {
+                ExternalError::Os(os_error!(OsError(message)))
+            },
+        })
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::Window)]
+    type WindowExt;
+
+    #[wasm_bindgen(method, getter, js_name = getScreenDetails)]
+    fn has_get_screen_details(this: &WindowExt) -> JsValue;
+
+    #[wasm_bindgen(method, js_name = getScreenDetails)]
+    fn get_screen_details(this: &WindowExt) -> Promise;
+
+    #[derive(Clone, Debug)]
+    #[wasm_bindgen(extends = EventTarget)]
+    type ScreenDetails;
+
+    #[wasm_bindgen(method, getter)]
+    fn screens(this: &ScreenDetails) -> Array;
+
+    #[wasm_bindgen(method, getter, js_name = currentScreen)]
+    fn current_screen(this: &ScreenDetails) -> ScreenDetailed;
+
+    #[derive(Clone, Debug, PartialEq)]
+    #[wasm_bindgen(extends = EventTarget)]
+    pub type ScreenDetailed;
+
+    #[wasm_bindgen(method, getter)]
+    fn left(this: &ScreenDetailed) -> f64;
+
+    #[wasm_bindgen(method, getter)]
+    fn top(this: &ScreenDetailed) -> f64;
+
+    #[wasm_bindgen(method, getter)]
+    fn width(this: &ScreenDetailed) -> f64;
+
+    #[wasm_bindgen(method, getter)]
+    fn height(this: &ScreenDetailed) -> f64;
+
+    #[wasm_bindgen(method, getter, js_name = devicePixelRatio)]
+    fn device_pixel_ratio(this: &ScreenDetailed) -> f64;
+
+    #[wasm_bindgen(method, getter)]
+    fn label(this: &ScreenDetailed) -> String;
+
+    #[wasm_bindgen(method, getter, js_name = isPrimary)]
+    fn is_primary(this: &ScreenDetailed) -> bool;
+}
@@ -0,0 +1,64 @@
+use std::cell::{Cell, RefCell};
+use std::iter;
+use std::rc::Rc;
+
+use super::media_query_handle::MediaQueryListHandle;
+use crate::event_loop::ContrastPreference;
+
+const REDUCED_MOTION: &str = "(prefers-reduced-motion: reduce)";
+
+const CONTRAST: [(&str, ContrastPreference); 3] = [
+    ("(prefers-contrast: more)", ContrastPreference::More),
+    ("(prefers-contrast: less)", ContrastPreference::Less),
+    ("(prefers-contrast: custom)", ContrastPreference::Custom),
+];
+
+// Browsers that don't support a media feature never match it, which results in the defaults.
+
+pub fn reduced_motion(window: &web_sys::Window) -> bool {
+    matches(window, REDUCED_MOTION)
+}
+
+pub fn contrast_preference(window: &web_sys::Window) -> ContrastPreference {
+    CONTRAST
+        .iter()
+        .find(|(query, _)| matches(window, query))
+        .map_or(ContrastPreference::NoPreference, |(_, preference)| *preference)
+}
+
+fn matches(window: &web_sys::Window, query: &str) -> bool {
+    window.match_media(query).ok().flatten().is_some_and(|media| media.matches())
+}
+
+/// Calls the handler when [`reduced_motion()`] or [`contrast_preference()`] change.
+pub struct AccessibilityHandle {
+    _handles: Vec<MediaQueryListHandle>,
+}
+
+impl AccessibilityHandle {
+    pub fn new<F>(window: &web_sys::Window, handler: F) -> Self
+    where
+        F: 'static + FnMut(),
+    {
+        let handler: Rc<RefCell<dyn FnMut()>> = Rc::new(RefCell::new(handler));
+        let state = Rc::new(Cell::new((reduced_motion(window), contrast_preference(window))));
+
+        let handles = iter::once(REDUCED_MOTION)
+            .chain(CONTRAST.iter().map(|(query, _)| *query))
+            .map(|query| {
+                let window_clone = window.clone();
+                let handler = Rc::clone(&handler);
+                let state = Rc::clone(&state);
+                MediaQueryListHandle::new(window, query, move |_| {
+                    // Switching from one contrast preference to another flips two media queries.
+                    let new = (reduced_motion(&window_clone), contrast_preference(&window_clone));
+                    if state.replace(new) != new {
+                        (handler.borrow_mut())()
+                    }
+                })
+            })
+            .collect();
+
+        Self { _handles: handles }
+    }
+}
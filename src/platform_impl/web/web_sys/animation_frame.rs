@@ -5,7 +5,7 @@ use wasm_bindgen::JsCast;
 
 pub struct AnimationFrameHandler {
     window: web_sys::Window,
-    closure: Closure<dyn FnMut()>,
+    closure: Closure<dyn FnMut(f64)>,
     handle: Rc<Cell<Option<i32>>>,
 }
 
@@ -14,20 +14,21 @@ impl AnimationFrameHandler {
         let handle = Rc::new(Cell::new(None));
         let closure = Closure::new({
             let handle = handle.clone();
-            move || handle.set(None)
+            move |_| handle.set(None)
         });
 
         Self { window, closure, handle }
     }
 
+    /// The handler receives the timestamp passed to the callback by the browser.
     pub fn on_animation_frame<F>(&mut self, mut f: F)
     where
-        F: 'static + FnMut(),
+        F: 'static + FnMut(f64),
     {
         let handle = self.handle.clone();
-        self.closure = Closure::new(move || {
+        self.closure = Closure::new(move |timestamp| {
             handle.set(None);
-            f();
+            f(timestamp);
         })
     }
 
@@ -44,9 +45,17 @@ impl AnimationFrameHandler {
         self.handle.set(Some(handle));
     }
 
-    pub fn cancel(&mut self) {
+    pub fn is_pending(&self) -> bool {
+        self.handle.get().is_some()
+    }
+
+    /// Returns `true` if a request was pending.
+    pub fn cancel(&mut self) -> bool {
         if let Some(handle) = self.handle.take() {
             self.window.cancel_animation_frame(handle).expect("Failed to cancel animation frame");
+            true
+        } else {
+            false
         }
     }
 }
@@ -1,40 +1,84 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use js_sys::{Object, Reflect};
 use smol_str::SmolStr;
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    CssStyleDeclaration, Document, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent,
-    PointerEvent, WheelEvent,
+    BeforeUnloadEvent, CssStyleDeclaration, Document, DragEvent, Element, Event, FocusEvent,
+    HtmlCanvasElement, HtmlElement, KeyboardEvent, PointerEvent, WheelEvent,
 };
+use web_time::Instant;
 
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize};
 use crate::error::OsError as RootOE;
-use crate::event::{Force, InnerSizeWriter, MouseButton, MouseScrollDelta};
-use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
+use crate::event::{DragDropKinds, Ime, InnerSizeWriter, Modifiers, MouseButton, MouseScrollDelta};
+use crate::keyboard::{Key, KeyLocation, PhysicalKey};
+use crate::platform::web::{
+    FocusLoss, FullscreenElement, FullscreenNavigationUi, PositionMode, VisualViewport,
+    WheelDeltaMode, WheelDeltaPolicy, WheelDetails,
+};
 use crate::platform_impl::OsError;
-use crate::window::{WindowAttributes, WindowId as RootWindowId};
+use crate::utils::RedrawDamage;
+use crate::window::{Fullscreen, InterfaceOrientation, WindowAttributes, WindowId as RootWindowId};
 
 use super::super::cursor::CursorHandler;
+use super::super::dropped_file::DroppedFile;
 use super::super::main_thread::MainThreadMarker;
 use super::super::WindowId;
 use super::animation_frame::AnimationFrameHandler;
+use super::event::TouchDetails;
 use super::event_handle::EventListenerHandle;
+use super::ime::ImeHandler;
 use super::intersection_handle::IntersectionObserverHandle;
 use super::media_query_handle::MediaQueryListHandle;
 use super::pointer::PointerHandler;
-use super::{event, fullscreen, ButtonsState, ResizeScaleHandle};
+use super::{
+    event, fullscreen, ButtonsState, ResizeScaleHandle, SafeAreaHandle, TransformPosition,
+};
 
 #[allow(dead_code)]
 pub struct Canvas {
     common: Common,
     id: WindowId,
     pub has_focus: Rc<Cell<bool>>,
+    /// Whether the canvas matched `:focus-visible` when it was last focused.
+    pub focus_visible: Rc<Cell<bool>>,
+    /// Why the canvas last lost focus, reset when it is focused again.
+    pub focus_loss: Rc<Cell<Option<FocusLoss>>>,
     pub prevent_default: Rc<Cell<bool>>,
+    pub focus_on_pointer_down: Rc<Cell<bool>>,
+    pub wheel_delta_policy: Rc<Cell<WheelDeltaPolicy>>,
+    pub wheel_pinch_gesture: Rc<Cell<bool>>,
+    pub last_wheel_details: Rc<Cell<Option<WheelDetails>>>,
+    pub auto_parent_size: bool,
+    pub close_requested_on_unload: bool,
+    pub confirm_close: Rc<Cell<bool>>,
+    pub last_frame_time: Rc<Cell<Option<Duration>>>,
+    /// The number of `pre_present_notify` calls.
+    pub present_generation: Cell<u64>,
+    pub presentation_feedback: Cell<bool>,
+    /// The damage of the redraw requests since the last `RedrawRequested`.
+    pub redraw_damage: RefCell<RedrawDamage>,
+    /// The generations presented at the next animation frame.
+    pending_presents: Rc<RefCell<Vec<u64>>>,
+    /// The files of the last drop.
+    pub dropped_files: Rc<RefCell<Vec<DroppedFile>>>,
+    /// The element to make fullscreen instead of the canvas.
+    pub fullscreen_element: Rc<RefCell<Option<FullscreenElement>>>,
+    pub fullscreen_navigation_ui: Cell<FullscreenNavigationUi>,
     pub is_intersecting: Option<bool>,
+    /// The last reported safe area.
+    safe_area: Cell<Option<PhysicalInsets<u32>>>,
+    position_mode: PositionMode,
+    /// Whether the canvas was positioned with `left` and `top`.
+    positioned: Cell<bool>,
+    transform_position: TransformPosition,
     on_touch_start: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
     on_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
@@ -45,10 +89,20 @@ pub struct Canvas {
     pointer_handler: PointerHandler,
     on_resize_scale: Option<ResizeScaleHandle>,
     on_intersect: Option<IntersectionObserverHandle>,
+    on_safe_area: Option<SafeAreaHandle>,
     animation_frame_handler: AnimationFrameHandler,
+    presentation_frame_handler: AnimationFrameHandler,
     on_touch_end: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_context_menu: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_before_unload: Option<EventListenerHandle<dyn FnMut(BeforeUnloadEvent)>>,
+    on_drag_enter: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_over: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_leave: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drop: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_orientation_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_fullscreen_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
     pub cursor: CursorHandler,
+    pub ime: ImeHandler,
 }
 
 pub struct Common {
@@ -111,6 +165,7 @@ impl Canvas {
         let style = Style::new(&window, &canvas);
 
         let cursor = CursorHandler::new(main_thread, canvas.clone(), style.clone());
+        let ime = ImeHandler::new(document.clone(), canvas.clone());
 
         let common = Common {
             window: window.clone(),
@@ -121,7 +176,11 @@ impl Canvas {
             current_size: Rc::default(),
         };
 
-        if let Some(size) = attr.inner_size {
+        if attr.platform_specific.auto_parent_size {
+            // The size is then observed by the `ResizeObserver` and copied to the backing store.
+            common.style.set("width", "100%");
+            common.style.set("height", "100%");
+        } else if let Some(size) = attr.inner_size {
             let size = size.to_logical(super::scale_factor(&common.window));
             super::set_canvas_size(&common.document, &common.raw, &common.style, size);
         }
@@ -136,25 +195,56 @@ impl Canvas {
             super::set_canvas_max_size(&common.document, &common.raw, &common.style, Some(size));
         }
 
-        if let Some(position) = attr.position {
-            let position = position.to_logical(super::scale_factor(&common.window));
-            super::set_canvas_position(&common.document, &common.raw, &common.style, position);
-        }
-
-        if attr.fullscreen.is_some() {
-            fullscreen::request_fullscreen(&document, &canvas);
+        let fullscreen_element = attr
+            .platform_specific
+            .fullscreen_element
+            .take()
+            .map(|element| element.into_element(main_thread));
+        let fullscreen_navigation_ui = attr.platform_specific.fullscreen_navigation_ui;
+
+        if let Some(fullscreen) = &attr.fullscreen {
+            let screen = match fullscreen {
+                Fullscreen::Borderless(Some(monitor)) => monitor.inner.screen(main_thread),
+                _ => None,
+            };
+            let target =
+                fullscreen::target_element(&document, &canvas, fullscreen_element.as_ref());
+            fullscreen::request_fullscreen(&document, &target, fullscreen_navigation_ui, screen);
         }
 
         if attr.active {
             let _ = common.raw.focus();
         }
 
-        Ok(Canvas {
+        let transform_position = TransformPosition::new(window.clone(), common.style.clone());
+
+        let canvas = Canvas {
             common,
             id,
             has_focus: Rc::new(Cell::new(false)),
+            focus_visible: Rc::new(Cell::new(false)),
+            focus_loss: Rc::new(Cell::new(None)),
             prevent_default: Rc::new(Cell::new(attr.platform_specific.prevent_default)),
+            focus_on_pointer_down: Rc::new(Cell::new(attr.platform_specific.focus_on_pointer_down)),
+            wheel_delta_policy: Rc::new(Cell::new(attr.platform_specific.wheel_delta_policy)),
+            wheel_pinch_gesture: Rc::new(Cell::new(attr.platform_specific.wheel_pinch_gesture)),
+            last_wheel_details: Rc::new(Cell::new(None)),
+            auto_parent_size: attr.platform_specific.auto_parent_size,
+            close_requested_on_unload: attr.platform_specific.close_requested_on_unload,
+            confirm_close: Rc::new(Cell::new(false)),
+            last_frame_time: Rc::new(Cell::new(None)),
+            present_generation: Cell::new(0),
+            presentation_feedback: Cell::new(false),
+            redraw_damage: RefCell::default(),
+            pending_presents: Rc::default(),
+            dropped_files: Rc::default(),
+            fullscreen_element: Rc::new(RefCell::new(fullscreen_element)),
+            fullscreen_navigation_ui: Cell::new(fullscreen_navigation_ui),
             is_intersecting: None,
+            safe_area: Cell::new(None),
+            position_mode: attr.platform_specific.position_mode,
+            positioned: Cell::new(false),
+            transform_position,
             on_touch_start: None,
             on_blur: None,
             on_focus: None,
@@ -165,11 +255,27 @@ impl Canvas {
             pointer_handler: PointerHandler::new(),
             on_resize_scale: None,
             on_intersect: None,
-            animation_frame_handler: AnimationFrameHandler::new(window),
+            on_safe_area: None,
+            animation_frame_handler: AnimationFrameHandler::new(window.clone()),
+            presentation_frame_handler: AnimationFrameHandler::new(window),
             on_touch_end: None,
             on_context_menu: None,
+            on_before_unload: None,
+            on_drag_enter: None,
+            on_drag_over: None,
+            on_drag_leave: None,
+            on_drop: None,
+            on_orientation_change: None,
+            on_fullscreen_change: None,
             cursor,
-        })
+            ime,
+        };
+
+        if let Some(position) = attr.position {
+            canvas.set_position(position.to_logical(super::scale_factor(&canvas.common.window)));
+        }
+
+        Ok(canvas)
     }
 
     pub fn set_cursor_lock(&self, lock: bool) -> Result<(), RootOE> {
@@ -189,6 +295,12 @@ impl Canvas {
     }
 
     pub fn position(&self) -> LogicalPosition<f64> {
+        if let (PositionMode::Transform, Some(position)) =
+            (self.position_mode, self.transform_position.position())
+        {
+            return position;
+        }
+
         let bounds = self.common.raw.get_bounding_client_rect();
         let mut position = LogicalPosition { x: bounds.x(), y: bounds.y() };
 
@@ -202,6 +314,45 @@ impl Canvas {
         position
     }
 
+    pub fn set_position(&self, position: LogicalPosition<f64>) {
+        match self.position_mode {
+            PositionMode::Absolute => {
+                super::set_canvas_position(self.document(), self.raw(), self.style(), position);
+                self.positioned.set(true);
+            },
+            PositionMode::Transform => {
+                self.transform_position.set(self.document(), self.raw(), position)
+            },
+        }
+    }
+
+    pub fn position_mode(&self) -> PositionMode {
+        self.position_mode
+    }
+
+    /// Moves a canvas positioned by winit over to the new mode.
+    pub fn set_position_mode(&mut self, mode: PositionMode) {
+        if self.position_mode == mode {
+            return;
+        }
+
+        match mode {
+            PositionMode::Absolute => {
+                self.position_mode = mode;
+                if let Some(position) = self.transform_position.clear() {
+                    self.set_position(position);
+                }
+            },
+            PositionMode::Transform => {
+                let position = self.positioned.replace(false).then(|| self.position());
+                self.position_mode = mode;
+                if let Some(position) = position {
+                    self.set_position(position);
+                }
+            },
+        }
+    }
+
     #[inline]
     pub fn old_size(&self) -> PhysicalSize<u32> {
         self.common.old_size.get()
@@ -219,7 +370,16 @@ impl Canvas {
 
     #[inline]
     pub fn set_current_size(&self, size: PhysicalSize<u32>) {
-        self.common.current_size.set(size)
+        self.common.current_size.set(size);
+
+        if self.auto_parent_size {
+            // Setting the backing store size doesn't affect the layout, because the CSS size of
+            // the canvas is set, so this doesn't trigger the `ResizeObserver` again.
+            #[allow(clippy::disallowed_methods)]
+            self.common.raw.set_width(size.width);
+            #[allow(clippy::disallowed_methods)]
+            self.common.raw.set_height(size.height);
+        }
     }
 
     #[inline]
@@ -251,80 +411,112 @@ impl Canvas {
         }));
     }
 
-    pub fn on_blur<F>(&mut self, mut handler: F)
+    pub fn on_blur<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(),
+        F: 'static + FnMut(FocusLoss),
     {
-        self.on_blur = Some(self.common.add_event("blur", move |_: FocusEvent| {
-            handler();
+        let handler = Rc::new(RefCell::new(handler));
+        let input = self.ime.input_target();
+        self.on_blur = Some(self.common.add_event("blur", {
+            let handler = Rc::clone(&handler);
+            let document = self.common.document.clone();
+            move |event: FocusEvent| {
+                // Moving the focus to the IME input doesn't unfocus the window.
+                if event.related_target().as_ref() != Some(&input) {
+                    (handler.borrow_mut())(focus_loss(&document));
+                }
+            }
         }));
+        let document = self.common.document.clone();
+        self.ime.on_blur(move || (handler.borrow_mut())(focus_loss(&document)));
     }
 
-    pub fn on_focus<F>(&mut self, mut handler: F)
+    /// The handler receives whether the focused element matches `:focus-visible`.
+    pub fn on_focus<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(),
+        F: 'static + FnMut(bool),
     {
-        self.on_focus = Some(self.common.add_event("focus", move |_: FocusEvent| {
-            handler();
+        let handler = Rc::new(RefCell::new(handler));
+        self.on_focus = Some(self.common.add_event("focus", {
+            let handler = Rc::clone(&handler);
+            let canvas = Rc::clone(&self.common.raw);
+            move |_: FocusEvent| (handler.borrow_mut())(is_focus_visible(&canvas))
         }));
+        self.ime.on_focus(move |input| (handler.borrow_mut())(is_focus_visible(input)));
     }
 
-    pub fn on_keyboard_release<F>(&mut self, mut handler: F)
+    pub fn focus_with_options(&self, prevent_scroll: bool) {
+        #[wasm_bindgen]
+        extern "C" {
+            type FocusWithOptions;
+
+            #[wasm_bindgen(method, js_name = focus)]
+            fn focus_with_options(this: &FocusWithOptions, options: &Object);
+        }
+
+        let options = Object::new();
+        let _ = Reflect::set(&options, &"preventScroll".into(), &prevent_scroll.into());
+        let canvas: &FocusWithOptions = self.common.raw.unchecked_ref();
+        canvas.focus_with_options(&options);
+    }
+
+    pub fn on_keyboard_release<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
+        F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, Modifiers),
     {
+        let handler = Rc::new(RefCell::new(handler));
         let prevent_default = Rc::clone(&self.prevent_default);
-        self.on_keyboard_release =
-            Some(self.common.add_event("keyup", move |event: KeyboardEvent| {
+        self.on_keyboard_release = Some(self.common.add_event("keyup", {
+            let handler = Rc::clone(&handler);
+            move |event: KeyboardEvent| {
                 if prevent_default.get() {
                     event.prevent_default();
                 }
-                let key = event::key(&event);
-                let modifiers = event::keyboard_modifiers(&event);
-                handler(
-                    event::key_code(&event),
-                    key,
-                    event::key_text(&event),
-                    event::key_location(&event),
-                    event.repeat(),
-                    modifiers,
-                );
-            }));
+                handle_key_event(&mut *handler.borrow_mut(), &event);
+            }
+        }));
+        self.ime.on_keyboard_release(move |event| {
+            handle_key_event(&mut *handler.borrow_mut(), event);
+        });
     }
 
-    pub fn on_keyboard_press<F>(&mut self, mut handler: F)
+    pub fn on_keyboard_press<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
+        F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, Modifiers),
     {
+        let handler = Rc::new(RefCell::new(handler));
         let prevent_default = Rc::clone(&self.prevent_default);
-        self.on_keyboard_press =
-            Some(self.common.add_event("keydown", move |event: KeyboardEvent| {
+        self.on_keyboard_press = Some(self.common.add_event("keydown", {
+            let handler = Rc::clone(&handler);
+            move |event: KeyboardEvent| {
                 if prevent_default.get() {
                     event.prevent_default();
                 }
-                let key = event::key(&event);
-                let modifiers = event::keyboard_modifiers(&event);
-                handler(
-                    event::key_code(&event),
-                    key,
-                    event::key_text(&event),
-                    event::key_location(&event),
-                    event.repeat(),
-                    modifiers,
-                );
-            }));
+                handle_key_event(&mut *handler.borrow_mut(), &event);
+            }
+        }));
+        self.ime.on_keyboard_press(move |event| {
+            handle_key_event(&mut *handler.borrow_mut(), event);
+        });
+    }
+
+    pub fn on_ime<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(Ime),
+    {
+        self.ime.on_ime(handler)
     }
 
     pub fn on_cursor_leave<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(ModifiersState, Option<i32>),
+        F: 'static + FnMut(Modifiers, Option<i32>),
     {
         self.pointer_handler.on_cursor_leave(&self.common, handler)
     }
 
     pub fn on_cursor_enter<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(ModifiersState, Option<i32>),
+        F: 'static + FnMut(Modifiers, Option<i32>),
     {
         self.pointer_handler.on_cursor_enter(&self.common, handler)
     }
@@ -335,9 +527,9 @@ impl Canvas {
         mouse_handler: M,
         touch_handler: T,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
-        T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, MouseButton),
+        T: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, TouchDetails),
     {
         self.pointer_handler.on_mouse_release(
             &self.common,
@@ -353,9 +545,9 @@ impl Canvas {
         mouse_handler: M,
         touch_handler: T,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
-        T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, MouseButton),
+        T: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, TouchDetails),
     {
         self.pointer_handler.on_mouse_press(
             &self.common,
@@ -363,6 +555,7 @@ impl Canvas {
             mouse_handler,
             touch_handler,
             Rc::clone(&self.prevent_default),
+            Rc::clone(&self.focus_on_pointer_down),
         )
     }
 
@@ -373,11 +566,11 @@ impl Canvas {
         touch_handler: T,
         button_handler: B,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
         T: 'static
-            + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = (PhysicalPosition<f64>, Force)>),
-        B: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, ButtonsState, MouseButton),
+            + FnMut(Modifiers, i32, &mut dyn Iterator<Item = (PhysicalPosition<f64>, TouchDetails)>),
+        B: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, ButtonsState, MouseButton),
     {
         self.pointer_handler.on_cursor_move(
             &self.common,
@@ -386,30 +579,49 @@ impl Canvas {
             touch_handler,
             button_handler,
             Rc::clone(&self.prevent_default),
+            Rc::clone(&self.focus_on_pointer_down),
         )
     }
 
     pub fn on_touch_cancel<F>(&mut self, handler: F)
     where
-        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force),
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, TouchDetails),
     {
         self.pointer_handler.on_touch_cancel(&self.common, handler)
     }
 
-    pub fn on_mouse_wheel<F>(&mut self, mut handler: F)
+    /// Wheel events with `ctrlKey` set are passed to the `pinch_handler` if
+    /// [`Self::wheel_pinch_gesture`] is enabled.
+    pub fn on_mouse_wheel<S, P>(&mut self, mut scroll_handler: S, mut pinch_handler: P)
     where
-        F: 'static + FnMut(i32, MouseScrollDelta, ModifiersState),
+        S: 'static + FnMut(i32, MouseScrollDelta, WheelDeltaMode, Modifiers),
+        P: 'static + FnMut(i32, f64, WheelDeltaMode, Modifiers),
     {
         let window = self.common.window.clone();
         let prevent_default = Rc::clone(&self.prevent_default);
+        let policy = Rc::clone(&self.wheel_delta_policy);
+        let pinch_gesture = Rc::clone(&self.wheel_pinch_gesture);
         self.on_mouse_wheel = Some(self.common.add_event("wheel", move |event: WheelEvent| {
             if prevent_default.get() {
                 event.prevent_default();
             }
 
-            if let Some(delta) = event::mouse_scroll_delta(&window, &event) {
-                let modifiers = event::mouse_modifiers(&event);
-                handler(0, delta, modifiers);
+            let Some(mode) = event::wheel_delta_mode(&event) else {
+                return;
+            };
+            let modifiers = event::mouse_modifiers(&event);
+
+            if pinch_gesture.get() && event.ctrl_key() {
+                let delta = event::pinch_delta(event.delta_y(), mode, policy.get());
+                pinch_handler(0, delta, mode, modifiers);
+            } else {
+                let delta = event::scroll_delta(
+                    (event.delta_x(), event.delta_y()),
+                    mode,
+                    policy.get(),
+                    super::scale_factor(&window),
+                );
+                scroll_handler(0, delta, mode, modifiers);
             }
         }));
     }
@@ -440,6 +652,78 @@ impl Canvas {
         ));
     }
 
+    pub fn on_orientation_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(InterfaceOrientation),
+    {
+        let Some(orientation) = super::screen_orientation(&self.common.window) else {
+            return;
+        };
+
+        self.on_orientation_change = Some(EventListenerHandle::new(
+            orientation.clone(),
+            "change",
+            Closure::new(move |_: Event| {
+                if let Some(orientation) = super::interface_orientation(&orientation) {
+                    handler(orientation)
+                }
+            }),
+        ));
+    }
+
+    /// Called when the fullscreen element or the canvas enters or leaves fullscreen, no matter
+    /// if through [`Canvas::request_fullscreen()`] or the browser, e.g. by pressing Escape.
+    pub fn on_fullscreen_change<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalSize<u32>),
+    {
+        let document = self.common.document.clone();
+        let canvas = Rc::clone(&self.common.raw);
+        let fullscreen_element = Rc::clone(&self.fullscreen_element);
+        let current_size = Rc::clone(&self.common.current_size);
+        let mut is_fullscreen = self.is_fullscreen();
+
+        self.on_fullscreen_change = Some(EventListenerHandle::new(
+            self.common.document.clone(),
+            fullscreen::change_event(&self.common.raw),
+            Closure::new(move |_: Event| {
+                let target = fullscreen::target_element(
+                    &document,
+                    &canvas,
+                    fullscreen_element.borrow().as_ref(),
+                );
+                let new = fullscreen::is_fullscreen(&document, &canvas, &target);
+                if new != is_fullscreen {
+                    is_fullscreen = new;
+                    handler(current_size.get())
+                }
+            }),
+        ));
+    }
+
+    pub(crate) fn notify_pinch_zoom(&self) {
+        if let Some(handle) = &self.on_resize_scale {
+            handle.notify_pinch_zoom();
+        }
+    }
+
+    pub fn visual_viewport(&self) -> Option<VisualViewport> {
+        let viewport = self.common.window.visual_viewport()?;
+        // Both are relative to the layout viewport.
+        let rect = self.raw().get_bounding_client_rect();
+        let scale = super::scale_factor(&self.common.window);
+
+        Some(VisualViewport {
+            scale: viewport.scale(),
+            position: LogicalPosition::new(
+                viewport.offset_left() - rect.left(),
+                viewport.offset_top() - rect.top(),
+            )
+            .to_physical(scale),
+            size: LogicalSize::new(viewport.width(), viewport.height()).to_physical(scale),
+        })
+    }
+
     pub(crate) fn on_intersection<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(bool),
@@ -447,11 +731,58 @@ impl Canvas {
         self.on_intersect = Some(IntersectionObserverHandle::new(self.raw(), handler));
     }
 
-    pub(crate) fn on_animation_frame<F>(&mut self, f: F)
+    /// Called when the safe area of the viewport changes, which doesn't necessarily change the
+    /// part of it overlapping the canvas.
+    pub(crate) fn on_safe_area_change<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_safe_area =
+            SafeAreaHandle::new(&self.common.window, &self.common.document, handler);
+        self.safe_area.set(Some(self.safe_area()));
+    }
+
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        match &self.on_safe_area {
+            Some(handle) => handle.insets(self.raw(), super::scale_factor(&self.common.window)),
+            None => PhysicalInsets::new(0, 0, 0, 0),
+        }
+    }
+
+    /// Returns the safe area if it changed since it was last reported.
+    pub(crate) fn take_safe_area_change(&self) -> Option<PhysicalInsets<u32>> {
+        let safe_area = self.safe_area();
+        (self.safe_area.replace(Some(safe_area)) != Some(safe_area)).then_some(safe_area)
+    }
+
+    pub(crate) fn on_animation_frame<F>(&mut self, mut f: F)
     where
         F: 'static + FnMut(),
     {
-        self.animation_frame_handler.on_animation_frame(f)
+        let last_frame_time = Rc::clone(&self.last_frame_time);
+        self.animation_frame_handler.on_animation_frame(move |timestamp| {
+            // The timestamp is in milliseconds since `performance.timeOrigin`.
+            last_frame_time.set(Some(Duration::from_secs_f64(timestamp.max(0.) / 1000.)));
+            f()
+        })
+    }
+
+    /// The handler receives the generations presented since the last animation frame, and the
+    /// time of the frame.
+    pub(crate) fn on_presentation_frame<F>(&mut self, mut f: F)
+    where
+        F: 'static + FnMut(Vec<u64>, Option<Instant>),
+    {
+        let pending_presents = Rc::clone(&self.pending_presents);
+        let performance = self.common.window.performance();
+        self.presentation_frame_handler.on_animation_frame(move |timestamp| {
+            // The timestamp is in milliseconds since `performance.timeOrigin`, like `now()`.
+            let time = performance.as_ref().and_then(|performance| {
+                let elapsed = (performance.now() - timestamp).max(0.) / 1000.;
+                Instant::now().checked_sub(Duration::from_secs_f64(elapsed))
+            });
+            f(pending_presents.take(), time)
+        })
     }
 
     pub(crate) fn on_context_menu(&mut self) {
@@ -464,22 +795,155 @@ impl Canvas {
             }));
     }
 
-    pub fn request_fullscreen(&self) {
-        fullscreen::request_fullscreen(self.document(), self.raw());
+    pub(crate) fn on_before_unload<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        let confirm_close = Rc::clone(&self.confirm_close);
+        self.on_before_unload = Some(EventListenerHandle::new(
+            self.common.window.clone(),
+            "beforeunload",
+            Closure::new(move |event: BeforeUnloadEvent| {
+                handler();
+
+                if confirm_close.get() {
+                    event.prevent_default();
+                    // Older browsers only ask for confirmation if `returnValue` is set.
+                    event.set_return_value("true");
+                }
+            }),
+        ));
+    }
+
+    /// Only drags carrying files or text are handled, the handler also receives how many files.
+    pub fn on_drag_enter<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(DragDropKinds, usize, PhysicalPosition<f64>),
+    {
+        let window = self.common.window.clone();
+        self.on_drag_enter = Some(self.common.add_event("dragenter", move |event: DragEvent| {
+            let kinds = event::drag_kinds(&event);
+            if !kinds.is_empty() {
+                event.prevent_default();
+                let position =
+                    event::mouse_position(&event).to_physical(super::scale_factor(&window));
+                handler(kinds, event::drag_file_count(&event), position);
+            }
+        }));
+    }
+
+    /// The handler is only called when the position changed, as `dragover` fires continuously.
+    pub fn on_drag_over<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalPosition<f64>),
+    {
+        let window = self.common.window.clone();
+        let last_position = Cell::new(None);
+        self.on_drag_over = Some(self.common.add_event("dragover", move |event: DragEvent| {
+            if event::drag_kinds(&event).is_empty() {
+                return;
+            }
+
+            // Otherwise the browser opens the files instead of dropping them.
+            event.prevent_default();
+
+            let position = event::mouse_position(&event).to_physical(super::scale_factor(&window));
+            if last_position.replace(Some(position)) != Some(position) {
+                handler(position);
+            }
+        }));
+    }
+
+    pub fn on_drag_leave<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(DragDropKinds),
+    {
+        self.on_drag_leave = Some(self.common.add_event("dragleave", move |event: DragEvent| {
+            let kinds = event::drag_kinds(&event);
+            if !kinds.is_empty() {
+                handler(kinds);
+            }
+        }));
+    }
+
+    /// The dropped files are stored in [`Canvas::dropped_files`] before the handler is called with
+    /// their names, dropped text is only read when no files are dropped.
+    pub(crate) fn on_drop<F>(&mut self, main_thread: MainThreadMarker, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalPosition<f64>, DragDropKinds, Vec<String>, Option<String>),
+    {
+        let window = self.common.window.clone();
+        let dropped_files = Rc::clone(&self.dropped_files);
+        self.on_drop = Some(self.common.add_event("drop", move |event: DragEvent| {
+            let kinds = event::drag_kinds(&event);
+            if kinds.is_empty() {
+                return;
+            }
+
+            event.prevent_default();
+
+            let files: Vec<_> = event::dropped_files(&event)
+                .into_iter()
+                .map(|file| DroppedFile::new(main_thread, file))
+                .collect();
+            let names = files.iter().map(|file| file.name().to_owned()).collect();
+            *dropped_files.borrow_mut() = files;
+
+            let text =
+                if kinds == DragDropKinds::TEXT { event::dropped_text(&event) } else { None };
+
+            let position = event::mouse_position(&event).to_physical(super::scale_factor(&window));
+            handler(position, kinds, names, text);
+        }));
+    }
+
+    pub fn request_fullscreen(&self, screen: Option<&JsValue>) {
+        fullscreen::request_fullscreen(
+            self.document(),
+            &self.fullscreen_target(),
+            self.fullscreen_navigation_ui.get(),
+            screen,
+        );
     }
 
     pub fn exit_fullscreen(&self) {
-        fullscreen::exit_fullscreen(self.document(), self.raw());
+        if self.is_fullscreen() {
+            fullscreen::exit_fullscreen(self.document(), self.raw());
+        }
     }
 
     pub fn is_fullscreen(&self) -> bool {
-        fullscreen::is_fullscreen(self.document(), self.raw())
+        fullscreen::is_fullscreen(self.document(), self.raw(), &self.fullscreen_target())
+    }
+
+    fn fullscreen_target(&self) -> web_sys::Element {
+        fullscreen::target_element(
+            self.document(),
+            self.raw(),
+            self.fullscreen_element.borrow().as_ref(),
+        )
     }
 
     pub fn request_animation_frame(&self) {
         self.animation_frame_handler.request();
     }
 
+    /// Returns `true` if a request was pending.
+    pub fn cancel_animation_frame(&mut self) -> bool {
+        self.animation_frame_handler.cancel()
+    }
+
+    pub fn pre_present_notify(&self) {
+        let generation = self.present_generation.get() + 1;
+        self.present_generation.set(generation);
+        if self.presentation_feedback.get() {
+            self.pending_presents.borrow_mut().push(generation);
+            if !self.presentation_frame_handler.is_pending() {
+                self.presentation_frame_handler.request();
+            }
+        }
+    }
+
     pub(crate) fn handle_scale_change(
         &self,
         runner: &super::super::event_loop::runner::Shared,
@@ -500,7 +964,12 @@ impl Canvas {
             });
 
             let new_size = *new_size.lock().unwrap();
-            new_size
+            // The size of the canvas follows its parent, so it can't be changed here.
+            if self.auto_parent_size {
+                current_size
+            } else {
+                new_size
+            }
         };
 
         if current_size != new_size {
@@ -514,13 +983,23 @@ impl Canvas {
                 .as_ref()
                 .expect("expected Window to still be active")
                 .notify_resize();
-        } else if self.old_size() != new_size {
-            // Then we at least send a resized event.
-            self.set_old_size(new_size);
-            runner.send_event(crate::event::Event::WindowEvent {
-                window_id: RootWindowId(self.id),
-                event: crate::event::WindowEvent::Resized(new_size),
-            })
+        } else {
+            if self.old_size() != new_size {
+                // Then we at least send a resized event.
+                self.set_old_size(new_size);
+                runner.send_event(crate::event::Event::WindowEvent {
+                    window_id: RootWindowId(self.id),
+                    event: crate::event::WindowEvent::Resized(new_size),
+                })
+            }
+
+            // The insets are in physical pixels, so they change with the scale factor.
+            if let Some(safe_area) = self.take_safe_area_change() {
+                runner.send_event(crate::event::Event::WindowEvent {
+                    window_id: RootWindowId(self.id),
+                    event: crate::event::WindowEvent::SafeAreaChanged(safe_area),
+                })
+            }
         }
     }
 
@@ -535,9 +1014,48 @@ impl Canvas {
         self.pointer_handler.remove_listeners();
         self.on_resize_scale = None;
         self.on_intersect = None;
+        self.on_safe_area = None;
         self.animation_frame_handler.cancel();
         self.on_touch_end = None;
         self.on_context_menu = None;
+        self.on_before_unload = None;
+        self.on_drag_enter = None;
+        self.on_drag_over = None;
+        self.on_drag_leave = None;
+        self.on_drop = None;
+        self.on_orientation_change = None;
+        self.on_fullscreen_change = None;
+        self.ime.remove_listeners();
+    }
+}
+
+fn handle_key_event<F>(handler: &mut F, event: &KeyboardEvent)
+where
+    F: FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, Modifiers),
+{
+    let key = event::key(event);
+    let modifiers = event::keyboard_modifiers(event);
+    handler(
+        event::key_code(event),
+        key,
+        event::key_text(event),
+        event::key_location(event),
+        event.repeat(),
+        modifiers,
+    );
+}
+
+/// Returns `false` if the browser doesn't support `:focus-visible`.
+fn is_focus_visible(element: &Element) -> bool {
+    element.matches(":focus-visible").unwrap_or(false)
+}
+
+/// The page keeps focus if it moved to another element, but not if the browser window lost it.
+fn focus_loss(document: &Document) -> FocusLoss {
+    if document.has_focus().unwrap_or(true) {
+        FocusLoss::Page
+    } else {
+        FocusLoss::Window
     }
 }
 
@@ -560,16 +1078,16 @@ impl Common {
 }
 
 impl Style {
-    fn new(window: &web_sys::Window, canvas: &HtmlCanvasElement) -> Self {
+    pub(crate) fn new(window: &web_sys::Window, element: &HtmlElement) -> Self {
         #[allow(clippy::disallowed_methods)]
         let read = window
-            .get_computed_style(canvas)
+            .get_computed_style(element)
             .expect("Failed to obtain computed style")
             // this can't fail: we aren't using a pseudo-element
             .expect("Invalid pseudo-element");
 
         #[allow(clippy::disallowed_methods)]
-        let write = canvas.style();
+        let write = element.style();
 
         Self { read, write }
     }
@@ -1,12 +1,13 @@
-use crate::event::{MouseButton, MouseScrollDelta};
-use crate::keyboard::{Key, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
+use crate::event::{DragDropKinds, Force, Modifiers, MouseButton, MouseScrollDelta, TouchTool};
+use crate::keyboard::{Key, KeyLocation, LockKeys, ModifiersState, NamedKey, PhysicalKey};
+use crate::platform::web::{WheelDeltaMode, WheelDeltaPolicy};
 
 use dpi::{LogicalPosition, PhysicalPosition};
 use smol_str::SmolStr;
 use std::cell::OnceCell;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{KeyboardEvent, MouseEvent, PointerEvent, WheelEvent};
+use web_sys::{DragEvent, File, KeyboardEvent, MouseEvent, PointerEvent, WheelEvent};
 
 bitflags::bitflags! {
     // https://www.w3.org/TR/pointerevents3/#the-buttons-property
@@ -116,23 +117,80 @@ impl MouseDelta {
     }
 }
 
+/// The line height used to convert pinch gestures in line mode with [`WheelDeltaPolicy::Raw`],
+/// which makes three lines, one notch of a mouse wheel in Firefox, equal to one notch in
+/// Chromium.
+const PINCH_LINE_HEIGHT: f64 = 100. / 3.;
+const PINCH_PAGE_HEIGHT: f64 = 30. * PINCH_LINE_HEIGHT;
+
+pub fn wheel_delta_mode(event: &WheelEvent) -> Option<WheelDeltaMode> {
+    match event.delta_mode() {
+        WheelEvent::DOM_DELTA_PIXEL => Some(WheelDeltaMode::Pixel),
+        WheelEvent::DOM_DELTA_LINE => Some(WheelDeltaMode::Line),
+        WheelEvent::DOM_DELTA_PAGE => Some(WheelDeltaMode::Page),
+        _ => None,
+    }
+}
+
 pub fn mouse_scroll_delta(
     window: &web_sys::Window,
     event: &WheelEvent,
 ) -> Option<MouseScrollDelta> {
-    let x = -event.delta_x();
-    let y = -event.delta_y();
+    let mode = wheel_delta_mode(event)?;
+    Some(scroll_delta(
+        (event.delta_x(), event.delta_y()),
+        mode,
+        WheelDeltaPolicy::Raw,
+        super::scale_factor(window),
+    ))
+}
 
-    match event.delta_mode() {
-        WheelEvent::DOM_DELTA_LINE => Some(MouseScrollDelta::LineDelta(x as f32, y as f32)),
-        WheelEvent::DOM_DELTA_PIXEL => {
-            let delta = LogicalPosition::new(x, y).to_physical(super::scale_factor(window));
-            Some(MouseScrollDelta::PixelDelta(delta))
+/// Converts the `deltaX` and `deltaY` of a wheel event, which are in the unit of `mode`.
+pub fn scroll_delta(
+    (x, y): (f64, f64),
+    mode: WheelDeltaMode,
+    policy: WheelDeltaPolicy,
+    scale_factor: f64,
+) -> MouseScrollDelta {
+    let (x, y) = (-x, -y);
+
+    match (mode, policy) {
+        (WheelDeltaMode::Pixel, _) => {
+            MouseScrollDelta::PixelDelta(LogicalPosition::new(x, y).to_physical(scale_factor))
         },
-        _ => None,
+        (mode, WheelDeltaPolicy::NormalizeToPixels { line_height, page_height }) => {
+            let height = match mode {
+                WheelDeltaMode::Line => line_height,
+                _ => page_height,
+            };
+            let delta = LogicalPosition::new(x * height, y * height);
+            MouseScrollDelta::PixelDelta(delta.to_physical(scale_factor))
+        },
+        (_, WheelDeltaPolicy::Raw) => MouseScrollDelta::LineDelta(x as f32, y as f32),
     }
 }
 
+/// Converts the `deltaY` of a wheel event with `ctrlKey` set to the delta of
+/// [`WindowEvent::PinchGesture`](crate::event::WindowEvent::PinchGesture).
+///
+/// Chromium and Firefox report trackpad pinch gestures as such wheel events, with `deltaY` set to
+/// `-100 * ln(scale)` in pixels, which is reversed here.
+pub fn pinch_delta(y: f64, mode: WheelDeltaMode, policy: WheelDeltaPolicy) -> f64 {
+    let (line_height, page_height) = match policy {
+        WheelDeltaPolicy::Raw => (PINCH_LINE_HEIGHT, PINCH_PAGE_HEIGHT),
+        WheelDeltaPolicy::NormalizeToPixels { line_height, page_height } => {
+            (line_height, page_height)
+        },
+    };
+    let pixels = match mode {
+        WheelDeltaMode::Pixel => y,
+        WheelDeltaMode::Line => y * line_height,
+        WheelDeltaMode::Page => y * page_height,
+    };
+
+    (-pixels / 100.).exp() - 1.
+}
+
 pub fn key_code(event: &KeyboardEvent) -> PhysicalKey {
     let code = event.code();
     PhysicalKey::from_key_code_attribute_value(&code)
@@ -168,7 +226,7 @@ pub fn key_location(event: &KeyboardEvent) -> KeyLocation {
     }
 }
 
-pub fn keyboard_modifiers(event: &KeyboardEvent) -> ModifiersState {
+pub fn keyboard_modifiers(event: &KeyboardEvent) -> Modifiers {
     let mut state = ModifiersState::empty();
 
     if event.shift_key() {
@@ -184,10 +242,14 @@ pub fn keyboard_modifiers(event: &KeyboardEvent) -> ModifiersState {
         state |= ModifiersState::SUPER;
     }
 
-    state
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(LockKeys::CAPS_LOCK, event.get_modifier_state("CapsLock"));
+    lock_keys.set(LockKeys::NUM_LOCK, event.get_modifier_state("NumLock"));
+
+    Modifiers { lock_keys, ..state.into() }
 }
 
-pub fn mouse_modifiers(event: &MouseEvent) -> ModifiersState {
+pub fn mouse_modifiers(event: &MouseEvent) -> Modifiers {
     let mut state = ModifiersState::empty();
 
     if event.shift_key() {
@@ -203,7 +265,74 @@ pub fn mouse_modifiers(event: &MouseEvent) -> ModifiersState {
         state |= ModifiersState::SUPER;
     }
 
-    state
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(LockKeys::CAPS_LOCK, event.get_modifier_state("CapsLock"));
+    lock_keys.set(LockKeys::NUM_LOCK, event.get_modifier_state("NumLock"));
+
+    Modifiers { lock_keys, ..state.into() }
+}
+
+/// The properties of touch and pen input that are reported in [`Touch`](crate::event::Touch).
+#[derive(Clone, Copy, Debug)]
+pub struct TouchDetails {
+    pub force: Force,
+    pub tool: TouchTool,
+    pub azimuth_angle: Option<f64>,
+    pub altitude_angle: Option<f64>,
+    pub twist_angle: Option<f64>,
+}
+
+pub fn touch_details(event: &PointerEvent) -> TouchDetails {
+    let force = Force::Normalized(event.pressure() as f64);
+
+    if event.pointer_type() != "pen" {
+        return TouchDetails {
+            force,
+            tool: TouchTool::Finger,
+            azimuth_angle: None,
+            altitude_angle: None,
+            twist_angle: None,
+        };
+    }
+
+    let (altitude_angle, azimuth_angle) = tilt_to_spherical(event.tilt_x(), event.tilt_y());
+    TouchDetails {
+        force,
+        tool: TouchTool::Stylus,
+        azimuth_angle: Some(azimuth_angle),
+        altitude_angle: Some(altitude_angle),
+        twist_angle: Some((event.twist() as f64).to_radians()),
+    }
+}
+
+/// Converts `tiltX` and `tiltY` to the altitude and azimuth of a pen.
+///
+/// `altitudeAngle` and `azimuthAngle` aren't used, as they aren't available in all browsers.
+/// See <https://w3c.github.io/pointerevents/#converting-between-tiltx-tilty-and-altitudeangle-azimuthangle>.
+fn tilt_to_spherical(tilt_x: i32, tilt_y: i32) -> (f64, f64) {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    let tilt_x_rad = (tilt_x as f64).to_radians();
+    let tilt_y_rad = (tilt_y as f64).to_radians();
+
+    if tilt_x.abs() == 90 || tilt_y.abs() == 90 {
+        return (0., 0.);
+    }
+
+    match (tilt_x, tilt_y) {
+        (0, 0) => (FRAC_PI_2, 0.),
+        (0, _) => {
+            (FRAC_PI_2 - tilt_y_rad.abs(), if tilt_y > 0 { FRAC_PI_2 } else { 3. * FRAC_PI_2 })
+        },
+        (_, 0) => (FRAC_PI_2 - tilt_x_rad.abs(), if tilt_x > 0 { 0. } else { PI }),
+        _ => {
+            let tan_x = tilt_x_rad.tan();
+            let tan_y = tilt_y_rad.tan();
+            let altitude = (1. / tan_x.hypot(tan_y)).atan();
+            let azimuth = tan_y.atan2(tan_x).rem_euclid(2. * PI);
+            (altitude, azimuth)
+        },
+    }
 }
 
 pub fn pointer_move_event(event: PointerEvent) -> impl Iterator<Item = PointerEvent> {
@@ -241,3 +370,115 @@ pub fn has_coalesced_events_support(event: &PointerEvent) -> bool {
         })
     })
 }
+
+/// The kinds of the dragged data handled by Winit, files take precedence over text.
+pub fn drag_kinds(event: &DragEvent) -> DragDropKinds {
+    let Some(data_transfer) = event.data_transfer() else {
+        return DragDropKinds::empty();
+    };
+
+    let types = data_transfer.types();
+    if types.iter().any(|kind| kind == "Files") {
+        DragDropKinds::PATHS
+    } else if types.iter().any(|kind| kind == "text/plain") {
+        DragDropKinds::TEXT
+    } else {
+        DragDropKinds::empty()
+    }
+}
+
+/// The dropped text, only available once dropped.
+pub fn dropped_text(event: &DragEvent) -> Option<String> {
+    event.data_transfer()?.get_data("text/plain").ok()
+}
+
+/// The number of files being dragged, which browsers only expose before the drop.
+pub fn drag_file_count(event: &DragEvent) -> usize {
+    let Some(items) = event.data_transfer().map(|data_transfer| data_transfer.items()) else {
+        return 0;
+    };
+
+    (0..items.length())
+        .filter_map(|index| items.get(index))
+        .filter(|item| item.kind() == "file")
+        .count()
+}
+
+/// The dropped files. Directories are skipped, as reading their content is only possible
+/// asynchronously.
+pub fn dropped_files(event: &DragEvent) -> Vec<File> {
+    #[wasm_bindgen]
+    extern "C" {
+        type DataTransferItemExt;
+
+        #[wasm_bindgen(method, getter, js_name = webkitGetAsEntry)]
+        fn has_webkit_get_as_entry(this: &DataTransferItemExt) -> JsValue;
+
+        #[wasm_bindgen(method, js_name = webkitGetAsEntry)]
+        fn webkit_get_as_entry(this: &DataTransferItemExt) -> Option<FileSystemEntry>;
+
+        type FileSystemEntry;
+
+        #[wasm_bindgen(method, getter, js_name = isDirectory)]
+        fn is_directory(this: &FileSystemEntry) -> bool;
+    }
+
+    let Some(items) = event.data_transfer().map(|data_transfer| data_transfer.items()) else {
+        return Vec::new();
+    };
+
+    (0..items.length())
+        .filter_map(|index| items.get(index))
+        .filter(|item| item.kind() == "file")
+        .filter(|item| {
+            let item: &DataTransferItemExt = item.unchecked_ref();
+            item.has_webkit_get_as_entry().is_undefined()
+                || !item.webkit_get_as_entry().is_some_and(|entry| entry.is_directory())
+        })
+        .filter_map(|item| item.get_as_file().ok().flatten())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NORMALIZE: WheelDeltaPolicy =
+        WheelDeltaPolicy::NormalizeToPixels { line_height: 16., page_height: 800. };
+
+    #[test]
+    fn chromium_pixel_mode() {
+        // One notch of a mouse wheel scrolling down.
+        let delta = scroll_delta((0., 100.), WheelDeltaMode::Pixel, WheelDeltaPolicy::Raw, 2.);
+        assert_eq!(delta, MouseScrollDelta::PixelDelta(PhysicalPosition::new(0., -200.)));
+        assert_eq!(scroll_delta((0., 100.), WheelDeltaMode::Pixel, NORMALIZE, 2.), delta);
+
+        // Pinching out by 10% on a trackpad.
+        let y = -100. * 1.1f64.ln();
+        let delta = pinch_delta(y, WheelDeltaMode::Pixel, WheelDeltaPolicy::Raw);
+        assert!((delta - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn firefox_line_mode() {
+        // One notch of a mouse wheel scrolling down.
+        let delta = scroll_delta((0., 3.), WheelDeltaMode::Line, WheelDeltaPolicy::Raw, 2.);
+        assert_eq!(delta, MouseScrollDelta::LineDelta(0., -3.));
+        let delta = scroll_delta((0., 3.), WheelDeltaMode::Line, NORMALIZE, 2.);
+        assert_eq!(delta, MouseScrollDelta::PixelDelta(PhysicalPosition::new(0., -96.)));
+
+        // Zooming matches one notch in Chromium.
+        let line = pinch_delta(3., WheelDeltaMode::Line, WheelDeltaPolicy::Raw);
+        let pixel = pinch_delta(100., WheelDeltaMode::Pixel, WheelDeltaPolicy::Raw);
+        assert!((line - pixel).abs() < 1e-9);
+        assert!(line < 0.);
+    }
+
+    #[test]
+    fn page_mode() {
+        let delta = scroll_delta((-1., 0.), WheelDeltaMode::Page, WheelDeltaPolicy::Raw, 1.);
+        assert_eq!(delta, MouseScrollDelta::LineDelta(1., 0.));
+        let delta = scroll_delta((-1., 0.), WheelDeltaMode::Page, NORMALIZE, 1.);
+        assert_eq!(delta, MouseScrollDelta::PixelDelta(PhysicalPosition::new(800., 0.)));
+    }
+}
@@ -1,69 +1,120 @@
 use std::cell::OnceCell;
 
-use js_sys::Promise;
+use js_sys::{Object, Promise, Reflect};
+use tracing::warn;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{Document, Element, HtmlCanvasElement};
 
-pub fn request_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
-    if is_fullscreen(document, canvas) {
+use crate::platform::web::{FullscreenElement, FullscreenNavigationUi};
+
+/// The element to make fullscreen, which is the canvas if no element matches the selector.
+pub fn target_element(
+    document: &Document,
+    canvas: &HtmlCanvasElement,
+    target: Option<&FullscreenElement>,
+) -> Element {
+    match target {
+        Some(FullscreenElement::Element(element)) => element.clone().into(),
+        Some(FullscreenElement::Selector(selector)) => match document.query_selector(selector) {
+            Ok(Some(element)) => element,
+            _ => {
+                warn!("no element matches the fullscreen selector `{selector}`, using the canvas");
+                canvas.clone().into()
+            },
+        },
+        None => canvas.clone().into(),
+    }
+}
+
+/// `screen` is the `ScreenDetailed` of the Window Management API to show the element on.
+pub fn request_fullscreen(
+    document: &Document,
+    element: &Element,
+    navigation_ui: FullscreenNavigationUi,
+    screen: Option<&JsValue>,
+) {
+    if fullscreen_element(document, element).as_ref() == Some(element) {
         return;
     }
 
     #[wasm_bindgen]
     extern "C" {
-        #[wasm_bindgen(extends = HtmlCanvasElement)]
+        #[wasm_bindgen(extends = Element)]
         type RequestFullscreen;
 
         #[wasm_bindgen(method, js_name = requestFullscreen)]
-        fn request_fullscreen(this: &RequestFullscreen) -> Promise;
+        fn request_fullscreen_with_options(this: &RequestFullscreen, options: &Object) -> Promise;
 
         #[wasm_bindgen(method, js_name = webkitRequestFullscreen)]
         fn webkit_request_fullscreen(this: &RequestFullscreen);
     }
 
-    let canvas: &RequestFullscreen = canvas.unchecked_ref();
+    let element: &RequestFullscreen = element.unchecked_ref();
 
-    if has_fullscreen_api_support(canvas) {
+    if has_fullscreen_api_support(element) {
         thread_local! {
             static REJECT_HANDLER: Closure<dyn FnMut(JsValue)> = Closure::new(|_| ());
         }
+        let options = Object::new();
+        let navigation_ui = match navigation_ui {
+            FullscreenNavigationUi::Auto => "auto",
+            FullscreenNavigationUi::Show => "show",
+            FullscreenNavigationUi::Hide => "hide",
+        };
+        let _ = Reflect::set(&options, &"navigationUI".into(), &navigation_ui.into());
+        if let Some(screen) = screen {
+            let _ = Reflect::set(&options, &"screen".into(), screen);
+        }
+        let promise = element.request_fullscreen_with_options(&options);
         REJECT_HANDLER.with(|handler| {
-            let _ = canvas.request_fullscreen().catch(handler);
+            let _ = promise.catch(handler);
         });
     } else {
-        canvas.webkit_request_fullscreen();
+        element.webkit_request_fullscreen();
     }
 }
 
-pub fn is_fullscreen(document: &Document, canvas: &HtmlCanvasElement) -> bool {
+/// Returns `true` if either `target` or the canvas is fullscreen.
+pub fn is_fullscreen(document: &Document, canvas: &HtmlCanvasElement, target: &Element) -> bool {
+    match fullscreen_element(document, target) {
+        Some(element) => {
+            let canvas: &Element = canvas;
+            &element == target || &element == canvas
+        },
+        None => false,
+    }
+}
+
+/// The name of the event fired on the document when an element enters or leaves fullscreen.
+pub fn change_event(element: &Element) -> &'static str {
+    if has_fullscreen_api_support(element) {
+        "fullscreenchange"
+    } else {
+        "webkitfullscreenchange"
+    }
+}
+
+fn fullscreen_element(document: &Document, element: &Element) -> Option<Element> {
     #[wasm_bindgen]
     extern "C" {
-        type FullscreenElement;
+        type DocumentFullscreenElement;
 
         #[wasm_bindgen(method, getter, js_name = webkitFullscreenElement)]
-        fn webkit_fullscreen_element(this: &FullscreenElement) -> Option<Element>;
+        fn webkit_fullscreen_element(this: &DocumentFullscreenElement) -> Option<Element>;
     }
 
-    let element = if has_fullscreen_api_support(canvas) {
+    if has_fullscreen_api_support(element) {
         #[allow(clippy::disallowed_methods)]
         document.fullscreen_element()
     } else {
-        let document: &FullscreenElement = document.unchecked_ref();
+        let document: &DocumentFullscreenElement = document.unchecked_ref();
         document.webkit_fullscreen_element()
-    };
-
-    match element {
-        Some(element) => {
-            let canvas: &Element = canvas;
-            canvas == &element
-        },
-        None => false,
     }
 }
 
-pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
+pub fn exit_fullscreen(document: &Document, element: &Element) {
     #[wasm_bindgen]
     extern "C" {
         type ExitFullscreen;
@@ -72,7 +123,7 @@ pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
         fn webkit_exit_fullscreen(this: &ExitFullscreen);
     }
 
-    if has_fullscreen_api_support(canvas) {
+    if has_fullscreen_api_support(element) {
         #[allow(clippy::disallowed_methods)]
         document.exit_fullscreen()
     } else {
@@ -81,7 +132,7 @@ pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
     }
 }
 
-fn has_fullscreen_api_support(canvas: &HtmlCanvasElement) -> bool {
+fn has_fullscreen_api_support(element: &Element) -> bool {
     thread_local! {
         static FULLSCREEN_API_SUPPORT: OnceCell<bool> = const { OnceCell::new() };
     }
@@ -90,13 +141,13 @@ fn has_fullscreen_api_support(canvas: &HtmlCanvasElement) -> bool {
         *support.get_or_init(|| {
             #[wasm_bindgen]
             extern "C" {
-                type CanvasFullScreenApiSupport;
+                type ElementFullScreenApiSupport;
 
                 #[wasm_bindgen(method, getter, js_name = requestFullscreen)]
-                fn has_request_fullscreen(this: &CanvasFullScreenApiSupport) -> JsValue;
+                fn has_request_fullscreen(this: &ElementFullScreenApiSupport) -> JsValue;
             }
 
-            let support: &CanvasFullScreenApiSupport = canvas.unchecked_ref();
+            let support: &ElementFullScreenApiSupport = element.unchecked_ref();
             !support.has_request_fullscreen().is_undefined()
         })
     })
@@ -0,0 +1,316 @@
+//! IME support through a hidden `<input>` element.
+//!
+//! A canvas can't receive text input, so while IME is allowed the focus is moved to a hidden
+//! `<input>` element placed over the IME cursor area. This also makes mobile browsers show their
+//! virtual keyboard. Keyboard events on the input are forwarded to the handlers of the canvas,
+//! except the ones the IME handles, and composition events are turned into [`Ime`] events.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CompositionEvent, CssStyleDeclaration, Document, Element, EventTarget, FocusEvent,
+    HtmlCanvasElement, HtmlInputElement, InputEvent, KeyboardEvent,
+};
+
+use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::event::Ime;
+use crate::window::ImePurpose;
+
+use super::event_handle::EventListenerHandle;
+
+/// The `keyCode` browsers report for key presses that are handled by the IME.
+const IME_KEY_CODE: u32 = 229;
+
+pub struct ImeHandler {
+    document: Document,
+    canvas: HtmlCanvasElement,
+    input: HtmlInputElement,
+    style: CssStyleDeclaration,
+    allowed: Rc<Cell<bool>>,
+    area: Cell<(LogicalPosition<f64>, LogicalSize<f64>)>,
+    /// Whether a key press was handled by the IME, which means the text it inserts has to be
+    /// committed.
+    pending_input: Rc<Cell<bool>>,
+    _on_canvas_focus: EventListenerHandle<dyn FnMut(FocusEvent)>,
+    on_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
+    on_keyboard_press: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_keyboard_release: Option<EventListenerHandle<dyn FnMut(KeyboardEvent)>>,
+    on_composition_update: Option<EventListenerHandle<dyn FnMut(CompositionEvent)>>,
+    on_composition_end: Option<EventListenerHandle<dyn FnMut(CompositionEvent)>>,
+    on_input: Option<EventListenerHandle<dyn FnMut(InputEvent)>>,
+}
+
+impl ImeHandler {
+    pub fn new(document: Document, canvas: HtmlCanvasElement) -> Self {
+        let input: HtmlInputElement = document
+            .create_element("input")
+            .expect("Failed to create input element")
+            .unchecked_into();
+        let _ = input.set_attribute("autocomplete", "off");
+        let _ = input.set_attribute("autocapitalize", "off");
+        let _ = input.set_attribute("spellcheck", "false");
+        let _ = input.set_attribute("aria-hidden", "true");
+
+        // The style is cached, like the one of the canvas.
+        #[allow(clippy::disallowed_methods)]
+        let style = input.style();
+        for (property, value) in [
+            ("position", "fixed"),
+            ("opacity", "0"),
+            ("pointer-events", "none"),
+            ("border", "none"),
+            ("padding", "0"),
+            ("margin", "0"),
+            ("outline", "none"),
+            // Safari on iOS zooms in on inputs with a smaller font size.
+            ("font-size", "16px"),
+        ] {
+            let _ = style.set_property(property, value);
+        }
+
+        let allowed = Rc::new(Cell::new(false));
+        let on_canvas_focus = EventListenerHandle::new(canvas.clone(), "focus", {
+            let allowed = Rc::clone(&allowed);
+            let input = input.clone();
+            Closure::new(move |_: FocusEvent| {
+                if allowed.get() {
+                    let _ = input.focus();
+                }
+            })
+        });
+
+        Self {
+            document,
+            canvas,
+            input,
+            style,
+            allowed,
+            area: Cell::new((LogicalPosition::default(), LogicalSize::default())),
+            pending_input: Rc::new(Cell::new(false)),
+            _on_canvas_focus: on_canvas_focus,
+            on_focus: None,
+            on_blur: None,
+            on_keyboard_press: None,
+            on_keyboard_release: None,
+            on_composition_update: None,
+            on_composition_end: None,
+            on_input: None,
+        }
+    }
+
+    /// The input, to compare with the target of events.
+    pub fn input_target(&self) -> EventTarget {
+        self.input.clone().into()
+    }
+
+    /// The handler receives the input, which is the focused element.
+    pub fn on_focus<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(&Element),
+    {
+        let input = self.input.clone();
+        self.on_focus = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "focus",
+            Closure::new(move |_: FocusEvent| handler(&input)),
+        ));
+    }
+
+    pub fn on_blur<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        let canvas = self.canvas.clone();
+        self.on_blur = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "blur",
+            Closure::new(move |event: FocusEvent| {
+                // Moving the focus back to the canvas doesn't unfocus the window.
+                let canvas: &EventTarget = &canvas;
+                if event.related_target().as_ref() != Some(canvas) {
+                    handler()
+                }
+            }),
+        ));
+    }
+
+    /// Unlike on the canvas, the default behavior of key presses isn't prevented, as the input
+    /// needs them to receive text.
+    pub fn on_keyboard_press<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(&KeyboardEvent),
+    {
+        let pending_input = Rc::clone(&self.pending_input);
+        self.on_keyboard_press = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "keydown",
+            Closure::new(move |event: KeyboardEvent| {
+                if event.is_composing() || event.key_code() == IME_KEY_CODE {
+                    pending_input.set(true);
+                } else {
+                    handler(&event)
+                }
+            }),
+        ));
+    }
+
+    pub fn on_keyboard_release<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(&KeyboardEvent),
+    {
+        self.on_keyboard_release = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "keyup",
+            Closure::new(move |event: KeyboardEvent| {
+                if !event.is_composing() && event.key_code() != IME_KEY_CODE {
+                    handler(&event)
+                }
+            }),
+        ));
+    }
+
+    pub fn on_ime<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(Ime),
+    {
+        let handler = Rc::new(RefCell::new(handler));
+
+        self.on_composition_update = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "compositionupdate",
+            Closure::new({
+                let handler = Rc::clone(&handler);
+                move |event: CompositionEvent| {
+                    let text = event.data().unwrap_or_default();
+                    // The browser doesn't tell where the cursor is in the composed text.
+                    let cursor = text.len();
+                    (handler.borrow_mut())(Ime::Preedit(text, Some((cursor, cursor))));
+                }
+            }),
+        ));
+
+        let input = self.input.clone();
+        let pending_input = Rc::clone(&self.pending_input);
+        self.on_composition_end = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "compositionend",
+            Closure::new({
+                let handler = Rc::clone(&handler);
+                move |event: CompositionEvent| {
+                    let mut handler = handler.borrow_mut();
+                    handler(Ime::Preedit(String::new(), None));
+                    if let Some(text) = event.data().filter(|text| !text.is_empty()) {
+                        handler(Ime::Commit(text));
+                    }
+
+                    pending_input.set(false);
+                    input.set_value("");
+                }
+            }),
+        ));
+
+        let input = self.input.clone();
+        let pending_input = Rc::clone(&self.pending_input);
+        self.on_input = Some(EventListenerHandle::new(
+            self.input.clone(),
+            "input",
+            Closure::new(move |event: InputEvent| {
+                if event.is_composing() {
+                    return;
+                }
+
+                // Text typed without composition, e.g. on virtual keyboards, isn't part of any
+                // key press that was reported.
+                if pending_input.replace(false) {
+                    if let Some(text) = event.data().filter(|text| !text.is_empty()) {
+                        (handler.borrow_mut())(Ime::Commit(text));
+                    }
+                }
+
+                input.set_value("");
+            }),
+        ));
+    }
+
+    /// Returns `true` if the state changed.
+    pub fn set_allowed(&self, allowed: bool) -> bool {
+        if self.allowed.replace(allowed) == allowed {
+            return false;
+        }
+
+        if allowed {
+            if let Some(body) = self.document.body() {
+                let _ = body.append_child(&self.input);
+            }
+            self.update_position();
+
+            let canvas: &web_sys::Element = &self.canvas;
+            if self.document.active_element().as_ref() == Some(canvas) {
+                let _ = self.input.focus();
+            }
+        } else {
+            let input: &web_sys::Element = &self.input;
+            if self.document.active_element().as_ref() == Some(input) {
+                let _ = self.canvas.focus();
+            }
+
+            self.pending_input.set(false);
+            self.input.set_value("");
+            self.input.remove();
+        }
+
+        true
+    }
+
+    pub fn set_cursor_area(&self, position: LogicalPosition<f64>, size: LogicalSize<f64>) {
+        self.area.set((position, size));
+        if self.allowed.get() {
+            self.update_position();
+        }
+    }
+
+    pub fn set_purpose(&self, purpose: ImePurpose) {
+        let (kind, input_mode) = match purpose {
+            ImePurpose::Password => ("password", "text"),
+            ImePurpose::Email => ("email", "email"),
+            // `type="number"` doesn't report composition, only the mode of the keyboard changes.
+            ImePurpose::Number => ("text", "decimal"),
+            ImePurpose::Normal | ImePurpose::Terminal => ("text", "text"),
+        };
+        self.input.set_type(kind);
+        let _ = self.input.set_attribute("inputmode", input_mode);
+    }
+
+    fn update_position(&self) {
+        let (position, size) = self.area.get();
+        let rect = self.canvas.get_bounding_client_rect();
+        for (property, value) in [
+            ("left", rect.left() + position.x),
+            ("top", rect.top() + position.y),
+            ("width", size.width.max(1.)),
+            ("height", size.height.max(1.)),
+        ] {
+            let _ = self.style.set_property(property, &format!("{value}px"));
+        }
+    }
+
+    pub fn remove_listeners(&mut self) {
+        self.on_focus = None;
+        self.on_blur = None;
+        self.on_keyboard_press = None;
+        self.on_keyboard_release = None;
+        self.on_composition_update = None;
+        self.on_composition_end = None;
+        self.on_input = None;
+    }
+}
+
+impl Drop for ImeHandler {
+    fn drop(&mut self) {
+        self.input.remove();
+    }
+}
@@ -0,0 +1,47 @@
+use js_sys::{Array, Promise};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+
+#[wasm_bindgen]
+extern "C" {
+    type WindowExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn navigator(this: &WindowExt) -> NavigatorExt;
+
+    type NavigatorExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn keyboard(this: &NavigatorExt) -> Option<Keyboard>;
+
+    type Keyboard;
+
+    #[wasm_bindgen(method, getter, js_name = lock)]
+    fn has_lock(this: &Keyboard) -> JsValue;
+
+    #[wasm_bindgen(method)]
+    fn lock(this: &Keyboard, key_codes: &Array) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn unlock(this: &Keyboard);
+}
+
+/// The [Keyboard Lock API] is only available in Chromium-based browsers.
+///
+/// [Keyboard Lock API]: https://developer.mozilla.org/en-US/docs/Web/API/Keyboard_API#keyboard_locking
+fn keyboard(window: &web_sys::Window) -> Option<Keyboard> {
+    let window: &WindowExt = window.unchecked_ref();
+    window.navigator().keyboard().filter(|keyboard| !keyboard.has_lock().is_undefined())
+}
+
+/// Captures the keys with the given `code` values, or all keys if `codes` is empty, while the page
+/// is fullscreen. Returns [`None`] if the API isn't available.
+pub fn lock_keyboard(window: &web_sys::Window, codes: &[String]) -> Option<Promise> {
+    let codes: Array = codes.iter().map(|code| JsValue::from_str(code)).collect();
+    keyboard(window).map(|keyboard| keyboard.lock(&codes))
+}
+
+/// Returns `false` if the API isn't available.
+pub fn unlock_keyboard(window: &web_sys::Window) -> bool {
+    keyboard(window).map(|keyboard| keyboard.unlock()).is_some()
+}
@@ -1,19 +1,37 @@
+mod accessibility;
 mod animation_frame;
 mod canvas;
 pub mod event;
 mod event_handle;
 mod fullscreen;
+mod ime;
 mod intersection_handle;
+mod keyboard_lock;
 mod media_query_handle;
+mod orientation;
 mod pointer;
+mod position;
 mod resize_scaling;
+mod safe_area;
 mod schedule;
+mod user_attention;
 
+pub use self::accessibility::{contrast_preference, reduced_motion, AccessibilityHandle};
 pub use self::canvas::{Canvas, Style};
 pub use self::event::ButtonsState;
 pub use self::event_handle::EventListenerHandle;
+pub use self::keyboard_lock::{lock_keyboard, unlock_keyboard};
+pub use self::orientation::{
+    interface_orientation, lock_orientation, orientation_angle, screen_orientation,
+    unlock_orientation,
+};
+pub use self::position::TransformPosition;
 pub use self::resize_scaling::ResizeScaleHandle;
+pub use self::safe_area::SafeAreaHandle;
 pub use self::schedule::Schedule;
+pub use self::user_attention::UserAttention;
+
+use std::cell::Cell;
 
 use crate::dpi::{LogicalPosition, LogicalSize};
 use wasm_bindgen::closure::Closure;
@@ -42,8 +60,33 @@ pub fn on_page_transition(
     PageTransitionEventHandle { _show_listener: show_listener, _hide_listener: hide_listener }
 }
 
+thread_local! {
+    /// See [`EventLoopExtWebSys::set_pinch_zoom_scaling()`].
+    ///
+    /// [`EventLoopExtWebSys::set_pinch_zoom_scaling()`]: crate::platform::web::EventLoopExtWebSys::set_pinch_zoom_scaling
+    static PINCH_ZOOM_SCALING: Cell<bool> = const { Cell::new(false) };
+}
+
 pub fn scale_factor(window: &web_sys::Window) -> f64 {
-    window.device_pixel_ratio()
+    window.device_pixel_ratio() * pinch_zoom(window)
+}
+
+/// The pinch-zoom scale of the page if it is included in the scale factor, otherwise `1`.
+pub fn pinch_zoom(window: &web_sys::Window) -> f64 {
+    if PINCH_ZOOM_SCALING.with(Cell::get) {
+        window.visual_viewport().map_or(1., |viewport| viewport.scale())
+    } else {
+        1.
+    }
+}
+
+/// Returns the previous value.
+pub fn set_pinch_zoom_scaling(enabled: bool) -> bool {
+    PINCH_ZOOM_SCALING.with(|scaling| scaling.replace(enabled))
+}
+
+pub fn pinch_zoom_scaling() -> bool {
+    PINCH_ZOOM_SCALING.with(Cell::get)
 }
 
 fn fix_canvas_size(style: &Style, mut size: LogicalSize<f64>) -> LogicalSize<f64> {
@@ -123,20 +166,33 @@ pub fn set_canvas_position(
     document: &Document,
     raw: &HtmlCanvasElement,
     style: &Style,
-    mut position: LogicalPosition<f64>,
+    position: LogicalPosition<f64>,
 ) {
-    if document.contains(Some(raw)) && style.get("display") != "none" {
-        position.x -= style_size_property(style, "margin-left")
-            + style_size_property(style, "border-left-width")
-            + style_size_property(style, "padding-left");
-        position.y -= style_size_property(style, "margin-top")
-            + style_size_property(style, "border-top-width")
-            + style_size_property(style, "padding-top");
-    }
+    let inset = canvas_inset(document, raw, style);
 
     style.set("position", "fixed");
-    style.set("left", &format!("{}px", position.x));
-    style.set("top", &format!("{}px", position.y));
+    style.set("left", &format!("{}px", position.x - inset.x));
+    style.set("top", &format!("{}px", position.y - inset.y));
+}
+
+/// The offset of the content box of the canvas from its margin box.
+pub fn canvas_inset(
+    document: &Document,
+    raw: &HtmlCanvasElement,
+    style: &Style,
+) -> LogicalPosition<f64> {
+    if document.contains(Some(raw)) && style.get("display") != "none" {
+        LogicalPosition::new(
+            style_size_property(style, "margin-left")
+                + style_size_property(style, "border-left-width")
+                + style_size_property(style, "padding-left"),
+            style_size_property(style, "margin-top")
+                + style_size_property(style, "border-top-width")
+                + style_size_property(style, "padding-top"),
+        )
+    } else {
+        LogicalPosition::new(0., 0.)
+    }
 }
 
 /// This function will panic if the element is not inserted in the DOM
@@ -0,0 +1,86 @@
+use js_sys::Promise;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::EventTarget;
+
+use crate::window::{InterfaceOrientation, OrientationLock};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::Screen)]
+    type ScreenExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn orientation(this: &ScreenExt) -> Option<ScreenOrientation>;
+
+    #[wasm_bindgen(extends = EventTarget)]
+    pub type ScreenOrientation;
+
+    #[wasm_bindgen(method, getter, js_name = type)]
+    fn type_(this: &ScreenOrientation) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn angle(this: &ScreenOrientation) -> u16;
+
+    #[wasm_bindgen(method, getter, js_name = lock)]
+    fn has_lock(this: &ScreenOrientation) -> JsValue;
+
+    #[wasm_bindgen(catch, method)]
+    fn lock(this: &ScreenOrientation, orientation: &str) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    fn unlock(this: &ScreenOrientation) -> Result<(), JsValue>;
+}
+
+/// The [Screen Orientation API] is only available since Safari v16.4.
+///
+/// [Screen Orientation API]: https://developer.mozilla.org/en-US/docs/Web/API/Screen_Orientation_API
+pub fn screen_orientation(window: &web_sys::Window) -> Option<ScreenOrientation> {
+    let screen: ScreenExt = window.screen().ok()?.unchecked_into();
+    screen.orientation()
+}
+
+pub fn interface_orientation(orientation: &ScreenOrientation) -> Option<InterfaceOrientation> {
+    // The primary landscape orientation of devices that are naturally portrait is reached by
+    // rotating the device counterclockwise.
+    match orientation.type_().as_str() {
+        "portrait-primary" => Some(InterfaceOrientation::Portrait),
+        "portrait-secondary" => Some(InterfaceOrientation::PortraitUpsideDown),
+        "landscape-primary" => Some(InterfaceOrientation::LandscapeRight),
+        "landscape-secondary" => Some(InterfaceOrientation::LandscapeLeft),
+        _ => None,
+    }
+}
+
+/// The rotation of the screen from its natural orientation in degrees.
+pub fn orientation_angle(orientation: &ScreenOrientation) -> u16 {
+    orientation.angle()
+}
+
+/// Returns [`None`] if locking isn't supported.
+pub fn lock_orientation(
+    window: &web_sys::Window,
+    lock: OrientationLock,
+) -> Option<Result<Promise, JsValue>> {
+    let orientation =
+        screen_orientation(window).filter(|orientation| orientation.has_lock().is_function())?;
+
+    let lock = match lock {
+        OrientationLock::Any => "any",
+        OrientationLock::Natural => "natural",
+        OrientationLock::Portrait => "portrait",
+        OrientationLock::Landscape => "landscape",
+        OrientationLock::Exact(InterfaceOrientation::Portrait) => "portrait-primary",
+        OrientationLock::Exact(InterfaceOrientation::PortraitUpsideDown) => "portrait-secondary",
+        OrientationLock::Exact(InterfaceOrientation::LandscapeRight) => "landscape-primary",
+        OrientationLock::Exact(InterfaceOrientation::LandscapeLeft) => "landscape-secondary",
+    };
+
+    Some(orientation.lock(lock))
+}
+
+pub fn unlock_orientation(window: &web_sys::Window) {
+    if let Some(orientation) = screen_orientation(window) {
+        let _ = orientation.unlock();
+    }
+}
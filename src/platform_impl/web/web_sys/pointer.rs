@@ -2,11 +2,10 @@ use std::cell::Cell;
 use std::rc::Rc;
 
 use super::canvas::Common;
-use super::event;
+use super::event::{self, TouchDetails};
 use super::event_handle::EventListenerHandle;
 use crate::dpi::PhysicalPosition;
-use crate::event::{Force, MouseButton};
-use crate::keyboard::ModifiersState;
+use crate::event::{Modifiers, MouseButton};
 
 use event::ButtonsState;
 use web_sys::PointerEvent;
@@ -35,7 +34,7 @@ impl PointerHandler {
 
     pub fn on_cursor_leave<F>(&mut self, canvas_common: &Common, mut handler: F)
     where
-        F: 'static + FnMut(ModifiersState, Option<i32>),
+        F: 'static + FnMut(Modifiers, Option<i32>),
     {
         self.on_cursor_leave =
             Some(canvas_common.add_event("pointerout", move |event: PointerEvent| {
@@ -52,7 +51,7 @@ impl PointerHandler {
 
     pub fn on_cursor_enter<F>(&mut self, canvas_common: &Common, mut handler: F)
     where
-        F: 'static + FnMut(ModifiersState, Option<i32>),
+        F: 'static + FnMut(Modifiers, Option<i32>),
     {
         self.on_cursor_enter =
             Some(canvas_common.add_event("pointerover", move |event: PointerEvent| {
@@ -74,9 +73,9 @@ impl PointerHandler {
         mut mouse_handler: M,
         mut touch_handler: T,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
-        T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, MouseButton),
+        T: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, TouchDetails),
     {
         let window = canvas_common.window.clone();
         self.on_pointer_release =
@@ -84,11 +83,12 @@ impl PointerHandler {
                 let modifiers = event::mouse_modifiers(&event);
 
                 match event.pointer_type().as_str() {
-                    "touch" => touch_handler(
+                    // Other pen buttons are released while the pen keeps touching or hovering.
+                    "touch" | "pen" if event.button() == 0 => touch_handler(
                         modifiers,
                         event.pointer_id(),
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
-                        Force::Normalized(event.pressure() as f64),
+                        event::touch_details(&event),
                     ),
                     "mouse" => mouse_handler(
                         modifiers,
@@ -108,10 +108,11 @@ impl PointerHandler {
         mut mouse_handler: M,
         mut touch_handler: T,
         prevent_default: Rc<Cell<bool>>,
+        focus_on_pointer_down: Rc<Cell<bool>>,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
-        T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, MouseButton),
+        T: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, TouchDetails),
     {
         let window = canvas_common.window.clone();
         let canvas = canvas_common.raw().clone();
@@ -121,7 +122,9 @@ impl PointerHandler {
                     // prevent text selection
                     event.prevent_default();
                     // but still focus element
-                    let _ = canvas.focus();
+                    if focus_on_pointer_down.get() {
+                        let _ = canvas.focus();
+                    }
                 }
 
                 let modifiers = event::mouse_modifiers(&event);
@@ -132,8 +135,20 @@ impl PointerHandler {
                             modifiers,
                             event.pointer_id(),
                             event::mouse_position(&event).to_physical(super::scale_factor(&window)),
-                            Force::Normalized(event.pressure() as f64),
+                            event::touch_details(&event),
+                        );
+                    },
+                    // Other pen buttons are pressed while the pen is already touching or hovering.
+                    "pen" if event.button() == 0 => {
+                        touch_handler(
+                            modifiers,
+                            event.pointer_id(),
+                            event::mouse_position(&event).to_physical(super::scale_factor(&window)),
+                            event::touch_details(&event),
                         );
+
+                        // Keep receiving the pen when it leaves the canvas, like with touches.
+                        let _e = canvas.set_pointer_capture(event.pointer_id());
                     },
                     "mouse" => {
                         mouse_handler(
@@ -154,6 +169,7 @@ impl PointerHandler {
             }));
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn on_cursor_move<MOD, M, T, B>(
         &mut self,
         canvas_common: &Common,
@@ -162,12 +178,13 @@ impl PointerHandler {
         mut touch_handler: T,
         mut button_handler: B,
         prevent_default: Rc<Cell<bool>>,
+        focus_on_pointer_down: Rc<Cell<bool>>,
     ) where
-        MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
+        MOD: 'static + FnMut(Modifiers),
+        M: 'static + FnMut(Modifiers, i32, &mut dyn Iterator<Item = PhysicalPosition<f64>>),
         T: 'static
-            + FnMut(ModifiersState, i32, &mut dyn Iterator<Item = (PhysicalPosition<f64>, Force)>),
-        B: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, ButtonsState, MouseButton),
+            + FnMut(Modifiers, i32, &mut dyn Iterator<Item = (PhysicalPosition<f64>, TouchDetails)>),
+        B: 'static + FnMut(Modifiers, i32, PhysicalPosition<f64>, ButtonsState, MouseButton),
     {
         let window = canvas_common.window.clone();
         let canvas = canvas_common.raw().clone();
@@ -177,16 +194,21 @@ impl PointerHandler {
 
                 let pointer_type = event.pointer_type();
 
-                if let "touch" | "mouse" = pointer_type.as_str() {
-                } else {
-                    modifier_handler(modifiers);
-                    return;
+                match pointer_type.as_str() {
+                    "touch" | "mouse" => (),
+                    // A hovering pen isn't reported.
+                    "pen" if event::mouse_buttons(&event).contains(ButtonsState::LEFT) => (),
+                    _ => {
+                        modifier_handler(modifiers);
+                        return;
+                    },
                 }
 
                 let id = event.pointer_id();
 
-                // chorded button event
-                if let Some(button) = event::mouse_button(&event) {
+                // chorded button event, pen buttons are ignored
+                if let Some(button) = event::mouse_button(&event).filter(|_| pointer_type != "pen")
+                {
                     debug_assert_eq!(
                         pointer_type, "mouse",
                         "expect pointer type of a chorded button event to be a mouse"
@@ -196,7 +218,9 @@ impl PointerHandler {
                         // prevent text selection
                         event.prevent_default();
                         // but still focus element
-                        let _ = canvas.focus();
+                        if focus_on_pointer_down.get() {
+                            let _ = canvas.focus();
+                        }
                     }
 
                     button_handler(
@@ -219,13 +243,13 @@ impl PointerHandler {
                         &mut event::pointer_move_event(event)
                             .map(|event| event::mouse_position(&event).to_physical(scale)),
                     ),
-                    "touch" => touch_handler(
+                    "touch" | "pen" => touch_handler(
                         modifiers,
                         id,
                         &mut event::pointer_move_event(event).map(|event| {
                             (
                                 event::mouse_position(&event).to_physical(scale),
-                                Force::Normalized(event.pressure() as f64),
+                                event::touch_details(&event),
                             )
                         }),
                     ),
@@ -236,16 +260,16 @@ impl PointerHandler {
 
     pub fn on_touch_cancel<F>(&mut self, canvas_common: &Common, mut handler: F)
     where
-        F: 'static + FnMut(i32, PhysicalPosition<f64>, Force),
+        F: 'static + FnMut(i32, PhysicalPosition<f64>, TouchDetails),
     {
         let window = canvas_common.window.clone();
         self.on_touch_cancel =
             Some(canvas_common.add_event("pointercancel", move |event: PointerEvent| {
-                if event.pointer_type() == "touch" {
+                if let "touch" | "pen" = event.pointer_type().as_str() {
                     handler(
                         event.pointer_id(),
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
-                        Force::Normalized(event.pressure() as f64),
+                        event::touch_details(&event),
                     );
                 }
             }));
@@ -0,0 +1,84 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use web_sys::{Document, HtmlCanvasElement};
+
+use super::animation_frame::AnimationFrameHandler;
+use super::Style;
+use crate::dpi::LogicalPosition;
+
+/// Positions the canvas with `transform: translate()` instead of `left` and `top`, which doesn't
+/// affect layout. The position is kept track of to avoid reading it back from the layout, and
+/// applied once per animation frame.
+pub struct TransformPosition {
+    style: Style,
+    /// The position of the content box, [`None`] until the canvas was positioned.
+    position: Cell<Option<LogicalPosition<f64>>>,
+    /// The offset of the content box from the margin box, measured once the canvas is positioned.
+    inset: Cell<LogicalPosition<f64>>,
+    /// The translation to apply on the next animation frame.
+    pending: Rc<Cell<Option<LogicalPosition<f64>>>>,
+    animation_frame: AnimationFrameHandler,
+}
+
+impl TransformPosition {
+    pub fn new(window: web_sys::Window, style: Style) -> Self {
+        let pending: Rc<Cell<Option<LogicalPosition<f64>>>> = Rc::default();
+        let mut animation_frame = AnimationFrameHandler::new(window);
+        animation_frame.on_animation_frame({
+            let style = style.clone();
+            let pending = Rc::clone(&pending);
+            move |_| {
+                if let Some(translation) = pending.take() {
+                    style.set(
+                        "transform",
+                        &format!("translate({}px, {}px)", translation.x, translation.y),
+                    );
+                }
+            }
+        });
+
+        Self {
+            style,
+            position: Cell::new(None),
+            inset: Cell::new(LogicalPosition::new(0., 0.)),
+            pending,
+            animation_frame,
+        }
+    }
+
+    pub fn position(&self) -> Option<LogicalPosition<f64>> {
+        self.position.get()
+    }
+
+    pub fn set(
+        &self,
+        document: &Document,
+        raw: &HtmlCanvasElement,
+        position: LogicalPosition<f64>,
+    ) {
+        if self.position.get().is_none() {
+            self.inset.set(super::canvas_inset(document, raw, &self.style));
+            self.style.set("position", "fixed");
+            self.style.set("left", "0px");
+            self.style.set("top", "0px");
+        }
+
+        self.position.set(Some(position));
+        let inset = self.inset.get();
+        self.pending.set(Some(LogicalPosition::new(position.x - inset.x, position.y - inset.y)));
+
+        if !self.animation_frame.is_pending() {
+            self.animation_frame.request();
+        }
+    }
+
+    /// Removes the translation and returns the position the canvas had.
+    pub fn clear(&mut self) -> Option<LogicalPosition<f64>> {
+        self.animation_frame.cancel();
+        self.pending.take();
+        let position = self.position.take()?;
+        self.style.remove("transform");
+        Some(position)
+    }
+}
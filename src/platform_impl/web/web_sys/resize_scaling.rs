@@ -3,7 +3,7 @@ use tracing::warn;
 use wasm_bindgen::prelude::{wasm_bindgen, Closure};
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    Document, HtmlCanvasElement, MediaQueryList, ResizeObserver, ResizeObserverBoxOptions,
+    Document, Event, HtmlCanvasElement, MediaQueryList, ResizeObserver, ResizeObserverBoxOptions,
     ResizeObserverEntry, ResizeObserverOptions, ResizeObserverSize, Window,
 };
 
@@ -11,6 +11,7 @@ use crate::dpi::{LogicalSize, PhysicalSize};
 
 use super::super::backend;
 use super::canvas::Style;
+use super::event_handle::EventListenerHandle;
 use super::media_query_handle::MediaQueryListHandle;
 
 use std::cell::{Cell, RefCell};
@@ -44,16 +45,24 @@ impl ResizeScaleHandle {
     pub(crate) fn notify_resize(&self) {
         self.0.notify()
     }
+
+    /// Checks if the pinch-zoom scale included in the scale factor changed.
+    pub(crate) fn notify_pinch_zoom(&self) {
+        self.0.handle_pinch_zoom()
+    }
 }
 
 /// This is a helper type to help manage the `MediaQueryList` used for detecting
-/// changes of the `devicePixelRatio`.
+/// changes of the `devicePixelRatio`, and the `visualViewport` used for detecting changes of the
+/// pinch-zoom scale.
 struct ResizeScaleInternal {
     window: Window,
     document: Document,
     canvas: HtmlCanvasElement,
     style: Style,
     mql: RefCell<MediaQueryListHandle>,
+    pinch_zoom: Cell<f64>,
+    _on_visual_viewport_resize: Option<EventListenerHandle<dyn FnMut(Event)>>,
     observer: ResizeObserver,
     _observer_closure: Closure<dyn FnMut(Array, ResizeObserver)>,
     scale_handler: Box<dyn Fn(PhysicalSize<u32>, f64)>,
@@ -84,6 +93,19 @@ impl ResizeScaleInternal {
                 }
             });
 
+            let on_visual_viewport_resize = window.visual_viewport().map(|viewport| {
+                let weak_self = weak_self.clone();
+                EventListenerHandle::new(
+                    viewport,
+                    "resize",
+                    Closure::new(move |_: Event| {
+                        if let Some(rc_self) = weak_self.upgrade() {
+                            rc_self.handle_pinch_zoom();
+                        }
+                    }),
+                )
+            });
+
             let weak_self = weak_self.clone();
             let observer_closure = Closure::new(move |entries: Array, _| {
                 if let Some(this) = weak_self.upgrade() {
@@ -100,11 +122,13 @@ impl ResizeScaleInternal {
             let observer = Self::create_observer(&canvas, observer_closure.as_ref());
 
             Self {
+                pinch_zoom: Cell::new(backend::pinch_zoom(&window)),
                 window,
                 document,
                 canvas,
                 style,
                 mql: RefCell::new(mql),
+                _on_visual_viewport_resize: on_visual_viewport_resize,
                 observer,
                 _observer_closure: observer_closure,
                 scale_handler: Box::new(scale_handler),
@@ -225,6 +249,17 @@ impl ResizeScaleInternal {
         self.notify();
     }
 
+    fn handle_pinch_zoom(&self) {
+        let pinch_zoom = backend::pinch_zoom(&self.window);
+
+        // `resize` is also fired when the size of the visual viewport changes, e.g. when the
+        // virtual keyboard is shown.
+        if self.pinch_zoom.replace(pinch_zoom) != pinch_zoom {
+            self.notify_scale.set(true);
+            self.notify();
+        }
+    }
+
     fn process_entry(&self, entries: Array) -> PhysicalSize<u32> {
         let entry: ResizeObserverEntry = entries.get(0).unchecked_into();
 
@@ -263,10 +298,15 @@ impl ResizeScaleInternal {
             },
         };
 
+        // `devicePixelContentBoxSize` doesn't account for pinch-zoom.
+        let pinch_zoom = backend::pinch_zoom(&self.window);
+        let inline_size = (entry.inline_size() * pinch_zoom).round() as u32;
+        let block_size = (entry.block_size() * pinch_zoom).round() as u32;
+
         if horizontal {
-            PhysicalSize::new(entry.inline_size() as u32, entry.block_size() as u32)
+            PhysicalSize::new(inline_size, block_size)
         } else {
-            PhysicalSize::new(entry.block_size() as u32, entry.inline_size() as u32)
+            PhysicalSize::new(block_size, inline_size)
         }
     }
 }
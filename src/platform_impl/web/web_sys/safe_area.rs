@@ -0,0 +1,75 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlElement, ResizeObserver, Window};
+
+use crate::dpi::{LogicalInsets, PhysicalInsets};
+
+use super::canvas::Style;
+
+/// Tracks the safe area of the viewport, e.g. the notch or the browser UI overlaying the page.
+///
+/// The browser only exposes it through the CSS `env(safe-area-inset-*)` variables, so a hidden
+/// element covering the viewport is padded with them. Its computed padding is then the safe area
+/// and a `ResizeObserver` notices when it changes, as this changes the size of its content box.
+pub struct SafeAreaHandle {
+    element: HtmlElement,
+    style: Style,
+    observer: ResizeObserver,
+    _closure: Closure<dyn FnMut(Array)>,
+}
+
+impl SafeAreaHandle {
+    pub fn new<F>(window: &Window, document: &Document, mut callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(),
+    {
+        let element: HtmlElement = document.create_element("div").ok()?.unchecked_into();
+        document.body()?.append_child(&element).ok()?;
+
+        let style = Style::new(window, &element);
+        style.set("position", "fixed");
+        style.set("inset", "0");
+        style.set("box-sizing", "border-box");
+        style.set(
+            "padding",
+            "env(safe-area-inset-top) env(safe-area-inset-right) env(safe-area-inset-bottom) \
+             env(safe-area-inset-left)",
+        );
+        style.set("visibility", "hidden");
+        style.set("pointer-events", "none");
+
+        let closure = Closure::new(move |_: Array| callback());
+        let observer = ResizeObserver::new(closure.as_ref().unchecked_ref())
+            .expect("Failed to create `ResizeObserver`");
+        observer.observe(&element);
+
+        Some(Self { element, style, observer, _closure: closure })
+    }
+
+    /// The part of the safe area overlapping the given element, relative to its edges.
+    pub fn insets(&self, element: &HtmlElement, scale: f64) -> PhysicalInsets<u32> {
+        let viewport = self.element.get_bounding_client_rect();
+        let rect = element.get_bounding_client_rect();
+
+        let top = viewport.top() + super::style_size_property(&self.style, "padding-top");
+        let left = viewport.left() + super::style_size_property(&self.style, "padding-left");
+        let bottom = viewport.bottom() - super::style_size_property(&self.style, "padding-bottom");
+        let right = viewport.right() - super::style_size_property(&self.style, "padding-right");
+
+        LogicalInsets::new(
+            (top - rect.top()).clamp(0., rect.height()),
+            (left - rect.left()).clamp(0., rect.width()),
+            (rect.bottom() - bottom).clamp(0., rect.height()),
+            (rect.right() - right).clamp(0., rect.width()),
+        )
+        .to_physical(scale)
+    }
+}
+
+impl Drop for SafeAreaHandle {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+        self.element.remove();
+    }
+}
@@ -0,0 +1,313 @@
+//! User attention requests.
+//!
+//! Browsers don't let a page flash its tab, so the title of the page is alternated with a prefix
+//! instead, optionally alongside a different favicon. Installed web apps additionally get a badge
+//! on their icon through the [Badging API], where supported.
+//!
+//! The badge is also used to show the count set with `Window::set_badge_count`, which takes
+//! precedence over the one of the requests.
+//!
+//! [Badging API]: https://developer.mozilla.org/en-US/docs/Web/API/Badging_API
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use js_sys::Promise;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Document, Element, Event, FocusEvent};
+
+use super::event_handle::EventListenerHandle;
+use crate::window::UserAttentionType;
+
+/// How often the title of the page alternates while [`UserAttentionType::Critical`] is requested.
+const FLASH_INTERVAL: i32 = 1000;
+
+const DEFAULT_PREFIX: &str = "(!) ";
+
+pub struct UserAttention {
+    shared: Rc<Shared>,
+    on_flash: Closure<dyn FnMut()>,
+    _on_focus: EventListenerHandle<dyn FnMut(FocusEvent)>,
+    _on_visibility_change: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+struct Shared {
+    window: web_sys::Window,
+    document: Document,
+    prefix: RefCell<String>,
+    favicon: RefCell<Option<String>>,
+    request: RefCell<Option<Request>>,
+    badge_count: Cell<Option<u64>>,
+}
+
+struct Request {
+    /// The title of the page to restore, [`None`] if the title isn't flashing.
+    title: Option<String>,
+    /// Whether the prefix is currently shown.
+    flashed: bool,
+    interval: Option<i32>,
+    favicon: Option<Favicon>,
+    /// Whether the request shows the badge, which isn't the case while it shows a count.
+    badge: bool,
+}
+
+enum Favicon {
+    /// An existing `<link rel="icon">` with its original `href`.
+    Replaced(Element, Option<String>),
+    /// A `<link rel="icon">` that was added because the page didn't have one.
+    Added(Element),
+}
+
+impl UserAttention {
+    pub fn new(window: web_sys::Window, document: Document) -> Self {
+        let shared = Rc::new(Shared {
+            window: window.clone(),
+            document: document.clone(),
+            prefix: RefCell::new(DEFAULT_PREFIX.to_owned()),
+            favicon: RefCell::new(None),
+            request: RefCell::new(None),
+            badge_count: Cell::new(None),
+        });
+
+        let on_flash = Closure::new({
+            let shared = Rc::clone(&shared);
+            move || shared.flash()
+        });
+
+        let on_focus = EventListenerHandle::new(window, "focus", {
+            let shared = Rc::clone(&shared);
+            Closure::new(move |_: FocusEvent| shared.cancel())
+        });
+
+        let on_visibility_change = EventListenerHandle::new(document, "visibilitychange", {
+            let shared = Rc::clone(&shared);
+            Closure::new(move |_: Event| {
+                if super::is_visible(&shared.document) {
+                    shared.cancel()
+                }
+            })
+        });
+
+        Self { shared, on_flash, _on_focus: on_focus, _on_visibility_change: on_visibility_change }
+    }
+
+    pub fn request(&self, request_type: Option<UserAttentionType>) {
+        let shared = &self.shared;
+        shared.cancel();
+
+        let Some(request_type) = request_type else {
+            return;
+        };
+
+        // Like on other platforms, this has no effect if the page already has the user's
+        // attention.
+        if super::is_visible(&shared.document) && shared.document.has_focus().unwrap_or(false) {
+            return;
+        }
+
+        let mut request =
+            Request { title: None, flashed: false, interval: None, favicon: None, badge: false };
+
+        if let UserAttentionType::Critical = request_type {
+            request.title = Some(shared.document.title());
+            request.interval = shared
+                .window
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    self.on_flash.as_ref().unchecked_ref(),
+                    FLASH_INTERVAL,
+                )
+                .ok();
+            request.favicon = shared
+                .favicon
+                .borrow()
+                .as_deref()
+                .and_then(|url| replace_favicon(&shared.document, url));
+        }
+
+        request.badge = shared.badge_count.get().is_none() && set_app_badge(&shared.window, None);
+
+        *shared.request.borrow_mut() = Some(request);
+        // Start flashing right away instead of after the first interval.
+        shared.flash();
+    }
+
+    /// Sets the title of the page, or the one to restore once the title stops flashing.
+    pub fn set_document_title(&self, title: &str) {
+        if let Some(Request { title: Some(original), flashed, .. }) =
+            &mut *self.shared.request.borrow_mut()
+        {
+            title.clone_into(original);
+            self.shared.set_title(title, *flashed);
+            return;
+        }
+
+        self.shared.document.set_title(title);
+    }
+
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        let shared = &self.shared;
+        if shared.badge_count.replace(count) == count {
+            return;
+        }
+
+        let mut request = shared.request.borrow_mut();
+        match (count, &mut *request) {
+            (Some(count), request) => {
+                set_app_badge(&shared.window, Some(count));
+                if let Some(request) = request {
+                    request.badge = false;
+                }
+            },
+            // Go back to the badge of the request.
+            (None, Some(request)) => request.badge = set_app_badge(&shared.window, None),
+            (None, None) => clear_app_badge(&shared.window),
+        }
+    }
+
+    pub fn prefix(&self) -> String {
+        self.shared.prefix.borrow().clone()
+    }
+
+    pub fn set_prefix(&self, prefix: String) {
+        *self.shared.prefix.borrow_mut() = prefix;
+    }
+
+    pub fn favicon(&self) -> Option<String> {
+        self.shared.favicon.borrow().clone()
+    }
+
+    /// Only applies to the next request.
+    pub fn set_favicon(&self, url: Option<String>) {
+        *self.shared.favicon.borrow_mut() = url;
+    }
+}
+
+impl Drop for UserAttention {
+    fn drop(&mut self) {
+        self.shared.cancel();
+        self.set_badge_count(None);
+    }
+}
+
+impl Shared {
+    fn flash(&self) {
+        if let Some(Request { title: Some(title), flashed, .. }) = &mut *self.request.borrow_mut() {
+            *flashed = !*flashed;
+            self.set_title(title, *flashed);
+        }
+    }
+
+    fn set_title(&self, title: &str, flashed: bool) {
+        if flashed {
+            self.document.set_title(&format!("{}{title}", self.prefix.borrow()));
+        } else {
+            self.document.set_title(title);
+        }
+    }
+
+    /// Restores the title and favicon of the page and clears the badge.
+    fn cancel(&self) {
+        let Some(request) = self.request.borrow_mut().take() else {
+            return;
+        };
+
+        if let Some(interval) = request.interval {
+            self.window.clear_interval_with_handle(interval);
+        }
+
+        if let Some(title) = request.title {
+            self.document.set_title(&title);
+        }
+
+        match request.favicon {
+            Some(Favicon::Replaced(link, Some(href))) => {
+                let _ = link.set_attribute("href", &href);
+            },
+            Some(Favicon::Replaced(link, None)) => {
+                let _ = link.remove_attribute("href");
+            },
+            Some(Favicon::Added(link)) => link.remove(),
+            None => (),
+        }
+
+        if request.badge {
+            clear_app_badge(&self.window);
+        }
+    }
+}
+
+fn replace_favicon(document: &Document, url: &str) -> Option<Favicon> {
+    if let Some(link) = document.query_selector("link[rel~='icon']").ok().flatten() {
+        let href = link.get_attribute("href");
+        link.set_attribute("href", url).ok()?;
+        return Some(Favicon::Replaced(link, href));
+    }
+
+    let head = document.query_selector("head").ok().flatten()?;
+    let link = document.create_element("link").ok()?;
+    link.set_attribute("rel", "icon").ok()?;
+    link.set_attribute("href", url).ok()?;
+    head.append_child(&link).ok()?;
+    Some(Favicon::Added(link))
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type WindowExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn navigator(this: &WindowExt) -> NavigatorExt;
+
+    type NavigatorExt;
+
+    #[wasm_bindgen(method, getter, js_name = setAppBadge)]
+    fn has_set_app_badge(this: &NavigatorExt) -> JsValue;
+
+    #[wasm_bindgen(method, js_name = setAppBadge)]
+    fn set_app_badge(this: &NavigatorExt) -> Promise;
+
+    #[wasm_bindgen(method, js_name = setAppBadge)]
+    fn set_app_badge_with_contents(this: &NavigatorExt, contents: f64) -> Promise;
+
+    #[wasm_bindgen(method, js_name = clearAppBadge)]
+    fn clear_app_badge(this: &NavigatorExt) -> Promise;
+}
+
+/// The [Badging API] is only available in Chromium-based browsers and Safari, and only for
+/// installed web apps.
+///
+/// [Badging API]: https://developer.mozilla.org/en-US/docs/Web/API/Badging_API
+fn navigator(window: &web_sys::Window) -> Option<NavigatorExt> {
+    let window: &WindowExt = window.unchecked_ref();
+    let navigator = window.navigator();
+    (!navigator.has_set_app_badge().is_undefined()).then_some(navigator)
+}
+
+/// Shows the count, or a plain badge with `None`. Returns `false` if the API isn't available.
+fn set_app_badge(window: &web_sys::Window, count: Option<u64>) -> bool {
+    let Some(navigator) = navigator(window) else {
+        return false;
+    };
+
+    ignore_rejection(match count {
+        Some(count) => navigator.set_app_badge_with_contents(count as f64),
+        None => navigator.set_app_badge(),
+    });
+    true
+}
+
+fn clear_app_badge(window: &web_sys::Window) {
+    if let Some(navigator) = navigator(window) {
+        ignore_rejection(navigator.clear_app_badge());
+    }
+}
+
+/// Setting the badge fails if the page isn't an installed web app, which isn't worth reporting.
+fn ignore_rejection(promise: Promise) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = JsFuture::from(promise).await;
+    });
+}
@@ -1,21 +1,37 @@
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
+use crate::event::{Event, Ime, WindowEvent};
 use crate::icon::Icon;
+use crate::keyboard::KeyCode;
+use crate::platform::web::{
+    FocusLoss, FullscreenElement, FullscreenNavigationUi, OrientationLockError, PositionMode,
+    VisualViewport, WheelDeltaPolicy, WheelDetails,
+};
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
+    AspectRatio, Cursor, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose,
+    InterfaceOrientation, OrientationLock, ProgressState, Rect, ResizeDirection, Theme,
+    UserAttentionType, WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
 };
 
+use super::dropped_file::DroppedFile;
+use super::event_loop::runner;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::monitor::MonitorHandle;
-use super::r#async::Dispatcher;
-use super::{backend, ActiveEventLoop, Fullscreen};
-use web_sys::HtmlCanvasElement;
+use super::r#async::{Dispatcher, Notified, Notifier};
+use super::{backend, ActiveEventLoop, Fullscreen, OsError};
+use tracing::warn;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{DomException, HtmlCanvasElement, HtmlElement};
 
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 pub struct Window {
     inner: Dispatcher<Inner>,
@@ -25,6 +41,11 @@ pub struct Inner {
     id: WindowId,
     pub window: web_sys::Window,
     canvas: Rc<RefCell<backend::Canvas>>,
+    runner: runner::Shared,
+    title: RefCell<String>,
+    user_attention: backend::UserAttention,
+    /// The `code` values of the keys to capture while fullscreen.
+    keyboard_lock: RefCell<Option<Vec<String>>>,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
 
@@ -51,8 +72,25 @@ impl Window {
         let runner = target.runner.clone();
         let destroy_fn = Box::new(move || runner.notify_destroy_window(RootWI(id)));
 
-        let inner = Inner { id, window: window.clone(), canvas, destroy_fn: Some(destroy_fn) };
+        let inner = Inner {
+            id,
+            window: window.clone(),
+            canvas,
+            runner: target.runner.clone(),
+            title: RefCell::new(String::new()),
+            user_attention: backend::UserAttention::new(window.clone(), document.clone()),
+            keyboard_lock: RefCell::new(None),
+            destroy_fn: Some(destroy_fn),
+        };
 
+        // Unless told otherwise, the first window gets to set the title of the page.
+        let set_document_title = attr
+            .platform_specific
+            .set_document_title
+            .unwrap_or_else(|| target.runner.document_title_owner().is_none());
+        if set_document_title {
+            target.runner.set_document_title_owner(Some(RootWI(id)));
+        }
         inner.set_title(&attr.title);
         inner.set_maximized(attr.maximized);
         inner.set_visible(attr.visible);
@@ -86,6 +124,150 @@ impl Window {
         self.inner.dispatch(move |inner| inner.canvas.borrow().prevent_default.set(prevent_default))
     }
 
+    pub(crate) fn last_frame_time(&self) -> Option<Duration> {
+        self.inner.queue(|inner| inner.canvas.borrow().last_frame_time.get())
+    }
+
+    pub(crate) fn visual_viewport(&self) -> Option<VisualViewport> {
+        self.inner.queue(|inner| inner.canvas.borrow().visual_viewport())
+    }
+
+    pub(crate) fn dropped_files(&self) -> Vec<DroppedFile> {
+        self.inner.queue(|inner| inner.canvas.borrow().dropped_files.borrow().clone())
+    }
+
+    pub(crate) fn user_attention_prefix(&self) -> String {
+        self.inner.queue(|inner| inner.user_attention.prefix())
+    }
+
+    pub(crate) fn set_user_attention_prefix(&self, prefix: String) {
+        self.inner.dispatch(move |inner| inner.user_attention.set_prefix(prefix))
+    }
+
+    pub(crate) fn user_attention_favicon(&self) -> Option<String> {
+        self.inner.queue(|inner| inner.user_attention.favicon())
+    }
+
+    pub(crate) fn set_user_attention_favicon(&self, url: Option<String>) {
+        self.inner.dispatch(move |inner| inner.user_attention.set_favicon(url))
+    }
+
+    pub(crate) fn set_fullscreen_element(&self, element: Option<FullscreenElement>) {
+        let element = element.map(FullscreenTarget::new);
+        self.inner.dispatch(move |inner| {
+            let element = element.map(|element| element.into_element(inner.runner.main_thread()));
+            *inner.canvas.borrow().fullscreen_element.borrow_mut() = element;
+        })
+    }
+
+    pub(crate) fn fullscreen_navigation_ui(&self) -> FullscreenNavigationUi {
+        self.inner.queue(|inner| inner.canvas.borrow().fullscreen_navigation_ui.get())
+    }
+
+    pub(crate) fn set_fullscreen_navigation_ui(&self, navigation_ui: FullscreenNavigationUi) {
+        self.inner.dispatch(move |inner| {
+            inner.canvas.borrow().fullscreen_navigation_ui.set(navigation_ui)
+        })
+    }
+
+    pub(crate) fn confirm_close(&self) -> bool {
+        self.inner.queue(|inner| inner.canvas.borrow().confirm_close.get())
+    }
+
+    pub(crate) fn set_confirm_close(&self, confirm_close: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.borrow().confirm_close.set(confirm_close))
+    }
+
+    pub(crate) fn set_keyboard_lock(&self, keys: Option<Vec<KeyCode>>) -> KeyboardLockFuture {
+        let notifier = Notifier::new();
+        let notified = notifier.notified();
+        self.inner.dispatch(move |inner| inner.set_keyboard_lock(keys, notifier));
+        KeyboardLockFuture(notified)
+    }
+
+    pub(crate) fn lock_orientation(&self, lock: OrientationLock) -> OrientationLockFuture {
+        let notifier = Notifier::new();
+        let notified = notifier.notified();
+        self.inner.dispatch(move |inner| inner.lock_orientation(lock, notifier));
+        OrientationLockFuture(notified)
+    }
+
+    pub(crate) fn unlock_orientation(&self) {
+        self.inner.dispatch(|inner| backend::unlock_orientation(&inner.window))
+    }
+
+    pub(crate) fn interface_orientation(&self) -> Option<InterfaceOrientation> {
+        self.inner.queue(|inner| {
+            backend::screen_orientation(&inner.window)
+                .and_then(|orientation| backend::interface_orientation(&orientation))
+        })
+    }
+
+    pub(crate) fn orientation_angle(&self) -> Option<u16> {
+        self.inner.queue(|inner| {
+            backend::screen_orientation(&inner.window)
+                .map(|orientation| backend::orientation_angle(&orientation))
+        })
+    }
+
+    pub(crate) fn focus_with_options(&self, prevent_scroll: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.borrow().focus_with_options(prevent_scroll))
+    }
+
+    pub(crate) fn focus_on_pointer_down(&self) -> bool {
+        self.inner.queue(|inner| inner.canvas.borrow().focus_on_pointer_down.get())
+    }
+
+    pub(crate) fn set_focus_on_pointer_down(&self, focus_on_pointer_down: bool) {
+        self.inner.dispatch(move |inner| {
+            inner.canvas.borrow().focus_on_pointer_down.set(focus_on_pointer_down)
+        })
+    }
+
+    pub(crate) fn focus_visible(&self) -> bool {
+        self.inner.queue(|inner| inner.canvas.borrow().focus_visible.get())
+    }
+
+    pub(crate) fn focus_loss(&self) -> Option<FocusLoss> {
+        self.inner.queue(|inner| inner.canvas.borrow().focus_loss.get())
+    }
+
+    pub(crate) fn position_mode(&self) -> PositionMode {
+        self.inner.queue(|inner| inner.canvas.borrow().position_mode())
+    }
+
+    pub(crate) fn set_position_mode(&self, mode: PositionMode) {
+        self.inner.dispatch(move |inner| inner.canvas.borrow_mut().set_position_mode(mode))
+    }
+
+    pub(crate) fn wheel_delta_policy(&self) -> WheelDeltaPolicy {
+        self.inner.queue(|inner| inner.canvas.borrow().wheel_delta_policy.get())
+    }
+
+    pub(crate) fn set_wheel_delta_policy(&self, policy: WheelDeltaPolicy) {
+        self.inner.dispatch(move |inner| inner.canvas.borrow().wheel_delta_policy.set(policy))
+    }
+
+    pub(crate) fn last_wheel_details(&self) -> Option<WheelDetails> {
+        self.inner.queue(|inner| inner.canvas.borrow().last_wheel_details.get())
+    }
+
+    pub(crate) fn wheel_pinch_gesture(&self) -> bool {
+        self.inner.queue(|inner| inner.canvas.borrow().wheel_pinch_gesture.get())
+    }
+
+    pub(crate) fn set_wheel_pinch_gesture(&self, enabled: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.borrow().wheel_pinch_gesture.set(enabled))
+    }
+
+    pub(crate) fn document_title_enabled(&self) -> bool {
+        self.inner.queue(|inner| inner.owns_document_title())
+    }
+
+    pub(crate) fn set_document_title_enabled(&self, enabled: bool) {
+        self.inner.dispatch(move |inner| inner.set_document_title_enabled(enabled))
+    }
+
     #[cfg(feature = "rwh_06")]
     #[inline]
     pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
@@ -113,13 +295,45 @@ impl Window {
 
 impl Inner {
     pub fn set_title(&self, title: &str) {
-        self.canvas.borrow().set_attribute("alt", title)
+        self.canvas.borrow().set_attribute("alt", title);
+        if self.owns_document_title() {
+            self.user_attention.set_document_title(title);
+        }
+        *self.title.borrow_mut() = title.to_owned();
+    }
+
+    fn owns_document_title(&self) -> bool {
+        self.runner.document_title_owner() == Some(RootWI(self.id))
+    }
+
+    fn set_document_title_enabled(&self, enabled: bool) {
+        if enabled {
+            // Only one window can own the title of the page, so this takes it over from any
+            // other window.
+            self.runner.set_document_title_owner(Some(RootWI(self.id)));
+            self.user_attention.set_document_title(&self.title.borrow());
+        } else if self.owns_document_title() {
+            self.runner.set_document_title_owner(None);
+        }
     }
 
     pub fn set_transparent(&self, _transparent: bool) {}
 
     pub fn set_blur(&self, _blur: bool) {}
 
+    pub fn set_opacity(&self, opacity: f32) {
+        let canvas = self.canvas.borrow();
+        if opacity >= 1.0 {
+            canvas.style().remove("opacity");
+        } else {
+            canvas.style().set("opacity", &opacity.to_string());
+        }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.canvas.borrow().style().get("opacity").parse().unwrap_or(1.0)
+    }
+
     pub fn set_visible(&self, _visible: bool) {
         // Intentionally a no-op
     }
@@ -130,10 +344,30 @@ impl Inner {
     }
 
     pub fn request_redraw(&self) {
-        self.canvas.borrow().request_animation_frame();
+        let canvas = self.canvas.borrow();
+        canvas.redraw_damage.borrow_mut().add_full();
+        canvas.request_animation_frame();
+    }
+
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let canvas = self.canvas.borrow();
+        canvas.redraw_damage.borrow_mut().add(rects, canvas.inner_size());
+        canvas.request_animation_frame();
     }
 
-    pub fn pre_present_notify(&self) {}
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let canvas = self.canvas.borrow();
+        let damage = canvas.redraw_damage.borrow();
+        damage.current(canvas.inner_size()).map(<[_]>::to_vec)
+    }
+
+    pub fn pre_present_notify(&self) {
+        self.canvas.borrow().pre_present_notify();
+    }
+
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        self.canvas.borrow().presentation_feedback.set(enabled);
+    }
 
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         Ok(self.canvas.borrow().position().to_physical(self.scale_factor()))
@@ -145,10 +379,7 @@ impl Inner {
     }
 
     pub fn set_outer_position(&self, position: Position) {
-        let canvas = self.canvas.borrow();
-        let position = position.to_logical::<f64>(self.scale_factor());
-
-        backend::set_canvas_position(canvas.document(), canvas.raw(), canvas.style(), position)
+        self.canvas.borrow().set_position(position.to_logical(self.scale_factor()))
     }
 
     #[inline]
@@ -162,10 +393,19 @@ impl Inner {
         self.inner_size()
     }
 
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        self.canvas.borrow().safe_area()
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
-        let size = size.to_logical(self.scale_factor());
         let canvas = self.canvas.borrow();
+        if canvas.auto_parent_size {
+            return Some(canvas.inner_size());
+        }
+
+        let size = size.to_logical(self.scale_factor());
         backend::set_canvas_size(canvas.document(), canvas.raw(), canvas.style(), size);
         None
     }
@@ -194,6 +434,11 @@ impl Inner {
         // Intentionally a no-op: users can't resize canvas elements
     }
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, _aspect_ratio: Option<AspectRatio>) {
+        // Intentionally a no-op: users can't resize canvas elements
+    }
+
     #[inline]
     pub fn set_resizable(&self, _resizable: bool) {
         // Intentionally a no-op: users can't resize canvas elements
@@ -208,7 +453,13 @@ impl Inner {
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        // The canvas has no buttons.
+        WindowButtons::empty()
+    }
+
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::empty()
     }
 
     #[inline]
@@ -249,11 +500,21 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn set_transient_parent(&self, _parent: Option<&Window>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn start_drag_and_drop(&self, _payload: DragPayload) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
@@ -297,11 +558,94 @@ impl Inner {
     pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         let canvas = &self.canvas.borrow();
 
-        if fullscreen.is_some() {
-            canvas.request_fullscreen();
+        if let Some(fullscreen) = &fullscreen {
+            let screen = match fullscreen {
+                Fullscreen::Borderless(Some(monitor)) => monitor.screen(self.runner.main_thread()),
+                _ => None,
+            };
+            canvas.request_fullscreen(screen);
         } else {
             canvas.exit_fullscreen()
         }
+
+        // Keys are only captured while fullscreen, but the browser can release the lock.
+        if let Some(codes) = self.keyboard_lock.borrow().as_ref() {
+            if fullscreen.is_some() {
+                if let Some(promise) = backend::lock_keyboard(&self.window, codes) {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let _ = JsFuture::from(promise).await;
+                    });
+                }
+            } else {
+                backend::unlock_keyboard(&self.window);
+            }
+        }
+    }
+
+    fn set_keyboard_lock(
+        &self,
+        keys: Option<Vec<KeyCode>>,
+        notifier: Notifier<Result<(), KeyboardLockError>>,
+    ) {
+        let codes = keys.map(|keys| {
+            keys.into_iter()
+                .filter_map(|key| {
+                    let code = key.to_key_code_attribute_value();
+                    if code.is_none() {
+                        warn!("`{key:?}` can't be captured on the web");
+                    }
+                    code
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let Some(codes) = codes else {
+            *self.keyboard_lock.borrow_mut() = None;
+            let result = if backend::unlock_keyboard(&self.window) {
+                Ok(())
+            } else {
+                Err(KeyboardLockError::NotSupported)
+            };
+            notifier.notify(result);
+            return;
+        };
+
+        let Some(promise) = backend::lock_keyboard(&self.window, &codes) else {
+            notifier.notify(Err(KeyboardLockError::NotSupported));
+            return;
+        };
+        *self.keyboard_lock.borrow_mut() = Some(codes);
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = JsFuture::from(promise).await.map(|_| ()).map_err(|error| {
+                let error: DomException = error.unchecked_into();
+                KeyboardLockError::Rejected(error.message())
+            });
+            notifier.notify(result);
+        });
+    }
+
+    fn lock_orientation(
+        &self,
+        lock: OrientationLock,
+        notifier: Notifier<Result<(), OrientationLockError>>,
+    ) {
+        let promise = match backend::lock_orientation(&self.window, lock) {
+            Some(Ok(promise)) => promise,
+            Some(Err(error)) => {
+                notifier.notify(Err(orientation_lock_error(error)));
+                return;
+            },
+            None => {
+                notifier.notify(Err(OrientationLockError::NotSupported));
+                return;
+            },
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = JsFuture::from(promise).await.map(|_| ()).map_err(orientation_lock_error);
+            notifier.notify(result);
+        });
     }
 
     #[inline]
@@ -324,18 +668,33 @@ impl Inner {
     }
 
     #[inline]
-    pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {
-        // Currently a no-op as it does not seem there is good support for this on web
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        let scale_factor = self.scale_factor();
+        self.canvas
+            .borrow()
+            .ime
+            .set_cursor_area(position.to_logical(scale_factor), size.to_logical(scale_factor));
     }
 
     #[inline]
-    pub fn set_ime_allowed(&self, _allowed: bool) {
-        // Currently not implemented
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if self.canvas.borrow().ime.set_allowed(allowed) {
+            let ime = if allowed { Ime::Enabled } else { Ime::Disabled };
+            self.runner.send_event(Event::WindowEvent {
+                window_id: RootWI(self.id),
+                event: WindowEvent::Ime(ime),
+            });
+        }
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {
-        // Currently not implemented
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.canvas.borrow().ime.set_purpose(purpose)
+    }
+
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        Some(ImeCapabilities::all())
     }
 
     #[inline]
@@ -344,23 +703,33 @@ impl Inner {
     }
 
     #[inline]
-    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
-        // Currently an intentional no-op
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        self.user_attention.request(request_type)
+    }
+
+    #[inline]
+    pub fn set_progress(&self, _state: ProgressState) -> Result<(), NotSupportedError> {
+        Err(NotSupportedError::new())
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        self.user_attention.set_badge_count(count)
     }
 
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
-        None
+        self.runner.monitor().current_monitor()
     }
 
     #[inline]
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
-        VecDeque::new()
+        self.runner.monitor().available_monitors().into()
     }
 
     #[inline]
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
-        None
+        self.runner.monitor().primary_monitor()
     }
 
     #[inline]
@@ -412,7 +781,7 @@ impl Inner {
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        self.title.borrow().clone()
     }
 
     pub fn reset_dead_keys(&self) {
@@ -422,11 +791,116 @@ impl Inner {
 
 impl Drop for Inner {
     fn drop(&mut self) {
+        if self.owns_document_title() {
+            self.runner.set_document_title_owner(None);
+        }
+
         if let Some(destroy_fn) = self.destroy_fn.take() {
             destroy_fn();
         }
     }
 }
+#[derive(Clone, Debug)]
+enum KeyboardLockError {
+    NotSupported,
+    Rejected(String),
+}
+
+#[derive(Debug)]
+pub struct KeyboardLockFuture(Notified<Result<(), KeyboardLockError>>);
+
+impl Future for KeyboardLockFuture {
+    type Output = Result<(), ExternalError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map_err(|error| match error {
+            KeyboardLockError::NotSupported => {
+                ExternalError::NotSupported(NotSupportedError::new())
+            },
+            KeyboardLockError::Rejected(message) => ExternalError::Os(os_error!(OsError(message))),
+        })
+    }
+}
+
+fn orientation_lock_error(error: JsValue) -> OrientationLockError {
+    let error: DomException = error.unchecked_into();
+    match error.name().as_str() {
+        "NotSupportedError" => OrientationLockError::NotSupported,
+        "SecurityError" => OrientationLockError::NotAllowed(error.message()),
+        "AbortError" => OrientationLockError::Aborted,
+        _ => OrientationLockError::Other(error.message()),
+    }
+}
+
+#[derive(Debug)]
+pub struct OrientationLockFuture(Notified<Result<(), OrientationLockError>>);
+
+impl Future for OrientationLockFuture {
+    type Output = Result<(), OrientationLockError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// The window is created by the event loop right before `AboutToWait`, unless the future was
+/// dropped by then.
+pub struct CreateWindowFuture(Arc<Mutex<WindowRequest>>);
+
+#[derive(Default)]
+struct WindowRequest {
+    result: Option<Result<Window, RootOE>>,
+    waker: Option<Waker>,
+}
+
+impl CreateWindowFuture {
+    pub(crate) fn new(target: &ActiveEventLoop, attributes: WindowAttributes) -> Self {
+        let request = Arc::new(Mutex::new(WindowRequest::default()));
+
+        let target = target.clone();
+        let weak = Arc::downgrade(&request);
+        target.runner.clone().request_window(Box::new(move || {
+            // Dropping the future cancels the request before any canvas is created.
+            let Some(request) = weak.upgrade() else {
+                return;
+            };
+
+            let result = Window::new(&target, attributes);
+            let mut request = request.lock().unwrap();
+            request.result = Some(result);
+            if let Some(waker) = request.waker.take() {
+                waker.wake();
+            }
+        }));
+
+        Self(request)
+    }
+}
+
+impl Future for CreateWindowFuture {
+    type Output = Result<Window, RootOE>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut request = self.0.lock().unwrap();
+
+        match request.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                request.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for CreateWindowFuture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CreateWindowFuture").finish_non_exhaustive()
+    }
+}
+
+// The ids are `u32`s like the ids of `WebWindowHandle`, `WindowId::into_raw` widens them so
+// that `WindowId::from_raw` gets them back.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(pub(crate) u32);
 
@@ -453,7 +927,16 @@ pub struct PlatformSpecificWindowAttributes {
     pub(crate) canvas: Option<Arc<MainThreadSafe<backend::RawCanvasType>>>,
     pub(crate) prevent_default: bool,
     pub(crate) focusable: bool,
+    pub(crate) focus_on_pointer_down: bool,
+    pub(crate) position_mode: PositionMode,
+    pub(crate) wheel_delta_policy: WheelDeltaPolicy,
+    pub(crate) wheel_pinch_gesture: bool,
     pub(crate) append: bool,
+    pub(crate) set_document_title: Option<bool>,
+    pub(crate) auto_parent_size: bool,
+    pub(crate) close_requested_on_unload: bool,
+    pub(crate) fullscreen_element: Option<FullscreenTarget>,
+    pub(crate) fullscreen_navigation_ui: FullscreenNavigationUi,
 }
 
 impl PlatformSpecificWindowAttributes {
@@ -468,10 +951,59 @@ impl PlatformSpecificWindowAttributes {
 
         self.canvas = Some(Arc::new(MainThreadSafe::new(main_thread, canvas)));
     }
+
+    pub(crate) fn set_fullscreen_element(&mut self, element: Option<FullscreenElement>) {
+        self.fullscreen_element = element.map(FullscreenTarget::new);
+    }
 }
 
 impl Default for PlatformSpecificWindowAttributes {
     fn default() -> Self {
-        Self { canvas: None, prevent_default: true, focusable: true, append: false }
+        Self {
+            canvas: None,
+            prevent_default: true,
+            focusable: true,
+            focus_on_pointer_down: true,
+            position_mode: PositionMode::default(),
+            wheel_delta_policy: WheelDeltaPolicy::default(),
+            wheel_pinch_gesture: false,
+            append: false,
+            set_document_title: None,
+            auto_parent_size: false,
+            close_requested_on_unload: false,
+            fullscreen_element: None,
+            fullscreen_navigation_ui: FullscreenNavigationUi::default(),
+        }
+    }
+}
+
+/// A [`FullscreenElement`] that can be sent to the main thread.
+#[derive(Clone, Debug)]
+pub(crate) enum FullscreenTarget {
+    Element(Arc<MainThreadSafe<HtmlElement>>),
+    Selector(String),
+}
+
+impl FullscreenTarget {
+    fn new(element: FullscreenElement) -> Self {
+        match element {
+            FullscreenElement::Element(element) => {
+                let main_thread = MainThreadMarker::new()
+                    .expect("received a `HtmlElement` outside the window context");
+                Self::Element(Arc::new(MainThreadSafe::new(main_thread, element)))
+            },
+            FullscreenElement::Selector(selector) => Self::Selector(selector),
+        }
+    }
+
+    pub(crate) fn into_element(self, main_thread: MainThreadMarker) -> FullscreenElement {
+        match self {
+            Self::Element(element) => FullscreenElement::Element(
+                Arc::try_unwrap(element)
+                    .map(|element| element.into_inner(main_thread))
+                    .unwrap_or_else(|element| element.get(main_thread).clone()),
+            ),
+            Self::Selector(selector) => FullscreenElement::Selector(selector),
+        }
     }
 }
@@ -0,0 +1,560 @@
+//! Rendering to a canvas from a worker.
+//!
+//! The main thread transfers control of the canvas to an `OffscreenCanvas`, which it posts to
+//! the worker together with a `MessagePort`. Input, focus and size changes of the canvas can only
+//! be observed on the main thread, so [`WorkerCanvasShim`] serializes them into plain objects and
+//! posts them to the port, where [`WorkerCanvas`] turns them back into [`WindowEvent`]s.
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+
+use js_sys::{Array, Function, Object, Reflect};
+use smol_str::SmolStr;
+use wasm_bindgen::prelude::{wasm_bindgen, Closure};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Event, HtmlCanvasElement, KeyboardEvent, MessageChannel, MessageEvent, MessagePort,
+    OffscreenCanvas, PointerEvent, WheelEvent, Worker,
+};
+use web_time::Instant;
+
+use super::backend::{self, event, EventListenerHandle, ResizeScaleHandle, Style};
+use super::device::DeviceId;
+use super::{KeyEventExtra, OsError};
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::OsError as RootOE;
+use crate::event::{
+    DeviceId as RootDeviceId, ElementState, Force, InnerSizeWriter, KeyEvent, Modifiers,
+    MouseButton, MouseScrollDelta, Touch, TouchPhase, TouchTool, WindowEvent,
+};
+use crate::keyboard::{Key, KeyLocation, LockKeys, ModifiersState, PhysicalKey};
+use crate::utils::{ClickCounter, ClickThresholds};
+
+/// Name of the property of the message posted to the worker that holds the canvas.
+const MESSAGE_KEY: &str = "__winit_offscreen_canvas";
+
+pub struct WorkerCanvasShim {
+    _port: MessagePort,
+    _listeners: Vec<EventListenerHandle<dyn FnMut(Event)>>,
+    _resize_scale: ResizeScaleHandle,
+}
+
+impl WorkerCanvasShim {
+    pub fn new(canvas: HtmlCanvasElement, worker: &Worker) -> Result<Self, RootOE> {
+        #[allow(clippy::disallowed_methods)]
+        let window = web_sys::window().ok_or_else(|| {
+            os_error!(OsError(
+                "`WorkerCanvasShim` can only be created on the main thread".to_owned()
+            ))
+        })?;
+        #[allow(clippy::disallowed_methods)]
+        let document = window.document().expect("Failed to obtain document");
+
+        let offscreen = canvas.transfer_control_to_offscreen().map_err(|_| {
+            os_error!(OsError("Failed to transfer control of the canvas to the worker".to_owned()))
+        })?;
+        let channel = MessageChannel::new()
+            .map_err(|_| os_error!(OsError("Failed to create `MessageChannel`".to_owned())))?;
+        let port = channel.port1();
+
+        let scale = backend::scale_factor(&window);
+        let message = Object::new();
+        let init = message_object(&[
+            ("canvas", offscreen.clone().into()),
+            ("port", channel.port2().into()),
+            ("scale", scale.into()),
+        ]);
+        let _ = Reflect::set(&message, &MESSAGE_KEY.into(), &init);
+        worker
+            .post_message_with_transfer(&message, &Array::of2(&offscreen, &channel.port2()))
+            .map_err(|_| {
+                os_error!(OsError("Failed to post the canvas to the worker".to_owned()))
+            })?;
+
+        // A tabindex is needed in order to capture local keyboard events.
+        let _ = canvas.set_attribute("tabindex", "0");
+
+        let mut listeners = Vec::new();
+        let mut listen = |event_type: &'static str, handler: Box<dyn FnMut(Event)>| {
+            listeners.push(EventListenerHandle::new(
+                canvas.clone(),
+                event_type,
+                Closure::wrap(handler),
+            ))
+        };
+
+        for (event_type, focused) in [("focus", true), ("blur", false)] {
+            let port = port.clone();
+            listen(
+                event_type,
+                Box::new(move |_| post(&port, "focus", &[("focused", focused.into())])),
+            );
+        }
+
+        for (event_type, phase) in [
+            ("pointerover", "enter"),
+            ("pointerout", "leave"),
+            ("pointermove", "move"),
+            ("pointerdown", "down"),
+            ("pointerup", "up"),
+            ("pointercancel", "cancel"),
+        ] {
+            let port = port.clone();
+            let window = window.clone();
+            let canvas = canvas.clone();
+            listen(
+                event_type,
+                Box::new(move |event| {
+                    let event: PointerEvent = event.unchecked_into();
+                    let mut phase = phase;
+                    let button = event::mouse_button(&event);
+
+                    if phase == "down" {
+                        // Prevent text selection, but still focus the canvas.
+                        event.prevent_default();
+                        let _ = canvas.focus();
+                        if event.pointer_type() == "mouse" {
+                            let _ = canvas.set_pointer_capture(event.pointer_id());
+                        }
+                    } else if let (Some(button), "move") = (button, phase) {
+                        // Pressing or releasing a button while another is pressed is reported as
+                        // a move, see <https://www.w3.org/TR/pointerevents3/#chorded-buttons>.
+                        phase = if event::mouse_buttons(&event).contains(button.into()) {
+                            "down"
+                        } else {
+                            "up"
+                        };
+                    }
+
+                    let position: PhysicalPosition<f64> =
+                        event::mouse_position(&event).to_physical(backend::scale_factor(&window));
+                    let modifiers = event::mouse_modifiers(&event);
+                    post(&port, "pointer", &[
+                        ("phase", phase.into()),
+                        ("touch", (event.pointer_type() == "touch").into()),
+                        ("id", event.pointer_id().into()),
+                        ("x", position.x.into()),
+                        ("y", position.y.into()),
+                        ("button", button.map(MouseButton::to_id).into()),
+                        ("force", event.pressure().into()),
+                        ("modifiers", modifiers.state().bits().into()),
+                        ("lock_keys", modifiers.lock_keys().bits().into()),
+                    ])
+                }),
+            );
+        }
+
+        {
+            let port = port.clone();
+            let window = window.clone();
+            listen(
+                "wheel",
+                Box::new(move |event| {
+                    let event: WheelEvent = event.unchecked_into();
+                    event.prevent_default();
+                    let (line, x, y) = match event::mouse_scroll_delta(&window, &event) {
+                        Some(MouseScrollDelta::LineDelta(x, y)) => (true, x as f64, y as f64),
+                        Some(MouseScrollDelta::PixelDelta(delta)) => (false, delta.x, delta.y),
+                        None => return,
+                    };
+                    let modifiers = event::mouse_modifiers(&event);
+                    post(&port, "wheel", &[
+                        ("id", 0.into()),
+                        ("line", line.into()),
+                        ("x", x.into()),
+                        ("y", y.into()),
+                        ("modifiers", modifiers.state().bits().into()),
+                        ("lock_keys", modifiers.lock_keys().bits().into()),
+                    ])
+                }),
+            );
+        }
+
+        for (event_type, pressed) in [("keydown", true), ("keyup", false)] {
+            let port = port.clone();
+            listen(
+                event_type,
+                Box::new(move |event| {
+                    let event: KeyboardEvent = event.unchecked_into();
+                    event.prevent_default();
+                    let modifiers = event::keyboard_modifiers(&event);
+                    post(&port, "key", &[
+                        ("pressed", pressed.into()),
+                        ("code", event.code().into()),
+                        ("key", event.key().into()),
+                        ("text", event::key_text(&event).as_deref().into()),
+                        ("location", location_to_id(event::key_location(&event)).into()),
+                        ("repeat", event.repeat().into()),
+                        ("modifiers", modifiers.state().bits().into()),
+                        ("lock_keys", modifiers.lock_keys().bits().into()),
+                    ])
+                }),
+            );
+        }
+
+        listen("contextmenu", Box::new(|event| event.prevent_default()));
+
+        let style = Style::new(&window, &canvas);
+        let resize_scale = ResizeScaleHandle::new(
+            window,
+            document,
+            canvas,
+            style,
+            {
+                let port = port.clone();
+                move |size: PhysicalSize<u32>, scale| {
+                    post(&port, "scale", &[
+                        ("scale", scale.into()),
+                        ("width", size.width.into()),
+                        ("height", size.height.into()),
+                    ])
+                }
+            },
+            {
+                let port = port.clone();
+                move |size: PhysicalSize<u32>| {
+                    post(&port, "resize", &[
+                        ("width", size.width.into()),
+                        ("height", size.height.into()),
+                    ])
+                }
+            },
+        );
+
+        Ok(Self { _port: port, _listeners: listeners, _resize_scale: resize_scale })
+    }
+}
+
+impl Drop for WorkerCanvasShim {
+    fn drop(&mut self) {
+        self._port.close();
+    }
+}
+
+pub struct WorkerCanvas(Rc<Inner>);
+
+type EventHandler = Box<dyn FnMut(WindowEvent)>;
+type AnimationFrame = (i32, Closure<dyn FnMut()>);
+
+struct Inner {
+    canvas: OffscreenCanvas,
+    port: MessagePort,
+    size: Cell<PhysicalSize<u32>>,
+    scale: Cell<f64>,
+    modifiers: Cell<Modifiers>,
+    click_counter: RefCell<ClickCounter>,
+    handler: RefCell<Option<EventHandler>>,
+    _on_message: EventListenerHandle<dyn FnMut(MessageEvent)>,
+    animation_frame: RefCell<Option<AnimationFrame>>,
+}
+
+impl WorkerCanvas {
+    pub fn from_message(data: &JsValue) -> Option<Self> {
+        let init = Reflect::get(data, &MESSAGE_KEY.into()).ok().filter(JsValue::is_object)?;
+        let canvas: OffscreenCanvas = get(&init, "canvas").dyn_into().ok()?;
+        let port: MessagePort = get(&init, "port").dyn_into().ok()?;
+        let scale = get(&init, "scale").as_f64().unwrap_or(1.);
+
+        let inner = Rc::new_cyclic(|weak: &Weak<Inner>| {
+            let weak = weak.clone();
+            let on_message = EventListenerHandle::new(
+                port.clone(),
+                "message",
+                Closure::new(move |event: MessageEvent| {
+                    if let Some(inner) = weak.upgrade() {
+                        inner.handle_message(&event.data());
+                    }
+                }),
+            );
+
+            Inner {
+                canvas,
+                port: port.clone(),
+                size: Cell::new(PhysicalSize::default()),
+                scale: Cell::new(scale),
+                modifiers: Cell::new(Modifiers::default()),
+                click_counter: RefCell::new(ClickCounter::default()),
+                handler: RefCell::new(None),
+                _on_message: on_message,
+                animation_frame: RefCell::new(None),
+            }
+        });
+        port.start();
+
+        Some(Self(inner))
+    }
+
+    pub fn offscreen_canvas(&self) -> &OffscreenCanvas {
+        &self.0.canvas
+    }
+
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        self.0.size.get()
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.0.scale.get()
+    }
+
+    pub fn set_event_handler(&self, handler: Box<dyn FnMut(WindowEvent)>) {
+        *self.0.handler.borrow_mut() = Some(handler);
+    }
+
+    pub fn request_redraw(&self) {
+        if self.0.animation_frame.borrow().is_some() {
+            return;
+        }
+
+        let weak = Rc::downgrade(&self.0);
+        let callback = Closure::new(move || {
+            if let Some(inner) = Weak::upgrade(&weak) {
+                inner.animation_frame.borrow_mut().take();
+                inner.send(WindowEvent::RedrawRequested);
+            }
+        });
+        let global: WorkerGlobalExt = js_sys::global().unchecked_into();
+        let handle = global.request_animation_frame(callback.as_ref().unchecked_ref());
+        *self.0.animation_frame.borrow_mut() = Some((handle, callback));
+    }
+
+    #[cfg(feature = "rwh_06")]
+    pub fn raw_window_handle_rwh_06(&self) -> rwh_06::RawWindowHandle {
+        // SAFETY: This will only work if the reference to `OffscreenCanvas` stays valid.
+        let canvas: &JsValue = &self.0.canvas;
+        let window_handle =
+            rwh_06::WebOffscreenCanvasWindowHandle::new(std::ptr::NonNull::from(canvas).cast());
+        rwh_06::RawWindowHandle::WebOffscreenCanvas(window_handle)
+    }
+}
+
+impl Drop for WorkerCanvas {
+    fn drop(&mut self) {
+        if let Some((handle, _)) = self.0.animation_frame.borrow_mut().take() {
+            let global: WorkerGlobalExt = js_sys::global().unchecked_into();
+            global.cancel_animation_frame(handle);
+        }
+        self.0.port.close();
+    }
+}
+
+impl Inner {
+    fn send(&self, event: WindowEvent) {
+        // Take the handler out while calling it, so it can be replaced from inside.
+        let Some(mut handler) = self.handler.borrow_mut().take() else {
+            return;
+        };
+        handler(event);
+        self.handler.borrow_mut().get_or_insert(handler);
+    }
+
+    fn update_modifiers(&self, data: &JsValue) {
+        let state = ModifiersState::from_bits_retain(get_u32(data, "modifiers"));
+        let lock_keys = LockKeys::from_bits_retain(get_u32(data, "lock_keys") as u8);
+        let modifiers = Modifiers { lock_keys, ..state.into() };
+        if self.modifiers.replace(modifiers) != modifiers {
+            self.send(WindowEvent::ModifiersChanged(modifiers));
+        }
+    }
+
+    fn handle_message(&self, data: &JsValue) {
+        match get(data, "type").as_string().as_deref() {
+            Some("focus") => {
+                let focused = get(data, "focused").is_truthy();
+                if !focused && self.modifiers.get() != Modifiers::default() {
+                    self.modifiers.set(Modifiers::default());
+                    self.send(WindowEvent::ModifiersChanged(Modifiers::default()));
+                }
+                self.send(WindowEvent::Focused(focused));
+            },
+            Some("resize") => {
+                let size = PhysicalSize::new(get_u32(data, "width"), get_u32(data, "height"));
+                if self.size.replace(size) != size {
+                    self.send(WindowEvent::Resized(size));
+                }
+            },
+            Some("scale") => {
+                let scale_factor = get(data, "scale").as_f64().unwrap_or(1.);
+                let size = PhysicalSize::new(get_u32(data, "width"), get_u32(data, "height"));
+                self.scale.set(scale_factor);
+                // The size of the canvas is decided by the page, so the requested size is ignored.
+                let new_size = Arc::new(Mutex::new(size));
+                self.send(WindowEvent::ScaleFactorChanged {
+                    scale_factor,
+                    inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_size)),
+                });
+                if self.size.replace(size) != size {
+                    self.send(WindowEvent::Resized(size));
+                }
+            },
+            Some("pointer") => {
+                self.update_modifiers(data);
+                self.handle_pointer(data);
+            },
+            Some("wheel") => {
+                self.update_modifiers(data);
+                let x = get(data, "x").as_f64().unwrap_or_default();
+                let y = get(data, "y").as_f64().unwrap_or_default();
+                let delta = if get(data, "line").is_truthy() {
+                    MouseScrollDelta::LineDelta(x as f32, y as f32)
+                } else {
+                    MouseScrollDelta::PixelDelta(PhysicalPosition::new(x, y))
+                };
+                self.send(WindowEvent::MouseWheel {
+                    device_id: device_id(data),
+                    delta,
+                    phase: TouchPhase::Moved,
+                    momentum_phase: None,
+                });
+            },
+            Some("key") => {
+                self.update_modifiers(data);
+                let state = if get(data, "pressed").is_truthy() {
+                    ElementState::Pressed
+                } else {
+                    ElementState::Released
+                };
+                let code = get(data, "code").as_string().unwrap_or_default();
+                let key = get(data, "key").as_string().unwrap_or_default();
+                self.send(WindowEvent::KeyboardInput {
+                    device_id: RootDeviceId(unsafe { DeviceId::dummy() }),
+                    event: KeyEvent {
+                        physical_key: PhysicalKey::from_key_code_attribute_value(&code),
+                        logical_key: Key::from_key_attribute_value(&key),
+                        text: get(data, "text").as_string().map(SmolStr::from),
+                        location: location_from_id(get_u32(data, "location")),
+                        state,
+                        repeat: get(data, "repeat").is_truthy(),
+                        platform_specific: KeyEventExtra,
+                    },
+                    is_synthetic: false,
+                });
+            },
+            _ => (),
+        }
+    }
+
+    fn handle_pointer(&self, data: &JsValue) {
+        let phase = get(data, "phase").as_string().unwrap_or_default();
+        let device_id = device_id(data);
+        let position = PhysicalPosition::new(
+            get(data, "x").as_f64().unwrap_or_default(),
+            get(data, "y").as_f64().unwrap_or_default(),
+        );
+
+        if get(data, "touch").is_truthy() {
+            let phase = match phase.as_str() {
+                "down" => TouchPhase::Started,
+                "move" => TouchPhase::Moved,
+                "up" => TouchPhase::Ended,
+                "cancel" => TouchPhase::Cancelled,
+                _ => return,
+            };
+            self.send(WindowEvent::Touch(Touch {
+                device_id,
+                phase,
+                location: position,
+                force: Some(Force::Normalized(get(data, "force").as_f64().unwrap_or_default())),
+                id: get(data, "id").as_f64().unwrap_or_default() as u64,
+                tool: TouchTool::Finger,
+                azimuth_angle: None,
+                altitude_angle: None,
+                twist_angle: None,
+                is_estimated: false,
+                is_predicted: false,
+            }));
+            return;
+        }
+
+        let button = get(data, "button").as_f64().map(|id| mouse_button_from_id(id as u16));
+        match (phase.as_str(), button) {
+            ("enter", _) => self.send(WindowEvent::CursorEntered { device_id }),
+            ("leave", _) => self.send(WindowEvent::CursorLeft { device_id }),
+            ("move", _) => {
+                self.click_counter.borrow_mut().moved(position, ClickThresholds::default());
+                self.send(WindowEvent::CursorMoved { device_id, position })
+            },
+            ("down" | "up", Some(button)) => {
+                let mut click_counter = self.click_counter.borrow_mut();
+                let (state, click_count) = if phase == "down" {
+                    let thresholds = ClickThresholds::default();
+                    let click_count =
+                        click_counter.press(button, position, Instant::now(), thresholds);
+                    (ElementState::Pressed, click_count)
+                } else {
+                    (ElementState::Released, click_counter.release(button))
+                };
+                drop(click_counter);
+                self.send(WindowEvent::CursorMoved { device_id, position });
+                self.send(WindowEvent::MouseInput { device_id, state, button, click_count });
+            },
+            _ => (),
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type WorkerGlobalExt;
+
+    #[wasm_bindgen(method, js_name = requestAnimationFrame)]
+    fn request_animation_frame(this: &WorkerGlobalExt, callback: &Function) -> i32;
+
+    #[wasm_bindgen(method, js_name = cancelAnimationFrame)]
+    fn cancel_animation_frame(this: &WorkerGlobalExt, handle: i32);
+}
+
+fn message_object(fields: &[(&str, JsValue)]) -> Object {
+    let object = Object::new();
+    for (key, value) in fields {
+        let _ = Reflect::set(&object, &JsValue::from_str(key), value);
+    }
+    object
+}
+
+fn post(port: &MessagePort, kind: &str, fields: &[(&str, JsValue)]) {
+    let message = message_object(fields);
+    let _ = Reflect::set(&message, &"type".into(), &kind.into());
+    let _ = port.post_message(&message);
+}
+
+fn get(data: &JsValue, key: &str) -> JsValue {
+    Reflect::get(data, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn get_u32(data: &JsValue, key: &str) -> u32 {
+    get(data, key).as_f64().unwrap_or_default() as u32
+}
+
+fn device_id(data: &JsValue) -> RootDeviceId {
+    RootDeviceId(DeviceId(get(data, "id").as_f64().unwrap_or_default() as i32))
+}
+
+fn mouse_button_from_id(id: u16) -> MouseButton {
+    match id {
+        0 => MouseButton::Left,
+        1 => MouseButton::Right,
+        2 => MouseButton::Middle,
+        3 => MouseButton::Back,
+        4 => MouseButton::Forward,
+        id => MouseButton::Other(id),
+    }
+}
+
+fn location_to_id(location: KeyLocation) -> u32 {
+    match location {
+        KeyLocation::Standard => 0,
+        KeyLocation::Left => 1,
+        KeyLocation::Right => 2,
+        KeyLocation::Numpad => 3,
+    }
+}
+
+fn location_from_id(id: u32) -> KeyLocation {
+    match id {
+        1 => KeyLocation::Left,
+        2 => KeyLocation::Right,
+        3 => KeyLocation::Numpad,
+        _ => KeyLocation::Standard,
+    }
+}
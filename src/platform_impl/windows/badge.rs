@@ -0,0 +1,131 @@
+//! The badge shown as an overlay icon on the taskbar button.
+//!
+//! Overlay icons are images, so the count is drawn by winit: a red disc with the count written
+//! in white with a tiny built-in font.
+
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSMICON};
+
+use crate::icon::BadIcon;
+use crate::platform_impl::platform::icon::WinIcon;
+
+/// Counts above this are shown as `99+`, since the overlay icon only fits a few characters.
+const MAX_COUNT: u64 = 99;
+
+const BACKGROUND: [u8; 3] = [0xd1, 0x34, 0x38];
+const FOREGROUND: [u8; 3] = [0xff, 0xff, 0xff];
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+/// The glyphs of the digits and of `+`, one row per byte with the leftmost pixel in bit 2.
+const GLYPHS: [[u8; GLYPH_HEIGHT as usize]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b011, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    [0b000, 0b010, 0b111, 0b010, 0b000],
+];
+const PLUS: usize = 10;
+
+/// Create the overlay icon showing the count, at the size of the small icons.
+pub(crate) fn badge_icon(count: u64) -> Result<WinIcon, BadIcon> {
+    let size = unsafe { GetSystemMetrics(SM_CXSMICON) }.max(16) as u32;
+    WinIcon::from_rgba(badge_rgba(count, size), size, size)
+}
+
+/// The indices in [`GLYPHS`] of the characters showing the count.
+fn badge_glyphs(count: u64) -> Vec<usize> {
+    if count > MAX_COUNT {
+        let mut glyphs = badge_glyphs(MAX_COUNT);
+        glyphs.push(PLUS);
+        return glyphs;
+    }
+
+    count.to_string().bytes().map(|digit| (digit - b'0') as usize).collect()
+}
+
+/// Draw the badge in a square image of `size` pixels.
+fn badge_rgba(count: u64, size: u32) -> Vec<u8> {
+    let mut rgba = vec![0; (size * size * 4) as usize];
+
+    // The disc, with antialiased edges.
+    let radius = size as f32 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 + 0.5 - radius;
+            let dy = y as f32 + 0.5 - radius;
+            let coverage = (radius - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            let offset = ((y * size + x) * 4) as usize;
+            rgba[offset..offset + 3].copy_from_slice(&BACKGROUND);
+            rgba[offset + 3] = (coverage * 255.0).round() as u8;
+        }
+    }
+
+    // The text, as large as it fits in the square inscribed in the disc.
+    let glyphs = badge_glyphs(count);
+    let count = glyphs.len() as u32;
+    let available = (size as f32 / std::f32::consts::SQRT_2) as u32;
+    let scale = (available / (count * (GLYPH_WIDTH + 1) - 1)).min(available / GLYPH_HEIGHT).max(1);
+    let width = (count * (GLYPH_WIDTH + 1) - 1) * scale;
+    let left = size.saturating_sub(width) / 2;
+    let top = size.saturating_sub(GLYPH_HEIGHT * scale) / 2;
+
+    for (index, &glyph) in glyphs.iter().enumerate() {
+        let glyph_left = left + index as u32 * (GLYPH_WIDTH + 1) * scale;
+        for (row, bits) in GLYPHS[glyph].iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                for y in 0..scale {
+                    for x in 0..scale {
+                        let x = glyph_left + column * scale + x;
+                        let y = top + row as u32 * scale + y;
+                        if x < size && y < size {
+                            let offset = ((y * size + x) * 4) as usize;
+                            rgba[offset..offset + 3].copy_from_slice(&FOREGROUND);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    rgba
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_counts_are_elided() {
+        assert_eq!(badge_glyphs(7), [7]);
+        assert_eq!(badge_glyphs(42), [4, 2]);
+        assert_eq!(badge_glyphs(99), [9, 9]);
+        assert_eq!(badge_glyphs(100), [9, 9, PLUS]);
+        assert_eq!(badge_glyphs(u64::MAX), [9, 9, PLUS]);
+    }
+
+    #[test]
+    fn text_is_centered() {
+        let size = 16;
+        let rgba = badge_rgba(1, size);
+        assert_eq!(rgba.len(), (size * size * 4) as usize);
+
+        let is_text = |x: u32, y: u32| {
+            let offset = ((y * size + x) * 4) as usize;
+            rgba[offset..offset + 3] == FOREGROUND
+        };
+        // The corners are transparent and the center is covered by the `1`.
+        assert_eq!(rgba[3], 0);
+        assert!(is_text(size / 2, size / 2));
+        assert!(!is_text(0, 0));
+    }
+}
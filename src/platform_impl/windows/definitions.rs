@@ -3,11 +3,13 @@
 
 use std::ffi::c_void;
 
-use windows_sys::core::{IUnknown, GUID, HRESULT};
+use windows_sys::core::{IUnknown, GUID, HRESULT, PCWSTR};
 use windows_sys::Win32::Foundation::{BOOL, HWND, POINTL};
 use windows_sys::Win32::System::Com::{
     IAdviseSink, IDataObject, IEnumFORMATETC, IEnumSTATDATA, FORMATETC, STGMEDIUM,
 };
+use windows_sys::Win32::UI::Shell::TBPFLAG;
+use windows_sys::Win32::UI::WindowsAndMessaging::HICON;
 
 #[repr(C)]
 pub struct IUnknownVtbl {
@@ -33,7 +35,7 @@ pub struct IDataObjectVtbl {
         pformatetc: *const FORMATETC,
         pmedium: *mut STGMEDIUM,
     ) -> HRESULT,
-    QueryGetData:
+    pub QueryGetData:
         unsafe extern "system" fn(This: *mut IDataObject, pformatetc: *const FORMATETC) -> HRESULT,
     pub GetCanonicalFormatEtc: unsafe extern "system" fn(
         This: *mut IDataObject,
@@ -43,7 +45,7 @@ pub struct IDataObjectVtbl {
     pub SetData: unsafe extern "system" fn(
         This: *mut IDataObject,
         pformatetc: *const FORMATETC,
-        pformatetcOut: *const FORMATETC,
+        pmedium: *const STGMEDIUM,
         fRelease: BOOL,
     ) -> HRESULT,
     pub EnumFormatEtc: unsafe extern "system" fn(
@@ -96,6 +98,22 @@ pub struct IDropTarget {
     pub lpVtbl: *const IDropTargetVtbl,
 }
 
+#[repr(C)]
+pub struct IDropSourceVtbl {
+    pub parent: IUnknownVtbl,
+    pub QueryContinueDrag: unsafe extern "system" fn(
+        This: *mut IDropSource,
+        fEscapePressed: BOOL,
+        grfKeyState: u32,
+    ) -> HRESULT,
+    pub GiveFeedback: unsafe extern "system" fn(This: *mut IDropSource, dwEffect: u32) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDropSource {
+    pub lpVtbl: *const IDropSourceVtbl,
+}
+
 #[repr(C)]
 pub struct ITaskbarListVtbl {
     pub parent: IUnknownVtbl,
@@ -126,6 +144,42 @@ pub struct ITaskbarList2 {
     pub lpVtbl: *const ITaskbarList2Vtbl,
 }
 
+// Only the leading methods used by winit are declared, the rest of the vtable is omitted.
+// The methods in between that winit doesn't use are declared as pointers.
+#[repr(C)]
+pub struct ITaskbarList3Vtbl {
+    pub parent: ITaskbarList2Vtbl,
+    pub SetProgressValue: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        ullCompleted: u64,
+        ullTotal: u64,
+    ) -> HRESULT,
+    pub SetProgressState: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        tbpFlags: TBPFLAG,
+    ) -> HRESULT,
+    pub RegisterTab: *const c_void,
+    pub UnregisterTab: *const c_void,
+    pub SetTabOrder: *const c_void,
+    pub SetTabActive: *const c_void,
+    pub ThumbBarAddButtons: *const c_void,
+    pub ThumbBarUpdateButtons: *const c_void,
+    pub ThumbBarSetImageList: *const c_void,
+    pub SetOverlayIcon: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        hIcon: HICON,
+        pszDescription: PCWSTR,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct ITaskbarList3 {
+    pub lpVtbl: *const ITaskbarList3Vtbl,
+}
+
 pub const CLSID_TaskbarList: GUID = GUID {
     data1: 0x56fdf344,
     data2: 0xfd6d,
@@ -146,3 +200,31 @@ pub const IID_ITaskbarList2: GUID = GUID {
     data3: 0x429b,
     data4: [0xa6, 0x6e, 0x19, 0x35, 0xe4, 0x4f, 0x43, 0x17],
 };
+
+pub const IID_ITaskbarList3: GUID = GUID {
+    data1: 0xea1afb91,
+    data2: 0x9e28,
+    data3: 0x4b86,
+    data4: [0x90, 0xe9, 0x9e, 0x9f, 0x8a, 0x5e, 0xef, 0xaf],
+};
+
+pub const IID_IUnknown: GUID = GUID {
+    data1: 0x00000000,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+pub const IID_IDataObject: GUID = GUID {
+    data1: 0x0000010e,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+pub const IID_IDropSource: GUID = GUID {
+    data1: 0x00000121,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
@@ -0,0 +1,284 @@
+use std::ffi::c_void;
+use std::os::windows::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{iter, mem, ptr, slice};
+
+use windows_sys::core::{IUnknown, GUID, HRESULT};
+use windows_sys::Win32::Foundation::{
+    GlobalFree, BOOL, DRAGDROP_S_CANCEL, DRAGDROP_S_DROP, DRAGDROP_S_USEDEFAULTCURSORS,
+    E_NOINTERFACE, HGLOBAL, POINT, S_OK,
+};
+use windows_sys::Win32::System::Com::{
+    IDataObject, DVASPECT_CONTENT, FORMATETC, STGMEDIUM, STGMEDIUM_0, TYMED_HGLOBAL,
+};
+use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GHND};
+use windows_sys::Win32::System::Ole::{
+    DoDragDrop, CF_HDROP, CF_UNICODETEXT, DROPEFFECT_COPY, DROPEFFECT_MOVE,
+};
+use windows_sys::Win32::System::SystemServices::{
+    MK_LBUTTON, MK_MBUTTON, MK_RBUTTON, MK_XBUTTON1, MK_XBUTTON2,
+};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VK_LBUTTON, VK_MBUTTON, VK_RBUTTON, VK_XBUTTON1, VK_XBUTTON2,
+};
+use windows_sys::Win32::UI::Shell::DROPFILES;
+
+use crate::platform_impl::platform::definitions::{
+    IDataObjectVtbl, IDropSource, IDropSourceVtbl, IID_IDataObject, IID_IDropSource, IID_IUnknown,
+    IUnknownVtbl,
+};
+use crate::platform_impl::platform::util::SH_CREATE_DATA_OBJECT;
+
+use crate::event::DragAction;
+use crate::window::DragPayload;
+
+const MOUSE_BUTTONS: u32 = MK_LBUTTON | MK_RBUTTON | MK_MBUTTON | MK_XBUTTON1 | MK_XBUTTON2;
+
+#[repr(C)]
+pub struct DragSourceData {
+    pub interface: IDropSource,
+    refcount: AtomicUsize,
+}
+
+pub struct DragSource {
+    pub data: *mut DragSourceData,
+}
+
+#[allow(non_snake_case)]
+impl DragSource {
+    pub fn new() -> DragSource {
+        let data = Box::new(DragSourceData {
+            interface: IDropSource { lpVtbl: &DROP_SOURCE_VTBL as *const IDropSourceVtbl },
+            refcount: AtomicUsize::new(1),
+        });
+        DragSource { data: Box::into_raw(data) }
+    }
+
+    // Implement IUnknown
+    pub unsafe extern "system" fn QueryInterface(
+        this: *mut IUnknown,
+        riid: *const GUID,
+        ppvObject: *mut *mut c_void,
+    ) -> HRESULT {
+        // `DoDragDrop` asks for other interfaces, such as `IDropSourceNotify`, which are optional.
+        let riid = unsafe { &*riid };
+        if guid_eq(riid, &IID_IUnknown) || guid_eq(riid, &IID_IDropSource) {
+            unsafe {
+                Self::AddRef(this);
+                *ppvObject = this.cast();
+            }
+            S_OK
+        } else {
+            unsafe { *ppvObject = ptr::null_mut() };
+            E_NOINTERFACE
+        }
+    }
+
+    pub unsafe extern "system" fn AddRef(this: *mut IUnknown) -> u32 {
+        let drag_source_data = unsafe { Self::from_interface(this) };
+        let count = drag_source_data.refcount.fetch_add(1, Ordering::Release) + 1;
+        count as u32
+    }
+
+    pub unsafe extern "system" fn Release(this: *mut IUnknown) -> u32 {
+        let drag_source = unsafe { Self::from_interface(this) };
+        let count = drag_source.refcount.fetch_sub(1, Ordering::Release) - 1;
+        if count == 0 {
+            // Destroy the underlying data
+            drop(unsafe { Box::from_raw(drag_source as *mut DragSourceData) });
+        }
+        count as u32
+    }
+
+    pub unsafe extern "system" fn QueryContinueDrag(
+        _this: *mut IDropSource,
+        fEscapePressed: BOOL,
+        grfKeyState: u32,
+    ) -> HRESULT {
+        if fEscapePressed != 0 {
+            DRAGDROP_S_CANCEL
+        } else if grfKeyState & MOUSE_BUTTONS == 0 {
+            DRAGDROP_S_DROP
+        } else {
+            S_OK
+        }
+    }
+
+    pub unsafe extern "system" fn GiveFeedback(_this: *mut IDropSource, _dwEffect: u32) -> HRESULT {
+        DRAGDROP_S_USEDEFAULTCURSORS
+    }
+
+    unsafe fn from_interface<'a, InterfaceT>(this: *mut InterfaceT) -> &'a mut DragSourceData {
+        unsafe { &mut *(this as *mut _) }
+    }
+}
+
+impl Drop for DragSource {
+    fn drop(&mut self) {
+        unsafe {
+            DragSource::Release(self.data as *mut IUnknown);
+        }
+    }
+}
+
+static DROP_SOURCE_VTBL: IDropSourceVtbl = IDropSourceVtbl {
+    parent: IUnknownVtbl {
+        QueryInterface: DragSource::QueryInterface,
+        AddRef: DragSource::AddRef,
+        Release: DragSource::Release,
+    },
+    QueryContinueDrag: DragSource::QueryContinueDrag,
+    GiveFeedback: DragSource::GiveFeedback,
+};
+
+fn guid_eq(a: &GUID, b: &GUID) -> bool {
+    (a.data1, a.data2, a.data3, a.data4) == (b.data1, b.data2, b.data3, b.data4)
+}
+
+/// Whether a mouse button is held, which `DoDragDrop` needs to not drop right away.
+pub fn is_button_held() -> bool {
+    [VK_LBUTTON, VK_RBUTTON, VK_MBUTTON, VK_XBUTTON1, VK_XBUTTON2]
+        .into_iter()
+        .any(|key| unsafe { GetAsyncKeyState(key as i32) } as u16 & 0x8000 != 0)
+}
+
+/// Drags `payload` until it's dropped or cancelled, running the modal loop of `DoDragDrop`
+/// meanwhile, and returns the action the drop was accepted with.
+pub unsafe fn do_drag_drop(payload: &DragPayload) -> Option<DragAction> {
+    let data_object = unsafe { create_data_object(payload) }?;
+    let drag_source = DragSource::new();
+
+    let mut effect = 0;
+    let result = unsafe {
+        DoDragDrop(
+            data_object,
+            &mut (*drag_source.data).interface as *mut _ as *mut c_void,
+            DROPEFFECT_COPY | DROPEFFECT_MOVE,
+            &mut effect,
+        )
+    };
+    unsafe { release(data_object) };
+
+    if result != DRAGDROP_S_DROP {
+        return None;
+    }
+
+    match effect {
+        DROPEFFECT_COPY => Some(DragAction::Copy),
+        DROPEFFECT_MOVE => Some(DragAction::Move),
+        _ => None,
+    }
+}
+
+/// Creates a data object offering `payload` as `CF_HDROP` or `CF_UNICODETEXT`.
+unsafe fn create_data_object(payload: &DragPayload) -> Option<IDataObject> {
+    let sh_create_data_object = (*SH_CREATE_DATA_OBJECT)?;
+    let mut data_object = ptr::null_mut();
+    let result = unsafe {
+        sh_create_data_object(
+            ptr::null(),
+            0,
+            ptr::null(),
+            ptr::null_mut(),
+            &IID_IDataObject,
+            &mut data_object,
+        )
+    };
+    if result != S_OK {
+        return None;
+    }
+
+    let (format, hglobal) = match payload {
+        DragPayload::Paths(paths) => (CF_HDROP, unsafe { hdrop(paths) }),
+        DragPayload::Text(text) => {
+            let text: Vec<u16> = text.encode_utf16().chain(iter::once(0)).collect();
+            (CF_UNICODETEXT, unsafe { global_memory(&[], &text) })
+        },
+    };
+    let Some(hglobal) = hglobal else {
+        unsafe { release(data_object) };
+        return None;
+    };
+
+    let format = FORMATETC {
+        cfFormat: format,
+        ptd: ptr::null_mut(),
+        dwAspect: DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL as u32,
+    };
+    let medium = STGMEDIUM {
+        tymed: TYMED_HGLOBAL as u32,
+        u: STGMEDIUM_0 { hGlobal: hglobal },
+        pUnkForRelease: ptr::null_mut(),
+    };
+
+    // The data object owns the memory once it's set.
+    let data_obj = data_object as *mut IDataObject;
+    let set_data_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).SetData };
+    if unsafe { set_data_fn(data_obj, &format, &medium, true.into()) } != S_OK {
+        unsafe {
+            GlobalFree(hglobal);
+            release(data_object);
+        }
+        return None;
+    }
+
+    Some(data_object)
+}
+
+unsafe fn release(data_object: IDataObject) {
+    let data_obj = data_object as *mut IDataObject;
+    let release_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).parent.Release };
+    unsafe { release_fn(data_obj as *mut IUnknown) };
+}
+
+/// Allocates the `DROPFILES` structure listing `paths`.
+unsafe fn hdrop(paths: &[PathBuf]) -> Option<HGLOBAL> {
+    let header = DROPFILES {
+        pFiles: mem::size_of::<DROPFILES>() as u32,
+        pt: POINT { x: 0, y: 0 },
+        fNC: false.into(),
+        fWide: true.into(),
+    };
+    let header = unsafe {
+        slice::from_raw_parts(&header as *const DROPFILES as *const u8, mem::size_of::<DROPFILES>())
+    };
+
+    // The null-terminated paths are followed by an additional null character.
+    let files: Vec<u16> = paths
+        .iter()
+        .flat_map(|path| path.as_os_str().encode_wide().chain(iter::once(0)))
+        .chain(iter::once(0))
+        .collect();
+
+    unsafe { global_memory(header, &files) }
+}
+
+/// Copies `header` followed by `text` into a new global memory object.
+unsafe fn global_memory(header: &[u8], text: &[u16]) -> Option<HGLOBAL> {
+    let size = header.len() + mem::size_of_val(text);
+    let hglobal = unsafe { GlobalAlloc(GHND, size) };
+    if hglobal.is_null() {
+        return None;
+    }
+
+    unsafe {
+        let data = GlobalLock(hglobal) as *mut u8;
+        if data.is_null() {
+            GlobalFree(hglobal);
+            return None;
+        }
+
+        ptr::copy_nonoverlapping(header.as_ptr(), data, header.len());
+        ptr::copy_nonoverlapping(
+            text.as_ptr().cast::<u8>(),
+            data.add(header.len()),
+            mem::size_of_val(text),
+        );
+        GlobalUnlock(hglobal);
+    }
+
+    Some(hglobal)
+}
@@ -5,9 +5,13 @@ use std::ptr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use windows_sys::core::{IUnknown, GUID, HRESULT};
-use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HWND, POINTL, S_OK};
+use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HWND, POINT, POINTL, S_OK};
+use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
 use windows_sys::Win32::System::Com::{IDataObject, DVASPECT_CONTENT, FORMATETC, TYMED_HGLOBAL};
-use windows_sys::Win32::System::Ole::{CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_NONE};
+use windows_sys::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+use windows_sys::Win32::System::Ole::{
+    ReleaseStgMedium, CF_HDROP, CF_UNICODETEXT, DROPEFFECT_COPY, DROPEFFECT_NONE,
+};
 use windows_sys::Win32::UI::Shell::{DragFinish, DragQueryFileW, HDROP};
 
 use tracing::debug;
@@ -17,7 +21,8 @@ use crate::platform_impl::platform::definitions::{
 };
 use crate::platform_impl::platform::WindowId;
 
-use crate::event::Event;
+use crate::dpi::PhysicalPosition;
+use crate::event::{DragDropEvent, DragDropItem, DragDropKinds, Event, WindowEvent};
 use crate::window::WindowId as RootWindowId;
 
 #[repr(C)]
@@ -29,6 +34,8 @@ pub struct FileDropHandlerData {
     cursor_effect: u32,
     hovered_is_valid: bool, /* If the currently hovered item is not valid there must not be any
                              * `HoveredFileCancelled` emitted */
+    drag_kinds: DragDropKinds, // Empty when the hovered item can't be dropped.
+    position: PhysicalPosition<f64>,
 }
 
 pub struct FileDropHandler {
@@ -45,6 +52,8 @@ impl FileDropHandler {
             send_event,
             cursor_effect: DROPEFFECT_NONE,
             hovered_is_valid: false,
+            drag_kinds: DragDropKinds::empty(),
+            position: PhysicalPosition::default(),
         });
         FileDropHandler { data: Box::into_raw(data) }
     }
@@ -80,13 +89,15 @@ impl FileDropHandler {
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
+        #[allow(deprecated)]
         use crate::event::WindowEvent::HoveredFile;
         let drop_handler = unsafe { Self::from_interface(this) };
         let hdrop = unsafe {
             Self::iterate_filenames(pDataObj, |filename| {
+                #[allow(deprecated)]
                 drop_handler.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(drop_handler.window)),
                     event: HoveredFile(filename),
@@ -94,8 +105,23 @@ impl FileDropHandler {
             })
         };
         drop_handler.hovered_is_valid = hdrop.is_some();
+        drop_handler.drag_kinds = if drop_handler.hovered_is_valid {
+            DragDropKinds::PATHS
+        } else if unsafe { Self::has_text(pDataObj) } {
+            DragDropKinds::TEXT
+        } else {
+            DragDropKinds::empty()
+        };
         drop_handler.cursor_effect =
-            if drop_handler.hovered_is_valid { DROPEFFECT_COPY } else { DROPEFFECT_NONE };
+            if drop_handler.drag_kinds.is_empty() { DROPEFFECT_NONE } else { DROPEFFECT_COPY };
+
+        if !drop_handler.drag_kinds.is_empty() {
+            drop_handler.position = unsafe { drop_handler.client_position(pt) };
+            drop_handler.send_drag_drop_event(DragDropEvent::Entered {
+                kinds: drop_handler.drag_kinds,
+                position: drop_handler.position,
+            });
+        }
         unsafe {
             *pdwEffect = drop_handler.cursor_effect;
         }
@@ -106,10 +132,13 @@ impl FileDropHandler {
     pub unsafe extern "system" fn DragOver(
         this: *mut IDropTarget,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         let drop_handler = unsafe { Self::from_interface(this) };
+        if !drop_handler.drag_kinds.is_empty() {
+            unsafe { drop_handler.update_position(pt) };
+        }
         unsafe {
             *pdwEffect = drop_handler.cursor_effect;
         }
@@ -118,14 +147,20 @@ impl FileDropHandler {
     }
 
     pub unsafe extern "system" fn DragLeave(this: *mut IDropTarget) -> HRESULT {
+        #[allow(deprecated)]
         use crate::event::WindowEvent::HoveredFileCancelled;
         let drop_handler = unsafe { Self::from_interface(this) };
         if drop_handler.hovered_is_valid {
+            #[allow(deprecated)]
             drop_handler.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(drop_handler.window)),
                 event: HoveredFileCancelled,
             });
         }
+        if !drop_handler.drag_kinds.is_empty() {
+            drop_handler.drag_kinds = DragDropKinds::empty();
+            drop_handler.send_drag_drop_event(DragDropEvent::Left);
+        }
 
         S_OK
     }
@@ -134,23 +169,36 @@ impl FileDropHandler {
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         _pdwEffect: *mut u32,
     ) -> HRESULT {
+        #[allow(deprecated)]
         use crate::event::WindowEvent::DroppedFile;
         let drop_handler = unsafe { Self::from_interface(this) };
+        let mut items = Vec::new();
         let hdrop = unsafe {
             Self::iterate_filenames(pDataObj, |filename| {
+                #[allow(deprecated)]
                 drop_handler.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(drop_handler.window)),
-                    event: DroppedFile(filename),
+                    event: DroppedFile(filename.clone()),
                 });
+                items.push(DragDropItem::Path(filename));
             })
         };
         if let Some(hdrop) = hdrop {
             unsafe { DragFinish(hdrop) };
         }
 
+        if !drop_handler.drag_kinds.is_empty() {
+            unsafe { drop_handler.update_position(pt) };
+            if drop_handler.drag_kinds == DragDropKinds::TEXT {
+                items.extend(unsafe { Self::get_text(pDataObj) }.map(DragDropItem::Text));
+            }
+            drop_handler.drag_kinds = DragDropKinds::empty();
+            drop_handler.send_drag_drop_event(DragDropEvent::Dropped { items });
+        }
+
         S_OK
     }
 
@@ -158,9 +206,9 @@ impl FileDropHandler {
         unsafe { &mut *(this as *mut _) }
     }
 
-    unsafe fn iterate_filenames<F>(data_obj: *const IDataObject, callback: F) -> Option<HDROP>
+    unsafe fn iterate_filenames<F>(data_obj: *const IDataObject, mut callback: F) -> Option<HDROP>
     where
-        F: Fn(PathBuf),
+        F: FnMut(PathBuf),
     {
         let drop_format = FORMATETC {
             cfFormat: CF_HDROP,
@@ -208,12 +256,76 @@ impl FileDropHandler {
             None
         }
     }
+
+    unsafe fn has_text(data_obj: *const IDataObject) -> bool {
+        let text_format = TEXT_FORMAT;
+        let query_get_data_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).QueryGetData };
+        unsafe { query_get_data_fn(data_obj as *mut _, &text_format) == S_OK }
+    }
+
+    unsafe fn get_text(data_obj: *const IDataObject) -> Option<String> {
+        let text_format = TEXT_FORMAT;
+        let mut medium = unsafe { std::mem::zeroed() };
+        let get_data_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).GetData };
+        if unsafe { get_data_fn(data_obj as *mut _, &text_format, &mut medium) } < 0 {
+            debug!("Unexpected error occurred while processing dropped text.");
+            return None;
+        }
+
+        let text = unsafe {
+            let hglobal = medium.u.hGlobal;
+            let data = GlobalLock(hglobal) as *const u16;
+            let text = (!data.is_null()).then(|| {
+                let len = GlobalSize(hglobal) / std::mem::size_of::<u16>();
+                let wide = std::slice::from_raw_parts(data, len);
+                // The text is null-terminated, but the allocation may be larger.
+                let len = wide.iter().position(|&c| c == 0).unwrap_or(len);
+                String::from_utf16_lossy(&wide[..len])
+            });
+            GlobalUnlock(hglobal);
+            text
+        };
+        unsafe { ReleaseStgMedium(&mut medium) };
+
+        text
+    }
 }
 
+const TEXT_FORMAT: FORMATETC = FORMATETC {
+    cfFormat: CF_UNICODETEXT,
+    ptd: ptr::null_mut(),
+    dwAspect: DVASPECT_CONTENT,
+    lindex: -1,
+    tymed: TYMED_HGLOBAL as u32,
+};
+
 impl FileDropHandlerData {
     fn send_event(&self, event: Event) {
         (self.send_event)(event);
     }
+
+    fn send_drag_drop_event(&self, event: DragDropEvent) {
+        self.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(self.window)),
+            event: WindowEvent::DragDrop(event),
+        });
+    }
+
+    /// Convert the screen coordinates of the cursor to the window's client area.
+    unsafe fn client_position(&self, pt: *const POINTL) -> PhysicalPosition<f64> {
+        let pt = unsafe { *pt };
+        let mut point = POINT { x: pt.x, y: pt.y };
+        unsafe { ScreenToClient(self.window, &mut point) };
+        PhysicalPosition::new(point.x as f64, point.y as f64)
+    }
+
+    unsafe fn update_position(&mut self, pt: *const POINTL) {
+        let position = unsafe { self.client_position(pt) };
+        if position != self.position {
+            self.position = position;
+            self.send_drag_drop_event(DragDropEvent::Moved { position });
+        }
+    }
 }
 
 impl Drop for FileDropHandler {
@@ -2,7 +2,7 @@
 
 mod runner;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::c_void;
 use std::marker::PhantomData;
@@ -12,20 +12,29 @@ use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 use std::{mem, panic, ptr};
 
-use crate::utils::Lazy;
+use crate::utils::{ClickThresholds, Lazy};
 
 use windows_sys::Win32::Devices::HumanInterfaceDevice::MOUSE_MOVE_RELATIVE;
 use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
 use windows_sys::Win32::Graphics::Gdi::{
     GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow, ScreenToClient,
-    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, SC_SCREENSAVE,
+    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT,
+    SC_SCREENSAVE,
 };
 use windows_sys::Win32::System::Ole::RevokeDragDrop;
+use windows_sys::Win32::System::Power::{
+    RegisterPowerSettingNotification, UnregisterPowerSettingNotification, HPOWERNOTIFY,
+    POWERBROADCAST_SETTING,
+};
+use windows_sys::Win32::System::RemoteDesktop::{
+    WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+};
+use windows_sys::Win32::System::SystemServices::GUID_POWER_SAVING_STATUS;
 use windows_sys::Win32::System::Threading::{GetCurrentThreadId, INFINITE};
 use windows_sys::Win32::UI::Controls::{HOVER_DEFAULT, WM_MOUSELEAVE};
 use windows_sys::Win32::UI::Input::Ime::{GCS_COMPSTR, GCS_RESULTSTR, ISC_SHOWUICOMPOSITIONWINDOW};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
+    GetDoubleClickTime, ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
 };
 use windows_sys::Win32::UI::Input::Pointer::{
     POINTER_FLAG_DOWN, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
@@ -35,35 +44,44 @@ use windows_sys::Win32::UI::Input::Touch::{
     TOUCHINPUT,
 };
 use windows_sys::Win32::UI::Input::{RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE};
+use windows_sys::Win32::UI::TextServices::HKL;
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetCursorPos,
-    GetMenu, GetMessageW, KillTimer, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    RegisterWindowMessageA, SetCursor, SetTimer, SetWindowPos, TranslateMessage, CREATESTRUCTW,
-    GIDC_ARRIVAL, GIDC_REMOVAL, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO,
-    MNC_CLOSE, MSG, NCCALCSIZE_PARAMS, PM_REMOVE, PT_PEN, PT_TOUCH, RI_MOUSE_HWHEEL,
-    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
-    SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT,
-    WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
-    WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN,
-    WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
-    WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE,
-    WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP,
-    WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE,
-    WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TOUCH, WM_WINDOWPOSCHANGED,
-    WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED,
+    GetMenu, GetMessageW, GetSystemMetrics, KillTimer, PeekMessageW, PostMessageW,
+    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetTimer, SetWindowPos, TranslateMessage,
+    CREATESTRUCTW, DEVICE_NOTIFY_WINDOW_HANDLE, GIDC_ARRIVAL, GIDC_REMOVAL, GWL_STYLE,
+    GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG, NCCALCSIZE_PARAMS,
+    PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND, PBT_POWERSETTINGCHANGE, PM_REMOVE, PT_PEN, PT_TOUCH,
+    RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SM_CXDOUBLECLK,
+    SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM,
+    WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT,
+    WMSZ_TOPRIGHT, WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DISPLAYCHANGE,
+    WM_DPICHANGED, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION,
+    WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT,
+    WM_INPUTLANGCHANGE, WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE,
+    WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN,
+    WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_POWERBROADCAST, WM_RBUTTONDOWN,
+    WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND,
+    WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TIMER, WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING,
+    WM_WTSSESSION_CHANGE, WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED,
     WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
+    WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
 };
 
 use crate::application::ApplicationHandler;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::EventLoopError;
 use crate::event::{
-    DeviceEvent, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, Touch, TouchPhase, WindowEvent,
+    DeviceEvent, ElementState, Event, Force, Ime, InnerSizeWriter, Modifiers, MouseButton,
+    RawKeyEvent, SessionEvent, Touch, TouchPhase, TouchTool, WindowEvent,
 };
-use crate::event_loop::{ActiveEventLoop as RootAEL, ControlFlow, DeviceEvents};
-use crate::keyboard::ModifiersState;
+use crate::event_loop::{
+    ActiveEventLoop as RootAEL, ControlFlow, DeviceEventKinds, DeviceEventSubscription,
+    MotionEventPolicy,
+};
+use crate::keyboard::{Key, KeyCode};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::dpi::{become_dpi_aware, dpi_to_scale_factor};
@@ -71,21 +89,23 @@ use crate::platform_impl::platform::drop_handler::FileDropHandler;
 use crate::platform_impl::platform::icon::WinCursor;
 use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
-use crate::platform_impl::platform::keyboard_layout::LAYOUT_CACHE;
+use crate::platform_impl::platform::keyboard_layout::{self, LAYOUT_CACHE};
 use crate::platform_impl::platform::monitor::{self, MonitorHandle};
+use crate::platform_impl::platform::wait_timer::{WaitTimer, PRECISE_WAIT_THRESHOLD};
 use crate::platform_impl::platform::window::InitData;
 use crate::platform_impl::platform::window_state::{
     CursorFlags, ImeState, WindowFlags, WindowState,
 };
 use crate::platform_impl::platform::{
-    raw_input, util, wrap_device_id, Fullscreen, WindowId, DEVICE_ID,
+    drag_source, presentation, raw_input, util, wrap_device_id, Fullscreen, WindowId, DEVICE_ID,
 };
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, WindowId as RootWindowId,
+    AspectRatio, AspectRatioAxis, CustomCursor as RootCustomCursor, CustomCursorSource,
+    DragPayload, ProgressState, WindowId as RootWindowId,
 };
 use runner::EventLoopRunner;
 
-use super::window::set_skip_taskbar;
+use super::window::{set_skip_taskbar, set_taskbar_badge, set_taskbar_progress};
 use super::SelectedCursor;
 
 pub(crate) struct WindowData {
@@ -109,6 +129,12 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: Rc<EventLoopRunner>,
+    /// The monitors known before the last `WM_DISPLAYCHANGE`.
+    monitors: RefCell<VecDeque<MonitorHandle>>,
+    /// The registration for the `GUID_POWER_SAVING_STATUS` notifications.
+    power_saver_notification: HPOWERNOTIFY,
+    /// The battery saver state reported by the last `PBT_POWERSETTINGCHANGE`.
+    power_saver: Cell<Option<bool>>,
 }
 
 impl ThreadMsgTargetData {
@@ -127,6 +153,7 @@ pub(crate) enum ProcResult {
 pub struct EventLoop {
     window_target: RootAEL,
     msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
+    wait_timer: WaitTimer,
 }
 
 pub(crate) struct PlatformSpecificEventLoopAttributes {
@@ -179,9 +206,11 @@ impl EventLoop {
         Ok(EventLoop {
             window_target: RootAEL {
                 p: ActiveEventLoop { thread_id, thread_msg_target, runner_shared },
+                user_event_queue: Default::default(),
                 _marker: PhantomData,
             },
             msg_hook: attributes.msg_hook.take(),
+            wait_timer: WaitTimer::new(),
         })
     }
 
@@ -217,9 +246,31 @@ impl EventLoop {
                     Event::Suspended => app.suspended(event_loop_windows_ref),
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
+                    Event::DestroySurfaces => app.destroy_surfaces(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::PowerStateChanged => app.power_state_changed(event_loop_windows_ref),
+                    Event::AccessibilitySettingsChanged => {
+                        app.accessibility_settings_changed(event_loop_windows_ref)
+                    },
+                    Event::KeyboardLayoutChanged => {
+                        app.keyboard_layout_changed(event_loop_windows_ref)
+                    },
+                    Event::SessionEvent(event) => app.session_event(event_loop_windows_ref, event),
+                    Event::MonitorConnected(monitor) => {
+                        app.monitor_connected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorDisconnected(monitor) => {
+                        app.monitor_disconnected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorVideoModeChanged(monitor) => {
+                        app.monitor_video_mode_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::UrlsOpened(urls) => app.urls_opened(event_loop_windows_ref, urls),
+                    Event::ShortcutItemActivated(item) => {
+                        app.shortcut_item_activated(event_loop_windows_ref, item)
+                    },
                 });
             }
         }
@@ -283,9 +334,31 @@ impl EventLoop {
                     Event::Suspended => app.suspended(event_loop_windows_ref),
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
+                    Event::DestroySurfaces => app.destroy_surfaces(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::PowerStateChanged => app.power_state_changed(event_loop_windows_ref),
+                    Event::AccessibilitySettingsChanged => {
+                        app.accessibility_settings_changed(event_loop_windows_ref)
+                    },
+                    Event::KeyboardLayoutChanged => {
+                        app.keyboard_layout_changed(event_loop_windows_ref)
+                    },
+                    Event::SessionEvent(event) => app.session_event(event_loop_windows_ref, event),
+                    Event::MonitorConnected(monitor) => {
+                        app.monitor_connected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorDisconnected(monitor) => {
+                        app.monitor_disconnected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorVideoModeChanged(monitor) => {
+                        app.monitor_video_mode_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::UrlsOpened(urls) => app.urls_opened(event_loop_windows_ref, urls),
+                    Event::ShortcutItemActivated(item) => {
+                        app.shortcut_item_activated(event_loop_windows_ref, item)
+                    },
                 });
 
                 runner.wakeup();
@@ -346,17 +419,25 @@ impl EventLoop {
         /// requested timeout is `ZERO` (and so we don't want to block)
         ///
         /// Returns `None` if no MSG was read, else a `Continue` or `Exit` status
-        fn wait_for_msg(msg: &mut MSG, timeout: Option<Duration>) -> Option<PumpStatus> {
-            if timeout == Some(Duration::ZERO) {
-                unsafe {
+        ///
+        /// Deadlines in the near future are waited for with `wait_timer`, for precision.
+        fn wait_for_msg(
+            msg: &mut MSG,
+            timeout: Option<Duration>,
+            wait_timer: &WaitTimer,
+        ) -> Option<PumpStatus> {
+            match timeout {
+                Some(Duration::ZERO) => unsafe {
                     if PeekMessageW(msg, 0, 0, 0, PM_REMOVE) != 0 {
                         Some(PumpStatus::Continue)
                     } else {
                         None
                     }
-                }
-            } else {
-                Some(get_msg_with_timeout(msg, timeout))
+                },
+                Some(timeout) if timeout < PRECISE_WAIT_THRESHOLD => {
+                    wait_timer.wait_for_msg(msg, timeout)
+                },
+                _ => Some(get_msg_with_timeout(msg, timeout)),
             }
         }
 
@@ -389,7 +470,7 @@ impl EventLoop {
         // API) and there's no API to construct or initialize a `MSG`. This
         // is the simplest way avoid uninitialized memory in Rust
         let mut msg = unsafe { mem::zeroed() };
-        let msg_status = wait_for_msg(&mut msg, timeout);
+        let msg_status = wait_for_msg(&mut msg, timeout, &self.wait_timer);
 
         // Before we potentially exit, make sure to consistently emit an event for the wake up
         runner.wakeup();
@@ -486,7 +567,7 @@ impl ActiveEventLoop {
     }
 
     pub fn create_custom_cursor(&self, source: CustomCursorSource) -> RootCustomCursor {
-        let inner = match WinCursor::new(&source.inner.0) {
+        let inner = match WinCursor::new(&source.inner) {
             Ok(cursor) => cursor,
             Err(err) => {
                 tracing::warn!("Failed to create custom cursor: {err}");
@@ -519,8 +600,27 @@ impl ActiveEventLoop {
         Ok(rwh_06::RawDisplayHandle::Windows(rwh_06::WindowsDisplayHandle::new()))
     }
 
-    pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        LAYOUT_CACHE.lock().unwrap().get_current_layout().1.key_from_key_code(code)
+    }
+
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        LAYOUT_CACHE.lock().unwrap().get_current_layout().1.key_code_from_key(key)
+    }
+
+    pub fn listen_device_events(&self, subscription: DeviceEventSubscription) {
+        self.runner_shared.set_device_events(subscription);
+        raw_input::register_all_mice_and_keyboards_for_raw_input(
+            self.thread_msg_target,
+            subscription,
+        );
+    }
+
+    pub fn set_motion_event_policy(&self, _policy: MotionEventPolicy) {}
+
+    pub fn motion_event_policy(&self) -> MotionEventPolicy {
+        // `WM_MOUSEMOVE` is always coalesced by the system.
+        MotionEventPolicy::Coalesced
     }
 
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -620,7 +720,7 @@ fn min_timeout(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
 }
 
 // Implementation taken from https://github.com/rust-lang/rust/blob/db5476571d9b27c862b95c1e64764b0ac8980e23/src/libstd/sys/windows/mod.rs
-fn dur2timeout(dur: Duration) -> u32 {
+pub(super) fn dur2timeout(dur: Duration) -> u32 {
     // Note that a duration is a (u64, u32) (seconds, nanoseconds) pair, and the
     // timeouts in windows APIs are typically u32 milliseconds. To translate, we
     // have two pieces to take care of:
@@ -778,11 +878,25 @@ pub(crate) static DESTROY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::Des
 // documentation in the `window_state` module for more information.
 pub(crate) static SET_RETAIN_STATE_ON_SIZE_MSG_ID: LazyMessageId =
     LazyMessageId::new("Winit::SetRetainMaximized\0");
+// Message sent by a `Window` to start dragging out of it once the current event was handled.
+// WPARAM contains a Box<DragPayload> that must be retrieved with `Box::from_raw`,
+// and LPARAM is unused.
+pub(crate) static START_DRAG_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::StartDragMsg\0");
+// Message sent by a `Window` after `pre_present_notify`, to emit `FramePresented` once the frame
+// was presented.
+// WPARAM contains the generation of the frame, and LPARAM is unused.
+pub(crate) static PRESENT_FEEDBACK_MSG_ID: LazyMessageId =
+    LazyMessageId::new("Winit::PresentFeedbackMsg\0");
 static THREAD_EVENT_TARGET_WINDOW_CLASS: Lazy<Vec<u16>> =
     Lazy::new(|| util::encode_wide("Winit Thread Event Target"));
+/// The ID of the timer of a window that shows the next frame of an animated cursor.
+pub(crate) const CURSOR_ANIMATION_TIMER_ID: usize = 1;
 /// When the taskbar is created, it registers a message with the "TaskbarCreated" string and then
 /// broadcasts this message to all top-level windows <https://docs.microsoft.com/en-us/windows/win32/shell/taskbar#taskbar-creation-notification>
 pub(crate) static TASKBAR_CREATED: LazyMessageId = LazyMessageId::new("TaskbarCreated\0");
+/// Sent to a window once its taskbar button was created, which is required before its progress
+/// can be set.
+static TASKBAR_BUTTON_CREATED: LazyMessageId = LazyMessageId::new("TaskbarButtonCreated\0");
 
 fn create_event_target_window() -> HWND {
     use windows_sys::Win32::UI::WindowsAndMessaging::{CS_HREDRAW, CS_VREDRAW};
@@ -844,7 +958,22 @@ fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: Rc<EventLoopRunner>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    // Report the session lock and the battery saver, the suspension is always reported.
+    let power_saver_notification = unsafe {
+        WTSRegisterSessionNotification(thread_msg_target, NOTIFY_FOR_THIS_SESSION);
+        RegisterPowerSettingNotification(
+            thread_msg_target,
+            &GUID_POWER_SAVING_STATUS,
+            DEVICE_NOTIFY_WINDOW_HANDLE,
+        )
+    };
+
+    let userdata = ThreadMsgTargetData {
+        event_loop_runner,
+        monitors: RefCell::new(monitor::available_monitors()),
+        power_saver_notification,
+        power_saver: Cell::new(None),
+    };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { super::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -875,26 +1004,100 @@ fn normalize_pointer_pressure(pressure: u32) -> Option<Force> {
     }
 }
 
+/// The double-click settings of the system.
+fn click_thresholds() -> ClickThresholds {
+    ClickThresholds {
+        interval: Duration::from_millis(unsafe { GetDoubleClickTime() } as u64),
+        // The double-click rectangle is centered on the first click.
+        distance: f64::from(unsafe { GetSystemMetrics(SM_CXDOUBLECLK) }) / 2.0,
+    }
+}
+
+/// Counts the clicks of a mouse button message, whose `lparam` has the position of the cursor.
+fn click_count(
+    userdata: &WindowData,
+    button: MouseButton,
+    state: ElementState,
+    lparam: LPARAM,
+) -> u32 {
+    let mut w = userdata.window_state_lock();
+    match state {
+        ElementState::Pressed => {
+            let x = super::get_x_lparam(lparam as u32) as f64;
+            let y = super::get_y_lparam(lparam as u32) as f64;
+            let position = PhysicalPosition::new(x, y);
+            w.mouse.click_counter.press(button, position, Instant::now(), click_thresholds())
+        },
+        ElementState::Released => w.mouse.click_counter.release(button),
+    }
+}
+
 /// Emit a `ModifiersChanged` event whenever modifiers have changed.
 /// Returns the current modifier state
 fn update_modifiers(window: HWND, userdata: &WindowData) {
     use crate::event::WindowEvent::ModifiersChanged;
 
-    let modifiers = {
+    let mut modifiers: Modifiers = {
         let mut layouts = LAYOUT_CACHE.lock().unwrap();
-        layouts.get_agnostic_mods()
+        layouts.get_agnostic_mods().into()
     };
+    modifiers.lock_keys = keyboard_layout::get_lock_keys();
 
     let mut window_state = userdata.window_state.lock().unwrap();
-    if window_state.modifiers_state != modifiers {
-        window_state.modifiers_state = modifiers;
+    if window_state.modifiers != modifiers {
+        window_state.modifiers = modifiers;
 
         // Drop lock
         drop(window_state);
 
         userdata.send_event(Event::WindowEvent {
             window_id: RootWindowId(WindowId(window)),
-            event: ModifiersChanged(modifiers.into()),
+            event: ModifiersChanged(modifiers),
+        });
+    }
+}
+
+/// Emit a `MonitorChanged` event whenever the monitor the window is on has changed.
+fn update_monitor(window: HWND, userdata: &WindowData) {
+    use crate::event::WindowEvent::MonitorChanged;
+
+    let new_monitor = unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST) };
+    if new_monitor == 0 {
+        return;
+    }
+
+    let mut window_state = userdata.window_state_lock();
+    let old_monitor = mem::replace(&mut window_state.current_monitor, new_monitor);
+    if old_monitor != new_monitor {
+        // `MonitorChanged` already covers the new color properties.
+        window_state.color_properties = MonitorHandle::new(new_monitor).color_properties();
+        drop(window_state);
+
+        userdata.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(window)),
+            event: MonitorChanged {
+                old: (old_monitor != 0)
+                    .then(|| RootMonitorHandle { inner: MonitorHandle::new(old_monitor) }),
+                new: RootMonitorHandle { inner: MonitorHandle::new(new_monitor) },
+            },
+        });
+    }
+}
+
+/// Emit a `ColorPropertiesChanged` event whenever the color properties of the monitor the
+/// window is on have changed.
+fn update_color_properties(window: HWND, userdata: &WindowData) {
+    use crate::event::WindowEvent::ColorPropertiesChanged;
+
+    let color_properties = monitor::current_monitor(window).color_properties();
+    let mut window_state = userdata.window_state_lock();
+    if window_state.color_properties != color_properties {
+        window_state.color_properties = color_properties;
+        drop(window_state);
+
+        userdata.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(window)),
+            event: ColorPropertiesChanged(color_properties),
         });
     }
 }
@@ -913,10 +1116,10 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
     use crate::event::WindowEvent::{Focused, ModifiersChanged};
 
-    userdata.window_state_lock().modifiers_state = ModifiersState::empty();
+    userdata.window_state_lock().modifiers = Modifiers::default();
     userdata.send_event(Event::WindowEvent {
         window_id: RootWindowId(WindowId(window)),
-        event: ModifiersChanged(ModifiersState::empty().into()),
+        event: ModifiersChanged(Modifiers::default()),
     });
 
     userdata.send_event(Event::WindowEvent {
@@ -1133,6 +1336,7 @@ unsafe fn public_window_callback_inner(
             // window outside the normal flow of the event loop. This way mark event as handled
             // and request a normal redraw with `RedrawWindow`.
             if !userdata.event_loop_runner.should_buffer() {
+                userdata.window_state_lock().redraw_damage.deliver();
                 userdata.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(window)),
                     event: WindowEvent::RedrawRequested,
@@ -1240,6 +1444,8 @@ unsafe fn public_window_callback_inner(
                     window_id: RootWindowId(WindowId(window)),
                     event: Moved(physical_position),
                 });
+
+                update_monitor(window, userdata);
             }
 
             // This is necessary for us to still get sent WM_SIZE.
@@ -1271,23 +1477,22 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_SIZING => {
-            /// Calculate the amount to add to round `value` to the nearest multiple of `increment`.
-            fn snap_to_nearest_increment_delta(value: i32, increment: i32) -> i32 {
-                let half_one = increment / 2;
-                let half_two = increment - half_one;
-                half_one - (value - half_two) % increment
-            }
-
-            let scale_factor = userdata.window_state_lock().scale_factor;
-            let Some(inc) = userdata
-                .window_state_lock()
+            let window_state = userdata.window_state_lock();
+            let scale_factor = window_state.scale_factor;
+            let increments = window_state
                 .resize_increments
-                .map(|inc| inc.to_physical(scale_factor))
-                .filter(|inc| inc.width > 0 && inc.height > 0)
-            else {
+                .map(|inc| inc.to_physical::<i32>(scale_factor))
+                .filter(|inc| inc.width > 0 && inc.height > 0);
+            let aspect_ratio = window_state.aspect_ratio;
+            let min_size = window_state.min_size.map(|size| size.to_physical(scale_factor));
+            let max_size = window_state.max_size.map(|size| size.to_physical(scale_factor));
+            let window_flags = window_state.window_flags;
+            drop(window_state);
+
+            if increments.is_none() && aspect_ratio.is_none() {
                 result = ProcResult::Value(0);
                 return;
-            };
+            }
 
             let side = wparam as u32;
             // The desired new size of the window, decorations included.
@@ -1295,65 +1500,21 @@ unsafe fn public_window_callback_inner(
 
             // We need to calculate the dimensions of the window decorations to get the true
             // size of the window's contents
-            let adj_rect = userdata
-                .window_state_lock()
-                .window_flags
-                .adjust_rect(window, *rect)
-                .unwrap_or(*rect);
-            let deco_width = rect.left - adj_rect.left + adj_rect.right - rect.right;
-            let deco_height = rect.top - adj_rect.top + adj_rect.bottom - rect.bottom;
-
-            let width = rect.right - rect.left - deco_width;
-            let height = rect.bottom - rect.top - deco_height;
-
-            let mut width_delta = snap_to_nearest_increment_delta(width, inc.width);
-            let mut height_delta = snap_to_nearest_increment_delta(height, inc.height);
-
-            // Windows won't bound check the value of `rect` after we're done here, so we
-            // have to check manually. If the width/height we snap to would go out of bounds, just
-            // set it equal to the min/max bound.
-            let min_size =
-                userdata.window_state_lock().min_size.map(|size| size.to_physical(scale_factor));
-            let max_size =
-                userdata.window_state_lock().max_size.map(|size| size.to_physical(scale_factor));
-            let final_width = width + width_delta;
-            let final_height = height + height_delta;
-            if let Some(min_size) = min_size {
-                if final_width < min_size.width {
-                    width_delta += min_size.width - final_width;
-                }
-                if final_height < min_size.height {
-                    height_delta += min_size.height - final_height;
-                }
-            }
-            if let Some(max_size) = max_size {
-                if final_width > max_size.width {
-                    width_delta -= final_width - max_size.width;
-                }
-                if final_height > max_size.height {
-                    height_delta -= final_height - max_size.height;
-                }
-            }
-
-            match side {
-                WMSZ_LEFT | WMSZ_BOTTOMLEFT | WMSZ_TOPLEFT => {
-                    rect.left -= width_delta;
-                },
-                WMSZ_RIGHT | WMSZ_BOTTOMRIGHT | WMSZ_TOPRIGHT => {
-                    rect.right += width_delta;
-                },
-                _ => {},
-            }
+            let adj_rect = window_flags.adjust_rect(window, *rect).unwrap_or(*rect);
+            let decorations = PhysicalSize::new(
+                rect.left - adj_rect.left + adj_rect.right - rect.right,
+                rect.top - adj_rect.top + adj_rect.bottom - rect.bottom,
+            );
 
-            match side {
-                WMSZ_TOP | WMSZ_TOPLEFT | WMSZ_TOPRIGHT => {
-                    rect.top -= height_delta;
-                },
-                WMSZ_BOTTOM | WMSZ_BOTTOMLEFT | WMSZ_BOTTOMRIGHT => {
-                    rect.bottom += height_delta;
-                },
-                _ => {},
-            }
+            constrain_sizing_rect(
+                rect,
+                side,
+                decorations,
+                increments,
+                aspect_ratio,
+                min_size,
+                max_size,
+            );
 
             result = ProcResult::DefWindowProc(wparam);
         },
@@ -1510,6 +1671,7 @@ unsafe fn public_window_callback_inner(
                         w.mouse
                             .set_cursor_flags(window, |f| f.set(CursorFlags::IN_WINDOW, true))
                             .ok();
+                        w.mouse.update_cursor_animation(window);
 
                         drop(w);
                         userdata.send_event(Event::WindowEvent {
@@ -1531,6 +1693,7 @@ unsafe fn public_window_callback_inner(
                         w.mouse
                             .set_cursor_flags(window, |f| f.set(CursorFlags::IN_WINDOW, false))
                             .ok();
+                        w.mouse.update_cursor_animation(window);
 
                         drop(w);
                         userdata.send_event(Event::WindowEvent {
@@ -1547,6 +1710,9 @@ unsafe fn public_window_callback_inner(
                 let mut w = userdata.window_state_lock();
                 cursor_moved = w.mouse.last_position != Some(position);
                 w.mouse.last_position = Some(position);
+                if cursor_moved {
+                    w.mouse.click_counter.moved(position, click_thresholds());
+                }
             }
 
             if cursor_moved {
@@ -1566,6 +1732,7 @@ unsafe fn public_window_callback_inner(
             {
                 let mut w = userdata.window_state_lock();
                 w.mouse.set_cursor_flags(window, |f| f.set(CursorFlags::IN_WINDOW, false)).ok();
+                w.mouse.update_cursor_animation(window);
             }
 
             userdata.send_event(Event::WindowEvent {
@@ -1590,6 +1757,7 @@ unsafe fn public_window_callback_inner(
                     device_id: DEVICE_ID,
                     delta: LineDelta(0.0, value),
                     phase: TouchPhase::Moved,
+                    momentum_phase: None,
                 },
             });
 
@@ -1610,6 +1778,7 @@ unsafe fn public_window_callback_inner(
                     device_id: DEVICE_ID,
                     delta: LineDelta(value, 0.0),
                     phase: TouchPhase::Moved,
+                    momentum_phase: None,
                 },
             });
 
@@ -1637,11 +1806,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { capture_mouse(window, &mut userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Left, Pressed, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button: Left },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Pressed,
+                    button: Left,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1653,11 +1829,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { release_mouse(userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Left, Released, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Released, button: Left },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Released,
+                    button: Left,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1669,11 +1852,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { capture_mouse(window, &mut userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Right, Pressed, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button: Right },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Pressed,
+                    button: Right,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1685,11 +1875,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { release_mouse(userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Right, Released, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Released, button: Right },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Released,
+                    button: Right,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1701,11 +1898,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { capture_mouse(window, &mut userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Middle, Pressed, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button: Middle },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Pressed,
+                    button: Middle,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1717,11 +1921,18 @@ unsafe fn public_window_callback_inner(
 
             unsafe { release_mouse(userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, Middle, Released, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Released, button: Middle },
+                event: MouseInput {
+                    device_id: DEVICE_ID,
+                    state: Released,
+                    button: Middle,
+                    click_count,
+                },
             });
             result = ProcResult::Value(0);
         },
@@ -1731,22 +1942,21 @@ unsafe fn public_window_callback_inner(
             use crate::event::MouseButton::{Back, Forward, Other};
             use crate::event::WindowEvent::MouseInput;
             let xbutton = super::get_xbutton_wparam(wparam as u32);
+            let button = match xbutton {
+                1 => Back,
+                2 => Forward,
+                _ => Other(xbutton),
+            };
 
             unsafe { capture_mouse(window, &mut userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, button, Pressed, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput {
-                    device_id: DEVICE_ID,
-                    state: Pressed,
-                    button: match xbutton {
-                        1 => Back,
-                        2 => Forward,
-                        _ => Other(xbutton),
-                    },
-                },
+                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button, click_count },
             });
             result = ProcResult::Value(0);
         },
@@ -1756,22 +1966,21 @@ unsafe fn public_window_callback_inner(
             use crate::event::MouseButton::{Back, Forward, Other};
             use crate::event::WindowEvent::MouseInput;
             let xbutton = super::get_xbutton_wparam(wparam as u32);
+            let button = match xbutton {
+                1 => Back,
+                2 => Forward,
+                _ => Other(xbutton),
+            };
 
             unsafe { release_mouse(userdata.window_state_lock()) };
 
+            let click_count = click_count(userdata, button, Released, lparam);
+
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
-                event: MouseInput {
-                    device_id: DEVICE_ID,
-                    state: Released,
-                    button: match xbutton {
-                        1 => Back,
-                        2 => Forward,
-                        _ => Other(xbutton),
-                    },
-                },
+                event: MouseInput { device_id: DEVICE_ID, state: Released, button, click_count },
             });
             result = ProcResult::Value(0);
         },
@@ -1824,6 +2033,12 @@ unsafe fn public_window_callback_inner(
                             },
                             location,
                             force: None, // WM_TOUCH doesn't support pressure information
+                            tool: TouchTool::Finger,
+                            azimuth_angle: None,
+                            altitude_angle: None,
+                            twist_angle: None,
+                            is_estimated: false,
+                            is_predicted: false,
                             id: input.dwID as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -1970,6 +2185,16 @@ unsafe fn public_window_callback_inner(
                             },
                             location,
                             force,
+                            tool: if pointer_info.pointerType == PT_PEN {
+                                TouchTool::Stylus
+                            } else {
+                                TouchTool::Finger
+                            },
+                            azimuth_angle: None,
+                            altitude_angle: None,
+                            twist_angle: None,
+                            is_estimated: false,
+                            is_predicted: false,
                             id: pointer_info.pointerId as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -2002,6 +2227,16 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_TIMER if wparam == CURSOR_ANIMATION_TIMER_ID => {
+            let mut w = userdata.window_state_lock();
+            if let SelectedCursor::Animation(animation, frame) = &mut w.mouse.selected_cursor {
+                *frame = (*frame + 1) % animation.frames.len();
+                unsafe { SetCursor(animation.frames[*frame].0.as_raw_handle()) };
+            }
+            w.mouse.update_cursor_animation(window);
+            result = ProcResult::Value(0);
+        },
+
         WM_KILLFOCUS => {
             let active_focus_changed = userdata.window_state_lock().set_focused(false);
             if active_focus_changed {
@@ -2026,13 +2261,7 @@ unsafe fn public_window_callback_inner(
 
             match set_cursor_to {
                 Some(selected_cursor) => {
-                    let hcursor = match selected_cursor {
-                        SelectedCursor::Named(cursor_icon) => unsafe {
-                            LoadCursorW(0, util::to_windows_cursor(cursor_icon))
-                        },
-                        SelectedCursor::Custom(cursor) => cursor.as_raw_handle(),
-                    };
-                    unsafe { SetCursor(hcursor) };
+                    unsafe { SetCursor(selected_cursor.as_raw_handle()) };
                     result = ProcResult::Value(0);
                 },
                 None => result = ProcResult::DefWindowProc(wparam),
@@ -2072,6 +2301,9 @@ unsafe fn public_window_callback_inner(
             // "you only need to use either the X-axis or the Y-axis value when scaling your
             // application since they are the same".
             // https://msdn.microsoft.com/en-us/library/windows/desktop/dn312083(v=vs.85).aspx
+            // The monitor change has to be reported before the scale factor change it causes.
+            update_monitor(window, userdata);
+
             let new_dpi_x = super::loword(wparam as u32) as u32;
             let new_scale_factor = dpi_to_scale_factor(new_dpi_x);
             let old_scale_factor: f64;
@@ -2272,6 +2504,20 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        // Also sent when HDR is toggled for a monitor.
+        WM_DISPLAYCHANGE => {
+            update_color_properties(window, userdata);
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
+        // Sent to the focused window, and only to it, when its thread switches keyboard layout.
+        WM_INPUTLANGCHANGE => {
+            if userdata.event_loop_runner.update_keyboard_layout(lparam as HKL) {
+                userdata.send_event(Event::KeyboardLayoutChanged);
+            }
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         WM_SETTINGCHANGE => {
             use crate::event::WindowEvent::ThemeChanged;
 
@@ -2295,6 +2541,11 @@ unsafe fn public_window_callback_inner(
 
         _ => {
             if msg == DESTROY_MSG_ID.get() {
+                let progress = mem::take(&mut userdata.window_state_lock().progress);
+                unsafe { set_taskbar_progress(window, progress, ProgressState::None) };
+                if userdata.window_state_lock().badge_count.take().is_some() {
+                    unsafe { set_taskbar_badge(window, None) };
+                }
                 unsafe { DestroyWindow(window) };
                 result = ProcResult::Value(0);
             } else if msg == SET_RETAIN_STATE_ON_SIZE_MSG_ID.get() {
@@ -2303,10 +2554,49 @@ unsafe fn public_window_callback_inner(
                     f.set(WindowFlags::MARKER_RETAIN_STATE_ON_SIZE, wparam != 0)
                 });
                 result = ProcResult::Value(0);
+            } else if msg == START_DRAG_MSG_ID.get() {
+                let payload = unsafe { Box::from_raw(wparam as *mut DragPayload) };
+                // This runs a modal loop until the drag ends, dispatching the messages meanwhile.
+                let action = unsafe { drag_source::do_drag_drop(&payload) };
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::DragSourceFinished { action },
+                });
+                result = ProcResult::Value(0);
+            } else if msg == PRESENT_FEEDBACK_MSG_ID.get() {
+                let feedback = {
+                    let mut window_state = userdata.window_state_lock();
+                    if window_state.presentation_feedback {
+                        presentation::frame_presented(
+                            wparam as u64,
+                            &mut window_state.dwm_missed_frames,
+                        )
+                    } else {
+                        None
+                    }
+                };
+                if let Some(feedback) = feedback {
+                    userdata.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: WindowEvent::FramePresented(feedback),
+                    });
+                }
+                result = ProcResult::Value(0);
             } else if msg == TASKBAR_CREATED.get() {
                 let window_state = userdata.window_state_lock();
                 unsafe { set_skip_taskbar(window, window_state.skip_taskbar) };
                 result = ProcResult::DefWindowProc(wparam);
+            } else if msg == TASKBAR_BUTTON_CREATED.get() {
+                // The progress and badge can only be shown once the taskbar button exists.
+                let (progress, badge_count) = {
+                    let window_state = userdata.window_state_lock();
+                    (window_state.progress, window_state.badge_count)
+                };
+                unsafe { set_taskbar_progress(window, ProgressState::None, progress) };
+                if badge_count.is_some() {
+                    unsafe { set_taskbar_badge(window, badge_count) };
+                }
+                result = ProcResult::DefWindowProc(wparam);
             } else {
                 result = ProcResult::DefWindowProc(wparam);
             }
@@ -2350,7 +2640,11 @@ unsafe extern "system" fn thread_event_target_callback(
     // the git blame and history would be preserved.
     let callback = || match msg {
         WM_NCDESTROY => {
-            unsafe { super::set_window_long(window, GWL_USERDATA, 0) };
+            unsafe {
+                WTSUnRegisterSessionNotification(window);
+                UnregisterPowerSettingNotification(userdata.power_saver_notification);
+                super::set_window_long(window, GWL_USERDATA, 0)
+            };
             userdata_removed = true;
             0
         },
@@ -2374,6 +2668,22 @@ unsafe extern "system" fn thread_event_target_callback(
             0
         },
 
+        WM_DISPLAYCHANGE => {
+            // The handles of the monitors that stay connected don't change.
+            let monitors = monitor::available_monitors();
+            let previous = userdata.monitors.replace(monitors.clone());
+            for monitor in previous.iter().filter(|monitor| !monitors.contains(monitor)) {
+                let monitor = RootMonitorHandle { inner: monitor.clone() };
+                userdata.send_event(Event::MonitorDisconnected(monitor));
+            }
+            for monitor in monitors.iter().filter(|monitor| !previous.contains(monitor)) {
+                let monitor = RootMonitorHandle { inner: monitor.clone() };
+                userdata.send_event(Event::MonitorConnected(monitor));
+            }
+
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        },
+
         WM_INPUT => {
             if let Some(data) = raw_input::get_raw_input_data(lparam as _) {
                 unsafe { handle_raw_input(&userdata, data) };
@@ -2382,6 +2692,50 @@ unsafe extern "system" fn thread_event_target_callback(
             unsafe { DefWindowProcW(window, msg, wparam, lparam) }
         },
 
+        WM_POWERBROADCAST => {
+            match wparam as u32 {
+                PBT_APMSUSPEND => {
+                    userdata.send_event(Event::SessionEvent(SessionEvent::Suspending))
+                },
+                PBT_APMRESUMEAUTOMATIC => {
+                    userdata.send_event(Event::SessionEvent(SessionEvent::Resumed))
+                },
+                PBT_POWERSETTINGCHANGE => {
+                    let setting = unsafe { &*(lparam as *const POWERBROADCAST_SETTING) };
+                    if is_power_saving_status(setting) {
+                        // The data is a `DWORD`, which isn't aligned in the structure.
+                        let status =
+                            unsafe { ptr::read_unaligned(setting.Data.as_ptr().cast::<u32>()) };
+                        let enabled = status != 0;
+                        // The current state is sent right after registering, only report changes.
+                        let previous = userdata.power_saver.replace(Some(enabled));
+                        if previous.is_some_and(|previous| previous != enabled) {
+                            let event = SessionEvent::PowerSaverChanged(enabled);
+                            userdata.send_event(Event::SessionEvent(event));
+                        }
+                    }
+                },
+                _ => (),
+            }
+
+            // Grant the request.
+            1
+        },
+
+        WM_WTSSESSION_CHANGE => {
+            match wparam as u32 {
+                WTS_SESSION_LOCK => {
+                    userdata.send_event(Event::SessionEvent(SessionEvent::SessionLocked))
+                },
+                WTS_SESSION_UNLOCK => {
+                    userdata.send_event(Event::SessionEvent(SessionEvent::SessionUnlocked))
+                },
+                _ => (),
+            }
+
+            0
+        },
+
         _ if msg == USER_EVENT_MSG_ID.get() => {
             // synthesis a placeholder UserEvent, so that if the callback is
             // re-entered it can be buffered for later delivery. the real
@@ -2408,6 +2762,16 @@ unsafe extern "system" fn thread_event_target_callback(
     result
 }
 
+/// Whether the notification is about `GUID_POWER_SAVING_STATUS`, which has a `DWORD` payload.
+fn is_power_saving_status(setting: &POWERBROADCAST_SETTING) -> bool {
+    let (guid, expected) = (&setting.PowerSetting, &GUID_POWER_SAVING_STATUS);
+    guid.data1 == expected.data1
+        && guid.data2 == expected.data2
+        && guid.data3 == expected.data3
+        && guid.data4 == expected.data4
+        && setting.DataLength as usize == mem::size_of::<u32>()
+}
+
 unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
     use crate::event::DeviceEvent::{Button, Key, Motion, MouseMotion, MouseWheel};
     use crate::event::ElementState::{Pressed, Released};
@@ -2415,10 +2779,16 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
 
     let device_id = wrap_device_id(data.header.hDevice as _);
 
+    // The raw input is registered per type of device, filter what wasn't requested.
+    let subscription = userdata.event_loop_runner.device_events();
+    let captures = |kind| subscription.captures(kind, device_id);
+
     if data.header.dwType == RIM_TYPEMOUSE {
         let mouse = unsafe { data.data.mouse };
 
-        if util::has_flag(mouse.usFlags as u32, MOUSE_MOVE_RELATIVE) {
+        if util::has_flag(mouse.usFlags as u32, MOUSE_MOVE_RELATIVE)
+            && captures(DeviceEventKinds::MOTION)
+        {
             let x = mouse.lLastX as f64;
             let y = mouse.lLastY as f64;
 
@@ -2445,7 +2815,8 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
         }
 
         let button_flags = unsafe { mouse.Anonymous.Anonymous.usButtonFlags };
-        if util::has_flag(button_flags as u32, RI_MOUSE_WHEEL) {
+        let wheel = captures(DeviceEventKinds::WHEEL);
+        if wheel && util::has_flag(button_flags as u32, RI_MOUSE_WHEEL) {
             let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
             let delta = button_data as f32 / WHEEL_DELTA as f32;
             userdata.send_event(Event::DeviceEvent {
@@ -2453,7 +2824,7 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
                 event: MouseWheel { delta: LineDelta(0.0, delta) },
             });
         }
-        if util::has_flag(button_flags as u32, RI_MOUSE_HWHEEL) {
+        if wheel && util::has_flag(button_flags as u32, RI_MOUSE_HWHEEL) {
             let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
             let delta = -button_data as f32 / WHEEL_DELTA as f32;
             userdata.send_event(Event::DeviceEvent {
@@ -2462,6 +2833,10 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
             });
         }
 
+        if !captures(DeviceEventKinds::BUTTON) {
+            return;
+        }
+
         let button_state = raw_input::get_raw_mouse_button_state(button_flags as u32);
         for (button, state) in button_state.iter().enumerate() {
             if let Some(state) = *state {
@@ -2472,6 +2847,10 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
             }
         }
     } else if data.header.dwType == RIM_TYPEKEYBOARD {
+        if !captures(DeviceEventKinds::KEY) {
+            return;
+        }
+
         let keyboard = unsafe { data.data.keyboard };
 
         let pressed = keyboard.Message == WM_KEYDOWN || keyboard.Message == WM_SYSKEYDOWN;
@@ -2501,6 +2880,106 @@ enum PointerMoveKind {
     None,
 }
 
+/// Adjusts the window rect given by `WM_SIZING` to the resize increments and the aspect ratio.
+///
+/// Both apply to the client area, which is `rect` without the `decorations`, and so do the
+/// `min_size` and `max_size` bounds.
+fn constrain_sizing_rect(
+    rect: &mut RECT,
+    side: u32,
+    decorations: PhysicalSize<i32>,
+    increments: Option<PhysicalSize<i32>>,
+    aspect_ratio: Option<AspectRatio>,
+    min_size: Option<PhysicalSize<i32>>,
+    max_size: Option<PhysicalSize<i32>>,
+) {
+    /// Calculate the amount to add to round `value` to the nearest multiple of `increment`.
+    fn snap_to_nearest_increment_delta(value: i32, increment: i32) -> i32 {
+        let half_one = increment / 2;
+        let half_two = increment - half_one;
+        half_one - (value - half_two) % increment
+    }
+
+    let width = rect.right - rect.left - decorations.width;
+    let height = rect.bottom - rect.top - decorations.height;
+
+    let (mut width_delta, mut height_delta) = match increments {
+        Some(inc) => (
+            snap_to_nearest_increment_delta(width, inc.width),
+            snap_to_nearest_increment_delta(height, inc.height),
+        ),
+        None => (0, 0),
+    };
+
+    // Windows won't bound check the value of `rect` after we're done here, so we
+    // have to check manually. If the width/height we snap to would go out of bounds, just
+    // set it equal to the min/max bound.
+    let final_width = width + width_delta;
+    let final_height = height + height_delta;
+    if let Some(min_size) = min_size {
+        if final_width < min_size.width {
+            width_delta += min_size.width - final_width;
+        }
+        if final_height < min_size.height {
+            height_delta += min_size.height - final_height;
+        }
+    }
+    if let Some(max_size) = max_size {
+        if final_width > max_size.width {
+            width_delta -= final_width - max_size.width;
+        }
+        if final_height > max_size.height {
+            height_delta -= final_height - max_size.height;
+        }
+    }
+
+    if let Some(aspect_ratio) = aspect_ratio {
+        // The dragged edge decides which dimension follows the other, corners follow the width.
+        let axis = match side {
+            WMSZ_TOP | WMSZ_BOTTOM => AspectRatioAxis::Height,
+            _ => AspectRatioAxis::Width,
+        };
+        let to_unsigned = |size: PhysicalSize<i32>| {
+            PhysicalSize::new(size.width.max(0) as u32, size.height.max(0) as u32)
+        };
+        let size = aspect_ratio.constrain(
+            to_unsigned(PhysicalSize::new(width + width_delta, height + height_delta)),
+            axis,
+            min_size.map(to_unsigned),
+            max_size.map(to_unsigned),
+        );
+        width_delta = size.width as i32 - width;
+        height_delta = size.height as i32 - height;
+    }
+
+    match side {
+        WMSZ_LEFT | WMSZ_BOTTOMLEFT | WMSZ_TOPLEFT => {
+            rect.left -= width_delta;
+        },
+        WMSZ_RIGHT | WMSZ_BOTTOMRIGHT | WMSZ_TOPRIGHT => {
+            rect.right += width_delta;
+        },
+        // Only the aspect ratio changes the width when dragging the top or bottom edge.
+        _ if aspect_ratio.is_some() => {
+            rect.right += width_delta;
+        },
+        _ => {},
+    }
+
+    match side {
+        WMSZ_TOP | WMSZ_TOPLEFT | WMSZ_TOPRIGHT => {
+            rect.top -= height_delta;
+        },
+        WMSZ_BOTTOM | WMSZ_BOTTOMLEFT | WMSZ_BOTTOMRIGHT => {
+            rect.bottom += height_delta;
+        },
+        _ if aspect_ratio.is_some() => {
+            rect.bottom += height_delta;
+        },
+        _ => {},
+    }
+}
+
 fn get_pointer_move_kind(
     window: HWND,
     mouse_was_inside_window: bool,
@@ -2526,3 +3005,89 @@ fn get_pointer_move_kind(
         PointerMoveKind::None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The decorations of a typical window, which make the outer size differ from the client area.
+    const DECORATIONS: PhysicalSize<i32> = PhysicalSize::new(16, 39);
+
+    fn client_size(rect: &RECT) -> PhysicalSize<i32> {
+        PhysicalSize::new(
+            rect.right - rect.left - DECORATIONS.width,
+            rect.bottom - rect.top - DECORATIONS.height,
+        )
+    }
+
+    #[test]
+    fn sizing_keeps_aspect_ratio_of_client_area() {
+        let aspect_ratio = Some(AspectRatio::new(16, 9));
+
+        // Dragging the right edge, the height follows the width.
+        let mut rect = RECT { left: 0, top: 0, right: 1616, bottom: 539 };
+        constrain_sizing_rect(&mut rect, WMSZ_RIGHT, DECORATIONS, None, aspect_ratio, None, None);
+        assert_eq!(client_size(&rect), PhysicalSize::new(1600, 900));
+        assert_eq!(rect.bottom, 939);
+
+        // Dragging the top edge, the width follows the height.
+        let mut rect = RECT { left: 0, top: 0, right: 116, bottom: 489 };
+        constrain_sizing_rect(&mut rect, WMSZ_TOP, DECORATIONS, None, aspect_ratio, None, None);
+        assert_eq!(client_size(&rect), PhysicalSize::new(800, 450));
+        assert_eq!((rect.left, rect.top), (0, 0));
+
+        // Dragging the top left corner, the top edge follows.
+        let mut rect = RECT { left: 100, top: 100, right: 916, bottom: 239 };
+        constrain_sizing_rect(&mut rect, WMSZ_TOPLEFT, DECORATIONS, None, aspect_ratio, None, None);
+        assert_eq!(client_size(&rect), PhysicalSize::new(800, 450));
+        assert_eq!((rect.left, rect.top, rect.bottom), (100, -250, 239));
+    }
+
+    #[test]
+    fn sizing_aspect_ratio_respects_bounds() {
+        let aspect_ratio = Some(AspectRatio::new(1, 1));
+        let min_size = Some(PhysicalSize::new(200, 300));
+        let max_size = Some(PhysicalSize::new(400, 400));
+
+        let mut rect = RECT { left: 0, top: 0, right: 116, bottom: 139 };
+        constrain_sizing_rect(
+            &mut rect,
+            WMSZ_BOTTOMRIGHT,
+            DECORATIONS,
+            None,
+            aspect_ratio,
+            min_size,
+            max_size,
+        );
+        assert_eq!(client_size(&rect), PhysicalSize::new(300, 300));
+
+        // The bounds win over the ratio.
+        let max_size = Some(PhysicalSize::new(250, 400));
+        let mut rect = RECT { left: 0, top: 0, right: 116, bottom: 139 };
+        constrain_sizing_rect(
+            &mut rect,
+            WMSZ_BOTTOMRIGHT,
+            DECORATIONS,
+            None,
+            aspect_ratio,
+            min_size,
+            max_size,
+        );
+        assert_eq!(client_size(&rect), PhysicalSize::new(250, 300));
+    }
+
+    #[test]
+    fn sizing_combines_increments_and_aspect_ratio() {
+        let mut rect = RECT { left: 0, top: 0, right: 421, bottom: 139 };
+        constrain_sizing_rect(
+            &mut rect,
+            WMSZ_RIGHT,
+            DECORATIONS,
+            Some(PhysicalSize::new(10, 10)),
+            Some(AspectRatio::new(2, 1)),
+            None,
+            None,
+        );
+        assert_eq!(client_size(&rect), PhysicalSize::new(410, 205));
+    }
+}
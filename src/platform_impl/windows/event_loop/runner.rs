@@ -6,9 +6,12 @@ use std::time::Instant;
 use std::{mem, panic};
 
 use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+use windows_sys::Win32::UI::TextServices::HKL;
 
 use crate::dpi::PhysicalSize;
 use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
+use crate::event_loop::DeviceEventSubscription;
 use crate::platform_impl::platform::event_loop::{WindowData, GWL_USERDATA};
 use crate::platform_impl::platform::get_window_long;
 use crate::window::WindowId;
@@ -27,6 +30,9 @@ pub(crate) struct EventLoopRunner {
     pub(super) interrupt_msg_dispatch: Cell<bool>,
 
     control_flow: Cell<ControlFlow>,
+    device_events: Cell<DeviceEventSubscription>,
+    /// The last keyboard layout reported to the application, which every window is told about.
+    keyboard_layout: Cell<HKL>,
     exit: Cell<Option<i32>>,
     runner_state: Cell<RunnerState>,
     last_events_cleared: Cell<Instant>,
@@ -64,6 +70,8 @@ impl EventLoopRunner {
             interrupt_msg_dispatch: Cell::new(false),
             runner_state: Cell::new(RunnerState::Uninitialized),
             control_flow: Cell::new(ControlFlow::default()),
+            device_events: Cell::new(DeviceEventSubscription::default()),
+            keyboard_layout: Cell::new(unsafe { GetKeyboardLayout(0) }),
             exit: Cell::new(None),
             panic_error: Cell::new(None),
             last_events_cleared: Cell::new(Instant::now()),
@@ -106,6 +114,8 @@ impl EventLoopRunner {
             runner_state,
             panic_error,
             control_flow: _,
+            device_events: _,
+            keyboard_layout: _,
             exit,
             last_events_cleared: _,
             event_handler,
@@ -141,6 +151,19 @@ impl EventLoopRunner {
         self.control_flow.get()
     }
 
+    pub fn set_device_events(&self, subscription: DeviceEventSubscription) {
+        self.device_events.set(subscription)
+    }
+
+    pub fn device_events(&self) -> DeviceEventSubscription {
+        self.device_events.get()
+    }
+
+    /// Remembers the keyboard layout, returning whether it changed.
+    pub fn update_keyboard_layout(&self, layout: HKL) -> bool {
+        self.keyboard_layout.replace(layout) != layout
+    }
+
     pub fn set_exit_code(&self, code: i32) {
         self.exit.set(Some(code))
     }
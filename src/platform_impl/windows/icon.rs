@@ -1,6 +1,7 @@
 use std::ffi::c_void;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fmt, io, mem};
 
 use cursor_icon::CursorIcon;
@@ -10,11 +11,12 @@ use windows_sys::Win32::Graphics::Gdi::{
     CreateBitmap, CreateCompatibleBitmap, DeleteObject, GetDC, ReleaseDC, SetBitmapBits,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CreateIcon, CreateIconIndirect, DestroyCursor, DestroyIcon, LoadImageW, SendMessageW, HCURSOR,
-    HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE, LR_LOADFROMFILE, WM_SETICON,
+    CreateIcon, CreateIconIndirect, DestroyCursor, DestroyIcon, LoadCursorW, LoadImageW,
+    SendMessageW, HCURSOR, HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE,
+    LR_LOADFROMFILE, WM_SETICON,
 };
 
-use crate::cursor::CursorImage;
+use crate::cursor::{CursorImage, OnlyCursorImageSource};
 use crate::dpi::PhysicalSize;
 use crate::icon::*;
 
@@ -167,6 +169,21 @@ pub fn unset_for_window(hwnd: HWND, icon_type: IconType) {
 pub enum SelectedCursor {
     Named(CursorIcon),
     Custom(Arc<RaiiCursor>),
+    /// An animated cursor and the index of the frame that is shown.
+    Animation(Arc<CursorAnimation>, usize),
+}
+
+impl SelectedCursor {
+    /// Returns the cursor that is shown, i.e. the current frame of an animated cursor.
+    pub fn as_raw_handle(&self) -> HCURSOR {
+        match self {
+            Self::Named(cursor_icon) => unsafe {
+                LoadCursorW(0, util::to_windows_cursor(*cursor_icon))
+            },
+            Self::Custom(cursor) => cursor.as_raw_handle(),
+            Self::Animation(animation, frame) => animation.frames[*frame].0.as_raw_handle(),
+        }
+    }
 }
 
 impl Default for SelectedCursor {
@@ -178,11 +195,47 @@ impl Default for SelectedCursor {
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum WinCursor {
     Cursor(Arc<RaiiCursor>),
+    Animation(Arc<CursorAnimation>),
     Failed,
 }
 
 impl WinCursor {
-    pub(crate) fn new(image: &CursorImage) -> Result<Self, io::Error> {
+    pub(crate) fn new(source: &OnlyCursorImageSource) -> Result<Self, io::Error> {
+        match source {
+            OnlyCursorImageSource::Image(image) => {
+                Ok(Self::Cursor(Arc::new(RaiiCursor::new(image)?)))
+            },
+            OnlyCursorImageSource::Animation(frames) => {
+                let frames = frames
+                    .iter()
+                    .map(|frame| Ok((RaiiCursor::new(&frame.image)?, frame.duration)))
+                    .collect::<Result<_, io::Error>>()?;
+                Ok(Self::Animation(Arc::new(CursorAnimation { frames })))
+            },
+        }
+    }
+}
+
+/// The frames of an animated cursor, which are shown one after another by a timer of the window
+/// while the cursor is inside of it.
+#[derive(Debug, Hash, Eq, PartialEq)]
+pub struct CursorAnimation {
+    pub frames: Vec<(RaiiCursor, Duration)>,
+}
+
+#[derive(Debug, Hash, Eq, PartialEq)]
+pub struct RaiiCursor {
+    handle: HCURSOR,
+}
+
+impl Drop for RaiiCursor {
+    fn drop(&mut self) {
+        unsafe { DestroyCursor(self.handle) };
+    }
+}
+
+impl RaiiCursor {
+    fn new(image: &CursorImage) -> Result<Self, io::Error> {
         let mut bgra = image.rgba.clone();
         bgra.chunks_exact_mut(4).for_each(|chunk| chunk.swap(0, 2));
 
@@ -227,23 +280,10 @@ impl WinCursor {
                 return Err(io::Error::last_os_error());
             }
 
-            Ok(Self::Cursor(Arc::new(RaiiCursor { handle })))
+            Ok(Self { handle })
         }
     }
-}
-
-#[derive(Debug, Hash, Eq, PartialEq)]
-pub struct RaiiCursor {
-    handle: HCURSOR,
-}
-
-impl Drop for RaiiCursor {
-    fn drop(&mut self) {
-        unsafe { DestroyCursor(self.handle) };
-    }
-}
 
-impl RaiiCursor {
     pub fn as_raw_handle(&self) -> HICON {
         self.handle
     }
@@ -42,9 +42,15 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
 };
 use windows_sys::Win32::UI::TextServices::HKL;
 
-use crate::keyboard::{Key, KeyCode, ModifiersState, NamedKey, NativeKey, PhysicalKey};
+use crate::keyboard::{
+    self, Key, KeyCode, LockKeys, ModifiersState, NamedKey, NativeKey, PhysicalKey,
+};
 use crate::platform_impl::{loword, primarylangid, scancode_to_physicalkey};
 
+/// Tells `ToUnicodeEx` to leave the keyboard state alone, so building a layout doesn't cancel a
+/// dead key being typed. Only supported since Windows 10, version 1607.
+const TO_UNICODE_KEEP_KEYBOARD_STATE: u32 = 1 << 2;
+
 pub(crate) static LAYOUT_CACHE: Lazy<Mutex<LayoutCache>> =
     Lazy::new(|| Mutex::new(LayoutCache::default()));
 
@@ -52,6 +58,18 @@ fn key_pressed(vkey: VIRTUAL_KEY) -> bool {
     unsafe { (GetKeyState(vkey as i32) & (1 << 15)) == (1 << 15) }
 }
 
+fn key_toggled(vkey: VIRTUAL_KEY) -> bool {
+    unsafe { (GetKeyState(vkey as i32) & 1) == 1 }
+}
+
+/// The lock keys that are on, as of the message being processed.
+pub(crate) fn get_lock_keys() -> LockKeys {
+    let mut lock_keys = LockKeys::empty();
+    lock_keys.set(LockKeys::CAPS_LOCK, key_toggled(VK_CAPITAL));
+    lock_keys.set(LockKeys::NUM_LOCK, key_toggled(VK_NUMLOCK));
+    lock_keys
+}
+
 const NUMPAD_VKEYS: [VIRTUAL_KEY; 16] = [
     VK_NUMPAD0,
     VK_NUMPAD1,
@@ -249,6 +267,24 @@ impl Layout {
         }
         Key::Unidentified(native_code)
     }
+
+    /// The key produced by the physical key without modifiers, with dead keys reported as the
+    /// character they produce on their own.
+    pub fn key_from_key_code(&self, code: KeyCode) -> Key {
+        let vkey = keycode_to_vkey(code, self.hkl);
+        match self.get_key(WindowsModifiers::empty(), false, vkey, &PhysicalKey::Code(code)) {
+            Key::Unidentified(_) => keyboard::layout_independent_key(code),
+            key => keyboard::standalone_key(key),
+        }
+    }
+
+    /// The physical key producing the key without modifiers, see [`Self::key_from_key_code`].
+    pub fn key_code_from_key(&self, key: Key) -> Option<KeyCode> {
+        let codes =
+            self.keys.get(&WindowsModifiers::empty()).into_iter().flat_map(|keys| keys.keys());
+        let codes = codes.copied().chain(keyboard::layout_independent_keys().map(|(code, _)| code));
+        keyboard::find_key_code(key, codes.map(|code| (code, self.key_from_key_code(code))))
+    }
 }
 
 #[derive(Default)]
@@ -457,18 +493,23 @@ impl LayoutCache {
                 (&key_state[0]) as *const _,
                 (&mut label_wide[0]) as *mut _,
                 label_wide.len() as i32,
-                0,
+                TO_UNICODE_KEEP_KEYBOARD_STATE,
                 locale_id as HKL,
             );
             if wide_len < 0 {
-                // If it's dead, we run `ToUnicode` again to consume the dead-key
+                // The spacing character of the dead key, which is only written when the keyboard
+                // state is kept.
+                let spacing_char = char::decode_utf16([label_wide[0]]).next().and_then(Result::ok);
+
+                // Older versions of Windows ignore the flag and store the dead key, so we run
+                // `ToUnicode` again to consume it.
                 wide_len = ToUnicodeEx(
                     vkey,
                     scancode,
                     (&key_state[0]) as *const _,
                     (&mut label_wide[0]) as *mut _,
                     label_wide.len() as i32,
-                    0,
+                    TO_UNICODE_KEEP_KEYBOARD_STATE,
                     locale_id as HKL,
                 );
                 if wide_len > 0 {
@@ -479,7 +520,7 @@ impl LayoutCache {
                         }
                     }
                 }
-                return ToUnicodeResult::Dead(None);
+                return ToUnicodeResult::Dead(spacing_char.filter(|ch| !ch.is_control()));
             }
             if wide_len > 0 {
                 let os_string = OsString::from_wide(&label_wide[0..wide_len as usize]);
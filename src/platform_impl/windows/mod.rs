@@ -16,6 +16,7 @@ pub use self::icon::WinIcon as PlatformIcon;
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 use crate::platform_impl::Fullscreen;
 
+use crate::error::PlatformError;
 use crate::event::DeviceId as RootDeviceId;
 use crate::icon::Icon;
 use crate::keyboard::Key;
@@ -91,6 +92,14 @@ fn wrap_device_id(id: u32) -> RootDeviceId {
 
 pub type OsError = std::io::Error;
 
+/// The `GetLastError()` code or `HRESULT` of an error, if it came from the OS.
+pub(crate) fn platform_error(error: &OsError) -> Option<PlatformError> {
+    let code = error.raw_os_error()?;
+    // Failed `HRESULT`s have the severity bit set, unlike the codes of `GetLastError()`.
+    let domain = if code < 0 { "HRESULT" } else { "Win32" };
+    Some(PlatformError::new(domain, Some((code as u32).into()), error.to_string()))
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {
     pub text_with_all_modifiers: Option<SmolStr>,
@@ -181,9 +190,11 @@ unsafe fn set_window_long(hwnd: HWND, nindex: WINDOW_LONG_PTR_INDEX, dwnewlong:
 
 #[macro_use]
 mod util;
+mod badge;
 mod dark_mode;
 mod definitions;
 mod dpi;
+mod drag_source;
 mod drop_handler;
 mod event_loop;
 mod icon;
@@ -191,6 +202,27 @@ mod ime;
 mod keyboard;
 mod keyboard_layout;
 mod monitor;
+mod presentation;
 mod raw_input;
+mod wait_timer;
 mod window;
 mod window_state;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_error_domain() {
+        // `ERROR_ACCESS_DENIED`
+        let error = platform_error(&OsError::from_raw_os_error(5)).unwrap();
+        assert_eq!((error.domain(), error.code()), ("Win32", Some(5)));
+
+        // `E_ACCESSDENIED`
+        let error = platform_error(&OsError::from_raw_os_error(0x80070005_u32 as i32)).unwrap();
+        assert_eq!((error.domain(), error.code()), ("HRESULT", Some(0x80070005)));
+
+        let error = OsError::new(std::io::ErrorKind::Other, "custom");
+        assert_eq!(platform_error(&error), None);
+    }
+}
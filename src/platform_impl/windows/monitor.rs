@@ -2,7 +2,16 @@ use std::collections::{BTreeSet, VecDeque};
 use std::hash::Hash;
 use std::{io, mem, ptr};
 
-use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT};
+use windows_sys::Win32::Devices::Display::{
+    DisplayConfigGetDeviceInfo, GetDisplayConfigBufferSizes, QueryDisplayConfig,
+    DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_DEVICE_INFO_TYPE,
+    DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO, DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO,
+    DISPLAYCONFIG_PATH_TARGET_INFO, DISPLAYCONFIG_SDR_WHITE_LEVEL,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME, QDC_ONLY_ACTIVE_PATHS,
+};
+use windows_sys::Win32::Foundation::{BOOL, ERROR_SUCCESS, HWND, LPARAM, LUID, POINT, RECT};
 use windows_sys::Win32::Graphics::Gdi::{
     EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, MonitorFromPoint,
     MonitorFromWindow, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
@@ -12,7 +21,7 @@ use windows_sys::Win32::Graphics::Gdi::{
 
 use super::util::decode_wide;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::monitor::{ColorProperties, ColorSpace, VideoModeHandle as RootVideoModeHandle};
 use crate::platform_impl::platform::dpi::{dpi_to_scale_factor, get_monitor_dpi};
 use crate::platform_impl::platform::util::has_flag;
 use crate::platform_impl::platform::window::Window;
@@ -145,6 +154,61 @@ pub(crate) fn get_monitor_info(hmonitor: HMONITOR) -> Result<MONITORINFOEXW, io:
     }
 }
 
+/// Finds the display configuration target driven by the GDI device named `device_name`.
+fn display_config_target(device_name: &[u16]) -> Option<DISPLAYCONFIG_PATH_TARGET_INFO> {
+    let mut path_count = 0;
+    let mut mode_count = 0;
+    if unsafe {
+        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+    } != ERROR_SUCCESS
+    {
+        return None;
+    }
+
+    let mut paths: Vec<DISPLAYCONFIG_PATH_INFO> =
+        vec![unsafe { mem::zeroed() }; path_count as usize];
+    let mut modes: Vec<DISPLAYCONFIG_MODE_INFO> =
+        vec![unsafe { mem::zeroed() }; mode_count as usize];
+    if unsafe {
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count,
+            paths.as_mut_ptr(),
+            &mut mode_count,
+            modes.as_mut_ptr(),
+            ptr::null_mut(),
+        )
+    } != ERROR_SUCCESS
+    {
+        return None;
+    }
+    paths.truncate(path_count as usize);
+
+    let device_name = decode_wide(device_name);
+    paths.into_iter().find_map(|path| {
+        let mut source_name: DISPLAYCONFIG_SOURCE_DEVICE_NAME = unsafe { mem::zeroed() };
+        source_name.header = device_info_header(
+            DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+            mem::size_of_val(&source_name),
+            path.sourceInfo.adapterId,
+            path.sourceInfo.id,
+        );
+        let status = unsafe { DisplayConfigGetDeviceInfo(&mut source_name.header) };
+        (status == ERROR_SUCCESS as i32
+            && decode_wide(&source_name.viewGdiDeviceName) == device_name)
+            .then_some(path.targetInfo)
+    })
+}
+
+fn device_info_header(
+    r#type: DISPLAYCONFIG_DEVICE_INFO_TYPE,
+    size: usize,
+    adapter_id: LUID,
+    id: u32,
+) -> DISPLAYCONFIG_DEVICE_INFO_HEADER {
+    DISPLAYCONFIG_DEVICE_INFO_HEADER { r#type, size: size as u32, adapterId: adapter_id, id }
+}
+
 impl MonitorHandle {
     pub(crate) fn new(hmonitor: HMONITOR) -> Self {
         MonitorHandle(hmonitor)
@@ -192,6 +256,57 @@ impl MonitorHandle {
         }
     }
 
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let target = display_config_target(&monitor_info.szDevice)?;
+
+        let mut color_info: DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO = unsafe { mem::zeroed() };
+        color_info.header = device_info_header(
+            DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+            mem::size_of_val(&color_info),
+            target.adapterId,
+            target.id,
+        );
+        if unsafe { DisplayConfigGetDeviceInfo(&mut color_info.header) } != ERROR_SUCCESS as i32 {
+            return None;
+        }
+
+        // The bitfield is `advancedColorSupported`, `advancedColorEnabled`, `wideColorEnforced`
+        // and `advancedColorForceDisabled`. Advanced color is forced on standard dynamic range
+        // monitors for color management, in which case the output isn't HDR.
+        let flags = unsafe { color_info.Anonymous.value };
+        let advanced_color_enabled = flags & 0b10 != 0;
+        let wide_color_enforced = flags & 0b100 != 0;
+        let hdr_enabled = advanced_color_enabled && !wide_color_enforced;
+        let color_space = match (hdr_enabled, wide_color_enforced) {
+            (true, _) => Some(ColorSpace::Bt2020),
+            (false, true) => None,
+            (false, false) => Some(ColorSpace::Srgb),
+        };
+
+        let mut white_level: DISPLAYCONFIG_SDR_WHITE_LEVEL = unsafe { mem::zeroed() };
+        white_level.header = device_info_header(
+            DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL,
+            mem::size_of_val(&white_level),
+            target.adapterId,
+            target.id,
+        );
+        // The white level is a multiplier of 80 nits, scaled by 1000.
+        let sdr_white_level_nits = (unsafe { DisplayConfigGetDeviceInfo(&mut white_level.header) }
+            == ERROR_SUCCESS as i32)
+            .then(|| white_level.SDRWhiteLevel as f32 / 1000.0 * 80.0);
+
+        Some(ColorProperties {
+            hdr_enabled,
+            max_luminance_nits: None,
+            sdr_white_level_nits,
+            color_space,
+            bits_per_channel: u8::try_from(color_info.bitsPerColorChannel)
+                .ok()
+                .filter(|&bits| bits > 0),
+        })
+    }
+
     #[inline]
     pub fn position(&self) -> PhysicalPosition<i32> {
         get_monitor_info(self.0)
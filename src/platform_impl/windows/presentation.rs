@@ -0,0 +1,55 @@
+//! Estimation of the presentation of frames from the timing of the DWM composition.
+
+use std::mem;
+use std::time::{Duration, Instant};
+
+use windows_sys::Win32::Graphics::Dwm::{DwmGetCompositionTimingInfo, DWM_TIMING_INFO};
+use windows_sys::Win32::System::Performance::{QueryPerformanceCounter, QueryPerformanceFrequency};
+
+use crate::event::PresentationFeedback;
+
+/// The feedback of the frame presented after the `pre_present_notify` call `generation`, which
+/// the DWM composes at the next vertical blank.
+///
+/// `missed_frames` is the number of frames the DWM missed or dropped at the previous feedback.
+pub fn frame_presented(
+    generation: u64,
+    missed_frames: &mut Option<u64>,
+) -> Option<PresentationFeedback> {
+    let mut info: DWM_TIMING_INFO = unsafe { mem::zeroed() };
+    info.cbSize = mem::size_of::<DWM_TIMING_INFO>() as u32;
+    // Since Windows 8.1 only the timing of the whole composition is available, not per window.
+    if unsafe { DwmGetCompositionTimingInfo(0, &mut info) } < 0 {
+        return None;
+    }
+
+    let mut now = 0;
+    let mut frequency = 0;
+    unsafe {
+        QueryPerformanceCounter(&mut now);
+        QueryPerformanceFrequency(&mut frequency);
+    }
+
+    let (vblank, period) = (info.qpcVBlank, info.qpcRefreshPeriod);
+    if frequency <= 0 || period == 0 {
+        return None;
+    }
+
+    let now = now as u64;
+    let next_vblank =
+        if vblank > now { vblank } else { vblank + ((now - vblank) / period + 1) * period };
+    let to_duration = |ticks: u64| {
+        Duration::from_nanos((ticks as u128 * 1_000_000_000 / frequency as u128) as u64)
+    };
+
+    let total_missed = info.cFramesMissed + info.cFramesDropped;
+    let missed =
+        missed_frames.replace(total_missed).is_some_and(|previous| total_missed > previous);
+
+    Some(PresentationFeedback {
+        generation,
+        presentation_time: Instant::now().checked_add(to_duration(next_vblank - now)),
+        refresh_interval: Some(to_duration(period)),
+        missed,
+    })
+}
@@ -23,7 +23,7 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 
 use super::scancode_to_physicalkey;
 use crate::event::ElementState;
-use crate::event_loop::DeviceEvents;
+use crate::event_loop::{DeviceEventKinds, DeviceEventSubscription, DeviceEvents};
 use crate::keyboard::{KeyCode, PhysicalKey};
 use crate::platform_impl::platform::util;
 
@@ -131,34 +131,42 @@ pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
 }
 
 pub fn register_all_mice_and_keyboards_for_raw_input(
-    mut window_handle: HWND,
-    filter: DeviceEvents,
+    window_handle: HWND,
+    subscription: DeviceEventSubscription,
 ) -> bool {
+    let kinds = subscription.kinds();
+    let mouse_kinds = DeviceEventKinds::MOTION | DeviceEventKinds::BUTTON | DeviceEventKinds::WHEEL;
+    if kinds.intersects(mouse_kinds) && !kinds.contains(mouse_kinds) {
+        tracing::debug!(
+            "Windows can't capture the kinds of raw mouse input separately, capturing all"
+        );
+    }
+    if subscription.device().is_some() {
+        tracing::debug!("Windows can't capture the raw input of a single device, capturing all");
+    }
+
     // RIDEV_DEVNOTIFY: receive hotplug events
     // RIDEV_INPUTSINK: receive events even if we're not in the foreground
     // RIDEV_REMOVE: don't receive device events (requires NULL hwndTarget)
-    let flags = match filter {
-        DeviceEvents::Never => {
-            window_handle = 0;
-            RIDEV_REMOVE
-        },
-        DeviceEvents::WhenFocused => RIDEV_DEVNOTIFY,
-        DeviceEvents::Always => RIDEV_DEVNOTIFY | RIDEV_INPUTSINK,
-    };
+    let device = |usage, captured: bool| {
+        let (flags, window_handle) = match subscription.when() {
+            DeviceEvents::Never => (RIDEV_REMOVE, 0),
+            _ if !captured => (RIDEV_REMOVE, 0),
+            DeviceEvents::WhenFocused => (RIDEV_DEVNOTIFY, window_handle),
+            DeviceEvents::Always => (RIDEV_DEVNOTIFY | RIDEV_INPUTSINK, window_handle),
+        };
 
-    let devices: [RAWINPUTDEVICE; 2] = [
         RAWINPUTDEVICE {
             usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_MOUSE,
+            usUsage: usage,
             dwFlags: flags,
             hwndTarget: window_handle,
-        },
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_KEYBOARD,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
+        }
+    };
+
+    let devices: [RAWINPUTDEVICE; 2] = [
+        device(HID_USAGE_GENERIC_MOUSE, kinds.intersects(mouse_kinds)),
+        device(HID_USAGE_GENERIC_KEYBOARD, kinds.contains(DeviceEventKinds::KEY)),
     ];
 
     register_raw_input_devices(&devices)
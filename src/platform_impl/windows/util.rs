@@ -6,9 +6,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::{io, mem, ptr};
 
 use crate::utils::Lazy;
-use windows_sys::core::{HRESULT, PCWSTR};
+use windows_sys::core::{GUID, HRESULT, PCWSTR};
 use windows_sys::Win32::Foundation::{BOOL, HANDLE, HMODULE, HWND, RECT};
 use windows_sys::Win32::Graphics::Gdi::{ClientToScreen, HMONITOR};
+use windows_sys::Win32::System::Com::IDataObject;
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::System::SystemServices::IMAGE_DOS_HEADER;
 use windows_sys::Win32::UI::HiDpi::{
@@ -247,6 +248,16 @@ pub type GetPointerTouchInfo =
 pub type GetPointerPenInfo =
     unsafe extern "system" fn(pointId: u32, penInfo: *mut POINTER_PEN_INFO) -> BOOL;
 
+// `windows-sys` only declares it with the `ITEMIDLIST` of a feature it doesn't have.
+pub type SHCreateDataObject = unsafe extern "system" fn(
+    pidlFolder: *const c_void,
+    cidl: u32,
+    apidl: *const *const c_void,
+    pdtInner: IDataObject,
+    riid: *const GUID,
+    ppv: *mut *mut c_void,
+) -> HRESULT;
+
 pub(crate) static GET_DPI_FOR_WINDOW: Lazy<Option<GetDpiForWindow>> =
     Lazy::new(|| get_function!("user32.dll", GetDpiForWindow));
 pub(crate) static ADJUST_WINDOW_RECT_EX_FOR_DPI: Lazy<Option<AdjustWindowRectExForDpi>> =
@@ -271,3 +282,5 @@ pub(crate) static GET_POINTER_TOUCH_INFO: Lazy<Option<GetPointerTouchInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerTouchInfo));
 pub(crate) static GET_POINTER_PEN_INFO: Lazy<Option<GetPointerPenInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerPenInfo));
+pub(crate) static SH_CREATE_DATA_OBJECT: Lazy<Option<SHCreateDataObject>> =
+    Lazy::new(|| get_function!("shell32.dll", SHCreateDataObject));
@@ -0,0 +1,101 @@
+//! Precise waiting for messages until a deadline in the near future.
+//!
+//! Timers set with `SetTimer` have the resolution of the system clock, 15.6 ms by default, so
+//! they're only used for deadlines further away, where waking up late doesn't matter as much and
+//! raising the clock resolution would cost battery life.
+
+use std::ptr;
+use std::time::Duration;
+
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+use windows_sys::Win32::Media::{timeBeginPeriod, timeEndPeriod};
+use windows_sys::Win32::System::Threading::{
+    CancelWaitableTimer, CreateWaitableTimerExW, SetWaitableTimer,
+    CREATE_WAITABLE_TIMER_HIGH_RESOLUTION, INFINITE, TIMER_ALL_ACCESS,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    GetMessageW, KillTimer, MsgWaitForMultipleObjectsEx, PeekMessageW, SetTimer, MSG,
+    MWMO_INPUTAVAILABLE, PM_REMOVE, QS_ALLINPUT, WM_QUIT,
+};
+
+use super::event_loop::dur2timeout;
+use crate::platform::pump_events::PumpStatus;
+
+/// Deadlines closer than this are waited for precisely.
+pub const PRECISE_WAIT_THRESHOLD: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub struct WaitTimer {
+    /// The high resolution waitable timer, only available since Windows 10 1803.
+    timer: Option<HANDLE>,
+}
+
+impl WaitTimer {
+    pub fn new() -> Self {
+        let timer = unsafe {
+            CreateWaitableTimerExW(
+                ptr::null(),
+                ptr::null(),
+                CREATE_WAITABLE_TIMER_HIGH_RESOLUTION,
+                TIMER_ALL_ACCESS,
+            )
+        };
+        Self { timer: (timer != 0).then_some(timer) }
+    }
+
+    /// Waits for the next message, or until `timeout` elapsed.
+    ///
+    /// Returns `None` if no MSG was read, else a `Continue` or `Exit` status.
+    pub fn wait_for_msg(&self, msg: &mut MSG, timeout: Duration) -> Option<PumpStatus> {
+        if let Some(timer) = self.timer {
+            // Negative due times are relative, in units of 100 ns.
+            let due_time = -((timeout.as_nanos() / 100).clamp(1, i64::MAX as u128) as i64);
+            if unsafe { SetWaitableTimer(timer, &due_time, 0, None, ptr::null(), 0) } != 0 {
+                let status = wait_for_msg_or_timer(msg, timer);
+                unsafe { CancelWaitableTimer(timer) };
+                return status;
+            }
+        }
+
+        // Raise the resolution of the system clock only for the time of the wait.
+        unsafe {
+            timeBeginPeriod(1);
+            let timer_id = SetTimer(0, 0, dur2timeout(timeout), None);
+            let get_status = GetMessageW(msg, 0, 0, 0);
+            KillTimer(0, timer_id);
+            timeEndPeriod(1);
+            // A return value of 0 implies `WM_QUIT`
+            Some(if get_status == 0 { PumpStatus::Exit(0) } else { PumpStatus::Continue })
+        }
+    }
+}
+
+impl Drop for WaitTimer {
+    fn drop(&mut self) {
+        if let Some(timer) = self.timer {
+            unsafe { CloseHandle(timer) };
+        }
+    }
+}
+
+fn wait_for_msg_or_timer(msg: &mut MSG, timer: HANDLE) -> Option<PumpStatus> {
+    loop {
+        let result = unsafe {
+            MsgWaitForMultipleObjectsEx(1, &timer, INFINITE, QS_ALLINPUT, MWMO_INPUTAVAILABLE)
+        };
+        if result != WAIT_OBJECT_0 + 1 {
+            // The timer elapsed, or waiting failed and we'd rather not block.
+            return None;
+        }
+
+        // Sent messages are dispatched by `PeekMessageW` without returning them, so keep
+        // waiting if that's all there was.
+        if unsafe { PeekMessageW(msg, 0, 0, 0, PM_REMOVE) } != 0 {
+            return Some(if msg.message == WM_QUIT {
+                PumpStatus::Exit(0)
+            } else {
+                PumpStatus::Continue
+            });
+        }
+    }
+}
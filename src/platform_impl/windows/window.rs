@@ -17,8 +17,9 @@ use windows_sys::Win32::Graphics::Dwm::{
 };
 use windows_sys::Win32::Graphics::Gdi::{
     ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn,
-    RedrawWindow, CDS_FULLSCREEN, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE, DISP_CHANGE_BADPARAM,
-    DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, RDW_INTERNALPAINT,
+    MonitorFromWindow, RedrawWindow, CDS_FULLSCREEN, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE,
+    DISP_CHANGE_BADPARAM, DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, MONITOR_DEFAULTTONEAREST,
+    RDW_INTERNALPAINT,
 };
 use windows_sys::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
@@ -30,15 +31,19 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     VIRTUAL_KEY, VK_LMENU, VK_MENU, VK_SPACE,
 };
 use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
+use windows_sys::Win32::UI::Shell::{
+    TBPFLAG, TBPF_ERROR, TBPF_INDETERMINATE, TBPF_NOPROGRESS, TBPF_NORMAL, TBPF_PAUSED,
+};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
-    GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement, GetWindowTextLengthW,
-    GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
-    SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW,
-    CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY,
-    GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP,
-    HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY,
+    GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindow, GetWindowPlacement,
+    GetWindowTextLengthW, GetWindowTextW, IsWindow, IsWindowVisible, PeekMessageW, PostMessageW,
+    RegisterClassExW, SetCursor, SetCursorPos, SetForegroundWindow, SetLayeredWindowAttributes,
+    SetMenuDefaultItem, SetWindowDisplayAffinity, SetWindowPlacement, SetWindowPos, SetWindowTextW,
+    TrackPopupMenu, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP,
+    FLASHW_TIMERNOFG, FLASHW_TRAY, GWLP_HINSTANCE, GWLP_HWNDPARENT, GW_OWNER, HTBOTTOM,
+    HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT,
+    LWA_ALPHA, LWA_COLORKEY, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY,
     PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER,
     SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD,
     WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
@@ -47,19 +52,22 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 use tracing::warn;
 
 use crate::cursor::Cursor;
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::icon::Icon;
-use crate::platform::windows::{BackdropType, Color, CornerPreference};
+use crate::platform::windows::{BackdropType, Color, CornerPreference, LayeredMode};
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::definitions::{
-    CLSID_TaskbarList, IID_ITaskbarList, IID_ITaskbarList2, ITaskbarList, ITaskbarList2,
+    CLSID_TaskbarList, IID_ITaskbarList, IID_ITaskbarList2, IID_ITaskbarList3, ITaskbarList,
+    ITaskbarList2, ITaskbarList3,
 };
 use crate::platform_impl::platform::dpi::{
     dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi,
 };
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
-use crate::platform_impl::platform::event_loop::{self, ActiveEventLoop, DESTROY_MSG_ID};
+use crate::platform_impl::platform::event_loop::{
+    self, ActiveEventLoop, DESTROY_MSG_ID, PRESENT_FEEDBACK_MSG_ID, START_DRAG_MSG_ID,
+};
 use crate::platform_impl::platform::icon::{self, IconType, WinCursor};
 use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
@@ -67,10 +75,12 @@ use crate::platform_impl::platform::monitor::{self, MonitorHandle};
 use crate::platform_impl::platform::window_state::{
     CursorFlags, SavedWindow, WindowFlags, WindowState,
 };
-use crate::platform_impl::platform::{util, Fullscreen, SelectedCursor, WindowId};
+use crate::platform_impl::platform::{
+    badge, drag_source, util, Fullscreen, SelectedCursor, WindowId,
+};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-    WindowButtons, WindowLevel,
+    AspectRatio, CursorGrabMode, DragPayload, ImeCapabilities, ImePurpose, ProgressState, Rect,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
 };
 
 /// The Win32 implementation of the main `Window` object.
@@ -131,6 +141,52 @@ impl Window {
 
     pub fn set_blur(&self, _blur: bool) {}
 
+    pub fn set_opacity(&self, opacity: f32) {
+        self.window_state_lock().opacity = opacity;
+
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
+                f.set(WindowFlags::TRANSLUCENT, opacity < 1.0)
+            });
+            let (opacity, mode) = {
+                let window_state = window_state.lock().unwrap();
+                (window_state.opacity, window_state.layered_mode)
+            };
+            unsafe { set_layered_attributes(window, opacity, mode) };
+        });
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.window_state_lock().opacity
+    }
+
+    pub fn set_layered_mode(&self, mode: LayeredMode) {
+        self.window_state_lock().layered_mode = mode;
+
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
+                f.set(WindowFlags::LAYERED, mode != LayeredMode::None)
+            });
+            let (opacity, mode) = {
+                let window_state = window_state.lock().unwrap();
+                (window_state.opacity, window_state.layered_mode)
+            };
+            unsafe { set_layered_attributes(window, opacity, mode) };
+        });
+    }
+
+    #[inline]
+    pub fn layered_mode(&self) -> LayeredMode {
+        self.window_state_lock().layered_mode
+    }
+
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         let window = self.window;
@@ -150,6 +206,24 @@ impl Window {
 
     #[inline]
     pub fn request_redraw(&self) {
+        self.window_state_lock().redraw_damage.add_full();
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let size = self.inner_size();
+        self.window_state_lock().redraw_damage.add(rects, size);
+        self.schedule_redraw();
+    }
+
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let size = self.inner_size();
+        self.window_state_lock().redraw_damage.current(size).map(<[_]>::to_vec)
+    }
+
+    fn schedule_redraw(&self) {
         // NOTE: mark that we requested a redraw to handle requests during `WM_PAINT` handling.
         self.window_state.lock().unwrap().redraw_requested = true;
         unsafe {
@@ -158,7 +232,21 @@ impl Window {
     }
 
     #[inline]
-    pub fn pre_present_notify(&self) {}
+    pub fn pre_present_notify(&self) {
+        let mut window_state = self.window_state_lock();
+        window_state.present_generation += 1;
+        if window_state.presentation_feedback {
+            let generation = window_state.present_generation as WPARAM;
+            unsafe { PostMessageW(self.hwnd(), PRESENT_FEEDBACK_MSG_ID.get(), generation, 0) };
+        }
+    }
+
+    #[inline]
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        let mut window_state = self.window_state_lock();
+        window_state.presentation_feedback = enabled;
+        window_state.dwm_missed_frames = None;
+    }
 
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
@@ -232,6 +320,11 @@ impl Window {
             .unwrap()
     }
 
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
@@ -282,6 +375,11 @@ impl Window {
         self.window_state_lock().resize_increments = increments;
     }
 
+    #[inline]
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        self.window_state_lock().aspect_ratio = aspect_ratio;
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         let window = self.window;
@@ -331,6 +429,11 @@ impl Window {
         buttons
     }
 
+    #[inline]
+    pub fn supported_buttons(&self) -> WindowButtons {
+        WindowButtons::all()
+    }
+
     /// Returns the `hwnd` of this window.
     #[inline]
     pub fn hwnd(&self) -> HWND {
@@ -401,29 +504,25 @@ impl Window {
 
     #[inline]
     pub fn set_cursor(&self, cursor: Cursor) {
-        match cursor {
-            Cursor::Icon(icon) => {
-                self.window_state_lock().mouse.selected_cursor = SelectedCursor::Named(icon);
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    let cursor = LoadCursorW(0, util::to_windows_cursor(icon));
-                    SetCursor(cursor);
-                });
-            },
-            Cursor::Custom(cursor) => {
-                let new_cursor = match cursor.inner {
-                    WinCursor::Cursor(cursor) => cursor,
-                    WinCursor::Failed => {
-                        warn!("Requested to apply failed cursor");
-                        return;
-                    },
-                };
-                self.window_state_lock().mouse.selected_cursor =
-                    SelectedCursor::Custom(new_cursor.clone());
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    SetCursor(new_cursor.as_raw_handle());
-                });
+        let selected_cursor = match cursor {
+            Cursor::Icon(icon) => SelectedCursor::Named(icon),
+            Cursor::Custom(cursor) => match cursor.inner {
+                WinCursor::Cursor(cursor) => SelectedCursor::Custom(cursor),
+                WinCursor::Animation(animation) => SelectedCursor::Animation(animation, 0),
+                WinCursor::Failed => {
+                    warn!("Requested to apply failed cursor");
+                    return;
+                },
             },
-        }
+        };
+        self.window_state_lock().mouse.selected_cursor = selected_cursor.clone();
+
+        let window = self.hwnd();
+        let window_state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            SetCursor(selected_cursor.as_raw_handle());
+            window_state.lock().unwrap().mouse.update_cursor_animation(window);
+        });
     }
 
     #[inline]
@@ -524,6 +623,24 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn set_transient_parent(&self, parent: Option<&Window>) -> Result<(), ExternalError> {
+        let owner = parent.map_or(0, |parent| parent.hwnd());
+
+        // Walk the owners of the new owner to reject cycles.
+        let mut ancestor = owner;
+        while ancestor != 0 {
+            if ancestor == self.hwnd() {
+                return Err(ExternalError::Ignored);
+            }
+            ancestor = unsafe { GetWindow(ancestor, GW_OWNER) };
+        }
+
+        // Despite its name, `GWLP_HWNDPARENT` sets the owner of a top-level window.
+        unsafe { super::set_window_long(self.hwnd(), GWLP_HWNDPARENT, owner) };
+        Ok(())
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         unsafe {
@@ -551,6 +668,23 @@ impl Window {
         Ok(())
     }
 
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        if !drag_source::is_button_held() {
+            return Err(ExternalError::Ignored);
+        }
+
+        // `DoDragDrop` blocks until the drag ends, so it's started from the event loop instead.
+        let payload = Box::into_raw(Box::new(payload));
+        unsafe {
+            if PostMessageW(self.hwnd(), START_DRAG_MSG_ID.get(), payload as WPARAM, 0) == 0 {
+                drop(Box::from_raw(payload));
+                return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+            }
+        }
+
+        Ok(())
+    }
+
     unsafe fn handle_showing_window_menu(&self, position: Position) {
         unsafe {
             let point = {
@@ -918,8 +1052,14 @@ impl Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            let scale_factor = state.lock().unwrap().scale_factor;
-            ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            let (allowed, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                state.ime_cursor_area = Some((spot, size));
+                (state.ime_allowed, state.scale_factor)
+            };
+            if allowed {
+                ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            }
         });
     }
 
@@ -928,14 +1068,27 @@ impl Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            state.lock().unwrap().ime_allowed = allowed;
+            let (cursor_area, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                state.ime_allowed = allowed;
+                (state.ime_cursor_area, state.scale_factor)
+            };
             ImeContext::set_ime_allowed(window, allowed);
+            if let (true, Some((spot, size))) = (allowed, cursor_area) {
+                ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            }
         })
     }
 
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        unsafe { ImeContext::system_has_ime() }
+            .then_some(ImeCapabilities::CURSOR_AREA | ImeCapabilities::PREEDIT)
+    }
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
@@ -963,6 +1116,29 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        self.thread_executor.execute_in_thread(move || {
+            let previous = mem::replace(&mut window_state.lock().unwrap().progress, state);
+            unsafe { set_taskbar_progress(window, previous, state) };
+        });
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        self.thread_executor.execute_in_thread(move || {
+            let previous = mem::replace(&mut window_state.lock().unwrap().badge_count, count);
+            if previous != count {
+                unsafe { set_taskbar_badge(window, count) };
+            }
+        });
+    }
+
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
         try_theme(self.window, theme);
@@ -1148,12 +1324,16 @@ impl<'a> InitData<'a> {
         let current_theme = try_theme(window, self.attributes.preferred_theme);
 
         let window_state = {
-            let window_state = WindowState::new(
+            let mut window_state = WindowState::new(
                 &self.attributes,
                 scale_factor,
                 current_theme,
                 self.attributes.preferred_theme,
             );
+            window_state.current_monitor =
+                unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST) };
+            window_state.color_properties =
+                MonitorHandle::new(window_state.current_monitor).color_properties();
             let window_state = Arc::new(Mutex::new(window_state));
             WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
                 *f = self.window_flags
@@ -1331,12 +1511,18 @@ unsafe fn init(
     window_flags.set(WindowFlags::CLOSABLE, true);
     window_flags.set(WindowFlags::CLIP_CHILDREN, attributes.platform_specific.clip_children);
 
-    let mut fallback_parent = || match attributes.platform_specific.owner {
-        Some(parent) => {
+    // Owned windows aren't shown on the taskbar, like dialogs.
+    let transient_parent = attributes
+        .transient_parent
+        .map(|parent| HWND::from(parent.0))
+        .filter(|&parent| unsafe { IsWindow(parent) } != false.into());
+    let mut fallback_parent = || match (attributes.platform_specific.owner, transient_parent) {
+        (Some(parent), _) => {
             window_flags.set(WindowFlags::POPUP, true);
             Some(parent)
         },
-        None => {
+        (None, Some(parent)) => Some(parent),
+        (None, None) => {
             window_flags.set(WindowFlags::ON_TASKBAR, true);
             None
         },
@@ -1447,6 +1633,7 @@ thread_local! {
 
     static TASKBAR_LIST: Cell<*mut ITaskbarList> = const { Cell::new(ptr::null_mut()) };
     static TASKBAR_LIST2: Cell<*mut ITaskbarList2> = const { Cell::new(ptr::null_mut()) };
+    static TASKBAR_LIST3: Cell<*mut ITaskbarList3> = const { Cell::new(ptr::null_mut()) };
 }
 
 pub fn com_initialized() {
@@ -1535,6 +1722,118 @@ pub(crate) unsafe fn set_skip_taskbar(hwnd: HWND, skip: bool) {
     });
 }
 
+/// Update the progress shown on the taskbar button of the window from `previous` to `state`.
+///
+/// The progress state is only changed when it differs from the previous one, since setting it
+/// again resets the indicator and makes it flicker.
+pub(crate) unsafe fn set_taskbar_progress(
+    hwnd: HWND,
+    previous: ProgressState,
+    state: ProgressState,
+) {
+    const PROGRESS_TOTAL: u64 = 10_000;
+
+    if previous == state {
+        return;
+    }
+
+    with_taskbar_list3(|task_bar_list3| {
+        let flags = progress_flags(state);
+        if progress_flags(previous) != flags {
+            let set_progress_state = unsafe { (*(*task_bar_list3).lpVtbl).SetProgressState };
+            unsafe { set_progress_state(task_bar_list3, hwnd, flags) };
+        }
+
+        if let Some(value) = state.value() {
+            let completed = (value * PROGRESS_TOTAL as f64).round() as u64;
+            let set_progress_value = unsafe { (*(*task_bar_list3).lpVtbl).SetProgressValue };
+            unsafe { set_progress_value(task_bar_list3, hwnd, completed, PROGRESS_TOTAL) };
+        }
+    })
+}
+
+/// Show the count as an overlay icon on the taskbar button of the window, or remove it.
+pub(crate) unsafe fn set_taskbar_badge(hwnd: HWND, count: Option<u64>) {
+    let icon = match count.map(badge::badge_icon) {
+        Some(Ok(icon)) => Some(icon),
+        Some(Err(err)) => {
+            tracing::warn!("Failed to create the badge icon: {err}");
+            return;
+        },
+        None => None,
+    };
+    let description = util::encode_wide(count.map(|count| count.to_string()).unwrap_or_default());
+
+    with_taskbar_list3(|task_bar_list3| {
+        let set_overlay_icon = unsafe { (*(*task_bar_list3).lpVtbl).SetOverlayIcon };
+        // The taskbar copies the icon, so it can be destroyed afterwards.
+        let icon = icon.as_ref().map_or(0, |icon| icon.as_raw_handle());
+        unsafe { set_overlay_icon(task_bar_list3, hwnd, icon, description.as_ptr()) };
+    })
+}
+
+fn with_taskbar_list3(f: impl FnOnce(*mut ITaskbarList3)) {
+    com_initialized();
+    TASKBAR_LIST3.with(|task_bar_list3_ptr| {
+        let mut task_bar_list3 = task_bar_list3_ptr.get();
+
+        if task_bar_list3.is_null() {
+            let hr = unsafe {
+                CoCreateInstance(
+                    &CLSID_TaskbarList,
+                    ptr::null_mut(),
+                    CLSCTX_ALL,
+                    &IID_ITaskbarList3,
+                    &mut task_bar_list3 as *mut _ as *mut _,
+                )
+            };
+            if hr != S_OK {
+                // In visual studio retrieving the taskbar list fails
+                return;
+            }
+
+            let hr_init = unsafe { (*(*task_bar_list3).lpVtbl).parent.parent.HrInit };
+            if unsafe { hr_init(task_bar_list3.cast()) } != S_OK {
+                // In some old windows, the taskbar object could not be created, we just ignore it
+                return;
+            }
+            task_bar_list3_ptr.set(task_bar_list3)
+        }
+
+        f(task_bar_list3_ptr.get())
+    })
+}
+
+/// Apply the opacity and the layered mode to the layered window.
+///
+/// This fails if the window isn't layered, in which case it's opaque anyway.
+unsafe fn set_layered_attributes(window: HWND, opacity: f32, mode: LayeredMode) {
+    let mut alpha = (opacity * 255.0).round() as u8;
+    let mut key = 0;
+    let mut flags = LWA_ALPHA;
+    match mode {
+        LayeredMode::None => (),
+        LayeredMode::ColorKey(color) => {
+            key = color.0;
+            flags |= LWA_COLORKEY;
+        },
+        LayeredMode::Alpha(mode_alpha) => {
+            alpha = (u16::from(alpha) * u16::from(mode_alpha) / 255) as u8;
+        },
+    }
+    unsafe { SetLayeredWindowAttributes(window, key, alpha, flags) };
+}
+
+fn progress_flags(state: ProgressState) -> TBPFLAG {
+    match state {
+        ProgressState::None => TBPF_NOPROGRESS,
+        ProgressState::Indeterminate => TBPF_INDETERMINATE,
+        ProgressState::Value(_) => TBPF_NORMAL,
+        ProgressState::Error(_) => TBPF_ERROR,
+        ProgressState::Paused(_) => TBPF_PAUSED,
+    }
+}
+
 unsafe fn force_window_active(handle: HWND) {
     // In some situation, calling SetForegroundWindow could not bring up the window,
     // This is a little hack which can "steal" the foreground window permission
@@ -1,23 +1,27 @@
-use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::event::Modifiers;
 use crate::icon::Icon;
-use crate::keyboard::ModifiersState;
+use crate::monitor::ColorProperties;
+use crate::platform::windows::LayeredMode;
 use crate::platform_impl::platform::{event_loop, util, Fullscreen, SelectedCursor};
-use crate::window::{Theme, WindowAttributes};
+use crate::utils::{ClickCounter, RedrawDamage};
+use crate::window::{AspectRatio, ProgressState, Theme, WindowAttributes};
 use bitflags::bitflags;
 use std::io;
 use std::sync::MutexGuard;
 use windows_sys::Win32::Foundation::{HWND, RECT};
-use windows_sys::Win32::Graphics::Gdi::InvalidateRgn;
+use windows_sys::Win32::Graphics::Gdi::{InvalidateRgn, HMONITOR};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    AdjustWindowRectEx, EnableMenuItem, GetMenu, GetSystemMenu, GetWindowLongW, SendMessageW,
-    SetWindowLongW, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE, HWND_BOTTOM, HWND_NOTOPMOST,
-    HWND_TOPMOST, MF_BYCOMMAND, MF_DISABLED, MF_ENABLED, SC_CLOSE, SWP_ASYNCWINDOWPOS,
-    SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOREPOSITION, SWP_NOSIZE, SWP_NOZORDER,
-    SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SW_SHOW, SW_SHOWNOACTIVATE, WINDOWPLACEMENT,
-    WINDOW_EX_STYLE, WINDOW_STYLE, WS_BORDER, WS_CAPTION, WS_CHILD, WS_CLIPCHILDREN,
-    WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_LAYERED, WS_EX_NOREDIRECTIONBITMAP,
-    WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE, WS_MAXIMIZE, WS_MAXIMIZEBOX, WS_MINIMIZE,
-    WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
+    AdjustWindowRectEx, EnableMenuItem, GetMenu, GetSystemMenu, GetWindowLongW, KillTimer,
+    SendMessageW, SetTimer, SetWindowLongW, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE,
+    HWND_BOTTOM, HWND_NOTOPMOST, HWND_TOPMOST, MF_BYCOMMAND, MF_DISABLED, MF_ENABLED, SC_CLOSE,
+    SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOREPOSITION, SWP_NOSIZE,
+    SWP_NOZORDER, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SW_SHOW, SW_SHOWNOACTIVATE,
+    WINDOWPLACEMENT, WINDOW_EX_STYLE, WINDOW_STYLE, WS_BORDER, WS_CAPTION, WS_CHILD,
+    WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_LAYERED,
+    WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE, WS_MAXIMIZE,
+    WS_MAXIMIZEBOX, WS_MINIMIZE, WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX,
+    WS_SYSMENU, WS_VISIBLE,
 };
 
 /// Contains information about states and the window that the callback is going to use.
@@ -29,6 +33,7 @@ pub(crate) struct WindowState {
     pub max_size: Option<Size>,
 
     pub resize_increments: Option<Size>,
+    pub aspect_ratio: Option<AspectRatio>,
 
     pub window_icon: Option<Icon>,
     pub taskbar_icon: Option<Icon>,
@@ -36,7 +41,7 @@ pub(crate) struct WindowState {
     pub saved_window: Option<SavedWindow>,
     pub scale_factor: f64,
 
-    pub modifiers_state: ModifiersState,
+    pub modifiers: Modifiers,
     pub fullscreen: Option<Fullscreen>,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
@@ -45,6 +50,9 @@ pub(crate) struct WindowState {
 
     pub ime_state: ImeState,
     pub ime_allowed: bool,
+    // The IME cursor area is kept while IME isn't allowed, since there is no input context to
+    // apply it to, and applied when IME gets allowed.
+    pub ime_cursor_area: Option<(Position, Size)>,
 
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
@@ -52,10 +60,28 @@ pub(crate) struct WindowState {
 
     // Flag whether redraw was requested.
     pub redraw_requested: bool,
+    pub redraw_damage: RedrawDamage,
 
     pub dragging: bool,
 
     pub skip_taskbar: bool,
+
+    pub opacity: f32,
+    pub layered_mode: LayeredMode,
+
+    pub progress: ProgressState,
+    pub badge_count: Option<u64>,
+
+    /// Used by `WM_WINDOWPOSCHANGED` and `WM_DPICHANGED` to emit `MonitorChanged`.
+    pub current_monitor: HMONITOR,
+    /// Used by `WM_DISPLAYCHANGE` to emit `ColorPropertiesChanged`.
+    pub color_properties: Option<ColorProperties>,
+
+    /// The number of `pre_present_notify` calls.
+    pub present_generation: u64,
+    pub presentation_feedback: bool,
+    /// The frames the DWM missed or dropped, when the last `FramePresented` was emitted.
+    pub dwm_missed_frames: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -69,6 +95,7 @@ pub struct MouseProperties {
     pub capture_count: u32,
     cursor_flags: CursorFlags,
     pub last_position: Option<PhysicalPosition<f64>>,
+    pub click_counter: ClickCounter,
 }
 
 bitflags! {
@@ -123,6 +150,12 @@ bitflags! {
 
         const CLIP_CHILDREN = 1 << 22;
 
+        /// The window has an opacity below `1.0`, which requires `WS_EX_LAYERED`.
+        const TRANSLUCENT = 1 << 23;
+
+        /// The window has a `LayeredMode`, which requires `WS_EX_LAYERED`.
+        const LAYERED = 1 << 24;
+
         const EXCLUSIVE_FULLSCREEN_OR_MASK = WindowFlags::ALWAYS_ON_TOP.bits();
     }
 }
@@ -147,12 +180,14 @@ impl WindowState {
                 capture_count: 0,
                 cursor_flags: CursorFlags::empty(),
                 last_position: None,
+                click_counter: ClickCounter::default(),
             },
 
             min_size: attributes.min_inner_size,
             max_size: attributes.max_inner_size,
 
             resize_increments: attributes.resize_increments,
+            aspect_ratio: attributes.aspect_ratio,
 
             window_icon: attributes.window_icon.clone(),
             taskbar_icon: None,
@@ -160,7 +195,7 @@ impl WindowState {
             saved_window: None,
             scale_factor,
 
-            modifiers_state: ModifiersState::default(),
+            modifiers: Modifiers::default(),
             fullscreen: None,
             current_theme,
             preferred_theme,
@@ -168,14 +203,29 @@ impl WindowState {
 
             ime_state: ImeState::Disabled,
             ime_allowed: false,
+            ime_cursor_area: None,
 
             is_active: false,
             is_focused: false,
             redraw_requested: false,
+            redraw_damage: RedrawDamage::default(),
 
             dragging: false,
 
             skip_taskbar: false,
+
+            opacity: 1.0,
+            layered_mode: LayeredMode::None,
+
+            progress: ProgressState::None,
+            badge_count: None,
+
+            current_monitor: 0,
+            color_properties: None,
+
+            present_generation: 0,
+            presentation_feedback: false,
+            dwm_missed_frames: None,
         }
     }
 
@@ -242,6 +292,24 @@ impl MouseProperties {
 
         Ok(())
     }
+
+    /// Schedules the next frame of an animated cursor while the cursor is inside the window, and
+    /// stops the animation otherwise, see [`event_loop::CURSOR_ANIMATION_TIMER_ID`].
+    ///
+    /// Must be called on the thread of the window.
+    pub fn update_cursor_animation(&self, window: HWND) {
+        match &self.selected_cursor {
+            SelectedCursor::Animation(animation, frame)
+                if self.cursor_flags.contains(CursorFlags::IN_WINDOW) =>
+            {
+                let timeout = event_loop::dur2timeout(animation.frames[*frame].1);
+                unsafe { SetTimer(window, event_loop::CURSOR_ANIMATION_TIMER_ID, timeout, None) };
+            },
+            _ => unsafe {
+                KillTimer(window, event_loop::CURSOR_ANIMATION_TIMER_ID);
+            },
+        }
+    }
 }
 
 impl WindowFlags {
@@ -302,6 +370,9 @@ impl WindowFlags {
         if self.contains(WindowFlags::CLIP_CHILDREN) {
             style |= WS_CLIPCHILDREN;
         }
+        if self.intersects(WindowFlags::TRANSLUCENT | WindowFlags::LAYERED) {
+            style_ex |= WS_EX_LAYERED;
+        }
 
         if self.intersects(
             WindowFlags::MARKER_EXCLUSIVE_FULLSCREEN | WindowFlags::MARKER_BORDERLESS_FULLSCREEN,
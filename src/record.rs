@@ -0,0 +1,622 @@
+//! Recording the events an application receives, and replaying them.
+//!
+//! A [`Recorder`] wraps an [`ApplicationHandler`] and passes the events it receives to a sink as
+//! [`Record`]s, stamped with the time since the recording started. The sink serializes them in the
+//! format of the application's choice, usually to a [`Write`] with [`Recorder::to_writer`]. A
+//! [`Replayer`] wraps another handler and dispatches the deserialized records to it again, with the
+//! original timing or faster, which helps reproducing input bugs.
+//!
+//! For example, to record the events as JSON lines with `serde_json`:
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use std::io::{BufRead, BufReader, BufWriter, Write};
+//! # use winit::application::ApplicationHandler;
+//! # use winit::event::WindowEvent;
+//! # use winit::event_loop::{ActiveEventLoop, EventLoop};
+//! # use winit::record::{Record, Recorder, Replayer};
+//! # use winit::window::WindowId;
+//! # struct App;
+//! # impl ApplicationHandler for App {
+//! #     fn can_create_surfaces(&mut self, _: &ActiveEventLoop) {}
+//! #     fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+//! # }
+//! let writer = BufWriter::new(File::create("events.jsonl")?);
+//! let mut recorder = Recorder::to_writer(App, writer, |writer, record| {
+//!     serde_json::to_writer(&mut *writer, record)?;
+//!     writer.write_all(b"\n")
+//! });
+//! EventLoop::new()?.run_app(&mut recorder)?;
+//!
+//! let records = BufReader::new(File::open("events.jsonl")?)
+//!     .lines()
+//!     .map(|line| Ok(serde_json::from_str(&line?)?))
+//!     .collect::<Result<Vec<Record>, Box<dyn std::error::Error>>>()?;
+//! EventLoop::new()?.run_app(&mut Replayer::new(App, records))?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The events are serialized as described in [`WindowEvent`]: they lose the handles they hold,
+//! like device IDs and the [`InnerSizeWriter`] of [`WindowEvent::ScaleFactorChanged`], and
+//! [`WindowEvent::MonitorChanged`] isn't recorded. Neither are the user events and the monitor
+//! notifications, as they can't be serialized.
+//!
+//! [`InnerSizeWriter`]: crate::event::InnerSizeWriter
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Write};
+#[cfg(not(web_platform))]
+use std::time::Instant;
+#[cfg(web_platform)]
+use web_time::Instant;
+
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::time::Duration;
+
+use crate::application::ApplicationHandler;
+use crate::event::{DeviceEvent, DeviceId, SessionEvent, ShortcutItem, StartCause, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, ControlFlow};
+use crate::monitor::MonitorHandle;
+use crate::window::WindowId;
+
+/// An event received by the application, at some time of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// The time since the recording started.
+    pub time: Duration,
+    /// The event.
+    pub event: RecordedEvent,
+}
+
+/// The events of [`ApplicationHandler`] that can be recorded.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    /// See [`ApplicationHandler::new_events`].
+    NewEvents(StartCause),
+    /// See [`ApplicationHandler::resumed`].
+    Resumed,
+    /// See [`ApplicationHandler::can_create_surfaces`].
+    CanCreateSurfaces,
+    /// See [`ApplicationHandler::proxy_wake_up`].
+    ProxyWakeUp,
+    /// See [`ApplicationHandler::window_event`].
+    WindowEvent { window_id: WindowId, event: WindowEvent },
+    /// See [`ApplicationHandler::device_event`].
+    DeviceEvent { device_id: DeviceId, event: DeviceEvent },
+    /// See [`ApplicationHandler::about_to_wait`].
+    AboutToWait,
+    /// See [`ApplicationHandler::suspended`].
+    Suspended,
+    /// See [`ApplicationHandler::destroy_surfaces`].
+    DestroySurfaces,
+    /// See [`ApplicationHandler::exiting`].
+    Exiting,
+    /// See [`ApplicationHandler::memory_warning`].
+    MemoryWarning,
+    /// See [`ApplicationHandler::accessibility_settings_changed`].
+    AccessibilitySettingsChanged,
+    /// See [`ApplicationHandler::power_state_changed`].
+    PowerStateChanged,
+    /// See [`ApplicationHandler::session_event`].
+    SessionEvent(SessionEvent),
+    /// See [`ApplicationHandler::keyboard_layout_changed`].
+    KeyboardLayoutChanged,
+    /// See [`ApplicationHandler::urls_opened`].
+    UrlsOpened(Vec<String>),
+    /// See [`ApplicationHandler::shortcut_item_activated`].
+    ShortcutItemActivated(ShortcutItem),
+}
+
+/// Records the events received by an application.
+///
+/// The events are passed on to the application, and to the sink as they come. The sink is a
+/// closure that serializes and writes a record, and should flush what it buffered once it gets
+/// [`RecordedEvent::Exiting`], like the one of [`Recorder::to_writer`] does. The recording stops at
+/// the first error, which is kept in [`Recorder::error`].
+#[derive(Debug)]
+pub struct Recorder<A, S> {
+    app: A,
+    sink: S,
+    start: Instant,
+    error: Option<io::Error>,
+}
+
+impl<A, S: FnMut(&Record) -> io::Result<()>> Recorder<A, S> {
+    /// Records the events received by `app` to `sink`, starting now.
+    pub fn new(app: A, sink: S) -> Self {
+        Self { app, sink, start: Instant::now(), error: None }
+    }
+
+    /// The recorded application.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The recorded application.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// The error that stopped the recording, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the application and the sink.
+    pub fn into_inner(self) -> (A, S) {
+        (self.app, self.sink)
+    }
+
+    fn record(&mut self, event: RecordedEvent) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let record = Record { time: self.start.elapsed(), event };
+        if let Err(error) = (self.sink)(&record) {
+            tracing::error!("Failed to record an event, stopping the recording: {error}");
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<'a, A> Recorder<A, Box<dyn FnMut(&Record) -> io::Result<()> + 'a>> {
+    /// Records the events received by `app` to `writer`, starting now.
+    ///
+    /// `encode` writes a record to the writer, in the format of the application's choice. The
+    /// writer is flushed once the recorder gets [`RecordedEvent::Exiting`].
+    pub fn to_writer<W: Write + 'a>(
+        app: A,
+        mut writer: W,
+        mut encode: impl FnMut(&mut W, &Record) -> io::Result<()> + 'a,
+    ) -> Self {
+        Self::new(
+            app,
+            Box::new(move |record: &Record| {
+                encode(&mut writer, record)?;
+                if record.event == RecordedEvent::Exiting {
+                    writer.flush()?;
+                }
+                Ok(())
+            }),
+        )
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A, S> ApplicationHandler for Recorder<A, S>
+where
+    A: ApplicationHandler,
+    S: FnMut(&Record) -> io::Result<()>,
+{
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        self.record(RecordedEvent::NewEvents(cause));
+        self.app.new_events(event_loop, cause);
+    }
+
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::Resumed);
+        self.app.resumed(event_loop);
+    }
+
+    fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::CanCreateSurfaces);
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::ProxyWakeUp);
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        self.app.user_event(event_loop, event);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if !matches!(event, WindowEvent::MonitorChanged { .. }) {
+            self.record(RecordedEvent::WindowEvent { window_id, event: event.clone() });
+        }
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        self.record(RecordedEvent::DeviceEvent { device_id, event: event.clone() });
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::AboutToWait);
+        self.app.about_to_wait(event_loop);
+    }
+
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::Suspended);
+        self.app.suspended(event_loop);
+    }
+
+    fn destroy_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::DestroySurfaces);
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::Exiting);
+        self.app.exiting(event_loop);
+    }
+
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::MemoryWarning);
+        self.app.memory_warning(event_loop);
+    }
+
+    fn accessibility_settings_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::AccessibilitySettingsChanged);
+        self.app.accessibility_settings_changed(event_loop);
+    }
+
+    fn power_state_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::PowerStateChanged);
+        self.app.power_state_changed(event_loop);
+    }
+
+    fn session_event(&mut self, event_loop: &ActiveEventLoop, event: SessionEvent) {
+        self.record(RecordedEvent::SessionEvent(event));
+        self.app.session_event(event_loop, event);
+    }
+
+    fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.record(RecordedEvent::KeyboardLayoutChanged);
+        self.app.keyboard_layout_changed(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    fn urls_opened(&mut self, event_loop: &ActiveEventLoop, urls: Vec<String>) {
+        self.record(RecordedEvent::UrlsOpened(urls.clone()));
+        self.app.urls_opened(event_loop, urls);
+    }
+
+    fn shortcut_item_activated(&mut self, event_loop: &ActiveEventLoop, item: ShortcutItem) {
+        self.record(RecordedEvent::ShortcutItemActivated(item.clone()));
+        self.app.shortcut_item_activated(event_loop, item);
+    }
+}
+
+/// Replays recorded events to an application.
+///
+/// The replayer runs in an event loop like any application. The lifecycle of the wrapped
+/// application follows that event loop, so [`RecordedEvent::NewEvents`], the surface and
+/// suspension events and [`RecordedEvent::AboutToWait`] aren't replayed, while the live ones are
+/// passed on. The other recorded events replace the live ones instead: of the live window events,
+/// only [`WindowEvent::RedrawRequested`] and [`WindowEvent::Destroyed`] are passed on, and they're
+/// the recorded window events that aren't replayed.
+///
+/// The recorded windows are matched with the windows of the application in order: the window of
+/// the first recorded window event is replayed as the first window the application receives a
+/// live window event for, and so on. A window can also be matched with
+/// [`Replayer::match_window`], before it receives any live event. The recorded events of a window
+/// wait for it to be matched, without holding back the other events.
+///
+/// The events are replayed at their recorded time, counted from the start of the event loop, by
+/// setting [`ControlFlow::WaitUntil`] if the application would wait longer.
+#[derive(Debug)]
+pub struct Replayer<A> {
+    app: A,
+    records: VecDeque<Record>,
+    speed: f64,
+    exit_when_finished: bool,
+    start: Option<Instant>,
+
+    /// The live windows of the recorded ones.
+    windows: HashMap<WindowId, WindowId>,
+    /// The recorded and live windows that aren't matched yet, in the order they were first seen.
+    unmatched_recorded: VecDeque<WindowId>,
+    unmatched_live: VecDeque<WindowId>,
+
+    /// The control flow set by the application, and the one set by the replayer.
+    app_control_flow: ControlFlow,
+    control_flow: Option<ControlFlow>,
+}
+
+impl<A> Replayer<A> {
+    /// Replays `records` to `app`.
+    pub fn new(app: A, records: Vec<Record>) -> Self {
+        Self {
+            app,
+            records: records.into(),
+            speed: 1.0,
+            exit_when_finished: false,
+            start: None,
+            windows: HashMap::new(),
+            unmatched_recorded: VecDeque::new(),
+            unmatched_live: VecDeque::new(),
+            app_control_flow: ControlFlow::default(),
+            control_flow: None,
+        }
+    }
+
+    /// Replays the events `speed` times faster than recorded, or as fast as possible with
+    /// [`f64::INFINITY`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `speed` isn't positive.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        assert!(speed > 0.0, "the replay speed must be positive");
+        self.speed = speed;
+        self
+    }
+
+    /// Exits the event loop once all the events were replayed.
+    pub fn with_exit_when_finished(mut self, exit_when_finished: bool) -> Self {
+        self.exit_when_finished = exit_when_finished;
+        self
+    }
+
+    /// Replays the events of the `recorded` window to the `live` one.
+    ///
+    /// The windows are otherwise matched in the order they're seen, so this is for the windows
+    /// that the events are due for before they receive a live event.
+    pub fn match_window(&mut self, recorded: WindowId, live: WindowId) {
+        self.unmatched_recorded.retain(|window| *window != recorded);
+        self.unmatched_live.retain(|window| *window != live);
+        self.windows.insert(recorded, live);
+    }
+
+    /// Whether all the events were replayed.
+    pub fn is_finished(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The replayed application.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The replayed application.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// Returns the application.
+    pub fn into_inner(self) -> A {
+        self.app
+    }
+
+    /// When the record is due, `None` if it's never replayed.
+    fn due_time(&self, record: &Record) -> Option<Instant> {
+        let offset = record.time.as_secs_f64() / self.speed;
+        self.start?.checked_add(Duration::try_from_secs_f64(offset).unwrap_or(Duration::ZERO))
+    }
+
+    /// Returns the live window matching the recorded one, if there is one yet.
+    fn live_window(&mut self, recorded: WindowId) -> Option<WindowId> {
+        if let Some(live) = self.windows.get(&recorded) {
+            return Some(*live);
+        }
+        if !self.unmatched_recorded.contains(&recorded) {
+            self.unmatched_recorded.push_back(recorded);
+        }
+        self.match_windows();
+        self.windows.get(&recorded).copied()
+    }
+
+    /// Matches the windows that weren't yet, in the order they were seen.
+    fn match_windows(&mut self) {
+        while !self.unmatched_recorded.is_empty() && !self.unmatched_live.is_empty() {
+            let recorded = self.unmatched_recorded.pop_front().unwrap();
+            let live = self.unmatched_live.pop_front().unwrap();
+            self.windows.insert(recorded, live);
+        }
+    }
+
+    /// Whether the record at `index` waits for its window to be matched.
+    fn is_waiting(&mut self, index: usize) -> bool {
+        match self.records[index].event {
+            RecordedEvent::WindowEvent { window_id, .. } => self.live_window(window_id).is_none(),
+            _ => false,
+        }
+    }
+
+    fn replay_due_events(&mut self, event_loop: &ActiveEventLoop)
+    where
+        A: ApplicationHandler,
+    {
+        let now = Instant::now();
+        let mut index = 0;
+        while let Some(record) = self.records.get(index) {
+            if self.due_time(record).map_or(true, |due| due > now) {
+                break;
+            }
+            if self.is_waiting(index) {
+                index += 1;
+                continue;
+            }
+
+            match self.records.remove(index).unwrap().event {
+                RecordedEvent::WindowEvent { window_id, event } => {
+                    if !matches!(event, WindowEvent::RedrawRequested | WindowEvent::Destroyed) {
+                        let live = self.windows[&window_id];
+                        self.app.window_event(event_loop, live, event);
+                    }
+                },
+                RecordedEvent::DeviceEvent { device_id, event } => {
+                    self.app.device_event(event_loop, device_id, event);
+                },
+                RecordedEvent::MemoryWarning => self.app.memory_warning(event_loop),
+                RecordedEvent::AccessibilitySettingsChanged => {
+                    self.app.accessibility_settings_changed(event_loop);
+                },
+                RecordedEvent::PowerStateChanged => self.app.power_state_changed(event_loop),
+                RecordedEvent::SessionEvent(event) => self.app.session_event(event_loop, event),
+                RecordedEvent::KeyboardLayoutChanged => {
+                    self.app.keyboard_layout_changed(event_loop);
+                },
+                RecordedEvent::UrlsOpened(urls) => self.app.urls_opened(event_loop, urls),
+                RecordedEvent::ShortcutItemActivated(item) => {
+                    self.app.shortcut_item_activated(event_loop, item);
+                },
+                // The lifecycle follows the live event loop.
+                RecordedEvent::NewEvents(_)
+                | RecordedEvent::Resumed
+                | RecordedEvent::CanCreateSurfaces
+                | RecordedEvent::ProxyWakeUp
+                | RecordedEvent::AboutToWait
+                | RecordedEvent::Suspended
+                | RecordedEvent::DestroySurfaces
+                | RecordedEvent::Exiting => (),
+            }
+        }
+    }
+
+    /// The time to wake up at to replay the next event that isn't waiting for its window.
+    fn next_wake_up(&mut self) -> Option<Instant> {
+        let index = (0..self.records.len()).find(|index| !self.is_waiting(*index))?;
+        self.due_time(&self.records[index])
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for Replayer<A> {
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        if self.start.is_none() {
+            self.start = Some(Instant::now());
+        }
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+        self.app.user_event(event_loop, event);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if !self.windows.values().any(|live| *live == window_id)
+            && !self.unmatched_live.contains(&window_id)
+        {
+            self.unmatched_live.push_back(window_id);
+            self.match_windows();
+        }
+        if matches!(event, WindowEvent::RedrawRequested | WindowEvent::Destroyed) {
+            self.app.window_event(event_loop, window_id, event);
+        }
+    }
+
+    fn device_event(&mut self, _: &ActiveEventLoop, _: DeviceId, _: DeviceEvent) {}
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        // Keep the control flow of the application, unless it changed it since.
+        if Some(event_loop.control_flow()) == self.control_flow {
+            event_loop.set_control_flow(self.app_control_flow);
+        }
+
+        self.replay_due_events(event_loop);
+        self.app.about_to_wait(event_loop);
+        self.app_control_flow = event_loop.control_flow();
+
+        if self.exit_when_finished && self.is_finished() {
+            event_loop.exit();
+        }
+
+        // Wake up for the next event if the application would wait longer.
+        let control_flow = match (self.app_control_flow, self.next_wake_up()) {
+            (ControlFlow::Wait, Some(wake_up)) => ControlFlow::WaitUntil(wake_up),
+            (ControlFlow::WaitUntil(deadline), Some(wake_up)) => {
+                ControlFlow::WaitUntil(deadline.min(wake_up))
+            },
+            (control_flow, _) => control_flow,
+        };
+        event_loop.set_control_flow(control_flow);
+        self.control_flow = Some(control_flow);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    fn memory_warning(&mut self, _: &ActiveEventLoop) {}
+
+    fn accessibility_settings_changed(&mut self, _: &ActiveEventLoop) {}
+
+    fn power_state_changed(&mut self, _: &ActiveEventLoop) {}
+
+    fn session_event(&mut self, _: &ActiveEventLoop, _: SessionEvent) {}
+
+    fn keyboard_layout_changed(&mut self, _: &ActiveEventLoop) {}
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_disconnected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_video_mode_changed(&mut self, event_loop: &ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_video_mode_changed(event_loop, monitor);
+    }
+
+    fn urls_opened(&mut self, _: &ActiveEventLoop, _: Vec<String>) {}
+
+    fn shortcut_item_activated(&mut self, _: &ActiveEventLoop, _: ShortcutItem) {}
+}
@@ -6,6 +6,16 @@
 
 use std::ops::Deref;
 use std::sync::OnceLock;
+use std::time::Duration;
+#[cfg(not(web_platform))]
+use std::time::Instant;
+
+#[cfg(web_platform)]
+use web_time::Instant;
+
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event::MouseButton;
+use crate::window::Rect;
 
 pub(crate) struct Lazy<T> {
     cell: OnceLock<T>,
@@ -26,3 +36,247 @@ impl<T> Deref for Lazy<T> {
         self.cell.get_or_init(self.init)
     }
 }
+
+/// How close in time and space two presses of a mouse button have to be to count as consecutive
+/// clicks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ClickThresholds {
+    /// The longest time between two presses.
+    pub interval: Duration,
+    /// The farthest the cursor can be from the previous press, on each axis, in physical pixels.
+    pub distance: f64,
+}
+
+impl Default for ClickThresholds {
+    /// The default settings of Windows, used where the system has none.
+    fn default() -> Self {
+        Self { interval: Duration::from_millis(500), distance: 2.0 }
+    }
+}
+
+/// Counts the consecutive clicks of the mouse buttons, on the platforms that don't do it.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ClickCounter {
+    last: Option<Click>,
+}
+
+#[derive(Debug, Clone)]
+struct Click {
+    button: MouseButton,
+    time: Instant,
+    position: PhysicalPosition<f64>,
+    count: u32,
+    /// The cursor moved too far away for the next press to be counted with this one.
+    expired: bool,
+}
+
+impl ClickCounter {
+    /// Counts a press of `button`, returning its click count.
+    pub fn press(
+        &mut self,
+        button: MouseButton,
+        position: PhysicalPosition<f64>,
+        time: Instant,
+        thresholds: ClickThresholds,
+    ) -> u32 {
+        let count = match &self.last {
+            Some(last)
+                if !last.expired
+                    && last.button == button
+                    && time.saturating_duration_since(last.time) <= thresholds.interval
+                    && is_near(last.position, position, thresholds.distance) =>
+            {
+                last.count.saturating_add(1)
+            },
+            _ => 1,
+        };
+
+        self.last = Some(Click { button, time, position, count, expired: false });
+        count
+    }
+
+    /// Returns the click count of a release of `button`, which is the one of the press it ends.
+    pub fn release(&self, button: MouseButton) -> u32 {
+        match &self.last {
+            Some(last) if last.button == button => last.count,
+            _ => 1,
+        }
+    }
+
+    /// Restarts the count when the cursor moves too far away from the last press.
+    pub fn moved(&mut self, position: PhysicalPosition<f64>, thresholds: ClickThresholds) {
+        if let Some(last) = &mut self.last {
+            if !is_near(last.position, position, thresholds.distance) {
+                last.expired = true;
+            }
+        }
+    }
+}
+
+fn is_near(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>, distance: f64) -> bool {
+    (a.x - b.x).abs() <= distance && (a.y - b.y).abs() <= distance
+}
+
+/// Accumulates the damage passed to `Window::request_redraw_with_damage()` until the next
+/// `RedrawRequested` of the window.
+///
+/// The backends keep one per window, and must call [`RedrawDamage::deliver`] before delivering
+/// `RedrawRequested` to the application.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct RedrawDamage {
+    /// The damage of the requests since the last `RedrawRequested`, `None` without any.
+    pending: Option<Damage>,
+    /// The damage of the last `RedrawRequested`.
+    current: Damage,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+enum Damage {
+    #[default]
+    Full,
+    /// Non-overlapping rectangles of a window of the given size.
+    Rects { rects: Vec<Rect<u32>>, size: PhysicalSize<u32> },
+}
+
+impl RedrawDamage {
+    /// Damages the whole window, which wins over any rectangles.
+    pub fn add_full(&mut self) {
+        self.pending = Some(Damage::Full);
+    }
+
+    /// Damages the given rectangles of a window of the given size.
+    pub fn add(&mut self, new: &[Rect<u32>], new_size: PhysicalSize<u32>) {
+        match &mut self.pending {
+            Some(Damage::Full) => (),
+            // A resized window is redrawn completely.
+            Some(Damage::Rects { size, .. }) if *size != new_size => self.add_full(),
+            Some(Damage::Rects { rects, .. }) => new.iter().for_each(|rect| merge(rects, *rect)),
+            None => {
+                let mut rects = Vec::with_capacity(new.len());
+                new.iter().for_each(|rect| merge(&mut rects, *rect));
+                self.pending = Some(Damage::Rects { rects, size: new_size });
+            },
+        }
+    }
+
+    /// Called when `RedrawRequested` is delivered, the redraw is complete if it wasn't requested.
+    pub fn deliver(&mut self) {
+        self.current = self.pending.take().unwrap_or_default();
+    }
+
+    /// The damage of the last `RedrawRequested` of a window that now has the given size, `None`
+    /// if the whole window has to be redrawn.
+    pub fn current(&self, current_size: PhysicalSize<u32>) -> Option<&[Rect<u32>]> {
+        match &self.current {
+            Damage::Rects { rects, size } if *size == current_size => Some(rects),
+            _ => None,
+        }
+    }
+}
+
+/// Adds `rect` to the non-overlapping `rects`, replacing the ones it overlaps with their bounds.
+fn merge(rects: &mut Vec<Rect<u32>>, mut rect: Rect<u32>) {
+    if rect.size.width == 0 || rect.size.height == 0 {
+        return;
+    }
+
+    // The bounds can overlap rectangles that the new one didn't, so search from the start.
+    while let Some(index) = rects.iter().position(|other| overlaps(other, &rect)) {
+        rect = bounds(&rects.swap_remove(index), &rect);
+    }
+    rects.push(rect);
+}
+
+fn overlaps(a: &Rect<u32>, b: &Rect<u32>) -> bool {
+    let (a_right, a_bottom) = far_corner(a);
+    let (b_right, b_bottom) = far_corner(b);
+    a.position.x < b_right
+        && b.position.x < a_right
+        && a.position.y < b_bottom
+        && b.position.y < a_bottom
+}
+
+fn bounds(a: &Rect<u32>, b: &Rect<u32>) -> Rect<u32> {
+    let (a_right, a_bottom) = far_corner(a);
+    let (b_right, b_bottom) = far_corner(b);
+    let position =
+        PhysicalPosition::new(a.position.x.min(b.position.x), a.position.y.min(b.position.y));
+    let size =
+        PhysicalSize::new(a_right.max(b_right) - position.x, a_bottom.max(b_bottom) - position.y);
+    Rect::new(position, size)
+}
+
+fn far_corner(rect: &Rect<u32>) -> (u32, u32) {
+    (
+        rect.position.x.saturating_add(rect.size.width),
+        rect.position.y.saturating_add(rect.size.height),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rect<u32> {
+        Rect::new(PhysicalPosition::new(x, y), PhysicalSize::new(width, height))
+    }
+
+    const SIZE: PhysicalSize<u32> = PhysicalSize::new(100, 100);
+
+    #[test]
+    fn damage_merges_overlapping_rects() {
+        let mut damage = RedrawDamage::default();
+        damage.add(&[rect(0, 0, 10, 10), rect(20, 0, 10, 10), rect(5, 5, 10, 10)], SIZE);
+        // Touching rectangles are kept apart.
+        damage.add(&[rect(30, 0, 10, 10), rect(50, 50, 0, 10)], SIZE);
+        damage.deliver();
+        assert_eq!(
+            damage.current(SIZE),
+            Some(&[rect(20, 0, 10, 10), rect(0, 0, 15, 15), rect(30, 0, 10, 10)][..])
+        );
+
+        // The bounds of two rectangles overlap a third one.
+        let mut damage = RedrawDamage::default();
+        damage.add(&[rect(0, 0, 10, 10), rect(20, 20, 10, 10), rect(0, 20, 5, 5)], SIZE);
+        damage.add(&[rect(5, 5, 20, 20)], SIZE);
+        damage.deliver();
+        assert_eq!(damage.current(SIZE), Some(&[rect(0, 0, 30, 30)][..]));
+    }
+
+    #[test]
+    fn full_damage_wins() {
+        let mut damage = RedrawDamage::default();
+        damage.add(&[rect(0, 0, 10, 10)], SIZE);
+        damage.add_full();
+        damage.add(&[rect(20, 0, 10, 10)], SIZE);
+        damage.deliver();
+        assert_eq!(damage.current(SIZE), None);
+
+        // The window was resized since the request.
+        damage.add(&[rect(0, 0, 10, 10)], SIZE);
+        damage.deliver();
+        assert_eq!(damage.current(PhysicalSize::new(50, 50)), None);
+        damage.add(&[rect(0, 0, 10, 10)], SIZE);
+        damage.add(&[rect(0, 0, 10, 10)], PhysicalSize::new(50, 50));
+        damage.deliver();
+        assert_eq!(damage.current(PhysicalSize::new(50, 50)), None);
+    }
+
+    #[test]
+    fn damage_is_cleared_after_delivery() {
+        let mut damage = RedrawDamage::default();
+        damage.add(&[rect(0, 0, 10, 10)], SIZE);
+        assert_eq!(damage.current(SIZE), None);
+        damage.deliver();
+        assert_eq!(damage.current(SIZE), Some(&[rect(0, 0, 10, 10)][..]));
+
+        // A redraw that wasn't requested, e.g. by the system, is complete.
+        damage.deliver();
+        assert_eq!(damage.current(SIZE), None);
+
+        // An empty request doesn't damage anything.
+        damage.add(&[], SIZE);
+        damage.deliver();
+        assert_eq!(damage.current(SIZE), Some(&[][..]));
+    }
+}
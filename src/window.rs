@@ -1,12 +1,16 @@
 //! The [`Window`] struct and associated types.
 use std::fmt;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
 
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::{ExternalError, NotSupportedError};
+use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{ExternalError, ImeRequestError, NotSupportedError};
 use crate::monitor::{MonitorHandle, VideoModeHandle};
 use crate::platform_impl::{self, PlatformSpecificWindowAttributes};
 
-pub use crate::cursor::{BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE};
+pub use crate::cursor::{
+    BadImage, Cursor, CursorFrame, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE,
+};
 pub use crate::icon::{BadIcon, Icon};
 
 #[doc(inline)]
@@ -65,7 +69,10 @@ impl Drop for Window {
 ///
 /// Whenever you receive an event specific to a window, this event contains a `WindowId` which you
 /// can then compare to the ids of your windows.
+///
+/// With the `serde` feature, the id is serialized as its raw value, see [`WindowId::into_raw`].
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(from = "u64", into = "u64"))]
 pub struct WindowId(pub(crate) platform_impl::WindowId);
 
 impl WindowId {
@@ -82,6 +89,23 @@ impl WindowId {
         #[allow(unused_unsafe)]
         WindowId(unsafe { platform_impl::WindowId::dummy() })
     }
+
+    /// Returns the raw value of the id, which [`WindowId::from_raw`] turns back into the same id.
+    ///
+    /// The raw value is only meaningful in the process and the session of the event loop that
+    /// created the window, it can't identify the window to another process or after a restart.
+    pub fn into_raw(self) -> u64 {
+        self.0.into()
+    }
+
+    /// Creates an id from a raw value returned by [`WindowId::into_raw`].
+    ///
+    /// The id compares equal to, and hashes like, the id the raw value was obtained from. Values
+    /// that weren't returned by `into_raw` in the same process may not identify any window, or
+    /// identify another one.
+    pub fn from_raw(raw: u64) -> Self {
+        Self(raw.into())
+    }
 }
 
 impl fmt::Debug for WindowId {
@@ -92,18 +116,25 @@ impl fmt::Debug for WindowId {
 
 impl From<WindowId> for u64 {
     fn from(window_id: WindowId) -> Self {
-        window_id.0.into()
+        window_id.into_raw()
     }
 }
 
 impl From<u64> for WindowId {
     fn from(raw_id: u64) -> Self {
-        Self(raw_id.into())
+        Self::from_raw(raw_id)
     }
 }
 
 /// Attributes used when creating a window.
+///
+/// With the `serde` feature, the attributes can be persisted. The window icon, the cursor, the
+/// parent windows and platform-specific attributes aren't serialized and take their default value
+/// when deserializing, as do missing fields. A [`Fullscreen`] mode that refers to a monitor can't
+/// be restored without an event loop and is deserialized as `None`; persist its
+/// [`FullscreenDescriptor`] separately to restore it.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct WindowAttributes {
     pub inner_size: Option<Size>,
     pub min_inner_size: Option<Size>,
@@ -117,18 +148,29 @@ pub struct WindowAttributes {
     pub transparent: bool,
     pub blur: bool,
     pub decorations: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub window_icon: Option<Icon>,
     pub preferred_theme: Option<Theme>,
     pub resize_increments: Option<Size>,
+    pub aspect_ratio: Option<AspectRatio>,
     pub content_protected: bool,
     pub window_level: WindowLevel,
     pub active: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub cursor: Cursor,
     #[cfg(feature = "rwh_06")]
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) transient_parent: Option<WindowId>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "fullscreen_serde::deserialize_attribute")
+    )]
     pub fullscreen: Option<Fullscreen>,
     // Platform-specific configuration.
     #[allow(dead_code)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) platform_specific: PlatformSpecificWindowAttributes,
 }
 
@@ -153,10 +195,12 @@ impl Default for WindowAttributes {
             window_icon: None,
             preferred_theme: None,
             resize_increments: None,
+            aspect_ratio: None,
             content_protected: false,
             cursor: Cursor::default(),
             #[cfg(feature = "rwh_06")]
             parent_window: None,
+            transient_parent: None,
             active: true,
             platform_specific: Default::default(),
         }
@@ -414,6 +458,17 @@ impl WindowAttributes {
         self
     }
 
+    /// Build window with an aspect ratio constraint.
+    ///
+    /// The default is `None`.
+    ///
+    /// See [`Window::set_aspect_ratio`] for details.
+    #[inline]
+    pub fn with_aspect_ratio(mut self, aspect_ratio: Option<AspectRatio>) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
     /// Prevents the window contents from being captured by other apps.
     ///
     /// The default is `false`.
@@ -482,6 +537,22 @@ impl WindowAttributes {
         self.parent_window = parent_window.map(SendSyncRawWindowHandle);
         self
     }
+
+    /// Get the transient parent stored on the attributes.
+    pub fn transient_parent(&self) -> Option<WindowId> {
+        self.transient_parent
+    }
+
+    /// Build window with a transient parent, like a dialog with its main window.
+    ///
+    /// See [`Window::set_transient_parent`] for details.
+    ///
+    /// The default is `None`.
+    #[inline]
+    pub fn with_transient_parent(mut self, parent: &Window) -> Self {
+        self.transient_parent = Some(parent.id());
+        self
+    }
 }
 
 /// Base Window functions.
@@ -593,9 +664,22 @@ impl Window {
     /// - **Wayland:** The events are aligned with the frame callbacks when
     ///   [`Window::pre_present_notify`] is used.
     /// - **Web:** [`WindowEvent::RedrawRequested`] will be aligned with the
-    ///   `requestAnimationFrame`.
+    ///   `requestAnimationFrame`. While the page is hidden, requests are coalesced into a single
+    ///   event emitted when it becomes visible again.
+    /// - **Android:** [`WindowEvent::RedrawRequested`] is aligned with the `AChoreographer` frame
+    ///   callbacks when enabled with [`EventLoopBuilderExtAndroid::with_choreographer_redraws()`].
     ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[cfg_attr(
+        android_platform,
+        doc = "[`EventLoopBuilderExtAndroid::with_choreographer_redraws()`]: \
+               crate::platform::android::EventLoopBuilderExtAndroid::with_choreographer_redraws"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "[`EventLoopBuilderExtAndroid::with_choreographer_redraws()`]: \
+               #only-available-on-android"
+    )]
     #[inline]
     pub fn request_redraw(&self) {
         let _span = tracing::debug_span!("winit::Window::request_redraw",).entered();
@@ -603,6 +687,48 @@ impl Window {
         self.window.maybe_queue_on_main(|w| w.request_redraw())
     }
 
+    /// Like [`Window::request_redraw`], but only the given rectangles of the window, in physical
+    /// pixels, changed.
+    ///
+    /// The damage of all requests until the next [`WindowEvent::RedrawRequested`] is merged and
+    /// can be queried with [`Window::redraw_damage`] while handling it, e.g. to only present the
+    /// changed part of the window. A call to [`Window::request_redraw`], a resize of the window or
+    /// a redraw requested by the system damages the whole window instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Marks the rectangles as needing display with `setNeedsDisplayInRect:`.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[inline]
+    pub fn request_redraw_with_damage(&self, rects: &[Rect<u32>]) {
+        let _span =
+            tracing::debug_span!("winit::Window::request_redraw_with_damage", rects = rects.len())
+                .entered();
+
+        let rects = rects.to_vec();
+        self.window.maybe_queue_on_main(move |w| w.request_redraw_with_damage(&rects))
+    }
+
+    /// Returns the damage of the current [`WindowEvent::RedrawRequested`], as non-overlapping
+    /// rectangles in physical pixels, or `None` if the whole window has to be redrawn.
+    ///
+    /// See [`Window::request_redraw_with_damage`] for how the damage is accumulated. Outside of
+    /// [`WindowEvent::RedrawRequested`], this returns the damage of the last one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The rectangles can be passed as the dirty rectangle of
+    ///   `CanvasRenderingContext2D.putImageData()`.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[inline]
+    pub fn redraw_damage(&self) -> Option<Vec<Rect<u32>>> {
+        let _span = tracing::debug_span!("winit::Window::redraw_damage",).entered();
+
+        self.window.maybe_wait_on_main(|w| w.redraw_damage())
+    }
+
     /// Notify the windowing system before presenting to the window.
     ///
     /// You should call this event after your drawing operations, but before you submit
@@ -634,7 +760,12 @@ impl Window {
     /// - **Android / iOS / X11 / Web / Windows / macOS / Orbital:** Unsupported.
     /// - **Wayland:** Schedules a frame callback to throttle [`WindowEvent::RedrawRequested`].
     ///
+    /// While enabled with [`Window::set_presentation_feedback()`],
+    /// [`WindowEvent::FramePresented`] is emitted once the frame was presented, on the platforms
+    /// that support it.
+    ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::FramePresented`]: crate::event::WindowEvent::FramePresented
     #[inline]
     pub fn pre_present_notify(&self) {
         let _span = tracing::debug_span!("winit::Window::pre_present_notify",).entered();
@@ -642,6 +773,28 @@ impl Window {
         self.window.maybe_queue_on_main(|w| w.pre_present_notify());
     }
 
+    /// Sets whether [`WindowEvent::FramePresented`] is emitted for the frames presented after
+    /// [`Window::pre_present_notify()`].
+    ///
+    /// The event tells when the frame was shown, which helps to pace rendering with the display.
+    /// Its [`generation`] identifies the [`Window::pre_present_notify()`] call it belongs to.
+    ///
+    /// Presentation feedback is **not** enabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / macOS / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::FramePresented`]: crate::event::WindowEvent::FramePresented
+    /// [`generation`]: crate::event::PresentationFeedback::generation
+    #[inline]
+    pub fn set_presentation_feedback(&self, enabled: bool) {
+        let _span =
+            tracing::debug_span!("winit::Window::set_presentation_feedback", enabled).entered();
+
+        self.window.maybe_queue_on_main(move |w| w.set_presentation_feedback(enabled))
+    }
+
     /// Reset the dead key state of the keyboard.
     ///
     /// This is useful when a dead key is bound to trigger an action. Then
@@ -729,10 +882,20 @@ impl Window {
     /// - **iOS:** Can only be called on the main thread. Sets the top left coordinates of the
     ///   window in the screen space coordinate system.
     /// - **Web:** Sets the top-left coordinates relative to the viewport. Doesn't account for CSS
-    ///   [`transform`].
+    ///   [`transform`], unless the canvas is positioned with one, see
+    ///   [`WindowExtWebSys::set_position_mode()`].
     /// - **Android / Wayland:** Unsupported.
     ///
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "[`WindowExtWebSys::set_position_mode()`]: \
+               crate::platform::web::WindowExtWebSys::set_position_mode"
+    )]
+    #[cfg_attr(
+        not(any(web_platform, docsrs)),
+        doc = "[`WindowExtWebSys::set_position_mode()`]: #only-available-on-wasm"
+    )]
     #[inline]
     pub fn set_outer_position<P: Into<Position>>(&self, position: P) {
         let position = position.into();
@@ -794,7 +957,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Web:** Sets the size of the canvas element. Doesn't account for CSS [`transform`].
+    /// - **Web:** Sets the size of the canvas element. Doesn't account for CSS [`transform`]. When
+    ///   the canvas follows the size of its parent element, this does nothing and returns the
+    ///   current size.
     ///
     /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
@@ -827,6 +992,39 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.outer_size())
     }
 
+    /// Returns the safe area of the window, as distances from the edges of the window.
+    ///
+    /// The safe area is the part of the window that isn't obscured by system UI such as the status
+    /// bar, the camera housing or the home indicator. Content that must be visible and
+    /// interactive should be placed inside of it. [`WindowEvent::SafeAreaChanged`] is emitted
+    /// when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Zero before iOS 11.
+    /// - **Android:** Includes the system bars and the display cutout, as far as they overlap the
+    ///   window. See [`WindowExtAndroid::set_edge_to_edge()`] to draw behind them.
+    /// - **macOS:** Includes the titlebar if the content view extends under it, and the camera
+    ///   housing when fullscreen. Zero before macOS 11.
+    /// - **Web:** The part of the `env(safe-area-inset-*)` CSS variables overlapping the canvas.
+    /// - **Orbital / Wayland / Windows / X11:** Always zero.
+    ///
+    /// [`WindowEvent::SafeAreaChanged`]: crate::event::WindowEvent::SafeAreaChanged
+    #[cfg_attr(
+        android_platform,
+        doc = "[`WindowExtAndroid::set_edge_to_edge()`]: \
+               crate::platform::android::WindowExtAndroid::set_edge_to_edge"
+    )]
+    #[cfg_attr(
+        not(android_platform),
+        doc = "[`WindowExtAndroid::set_edge_to_edge()`]: #only-available-on-android"
+    )]
+    #[inline]
+    pub fn safe_area(&self) -> PhysicalInsets<u32> {
+        let _span = tracing::debug_span!("winit::Window::safe_area",).entered();
+        self.window.maybe_wait_on_main(|w| w.safe_area())
+    }
+
     /// Sets a minimum dimension size for the window.
     ///
     /// ```no_run
@@ -915,6 +1113,30 @@ impl Window {
         .entered();
         self.window.maybe_queue_on_main(move |w| w.set_resize_increments(increments))
     }
+
+    /// Constrains the inner size of the window to an aspect ratio while the user resizes it.
+    ///
+    /// The ratio applies to the inner size, decorations aren't taken into account. The minimum
+    /// and maximum inner sizes still apply and win when both constraints can't be satisfied.
+    /// Maximized and fullscreen windows ignore the ratio. The current size of the window is
+    /// left as is, use [`Window::request_inner_size`] to change it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Takes precedence over the resize increments.
+    /// - **X11:** Relies on the window manager honoring the aspect hints of `WM_NORMAL_HINTS`.
+    /// - **Wayland:** Applied to the size suggested by the compositor, so the window may not follow
+    ///   the pointer exactly.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_aspect_ratio(&self, aspect_ratio: Option<AspectRatio>) {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_aspect_ratio",
+            aspect_ratio = ?aspect_ratio
+        )
+        .entered();
+        self.window.maybe_queue_on_main(move |w| w.set_aspect_ratio(aspect_ratio))
+    }
 }
 
 /// Misc. attribute functions.
@@ -924,6 +1146,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS / Android:** Unsupported.
+    /// - **Web:** Sets the `alt` attribute of the canvas, and `document.title` if enabled with
+    ///   `WindowExtWebSys::set_document_title_enabled()`.
     #[inline]
     pub fn set_title(&self, title: &str) {
         let _span = tracing::debug_span!("winit::Window::set_title", title).entered();
@@ -965,6 +1189,38 @@ impl Window {
         self.window.maybe_queue_on_main(move |w| w.set_blur(blur))
     }
 
+    /// Change the opacity of the whole window, clamped to `0.0..=1.0`.
+    ///
+    /// Unlike [`WindowAttributes::with_transparent`], which lets the content of the window show
+    /// what is behind it where it is drawn with alpha, this fades everything the window shows,
+    /// including its decorations where the platform allows it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets `_NET_WM_WINDOW_OPACITY`, which only has an effect with a compositor.
+    /// - **Windows:** Makes the window a layered window while the opacity is below `1.0`.
+    /// - **iOS:** Decorations don't exist, the opacity applies to the window or the view embedded
+    ///   with `WindowAttributesExtIOS::with_parent_view()`.
+    /// - **Web:** Sets the `opacity` CSS property of the canvas.
+    /// - **Wayland / Android / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let _span = tracing::debug_span!("winit::Window::set_opacity", opacity).entered();
+        let opacity = opacity.clamp(0.0, 1.0);
+        self.window.maybe_queue_on_main(move |w| w.set_opacity(opacity))
+    }
+
+    /// Returns the opacity of the window, see [`Window::set_opacity`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Android / Orbital:** Always returns `1.0`.
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        let _span = tracing::debug_span!("winit::Window::opacity",).entered();
+        self.window.maybe_wait_on_main(|w| w.opacity())
+    }
+
     /// Modifies the window's visibility.
     ///
     /// If `false`, this will hide the window. If `true`, this will show the window.
@@ -1029,10 +1285,14 @@ impl Window {
 
     /// Sets the enabled window buttons.
     ///
+    /// Buttons that aren't in [`Window::supported_buttons`] are ignored.
+    ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented.
-    /// - **Web / iOS / Android:** Unsupported.
+    /// - **X11:** Done through the Motif hints, which the window manager may ignore.
+    /// - **Wayland:** Only the minimize and maximize buttons of the client-side decorations can be
+    ///   disabled, they're hidden.
+    /// - **Web / iOS / Android / Orbital:** Unsupported.
     pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
         let _span = tracing::debug_span!(
             "winit::Window::set_enabled_buttons",
@@ -1046,13 +1306,50 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented. Always returns [`WindowButtons::all`].
-    /// - **Web / iOS / Android:** Unsupported. Always returns [`WindowButtons::all`].
+    /// - **Web:** Unsupported. Always returns [`WindowButtons::empty`].
+    /// - **iOS / Android / Orbital:** Unsupported. Always returns [`WindowButtons::all`].
     pub fn enabled_buttons(&self) -> WindowButtons {
         let _span = tracing::debug_span!("winit::Window::enabled_buttons",).entered();
         self.window.maybe_wait_on_main(|w| w.enabled_buttons())
     }
 
+    /// Gets the window buttons that [`Window::set_enabled_buttons`] can disable.
+    ///
+    /// This lets settings UIs only offer the options that have an effect.
+    ///
+    /// ```no_run
+    /// # use winit::window::{Window, WindowButtons};
+    /// # fn scope(window: &Window) {
+    /// let supported = window.supported_buttons();
+    /// let enabled = window.enabled_buttons();
+    ///
+    /// for (label, button) in [
+    ///     ("Allow closing", WindowButtons::CLOSE),
+    ///     ("Allow minimizing", WindowButtons::MINIMIZE),
+    ///     ("Allow maximizing", WindowButtons::MAXIMIZE),
+    /// ] {
+    ///     // Grey out the checkboxes of the buttons that can't be disabled.
+    ///     let sensitive = supported.contains(button);
+    ///     let checked = enabled.contains(button);
+    ///     println!("{label}: checked: {checked}, sensitive: {sensitive}");
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS:** Always returns [`WindowButtons::all`].
+    /// - **X11:** Returns [`WindowButtons::all`] when a window manager is running, though it may
+    ///   ignore the Motif hints.
+    /// - **Wayland:** Returns [`WindowButtons::MINIMIZE`] and [`WindowButtons::MAXIMIZE`] when the
+    ///   decorations are drawn by winit, and [`WindowButtons::empty`] when they're drawn by the
+    ///   compositor.
+    /// - **Web / iOS / Android / Orbital:** Always returns [`WindowButtons::empty`].
+    pub fn supported_buttons(&self) -> WindowButtons {
+        let _span = tracing::debug_span!("winit::Window::supported_buttons",).entered();
+        self.window.maybe_wait_on_main(|w| w.supported_buttons())
+    }
+
     /// Sets the window to minimized or back
     ///
     /// ## Platform-specific
@@ -1123,8 +1420,16 @@ impl Window {
     /// - **iOS:** Can only be called on the main thread.
     /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
-    /// - **Android / Orbital:** Unsupported.
-    /// - **Web:** Does nothing without a [transient activation].
+    /// - **Android:** Hides the system bars. [`Fullscreen::Exclusive`] behaves like
+    ///   [`Fullscreen::Borderless`]. How the hidden bars can be revealed is set with
+    ///   `WindowExtAndroid::set_system_bars_behavior()`. Fullscreen is kept when the activity is
+    ///   recreated.
+    /// - **Orbital:** Unsupported.
+    /// - **Web:** Does nothing without a [transient activation]. [`Fullscreen::Borderless`] with a
+    ///   monitor only selects the screen once permission to use the Window Management API was
+    ///   granted with `EventLoopExtWebSys::request_detailed_monitor_permission()`. The element set
+    ///   with `WindowExtWebSys::set_fullscreen_element()` is made fullscreen instead of the canvas,
+    ///   if any.
     ///
     /// [transient activation]: https://developer.mozilla.org/en-US/docs/Glossary/Transient_activation
     #[inline]
@@ -1142,7 +1447,9 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread.
-    /// - **Android / Orbital:** Will always return `None`.
+    /// - **Android:** Can only return `None` or `Borderless(None)`. Returns `None` once the user
+    ///   revealed the system bars, unless they're only shown temporarily.
+    /// - **Orbital:** Will always return `None`.
     /// - **Wayland:** Can return `Borderless(None)` when there are no monitors.
     /// - **Web:** Can only return `None` or `Borderless(None)`.
     #[inline]
@@ -1180,6 +1487,35 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.is_decorated())
     }
 
+    /// Sets or unsets the transient parent of the window.
+    ///
+    /// A window with a transient parent, like a dialog, stays above its parent. Unlike
+    /// [`WindowAttributes::with_parent_window`], the window isn't embedded into its parent.
+    ///
+    /// Returns [`ExternalError::Ignored`] if `parent` is the window itself or one of the windows
+    /// it is the transient parent of, directly or not.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Sets the owner of the window, which is minimized and destroyed along with its
+    ///   parent. Not supported for child windows.
+    /// - **macOS:** Adds the window as a child window of its parent, which makes it move along with
+    ///   its parent.
+    /// - **X11:** Sets `WM_TRANSIENT_FOR`, the window manager decides of the exact behavior.
+    /// - **Wayland:** Sets the parent of the `xdg_toplevel`, the compositor decides of the exact
+    ///   behavior.
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn set_transient_parent(&self, parent: Option<&Window>) -> Result<(), ExternalError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_transient_parent",
+            parent = ?parent.map(|parent| parent.id())
+        )
+        .entered();
+        let parent = parent.map(|parent| &parent.window);
+        self.window.maybe_wait_on_main(|w| w.set_transient_parent(parent))
+    }
+
     /// Change the window level.
     ///
     /// This is just a hint to the OS, and the system could ignore it.
@@ -1227,6 +1563,8 @@ impl Window {
     ///
     /// (Apple's official term is "candidate window", see their [chinese] and [japanese] guides).
     ///
+    /// The area can be set while IME isn't allowed, it's then applied when IME gets allowed.
+    ///
     /// ## Example
     ///
     /// ```no_run
@@ -1244,7 +1582,12 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **X11:** - area is not supported, only position.
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **iOS:** The area is used to position the system's candidate and correction UI.
+    /// - **Web:** The area is where the hidden input element receiving the text is placed, which is
+    ///   where browsers show their candidate window.
+    /// - **Android:** The bottom left of the area is reported to the IME as the location of the
+    ///   cursor, which positions e.g. the floating toolbar of the on-screen keyboard next to it.
+    /// - **Orbital:** Unsupported.
     ///
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
     /// [japanese]: https://support.apple.com/guide/japanese-input-method/use-the-candidate-window-jpim10262/6.3/mac/12.0
@@ -1277,10 +1620,25 @@ impl Window {
     ///
     /// - **macOS:** IME must be enabled to receive text-input where dead-key sequences are
     ///   combined.
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **iOS:** Allowing IME shows the on-screen keyboard, and disallowing it hides the keyboard.
+    ///   While IME is allowed, the text of hardware key presses is delivered as [`Ime::Commit`]
+    ///   instead of as part of [`KeyboardInput`].
+    /// - **Web:** Allowing IME moves the focus from the canvas to a hidden input element, which
+    ///   also shows the on-screen keyboard of mobile browsers if it happens in response to user
+    ///   input. Text that virtual keyboards enter without reporting key presses is delivered as
+    ///   [`Ime::Commit`].
+    /// - **Android:** Allowing IME shows the on-screen keyboard, and disallowing it hides the
+    ///   keyboard. The keyboard is shown again when the activity is recreated. With the
+    ///   `android-game-activity` feature, the text entered through the keyboard, including
+    ///   composition and swipe typing, is delivered as [`Ime`] events, and text that the keyboard
+    ///   deletes as [`Ime::DeleteSurrounding`]. With `android-native-activity`, only the
+    ///   [`KeyboardInput`] events of the keyboard are delivered.
+    /// - **Orbital:** Unsupported.
     /// - **X11**: Enabling IME will disable dead keys reporting during compose.
     ///
     /// [`Ime`]: crate::event::WindowEvent::Ime
+    /// [`Ime::Commit`]: crate::event::Ime::Commit
+    /// [`Ime::DeleteSurrounding`]: crate::event::Ime::DeleteSurrounding
     /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
     #[inline]
     pub fn set_ime_allowed(&self, allowed: bool) {
@@ -1292,7 +1650,12 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    /// - **macOS:** [`ImePurpose::Password`] enables secure event input while the window is
+    ///   focused, and disables IME composition and input source switching.
+    /// - **iOS:** Selects the type of the on-screen keyboard.
+    /// - **Android:** Selects the input type of the on-screen keyboard, which requires the
+    ///   `android-game-activity` feature.
+    /// - **Web / Windows / X11 / Orbital:** Unsupported.
     #[inline]
     pub fn set_ime_purpose(&self, purpose: ImePurpose) {
         let _span = tracing::debug_span!(
@@ -1303,6 +1666,64 @@ impl Window {
         self.window.maybe_queue_on_main(move |w| w.set_ime_purpose(purpose))
     }
 
+    /// Returns the IME features supported by the window, or `None` if IME is unsupported.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Windows:** Returns [`ImeCapabilities::CURSOR_AREA`] and
+    ///   [`ImeCapabilities::PREEDIT`].
+    /// - **Wayland:** Returns [`ImeCapabilities::all`] when the compositor supports text input,
+    ///   `None` otherwise.
+    /// - **macOS / iOS / Web:** Returns [`ImeCapabilities::all`].
+    /// - **Android:** Returns [`ImeCapabilities::CURSOR_AREA`] and [`ImeCapabilities::PURPOSE`],
+    ///   and also [`ImeCapabilities::PREEDIT`] with the `android-game-activity` feature.
+    /// - **Orbital:** Returns `None`.
+    #[inline]
+    pub fn ime_capabilities(&self) -> Option<ImeCapabilities> {
+        let _span = tracing::debug_span!("winit::Window::ime_capabilities",).entered();
+        self.window.maybe_wait_on_main(|w| w.ime_capabilities())
+    }
+
+    /// Updates the IME state of the window.
+    ///
+    /// Unlike the individual setters, this checks the request against
+    /// [`Window::ime_capabilities`] and returns an error instead of silently ignoring it. The
+    /// cursor area and purpose may be updated while IME is disabled, so that they're already
+    /// correct when IME gets enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use winit::dpi::{LogicalPosition, LogicalSize};
+    /// # use winit::window::{ImePurpose, ImeRequest, Window};
+    /// # fn scope(window: &Window) {
+    /// let area = ImeRequest::CursorArea(
+    ///     LogicalPosition::new(400.0, 200.0).into(),
+    ///     LogicalSize::new(100, 20).into(),
+    /// );
+    /// let _ = window.request_ime_update(area);
+    /// let _ = window.request_ime_update(ImeRequest::Purpose(ImePurpose::Email));
+    /// window.request_ime_update(ImeRequest::Enable).expect("IME is unsupported");
+    /// # }
+    /// ```
+    pub fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
+        let _span =
+            tracing::debug_span!("winit::Window::request_ime_update", request = ?request).entered();
+        let capabilities = self.ime_capabilities().ok_or(ImeRequestError::NotSupported)?;
+        let missing = request.required_capabilities().difference(capabilities);
+        if !missing.is_empty() {
+            return Err(ImeRequestError::MissingCapabilities(missing));
+        }
+
+        self.window.maybe_queue_on_main(move |w| match request {
+            ImeRequest::Enable => w.set_ime_allowed(true),
+            ImeRequest::Disable => w.set_ime_allowed(false),
+            ImeRequest::CursorArea(position, size) => w.set_ime_cursor_area(position, size),
+            ImeRequest::Purpose(purpose) => w.set_ime_purpose(purpose),
+        });
+        Ok(())
+    }
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1339,10 +1760,12 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **iOS / Android / Orbital:** Unsupported.
     /// - **macOS:** `None` has no effect.
     /// - **X11:** Requests for user attention must be manually cleared.
     /// - **Wayland:** Requires `xdg_activation_v1` protocol, `None` has no effect.
+    /// - **Web:** The request is unset once the page becomes visible or gains focus. See
+    ///   [`UserAttentionType`] for details.
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let _span = tracing::debug_span!(
@@ -1353,6 +1776,87 @@ impl Window {
         self.window.maybe_queue_on_main(move |w| w.request_user_attention(request_type))
     }
 
+    /// Shows the progress of a long running operation on the window's taskbar button or on the
+    /// application's dock / launcher icon. See [`ProgressState`] for the possible states.
+    ///
+    /// Setting the same state again is cheap and doesn't cause the indicator to flicker. The
+    /// indicator is cleared when the window is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `ITaskbarList3`, the progress is shown on the window's taskbar button.
+    /// - **macOS:** The progress is shown on the application's dock tile, which is shared between
+    ///   all windows. [`ProgressState::Error`] and [`ProgressState::Paused`] are displayed like
+    ///   [`ProgressState::Value`].
+    /// - **Wayland / X11:** Uses the `com.canonical.Unity.LauncherEntry` DBus interface on the
+    ///   session bus, which is understood by KDE Plasma, Unity and some docks. The launcher entry
+    ///   is matched with the `<app id>.desktop` desktop file, where the app id is the `general`
+    ///   name passed to [`WindowAttributesExtWayland::with_name`] or
+    ///   [`WindowAttributesExtX11::with_name`], defaulting to the name of the binary.
+    ///   [`ProgressState::Indeterminate`] shows an empty progress bar and [`ProgressState::Error`]
+    ///   additionally marks the entry as urgent. Returns [`NotSupportedError`] if the session bus
+    ///   isn't available.
+    /// - **iOS / Android / Web / Orbital:** Always returns [`NotSupportedError`].
+    #[cfg_attr(
+        any(wayland_platform, docsrs),
+        doc = "[`WindowAttributesExtWayland::with_name`]: \
+               crate::platform::wayland::WindowAttributesExtWayland::with_name"
+    )]
+    #[cfg_attr(
+        not(any(wayland_platform, docsrs)),
+        doc = "[`WindowAttributesExtWayland::with_name`]: #only-available-on-wayland"
+    )]
+    #[cfg_attr(
+        any(x11_platform, docsrs),
+        doc = "[`WindowAttributesExtX11::with_name`]: \
+               crate::platform::x11::WindowAttributesExtX11::with_name"
+    )]
+    #[cfg_attr(
+        not(any(x11_platform, docsrs)),
+        doc = "[`WindowAttributesExtX11::with_name`]: #only-available-on-x11"
+    )]
+    #[inline]
+    pub fn set_progress(&self, state: ProgressState) -> Result<(), NotSupportedError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_progress",
+            state = ?state
+        )
+        .entered();
+        self.window.maybe_wait_on_main(move |w| w.set_progress(state))
+    }
+
+    /// Shows a count, like the number of unread messages, as a badge on the window's taskbar
+    /// button or on the application's dock / launcher icon. `None` and `Some(0)` clear the badge.
+    ///
+    /// Large counts are shortened following the conventions of the platform. Setting the same
+    /// count again is cheap, and the badge is cleared when the window is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses an overlay icon on the window's taskbar button, with counts above 99
+    ///   shown as `99+`. The badge is shown again when the taskbar is restarted.
+    /// - **macOS:** Uses the badge label of the application's dock tile, which is shared between
+    ///   all windows.
+    /// - **Wayland / X11:** Uses the `count` of the `com.canonical.Unity.LauncherEntry` DBus
+    ///   interface, see [`Window::set_progress`] for how the launcher entry is matched.
+    /// - **iOS:** Uses the badge number of the application icon, which requires the application to
+    ///   be authorized to badge its icon by the user notifications framework.
+    /// - **Web:** Uses the [Badging API], which is only available to installed web apps. The badge
+    ///   takes precedence over the one shown by [`Window::request_user_attention`].
+    /// - **Android / Orbital:** Unsupported.
+    ///
+    /// [Badging API]: https://developer.mozilla.org/en-US/docs/Web/API/Badging_API
+    #[inline]
+    pub fn set_badge_count(&self, count: Option<u64>) {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_badge_count",
+            count = ?count
+        )
+        .entered();
+        let count = count.filter(|&count| count != 0);
+        self.window.maybe_queue_on_main(move |w| w.set_badge_count(count))
+    }
+
     /// Set or override the window theme.
     ///
     /// Specify `None` to reset the theme to the system default.
@@ -1363,7 +1867,8 @@ impl Window {
     ///   get the system preference.
     /// - **X11:** Sets `_GTK_THEME_VARIANT` hint to `dark` or `light` and if `None` is used, it
     ///   will default to  [`Theme::Dark`].
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **iOS:** Sets the `overrideUserInterfaceStyle` of the window. Unsupported before iOS 13.
+    /// - **Android / Web / Orbital:** Unsupported.
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
         let _span = tracing::debug_span!(
@@ -1381,7 +1886,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported, returns `None`.
+    /// - **iOS:** Returns the effective theme of the window, which is always [`Theme::Light`]
+    ///   before iOS 13.
+    /// - **Android / Wayland / x11 / Orbital:** Unsupported, returns `None`.
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
         let _span = tracing::debug_span!("winit::Window::theme",).entered();
@@ -1405,9 +1912,17 @@ impl Window {
 
     /// Gets the current title of the window.
     ///
+    /// This is the title last set with [`Window::set_title`] or [`WindowAttributes::with_title`],
+    /// unless the user or the window manager changed it since, in which case the title shown by
+    /// the system is returned. Setting a title and getting it right after returns the same string.
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / x11 / Wayland / Web:** Unsupported. Always returns an empty string.
+    /// - **Windows / macOS / X11 / Orbital:** Queries the system.
+    /// - **iOS / Android:** Returns the last title set, even though it isn't shown.
+    /// - **Wayland:** Returns the last title set, even if the compositor was only sent its first
+    ///   1024 bytes.
+    /// - **Web:** Returns the last title set, whether or not it was written to `document.title`.
     #[inline]
     pub fn title(&self) -> String {
         let _span = tracing::debug_span!("winit::Window::title",).entered();
@@ -1421,7 +1936,10 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Orbital:** Unsupported.
+    /// - **iOS:** Shown for the pointer of trackpads and mice since iOS 13.4. Only
+    ///   [`CursorIcon::Text`] and [`CursorIcon::VerticalText`] have a matching pointer shape, the
+    ///   default pointer is shown for all other icons. Custom cursors are unsupported.
+    /// - **Android / Orbital:** Unsupported.
     /// - **Web:** Custom cursors have to be loaded and decoded first, until then the previous
     ///   cursor is shown.
     #[inline]
@@ -1503,7 +2021,9 @@ impl Window {
     /// - **Wayland:** The cursor is only hidden within the confines of the window.
     /// - **macOS:** The cursor is hidden as long as the window has input focus, even if the cursor
     ///   is outside of the window.
-    /// - **iOS / Android:** Unsupported.
+    /// - **iOS:** The pointer of trackpads and mice is hidden while it is over the window, since
+    ///   iOS 13.4.
+    /// - **Android:** Unsupported.
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         let _span = tracing::debug_span!("winit::Window::set_cursor_visible", visible).entered();
@@ -1546,6 +2066,37 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.drag_resize_window(direction))
     }
 
+    /// Starts dragging `payload` out of the window with the mouse button being held, so it can be
+    /// dropped onto other applications.
+    ///
+    /// This should be called while handling the [`WindowEvent::MouseInput`] press or the
+    /// following [`WindowEvent::CursorMoved`] events, since it returns
+    /// [`ExternalError::Ignored`] when no mouse button is held. Once the drag ends,
+    /// [`WindowEvent::DragSourceFinished`] is emitted with the action the drop was accepted with.
+    ///
+    /// The dragged data is shown with the platform's generic representation.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The drag starts once the current event was handled, and needs drag and drop
+    ///   to be enabled, which is the default.
+    /// - **Wayland:** The drag starts once the current event was handled, and needs the
+    ///   `wl_data_device_manager` global.
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    ///
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    /// [`WindowEvent::DragSourceFinished`]: crate::event::WindowEvent::DragSourceFinished
+    #[inline]
+    pub fn start_drag_and_drop(&self, payload: DragPayload) -> Result<(), ExternalError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::start_drag_and_drop",
+            payload = ?payload
+        )
+        .entered();
+        self.window.maybe_wait_on_main(|w| w.start_drag_and_drop(payload))
+    }
+
     /// Show [window menu] at a specified position .
     ///
     /// This is the context menu that is normally shown when interacting with
@@ -1614,7 +2165,10 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// **Wayland / Web:** Always returns `None`.
+    /// - **Wayland:** Always returns `None`.
+    /// - **Web:** Returns the screen the browser window is on, unless permission to use the Window
+    ///   Management API was granted with
+    ///   `EventLoopExtWebSys::request_detailed_monitor_permission()`.
     ///
     /// [`ActiveEventLoop::primary_monitor`]: crate::event_loop::ActiveEventLoop::primary_monitor
     #[inline]
@@ -1682,6 +2236,24 @@ unsafe impl rwh_04::HasRawWindowHandle for Window {
     }
 }
 
+/// A rectangle, e.g. a part of a window in physical pixels.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rect<P> {
+    /// The position of the top-left corner.
+    pub position: PhysicalPosition<P>,
+    /// The size, extending to the right and down from the position.
+    pub size: PhysicalSize<P>,
+}
+
+impl<P> Rect<P> {
+    /// Creates a rectangle from its top-left corner and its size.
+    #[inline]
+    pub const fn new(position: PhysicalPosition<P>, size: PhysicalSize<P>) -> Self {
+        Rect { position, size }
+    }
+}
+
 /// The behavior of cursor grabbing.
 ///
 /// Use this enum with [`Window::set_cursor_grab`] to grab the cursor.
@@ -1698,6 +2270,9 @@ pub enum CursorGrabMode {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11:** The cursor is confined with pointer barriers while the window is focused, and
+    ///   only once it enters the window. Falls back to an active pointer grab when the XFixes 5.0
+    ///   extension is not available.
     /// - **macOS:** Not implemented. Always returns [`ExternalError::NotSupported`] for now.
     /// - **iOS / Android / Web:** Always returns an [`ExternalError::NotSupported`].
     Confined,
@@ -1717,6 +2292,7 @@ pub enum CursorGrabMode {
 
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ResizeDirection {
     East,
     North,
@@ -1744,7 +2320,131 @@ impl From<ResizeDirection> for CursorIcon {
     }
 }
 
+/// The data dragged out of a window, see [`Window::start_drag_and_drop`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DragPayload {
+    /// Files or directories, as absolute paths.
+    Paths(Vec<PathBuf>),
+    /// Plain text.
+    Text(String),
+}
+
+/// The ratio between the width and the height of a window.
+///
+/// See [`Window::set_aspect_ratio`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AspectRatio {
+    width: NonZeroU32,
+    height: NonZeroU32,
+}
+
+impl AspectRatio {
+    /// Creates a new aspect ratio, like `AspectRatio::new(16, 9)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `width` or `height` is zero.
+    pub fn new(width: u32, height: u32) -> Self {
+        let width = NonZeroU32::new(width).expect("aspect ratio width must not be zero");
+        let height = NonZeroU32::new(height).expect("aspect ratio height must not be zero");
+        Self { width, height }
+    }
+
+    /// The width part of the ratio.
+    pub fn width(&self) -> u32 {
+        self.width.get()
+    }
+
+    /// The height part of the ratio.
+    pub fn height(&self) -> u32 {
+        self.height.get()
+    }
+
+    /// Fits `size` to the ratio, following the dimension given by `axis`.
+    ///
+    /// The `min` and `max` bounds are kept whenever possible, and win over the ratio when they
+    /// can't be satisfied together. All the sizes must be in the same unit.
+    #[cfg_attr(not(any(windows_platform, wayland_platform)), allow(dead_code))]
+    pub(crate) fn constrain(
+        self,
+        size: PhysicalSize<u32>,
+        axis: AspectRatioAxis,
+        min: Option<PhysicalSize<u32>>,
+        max: Option<PhysicalSize<u32>>,
+    ) -> PhysicalSize<u32> {
+        let (ratio_width, ratio_height) = (self.width.get() as u64, self.height.get() as u64);
+        let with_width = |width: u32| {
+            let height = (width as u64 * ratio_height + ratio_width / 2) / ratio_width;
+            PhysicalSize::new(width, height.min(u32::MAX as u64) as u32)
+        };
+        let with_height = |height: u32| {
+            let width = (height as u64 * ratio_width + ratio_height / 2) / ratio_height;
+            PhysicalSize::new(width.min(u32::MAX as u64) as u32, height)
+        };
+
+        let mut size = match axis {
+            AspectRatioAxis::Width => with_width(size.width),
+            AspectRatioAxis::Height => with_height(size.height),
+            AspectRatioAxis::Fit
+                if size.width as u64 * ratio_height <= size.height as u64 * ratio_width =>
+            {
+                with_width(size.width)
+            },
+            AspectRatioAxis::Fit => with_height(size.height),
+        };
+
+        if let Some(min) = min {
+            if size.width < min.width {
+                size = with_width(min.width);
+            }
+            if size.height < min.height {
+                size = with_height(min.height);
+            }
+        }
+        if let Some(max) = max {
+            if size.width > max.width {
+                size = with_width(max.width);
+            }
+            if size.height > max.height {
+                size = with_height(max.height);
+            }
+        }
+
+        // The bounds may conflict with the ratio, in which case the bounds win.
+        if let Some(max) = max {
+            size.width = size.width.min(max.width);
+            size.height = size.height.min(max.height);
+        }
+        if let Some(min) = min {
+            size.width = size.width.max(min.width);
+            size.height = size.height.max(min.height);
+        }
+
+        size
+    }
+}
+
+/// The dimension followed when fitting a size to an [`AspectRatio`].
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum AspectRatioAxis {
+    /// Keep the width, like when dragging the left or right edge.
+    Width,
+    /// Keep the height, like when dragging the top or bottom edge.
+    Height,
+    /// Pick the largest size with the ratio that fits in the given size.
+    Fit,
+}
+
 /// Fullscreen modes.
+///
+/// With the `serde` feature, a fullscreen mode is serialized as its [`FullscreenDescriptor`].
+/// Monitors can't be looked up while deserializing, so only `Fullscreen::Borderless(None)` can be
+/// deserialized directly, and anything else is an error; deserialize a [`FullscreenDescriptor`]
+/// instead and resolve it with [`ActiveEventLoop::resolve_fullscreen`].
+///
+/// [`ActiveEventLoop::resolve_fullscreen`]: crate::event_loop::ActiveEventLoop::resolve_fullscreen
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Fullscreen {
     Exclusive(VideoModeHandle),
@@ -1753,6 +2453,112 @@ pub enum Fullscreen {
     Borderless(Option<MonitorHandle>),
 }
 
+/// A description of a [`Fullscreen`] mode that doesn't hold on to a monitor, so that it can be
+/// persisted and turned back into a [`Fullscreen`] with
+/// [`ActiveEventLoop::resolve_fullscreen`].
+///
+/// The monitor is identified by its [name], as there is no stable monitor identifier on every
+/// platform. Names aren't guaranteed to be unique nor to stay the same once monitors are
+/// reconnected, so resolving a descriptor may pick another monitor with the same name or none at
+/// all. A monitor without a name is stored as `None`, like a mode without a monitor.
+///
+/// [name]: MonitorHandle::name
+/// [`ActiveEventLoop::resolve_fullscreen`]: crate::event_loop::ActiveEventLoop::resolve_fullscreen
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FullscreenDescriptor {
+    /// See [`Fullscreen::Exclusive`].
+    Exclusive {
+        /// The name of the monitor, or `None` for the primary monitor.
+        #[cfg_attr(feature = "serde", serde(default))]
+        monitor: Option<String>,
+        /// See [`VideoModeHandle::size`].
+        size: PhysicalSize<u32>,
+        /// See [`VideoModeHandle::bit_depth`].
+        bit_depth: u16,
+        /// See [`VideoModeHandle::refresh_rate_millihertz`].
+        refresh_rate_millihertz: u32,
+    },
+    /// See [`Fullscreen::Borderless`].
+    Borderless {
+        /// The name of the monitor, or `None` for the current monitor.
+        #[cfg_attr(feature = "serde", serde(default))]
+        monitor: Option<String>,
+    },
+}
+
+impl From<&Fullscreen> for FullscreenDescriptor {
+    fn from(fullscreen: &Fullscreen) -> Self {
+        match fullscreen {
+            Fullscreen::Exclusive(video_mode) => FullscreenDescriptor::Exclusive {
+                monitor: video_mode.monitor().name(),
+                size: video_mode.size(),
+                bit_depth: video_mode.bit_depth(),
+                refresh_rate_millihertz: video_mode.refresh_rate_millihertz(),
+            },
+            Fullscreen::Borderless(monitor) => FullscreenDescriptor::Borderless {
+                monitor: monitor.as_ref().and_then(|monitor| monitor.name()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod fullscreen_serde {
+    use super::{Fullscreen, FullscreenDescriptor};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for Fullscreen {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            FullscreenDescriptor::from(self).serialize(serializer)
+        }
+    }
+
+    /// Deserializes [`WindowAttributes::fullscreen`], which is `None` when the mode can't be
+    /// restored, so that the rest of the attributes can still be read.
+    ///
+    /// [`WindowAttributes::fullscreen`]: super::WindowAttributes::fullscreen
+    pub(super) fn deserialize_attribute<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<Fullscreen>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Option::<FullscreenDescriptor>::deserialize(deserializer)? {
+            Some(FullscreenDescriptor::Borderless { monitor: None }) => {
+                Some(Fullscreen::Borderless(None))
+            },
+            Some(descriptor) => {
+                tracing::warn!("can't restore {descriptor:?} without an event loop, ignoring it");
+                None
+            },
+            None => None,
+        })
+    }
+
+    impl<'de> Deserialize<'de> for Fullscreen {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            // Monitors can't be looked up without an event loop.
+            match FullscreenDescriptor::deserialize(deserializer)? {
+                FullscreenDescriptor::Borderless { monitor: None } => {
+                    Ok(Fullscreen::Borderless(None))
+                },
+                _ => Err(D::Error::custom(
+                    "fullscreen on a specific monitor can't be deserialized without an event \
+                     loop, deserialize a `FullscreenDescriptor` instead",
+                )),
+            }
+        }
+    }
+}
+
 /// The theme variant to use.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1764,6 +2570,43 @@ pub enum Theme {
     Dark,
 }
 
+/// The orientation of the user interface of a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InterfaceOrientation {
+    /// The device is held upright, with the top of the interface at the top of the device.
+    Portrait,
+
+    /// The device is held upside down, with the top of the interface at the bottom of the device.
+    PortraitUpsideDown,
+
+    /// The device is held sideways, with the bottom of the device on the left side.
+    LandscapeLeft,
+
+    /// The device is held sideways, with the bottom of the device on the right side.
+    LandscapeRight,
+}
+
+/// The orientations the user interface of a window can be locked to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OrientationLock {
+    /// Any orientation the device supports, which still prevents following the system setting.
+    Any,
+
+    /// The natural orientation of the device, e.g. portrait for phones.
+    Natural,
+
+    /// Either portrait orientation.
+    Portrait,
+
+    /// Either landscape orientation.
+    Landscape,
+
+    /// Exactly the given orientation.
+    Exact(InterfaceOrientation),
+}
+
 /// ## Platform-specific
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between [`Critical`] and
@@ -1772,22 +2615,64 @@ pub enum Theme {
 /// [`Critical`]: Self::Critical
 /// [`Informational`]: Self::Informational
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UserAttentionType {
+    #[cfg_attr(not(any(web_platform, docsrs)), allow(rustdoc::broken_intra_doc_links))]
     /// ## Platform-specific
     ///
     /// - **macOS:** Bounces the dock icon until the application is in focus.
     /// - **Windows:** Flashes both the window and the taskbar button until the application is in
     ///   focus.
+    /// - **Web:** Alternates the title of the page with a prefix, optionally shows a different
+    ///   favicon, and sets the badge of installed web apps. See
+    ///   [`WindowExtWebSys::set_user_attention_prefix()`].
+    ///
+    /// [`WindowExtWebSys::set_user_attention_prefix()`]: crate::platform::web::WindowExtWebSys::set_user_attention_prefix
     Critical,
 
     /// ## Platform-specific
     ///
     /// - **macOS:** Bounces the dock icon once.
     /// - **Windows:** Flashes the taskbar button until the application is in focus.
+    /// - **Web:** Sets the badge of installed web apps, if supported.
     #[default]
     Informational,
 }
 
+/// The state of the progress indicator set with [`Window::set_progress`].
+///
+/// Progress values are in the range `0.0..=1.0` and are clamped to it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ProgressState {
+    /// No progress indicator is shown.
+    #[default]
+    None,
+
+    /// The operation is running but its progress can't be determined.
+    Indeterminate,
+
+    /// The operation is running normally.
+    Value(f64),
+
+    /// The operation failed, the indicator is usually shown in red.
+    Error(f64),
+
+    /// The operation is paused, the indicator is usually shown in yellow.
+    Paused(f64),
+}
+
+impl ProgressState {
+    /// Returns the progress value clamped to `0.0..=1.0`, if the state has one.
+    pub fn value(&self) -> Option<f64> {
+        match *self {
+            Self::None | Self::Indeterminate => None,
+            Self::Value(value) | Self::Error(value) | Self::Paused(value) => {
+                Some(if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) })
+            },
+        }
+    }
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct WindowButtons: u32 {
@@ -1797,6 +2682,56 @@ bitflags::bitflags! {
     }
 }
 
+#[cfg(feature = "serde")]
+mod window_buttons_serde {
+    use super::WindowButtons;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(default)]
+    #[serde(rename = "WindowButtons")]
+    pub struct WindowButtonsSerialize {
+        pub close: bool,
+        pub minimize: bool,
+        pub maximize: bool,
+    }
+
+    impl Default for WindowButtonsSerialize {
+        fn default() -> Self {
+            Self { close: true, minimize: true, maximize: true }
+        }
+    }
+
+    impl Serialize for WindowButtons {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let s = WindowButtonsSerialize {
+                close: self.contains(WindowButtons::CLOSE),
+                minimize: self.contains(WindowButtons::MINIMIZE),
+                maximize: self.contains(WindowButtons::MAXIMIZE),
+            };
+            s.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for WindowButtons {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let WindowButtonsSerialize { close, minimize, maximize } =
+                WindowButtonsSerialize::deserialize(deserializer)?;
+            let mut buttons = WindowButtons::empty();
+            buttons.set(WindowButtons::CLOSE, close);
+            buttons.set(WindowButtons::MINIMIZE, minimize);
+            buttons.set(WindowButtons::MAXIMIZE, maximize);
+            Ok(buttons)
+        }
+    }
+}
+
 /// A window level groups windows with respect to their z-position.
 ///
 /// The relative ordering between windows in different window levels is fixed.
@@ -1806,6 +2741,7 @@ bitflags::bitflags! {
 ///
 /// - **iOS / Android / Web / Wayland:** Unsupported.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WindowLevel {
     /// The window will always be below normal windows.
     ///
@@ -1827,7 +2763,11 @@ pub enum WindowLevel {
 ///
 /// ## Platform-specific
 ///
-/// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+/// - **iOS:** Selects the type of the on-screen keyboard, [`ImePurpose::Password`] also enables
+///   secure text entry.
+/// - **Web:** Selects the type of the on-screen keyboard, [`ImePurpose::Password`] also disables
+///   composition.
+/// - **Android / Windows / X11 / macOS / Orbital:** Unsupported.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
 pub enum ImePurpose {
@@ -1839,6 +2779,10 @@ pub enum ImePurpose {
     ///
     /// For example, that could alter OSK on Wayland to show extra buttons.
     Terminal,
+    /// The IME is used to input an email address.
+    Email,
+    /// The IME is used to input a number.
+    Number,
 }
 
 impl Default for ImePurpose {
@@ -1847,10 +2791,58 @@ impl Default for ImePurpose {
     }
 }
 
+bitflags::bitflags! {
+    /// The IME features that a backend supports, as returned by [`Window::ime_capabilities`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ImeCapabilities: u8 {
+        /// The cursor area can be set, see [`ImeRequest::CursorArea`].
+        const CURSOR_AREA = 1 << 0;
+        /// The purpose can be set, see [`ImeRequest::Purpose`].
+        const PURPOSE = 1 << 1;
+        /// The text being composed is reported with [`Ime::Preedit`].
+        ///
+        /// [`Ime::Preedit`]: crate::event::Ime::Preedit
+        const PREEDIT = 1 << 2;
+    }
+}
+
+/// An update of the IME state of a window, for use in [`Window::request_ime_update`].
+///
+/// The cursor area and purpose can be updated while IME is disabled, the latest values are
+/// applied when IME gets enabled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ImeRequest {
+    /// Allow IME, see [`Window::set_ime_allowed`].
+    Enable,
+    /// Disallow IME, see [`Window::set_ime_allowed`].
+    Disable,
+    /// Set the cursor area, see [`Window::set_ime_cursor_area`].
+    ///
+    /// Requires [`ImeCapabilities::CURSOR_AREA`].
+    CursorArea(Position, Size),
+    /// Set the purpose, see [`Window::set_ime_purpose`].
+    ///
+    /// Requires [`ImeCapabilities::PURPOSE`].
+    Purpose(ImePurpose),
+}
+
+impl ImeRequest {
+    /// The capabilities the backend needs to fulfill the request.
+    fn required_capabilities(&self) -> ImeCapabilities {
+        match self {
+            Self::Enable | Self::Disable => ImeCapabilities::empty(),
+            Self::CursorArea(..) => ImeCapabilities::CURSOR_AREA,
+            Self::Purpose(_) => ImeCapabilities::PURPOSE,
+        }
+    }
+}
+
 /// An opaque token used to activate the [`Window`].
 ///
 /// [`Window`]: crate::window::Window
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
 pub struct ActivationToken {
     pub(crate) _token: String,
 }
@@ -1860,3 +2852,62 @@ impl ActivationToken {
         Self { _token }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_ratio_follows_axis() {
+        let ratio = AspectRatio::new(16, 9);
+        let size = PhysicalSize::new(1600, 600);
+
+        assert_eq!(
+            ratio.constrain(size, AspectRatioAxis::Width, None, None),
+            PhysicalSize::new(1600, 900)
+        );
+        assert_eq!(
+            ratio.constrain(size, AspectRatioAxis::Height, None, None),
+            PhysicalSize::new(1067, 600)
+        );
+        assert_eq!(
+            ratio.constrain(size, AspectRatioAxis::Fit, None, None),
+            PhysicalSize::new(1067, 600)
+        );
+        assert_eq!(
+            ratio.constrain(PhysicalSize::new(800, 900), AspectRatioAxis::Fit, None, None),
+            PhysicalSize::new(800, 450)
+        );
+    }
+
+    #[test]
+    fn aspect_ratio_keeps_bounds() {
+        let ratio = AspectRatio::new(4, 3);
+        let min = Some(PhysicalSize::new(400, 100));
+        let max = Some(PhysicalSize::new(1000, 600));
+
+        // The bounds are met while keeping the ratio.
+        assert_eq!(
+            ratio.constrain(PhysicalSize::new(200, 200), AspectRatioAxis::Width, min, max),
+            PhysicalSize::new(400, 300)
+        );
+        assert_eq!(
+            ratio.constrain(PhysicalSize::new(1200, 200), AspectRatioAxis::Width, min, max),
+            PhysicalSize::new(800, 600)
+        );
+
+        // The bounds win when the ratio can't be kept.
+        let min = Some(PhysicalSize::new(400, 400));
+        let max = Some(PhysicalSize::new(500, 1000));
+        assert_eq!(
+            ratio.constrain(PhysicalSize::new(450, 0), AspectRatioAxis::Width, min, max),
+            PhysicalSize::new(500, 400)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn aspect_ratio_rejects_zero() {
+        AspectRatio::new(0, 1);
+    }
+}
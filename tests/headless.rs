@@ -0,0 +1,739 @@
+//! Drives applications with the headless backend, checking the events they receive.
+//!
+//! There can only be one event loop per process, so every test is run in its own process. The
+//! arguments that aren't flags filter the tests by name, like with the default test harness.
+
+#[cfg(headless_platform)]
+#[allow(dead_code)]
+#[path = "../examples/control_flow.rs"]
+mod control_flow;
+
+#[cfg(headless_platform)]
+mod driven {
+    use std::any::Any;
+    use std::collections::HashSet;
+    use std::process::{self, Command};
+    use std::time::Duration;
+    use std::{env, thread};
+
+    use winit::application::ApplicationHandler;
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+    use winit::event::{ElementState, Ime, MouseButton, SessionEvent, StartCause, WindowEvent};
+    use winit::event_loop::{
+        ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy, MotionEventPolicy,
+    };
+    use winit::keyboard::{Key, KeyCode, LockKeys, ModifiersState, NamedKey, PhysicalKey};
+    use winit::platform::headless::{
+        EventLoopBuilderExtHeadless, EventLoopExtHeadless, HeadlessDriver, HeadlessImeState,
+    };
+    use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+    use winit::window::{
+        Fullscreen, FullscreenDescriptor, ImeCapabilities, ImePurpose, ImeRequest, Rect, Window,
+        WindowId,
+    };
+
+    use crate::control_flow::ControlFlowDemo;
+
+    /// The environment variable with the name of the test that the process runs.
+    const TEST_VAR: &str = "WINIT_HEADLESS_TEST";
+
+    const TESTS: &[(&str, fn())] = &[
+        ("window_creation", window_creation),
+        ("title", title),
+        ("keyboard", keyboard),
+        ("lock_keys_and_focus", lock_keys_and_focus),
+        ("keyboard_layout", keyboard_layout),
+        ("pointer", pointer),
+        ("motion_coalescing", motion_coalescing),
+        ("click_count", click_count),
+        ("ime", ime),
+        ("resize_and_scale_factor", resize_and_scale_factor),
+        ("redraw_damage", redraw_damage),
+        ("fullscreen_descriptor", fullscreen_descriptor),
+        ("session_events", session_events),
+        ("timers", timers),
+        ("user_events", user_events),
+        ("close", close),
+        #[cfg(feature = "serde")]
+        ("record_and_replay", record_and_replay),
+        ("example_control_flow", example_control_flow),
+    ];
+
+    pub fn main() {
+        if let Ok(name) = env::var(TEST_VAR) {
+            let (_, test) = TESTS.iter().find(|(test, _)| *test == name).expect("unknown test");
+            return test();
+        }
+
+        let filters: Vec<_> = env::args().skip(1).filter(|arg| !arg.starts_with('-')).collect();
+        let tests: Vec<_> = TESTS
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| filters.is_empty() || filters.iter().any(|filter| name.contains(filter)))
+            .collect();
+
+        println!("\nrunning {} tests", tests.len());
+        let mut failures = Vec::new();
+        for name in &tests {
+            let output =
+                Command::new(env::current_exe().unwrap()).env(TEST_VAR, name).output().unwrap();
+            if output.status.success() {
+                println!("test {name} ... ok");
+            } else {
+                println!("test {name} ... FAILED");
+                failures.push((name, output));
+            }
+        }
+
+        for (name, output) in &failures {
+            println!("\n---- {name} stderr ----\n{}", String::from_utf8_lossy(&output.stderr));
+        }
+        let result = if failures.is_empty() { "ok" } else { "FAILED" };
+        let passed = tests.len() - failures.len();
+        println!("\ntest result: {result}. {passed} passed; {} failed\n", failures.len());
+        if !failures.is_empty() {
+            process::exit(101);
+        }
+    }
+
+    #[derive(Default)]
+    struct App {
+        window: Option<Window>,
+        events: Vec<WindowEvent>,
+        resume_time_reached: bool,
+        timeout: Option<Duration>,
+        motion_event_policy: Option<MotionEventPolicy>,
+        layout_keys: Vec<Key>,
+        layout_codes: Vec<Option<KeyCode>>,
+        session_events: Vec<SessionEvent>,
+        redraw_damage: Vec<Option<Vec<Rect<u32>>>>,
+        fullscreen_descriptor: Option<FullscreenDescriptor>,
+        resolved_fullscreen: Option<Fullscreen>,
+        proxy: Option<EventLoopProxy>,
+        user_events: Vec<u32>,
+    }
+
+    impl ApplicationHandler for App {
+        fn new_events(&mut self, _: &ActiveEventLoop, cause: StartCause) {
+            if let StartCause::ResumeTimeReached { .. } = cause {
+                self.resume_time_reached = true;
+            }
+        }
+
+        fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+            let attributes =
+                Window::default_attributes().with_inner_size(PhysicalSize::new(64, 48));
+            self.window = Some(event_loop.create_window(attributes).unwrap());
+            self.proxy = Some(event_loop.create_proxy());
+        }
+
+        fn user_event(&mut self, _: &ActiveEventLoop, event: Box<dyn Any + Send>) {
+            self.user_events.push(*event.downcast().unwrap());
+        }
+
+        fn window_event(&mut self, event_loop: &ActiveEventLoop, _: WindowId, event: WindowEvent) {
+            if event == WindowEvent::CloseRequested {
+                self.window = None;
+                event_loop.exit();
+            }
+            if event == WindowEvent::RedrawRequested {
+                self.redraw_damage.push(self.window.as_ref().unwrap().redraw_damage());
+            }
+            self.events.push(event);
+        }
+
+        fn keyboard_layout_changed(&mut self, event_loop: &ActiveEventLoop) {
+            self.layout_keys = [KeyCode::KeyQ, KeyCode::BracketLeft, KeyCode::Enter]
+                .into_iter()
+                .map(|code| event_loop.key_from_key_code(code))
+                .collect();
+            self.layout_codes = [
+                Key::Character("Q".into()),
+                Key::Dead(Some('^')),
+                Key::Named(NamedKey::Tab),
+                Key::Character("z".into()),
+            ]
+            .into_iter()
+            .map(|key| event_loop.key_code_from_key(key))
+            .collect();
+        }
+
+        fn session_event(&mut self, _: &ActiveEventLoop, event: SessionEvent) {
+            self.session_events.push(event);
+        }
+
+        fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+            if let Some(timeout) = self.timeout.take() {
+                event_loop.set_control_flow(ControlFlow::wait_duration(timeout));
+            }
+            if let Some(policy) = self.motion_event_policy.take() {
+                event_loop.set_motion_event_policy(policy);
+            }
+            if let Some(descriptor) = self.fullscreen_descriptor.take() {
+                self.resolved_fullscreen = event_loop.resolve_fullscreen(&descriptor);
+            }
+        }
+    }
+
+    /// A headless event loop running [`App`], with its window already created.
+    struct Harness {
+        event_loop: EventLoop,
+        driver: HeadlessDriver,
+        app: App,
+        window_id: WindowId,
+        /// The events of the first iteration, which created the window.
+        created: Vec<WindowEvent>,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let mut event_loop = EventLoop::builder().with_headless().build().unwrap();
+            let driver = event_loop.headless_driver().unwrap();
+            let mut app = App::default();
+            event_loop.pump_app_events(Some(Duration::ZERO), &mut app);
+            let window_id = app.window.as_ref().unwrap().id();
+            let created = std::mem::take(&mut app.events);
+            Self { event_loop, driver, app, window_id, created }
+        }
+
+        fn window(&self) -> &Window {
+            self.app.window.as_ref().unwrap()
+        }
+
+        /// Runs an iteration of the event loop, returning its status and the window events.
+        fn pump_with_status(&mut self) -> (PumpStatus, Vec<WindowEvent>) {
+            let status = self.event_loop.pump_app_events(Some(Duration::ZERO), &mut self.app);
+            (status, std::mem::take(&mut self.app.events))
+        }
+
+        fn pump(&mut self) -> Vec<WindowEvent> {
+            self.pump_with_status().1
+        }
+    }
+
+    fn window_creation() {
+        fn needs_send<T: Send>() {}
+        needs_send::<HeadlessDriver>();
+
+        // The window is created focused and drawn once.
+        let mut harness = Harness::new();
+        assert_eq!(harness.created, vec![WindowEvent::Focused(true), WindowEvent::RedrawRequested]);
+        let window_id = harness.window_id;
+        assert_eq!(harness.driver.windows(), vec![window_id]);
+
+        // The id can be turned into a raw value and back.
+        assert_eq!(WindowId::from_raw(window_id.into_raw()), window_id);
+        assert!(HashSet::from([window_id]).contains(&WindowId::from_raw(window_id.into_raw())));
+
+        // Nothing happens without input.
+        assert!(harness.pump().is_empty());
+    }
+
+    fn title() {
+        // The title is returned exactly as it was set, including characters outside of the BMP,
+        // which take surrogate pairs in UTF-16, and titles longer than what some systems show.
+        let harness = Harness::new();
+        let window = harness.window();
+        assert_eq!(window.title(), "winit window");
+        let long_title = "\u{1F980} crab ".repeat(1000);
+        for title in
+            ["", "\u{1F980}\u{1F30D} na\u{EF}ve \u{1D11E}", "e\u{301}\u{200D}", &long_title]
+        {
+            window.set_title(title);
+            assert_eq!(window.title(), title);
+        }
+    }
+
+    fn keyboard() {
+        // Keyboard input goes to the focused window, with the modifiers it changed.
+        let mut harness = Harness::new();
+        let driver = &harness.driver;
+        driver.key(KeyCode::ShiftLeft, Key::Named(NamedKey::Shift), None, ElementState::Pressed);
+        let a = Key::Character("A".into());
+        driver.key(KeyCode::KeyA, a.clone(), Some("A".into()), ElementState::Pressed);
+        driver.key(KeyCode::KeyA, a, Some("A".into()), ElementState::Pressed);
+        let keyboard = harness.pump();
+        assert_eq!(keyboard.len(), 4, "{keyboard:?}");
+        assert!(matches!(
+            &keyboard[1],
+            WindowEvent::ModifiersChanged(modifiers) if modifiers.state() == ModifiersState::SHIFT
+        ));
+        match (&keyboard[2], &keyboard[3]) {
+            (
+                WindowEvent::KeyboardInput { event: first, .. },
+                WindowEvent::KeyboardInput { event: second, .. },
+            ) => {
+                assert_eq!(first.physical_key, PhysicalKey::Code(KeyCode::KeyA));
+                assert_eq!(first.text.as_deref(), Some("A"));
+                assert!(!first.repeat);
+                assert!(second.repeat);
+            },
+            other => panic!("unexpected events: {other:?}"),
+        }
+    }
+
+    fn lock_keys_and_focus() {
+        // Toggling caps lock changes the modifiers once, when pressing the key.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        let driver = harness.driver.clone();
+        driver.key(KeyCode::ShiftLeft, Key::Named(NamedKey::Shift), None, ElementState::Pressed);
+        harness.pump();
+        driver.key(KeyCode::CapsLock, Key::Named(NamedKey::CapsLock), None, ElementState::Pressed);
+        driver.key(KeyCode::CapsLock, Key::Named(NamedKey::CapsLock), None, ElementState::Released);
+        let caps_lock = harness.pump();
+        assert_eq!(caps_lock.len(), 3, "{caps_lock:?}");
+        assert!(matches!(
+            &caps_lock[1],
+            WindowEvent::ModifiersChanged(modifiers)
+                if modifiers.lock_keys() == LockKeys::CAPS_LOCK
+                    && modifiers.state() == ModifiersState::SHIFT
+        ));
+
+        // Losing the focus releases the modifiers, and the input is ignored until it comes back.
+        driver.focus(None);
+        let unfocused = harness.pump();
+        assert!(matches!(
+            unfocused.as_slice(),
+            [WindowEvent::ModifiersChanged(modifiers), WindowEvent::Focused(false)]
+                if modifiers.state().is_empty()
+        ));
+        driver.key(
+            KeyCode::KeyB,
+            Key::Character("b".into()),
+            Some("b".into()),
+            ElementState::Pressed,
+        );
+        assert!(harness.pump().is_empty());
+
+        // The lock keys are reported again when the focus comes back.
+        driver.focus(Some(window_id));
+        let focused = harness.pump();
+        assert!(matches!(
+            focused.as_slice(),
+            [WindowEvent::Focused(true), WindowEvent::ModifiersChanged(modifiers)]
+                if modifiers.lock_keys().caps_lock() && modifiers.state().is_empty()
+        ));
+    }
+
+    fn keyboard_layout() {
+        // Keys are mapped with the layout set by the driver, dead keys as standalone characters.
+        let mut harness = Harness::new();
+        harness.driver.set_keyboard_layout([
+            (KeyCode::KeyA, Key::Character("q".into())),
+            (KeyCode::KeyQ, Key::Character("a".into())),
+            (KeyCode::BracketLeft, Key::Dead(Some('^'))),
+        ]);
+        assert!(harness.pump().is_empty());
+        assert_eq!(harness.app.layout_keys, vec![
+            Key::Character("a".into()),
+            Key::Character("^".into()),
+            Key::Named(NamedKey::Enter),
+        ]);
+        assert_eq!(harness.app.layout_codes, vec![
+            Some(KeyCode::KeyA),
+            Some(KeyCode::BracketLeft),
+            Some(KeyCode::Tab),
+            None
+        ]);
+    }
+
+    fn pointer() {
+        // The pointer enters the window before moving and clicking in it.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        harness.driver.move_pointer(Some((window_id, PhysicalPosition::new(10.0, 20.0))));
+        harness.driver.mouse_input(ElementState::Pressed, MouseButton::Left);
+        let pointer = harness.pump();
+        assert!(matches!(
+            pointer.as_slice(),
+            [
+                WindowEvent::CursorEntered { .. },
+                WindowEvent::CursorMoved { position, .. },
+                WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    button: MouseButton::Left,
+                    ..
+                },
+            ] if *position == PhysicalPosition::new(10.0, 20.0)
+        ));
+    }
+
+    fn motion_coalescing() {
+        // A flood of motion is coalesced into its last position by default.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        harness.driver.move_pointer(Some((window_id, PhysicalPosition::new(0.0, 0.0))));
+        harness.pump();
+        for x in 0..1000 {
+            harness.driver.move_pointer(Some((window_id, PhysicalPosition::new(x as f64, 20.0))));
+        }
+        let coalesced = harness.pump();
+        assert!(matches!(
+            coalesced.as_slice(),
+            [WindowEvent::CursorMoved { position, .. }]
+                if *position == PhysicalPosition::new(999.0, 20.0)
+        ));
+
+        // Every position is reported when the motion isn't coalesced.
+        harness.app.motion_event_policy = Some(MotionEventPolicy::Unfiltered);
+        harness.pump();
+        for x in 0..1000 {
+            harness.driver.move_pointer(Some((window_id, PhysicalPosition::new(x as f64, 30.0))));
+        }
+        let unfiltered = harness.pump();
+        assert_eq!(unfiltered.len(), 1000);
+    }
+
+    fn click_count() {
+        // Quick presses of a button at the same place are counted as consecutive clicks, the count
+        // starts over when they're too far apart in time or space, or with another button.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        let driver = harness.driver.clone();
+        driver.move_pointer(Some((window_id, PhysicalPosition::new(999.0, 30.0))));
+        for _ in 0..3 {
+            driver.mouse_input(ElementState::Pressed, MouseButton::Left);
+            driver.mouse_input(ElementState::Released, MouseButton::Left);
+        }
+        driver.advance_time(Duration::from_millis(600));
+        driver.mouse_input(ElementState::Pressed, MouseButton::Left);
+        driver.mouse_input(ElementState::Released, MouseButton::Left);
+        driver.move_pointer(Some((window_id, PhysicalPosition::new(989.0, 30.0))));
+        driver.move_pointer(Some((window_id, PhysicalPosition::new(999.0, 30.0))));
+        driver.mouse_input(ElementState::Pressed, MouseButton::Left);
+        driver.mouse_input(ElementState::Released, MouseButton::Left);
+        driver.mouse_input(ElementState::Pressed, MouseButton::Right);
+        driver.mouse_input(ElementState::Released, MouseButton::Right);
+        let clicks: Vec<_> = harness
+            .pump()
+            .into_iter()
+            .filter_map(|event| match event {
+                WindowEvent::MouseInput { state, click_count, .. } => Some((state, click_count)),
+                _ => None,
+            })
+            .collect();
+        let (pressed, released) = (ElementState::Pressed, ElementState::Released);
+        assert_eq!(clicks, vec![
+            (pressed, 1),
+            (released, 1),
+            (pressed, 2),
+            (released, 2),
+            (pressed, 3),
+            (released, 3),
+            (pressed, 1),
+            (released, 1),
+            (pressed, 1),
+            (released, 1),
+            (pressed, 1),
+            (released, 1),
+        ]);
+    }
+
+    fn ime() {
+        // The IME cursor area and purpose are kept while IME is disabled, and are set when it's
+        // enabled again.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        let window = harness.window();
+        assert_eq!(window.ime_capabilities(), Some(ImeCapabilities::all()));
+        let area = (PhysicalPosition::new(5, 6), PhysicalSize::new(30, 10));
+        let ime_state = |allowed, cursor_area, purpose| {
+            Some(HeadlessImeState { allowed, cursor_area, purpose })
+        };
+        for (request, state) in [
+            (ImeRequest::Enable, ime_state(true, None, ImePurpose::Normal)),
+            (
+                ImeRequest::CursorArea(area.0.into(), area.1.into()),
+                ime_state(true, Some(area), ImePurpose::Normal),
+            ),
+            (
+                ImeRequest::Purpose(ImePurpose::Email),
+                ime_state(true, Some(area), ImePurpose::Email),
+            ),
+            (ImeRequest::Disable, ime_state(false, Some(area), ImePurpose::Email)),
+            (ImeRequest::Enable, ime_state(true, Some(area), ImePurpose::Email)),
+        ] {
+            window.request_ime_update(request).unwrap();
+            assert_eq!(harness.driver.ime_state(window_id), state, "after {request:?}");
+        }
+        let ime = harness.pump();
+        assert_eq!(ime, vec![
+            WindowEvent::Ime(Ime::Enabled),
+            WindowEvent::Ime(Ime::Disabled),
+            WindowEvent::Ime(Ime::Enabled)
+        ]);
+    }
+
+    fn resize_and_scale_factor() {
+        // Resizing is followed by a redraw.
+        let mut harness = Harness::new();
+        let window_id = harness.window_id;
+        harness.driver.resize(window_id, PhysicalSize::new(100, 80));
+        let resized = harness.pump();
+        assert_eq!(resized, vec![
+            WindowEvent::Resized(PhysicalSize::new(100, 80)),
+            WindowEvent::RedrawRequested
+        ]);
+
+        // The logical size is kept when the scale factor changes.
+        harness.driver.set_scale_factor(window_id, 2.0);
+        let scaled = harness.pump();
+        assert!(matches!(
+            scaled[0],
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } if scale_factor == 2.0
+        ));
+        assert_eq!(scaled[1..], [
+            WindowEvent::Resized(PhysicalSize::new(200, 160)),
+            WindowEvent::RedrawRequested
+        ]);
+        assert_eq!(harness.window().inner_size(), PhysicalSize::new(200, 160));
+    }
+
+    fn redraw_damage() {
+        // The damage of the redraw requests is merged until the redraw. The whole window is
+        // damaged when it's requested, and by redraws that weren't requested.
+        let rect =
+            |x, y, size| Rect::new(PhysicalPosition::new(x, y), PhysicalSize::new(size, size));
+        let mut harness = Harness::new();
+        harness.app.redraw_damage.clear();
+        harness.window().request_redraw_with_damage(&[rect(0, 0, 10), rect(5, 5, 10)]);
+        harness.window().request_redraw_with_damage(&[rect(50, 50, 10)]);
+        assert_eq!(harness.pump(), vec![WindowEvent::RedrawRequested]);
+        harness.window().request_redraw_with_damage(&[rect(0, 0, 10)]);
+        harness.window().request_redraw();
+        assert_eq!(harness.pump(), vec![WindowEvent::RedrawRequested]);
+        harness.window().request_redraw_with_damage(&[rect(0, 0, 10)]);
+        harness.pump();
+        harness.driver.resize(harness.window_id, PhysicalSize::new(100, 80));
+        harness.pump();
+        assert_eq!(harness.app.redraw_damage, vec![
+            Some(vec![rect(0, 0, 15), rect(50, 50, 10)]),
+            None,
+            Some(vec![rect(0, 0, 10)]),
+            None
+        ]);
+    }
+
+    fn fullscreen_descriptor() {
+        // An exclusive fullscreen mode is persisted as its descriptor and resolved again by the
+        // event loop. The window attributes can be read back, but without the mode.
+        let mut harness = Harness::new();
+        let video_mode = harness.window().current_monitor().unwrap().video_modes().next();
+        let fullscreen = Fullscreen::Exclusive(video_mode.unwrap());
+        let descriptor = FullscreenDescriptor::from(&fullscreen);
+        #[cfg(feature = "serde")]
+        let descriptor = {
+            use winit::window::WindowAttributes;
+
+            let attributes = Window::default_attributes().with_fullscreen(Some(fullscreen.clone()));
+            let json = serde_json::to_string(&attributes).unwrap();
+            let deserialized: WindowAttributes = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.fullscreen, None);
+
+            let json = serde_json::to_string(&descriptor).unwrap();
+            serde_json::from_str(&json).unwrap()
+        };
+        harness.app.fullscreen_descriptor = Some(descriptor);
+        harness.pump();
+        assert_eq!(harness.app.resolved_fullscreen, Some(fullscreen));
+    }
+
+    fn session_events() {
+        // Session events are delivered in order and leave the windows alone.
+        let mut harness = Harness::new();
+        let session = [
+            SessionEvent::SessionLocked,
+            SessionEvent::SessionUnlocked,
+            SessionEvent::PowerSaverChanged(true),
+        ];
+        for event in session {
+            harness.driver.session_event(event);
+        }
+        assert!(harness.pump().is_empty());
+        assert_eq!(harness.app.session_events, session);
+    }
+
+    fn timers() {
+        // Timers fire when the clock of the event loop is advanced, not when real time passes.
+        let mut harness = Harness::new();
+        harness.app.timeout = Some(Duration::from_secs(3600));
+        harness.pump();
+        assert!(!harness.app.resume_time_reached);
+        harness.driver.advance_time(Duration::from_secs(3600));
+        harness.pump();
+        assert!(harness.app.resume_time_reached);
+    }
+
+    fn user_events() {
+        // The events sent from other threads with a proxy created by the running event loop are
+        // delivered in order.
+        let mut harness = Harness::new();
+        let proxy = harness.app.proxy.clone().unwrap();
+        thread::spawn(move || {
+            for event in 0..100u32 {
+                proxy.send_event(event).unwrap();
+            }
+        })
+        .join()
+        .unwrap();
+        assert!(harness.pump().is_empty());
+        assert_eq!(harness.app.user_events, (0..100).collect::<Vec<_>>());
+    }
+
+    fn close() {
+        // Closing the window exits the application.
+        let mut harness = Harness::new();
+        harness.driver.close(harness.window_id);
+        let closed = harness.pump_with_status();
+        assert_eq!(closed, (PumpStatus::Exit(0), vec![WindowEvent::CloseRequested]));
+        assert!(harness.driver.windows().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    fn record_and_replay() {
+        use std::io::Write;
+
+        use winit::record::{Record, Recorder, Replayer};
+
+        // The events received by an application are recorded as JSON lines.
+        let mut event_loop = EventLoop::builder().with_headless().build().unwrap();
+        let driver = event_loop.headless_driver().unwrap();
+        let mut recording = Vec::new();
+        let mut recorder = Recorder::to_writer(App::default(), &mut recording, |writer, record| {
+            serde_json::to_writer(&mut *writer, record)?;
+            writer.write_all(b"\n")
+        });
+        event_loop.pump_app_events(Some(Duration::ZERO), &mut recorder);
+        let recorded_window = recorder.app().window.as_ref().unwrap().id();
+        let a = Key::Character("a".into());
+        driver.key(KeyCode::KeyA, a.clone(), Some("a".into()), ElementState::Pressed);
+        driver.key(KeyCode::KeyA, a, None, ElementState::Released);
+        driver.move_pointer(Some((recorded_window, PhysicalPosition::new(10.0, 20.0))));
+        driver.mouse_input(ElementState::Pressed, MouseButton::Left);
+        driver.resize(recorded_window, PhysicalSize::new(100, 80));
+        driver.session_event(SessionEvent::SessionLocked);
+        event_loop.pump_app_events(Some(Duration::ZERO), &mut recorder);
+        assert!(recorder.error().is_none());
+        let (recorded, _) = recorder.into_inner();
+        let records: Vec<Record> = String::from_utf8(recording)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        // They're replayed to another application, in the first iteration, to a window that
+        // doesn't receive any live event, as it's hidden.
+        let attributes = Window::default_attributes().with_visible(false);
+        #[allow(deprecated)]
+        let window = event_loop.create_window(attributes).unwrap();
+        let live_window = window.id();
+        let app = App { window: Some(window), ..App::default() };
+        let mut replayer = Replayer::new(app, records).with_speed(f64::INFINITY);
+        replayer.match_window(recorded_window, live_window);
+        event_loop.pump_app_events(Some(Duration::ZERO), &mut replayer);
+        assert!(replayer.is_finished());
+        let replayed = replayer.into_inner();
+        // The events are compared serialized, as the device IDs don't survive the recording.
+        let serialized = |events: &[WindowEvent]| -> Vec<_> {
+            events
+                .iter()
+                .filter(|event| **event != WindowEvent::RedrawRequested)
+                .map(|event| serde_json::to_value(event).unwrap())
+                .collect()
+        };
+        let recorded_events = serialized(&recorded.events);
+        assert_eq!(recorded_events.len(), 7, "{:?}", recorded.events);
+        assert_eq!(serialized(&replayed.events), recorded_events);
+        assert_eq!(replayed.session_events, recorded.session_events);
+    }
+
+    /// Runs an application, keeping the events it receives and its last control flow.
+    #[derive(Default)]
+    struct Observed<A> {
+        app: A,
+        events: Vec<WindowEvent>,
+        control_flow: Option<ControlFlow>,
+    }
+
+    impl<A: ApplicationHandler> ApplicationHandler for Observed<A> {
+        fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+            self.app.new_events(event_loop, cause);
+        }
+
+        fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+            self.app.can_create_surfaces(event_loop);
+        }
+
+        fn window_event(
+            &mut self,
+            event_loop: &ActiveEventLoop,
+            window_id: WindowId,
+            event: WindowEvent,
+        ) {
+            self.events.push(event.clone());
+            self.app.window_event(event_loop, window_id, event);
+        }
+
+        fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+            self.app.about_to_wait(event_loop);
+            self.control_flow = Some(event_loop.control_flow());
+        }
+    }
+
+    fn example_control_flow() {
+        // The `control_flow` example switches the control flow with the keyboard, redraws
+        // continuously when asked to, and exits on escape.
+        let mut event_loop = EventLoop::builder().with_headless().build().unwrap();
+        let driver = event_loop.headless_driver().unwrap();
+        let mut app = Observed::<ControlFlowDemo>::default();
+        let mut pump = |app: &mut Observed<ControlFlowDemo>| {
+            event_loop.pump_app_events(Some(Duration::ZERO), app)
+        };
+        let press = |key: Key| {
+            let text = match &key {
+                Key::Character(text) => Some(text.clone()),
+                _ => None,
+            };
+            for state in [ElementState::Pressed, ElementState::Released] {
+                driver.key(KeyCode::Fn, key.clone(), text.clone(), state);
+            }
+        };
+
+        pump(&mut app);
+        assert_eq!(driver.windows().len(), 1);
+        assert_eq!(app.control_flow, Some(ControlFlow::Wait));
+
+        press(Key::Character("2".into()));
+        pump(&mut app);
+        assert!(matches!(app.control_flow, Some(ControlFlow::WaitUntil(_))));
+        press(Key::Character("3".into()));
+        pump(&mut app);
+        assert_eq!(app.control_flow, Some(ControlFlow::Poll));
+        press(Key::Character("1".into()));
+        pump(&mut app);
+        assert_eq!(app.control_flow, Some(ControlFlow::Wait));
+
+        press(Key::Character("r".into()));
+        pump(&mut app);
+        app.events.clear();
+        pump(&mut app);
+        pump(&mut app);
+        let redraws =
+            app.events.iter().filter(|event| **event == WindowEvent::RedrawRequested).count();
+        assert_eq!(redraws, 2);
+
+        press(Key::Named(NamedKey::Escape));
+        assert_eq!(pump(&mut app), PumpStatus::Exit(0));
+    }
+}
+
+#[cfg(headless_platform)]
+fn main() {
+    driven::main();
+}
+
+#[cfg(not(headless_platform))]
+fn main() {
+    println!("the headless backend isn't enabled, enable the `headless` feature to test it");
+}
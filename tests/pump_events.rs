@@ -0,0 +1,78 @@
+//! Checks that pumping with a zero timeout dispatches pending events without blocking.
+//!
+//! It needs a display, so it's only run when the `WINIT_TEST_PUMP_EVENTS` environment variable is
+//! set.
+
+#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+fn main() {
+    use std::time::{Duration, Instant};
+
+    use winit::application::ApplicationHandler;
+    use winit::event::WindowEvent;
+    use winit::event_loop::{ActiveEventLoop, EventLoop};
+    use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+    use winit::window::{Window, WindowId};
+
+    /// How long a pump with a zero timeout may take, generous for slow machines.
+    const MAX_POLL: Duration = Duration::from_millis(100);
+
+    #[derive(Default)]
+    struct App {
+        window: Option<Window>,
+        exit: bool,
+    }
+
+    impl ApplicationHandler for App {
+        fn can_create_surfaces(&mut self, event_loop: &ActiveEventLoop) {
+            let window = event_loop.create_window(Window::default_attributes()).unwrap();
+            window.request_redraw();
+            self.window = Some(window);
+        }
+
+        fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+
+        fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+            if self.exit {
+                event_loop.exit();
+            }
+        }
+    }
+
+    if std::env::var_os("WINIT_TEST_PUMP_EVENTS").is_none() {
+        println!("skipping, set WINIT_TEST_PUMP_EVENTS to run");
+        return;
+    }
+
+    let mut event_loop = EventLoop::new().unwrap();
+    let mut app = App::default();
+
+    // The requested redraw is dispatched by one of the first pumps.
+    let mut redraws = 0;
+    for _ in 0..100 {
+        let report = event_loop.pump_app_events_until(Some(Instant::now()), &mut app);
+        assert_eq!(report.status, PumpStatus::Continue);
+        assert!(report.elapsed < MAX_POLL, "polling took {:?}", report.elapsed);
+        assert!(report.redraws <= report.window_events);
+        redraws += report.redraws;
+        if redraws > 0 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(redraws > 0, "no redraw was dispatched");
+
+    // Nothing is pending anymore, so polling returns right away.
+    let start = Instant::now();
+    let status = event_loop.pump_app_events(Some(Duration::ZERO), &mut app);
+    assert_eq!(status, PumpStatus::Continue);
+    assert!(start.elapsed() < MAX_POLL, "polling took {:?}", start.elapsed());
+
+    app.exit = true;
+    let report = event_loop.pump_app_events_until(Some(Instant::now()), &mut app);
+    assert_eq!(report.status, PumpStatus::Exit(0));
+}
+
+#[cfg(not(any(windows_platform, macos_platform, x11_platform, wayland_platform)))]
+fn main() {
+    println!("pump_events isn't tested on this platform");
+}
@@ -1,17 +1,117 @@
 #![cfg(feature = "serde")]
 
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
-use winit::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
-use winit::window::CursorIcon;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, SessionEvent, TouchPhase};
+use winit::keyboard::{Key, KeyCode, KeyLocation, LockKeys, ModifiersState, NamedKey, PhysicalKey};
+use winit::window::{
+    AspectRatio, CursorGrabMode, CursorIcon, Fullscreen, FullscreenDescriptor, Rect,
+    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
+};
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+}
+
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<WindowAttributes>();
+
+    round_trip(WindowLevel::AlwaysOnBottom);
+    round_trip(WindowLevel::Normal);
+    round_trip(WindowLevel::AlwaysOnTop);
+    round_trip(WindowButtons::empty());
+    round_trip(WindowButtons::CLOSE | WindowButtons::MAXIMIZE);
+    round_trip(WindowButtons::all());
+    round_trip(Theme::Light);
+    round_trip(Theme::Dark);
+    round_trip(CursorGrabMode::None);
+    round_trip(CursorGrabMode::Confined);
+    round_trip(CursorGrabMode::Locked);
+    round_trip(ResizeDirection::NorthEast);
+    round_trip(ResizeDirection::West);
+    round_trip(Rect::new(PhysicalPosition::new(10u32, 20), PhysicalSize::new(30, 40)));
+    round_trip(UserAttentionType::Critical);
+    round_trip(UserAttentionType::Informational);
+    round_trip(Fullscreen::Borderless(None));
+    round_trip(FullscreenDescriptor::Borderless { monitor: Some("DP-1".into()) });
+    round_trip(FullscreenDescriptor::Exclusive {
+        monitor: None,
+        size: PhysicalSize::new(1920, 1080),
+        bit_depth: 32,
+        refresh_rate_millihertz: 60_000,
+    });
+    round_trip(AspectRatio::new(16, 9));
+    round_trip(WindowId::from_raw(42));
+    assert_eq!(serde_json::to_string(&WindowId::from_raw(42)).unwrap(), "42");
+}
+
+#[test]
+fn window_attributes_serde() {
+    let attributes = WindowAttributes::default()
+        .with_title("serde")
+        .with_inner_size(LogicalSize::new(640.0, 480.0))
+        .with_position(PhysicalPosition::new(10, 20))
+        .with_resizable(false)
+        .with_enabled_buttons(WindowButtons::CLOSE)
+        .with_maximized(true)
+        .with_theme(Some(Theme::Dark))
+        .with_window_level(WindowLevel::AlwaysOnTop)
+        .with_aspect_ratio(Some(AspectRatio::new(4, 3)))
+        .with_fullscreen(Some(Fullscreen::Borderless(None)));
+
+    let json = serde_json::to_string(&attributes).unwrap();
+    let deserialized: WindowAttributes = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.title, attributes.title);
+    assert_eq!(deserialized.inner_size, attributes.inner_size);
+    assert_eq!(deserialized.position, attributes.position);
+    assert_eq!(deserialized.resizable, attributes.resizable);
+    assert_eq!(deserialized.enabled_buttons, attributes.enabled_buttons);
+    assert_eq!(deserialized.maximized, attributes.maximized);
+    assert_eq!(deserialized.preferred_theme, attributes.preferred_theme);
+    assert_eq!(deserialized.window_level, attributes.window_level);
+    assert_eq!(deserialized.aspect_ratio, attributes.aspect_ratio);
+    assert_eq!(deserialized.fullscreen, attributes.fullscreen);
+}
+
+#[test]
+fn window_attributes_serde_compatibility() {
+    // Missing fields take their default value, and unknown fields are ignored.
+    let attributes: WindowAttributes =
+        serde_json::from_str(r#"{ "title": "old", "from_the_future": 1 }"#).unwrap();
+    let default = WindowAttributes::default();
+    assert_eq!(attributes.title, "old");
+    assert_eq!(attributes.resizable, default.resizable);
+    assert_eq!(attributes.enabled_buttons, default.enabled_buttons);
+    assert_eq!(attributes.visible, default.visible);
+
+    let buttons: WindowButtons =
+        serde_json::from_str(r#"{ "close": false, "from_the_future": true }"#).unwrap();
+    assert_eq!(buttons, WindowButtons::MINIMIZE | WindowButtons::MAXIMIZE);
+
+    assert!(serde_json::from_str::<AspectRatio>(r#"{ "width": 0, "height": 1 }"#).is_err());
+
+    // A specific monitor can only be found again through a `FullscreenDescriptor`.
+    let fullscreen = r#"{ "Borderless": { "monitor": "DP-1" } }"#;
+    assert!(serde_json::from_str::<Fullscreen>(fullscreen).is_err());
+    assert_eq!(
+        serde_json::from_str::<FullscreenDescriptor>(fullscreen).unwrap(),
+        FullscreenDescriptor::Borderless { monitor: Some("DP-1".into()) }
+    );
+    let attributes: WindowAttributes =
+        serde_json::from_str(&format!(r#"{{ "title": "monitor", "fullscreen": {fullscreen} }}"#))
+            .unwrap();
+    assert_eq!(attributes.title, "monitor");
+    assert_eq!(attributes.fullscreen, None);
 }
 
 #[test]
@@ -26,6 +126,10 @@ fn events_serde() {
     needs_serde::<PhysicalKey>();
     needs_serde::<KeyLocation>();
     needs_serde::<ModifiersState>();
+    needs_serde::<LockKeys>();
+
+    round_trip(SessionEvent::Suspending);
+    round_trip(SessionEvent::PowerSaverChanged(true));
 }
 
 #[test]
@@ -36,3 +140,85 @@ fn dpi_serde() {
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
 }
+
+#[test]
+fn record_serde() {
+    use std::time::Duration;
+
+    use winit::event::{DeviceEvent, DeviceId, Ime, StartCause, WindowEvent};
+    use winit::record::{Record, RecordedEvent};
+    use winit::window::WindowId;
+
+    // Some events can only be built by the backends, so they're read from their serialized form.
+    let key_event: WindowEvent = serde_json::from_str(
+        r#"{ "KeyboardInput": {
+            "device_id": null,
+            "event": {
+                "physical_key": { "Code": "KeyA" },
+                "logical_key": { "Character": "a" },
+                "text": "a",
+                "location": "Standard",
+                "state": "Pressed",
+                "repeat": false
+            },
+            "is_synthetic": false
+        } }"#,
+    )
+    .unwrap();
+    let scale_factor_changed: WindowEvent = serde_json::from_str(
+        r#"{ "ScaleFactorChanged": { "scale_factor": 2.0, "inner_size_writer": null } }"#,
+    )
+    .unwrap();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    let window_id = WindowId::from(7);
+    let mut records = vec![
+        Record { time: Duration::ZERO, event: RecordedEvent::NewEvents(StartCause::Init) },
+        Record { time: Duration::ZERO, event: RecordedEvent::CanCreateSurfaces },
+    ];
+    for i in 0..300u32 {
+        let x = f64::from(i) / 3.0;
+        let event = match i % 6 {
+            0 => WindowEvent::CursorMoved { device_id, position: (x, -x).into() },
+            1 => WindowEvent::MouseInput {
+                device_id,
+                state: if i % 4 == 1 { ElementState::Pressed } else { ElementState::Released },
+                button: MouseButton::Other(i as u16),
+                click_count: i % 3 + 1,
+            },
+            2 => WindowEvent::MouseWheel {
+                device_id,
+                delta: MouseScrollDelta::PixelDelta((x, 0.5).into()),
+                phase: TouchPhase::Moved,
+                momentum_phase: None,
+            },
+            3 => WindowEvent::Ime(Ime::Preedit(format!("{i}"), Some((0, 1)))),
+            4 => key_event.clone(),
+            _ => scale_factor_changed.clone(),
+        };
+        let time = Duration::from_micros(u64::from(i) * 16_667);
+        records.push(Record { time, event: RecordedEvent::WindowEvent { window_id, event } });
+        records.push(Record {
+            time,
+            event: RecordedEvent::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseMotion { delta: (x, 1.0).into() },
+            },
+        });
+    }
+    records.push(Record { time: Duration::from_secs(5), event: RecordedEvent::Exiting });
+
+    let lines: Vec<String> =
+        records.iter().map(|record| serde_json::to_string(record).unwrap()).collect();
+    let read: Vec<Record> = lines.iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(read, records);
+
+    assert!(serde_json::from_str::<Record>(r#"{ "time": 1 }"#).is_err());
+
+    // Recorded before the click count was reported.
+    let mouse_input: WindowEvent = serde_json::from_str(
+        r#"{ "MouseInput": { "device_id": null, "state": "Pressed", "button": "Left" } }"#,
+    )
+    .unwrap();
+    assert!(matches!(mouse_input, WindowEvent::MouseInput { click_count: 1, .. }));
+}
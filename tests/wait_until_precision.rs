@@ -0,0 +1,55 @@
+//! Checks that `ControlFlow::WaitUntil` resumes in time on an idle system.
+//!
+//! It needs a display and can be flaky on busy machines, so it's only run when the
+//! `WINIT_TEST_WAIT_UNTIL` environment variable is set.
+
+use std::time::{Duration, Instant};
+
+use winit::application::ApplicationHandler;
+use winit::event::{StartCause, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::window::WindowId;
+
+const INTERVAL: Duration = Duration::from_millis(5);
+const TOLERANCE: Duration = Duration::from_millis(2);
+const ITERATIONS: u32 = 50;
+
+#[derive(Default)]
+struct App {
+    resumed: u32,
+    latest: Duration,
+}
+
+impl ApplicationHandler for App {
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        if let StartCause::ResumeTimeReached { requested_resume, .. } = cause {
+            self.latest = self.latest.max(Instant::now() - requested_resume);
+            self.resumed += 1;
+            if self.resumed == ITERATIONS {
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        event_loop.set_control_flow(ControlFlow::wait_duration(INTERVAL));
+    }
+
+    fn can_create_surfaces(&mut self, _event_loop: &ActiveEventLoop) {}
+
+    fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+}
+
+fn main() {
+    if std::env::var_os("WINIT_TEST_WAIT_UNTIL").is_none() {
+        println!("skipping, set WINIT_TEST_WAIT_UNTIL to run");
+        return;
+    }
+
+    let event_loop = EventLoop::new().unwrap();
+    let mut app = App::default();
+    event_loop.run_app(&mut app).unwrap();
+
+    assert!(app.latest <= TOLERANCE, "resumed up to {:?} late", app.latest);
+    println!("resumed up to {:?} late", app.latest);
+}